@@ -8,8 +8,9 @@ use crate::avm1::{
     fscommand, globals, scope, skip_actions, start_drag, Avm1, AvmString, ScriptObject, Value,
 };
 use crate::backend::navigator::{NavigationMethod, RequestOptions};
-use crate::context::UpdateContext;
+use crate::context::{UpdateContext, ACTIONS_PER_TIMEOUT_CHECK};
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::security::{self, LoadKind};
 use crate::tag_utils::SwfSlice;
 use enumset::EnumSet;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
@@ -397,8 +398,17 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         code: SwfSlice,
     ) -> Result<ReturnType<'gc>, Error<'gc>> {
         let mut read = Reader::new(code.as_ref(), self.swf_version());
+        let mut actions_since_timeout_check: u16 = 0;
 
         loop {
+            actions_since_timeout_check += 1;
+            if actions_since_timeout_check >= ACTIONS_PER_TIMEOUT_CHECK {
+                actions_since_timeout_check = 0;
+                if context.is_execution_timed_out() {
+                    break Err(Error::ExecutionTimeout);
+                }
+            }
+
             let result = self.do_action(&code, context, &mut read);
             match result {
                 Ok(FrameControl::Return(return_type)) => break Ok(return_type),
@@ -1238,13 +1248,20 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
             let url = url.to_string();
             match target[6..].parse::<u32>() {
                 Ok(level_id) => {
-                    let fetch = context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = security::fetch(
+                        self,
+                        context,
+                        &url,
+                        RequestOptions::get(),
+                        LoadKind::Movie,
+                    );
                     let level = self.resolve_level(level_id, context);
 
                     let process = context.load_manager.load_movie_into_clip(
                         context.player.clone().unwrap(),
                         level,
                         fetch,
+                        security::loaded_movie_url(self, &url),
                         None,
                     );
                     context.navigator.spawn_future(process);
@@ -1260,7 +1277,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         }
 
         if let Some(fscommand) = fscommand::parse(url) {
-            fscommand::handle(fscommand, self, context)?;
+            fscommand::handle(fscommand, target, self, context)?;
         } else {
             context
                 .navigator
@@ -1284,7 +1301,8 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         let url = url_val.coerce_to_string(self, context)?;
 
         if let Some(fscommand) = fscommand::parse(&url) {
-            fscommand::handle(fscommand, self, context)?;
+            let args = target.coerce_to_string(self, context)?;
+            fscommand::handle(fscommand, &args, self, context)?;
             return Ok(FrameControl::Continue);
         }
 
@@ -1312,7 +1330,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
                     Cow::Borrowed(&url),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
-                let fetch = context.navigator.fetch(&url, opts);
+                let fetch = security::fetch(self, context, &url, opts, LoadKind::Data);
                 let process = context.load_manager.load_form_into_object(
                     context.player.clone().unwrap(),
                     target_obj,
@@ -1330,11 +1348,12 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
                     Cow::Borrowed(&url),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
-                let fetch = context.navigator.fetch(&url, opts);
+                let fetch = security::fetch(self, context, &url, opts, LoadKind::Movie);
                 let process = context.load_manager.load_movie_into_clip(
                     context.player.clone().unwrap(),
                     clip_target,
                     fetch,
+                    security::loaded_movie_url(self, &url),
                     None,
                 );
                 context.navigator.spawn_future(process);
@@ -17,6 +17,9 @@ pub enum Error<'gc> {
 
     #[error("A script has thrown a custom error.")]
     ThrownValue(Value<'gc>),
+
+    #[error("A script has executed for longer than the maximum execution duration, and all scripts in this movie have been aborted.")]
+    ExecutionTimeout,
 }
 
 impl Error<'_> {
@@ -27,6 +30,7 @@ impl Error<'_> {
             Error::SpecialRecursionLimit => true,
             Error::InvalidSwf(_) => true,
             Error::ThrownValue(_) => false,
+            Error::ExecutionTimeout => true,
         }
     }
 }
@@ -13,14 +13,16 @@ pub fn parse(url: &str) -> Option<&str> {
     }
 }
 
-/// TODO: FSCommand URL handling
+/// Deliver an FSCommand to the host of the movie.
 pub fn handle<'gc>(
-    fscommand: &str,
+    command: &str,
+    args: &str,
     _activation: &mut Activation,
-    _ac: &mut UpdateContext,
+    context: &mut UpdateContext,
 ) -> Result<(), Error<'gc>> {
-    log::warn!("Unhandled FSCommand: {}", fscommand);
+    if !context.external_interface.invoke_fs_command(command, args) {
+        log::warn!("Unhandled FSCommand: {}", command);
+    }
 
-    //This should be an error.
     Ok(())
 }
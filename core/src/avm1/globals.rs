@@ -10,16 +10,29 @@ use rand::Rng;
 use std::f64;
 
 mod array;
+mod bevel_filter;
+mod bitmap_data;
+pub(crate) mod bitmap_filter;
+mod blur_filter;
 pub(crate) mod boolean;
 pub(crate) mod button;
 mod color;
+mod color_matrix_filter;
 mod color_transform;
 pub(crate) mod context_menu;
 pub(crate) mod context_menu_item;
+mod convolution_filter;
+mod date;
 pub(crate) mod display_object;
+mod drop_shadow_filter;
 pub(crate) mod error;
+mod external_interface;
 mod function;
+mod glow_filter;
+mod gradient_bevel_filter;
+mod gradient_glow_filter;
 mod key;
+pub(crate) mod load_vars;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
@@ -29,10 +42,12 @@ pub(crate) mod number;
 mod object;
 mod point;
 mod rectangle;
+mod selection;
 pub(crate) mod shared_object;
 mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -194,6 +209,8 @@ pub struct SystemPrototypes<'gc> {
     pub function: Object<'gc>,
     pub movie_clip: Object<'gc>,
     pub sound: Object<'gc>,
+    pub date: Object<'gc>,
+    pub bitmap_data: Object<'gc>,
     pub text_field: Object<'gc>,
     pub text_format: Object<'gc>,
     pub array: Object<'gc>,
@@ -209,6 +226,15 @@ pub struct SystemPrototypes<'gc> {
     pub color_transform: Object<'gc>,
     pub context_menu: Object<'gc>,
     pub context_menu_item: Object<'gc>,
+    pub bitmap_filter: Object<'gc>,
+    pub blur_filter: Object<'gc>,
+    pub drop_shadow_filter: Object<'gc>,
+    pub glow_filter: Object<'gc>,
+    pub bevel_filter: Object<'gc>,
+    pub gradient_glow_filter: Object<'gc>,
+    pub gradient_bevel_filter: Object<'gc>,
+    pub color_matrix_filter: Object<'gc>,
+    pub convolution_filter: Object<'gc>,
 }
 
 /// Initialize default global scope and builtins for an AVM1 instance.
@@ -230,10 +256,19 @@ pub fn create_globals<'gc>(
 
     let sound_proto: Object<'gc> = sound::create_proto(gc_context, object_proto, function_proto);
 
+    let date_proto: Object<'gc> = date::create_proto(gc_context, object_proto, function_proto);
+
+    let bitmap_data_proto: Object<'gc> =
+        bitmap_data::create_proto(gc_context, object_proto, function_proto);
+
     let text_field_proto: Object<'gc> =
         text_field::create_proto(gc_context, object_proto, function_proto);
     let text_format_proto: Object<'gc> =
         text_format::create_proto(gc_context, object_proto, function_proto);
+    let style_sheet_proto: Object<'gc> =
+        style_sheet::create_proto(gc_context, object_proto, function_proto);
+    let load_vars_proto: Object<'gc> =
+        load_vars::create_proto(gc_context, object_proto, function_proto);
 
     let array_proto: Object<'gc> = array::create_proto(gc_context, object_proto, function_proto);
 
@@ -256,6 +291,24 @@ pub fn create_globals<'gc>(
         rectangle::create_proto(gc_context, object_proto, function_proto);
     let color_transform_proto: Object<'gc> =
         color_transform::create_proto(gc_context, object_proto, function_proto);
+    let bitmap_filter_proto: Object<'gc> =
+        bitmap_filter::create_proto(gc_context, object_proto, function_proto);
+    let blur_filter_proto: Object<'gc> =
+        blur_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let drop_shadow_filter_proto: Object<'gc> =
+        drop_shadow_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let glow_filter_proto: Object<'gc> =
+        glow_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let bevel_filter_proto: Object<'gc> =
+        bevel_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let gradient_glow_filter_proto: Object<'gc> =
+        gradient_glow_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let gradient_bevel_filter_proto: Object<'gc> =
+        gradient_bevel_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let color_matrix_filter_proto: Object<'gc> =
+        color_matrix_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
+    let convolution_filter_proto: Object<'gc> =
+        convolution_filter::create_proto(gc_context, bitmap_filter_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(gc_context, object_proto, function_proto);
@@ -318,6 +371,18 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         Some(text_format_proto),
     );
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(style_sheet::constructor),
+            Some(function_proto),
+            Some(style_sheet_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
     let array = array::create_array_object(gc_context, Some(array_proto), Some(function_proto));
     let xmlnode = FunctionObject::function(
         gc_context,
@@ -333,6 +398,7 @@ pub fn create_globals<'gc>(
     );
     let string = string::create_string_object(gc_context, Some(string_proto), Some(function_proto));
     let number = number::create_number_object(gc_context, Some(number_proto), Some(function_proto));
+    let date = date::create_date_object(gc_context, Some(date_proto), Some(function_proto));
     let boolean =
         boolean::create_boolean_object(gc_context, Some(boolean_proto), Some(function_proto));
 
@@ -361,6 +427,145 @@ pub fn create_globals<'gc>(
         EnumSet::empty(),
     );
 
+    let display = ScriptObject::object(gc_context, Some(object_proto));
+    flash.define_value(gc_context, "display", display.into(), EnumSet::empty());
+    display.define_value(
+        gc_context,
+        "BitmapData",
+        bitmap_data::create_bitmap_data_object(
+            gc_context,
+            Some(bitmap_data_proto),
+            Some(function_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+
+    let external = ScriptObject::object(gc_context, Some(object_proto));
+    flash.define_value(gc_context, "external", external.into(), EnumSet::empty());
+    external.define_value(
+        gc_context,
+        "ExternalInterface",
+        external_interface::create_external_interface_object(
+            gc_context,
+            Some(object_proto),
+            Some(function_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+
+    let filters = ScriptObject::object(gc_context, Some(object_proto));
+    flash.define_value(gc_context, "filters", filters.into(), EnumSet::empty());
+    filters.define_value(
+        gc_context,
+        "BitmapFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(bitmap_filter::constructor),
+            Some(function_proto),
+            Some(bitmap_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "BlurFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(blur_filter::constructor),
+            Some(function_proto),
+            Some(blur_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "DropShadowFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(drop_shadow_filter::constructor),
+            Some(function_proto),
+            Some(drop_shadow_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "GlowFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(glow_filter::constructor),
+            Some(function_proto),
+            Some(glow_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "BevelFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(bevel_filter::constructor),
+            Some(function_proto),
+            Some(bevel_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "GradientGlowFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(gradient_glow_filter::constructor),
+            Some(function_proto),
+            Some(gradient_glow_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "GradientBevelFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(gradient_bevel_filter::constructor),
+            Some(function_proto),
+            Some(gradient_bevel_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "ColorMatrixFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(color_matrix_filter::constructor),
+            Some(function_proto),
+            Some(color_matrix_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+    filters.define_value(
+        gc_context,
+        "ConvolutionFilter",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(convolution_filter::constructor),
+            Some(function_proto),
+            Some(convolution_filter_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
+
     let listeners = SystemListeners::new(gc_context, Some(array_proto));
 
     let mut globals = ScriptObject::bare_object(gc_context);
@@ -368,6 +573,7 @@ pub fn create_globals<'gc>(
     globals.define_value(gc_context, "Array", array.into(), EnumSet::empty());
     globals.define_value(gc_context, "Button", button.into(), EnumSet::empty());
     globals.define_value(gc_context, "Color", color.into(), EnumSet::empty());
+    globals.define_value(gc_context, "Date", date.into(), EnumSet::empty());
     globals.define_value(gc_context, "Error", error.into(), EnumSet::empty());
     globals.define_value(gc_context, "Object", object.into(), EnumSet::empty());
     globals.define_value(gc_context, "Function", function.into(), EnumSet::empty());
@@ -386,6 +592,18 @@ pub fn create_globals<'gc>(
         text_format.into(),
         EnumSet::empty(),
     );
+    globals.define_value(
+        gc_context,
+        "LoadVars",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(load_vars::constructor),
+            Some(function_proto),
+            Some(load_vars_proto),
+        )
+        .into(),
+        EnumSet::empty(),
+    );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), EnumSet::empty());
     globals.define_value(gc_context, "XML", xml.into(), EnumSet::empty());
     globals.define_value(gc_context, "String", string.into(), EnumSet::empty());
@@ -483,6 +701,17 @@ pub fn create_globals<'gc>(
         )),
         EnumSet::empty(),
     );
+    globals.define_value(
+        gc_context,
+        "Selection",
+        Value::Object(selection::create_selection_object(
+            gc_context,
+            Some(object_proto),
+            Some(function_proto),
+            &listeners.selection,
+        )),
+        EnumSet::empty(),
+    );
     globals.define_value(
         gc_context,
         "Stage",
@@ -566,6 +795,8 @@ pub fn create_globals<'gc>(
             function: function_proto,
             movie_clip: movie_clip_proto,
             sound: sound_proto,
+            date: date_proto,
+            bitmap_data: bitmap_data_proto,
             text_field: text_field_proto,
             text_format: text_format_proto,
             array: array_proto,
@@ -581,6 +812,15 @@ pub fn create_globals<'gc>(
             color_transform: color_transform_proto,
             context_menu: context_menu_proto,
             context_menu_item: context_menu_item_proto,
+            bitmap_filter: bitmap_filter_proto,
+            blur_filter: blur_filter_proto,
+            drop_shadow_filter: drop_shadow_filter_proto,
+            glow_filter: glow_filter_proto,
+            bevel_filter: bevel_filter_proto,
+            gradient_glow_filter: gradient_glow_filter_proto,
+            gradient_bevel_filter: gradient_bevel_filter_proto,
+            color_matrix_filter: color_matrix_filter_proto,
+            convolution_filter: convolution_filter_proto,
         },
         globals.into(),
         listeners,
@@ -0,0 +1,158 @@
+//! flash.filters.BevelFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::bitmap_filter::{self, add_property};
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::{Object, Value};
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use swf::{BevelFilter, Color, Filter};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bitmap_filter::apply_args(
+        activation,
+        context,
+        this,
+        args,
+        &[
+            "distance",
+            "angle",
+            "highlightColor",
+            "highlightAlpha",
+            "shadowColor",
+            "shadowAlpha",
+            "blurX",
+            "blurY",
+            "strength",
+            "quality",
+            "type",
+            "knockout",
+        ],
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let filter = Filter::BevelFilter(Box::new(BevelFilter {
+        shadow_color: Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        },
+        highlight_color: Color {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        },
+        blur_x: 4.0,
+        blur_y: 4.0,
+        angle: 45f64.to_radians(),
+        distance: 4.0,
+        strength: 1.0,
+        is_inner: true,
+        is_knockout: false,
+        is_on_top: false,
+        num_passes: 1,
+    }));
+    let object: Object<'gc> = FilterObject::new(gc_context, Some(proto), filter).into();
+
+    add_property(
+        gc_context,
+        object,
+        "distance",
+        bitmap_filter::get_distance,
+        bitmap_filter::set_distance,
+    );
+    add_property(
+        gc_context,
+        object,
+        "angle",
+        bitmap_filter::get_angle,
+        bitmap_filter::set_angle,
+    );
+    add_property(
+        gc_context,
+        object,
+        "highlightColor",
+        bitmap_filter::get_highlight_color,
+        bitmap_filter::set_highlight_color,
+    );
+    add_property(
+        gc_context,
+        object,
+        "highlightAlpha",
+        bitmap_filter::get_highlight_alpha,
+        bitmap_filter::set_highlight_alpha,
+    );
+    add_property(
+        gc_context,
+        object,
+        "shadowColor",
+        bitmap_filter::get_shadow_color,
+        bitmap_filter::set_shadow_color,
+    );
+    add_property(
+        gc_context,
+        object,
+        "shadowAlpha",
+        bitmap_filter::get_shadow_alpha,
+        bitmap_filter::set_shadow_alpha,
+    );
+    add_property(
+        gc_context,
+        object,
+        "blurX",
+        bitmap_filter::get_blur_x,
+        bitmap_filter::set_blur_x,
+    );
+    add_property(
+        gc_context,
+        object,
+        "blurY",
+        bitmap_filter::get_blur_y,
+        bitmap_filter::set_blur_y,
+    );
+    add_property(
+        gc_context,
+        object,
+        "strength",
+        bitmap_filter::get_strength,
+        bitmap_filter::set_strength,
+    );
+    add_property(
+        gc_context,
+        object,
+        "quality",
+        bitmap_filter::get_quality,
+        bitmap_filter::set_quality,
+    );
+    add_property(
+        gc_context,
+        object,
+        "type",
+        bitmap_filter::get_type,
+        bitmap_filter::set_type,
+    );
+    add_property(
+        gc_context,
+        object,
+        "knockout",
+        bitmap_filter::get_knockout,
+        bitmap_filter::set_knockout,
+    );
+
+    object
+}
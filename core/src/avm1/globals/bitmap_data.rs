@@ -0,0 +1,809 @@
+//! flash.display.BitmapData
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::color_transform::object_to_color_transform;
+use crate::avm1::globals::matrix::object_to_matrix;
+use crate::avm1::globals::point::value_to_point;
+use crate::avm1::globals::rectangle;
+use crate::avm1::object::bitmap_data_object::BitmapDataObject;
+use crate::avm1::property::Attribute::*;
+use crate::avm1::{Object, TObject, Value};
+use crate::bitmap_data::{self, BitmapData, PerlinNoiseOptions, PixelRect, ThresholdOperation};
+use crate::character::Character;
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{Bitmap, DisplayObject, TDisplayObject};
+use crate::prelude::*;
+use crate::transform::{Transform, TransformStack};
+use enumset::EnumSet;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.BitmapData`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = match this.as_bitmap_data_object() {
+        Some(object) => object,
+        None => return Ok(Value::Undefined),
+    };
+
+    let width = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation, context)?;
+    let height = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation, context)?;
+    let transparent = args
+        .get(2)
+        .map(|v| v.as_bool(activation.current_swf_version()))
+        .unwrap_or(true);
+    let fill_color = match args.get(3) {
+        Some(fill_color) => fill_color.coerce_to_u32(activation, context)?,
+        None => 0xFFFF_FFFF,
+    };
+
+    // Invalid sizes leave the bitmap disposed, with a width and height of -1.
+    let max_size = bitmap_data::MAX_SIZE as i32;
+    if width < 1 || width > max_size || height < 1 || height > max_size {
+        log::warn!("Invalid BitmapData size: {}x{}", width, height);
+        return Ok(Value::Undefined);
+    }
+
+    object.set_bitmap_data(
+        context.gc_context,
+        BitmapData::new(width as u32, height as u32, transparent, fill_color),
+    );
+    Ok(Value::Undefined)
+}
+
+/// Returns the pixels of `this`, unless it isn't a `BitmapData` or has been
+/// disposed.
+fn pixels(this: Object<'_>) -> Option<GcCell<'_, BitmapData>> {
+    this.as_bitmap_data_object()
+        .map(|object| object.bitmap_data())
+        .filter(|bitmap_data| !bitmap_data.read().disposed())
+}
+
+/// Returns the pixels of a `BitmapData` passed as an argument.
+fn value_to_pixels<'gc>(value: Option<&Value<'gc>>) -> Option<GcCell<'gc, BitmapData>> {
+    match value {
+        Some(Value::Object(object)) => pixels(*object),
+        _ => None,
+    }
+}
+
+fn value_to_pixel_rect<'gc>(
+    value: Option<&Value<'gc>>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<Option<PixelRect>, Error<'gc>> {
+    let object = match value {
+        Some(Value::Object(object)) => *object,
+        _ => return Ok(None),
+    };
+    let x = object
+        .get("x", activation, context)?
+        .coerce_to_i32(activation, context)?;
+    let y = object
+        .get("y", activation, context)?
+        .coerce_to_i32(activation, context)?;
+    let width = object
+        .get("width", activation, context)?
+        .coerce_to_i32(activation, context)?;
+    let height = object
+        .get("height", activation, context)?
+        .coerce_to_i32(activation, context)?;
+    Ok(Some(PixelRect::new(x, y, width, height)))
+}
+
+fn value_to_pixel_point<'gc>(
+    value: Option<&Value<'gc>>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<(i32, i32), Error<'gc>> {
+    match value {
+        Some(value @ Value::Object(_)) => {
+            let (x, y) = value_to_point(value.to_owned(), activation, context)?;
+            Ok((x as i32, y as i32))
+        }
+        _ => Ok((0, 0)),
+    }
+}
+
+fn arg_i32<'gc>(
+    args: &[Value<'gc>],
+    index: usize,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<i32, Error<'gc>> {
+    args.get(index)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation, context)
+}
+
+fn arg_bool<'gc>(
+    args: &[Value<'gc>],
+    index: usize,
+    activation: &mut Activation<'_, 'gc>,
+    default: bool,
+) -> bool {
+    args.get(index)
+        .map(|v| v.as_bool(activation.current_swf_version()))
+        .unwrap_or(default)
+}
+
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match pixels(this) {
+        Some(pixels) => pixels.read().width().into(),
+        None => (-1).into(),
+    })
+}
+
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match pixels(this) {
+        Some(pixels) => pixels.read().height().into(),
+        None => (-1).into(),
+    })
+}
+
+pub fn transparent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match pixels(this) {
+        Some(pixels) => pixels.read().transparent().into(),
+        None => (-1).into(),
+    })
+}
+
+pub fn rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pixels = match pixels(this) {
+        Some(pixels) => pixels,
+        None => return Ok((-1).into()),
+    };
+    let args = [
+        0.into(),
+        0.into(),
+        pixels.read().width().into(),
+        pixels.read().height().into(),
+    ];
+    let proto = context.system_prototypes.rectangle;
+    let rectangle = proto.new(activation, context, proto, &args)?;
+    let _ = rectangle::constructor(activation, context, rectangle, &args)?;
+    Ok(rectangle.into())
+}
+
+pub fn get_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        let x = arg_i32(args, 0, activation, context)?;
+        let y = arg_i32(args, 1, activation, context)?;
+        return Ok(pixels.read().get_pixel(x, y).into());
+    }
+    Ok((-1).into())
+}
+
+pub fn get_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        let x = arg_i32(args, 0, activation, context)?;
+        let y = arg_i32(args, 1, activation, context)?;
+        // The color is returned as a signed integer.
+        return Ok((pixels.read().get_pixel32(x, y) as i32).into());
+    }
+    Ok((-1).into())
+}
+
+pub fn set_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        let x = arg_i32(args, 0, activation, context)?;
+        let y = arg_i32(args, 1, activation, context)?;
+        let color = arg_i32(args, 2, activation, context)? as u32;
+        pixels.write(context.gc_context).set_pixel(x, y, color);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        let x = arg_i32(args, 0, activation, context)?;
+        let y = arg_i32(args, 1, activation, context)?;
+        let color = arg_i32(args, 2, activation, context)? as u32;
+        pixels.write(context.gc_context).set_pixel32(x, y, color);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn fill_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        if let Some(rect) = value_to_pixel_rect(args.get(0), activation, context)? {
+            let color = arg_i32(args, 1, activation, context)? as u32;
+            pixels.write(context.gc_context).fill_rect(rect, color);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+/// Runs `f` with the pixels of `dest` and `source`. If they are the same
+/// bitmap, `f` reads from a copy of the pixels taken beforehand.
+fn with_source<'gc, R>(
+    gc_context: MutationContext<'gc, '_>,
+    dest: GcCell<'gc, BitmapData>,
+    source: GcCell<'gc, BitmapData>,
+    f: impl FnOnce(&mut BitmapData, &BitmapData) -> R,
+) -> R {
+    if GcCell::ptr_eq(dest, source) {
+        let snapshot = source.read().clone();
+        f(&mut dest.write(gc_context), &snapshot)
+    } else {
+        f(&mut dest.write(gc_context), &source.read())
+    }
+}
+
+pub fn copy_pixels<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (pixels, source) = match (pixels(this), value_to_pixels(args.get(0))) {
+        (Some(pixels), Some(source)) => (pixels, source),
+        _ => return Ok(Value::Undefined),
+    };
+    let source_rect = match value_to_pixel_rect(args.get(1), activation, context)? {
+        Some(source_rect) => source_rect,
+        None => return Ok(Value::Undefined),
+    };
+    let dest_point = value_to_pixel_point(args.get(2), activation, context)?;
+    let alpha_source = value_to_pixels(args.get(3));
+    let alpha_point = value_to_pixel_point(args.get(4), activation, context)?;
+    let merge_alpha = arg_bool(args, 5, activation, false);
+
+    let alpha_snapshot;
+    let alpha_ref;
+    let alpha_data = match &alpha_source {
+        Some(alpha_source) if GcCell::ptr_eq(*alpha_source, pixels) => {
+            alpha_snapshot = alpha_source.read().clone();
+            Some(&alpha_snapshot)
+        }
+        Some(alpha_source) => {
+            alpha_ref = alpha_source.read();
+            Some(&*alpha_ref)
+        }
+        None => None,
+    };
+
+    with_source(context.gc_context, pixels, source, |pixels, source| {
+        pixels.copy_pixels(
+            source,
+            source_rect,
+            dest_point,
+            alpha_data.map(|alpha_data| (alpha_data, alpha_point)),
+            merge_alpha,
+        )
+    });
+    Ok(Value::Undefined)
+}
+
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pixels = match pixels(this) {
+        Some(pixels) => pixels,
+        None => return Ok(Value::Undefined),
+    };
+    let rect = value_to_pixel_rect(args.get(0), activation, context)?;
+    if let (Some(rect), Some(Value::Object(color_transform))) = (rect, args.get(1)) {
+        let mut color_transform = object_to_color_transform(*color_transform, activation, context)?;
+        // The offsets of a `flash.geom.ColorTransform` range from -255 to 255.
+        color_transform.r_add /= 255.0;
+        color_transform.g_add /= 255.0;
+        color_transform.b_add /= 255.0;
+        color_transform.a_add /= 255.0;
+        pixels
+            .write(context.gc_context)
+            .color_transform(rect, &color_transform);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn noise<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        let seed = arg_i32(args, 0, activation, context)?;
+        let low = match args.get(1) {
+            Some(low) => low.coerce_to_i32(activation, context)?,
+            None => 0,
+        };
+        let high = match args.get(2) {
+            Some(high) => high.coerce_to_i32(activation, context)?,
+            None => 255,
+        };
+        let channels = match args.get(3) {
+            Some(channels) => channels.coerce_to_i32(activation, context)?,
+            None => 7,
+        };
+        let gray_scale = arg_bool(args, 4, activation, false);
+        pixels.write(context.gc_context).noise(
+            seed,
+            low.max(0).min(255) as u8,
+            high.max(0).min(255) as u8,
+            channels as u8,
+            gray_scale,
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn perlin_noise<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pixels = match pixels(this) {
+        Some(pixels) => pixels,
+        None => return Ok(Value::Undefined),
+    };
+
+    let base_x = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation, context)?;
+    let base_y = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation, context)?;
+    let num_octaves = arg_i32(args, 2, activation, context)?.max(0) as usize;
+    let seed = arg_i32(args, 3, activation, context)?;
+    let stitch = arg_bool(args, 4, activation, false);
+    let fractal_noise = arg_bool(args, 5, activation, false);
+    let channels = match args.get(6) {
+        Some(channels) => channels.coerce_to_i32(activation, context)?,
+        None => 7,
+    };
+    let gray_scale = arg_bool(args, 7, activation, false);
+    let mut offsets = Vec::new();
+    if let Some(Value::Object(offsets_array)) = args.get(8) {
+        for offset in offsets_array.array() {
+            offsets.push(value_to_point(offset, activation, context)?);
+        }
+    }
+
+    pixels
+        .write(context.gc_context)
+        .perlin_noise(&PerlinNoiseOptions {
+            base_x,
+            base_y,
+            num_octaves,
+            seed,
+            stitch,
+            fractal_noise,
+            channels: channels as u8,
+            gray_scale,
+            offsets,
+        });
+    Ok(Value::Undefined)
+}
+
+pub fn threshold<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (pixels, source) = match (pixels(this), value_to_pixels(args.get(0))) {
+        (Some(pixels), Some(source)) => (pixels, source),
+        _ => return Ok(0.into()),
+    };
+    let source_rect = match value_to_pixel_rect(args.get(1), activation, context)? {
+        Some(source_rect) => source_rect,
+        None => return Ok(0.into()),
+    };
+    let dest_point = value_to_pixel_point(args.get(2), activation, context)?;
+    let operation = args
+        .get(3)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+    let operation = match ThresholdOperation::from_str(&operation) {
+        Some(operation) => operation,
+        None => return Ok(0.into()),
+    };
+    let threshold = arg_i32(args, 4, activation, context)? as u32;
+    let color = arg_i32(args, 5, activation, context)? as u32;
+    let mask = match args.get(6) {
+        Some(mask) => mask.coerce_to_i32(activation, context)? as u32,
+        None => 0xFFFF_FFFF,
+    };
+    let copy_source = arg_bool(args, 7, activation, false);
+
+    let count = with_source(context.gc_context, pixels, source, |pixels, source| {
+        pixels.threshold(
+            source,
+            source_rect,
+            dest_point,
+            operation,
+            threshold,
+            color,
+            mask,
+            copy_source,
+        )
+    });
+    Ok(count.into())
+}
+
+pub fn flood_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        let x = arg_i32(args, 0, activation, context)?;
+        let y = arg_i32(args, 1, activation, context)?;
+        let color = arg_i32(args, 2, activation, context)? as u32;
+        pixels.write(context.gc_context).flood_fill(x, y, color);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn draw<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pixels = match pixels(this) {
+        Some(pixels) => pixels,
+        None => return Ok(Value::Undefined),
+    };
+
+    // The source is either a display object or another `BitmapData`.
+    let source: DisplayObject<'gc> = match args.get(0) {
+        Some(Value::Object(object)) => {
+            if let Some(display_object) = object.as_display_object() {
+                display_object
+            } else if let Some(source) = value_to_pixels(args.get(0)) {
+                Bitmap::new_with_bitmap_data(context, source).into()
+            } else {
+                return Ok(Value::Undefined);
+            }
+        }
+        _ => return Ok(Value::Undefined),
+    };
+
+    let matrix = match args.get(1) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation, context)?,
+        _ => Default::default(),
+    };
+    let mut color_transform = match args.get(2) {
+        Some(Value::Object(color_transform)) => {
+            object_to_color_transform(*color_transform, activation, context)?
+        }
+        _ => Default::default(),
+    };
+    color_transform.r_add /= 255.0;
+    color_transform.g_add /= 255.0;
+    color_transform.b_add /= 255.0;
+    color_transform.a_add /= 255.0;
+    // TODO: Support `blendMode` and `smoothing`.
+    let (width, height) = (pixels.read().width(), pixels.read().height());
+    let clip_rect = value_to_pixel_rect(args.get(4), activation, context)?
+        .unwrap_or_else(|| PixelRect::new(0, 0, width as i32, height as i32));
+
+    // The transform of the source is replaced by the given one while drawing.
+    let mut source_mut = source;
+    let old_matrix = *source.matrix();
+    let old_color_transform = *source.color_transform();
+    source_mut.set_matrix(context.gc_context, &Default::default());
+    source_mut.set_color_transform(context.gc_context, &Default::default());
+
+    let library = &*context.library;
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix,
+        color_transform,
+    });
+    let result = context
+        .renderer
+        .render_offscreen(width, height, &mut |renderer| {
+            let mut render_context = RenderContext {
+                renderer,
+                library,
+                transform_stack: &mut transform_stack,
+                // The source is never culled, wherever it is on the stage.
+                view_bounds: BoundingBox {
+                    x_min: Twips::new(i32::MIN),
+                    y_min: Twips::new(i32::MIN),
+                    x_max: Twips::new(i32::MAX),
+                    y_max: Twips::new(i32::MAX),
+                    valid: true,
+                },
+                clip_depth_stack: vec![],
+            };
+            source.render(&mut render_context);
+        });
+
+    source_mut.set_matrix(context.gc_context, &old_matrix);
+    source_mut.set_color_transform(context.gc_context, &old_color_transform);
+
+    match result {
+        Ok(rgba) => pixels.write(context.gc_context).draw_rgba(&rgba, clip_rect),
+        Err(e) => log::warn!("BitmapData.draw: {}", e),
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn clone<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match pixels(this) {
+        Some(pixels) => BitmapDataObject::new(
+            context.gc_context,
+            Some(context.system_prototypes.bitmap_data),
+            pixels.read().clone(),
+        )
+        .into(),
+        None => Value::Undefined,
+    })
+}
+
+pub fn dispose<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(pixels) = pixels(this) {
+        pixels.write(context.gc_context).dispose();
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.loadBitmap`
+pub fn load_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let export_name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+    let library = activation
+        .base_clip()
+        .movie()
+        .and_then(|movie| context.library.library_for_movie(movie));
+    let bitmap = match library.map(|l| (l, l.get_character_by_export_name(&export_name))) {
+        Some((library, Some(Character::Bitmap(bitmap)))) => {
+            bitmap.tag().map(|tag| tag.decode(library.jpeg_tables()))
+        }
+        _ => None,
+    };
+
+    match bitmap {
+        Some(Ok(bitmap)) => Ok(BitmapDataObject::new(
+            context.gc_context,
+            Some(context.system_prototypes.bitmap_data),
+            BitmapData::from_bitmap(bitmap),
+        )
+        .into()),
+        Some(Err(e)) => {
+            log::warn!(
+                "BitmapData.loadBitmap: Unable to decode '{}': {}",
+                export_name,
+                e
+            );
+            Ok(Value::Undefined)
+        }
+        None => {
+            log::warn!(
+                "BitmapData.loadBitmap: No bitmap exported as '{}'",
+                export_name
+            );
+            Ok(Value::Undefined)
+        }
+    }
+}
+
+pub fn create_bitmap_data_object<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    bitmap_data_proto: Option<Object<'gc>>,
+    fn_proto: Option<Object<'gc>>,
+) -> Object<'gc> {
+    let bitmap_data = FunctionObject::function(
+        gc_context,
+        Executable::Native(constructor),
+        fn_proto,
+        bitmap_data_proto,
+    );
+    let mut object = bitmap_data.as_script_object().unwrap();
+
+    object.force_set_function(
+        "loadBitmap",
+        load_bitmap,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    bitmap_data
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let bitmap_data = BitmapDataObject::empty_bitmap_data_object(gc_context, Some(proto));
+    let mut object = bitmap_data.as_script_object().unwrap();
+
+    object.add_property(
+        gc_context,
+        "width",
+        Executable::Native(width),
+        None,
+        EnumSet::empty(),
+    );
+    object.add_property(
+        gc_context,
+        "height",
+        Executable::Native(height),
+        None,
+        EnumSet::empty(),
+    );
+    object.add_property(
+        gc_context,
+        "transparent",
+        Executable::Native(transparent),
+        None,
+        EnumSet::empty(),
+    );
+    object.add_property(
+        gc_context,
+        "rectangle",
+        Executable::Native(rectangle),
+        None,
+        EnumSet::empty(),
+    );
+
+    object.force_set_function(
+        "getPixel",
+        get_pixel,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getPixel32",
+        get_pixel32,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "setPixel",
+        set_pixel,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "setPixel32",
+        set_pixel32,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "fillRect",
+        fill_rect,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "copyPixels",
+        copy_pixels,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "colorTransform",
+        color_transform,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function("noise", noise, gc_context, EnumSet::empty(), Some(fn_proto));
+    object.force_set_function(
+        "perlinNoise",
+        perlin_noise,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "threshold",
+        threshold,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "floodFill",
+        flood_fill,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    object.force_set_function("draw", draw, gc_context, EnumSet::empty(), Some(fn_proto));
+    object.force_set_function("clone", clone, gc_context, EnumSet::empty(), Some(fn_proto));
+    object.force_set_function(
+        "dispose",
+        dispose,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+
+    bitmap_data.into()
+}
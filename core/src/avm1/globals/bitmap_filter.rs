@@ -0,0 +1,826 @@
+//! flash.filters.BitmapFilter object
+//!
+//! The properties of all filter classes are implemented here, as most of them
+//! are shared by several kinds of filter.
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, NativeFunction};
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::context::UpdateContext;
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+use swf::{Color, Filter, GradientRecord};
+
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Initialize a newly constructed filter from its constructor arguments.
+///
+/// Each argument is assigned to the property of the same position; missing
+/// arguments leave the default value of the filter untouched.
+pub fn apply_args<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    properties: &[&str],
+) -> Result<(), Error<'gc>> {
+    for (arg, name) in args.iter().zip(properties) {
+        this.set(name, arg.clone(), activation, context)?;
+    }
+
+    Ok(())
+}
+
+/// Add a native property to a filter prototype.
+pub fn add_property<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    object: Object<'gc>,
+    name: &str,
+    getter: NativeFunction<'gc>,
+    setter: NativeFunction<'gc>,
+) {
+    object.add_property(
+        gc_context,
+        name,
+        Executable::Native(getter),
+        Some(Executable::Native(setter)),
+        EnumSet::empty(),
+    );
+}
+
+/// Create a script object for a filter, using the prototype of its class.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Object<'gc> {
+    let prototypes = &activation.avm.prototypes;
+    let proto = match filter {
+        Filter::BlurFilter(_) => prototypes.blur_filter,
+        Filter::DropShadowFilter(_) => prototypes.drop_shadow_filter,
+        Filter::GlowFilter(_) => prototypes.glow_filter,
+        Filter::BevelFilter(_) => prototypes.bevel_filter,
+        Filter::GradientGlowFilter(_) => prototypes.gradient_glow_filter,
+        Filter::GradientBevelFilter(_) => prototypes.gradient_bevel_filter,
+        Filter::ColorMatrixFilter(_) => prototypes.color_matrix_filter,
+        Filter::ConvolutionFilter(_) => prototypes.convolution_filter,
+    };
+
+    FilterObject::new(context.gc_context, Some(proto), filter.clone()).into()
+}
+
+pub fn clone<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let copy = filter.filter().clone();
+        return Ok(FilterObject::new(context.gc_context, this.proto(), copy).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define a getter and setter for a field that is shared by several kinds of
+/// filter.
+///
+/// The getter and setter expressions are given the field of whichever kind
+/// of filter the object holds.
+macro_rules! filter_property {
+    ($getter:ident, $setter:ident, $coerce:expr, [$($kind:ident => $field:ident),*],
+     |$get_field:ident| $get:expr, |$set_field:ident, $value:ident| $set:expr) => {
+        pub fn $getter<'gc>(
+            _activation: &mut Activation<'_, 'gc>,
+            _context: &mut UpdateContext<'_, 'gc, '_>,
+            this: Object<'gc>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            if let Some(filter) = this.as_filter_object() {
+                match &*filter.filter() {
+                    $(Filter::$kind(filter) => {
+                        let $get_field = &filter.$field;
+                        return Ok($get.into());
+                    })*
+                    _ => (),
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+
+        pub fn $setter<'gc>(
+            activation: &mut Activation<'_, 'gc>,
+            context: &mut UpdateContext<'_, 'gc, '_>,
+            this: Object<'gc>,
+            args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error<'gc>> {
+            if let Some(filter) = this.as_filter_object() {
+                let $value = $coerce(args.get(0).unwrap_or(&Value::Undefined), activation, context)?;
+                match &mut *filter.filter_mut(context.gc_context) {
+                    $(Filter::$kind(filter) => {
+                        let $set_field = &mut filter.$field;
+                        $set;
+                    })*
+                    _ => (),
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+fn to_f64<'gc>(
+    value: &Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<f64, Error<'gc>> {
+    value.coerce_to_f64(activation, context)
+}
+
+fn to_i32<'gc>(
+    value: &Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<i32, Error<'gc>> {
+    value.coerce_to_i32(activation, context)
+}
+
+fn to_u32<'gc>(
+    value: &Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<u32, Error<'gc>> {
+    value.coerce_to_u32(activation, context)
+}
+
+fn to_bool<'gc>(
+    value: &Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<bool, Error<'gc>> {
+    Ok(value.as_bool(activation.current_swf_version()))
+}
+
+/// Coerce a value to a list of numbers, if it is an array.
+fn to_f64_list<'gc>(
+    value: &Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<Option<Vec<f64>>, Error<'gc>> {
+    if let Value::Object(object) = value {
+        let mut list = Vec::new();
+        for element in object.array() {
+            list.push(element.coerce_to_f64(activation, context)?);
+        }
+        Ok(Some(list))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Create an array of numbers.
+fn f64_list_to_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    list: impl Iterator<Item = f64>,
+) -> Value<'gc> {
+    let array = ScriptObject::array(context.gc_context, Some(activation.avm.prototypes.array));
+    for (i, value) in list.enumerate() {
+        array.set_array_element(i, value.into(), context.gc_context);
+    }
+    array.into()
+}
+
+fn color_rgb(color: &Color) -> u32 {
+    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+}
+
+fn set_color_rgb(color: &mut Color, rgb: u32) {
+    color.r = (rgb >> 16) as u8;
+    color.g = (rgb >> 8) as u8;
+    color.b = rgb as u8;
+}
+
+fn color_alpha(color: &Color) -> f64 {
+    f64::from(color.a) / 255.0
+}
+
+fn set_color_alpha(color: &mut Color, alpha: f64) {
+    color.a = (alpha.max(0.0).min(1.0) * 255.0) as u8;
+}
+
+filter_property!(
+    get_blur_x,
+    set_blur_x,
+    to_f64,
+    [
+        BlurFilter => blur_x,
+        DropShadowFilter => blur_x,
+        GlowFilter => blur_x,
+        BevelFilter => blur_x,
+        GradientGlowFilter => blur_x,
+        GradientBevelFilter => blur_x
+    ],
+    |blur| *blur,
+    |blur, value| *blur = value.max(0.0).min(255.0)
+);
+
+filter_property!(
+    get_blur_y,
+    set_blur_y,
+    to_f64,
+    [
+        BlurFilter => blur_y,
+        DropShadowFilter => blur_y,
+        GlowFilter => blur_y,
+        BevelFilter => blur_y,
+        GradientGlowFilter => blur_y,
+        GradientBevelFilter => blur_y
+    ],
+    |blur| *blur,
+    |blur, value| *blur = value.max(0.0).min(255.0)
+);
+
+filter_property!(
+    get_quality,
+    set_quality,
+    to_i32,
+    [
+        BlurFilter => num_passes,
+        DropShadowFilter => num_passes,
+        GlowFilter => num_passes,
+        BevelFilter => num_passes,
+        GradientGlowFilter => num_passes,
+        GradientBevelFilter => num_passes
+    ],
+    |passes| *passes,
+    |passes, value| *passes = value.max(0).min(15) as u8
+);
+
+filter_property!(
+    get_distance,
+    set_distance,
+    to_f64,
+    [
+        DropShadowFilter => distance,
+        BevelFilter => distance,
+        GradientGlowFilter => distance,
+        GradientBevelFilter => distance
+    ],
+    |distance| *distance,
+    |distance, value| *distance = value
+);
+
+// Angles are stored in radians, but exposed in degrees.
+filter_property!(
+    get_angle,
+    set_angle,
+    to_f64,
+    [
+        DropShadowFilter => angle,
+        BevelFilter => angle,
+        GradientGlowFilter => angle,
+        GradientBevelFilter => angle
+    ],
+    |angle| angle.to_degrees(),
+    |angle, value| *angle = value.to_radians()
+);
+
+filter_property!(
+    get_strength,
+    set_strength,
+    to_f64,
+    [
+        DropShadowFilter => strength,
+        GlowFilter => strength,
+        BevelFilter => strength,
+        GradientGlowFilter => strength,
+        GradientBevelFilter => strength
+    ],
+    |strength| f64::from(*strength),
+    |strength, value| *strength = value.max(0.0).min(255.0) as f32
+);
+
+filter_property!(
+    get_knockout,
+    set_knockout,
+    to_bool,
+    [
+        DropShadowFilter => is_knockout,
+        GlowFilter => is_knockout,
+        BevelFilter => is_knockout,
+        GradientGlowFilter => is_knockout,
+        GradientBevelFilter => is_knockout
+    ],
+    |knockout| *knockout,
+    |knockout, value| *knockout = value
+);
+
+filter_property!(
+    get_inner,
+    set_inner,
+    to_bool,
+    [DropShadowFilter => is_inner, GlowFilter => is_inner],
+    |inner| *inner,
+    |inner, value| *inner = value
+);
+
+filter_property!(
+    get_color,
+    set_color,
+    to_u32,
+    [
+        DropShadowFilter => color,
+        GlowFilter => color,
+        ConvolutionFilter => default_color
+    ],
+    |color| color_rgb(color),
+    |color, value| set_color_rgb(color, value)
+);
+
+filter_property!(
+    get_alpha,
+    set_alpha,
+    to_f64,
+    [
+        DropShadowFilter => color,
+        GlowFilter => color,
+        ConvolutionFilter => default_color
+    ],
+    |color| color_alpha(color),
+    |color, value| set_color_alpha(color, value)
+);
+
+filter_property!(
+    get_highlight_color,
+    set_highlight_color,
+    to_u32,
+    [BevelFilter => highlight_color],
+    |color| color_rgb(color),
+    |color, value| set_color_rgb(color, value)
+);
+
+filter_property!(
+    get_highlight_alpha,
+    set_highlight_alpha,
+    to_f64,
+    [BevelFilter => highlight_color],
+    |color| color_alpha(color),
+    |color, value| set_color_alpha(color, value)
+);
+
+filter_property!(
+    get_shadow_color,
+    set_shadow_color,
+    to_u32,
+    [BevelFilter => shadow_color],
+    |color| color_rgb(color),
+    |color, value| set_color_rgb(color, value)
+);
+
+filter_property!(
+    get_shadow_alpha,
+    set_shadow_alpha,
+    to_f64,
+    [BevelFilter => shadow_color],
+    |color| color_alpha(color),
+    |color, value| set_color_alpha(color, value)
+);
+
+filter_property!(
+    get_divisor,
+    set_divisor,
+    to_f64,
+    [ConvolutionFilter => divisor],
+    |divisor| *divisor,
+    |divisor, value| *divisor = value
+);
+
+filter_property!(
+    get_bias,
+    set_bias,
+    to_f64,
+    [ConvolutionFilter => bias],
+    |bias| *bias,
+    |bias, value| *bias = value
+);
+
+filter_property!(
+    get_preserve_alpha,
+    set_preserve_alpha,
+    to_bool,
+    [ConvolutionFilter => is_preserve_alpha],
+    |preserve_alpha| *preserve_alpha,
+    |preserve_alpha, value| *preserve_alpha = value
+);
+
+filter_property!(
+    get_clamp,
+    set_clamp,
+    to_bool,
+    [ConvolutionFilter => is_clamped],
+    |clamp| *clamp,
+    |clamp, value| *clamp = value
+);
+
+/// Get the bevel type flags of a filter, as `(is_inner, is_on_top)`.
+fn bevel_type_flags(filter: &Filter) -> Option<(bool, bool)> {
+    match filter {
+        Filter::BevelFilter(filter) => Some((filter.is_inner, filter.is_on_top)),
+        Filter::GradientGlowFilter(filter) => Some((filter.is_inner, filter.is_on_top)),
+        Filter::GradientBevelFilter(filter) => Some((filter.is_inner, filter.is_on_top)),
+        _ => None,
+    }
+}
+
+/// Implements the `type` property of bevel and gradient filters.
+pub fn get_type<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        return Ok(match bevel_type_flags(&filter.filter()) {
+            Some((_, true)) => "full".into(),
+            Some((true, false)) => "inner".into(),
+            Some((false, false)) => "outer".into(),
+            None => Value::Undefined,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let bevel_type = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        let (is_inner, is_on_top) = match &bevel_type[..] {
+            "inner" => (true, false),
+            "outer" => (false, false),
+            "full" => (false, true),
+            _ => return Ok(Value::Undefined),
+        };
+
+        match &mut *filter.filter_mut(context.gc_context) {
+            Filter::BevelFilter(filter) => {
+                filter.is_inner = is_inner;
+                filter.is_on_top = is_on_top;
+            }
+            Filter::GradientGlowFilter(filter) => {
+                filter.is_inner = is_inner;
+                filter.is_on_top = is_on_top;
+            }
+            Filter::GradientBevelFilter(filter) => {
+                filter.is_inner = is_inner;
+                filter.is_on_top = is_on_top;
+            }
+            _ => (),
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Get the gradient of a gradient filter.
+fn gradient(filter: &Filter) -> Option<&[GradientRecord]> {
+    match filter {
+        Filter::GradientGlowFilter(filter) => Some(&filter.colors),
+        Filter::GradientBevelFilter(filter) => Some(&filter.colors),
+        _ => None,
+    }
+}
+
+/// Get the gradient of a gradient filter for modification.
+fn gradient_mut(filter: &mut Filter) -> Option<&mut Vec<GradientRecord>> {
+    match filter {
+        Filter::GradientGlowFilter(filter) => Some(&mut filter.colors),
+        Filter::GradientBevelFilter(filter) => Some(&mut filter.colors),
+        _ => None,
+    }
+}
+
+/// Implements the `colors`, `alphas` and `ratios` getters of gradient filters.
+fn get_gradient_list<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    entry: fn(&GradientRecord) -> f64,
+) -> Value<'gc> {
+    let list = this.as_filter_object().and_then(|filter| {
+        gradient(&filter.filter()).map(|colors| colors.iter().map(entry).collect())
+    });
+
+    match list {
+        Some(list) => {
+            let list: Vec<f64> = list;
+            f64_list_to_array(activation, context, list.into_iter())
+        }
+        None => Value::Undefined,
+    }
+}
+
+pub fn get_colors<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(get_gradient_list(activation, context, this, |record| {
+        f64::from(color_rgb(&record.color))
+    }))
+}
+
+/// Setting the colors of a gradient filter determines the number of entries
+/// in the gradient. New entries are opaque and spread evenly.
+pub fn set_colors<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let list = to_f64_list(
+            args.get(0).unwrap_or(&Value::Undefined),
+            activation,
+            context,
+        )?;
+        if let Some(list) = list {
+            let mut filter = filter.filter_mut(context.gc_context);
+            if let Some(colors) = gradient_mut(&mut filter) {
+                let len = list.len();
+                colors.truncate(len);
+                while colors.len() < len {
+                    let ratio = if len > 1 {
+                        (colors.len() * 255 / (len - 1)) as u8
+                    } else {
+                        0
+                    };
+                    colors.push(GradientRecord {
+                        ratio,
+                        color: Color {
+                            r: 0,
+                            g: 0,
+                            b: 0,
+                            a: 255,
+                        },
+                    });
+                }
+
+                for (record, value) in colors.iter_mut().zip(list) {
+                    set_color_rgb(&mut record.color, value as u32);
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_alphas<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(get_gradient_list(activation, context, this, |record| {
+        color_alpha(&record.color)
+    }))
+}
+
+pub fn set_alphas<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let list = to_f64_list(
+            args.get(0).unwrap_or(&Value::Undefined),
+            activation,
+            context,
+        )?;
+        if let Some(list) = list {
+            let mut filter = filter.filter_mut(context.gc_context);
+            if let Some(colors) = gradient_mut(&mut filter) {
+                for (record, value) in colors.iter_mut().zip(list) {
+                    set_color_alpha(&mut record.color, value);
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_ratios<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(get_gradient_list(activation, context, this, |record| {
+        f64::from(record.ratio)
+    }))
+}
+
+pub fn set_ratios<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let list = to_f64_list(
+            args.get(0).unwrap_or(&Value::Undefined),
+            activation,
+            context,
+        )?;
+        if let Some(list) = list {
+            let mut filter = filter.filter_mut(context.gc_context);
+            if let Some(colors) = gradient_mut(&mut filter) {
+                for (record, value) in colors.iter_mut().zip(list) {
+                    record.ratio = value.max(0.0).min(255.0) as u8;
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements the `matrix` property of color matrix and convolution filters.
+pub fn get_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = this
+        .as_filter_object()
+        .and_then(|filter| match &*filter.filter() {
+            Filter::ColorMatrixFilter(filter) => Some(filter.matrix.to_vec()),
+            Filter::ConvolutionFilter(filter) => Some(filter.matrix.clone()),
+            _ => None,
+        });
+
+    Ok(match matrix {
+        Some(matrix) => f64_list_to_array(activation, context, matrix.into_iter()),
+        None => Value::Undefined,
+    })
+}
+
+/// Missing entries of the new matrix are set to zero, and surplus entries are
+/// ignored.
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let list = to_f64_list(
+            args.get(0).unwrap_or(&Value::Undefined),
+            activation,
+            context,
+        )?;
+        if let Some(list) = list {
+            let entries = list.into_iter().chain(std::iter::repeat(0.0));
+            match &mut *filter.filter_mut(context.gc_context) {
+                Filter::ColorMatrixFilter(filter) => {
+                    for (entry, value) in filter.matrix.iter_mut().zip(entries) {
+                        *entry = value;
+                    }
+                }
+                Filter::ConvolutionFilter(filter) => {
+                    for (entry, value) in filter.matrix.iter_mut().zip(entries) {
+                        *entry = value;
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Resize the matrix of a convolution filter, keeping the existing entries
+/// in place.
+fn resize_convolution(filter: &mut Filter, cols: Option<u8>, rows: Option<u8>) {
+    if let Filter::ConvolutionFilter(filter) = filter {
+        let new_cols = cols.unwrap_or(filter.num_matrix_cols);
+        let new_rows = rows.unwrap_or(filter.num_matrix_rows);
+        let mut matrix = vec![0.0; usize::from(new_cols) * usize::from(new_rows)];
+        for y in 0..new_rows.min(filter.num_matrix_rows) {
+            for x in 0..new_cols.min(filter.num_matrix_cols) {
+                matrix[usize::from(y) * usize::from(new_cols) + usize::from(x)] = filter.matrix
+                    [usize::from(y) * usize::from(filter.num_matrix_cols) + usize::from(x)];
+            }
+        }
+
+        filter.matrix = matrix;
+        filter.num_matrix_cols = new_cols;
+        filter.num_matrix_rows = new_rows;
+    }
+}
+
+/// Get the size of the matrix of a convolution filter, as `(cols, rows)`.
+fn convolution_size(this: Object<'_>) -> Option<(u8, u8)> {
+    match &*this.as_filter_object()?.filter() {
+        Filter::ConvolutionFilter(filter) => Some((filter.num_matrix_cols, filter.num_matrix_rows)),
+        _ => None,
+    }
+}
+
+pub fn get_matrix_x<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(convolution_size(this)
+        .map(|(cols, _)| cols.into())
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn get_matrix_y<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(convolution_size(this)
+        .map(|(_, rows)| rows.into())
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_matrix_x<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let cols = to_i32(
+            args.get(0).unwrap_or(&Value::Undefined),
+            activation,
+            context,
+        )?;
+        let cols = cols.max(0).min(15) as u8;
+        resize_convolution(&mut filter.filter_mut(context.gc_context), Some(cols), None);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_matrix_y<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(filter) = this.as_filter_object() {
+        let rows = to_i32(
+            args.get(0).unwrap_or(&Value::Undefined),
+            activation,
+            context,
+        )?;
+        let rows = rows.max(0).min(15) as u8;
+        resize_convolution(&mut filter.filter_mut(context.gc_context), None, Some(rows));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function("clone", clone, gc_context, EnumSet::empty(), Some(fn_proto));
+
+    object.into()
+}
@@ -0,0 +1,64 @@
+//! flash.filters.BlurFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::bitmap_filter::{self, add_property};
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::{Object, Value};
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use swf::{BlurFilter, Filter};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bitmap_filter::apply_args(
+        activation,
+        context,
+        this,
+        args,
+        &["blurX", "blurY", "quality"],
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let filter = Filter::BlurFilter(Box::new(BlurFilter {
+        blur_x: 4.0,
+        blur_y: 4.0,
+        num_passes: 1,
+    }));
+    let object: Object<'gc> = FilterObject::new(gc_context, Some(proto), filter).into();
+
+    add_property(
+        gc_context,
+        object,
+        "blurX",
+        bitmap_filter::get_blur_x,
+        bitmap_filter::set_blur_x,
+    );
+    add_property(
+        gc_context,
+        object,
+        "blurY",
+        bitmap_filter::get_blur_y,
+        bitmap_filter::set_blur_y,
+    );
+    add_property(
+        gc_context,
+        object,
+        "quality",
+        bitmap_filter::get_quality,
+        bitmap_filter::set_quality,
+    );
+
+    object
+}
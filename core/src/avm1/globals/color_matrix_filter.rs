@@ -0,0 +1,47 @@
+//! flash.filters.ColorMatrixFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::bitmap_filter::{self, add_property};
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::{Object, Value};
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use swf::{ColorMatrixFilter, Filter};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bitmap_filter::apply_args(activation, context, this, args, &["matrix"])?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let filter = Filter::ColorMatrixFilter(Box::new(ColorMatrixFilter {
+        matrix: [
+            1.0, 0.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ],
+    }));
+    let object: Object<'gc> = FilterObject::new(gc_context, Some(proto), filter).into();
+
+    add_property(
+        gc_context,
+        object,
+        "matrix",
+        bitmap_filter::get_matrix,
+        bitmap_filter::set_matrix,
+    );
+
+    object
+}
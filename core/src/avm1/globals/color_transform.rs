@@ -78,8 +78,6 @@ pub fn constructor<'gc>(
     Ok(Value::Undefined)
 }
 
-// We'll need this soon!
-#[allow(dead_code)]
 pub fn object_to_color_transform<'gc>(
     object: Object<'gc>,
     activation: &mut Activation<'_, 'gc>,
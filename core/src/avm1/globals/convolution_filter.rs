@@ -0,0 +1,126 @@
+//! flash.filters.ConvolutionFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::bitmap_filter::{self, add_property};
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::{Object, Value};
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use swf::{Color, ConvolutionFilter, Filter};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bitmap_filter::apply_args(
+        activation,
+        context,
+        this,
+        args,
+        &[
+            "matrixX",
+            "matrixY",
+            "matrix",
+            "divisor",
+            "bias",
+            "preserveAlpha",
+            "clamp",
+            "color",
+            "alpha",
+        ],
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let filter = Filter::ConvolutionFilter(Box::new(ConvolutionFilter {
+        num_matrix_rows: 0,
+        num_matrix_cols: 0,
+        matrix: Vec::new(),
+        divisor: 1.0,
+        bias: 0.0,
+        default_color: Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        },
+        is_clamped: true,
+        is_preserve_alpha: true,
+    }));
+    let object: Object<'gc> = FilterObject::new(gc_context, Some(proto), filter).into();
+
+    add_property(
+        gc_context,
+        object,
+        "matrixX",
+        bitmap_filter::get_matrix_x,
+        bitmap_filter::set_matrix_x,
+    );
+    add_property(
+        gc_context,
+        object,
+        "matrixY",
+        bitmap_filter::get_matrix_y,
+        bitmap_filter::set_matrix_y,
+    );
+    add_property(
+        gc_context,
+        object,
+        "matrix",
+        bitmap_filter::get_matrix,
+        bitmap_filter::set_matrix,
+    );
+    add_property(
+        gc_context,
+        object,
+        "divisor",
+        bitmap_filter::get_divisor,
+        bitmap_filter::set_divisor,
+    );
+    add_property(
+        gc_context,
+        object,
+        "bias",
+        bitmap_filter::get_bias,
+        bitmap_filter::set_bias,
+    );
+    add_property(
+        gc_context,
+        object,
+        "preserveAlpha",
+        bitmap_filter::get_preserve_alpha,
+        bitmap_filter::set_preserve_alpha,
+    );
+    add_property(
+        gc_context,
+        object,
+        "clamp",
+        bitmap_filter::get_clamp,
+        bitmap_filter::set_clamp,
+    );
+    add_property(
+        gc_context,
+        object,
+        "color",
+        bitmap_filter::get_color,
+        bitmap_filter::set_color,
+    );
+    add_property(
+        gc_context,
+        object,
+        "alpha",
+        bitmap_filter::get_alpha,
+        bitmap_filter::set_alpha,
+    );
+
+    object
+}
@@ -0,0 +1,625 @@
+//! `Date` class impl
+//!
+//! Dates are stored as a time value in milliseconds since the Unix epoch, in
+//! UTC. The date arithmetic follows ECMA-262 3rd edition s. 15.9.1, and the
+//! clock and local time zone are provided by the locale backend.
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::date_object::DateObject;
+use crate::avm1::property::Attribute::*;
+use crate::avm1::{AvmString, Object, TObject, Value};
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use std::f64::NAN;
+
+const MS_PER_SECOND: f64 = 1000.0;
+const MS_PER_MINUTE: f64 = 60_000.0;
+const MS_PER_HOUR: f64 = 3_600_000.0;
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// The largest time value a `Date` can hold, in either direction.
+const MAX_TIME: f64 = 8.64e15;
+
+/// The day of the year each month starts on, in a non-leap year.
+const MONTH_OFFSETS: [f64; 12] = [
+    0.0, 31.0, 59.0, 90.0, 120.0, 151.0, 181.0, 212.0, 243.0, 273.0, 304.0, 334.0,
+];
+
+const WEEK_DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// The components of a date, in the order the setters accept them.
+const YEAR: usize = 0;
+const MONTH: usize = 1;
+const DATE: usize = 2;
+const HOURS: usize = 3;
+const MINUTES: usize = 4;
+const SECONDS: usize = 5;
+const MILLISECONDS: usize = 6;
+
+fn day(t: f64) -> f64 {
+    (t / MS_PER_DAY).floor()
+}
+
+fn time_within_day(t: f64) -> f64 {
+    t.rem_euclid(MS_PER_DAY)
+}
+
+fn is_leap_year(year: f64) -> bool {
+    (year % 4.0 == 0.0 && year % 100.0 != 0.0) || year % 400.0 == 0.0
+}
+
+fn day_from_year(year: f64) -> f64 {
+    365.0 * (year - 1970.0) + ((year - 1969.0) / 4.0).floor() - ((year - 1901.0) / 100.0).floor()
+        + ((year - 1601.0) / 400.0).floor()
+}
+
+fn time_from_year(year: f64) -> f64 {
+    MS_PER_DAY * day_from_year(year)
+}
+
+fn year_from_time(t: f64) -> f64 {
+    let mut year = (t / (MS_PER_DAY * 365.2425)).floor() + 1970.0;
+    while time_from_year(year) > t {
+        year -= 1.0;
+    }
+    while time_from_year(year + 1.0) <= t {
+        year += 1.0;
+    }
+    year
+}
+
+fn month_start(month: usize, leap_year: bool) -> f64 {
+    if leap_year && month >= 2 {
+        MONTH_OFFSETS[month] + 1.0
+    } else {
+        MONTH_OFFSETS[month]
+    }
+}
+
+/// Returns the year, month and day of the month of a time value.
+fn year_month_date(t: f64) -> (f64, f64, f64) {
+    let year = year_from_time(t);
+    let leap_year = is_leap_year(year);
+    let day_within_year = day(t) - day_from_year(year);
+    let month = (0..12)
+        .rev()
+        .find(|&month| month_start(month, leap_year) <= day_within_year)
+        .unwrap_or(0);
+    let date = day_within_year - month_start(month, leap_year) + 1.0;
+    (year, month as f64, date)
+}
+
+fn week_day(t: f64) -> f64 {
+    (day(t) + 4.0).rem_euclid(7.0)
+}
+
+/// Splits a time value into its year, month, date, hours, minutes, seconds
+/// and milliseconds.
+fn components(t: f64) -> [f64; 7] {
+    if t.is_nan() {
+        return [NAN; 7];
+    }
+    let (year, month, date) = year_month_date(t);
+    let time = time_within_day(t);
+    [
+        year,
+        month,
+        date,
+        (time / MS_PER_HOUR).floor(),
+        (time / MS_PER_MINUTE).floor() % 60.0,
+        (time / MS_PER_SECOND).floor() % 60.0,
+        time % MS_PER_SECOND,
+    ]
+}
+
+/// ECMA-262 3rd edition s. 15.9.1.11 MakeTime
+fn make_time(hours: f64, minutes: f64, seconds: f64, milliseconds: f64) -> f64 {
+    if !(hours.is_finite()
+        && minutes.is_finite()
+        && seconds.is_finite()
+        && milliseconds.is_finite())
+    {
+        return NAN;
+    }
+    hours.trunc() * MS_PER_HOUR
+        + minutes.trunc() * MS_PER_MINUTE
+        + seconds.trunc() * MS_PER_SECOND
+        + milliseconds.trunc()
+}
+
+/// ECMA-262 3rd edition s. 15.9.1.12 MakeDay
+fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    if !(year.is_finite() && month.is_finite() && date.is_finite()) {
+        return NAN;
+    }
+    let (year, month, date) = (year.trunc(), month.trunc(), date.trunc());
+    let year = year + (month / 12.0).floor();
+    if year.abs() > 400_000.0 {
+        return NAN;
+    }
+    let month = month.rem_euclid(12.0) as usize;
+    day_from_year(year) + month_start(month, is_leap_year(year)) + date - 1.0
+}
+
+/// ECMA-262 3rd edition s. 15.9.1.13 MakeDate
+fn make_date(day: f64, time: f64) -> f64 {
+    if !(day.is_finite() && time.is_finite()) {
+        return NAN;
+    }
+    day * MS_PER_DAY + time
+}
+
+/// ECMA-262 3rd edition s. 15.9.1.14 TimeClip
+fn time_clip(t: f64) -> f64 {
+    if !t.is_finite() || t.abs() > MAX_TIME {
+        return NAN;
+    }
+    t.trunc() + 0.0
+}
+
+/// Builds a time value out of all seven date components.
+fn from_components(c: [f64; 7]) -> f64 {
+    make_date(
+        make_day(c[YEAR], c[MONTH], c[DATE]),
+        make_time(c[HOURS], c[MINUTES], c[SECONDS], c[MILLISECONDS]),
+    )
+}
+
+/// Flash treats the years 0 to 99 as 1900 to 1999.
+fn full_year(year: f64) -> f64 {
+    let year = year.trunc();
+    if year >= 0.0 && year <= 99.0 {
+        1900.0 + year
+    } else {
+        year
+    }
+}
+
+/// The offset of local time from UTC at the given UTC time, in milliseconds.
+fn local_offset(context: &UpdateContext<'_, '_, '_>, t: f64) -> f64 {
+    if t.is_nan() {
+        return 0.0;
+    }
+    f64::from(context.locale.local_utc_offset(t)) * MS_PER_MINUTE
+}
+
+/// Converts a UTC time value to local time.
+fn local_time(context: &UpdateContext<'_, '_, '_>, t: f64) -> f64 {
+    t + local_offset(context, t)
+}
+
+/// Converts a local time value to UTC.
+fn utc(context: &UpdateContext<'_, '_, '_>, t: f64) -> f64 {
+    t - local_offset(context, t - local_offset(context, t))
+}
+
+fn date_time(this: Object<'_>) -> f64 {
+    this.as_date_object()
+        .map(|date| date.date_time())
+        .unwrap_or(NAN)
+}
+
+fn current_time(context: &UpdateContext<'_, '_, '_>) -> f64 {
+    time_clip(context.locale.current_time())
+}
+
+/// Formats a time value the way Flash does, e.g.
+/// `Sat Jan 1 00:00:00 GMT+0100 2000`.
+fn format_date(context: &UpdateContext<'_, '_, '_>, t: f64) -> String {
+    if t.is_nan() {
+        return "Invalid Date".to_string();
+    }
+    let offset = context.locale.local_utc_offset(t);
+    let local = t + f64::from(offset) * MS_PER_MINUTE;
+    let c = components(local);
+    format!(
+        "{} {} {} {:02}:{:02}:{:02} GMT{}{:02}{:02} {}",
+        WEEK_DAYS[week_day(local) as usize],
+        MONTHS[c[MONTH] as usize],
+        c[DATE],
+        c[HOURS],
+        c[MINUTES],
+        c[SECONDS],
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 60,
+        offset.abs() % 60,
+        c[YEAR],
+    )
+}
+
+/// Coerces the date components in `args` to numbers, defaulting missing
+/// components to `defaults`.
+fn coerce_components<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    defaults: [f64; 7],
+) -> Result<[f64; 7], Error<'gc>> {
+    let mut c = defaults;
+    for (component, arg) in c.iter_mut().zip(args) {
+        *component = arg.coerce_to_f64(activation, context)?;
+    }
+    c[YEAR] = full_year(c[YEAR]);
+    Ok(c)
+}
+
+/// `Date` constructor/function
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = match this.as_date_object() {
+        Some(date) => date,
+        // If Date is called as a function, return the current date as a string.
+        None => {
+            let now = current_time(context);
+            return Ok(AvmString::new(context.gc_context, format_date(context, now)).into());
+        }
+    };
+
+    let date_time = match args {
+        [] => current_time(context),
+        [time] => time_clip(time.coerce_to_f64(activation, context)?),
+        _ => {
+            let c = coerce_components(
+                activation,
+                context,
+                args,
+                [NAN, NAN, 1.0, 0.0, 0.0, 0.0, 0.0],
+            )?;
+            time_clip(utc(context, from_components(c)))
+        }
+    };
+    date.set_date_time(context.gc_context, date_time);
+
+    Ok(this.into())
+}
+
+/// Implements `Date.UTC`
+fn utc_static<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let c = coerce_components(
+        activation,
+        context,
+        args,
+        [NAN, NAN, 1.0, 0.0, 0.0, 0.0, 0.0],
+    )?;
+    Ok(time_clip(from_components(c)).into())
+}
+
+/// Replaces up to `max_args` date components, starting at `first`, and
+/// stores the resulting time value.
+fn set_components<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    is_local: bool,
+    first: usize,
+    max_args: usize,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = match this.as_date_object() {
+        Some(date) => date,
+        None => return Ok(NAN.into()),
+    };
+
+    let mut t = date.date_time();
+    if is_local {
+        t = local_time(context, t);
+    }
+    // Setting the year of an invalid date starts from the epoch.
+    if first == YEAR && t.is_nan() {
+        t = 0.0;
+    }
+
+    let mut c = components(t);
+    for i in 0..max_args {
+        let value = match args.get(i) {
+            Some(arg) => arg.coerce_to_f64(activation, context)?,
+            // The first component is mandatory; the rest keep their value.
+            None if i == 0 => Value::Undefined.coerce_to_f64(activation, context)?,
+            None => break,
+        };
+        c[first + i] = value;
+    }
+
+    let mut t = from_components(c);
+    if is_local {
+        t = utc(context, t);
+    }
+    let t = time_clip(t);
+    date.set_date_time(context.gc_context, t);
+    Ok(t.into())
+}
+
+macro_rules! date_getters {
+    ( $object: ident, $gc_context: ident, $proto: ident, $($name: expr => ($is_local: expr, $get: expr)),* ) => {{
+        $(
+            $object.force_set_function(
+                $name,
+                |_activation, context, this, _args| -> Result<Value<'gc>, Error<'gc>> {
+                    let t = date_time(this);
+                    if t.is_nan() {
+                        return Ok(NAN.into());
+                    }
+                    let t = if $is_local { local_time(context, t) } else { t };
+                    let get: fn(f64) -> f64 = $get;
+                    Ok(get(t).into())
+                },
+                $gc_context,
+                DontDelete | ReadOnly | DontEnum,
+                $proto
+            );
+        )*
+    }};
+}
+
+macro_rules! date_setters {
+    ( $object: ident, $gc_context: ident, $proto: ident, $($name: expr => ($is_local: expr, $first: expr, $max_args: expr)),* ) => {{
+        $(
+            $object.force_set_function(
+                $name,
+                |activation, context, this, args| -> Result<Value<'gc>, Error<'gc>> {
+                    set_components(activation, context, this, args, $is_local, $first, $max_args)
+                },
+                $gc_context,
+                DontDelete | ReadOnly | DontEnum,
+                $proto
+            );
+        )*
+    }};
+}
+
+fn get_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(date_time(this).into())
+}
+
+fn set_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = match this.as_date_object() {
+        Some(date) => date,
+        None => return Ok(NAN.into()),
+    };
+    let t = time_clip(
+        args.get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_f64(activation, context)?,
+    );
+    date.set_date_time(context.gc_context, t);
+    Ok(t.into())
+}
+
+fn set_year<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let year = full_year(
+        args.get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_f64(activation, context)?,
+    );
+    set_components(activation, context, this, &[year.into()], true, YEAR, 1)
+}
+
+fn get_timezone_offset<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let t = date_time(this);
+    if t.is_nan() {
+        return Ok(NAN.into());
+    }
+    // The offset is reported in minutes *west* of UTC.
+    Ok((-context.locale.local_utc_offset(t)).into())
+}
+
+fn to_string<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_string = format_date(context, date_time(this));
+    Ok(AvmString::new(context.gc_context, date_string).into())
+}
+
+pub fn create_date_object<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    date_proto: Option<Object<'gc>>,
+    fn_proto: Option<Object<'gc>>,
+) -> Object<'gc> {
+    let date = FunctionObject::function(
+        gc_context,
+        Executable::Native(constructor),
+        fn_proto,
+        date_proto,
+    );
+    let mut object = date.as_script_object().unwrap();
+
+    object.force_set_function(
+        "UTC",
+        utc_static,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    date
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let date = DateObject::empty_date_object(gc_context, Some(proto));
+    let mut object = date.as_script_object().unwrap();
+    let fn_proto = Some(fn_proto);
+
+    date_getters!(object, gc_context, fn_proto,
+        "getFullYear" => (true, |t| year_from_time(t)),
+        "getYear" => (true, |t| year_from_time(t) - 1900.0),
+        "getMonth" => (true, |t| year_month_date(t).1),
+        "getDate" => (true, |t| year_month_date(t).2),
+        "getDay" => (true, week_day),
+        "getHours" => (true, |t| components(t)[HOURS]),
+        "getMinutes" => (true, |t| components(t)[MINUTES]),
+        "getSeconds" => (true, |t| components(t)[SECONDS]),
+        "getMilliseconds" => (true, |t| components(t)[MILLISECONDS]),
+        "getUTCFullYear" => (false, |t| year_from_time(t)),
+        "getUTCYear" => (false, |t| year_from_time(t) - 1900.0),
+        "getUTCMonth" => (false, |t| year_month_date(t).1),
+        "getUTCDate" => (false, |t| year_month_date(t).2),
+        "getUTCDay" => (false, week_day),
+        "getUTCHours" => (false, |t| components(t)[HOURS]),
+        "getUTCMinutes" => (false, |t| components(t)[MINUTES]),
+        "getUTCSeconds" => (false, |t| components(t)[SECONDS]),
+        "getUTCMilliseconds" => (false, |t| components(t)[MILLISECONDS])
+    );
+
+    date_setters!(object, gc_context, fn_proto,
+        "setFullYear" => (true, YEAR, 3),
+        "setMonth" => (true, MONTH, 2),
+        "setDate" => (true, DATE, 1),
+        "setHours" => (true, HOURS, 4),
+        "setMinutes" => (true, MINUTES, 3),
+        "setSeconds" => (true, SECONDS, 2),
+        "setMilliseconds" => (true, MILLISECONDS, 1),
+        "setUTCFullYear" => (false, YEAR, 3),
+        "setUTCMonth" => (false, MONTH, 2),
+        "setUTCDate" => (false, DATE, 1),
+        "setUTCHours" => (false, HOURS, 4),
+        "setUTCMinutes" => (false, MINUTES, 3),
+        "setUTCSeconds" => (false, SECONDS, 2),
+        "setUTCMilliseconds" => (false, MILLISECONDS, 1)
+    );
+
+    object.force_set_function(
+        "getTime",
+        get_time,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "setTime",
+        set_time,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "setYear",
+        set_year,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "getTimezoneOffset",
+        get_timezone_offset,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "toString",
+        to_string,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "valueOf",
+        get_time,
+        gc_context,
+        DontDelete | ReadOnly | DontEnum,
+        fn_proto,
+    );
+
+    date.into()
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    fn setup<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Object<'gc> {
+        create_date_object(
+            context.gc_context,
+            Some(activation.avm.prototypes().date),
+            Some(activation.avm.prototypes().function),
+        )
+    }
+
+    test_method!(test_utc, "UTC", setup,
+        [19] => {
+            [] => NAN,
+            [2000.0] => NAN,
+            [1970.0, 0.0] => 0.0,
+            [2000.0, 0.0] => 946_684_800_000.0,
+            [2000.0, 1.0, 29.0, 12.0, 30.0, 15.0, 500.0] => 951_827_415_500.0,
+            [99.0, 11.0, 31.0] => 946_598_400_000.0,
+            [2001.0, -1.0] => 975_628_800_000.0,
+            [2000.0, 12.0] => 978_307_200_000.0,
+            [1969.0, 11.0, 31.0, 23.0] => -3_600_000.0
+        }
+    );
+
+    #[test]
+    fn test_components() {
+        assert_eq!(components(0.0), [1970.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(
+            components(951_827_415_500.0),
+            [2000.0, 1.0, 29.0, 12.0, 30.0, 15.0, 500.0]
+        );
+        assert_eq!(
+            components(-1.0),
+            [1969.0, 11.0, 31.0, 23.0, 59.0, 59.0, 999.0]
+        );
+        assert_eq!(week_day(0.0), 4.0);
+        assert_eq!(week_day(-MS_PER_DAY), 3.0);
+    }
+
+    #[test]
+    fn test_time_clip() {
+        assert_eq!(time_clip(MAX_TIME), MAX_TIME);
+        assert!(time_clip(MAX_TIME + 1.0).is_nan());
+        assert!(time_clip(std::f64::INFINITY).is_nan());
+        assert_eq!(time_clip(-1.5), -1.0);
+    }
+}
@@ -3,9 +3,12 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::Executable;
+use crate::avm1::globals::bitmap_filter;
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{Object, ScriptObject, TObject, UpdateContext, Value};
-use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::display_object::{
+    blend_mode_from_index, blend_mode_from_name, blend_mode_name, DisplayObject, TDisplayObject,
+};
 use enumset::EnumSet;
 use gc_arena::MutationContext;
 
@@ -76,6 +79,22 @@ pub fn define_display_object_proto<'gc>(
         Some(Executable::Native(overwrite_parent)),
         DontDelete | ReadOnly | DontEnum,
     );
+
+    object.add_property(
+        gc_context,
+        "filters",
+        Executable::Native(get_filters),
+        Some(Executable::Native(set_filters)),
+        DontDelete | DontEnum,
+    );
+
+    object.add_property(
+        gc_context,
+        "blendMode",
+        Executable::Native(get_blend_mode),
+        Some(Executable::Native(set_blend_mode)),
+        DontDelete | DontEnum,
+    );
 }
 
 pub fn get_parent<'gc>(
@@ -92,6 +111,91 @@ pub fn get_parent<'gc>(
         .unwrap_or(Value::Undefined))
 }
 
+/// Get a copy of the filters of a display object.
+///
+/// Modifying the returned filters has no effect until they are assigned back
+/// to `filters`.
+pub fn get_filters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let filters = display_object.filters().to_vec();
+        let array = ScriptObject::array(context.gc_context, Some(activation.avm.prototypes.array));
+        for (i, filter) in filters.iter().enumerate() {
+            let filter = bitmap_filter::filter_to_object(activation, context, filter);
+            array.set_array_element(i, filter.into(), context.gc_context);
+        }
+        return Ok(array.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Replace the filters of a display object with copies of the filters in an
+/// array. Elements that are not filters are ignored.
+pub fn set_filters<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut display_object) = this.as_display_object() {
+        let filters = match args.get(0) {
+            Some(Value::Object(array)) => array
+                .array()
+                .iter()
+                .filter_map(|value| match value {
+                    Value::Object(object) => object.as_filter_object(),
+                    _ => None,
+                })
+                .map(|filter| filter.filter().clone())
+                .collect(),
+            _ => Vec::new(),
+        };
+        display_object.set_filters(context.gc_context, filters);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_blend_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        return Ok(blend_mode_name(display_object.blend_mode()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Set the blend mode of a display object, either by name or by its numeric
+/// value. Unknown blend modes are ignored.
+pub fn set_blend_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut display_object) = this.as_display_object() {
+        let blend_mode = match args.get(0) {
+            Some(Value::String(name)) => blend_mode_from_name(name),
+            Some(Value::Number(index)) => blend_mode_from_index(*index as i32),
+            _ => None,
+        };
+        if let Some(blend_mode) = blend_mode {
+            display_object.set_blend_mode(context.gc_context, blend_mode);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_depth<'gc>(
     display_object: DisplayObject<'gc>,
     activation: &mut Activation<'_, 'gc>,
@@ -0,0 +1,129 @@
+//! flash.filters.DropShadowFilter object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::bitmap_filter::{self, add_property};
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::{Object, Value};
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use swf::{Color, DropShadowFilter, Filter};
+
+/// The `hideObject` argument is not supported, as it cannot be stored in a
+/// SWF filter.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bitmap_filter::apply_args(
+        activation,
+        context,
+        this,
+        args,
+        &[
+            "distance", "angle", "color", "alpha", "blurX", "blurY", "strength", "quality",
+            "inner", "knockout",
+        ],
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let filter = Filter::DropShadowFilter(Box::new(DropShadowFilter {
+        color: Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        },
+        blur_x: 4.0,
+        blur_y: 4.0,
+        angle: 45f64.to_radians(),
+        distance: 4.0,
+        strength: 1.0,
+        is_inner: false,
+        is_knockout: false,
+        num_passes: 1,
+    }));
+    let object: Object<'gc> = FilterObject::new(gc_context, Some(proto), filter).into();
+
+    add_property(
+        gc_context,
+        object,
+        "distance",
+        bitmap_filter::get_distance,
+        bitmap_filter::set_distance,
+    );
+    add_property(
+        gc_context,
+        object,
+        "angle",
+        bitmap_filter::get_angle,
+        bitmap_filter::set_angle,
+    );
+    add_property(
+        gc_context,
+        object,
+        "color",
+        bitmap_filter::get_color,
+        bitmap_filter::set_color,
+    );
+    add_property(
+        gc_context,
+        object,
+        "alpha",
+        bitmap_filter::get_alpha,
+        bitmap_filter::set_alpha,
+    );
+    add_property(
+        gc_context,
+        object,
+        "blurX",
+        bitmap_filter::get_blur_x,
+        bitmap_filter::set_blur_x,
+    );
+    add_property(
+        gc_context,
+        object,
+        "blurY",
+        bitmap_filter::get_blur_y,
+        bitmap_filter::set_blur_y,
+    );
+    add_property(
+        gc_context,
+        object,
+        "strength",
+        bitmap_filter::get_strength,
+        bitmap_filter::set_strength,
+    );
+    add_property(
+        gc_context,
+        object,
+        "quality",
+        bitmap_filter::get_quality,
+        bitmap_filter::set_quality,
+    );
+    add_property(
+        gc_context,
+        object,
+        "inner",
+        bitmap_filter::get_inner,
+        bitmap_filter::set_inner,
+    );
+    add_property(
+        gc_context,
+        object,
+        "knockout",
+        bitmap_filter::get_knockout,
+        bitmap_filter::set_knockout,
+    );
+
+    object
+}
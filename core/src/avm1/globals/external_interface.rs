@@ -0,0 +1,113 @@
+//! `flash.external.ExternalInterface` object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::Executable;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, UpdateContext, Value};
+use crate::display_object::TDisplayObject;
+use crate::external::{Callback, Value as ExternalValue};
+use gc_arena::MutationContext;
+
+pub fn available<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(context.external_interface.available().into())
+}
+
+pub fn add_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if args.len() < 3 || !context.external_interface.available() {
+        return Ok(false.into());
+    }
+
+    let name = args[0].coerce_to_string(activation, context)?.to_string();
+    let method = match &args[2] {
+        Value::Object(method) if method.as_executable().is_some() => *method,
+        _ => return Ok(false.into()),
+    };
+
+    context.external_interface.add_callback(
+        name,
+        Callback::Avm1 {
+            this: args[1].clone(),
+            method,
+        },
+    );
+    Ok(true.into())
+}
+
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(name) => name.coerce_to_string(activation, context)?.to_string(),
+        None => return Ok(Value::Null),
+    };
+
+    let movie = activation
+        .base_clip()
+        .movie()
+        .unwrap_or_else(|| context.swf.clone());
+    if !context.system.security.can_call_host(&movie) {
+        log::warn!(
+            "ExternalInterface.call({}) denied by the security sandbox",
+            name
+        );
+        return Ok(Value::Null);
+    }
+
+    let mut external_args = Vec::with_capacity(args.len() - 1);
+    for arg in &args[1..] {
+        external_args.push(ExternalValue::from_avm1(activation, context, arg.clone())?);
+    }
+
+    let result = context
+        .external_interface
+        .call_method(&name, &external_args);
+    Ok(result.into_avm1(activation, context))
+}
+
+pub fn create_external_interface_object<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Option<Object<'gc>>,
+    fn_proto: Option<Object<'gc>>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, proto);
+
+    object.add_property(
+        gc_context,
+        "available",
+        Executable::Native(available),
+        None,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+    );
+
+    object.force_set_function(
+        "addCallback",
+        add_callback,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "call",
+        call,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.into()
+}
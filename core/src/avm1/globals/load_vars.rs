@@ -0,0 +1,480 @@
+//! `LoadVars` class impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, UpdateContext, Value};
+use crate::backend::navigator::{NavigationMethod, RequestOptions};
+use crate::security::{self, LoadKind};
+use gc_arena::MutationContext;
+use std::collections::HashMap;
+use url::form_urlencoded;
+
+/// The default `contentType` of `LoadVars` requests.
+const DEFAULT_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Request headers that movies are not permitted to set with
+/// `addRequestHeader`.
+const DISALLOWED_HEADERS: &[&str] = &[
+    "accept-charset",
+    "accept-encoding",
+    "accept-ranges",
+    "age",
+    "allow",
+    "allowed",
+    "authorization",
+    "charge-to",
+    "connect",
+    "connection",
+    "content-length",
+    "content-location",
+    "content-range",
+    "cookie",
+    "date",
+    "delete",
+    "etag",
+    "expect",
+    "get",
+    "head",
+    "host",
+    "if-modified-since",
+    "keep-alive",
+    "last-modified",
+    "location",
+    "max-forwards",
+    "options",
+    "origin",
+    "post",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "public",
+    "put",
+    "range",
+    "referer",
+    "request-range",
+    "retry-after",
+    "server",
+    "te",
+    "trace",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "uri",
+    "user-agent",
+    "vary",
+    "via",
+    "warning",
+    "www-authenticate",
+    "x-flash-version",
+];
+
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Record the size of a finished load on a `LoadVars` object, for
+/// `getBytesLoaded` and `getBytesTotal`.
+pub fn set_bytes_loaded<'gc>(
+    object: Object<'gc>,
+    length: usize,
+    gc_context: MutationContext<'gc, '_>,
+) {
+    for name in &["_bytesLoaded", "_bytesTotal"] {
+        object.define_value(gc_context, name, length.into(), Attribute::DontEnum.into());
+    }
+}
+
+/// URL-encode a string the way `escape` does, keeping only ASCII
+/// alphanumerics.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    escaped
+}
+
+/// Read the enumerable properties of an object as variables to send.
+fn object_to_vars<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let mut vars = Vec::new();
+    for name in object.get_keys(activation) {
+        let value = object
+            .get(&name, activation, context)?
+            .coerce_to_string(activation, context)?
+            .to_string();
+        vars.push((name, value));
+    }
+    Ok(vars)
+}
+
+/// Encode the enumerable properties of an object, as `toString` does.
+fn encode_vars<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<String, Error<'gc>> {
+    Ok(object_to_vars(object, activation, context)?
+        .iter()
+        .map(|(name, value)| format!("{}={}", escape(name), escape(value)))
+        .collect::<Vec<_>>()
+        .join("&"))
+}
+
+/// Read the HTTP method argument of `send` and `sendAndLoad`, which
+/// defaults to POST.
+fn method_arg<'gc>(
+    value: Option<&Value<'gc>>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<NavigationMethod, Error<'gc>> {
+    Ok(match value {
+        Some(Value::Undefined) | None => NavigationMethod::POST,
+        Some(method) => {
+            let method = method.coerce_to_string(activation, context)?;
+            NavigationMethod::from_method_str(&method.to_ascii_uppercase())
+                .unwrap_or(NavigationMethod::POST)
+        }
+    })
+}
+
+/// Read the headers added to an object with `addRequestHeader`.
+fn custom_headers<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let mut headers = Vec::new();
+    if let Value::Object(list) = object.get("_customHeaders", activation, context)? {
+        let list = list.array();
+        for pair in list.chunks_exact(2) {
+            headers.push((
+                pair[0].coerce_to_string(activation, context)?.to_string(),
+                pair[1].coerce_to_string(activation, context)?.to_string(),
+            ));
+        }
+    }
+    Ok(headers)
+}
+
+/// Start loading a URL into a `LoadVars` object.
+fn spawn_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    target: Object<'gc>,
+    url: &str,
+    options: RequestOptions,
+) -> Result<(), Error<'gc>> {
+    target.set("loaded", false.into(), activation, context)?;
+
+    let fetch = security::fetch(activation, context, url, options, LoadKind::Data);
+    let target_clip = activation.target_clip_or_root();
+    let process = context.load_manager.load_vars_into_object(
+        context.player.clone().unwrap(),
+        target,
+        target_clip,
+        fetch,
+    );
+    context.navigator.spawn_future(process);
+
+    Ok(())
+}
+
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => return Ok(false.into()),
+        Some(url) => url.coerce_to_string(activation, context)?,
+    };
+
+    spawn_load(activation, context, this, &url, RequestOptions::get())?;
+
+    Ok(true.into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => return Ok(false.into()),
+        Some(url) => url.coerce_to_string(activation, context)?,
+    };
+    let window = match args.get(1) {
+        Some(Value::Undefined) | None => None,
+        Some(window) => Some(window.coerce_to_string(activation, context)?.to_string()),
+    };
+    let method = method_arg(args.get(2), activation, context)?;
+    let vars: HashMap<String, String> = object_to_vars(this, activation, context)?
+        .into_iter()
+        .collect();
+
+    context.navigator.navigate_to_url(
+        security::loaded_movie_url(activation, &url),
+        window,
+        Some((method, vars)),
+    );
+
+    Ok(true.into())
+}
+
+pub fn send_and_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => return Ok(false.into()),
+        Some(url) => url.coerce_to_string(activation, context)?,
+    };
+    let target = match args.get(1) {
+        Some(Value::Object(target)) => *target,
+        _ => return Ok(false.into()),
+    };
+    let method = method_arg(args.get(2), activation, context)?;
+    let query = encode_vars(this, activation, context)?;
+
+    let (url, options) = match method {
+        NavigationMethod::GET => {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            (
+                format!("{}{}{}", url, separator, query),
+                RequestOptions::get(),
+            )
+        }
+        NavigationMethod::POST => {
+            let content_type = match this.get("contentType", activation, context)? {
+                Value::Undefined | Value::Null => DEFAULT_CONTENT_TYPE.to_string(),
+                content_type => content_type
+                    .coerce_to_string(activation, context)?
+                    .to_string(),
+            };
+            let headers = custom_headers(this, activation, context)?;
+            (
+                url.to_string(),
+                RequestOptions::post(Some((query.into_bytes(), content_type)))
+                    .with_headers(headers),
+            )
+        }
+    };
+
+    spawn_load(activation, context, target, &url, options)?;
+
+    Ok(true.into())
+}
+
+pub fn decode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(src) = args.get(0) {
+        let src = src.coerce_to_string(activation, context)?;
+        for (name, value) in form_urlencoded::parse(src.as_bytes()) {
+            this.set(
+                &name,
+                AvmString::new(context.gc_context, value.into_owned()).into(),
+                activation,
+                context,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let vars = encode_vars(this, activation, context)?;
+    Ok(AvmString::new(context.gc_context, vars).into())
+}
+
+pub fn get_bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.get("_bytesLoaded", activation, context)
+}
+
+pub fn get_bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.get("_bytesTotal", activation, context)
+}
+
+pub fn add_request_header<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Headers are either given as a name and a value, or as an array of
+    // alternating names and values.
+    let values = match args {
+        [Value::Object(list), ..] => list.array(),
+        [name, value, ..] => vec![name.clone(), value.clone()],
+        _ => return Ok(Value::Undefined),
+    };
+
+    let list = match this.get("_customHeaders", activation, context)? {
+        Value::Object(list) => list,
+        _ => {
+            let list: Object<'gc> =
+                ScriptObject::array(context.gc_context, Some(activation.avm.prototypes.array))
+                    .into();
+            this.define_value(
+                context.gc_context,
+                "_customHeaders",
+                list.into(),
+                Attribute::DontEnum.into(),
+            );
+            list
+        }
+    };
+
+    for pair in values.chunks_exact(2) {
+        let name = pair[0].coerce_to_string(activation, context)?;
+        if DISALLOWED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            log::warn!("LoadVars.addRequestHeader: header {} is not allowed", name);
+            continue;
+        }
+
+        let value = pair[1].coerce_to_string(activation, context)?;
+        let length = list.length();
+        list.set_array_element(length, name.into(), context.gc_context);
+        list.set_array_element(length + 1, value.into(), context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match args.get(0) {
+        Some(Value::Undefined) | None => {
+            this.call_method("onLoad", &[false.into()], activation, context)?;
+        }
+        Some(src) => {
+            this.call_method("decode", &[src.clone()], activation, context)?;
+            this.set("loaded", true.into(), activation, context)?;
+            this.call_method("onLoad", &[true.into()], activation, context)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "load",
+        load,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "sendAndLoad",
+        send_and_load,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "decode",
+        decode,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "toString",
+        to_string,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getBytesLoaded",
+        get_bytes_loaded,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getBytesTotal",
+        get_bytes_total,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "addRequestHeader",
+        add_request_header,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "onData",
+        on_data,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+
+    object.define_value(
+        gc_context,
+        "contentType",
+        DEFAULT_CONTENT_TYPE.into(),
+        Attribute::DontEnum.into(),
+    );
+
+    object.into()
+}
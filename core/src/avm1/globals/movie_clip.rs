@@ -3,12 +3,13 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::display_object::{self, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
-use crate::avm1::globals::matrix::gradient_object_to_matrix;
+use crate::avm1::globals::matrix::{gradient_object_to_matrix, object_to_matrix};
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, UpdateContext, Value};
 use crate::backend::navigator::NavigationMethod;
-use crate::display_object::{DisplayObject, EditText, MovieClip, TDisplayObject};
+use crate::display_object::{Bitmap, DisplayObject, EditText, MovieClip, TDisplayObject};
 use crate::prelude::*;
+use crate::security::{self, LoadKind};
 use crate::shape_utils::DrawCommand;
 use crate::tag_utils::SwfSlice;
 use gc_arena::MutationContext;
@@ -104,6 +105,7 @@ pub fn create_proto<'gc>(
         gc_context,
         object,
         Some(fn_proto),
+        "attachBitmap" => attach_bitmap,
         "attachMovie" => attach_movie,
         "createEmptyMovieClip" => create_empty_movie_clip,
         "createTextField" => create_text_field,
@@ -133,6 +135,7 @@ pub fn create_proto<'gc>(
         "unloadMovie" => unload_movie,
         "beginFill" => begin_fill,
         "beginGradientFill" => begin_gradient_fill,
+        "beginBitmapFill" => begin_bitmap_fill,
         "moveTo" => move_to,
         "lineTo" => line_to,
         "curveTo" => curve_to,
@@ -353,6 +356,38 @@ fn begin_gradient_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+fn begin_bitmap_fill<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bitmap_data = match args.get(0) {
+        Some(Value::Object(bitmap_data)) => bitmap_data
+            .as_bitmap_data_object()
+            .map(|object| object.bitmap_data()),
+        _ => None,
+    };
+    if let Some(bitmap_data) = bitmap_data {
+        let mut matrix = match args.get(1) {
+            Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation, context)?,
+            _ => Default::default(),
+        };
+        // Bitmap fill matrices map bitmap pixels to twips.
+        matrix.a *= 20.0;
+        matrix.b *= 20.0;
+        matrix.c *= 20.0;
+        matrix.d *= 20.0;
+        let swf_version = activation.current_swf_version();
+        let is_repeating = args.get(2).map(|v| v.as_bool(swf_version)).unwrap_or(true);
+        let is_smoothed = args.get(3).map(|v| v.as_bool(swf_version)).unwrap_or(false);
+        movie_clip.set_bitmap_fill_style(context, bitmap_data, matrix, is_smoothed, is_repeating);
+    } else {
+        movie_clip.set_fill_style(context, None);
+    }
+    Ok(Value::Undefined)
+}
+
 fn move_to<'gc>(
     movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc>,
@@ -493,6 +528,37 @@ fn attach_movie<'gc>(
     }
 }
 
+fn attach_bitmap<'gc>(
+    mut movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bitmap_data = match args.get(0) {
+        Some(Value::Object(bitmap_data)) => bitmap_data
+            .as_bitmap_data_object()
+            .map(|object| object.bitmap_data()),
+        _ => None,
+    };
+    let bitmap_data = match bitmap_data {
+        Some(bitmap_data) => bitmap_data,
+        None => return Ok(Value::Undefined),
+    };
+    let depth = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation, context)?
+        .wrapping_add(AVM_DEPTH_BIAS);
+    if depth < 0 || depth > AVM_MAX_DEPTH {
+        return Ok(Value::Undefined);
+    }
+
+    // TODO: Support `pixelSnapping` and `smoothing`.
+    let bitmap = Bitmap::new_with_bitmap_data(context, bitmap_data);
+    movie_clip.add_child_from_avm(context, bitmap.into(), depth);
+    Ok(Value::Undefined)
+}
+
 fn create_empty_movie_clip<'gc>(
     mut movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc>,
@@ -1028,7 +1094,11 @@ pub fn get_url<'a, 'gc>(
     if let Some(url_val) = args.get(0) {
         let url = url_val.coerce_to_string(activation, context)?;
         if let Some(fscommand) = fscommand::parse(&url) {
-            fscommand::handle(fscommand, activation, context);
+            let args = match args.get(1) {
+                Some(args) => args.coerce_to_string(activation, context)?.to_string(),
+                None => String::new(),
+            };
+            fscommand::handle(fscommand, &args, activation, context);
             return Ok(Value::Undefined);
         }
 
@@ -1091,11 +1161,12 @@ fn load_movie<'gc>(
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation, context)?);
     let (url, opts) = activation.locals_into_request_options(context, Cow::Borrowed(&url), method);
-    let fetch = context.navigator.fetch(&url, opts);
+    let fetch = security::fetch(activation, context, &url, opts, LoadKind::Movie);
     let process = context.load_manager.load_movie_into_clip(
         context.player.clone().unwrap(),
         DisplayObject::MovieClip(target),
         fetch,
+        security::loaded_movie_url(activation, &url),
         None,
     );
 
@@ -1115,7 +1186,7 @@ fn load_variables<'gc>(
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation, context)?);
     let (url, opts) = activation.locals_into_request_options(context, Cow::Borrowed(&url), method);
-    let fetch = context.navigator.fetch(&url, opts);
+    let fetch = security::fetch(activation, context, &url, opts, LoadKind::Data);
     let target = target.object().coerce_to_object(activation, context);
     let process =
         context
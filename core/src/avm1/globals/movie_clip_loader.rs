@@ -8,6 +8,7 @@ use crate::avm1::property::Attribute;
 use crate::avm1::{Object, UpdateContext, Value};
 use crate::backend::navigator::RequestOptions;
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::security::{self, LoadKind};
 use enumset::EnumSet;
 use gc_arena::MutationContext;
 
@@ -130,11 +131,18 @@ pub fn load_clip<'gc>(
             .as_display_object()
             .and_then(|dobj| dobj.as_movie_clip())
         {
-            let fetch = context.navigator.fetch(&url, RequestOptions::get());
+            let fetch = security::fetch(
+                activation,
+                context,
+                &url,
+                RequestOptions::get(),
+                LoadKind::Movie,
+            );
             let process = context.load_manager.load_movie_into_clip(
                 context.player.clone().unwrap(),
                 DisplayObject::MovieClip(movieclip),
                 fetch,
+                security::loaded_movie_url(activation, &url),
                 Some(this),
             );
 
@@ -11,7 +11,7 @@ use enumset::EnumSet;
 use gc_arena::MutationContext;
 use std::f64::NAN;
 
-fn constructor<'gc>(
+pub fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc>,
     context: &mut UpdateContext<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -0,0 +1,197 @@
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::listeners::Listeners;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, UpdateContext, Value};
+use crate::display_object::{EditText, TDisplayObject, TextSelection};
+use crate::string_utils::{byte_offset_to_utf16_index, utf16_index_to_byte_offset};
+use gc_arena::MutationContext;
+
+pub fn get_begin_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((text, selection)) = focused_text_field(context)
+        .and_then(|text_field| Some((text_field.text(), text_field.selection()?)))
+    {
+        Ok(byte_offset_to_utf16_index(&text, selection.start()).into())
+    } else {
+        Ok((-1).into())
+    }
+}
+
+pub fn get_end_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((text, selection)) = focused_text_field(context)
+        .and_then(|text_field| Some((text_field.text(), text_field.selection()?)))
+    {
+        Ok(byte_offset_to_utf16_index(&text, selection.end()).into())
+    } else {
+        Ok((-1).into())
+    }
+}
+
+pub fn get_caret_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((text, selection)) = focused_text_field(context)
+        .and_then(|text_field| Some((text_field.text(), text_field.selection()?)))
+    {
+        Ok(byte_offset_to_utf16_index(&text, selection.to()).into())
+    } else {
+        Ok((-1).into())
+    }
+}
+
+pub fn set_selection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text_field) = focused_text_field(context) {
+        // Flash indices count UTF-16 code units.
+        let text = text_field.text();
+        let begin = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation, context)?
+            .max(0) as usize;
+        let end = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation, context)?
+            .max(0) as usize;
+        text_field.set_selection(
+            Some(TextSelection::for_range(
+                utf16_index_to_byte_offset(&text, begin),
+                utf16_index_to_byte_offset(&text, end),
+            )),
+            context.gc_context,
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_focus<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match context.focus_tracker.get() {
+        Some(focus) if !focus.removed() => {
+            Ok(AvmString::new(context.gc_context, focus.path()).into())
+        }
+        _ => Ok(Value::Null),
+    }
+}
+
+pub fn set_focus<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let focus_tracker = context.focus_tracker;
+    match args.get(0) {
+        None => Ok(false.into()),
+        Some(Value::Undefined) | Some(Value::Null) => {
+            focus_tracker.set(None, context);
+            Ok(true.into())
+        }
+        Some(target) => {
+            let start_clip = activation.target_clip_or_root();
+            let object =
+                activation.resolve_target_display_object(context, start_clip, target.clone())?;
+            match object {
+                Some(object) if object.is_focusable() => {
+                    focus_tracker.set(Some(object), context);
+                    focus_tracker.set_highlighted(context.gc_context, true);
+                    Ok(true.into())
+                }
+                _ => Ok(false.into()),
+            }
+        }
+    }
+}
+
+/// Returns the text field that has focus, if any.
+fn focused_text_field<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) -> Option<EditText<'gc>> {
+    context
+        .focus_tracker
+        .get()
+        .and_then(|focus| focus.as_edit_text())
+}
+
+pub fn create_selection_object<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Option<Object<'gc>>,
+    fn_proto: Option<Object<'gc>>,
+    listener: &Listeners<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, proto);
+
+    register_listener!(gc_context, object, listener, fn_proto, selection);
+
+    object.force_set_function(
+        "getBeginIndex",
+        get_begin_index,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "getEndIndex",
+        get_end_index,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "getCaretIndex",
+        get_caret_index,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "setSelection",
+        set_selection,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "getFocus",
+        get_focus,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.force_set_function(
+        "setFocus",
+        set_focus,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    object.into()
+}
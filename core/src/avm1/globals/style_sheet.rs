@@ -0,0 +1,294 @@
+//! `TextField.StyleSheet` class impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, UpdateContext, Value};
+use crate::backend::navigator::RequestOptions;
+use crate::html::{Style, TextFormat};
+use crate::security::{self, LoadKind};
+use enumset::EnumSet;
+use gc_arena::MutationContext;
+
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+/// Read the properties of an AVM1 object into a CSS style.
+fn style_from_object<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<Style, Error<'gc>> {
+    let mut style = Style::new();
+    for name in object.get_keys(activation) {
+        let value = object
+            .get(&name, activation, context)?
+            .coerce_to_string(activation, context)?
+            .to_string();
+        style.insert(name, value);
+    }
+    Ok(style)
+}
+
+pub fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let css = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        return Ok(style_sheet
+            .style_sheet_mut(context.gc_context)
+            .parse_css(&css)
+            .into());
+    }
+
+    Ok(false.into())
+}
+
+pub fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        let style = style_sheet.style_sheet().style(&name).cloned();
+
+        if let Some(style) = style {
+            // The returned object is a copy; changing it doesn't affect the style sheet.
+            let object =
+                ScriptObject::object(context.gc_context, Some(activation.avm.prototypes.object));
+            for (name, value) in style {
+                object.define_value(
+                    context.gc_context,
+                    &name,
+                    AvmString::new(context.gc_context, value).into(),
+                    EnumSet::empty(),
+                );
+            }
+            return Ok(object.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+pub fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        let style = match args.get(1) {
+            Some(Value::Object(object)) => Some(style_from_object(*object, activation, context)?),
+            _ => None,
+        };
+        style_sheet
+            .style_sheet_mut(context.gc_context)
+            .set_style(&name, style);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let array = ScriptObject::array(context.gc_context, Some(activation.avm.prototypes.array));
+
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        let names: Vec<String> = style_sheet
+            .style_sheet()
+            .style_names()
+            .map(str::to_string)
+            .collect();
+        for (i, name) in names.into_iter().enumerate() {
+            array.set_array_element(
+                i,
+                AvmString::new(context.gc_context, name).into(),
+                context.gc_context,
+            );
+        }
+    }
+
+    Ok(array.into())
+}
+
+pub fn clear<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.as_style_sheet_object() {
+        style_sheet.style_sheet_mut(context.gc_context).clear();
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match args.get(0) {
+        Some(Value::Object(object)) => {
+            let style = style_from_object(*object, activation, context)?;
+            let text_format = TextFormat::from_css_style(&style, Default::default());
+            Ok(text_format.as_avm1_object(activation, context)?.into())
+        }
+        _ => Ok(Value::Null),
+    }
+}
+
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => return Ok(false.into()),
+        Some(url) => url.coerce_to_string(activation, context)?,
+    };
+
+    let fetch = security::fetch(
+        activation,
+        context,
+        &url,
+        RequestOptions::get(),
+        LoadKind::Data,
+    );
+    let target_clip = activation.target_clip_or_root();
+    let process = context.load_manager.load_text_into_object(
+        context.player.clone().unwrap(),
+        this,
+        target_clip,
+        fetch,
+    );
+    context.navigator.spawn_future(process);
+
+    Ok(true.into())
+}
+
+pub fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let success = match args.get(0) {
+        Some(Value::Undefined) | None => false,
+        Some(src) => {
+            let src = src.coerce_to_string(activation, context)?;
+            this.call_method(
+                "parseCSS",
+                &[AvmString::new(context.gc_context, src.to_string()).into()],
+                activation,
+                context,
+            )?
+            .as_bool(activation.current_swf_version())
+        }
+    };
+
+    this.call_method("onLoad", &[success.into()], activation, context)?;
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let style_sheet_object = StyleSheetObject::empty_style_sheet(gc_context, Some(proto));
+    let mut object = style_sheet_object.as_script_object().unwrap();
+
+    object.force_set_function(
+        "parseCSS",
+        parse_css,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getStyle",
+        get_style,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "setStyle",
+        set_style,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "getStyleNames",
+        get_style_names,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "clear",
+        clear,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "transform",
+        transform,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "load",
+        load,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "onData",
+        on_data,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete,
+        Some(fn_proto),
+    );
+
+    style_sheet_object.into()
+}
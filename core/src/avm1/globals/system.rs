@@ -4,6 +4,7 @@ use crate::avm1::function::Executable;
 use crate::avm1::object::Object;
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::context::UpdateContext;
+use crate::security::SecurityState;
 use core::fmt;
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::MutationContext;
@@ -29,25 +30,6 @@ impl fmt::Display for CpuArchitecture {
     }
 }
 
-/// Available type of sandbox for a given SWF
-pub enum SandboxType {
-    Remote,
-    LocalWithFile,
-    LocalWithNetwork,
-    LocalTrusted,
-}
-
-impl fmt::Display for SandboxType {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.write_str(match self {
-            SandboxType::Remote => "remote",
-            SandboxType::LocalWithFile => "localWithFile",
-            SandboxType::LocalWithNetwork => "localWithNetwork",
-            SandboxType::LocalTrusted => "localTrusted",
-        })
-    }
-}
-
 /// The available host operating systems
 pub enum OperatingSystem {
     WindowsXp,
@@ -264,8 +246,8 @@ pub struct SystemProperties {
     pub manufacturer: Manufacturer,
     /// The os of the host
     pub os: OperatingSystem,
-    /// The type of the player sandbox
-    pub sandbox_type: SandboxType,
+    /// The security sandbox state of the player
+    pub security: SecurityState,
     /// The cpu architecture of the platform
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
@@ -395,7 +377,7 @@ impl Default for SystemProperties {
             dpi: 1_f32,
             manufacturer: Manufacturer::Linux,
             os: OperatingSystem::Linux,
-            sandbox_type: SandboxType::LocalTrusted,
+            security: SecurityState::default(),
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
         }
@@ -4,37 +4,57 @@ use crate::avm1::function::Executable;
 use crate::avm1::object::Object;
 use crate::avm1::{AvmString, ScriptObject, TObject, Value};
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
+use crate::security;
 use enumset::EnumSet;
 use gc_arena::MutationContext;
 use std::convert::Into;
 
 fn allow_domain<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _context: &mut UpdateContext<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("System.security.allowDomain() not implemented");
+    if let Some(movie) = activation.base_clip().movie() {
+        for domain in args {
+            let domain = domain.coerce_to_string(activation, context)?;
+            context.system.security.allow_domain(&movie, &domain);
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
 fn allow_insecure_domain<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("System.security.allowInsecureDomain() not implemented");
-    Ok(Value::Undefined)
+    // We don't distinguish movies loaded over HTTPS from other remote movies
+    // when scripting, so this is the same as `allowDomain`.
+    allow_domain(activation, context, this, args)
 }
 
 fn load_policy_file<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _context: &mut UpdateContext<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("System.security.allowInsecureDomain() not implemented");
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+
+    if let Some(movie) = activation.base_clip().movie() {
+        match security::resolve_url(&movie, &url) {
+            Some(url) => context.system.security.add_policy_file(url),
+            None => log::warn!("System.security.loadPolicyFile: invalid URL {}", url),
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -49,12 +69,18 @@ fn escape_domain<'gc>(
 }
 
 fn get_sandbox_type<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     context: &mut UpdateContext<'_, 'gc, '_>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok(AvmString::new(context.gc_context, context.system.sandbox_type.to_string()).into())
+    let movie = activation
+        .base_clip()
+        .movie()
+        .unwrap_or_else(|| context.swf.clone());
+    let sandbox_type = context.system.security.sandbox_type(&movie);
+
+    Ok(AvmString::new(context.gc_context, sandbox_type.to_string()).into())
 }
 
 fn get_choose_local_swf_path<'gc>(
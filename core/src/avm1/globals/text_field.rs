@@ -7,6 +7,7 @@ use crate::avm1::{AvmString, Object, ScriptObject, TObject, UpdateContext, Value
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject};
 use crate::html::TextFormat;
 use gc_arena::MutationContext;
+use swf::Color;
 
 /// Implements `TextField`
 pub fn constructor<'gc>(
@@ -416,6 +417,540 @@ pub fn set_auto_size<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_type<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(if etext.is_editable() {
+            "input".into()
+        } else {
+            "dynamic".into()
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+
+        // Other values are ignored.
+        if value.eq_ignore_ascii_case("input") {
+            etext.set_editable(true, context);
+        } else if value.eq_ignore_ascii_case("dynamic") {
+            etext.set_editable(false, context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_selectable<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.is_selectable().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_selectable<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            etext.set_selectable(value.as_bool(activation.current_swf_version()), context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_password<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.is_password().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_password<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            etext.set_password(value.as_bool(activation.current_swf_version()), context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(match etext.max_chars() {
+            0 => Value::Null,
+            max_chars => max_chars.into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let max_chars = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => 0,
+            value => value.coerce_to_i32(activation, context)?.max(0),
+        };
+        etext.set_max_chars(max_chars, context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_restrict<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(match etext.restrict() {
+            Some(restrict) => AvmString::new(context.gc_context, restrict).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => etext.set_restrict(None, context),
+            value => {
+                let restrict = value.coerce_to_string(activation, context)?;
+                etext.set_restrict(Some(&restrict), context);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_background<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.has_background().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_background<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            let has_background = value.as_bool(activation.current_swf_version());
+            etext.set_has_background(context.gc_context, has_background);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_background_color<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.background_color().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_background_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            let rgb = value.coerce_to_u32(activation, context)? & 0xFFFFFF;
+            etext.set_background_color(context.gc_context, rgb);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_border_color<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.border_color().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_border_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            let rgb = value.coerce_to_u32(activation, context)? & 0xFFFFFF;
+            etext.set_border_color(context.gc_context, rgb);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_text_color<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext
+            .text_color()
+            .map(|color| color_rgb(&color))
+            .unwrap_or(0)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_text_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            let rgb = value.coerce_to_u32(activation, context)?;
+            etext.set_text_color(Color::from_rgb(rgb, 0xFF), context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_condense_white<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.is_condense_white().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_condense_white<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            etext.set_condense_white(context, value.as_bool(activation.current_swf_version()));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext
+            .style_sheet()
+            .map(|style_sheet| Value::Object(style_sheet.into()))
+            .unwrap_or(Value::Undefined));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        let style_sheet = match args.get(0) {
+            Some(Value::Object(object)) => object.as_style_sheet_object(),
+            _ => None,
+        };
+        etext.set_style_sheet(style_sheet, context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok((etext.scroll() as f64).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            let scroll = value.coerce_to_i32(activation, context)?.max(1) as usize;
+            etext.set_scroll(scroll, context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_maxscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok((etext.maxscroll() as f64).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_bottom_scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok((etext.bottom_scroll() as f64).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_hscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.hscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_hscroll<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        if let Some(value) = args.get(0) {
+            let hscroll = value.coerce_to_i32(activation, context)?.max(0);
+            etext.set_hscroll(f64::from(hscroll), context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_maxhscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.maxhscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn color_rgb(color: &Color) -> u32 {
+    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
@@ -524,6 +1059,118 @@ pub fn attach_virtual_properties<'gc>(gc_context: MutationContext<'gc, '_>, obje
         Some(Executable::Native(set_embed_fonts)),
         ReadOnly.into(),
     );
+    object.add_property(
+        gc_context,
+        "type",
+        Executable::Native(get_type),
+        Some(Executable::Native(set_type)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "selectable",
+        Executable::Native(get_selectable),
+        Some(Executable::Native(set_selectable)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "password",
+        Executable::Native(get_password),
+        Some(Executable::Native(set_password)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxChars",
+        Executable::Native(get_max_chars),
+        Some(Executable::Native(set_max_chars)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "restrict",
+        Executable::Native(get_restrict),
+        Some(Executable::Native(set_restrict)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "background",
+        Executable::Native(get_background),
+        Some(Executable::Native(set_background)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "backgroundColor",
+        Executable::Native(get_background_color),
+        Some(Executable::Native(set_background_color)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "borderColor",
+        Executable::Native(get_border_color),
+        Some(Executable::Native(set_border_color)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "textColor",
+        Executable::Native(get_text_color),
+        Some(Executable::Native(set_text_color)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "condenseWhite",
+        Executable::Native(get_condense_white),
+        Some(Executable::Native(set_condense_white)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "styleSheet",
+        Executable::Native(get_style_sheet),
+        Some(Executable::Native(set_style_sheet)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "scroll",
+        Executable::Native(get_scroll),
+        Some(Executable::Native(set_scroll)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxscroll",
+        Executable::Native(get_maxscroll),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "bottomScroll",
+        Executable::Native(get_bottom_scroll),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "hscroll",
+        Executable::Native(get_hscroll),
+        Some(Executable::Native(set_hscroll)),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxhscroll",
+        Executable::Native(get_maxhscroll),
+        None,
+        ReadOnly.into(),
+    );
 }
 
 fn get_new_text_format<'gc>(
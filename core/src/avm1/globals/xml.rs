@@ -8,6 +8,7 @@ use crate::avm1::object::xml_object::XMLObject;
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, TObject, UpdateContext, Value};
 use crate::backend::navigator::RequestOptions;
+use crate::security::{self, LoadKind};
 use crate::xml;
 use crate::xml::{XMLDocument, XMLNode};
 use enumset::EnumSet;
@@ -829,7 +830,7 @@ pub fn xml_load<'gc>(
 
         this.set("loaded", false.into(), activation, ac)?;
 
-        let fetch = ac.navigator.fetch(&url, RequestOptions::get());
+        let fetch = security::fetch(activation, ac, &url, RequestOptions::get(), LoadKind::Data);
         let target_clip = activation.target_clip_or_root();
         let process = ac.load_manager.load_xml_into_node(
             ac.player.clone().unwrap(),
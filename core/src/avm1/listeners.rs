@@ -140,6 +140,7 @@ impl<'gc> Listeners<'gc> {
 pub enum SystemListener {
     Mouse,
     Ime,
+    Selection,
 }
 
 #[derive(Clone, Collect, Debug, Copy)]
@@ -147,6 +148,7 @@ pub enum SystemListener {
 pub struct SystemListeners<'gc> {
     pub mouse: Listeners<'gc>,
     pub ime: Listeners<'gc>,
+    pub selection: Listeners<'gc>,
 }
 
 impl<'gc> SystemListeners<'gc> {
@@ -154,6 +156,7 @@ impl<'gc> SystemListeners<'gc> {
         Self {
             mouse: Listeners::new(gc_context, array_proto),
             ime: Listeners::new(gc_context, array_proto),
+            selection: Listeners::new(gc_context, array_proto),
         }
     }
 
@@ -161,6 +164,7 @@ impl<'gc> SystemListeners<'gc> {
         match listener {
             SystemListener::Mouse => self.mouse,
             SystemListener::Ime => self.ime,
+            SystemListener::Selection => self.selection,
         }
     }
 }
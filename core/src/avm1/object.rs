@@ -8,7 +8,11 @@ use crate::avm1::object::value_object::ValueObject;
 use crate::avm1::property::Attribute;
 
 use crate::avm1::activation::Activation;
+use crate::avm1::object::bitmap_data_object::BitmapDataObject;
 use crate::avm1::object::color_transform_object::ColorTransformObject;
+use crate::avm1::object::date_object::DateObject;
+use crate::avm1::object::filter_object::FilterObject;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
 use crate::avm1::object::xml_attributes_object::XMLAttributesObject;
 use crate::avm1::object::xml_idmap_object::XMLIDMapObject;
 use crate::avm1::object::xml_object::XMLObject;
@@ -21,12 +25,16 @@ use ruffle_macros::enum_trait_object;
 use std::borrow::Cow;
 use std::fmt::Debug;
 
+pub mod bitmap_data_object;
 pub mod color_transform_object;
 mod custom_object;
+pub mod date_object;
+pub mod filter_object;
 pub mod script_object;
 pub mod shared_object;
 pub mod sound_object;
 pub mod stage_object;
+pub mod style_sheet_object;
 pub mod super_object;
 pub mod value_object;
 pub mod xml_attributes_object;
@@ -50,6 +58,10 @@ pub mod xml_object;
         FunctionObject(FunctionObject<'gc>),
         SharedObject(SharedObject<'gc>),
         ColorTransformObject(ColorTransformObject<'gc>),
+        FilterObject(FilterObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>),
+        DateObject(DateObject<'gc>),
+        BitmapDataObject(BitmapDataObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -438,6 +450,26 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    /// Get the underlying `FilterObject`, if it exists
+    fn as_filter_object(&self) -> Option<FilterObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `StyleSheetObject`, if it exists
+    fn as_style_sheet_object(&self) -> Option<StyleSheetObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `DateObject`, if it exists
+    fn as_date_object(&self) -> Option<DateObject<'gc>> {
+        None
+    }
+
+    /// Get the underlying `BitmapDataObject`, if it exists
+    fn as_bitmap_data_object(&self) -> Option<BitmapDataObject<'gc>> {
+        None
+    }
+
     fn as_ptr(&self) -> *const ObjectPtr;
 
     /// Check if this object is in the prototype chain of the specified test object.
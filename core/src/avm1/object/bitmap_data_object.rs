@@ -0,0 +1,94 @@
+use crate::impl_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::bitmap_data::BitmapData;
+use crate::context::UpdateContext;
+use std::fmt;
+
+/// A BitmapData
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct BitmapDataObject<'gc>(GcCell<'gc, BitmapDataObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct BitmapDataObjectData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    /// The pixels of this bitmap, shared with any clip it is attached to.
+    bitmap_data: GcCell<'gc, BitmapData>,
+}
+
+impl fmt::Debug for BitmapDataObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        let bitmap_data = this.bitmap_data.read();
+        f.debug_struct("BitmapDataObject")
+            .field("width", &bitmap_data.width())
+            .field("height", &bitmap_data.height())
+            .field("transparent", &bitmap_data.transparent())
+            .finish()
+    }
+}
+
+impl<'gc> BitmapDataObject<'gc> {
+    pub fn new(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        bitmap_data: BitmapData,
+    ) -> Self {
+        BitmapDataObject(GcCell::allocate(
+            gc_context,
+            BitmapDataObjectData {
+                base: ScriptObject::object(gc_context, proto),
+                bitmap_data: GcCell::allocate(gc_context, bitmap_data),
+            },
+        ))
+    }
+
+    /// Creates a `BitmapData` that starts out disposed, to be initialized by
+    /// its constructor.
+    pub fn empty_bitmap_data_object(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+    ) -> Self {
+        let mut bitmap_data = BitmapData::new(0, 0, true, 0);
+        bitmap_data.dispose();
+        Self::new(gc_context, proto, bitmap_data)
+    }
+
+    pub fn bitmap_data(self) -> GcCell<'gc, BitmapData> {
+        self.0.read().bitmap_data
+    }
+
+    pub fn set_bitmap_data(self, gc_context: MutationContext<'gc, '_>, bitmap_data: BitmapData) {
+        self.0.write(gc_context).bitmap_data = GcCell::allocate(gc_context, bitmap_data);
+    }
+}
+
+impl<'gc> TObject<'gc> for BitmapDataObject<'gc> {
+    impl_custom_object!(base);
+
+    #[allow(clippy::new_ret_no_self)]
+    fn new(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        _this: Object<'gc>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(BitmapDataObject::empty_bitmap_data_object(
+            context.gc_context,
+            Some(activation.avm.prototypes.bitmap_data),
+        )
+        .into())
+    }
+
+    fn as_bitmap_data_object(&self) -> Option<BitmapDataObject<'gc>> {
+        Some(*self)
+    }
+}
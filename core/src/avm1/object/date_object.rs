@@ -0,0 +1,79 @@
+use crate::impl_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::context::UpdateContext;
+use std::fmt;
+
+/// A Date
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct DateObject<'gc>(GcCell<'gc, DateObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct DateObjectData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    /// The time value of this date, in milliseconds since the Unix epoch (UTC).
+    ///
+    /// `NaN` represents an invalid date.
+    date_time: f64,
+}
+
+impl fmt::Debug for DateObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("DateObject")
+            .field("date_time", &this.date_time)
+            .finish()
+    }
+}
+
+impl<'gc> DateObject<'gc> {
+    pub fn empty_date_object(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+    ) -> Self {
+        DateObject(GcCell::allocate(
+            gc_context,
+            DateObjectData {
+                base: ScriptObject::object(gc_context, proto),
+                date_time: f64::NAN,
+            },
+        ))
+    }
+
+    pub fn date_time(self) -> f64 {
+        self.0.read().date_time
+    }
+
+    pub fn set_date_time(self, gc_context: MutationContext<'gc, '_>, date_time: f64) {
+        self.0.write(gc_context).date_time = date_time;
+    }
+}
+
+impl<'gc> TObject<'gc> for DateObject<'gc> {
+    impl_custom_object!(base);
+
+    #[allow(clippy::new_ret_no_self)]
+    fn new(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        _this: Object<'gc>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        Ok(
+            DateObject::empty_date_object(context.gc_context, Some(activation.avm.prototypes.date))
+                .into(),
+        )
+    }
+
+    fn as_date_object(&self) -> Option<DateObject<'gc>> {
+        Some(*self)
+    }
+}
@@ -0,0 +1,106 @@
+use crate::avm1::error::Error;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::context::UpdateContext;
+use crate::impl_custom_object_without_set;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+use crate::avm1::activation::Activation;
+use std::cell::{Ref, RefMut};
+use std::fmt;
+use swf::Filter;
+
+/// A `flash.filters` bitmap filter.
+///
+/// The filter is stored in its SWF representation, so that it can be applied
+/// to display objects as is. The kind of filter is determined by the
+/// prototype that the object was constructed from.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct FilterObject<'gc>(GcCell<'gc, FilterData<'gc>>);
+
+#[derive(Clone)]
+pub struct FilterData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    filter: Filter,
+}
+
+unsafe impl<'gc> Collect for FilterData<'gc> {
+    #[inline]
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        self.base.trace(cc);
+    }
+}
+
+impl fmt::Debug for FilterObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("FilterObject")
+            .field("filter", &this.filter)
+            .finish()
+    }
+}
+
+impl<'gc> FilterObject<'gc> {
+    pub fn new(
+        gc_context: MutationContext<'gc, '_>,
+        proto: Option<Object<'gc>>,
+        filter: Filter,
+    ) -> Self {
+        FilterObject(GcCell::allocate(
+            gc_context,
+            FilterData {
+                base: ScriptObject::object(gc_context, proto),
+                filter,
+            },
+        ))
+    }
+
+    pub fn filter(&self) -> Ref<Filter> {
+        Ref::map(self.0.read(), |data| &data.filter)
+    }
+
+    pub fn filter_mut(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<Filter> {
+        RefMut::map(self.0.write(gc_context), |data| &mut data.filter)
+    }
+}
+
+impl<'gc> TObject<'gc> for FilterObject<'gc> {
+    impl_custom_object_without_set!(base);
+
+    fn set(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        let base = self.0.read().base;
+        base.internal_set(
+            name,
+            value,
+            activation,
+            context,
+            (*self).into(),
+            base.proto(),
+        )
+    }
+
+    fn as_filter_object(&self) -> Option<FilterObject<'gc>> {
+        Some(*self)
+    }
+
+    #[allow(clippy::new_ret_no_self)]
+    fn new(
+        &self,
+        _activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        this: Object<'gc>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        // The prototype of each filter class holds the default filter of that class.
+        let filter = self.filter().clone();
+        Ok(FilterObject::new(context.gc_context, Some(this), filter).into())
+    }
+}
@@ -856,10 +856,15 @@ mod tests {
     use crate::avm1::{Avm1, Timers};
     use crate::backend::audio::NullAudioBackend;
     use crate::backend::input::NullInputBackend;
+    use crate::backend::locale::NullLocaleBackend;
     use crate::backend::navigator::NullNavigatorBackend;
     use crate::backend::render::NullRenderer;
     use crate::backend::storage::MemoryStorageBackend;
+    use crate::backend::video::NullVideoBackend;
     use crate::display_object::MovieClip;
+    use crate::external::ExternalInterface;
+    use crate::focus_tracker::FocusTracker;
+    use crate::font::FontSource;
     use crate::library::Library;
     use crate::loader::LoadManager;
     use crate::prelude::*;
@@ -868,6 +873,7 @@ mod tests {
     use rand::{rngs::SmallRng, SeedableRng};
     use std::collections::{BTreeMap, HashMap};
     use std::sync::Arc;
+    use std::time::Duration;
 
     fn with_object<F, R>(swf_version: u8, test: F) -> R
     where
@@ -894,6 +900,7 @@ mod tests {
                 rng: &mut SmallRng::from_seed([0u8; 16]),
                 action_queue: &mut crate::context::ActionQueue::new(),
                 audio: &mut NullAudioBackend::new(),
+                video: &mut NullVideoBackend::new(),
                 input: &mut NullInputBackend::new(),
                 background_color: &mut Color {
                     r: 0,
@@ -902,10 +909,12 @@ mod tests {
                     a: 0,
                 },
                 library: &mut Library::default(),
+                font_source: &FontSource::new(),
                 navigator: &mut NullNavigatorBackend::new(),
                 renderer: &mut NullRenderer::new(),
                 system_prototypes: avm.prototypes().clone(),
                 mouse_hovered_object: None,
+                focus_tracker: FocusTracker::new(gc_context),
                 mouse_position: &(Twips::new(0), Twips::new(0)),
                 drag_object: &mut None,
                 stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
@@ -914,10 +923,14 @@ mod tests {
                 system: &mut SystemProperties::default(),
                 instance_counter: &mut 0,
                 storage: &mut MemoryStorageBackend::default(),
+                locale: &NullLocaleBackend::new(),
                 shared_objects: &mut HashMap::new(),
                 unbound_text_fields: &mut Vec::new(),
                 timers: &mut Timers::new(),
+                external_interface: &mut ExternalInterface::default(),
                 needs_render: &mut false,
+                update_start: Duration::from_secs(0),
+                max_execution_duration: Duration::from_secs(15),
             };
 
             root.post_instantiation(&mut avm, &mut context, root, None, false);
@@ -149,6 +149,19 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
                 .get_level_by_path(name, context, case_sensitive)
         {
             // 4) _levelN
+            // Levels holding movies from another domain may only be scripted
+            // with that movie's permission.
+            if let (Some(requester), Some(target)) = (activation.base_clip().movie(), level.movie())
+            {
+                if !context.system.security.can_script(&requester, &target) {
+                    log::warn!(
+                        "Security sandbox violation: {} may not script {}",
+                        requester.url().unwrap_or("<unknown>"),
+                        target.url().unwrap_or("<unknown>")
+                    );
+                    return Ok(Value::Undefined);
+                }
+            }
             Ok(level.object())
         } else {
             // 5) Prototype
@@ -898,11 +911,14 @@ fn drop_target<'gc>(
 
 fn url<'gc>(
     _activation: &mut Activation<'_, 'gc>,
-    _context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Unimplemented property _url");
-    Ok("".into())
+    Ok(this
+        .movie()
+        .and_then(|movie| movie.url().map(|url| url.to_string()))
+        .map(|url| AvmString::new(context.gc_context, url).into())
+        .unwrap_or_else(|| "".into()))
 }
 
 fn high_quality<'gc>(
@@ -927,19 +943,28 @@ fn set_high_quality<'gc>(
 fn focus_rect<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     _context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
+    this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("Unimplemented property _focusrect");
-    Ok(Value::Null)
+    // The `_focusrect` of a root acts as the global default, which is on.
+    let focus_rect = match this.focus_rect() {
+        Some(focus_rect) => focus_rect.into(),
+        None if this.parent().is_none() => true.into(),
+        None => Value::Null,
+    };
+    Ok(focus_rect)
 }
 
 fn set_focus_rect<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mut this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    log::warn!("Unimplemented property _focusrect");
+    let focus_rect = match val {
+        Value::Undefined | Value::Null => None,
+        val => Some(val.as_bool(activation.current_swf_version())),
+    };
+    this.set_focus_rect(context.gc_context, focus_rect);
     Ok(())
 }
 
@@ -1,15 +1,19 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
-use crate::avm2::activation::Activation;
+use crate::avm1::Avm1;
+use crate::avm2::event_object::EventObject;
+use crate::avm2::events::BROADCAST_EVENTS;
 use crate::avm2::globals::SystemPrototypes;
-use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::script::TranslationUnit;
-use crate::avm2::value::Value;
+use crate::avm2::string::AvmString;
 use crate::context::UpdateContext;
+use crate::display_object::DisplayObject;
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 use swf::avm2::read::Reader;
 
@@ -22,12 +26,18 @@ macro_rules! avm_debug {
 }
 
 mod activation;
+mod array;
+mod array_object;
 mod class;
+mod dispatch_object;
+mod event_object;
+mod events;
 mod function;
 mod globals;
 mod method;
 mod names;
 mod object;
+mod primitive_object;
 mod property;
 mod property_map;
 mod return_value;
@@ -35,15 +45,41 @@ mod scope;
 mod script;
 mod script_object;
 mod slot;
+mod stage_object;
 mod string;
 mod r#trait;
 mod value;
 
+pub use crate::avm2::activation::Activation;
+pub use crate::avm2::array::ArrayStorage;
+pub use crate::avm2::array_object::ArrayObject;
+pub use crate::avm2::events::{Event, EventData};
+pub use crate::avm2::names::{Namespace, QName};
+pub use crate::avm2::object::{Object, TObject};
+pub use crate::avm2::script_object::ScriptObject;
+pub use crate::avm2::stage_object::StageObject;
+pub use crate::avm2::value::Value;
+
 /// Boxed error alias.
 ///
 /// As AVM2 is a far stricter VM than AVM1, this may eventually be replaced
 /// with a proper Avm2Error enum.
-type Error = Box<dyn std::error::Error>;
+pub type Error = Box<dyn std::error::Error>;
+
+/// An error raised by the `throw` of an ActionScript value.
+///
+/// Boxed errors cannot hold garbage-collected values, so the thrown value
+/// itself is held by the `Avm2` instance until it is caught.
+#[derive(Debug)]
+pub struct ThrownValue;
+
+impl fmt::Display for ThrownValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "A script has thrown a value.")
+    }
+}
+
+impl std::error::Error for ThrownValue {}
 
 /// The state of an AVM2 interpreter.
 #[derive(Collect)]
@@ -57,6 +93,16 @@ pub struct Avm2<'gc> {
 
     /// System prototypes.
     system_prototypes: SystemPrototypes<'gc>,
+
+    /// The value thrown by the exception currently being propagated, if any.
+    exception: Option<Value<'gc>>,
+
+    /// Display objects that are listening to each broadcast event type.
+    broadcast_list: HashMap<AvmString<'gc>, Vec<Object<'gc>>>,
+
+    /// The display object that the mouse was last pressed over, which
+    /// receives a `click` event if the mouse is also released over it.
+    mouse_down_target: Option<DisplayObject<'gc>>,
 }
 
 impl<'gc> Avm2<'gc> {
@@ -68,6 +114,9 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::new(),
             globals,
             system_prototypes,
+            exception: None,
+            broadcast_list: HashMap::new(),
+            mouse_down_target: None,
         }
     }
 
@@ -79,12 +128,66 @@ impl<'gc> Avm2<'gc> {
     /// Run a script's initializer method.
     pub fn run_script_initializer(
         &mut self,
+        avm1: &mut Avm1<'gc>,
         script: GcCell<'gc, Script<'gc>>,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<(), Error> {
-        let mut init_activation = Activation::from_script(self, context, script, self.globals)?;
+        let mut init_activation =
+            Activation::from_script(avm1, self, context, script, self.globals)?;
 
-        init_activation.run_stack_frame_for_script(context, script)
+        let result = init_activation.run_stack_frame_for_script(context, script);
+
+        drop(init_activation);
+
+        result.map_err(|e| self.uncaught_error(avm1, e, context))
+    }
+
+    /// Start propagating a thrown value.
+    ///
+    /// The returned error should be returned from the current opcode so that
+    /// the interpreter can unwind to a matching exception handler.
+    pub fn throw(&mut self, value: Value<'gc>) -> Error {
+        self.exception = Some(value);
+
+        Box::new(ThrownValue)
+    }
+
+    /// Retrieve the thrown value that raised a given error.
+    ///
+    /// Returns `None` for errors which are not the result of a `throw`, which
+    /// cannot be caught by scripts.
+    pub fn take_exception(&mut self, error: &Error) -> Option<Value<'gc>> {
+        if error.is::<ThrownValue>() {
+            self.exception.take()
+        } else {
+            None
+        }
+    }
+
+    /// Convert an error that escaped all script handlers into one that
+    /// describes the uncaught value, for reporting to the host.
+    fn uncaught_error(
+        &mut self,
+        avm1: &mut Avm1<'gc>,
+        error: Error,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Error {
+        if let Some(value) = self.take_exception(&error) {
+            let mut activation = Activation::from_nothing(avm1, self, context);
+            let message = match value.coerce_to_string(&mut activation, context) {
+                Ok(message) => message.to_string(),
+                Err(_) => format!("{:?}", value),
+            };
+
+            drop(activation);
+
+            // Stringifying the value may have thrown again.
+            self.exception = None;
+
+            format!("Uncaught exception: {}", message).into()
+        } else {
+            error
+        }
     }
 
     /// Load an ABC file embedded in a `SwfSlice`.
@@ -92,6 +195,7 @@ impl<'gc> Avm2<'gc> {
     /// The `SwfSlice` must resolve to the contents of an ABC file.
     pub fn load_abc(
         &mut self,
+        avm1: &mut Avm1<'gc>,
         abc: SwfSlice,
         _abc_name: &str,
         _lazy_init: bool,
@@ -106,7 +210,7 @@ impl<'gc> Avm2<'gc> {
             let script = tunit.load_script(i as u32, context.gc_context)?;
             let mut globals = self.globals();
             let scope = Scope::push_scope(None, globals, context.gc_context);
-            let mut null_activation = Activation::from_nothing(self, context);
+            let mut null_activation = Activation::from_nothing(avm1, self, context);
 
             // TODO: Lazyinit means we shouldn't do this until traits are
             // actually mentioned...
@@ -122,12 +226,159 @@ impl<'gc> Avm2<'gc> {
 
             drop(null_activation);
 
-            self.run_script_initializer(script, context)?;
+            self.run_script_initializer(avm1, script, context)?;
         }
 
         Ok(())
     }
 
+    /// Construct the AS3 object for a display object whose library symbol is
+    /// linked to an AS3 class.
+    ///
+    /// The object is attached to the display object before the class
+    /// constructor runs, so that builtin display object constructors do not
+    /// create another display object.
+    pub fn construct_display_object(
+        &mut self,
+        avm1: &mut Avm1<'gc>,
+        display_object: DisplayObject<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(avm1, self, context);
+        let result = match stage_object::linked_class(&mut activation, context, display_object) {
+            Ok(Some(class)) => {
+                stage_object::display_object_to_object(&mut activation, context, display_object)
+                    .and_then(|object| {
+                        class.call(Some(object), &[], &mut activation, context, object.proto())
+                    })
+                    .map(|_| ())
+            }
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        };
+
+        drop(activation);
+
+        result.map_err(|e| self.uncaught_error(avm1, e, context))
+    }
+
+    /// Add an object to the broadcast list of an event type.
+    ///
+    /// Broadcast events, such as `enterFrame`, are dispatched to every
+    /// display object that listens to them, whether or not it is on the
+    /// display list. Event types that are not broadcast are ignored.
+    pub fn register_broadcast_listener(&mut self, object: Object<'gc>, event_type: AvmString<'gc>) {
+        if !BROADCAST_EVENTS.contains(&event_type.as_str()) {
+            return;
+        }
+
+        let listeners = self
+            .broadcast_list
+            .entry(event_type)
+            .or_insert_with(Vec::new);
+        if !listeners.iter().any(|l| Object::ptr_eq(*l, object)) {
+            listeners.push(object);
+        }
+    }
+
+    /// Remove an object from the broadcast list of an event type.
+    pub fn unregister_broadcast_listener(
+        &mut self,
+        object: Object<'gc>,
+        event_type: AvmString<'gc>,
+    ) {
+        if let Some(listeners) = self.broadcast_list.get_mut(&event_type) {
+            listeners.retain(|l| !Object::ptr_eq(*l, object));
+        }
+    }
+
+    /// Create an object for an event, with the prototype of the event class
+    /// matching its data.
+    fn event_object(&self, mc: MutationContext<'gc, '_>, event: Event<'gc>) -> Object<'gc> {
+        let proto = match event.event_data() {
+            EventData::Event => self.system_prototypes.event,
+            EventData::Mouse { .. } => self.system_prototypes.mouse_event,
+            EventData::Keyboard { .. } => self.system_prototypes.keyboard_event,
+        };
+
+        EventObject::from_event(mc, proto, event)
+    }
+
+    /// Dispatch an event to an object, and along the display list if the
+    /// object is a display object.
+    ///
+    /// Returns `false` if the event was cancelled.
+    pub fn dispatch_event(
+        &mut self,
+        avm1: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+        target: Object<'gc>,
+    ) -> Result<bool, Error> {
+        let event_object = self.event_object(context.gc_context, event);
+        let mut activation = Activation::from_nothing(avm1, self, context);
+        let result = events::dispatch_event(&mut activation, context, target, event_object);
+
+        drop(activation);
+
+        result.map_err(|e| self.uncaught_error(avm1, e, context))
+    }
+
+    /// Dispatch an event to a display object, and along the display list.
+    ///
+    /// The display object is given an AS3 object first if it does not have
+    /// one yet, so that its ancestors can still capture the event.
+    pub fn dispatch_display_object_event(
+        &mut self,
+        avm1: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+        display_object: DisplayObject<'gc>,
+    ) -> Result<bool, Error> {
+        let mut activation = Activation::from_nothing(avm1, self, context);
+        let target =
+            stage_object::display_object_to_object(&mut activation, context, display_object);
+
+        drop(activation);
+
+        let target = target.map_err(|e| self.uncaught_error(avm1, e, context))?;
+
+        self.dispatch_event(avm1, context, event, target)
+    }
+
+    /// Dispatch a broadcast event to every object that listens to it.
+    ///
+    /// Each listener receives its own copy of the event. An error in one
+    /// listener does not stop the event from being dispatched to the others.
+    pub fn broadcast_event(
+        &mut self,
+        avm1: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: Event<'gc>,
+    ) {
+        let listeners = self
+            .broadcast_list
+            .get(&event.event_type())
+            .cloned()
+            .unwrap_or_default();
+
+        for listener in listeners {
+            if let Err(e) = self.dispatch_event(avm1, context, event.duplicate(), listener) {
+                log::warn!("Error dispatching {} event: {}", event.event_type(), e);
+            }
+        }
+    }
+
+    /// Record the display object that the mouse was pressed over.
+    pub fn set_mouse_down_target(&mut self, target: Option<DisplayObject<'gc>>) {
+        self.mouse_down_target = target;
+    }
+
+    /// Take the display object that the mouse was pressed over, clearing it.
+    pub fn take_mouse_down_target(&mut self) -> Option<DisplayObject<'gc>> {
+        self.mouse_down_target.take()
+    }
+
     pub fn globals(&self) -> Object<'gc> {
         self.globals
     }
@@ -1,7 +1,11 @@
 //! Activation frames
 
+use crate::avm1::Avm1;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::array_object::ArrayObject;
 use crate::avm2::class::Class;
 use crate::avm2::function::FunctionObject;
+use crate::avm2::globals::error;
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::object::{Object, TObject};
@@ -9,16 +13,17 @@ use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::script_object::ScriptObject;
 use crate::avm2::string::AvmString;
-use crate::avm2::value::Value;
-use crate::avm2::{value, Avm2, Error};
-use crate::context::UpdateContext;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::{value, Avm2, Error, ThrownValue};
+use crate::context::{UpdateContext, ACTIONS_PER_TIMEOUT_CHECK};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
+use std::convert::TryFrom;
 use std::io::Cursor;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
-    Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
-    Namespace as AbcNamespace, Op,
+    Class as AbcClass, Exception as AbcException, Index, Method as AbcMethod,
+    Multiname as AbcMultiname, Namespace as AbcNamespace, Op,
 };
 
 /// Represents a particular register set.
@@ -65,6 +70,12 @@ enum FrameControl<'gc> {
 #[derive(Collect)]
 #[collect(no_drop)]
 pub struct Activation<'a, 'gc: 'a> {
+    /// The AVM1 instance running alongside us.
+    ///
+    /// Display object manipulations, such as timeline gotos, may need to run
+    /// AVM1 code.
+    avm1: &'a mut Avm1<'gc>,
+
     /// The AVM2 instance we execute under.
     avm2: &'a mut Avm2<'gc>,
 
@@ -114,10 +125,15 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     ///
     /// It is a logic error to attempt to run AVM2 code in a nothing
     /// `Activation`.
-    pub fn from_nothing(avm2: &'a mut Avm2<'gc>, context: &mut UpdateContext<'_, 'gc, '_>) -> Self {
+    pub fn from_nothing(
+        avm1: &'a mut Avm1<'gc>,
+        avm2: &'a mut Avm2<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Self {
         let local_registers = GcCell::allocate(context.gc_context, RegisterSet::new(0));
 
         Self {
+            avm1,
             avm2,
             this: None,
             arguments: None,
@@ -133,6 +149,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     /// Construct an activation for the execution of a particular script's
     /// initializer method.
     pub fn from_script(
+        avm1: &'a mut Avm1<'gc>,
         avm2: &'a mut Avm2<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
         script: GcCell<'gc, Script<'gc>>,
@@ -153,6 +170,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
             .unwrap() = global.into();
 
         Ok(Self {
+            avm1,
             avm2,
             this: Some(global),
             arguments: None,
@@ -167,7 +185,9 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
 
     /// Construct an activation for the execution of a particular bytecode
     /// method.
+    #[allow(clippy::too_many_arguments)]
     pub fn from_method(
+        avm1: &'a mut Avm1<'gc>,
         avm2: &'a mut Avm2<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -199,6 +219,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         }
 
         Ok(Self {
+            avm1,
             avm2,
             this,
             arguments: None,
@@ -280,10 +301,19 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         }
     }
 
+    pub fn avm1(&mut self) -> &mut Avm1<'gc> {
+        self.avm1
+    }
+
     pub fn avm2(&mut self) -> &mut Avm2<'gc> {
         self.avm2
     }
 
+    /// Borrow both virtual machines at once, for creating a child activation.
+    pub fn avms(&mut self) -> (&mut Avm1<'gc>, &mut Avm2<'gc>) {
+        (self.avm1, self.avm2)
+    }
+
     /// Set the return value.
     pub fn set_return_value(&mut self, value: Value<'gc>) {
         self.return_value = Some(value);
@@ -394,16 +424,181 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         let body: Result<_, Error> = method
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
-        let mut read = Reader::new(Cursor::new(body?.code.as_ref()));
+        let body = body?;
+        let mut read = Reader::new(Cursor::new(body.code.as_ref()));
+
+        // Exception handlers discard everything this frame pushed onto the
+        // operand and scope stacks.
+        let stack_depth = self.avm2.stack.len();
+        let init_scope = self.scope;
+        let mut ops_since_timeout_check: u16 = 0;
 
         loop {
-            let result = self.do_next_opcode(method, context, &mut read);
+            ops_since_timeout_check += 1;
+            if ops_since_timeout_check >= ACTIONS_PER_TIMEOUT_CHECK {
+                ops_since_timeout_check = 0;
+                if context.is_execution_timed_out() {
+                    break Err("Error #1502: A script has executed for longer than the maximum execution duration.".into());
+                }
+            }
+
+            let instruction_start = read.seek(0)?;
+            let result = self.do_next_opcode(method, context, &mut read, instruction_start);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
-                Err(e) => break Err(e),
+                Err(e) => {
+                    let value = match self.avm2.take_exception(&e) {
+                        Some(value) => value,
+                        // Without any handlers, interpreter errors propagate
+                        // as-is so that uncaught ones keep their description.
+                        None if body.exceptions.is_empty() => break Err(e),
+                        None => self.error_object(context, &e)?,
+                    };
+
+                    let handler = self.find_exception_handler(
+                        method,
+                        context,
+                        &body.exceptions,
+                        instruction_start,
+                        &value,
+                    )?;
+
+                    if let Some(handler) = handler {
+                        self.avm2.stack.truncate(stack_depth);
+                        self.scope = init_scope;
+                        self.avm2.push(value);
+
+                        let position = read.seek(0)?;
+                        read.seek(i64::from(handler.target_offset) - position as i64)?;
+                    } else {
+                        break Err(self.avm2.throw(value));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Construct the `Error` instance that scripts catch in place of an
+    /// interpreter error.
+    ///
+    /// The class is named by the error's message prefix, such as
+    /// `TypeError: `; errors without one become plain `Error`s.
+    fn error_object(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        error: &Error,
+    ) -> Result<Value<'gc>, Error> {
+        let description = error.to_string();
+        let (class_name, message) = error::split_error_message(&description);
+        let mut globals = self.avm2.globals();
+        let mut class = globals
+            .get_property(
+                globals,
+                &QName::new(Namespace::public_namespace(), class_name),
+                self,
+                context,
+            )?
+            .as_object()?;
+        let proto = class
+            .get_property(
+                class,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                self,
+                context,
+            )?
+            .as_object()?;
+
+        let args = [AvmString::new(context.gc_context, message.to_string()).into()];
+        let object = proto.construct(self, context, &args)?;
+        class.call(Some(object), &args, self, context, object.proto())?;
+
+        Ok(object.into())
+    }
+
+    /// Find the first exception handler in a method body that covers a given
+    /// instruction and accepts the thrown value's type.
+    fn find_exception_handler<'b>(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handlers: &'b [AbcException],
+        instruction_start: u64,
+        value: &Value<'gc>,
+    ) -> Result<Option<&'b AbcException>, Error> {
+        for handler in handlers {
+            let covers_instruction = u64::from(handler.from_offset) <= instruction_start
+                && instruction_start < u64::from(handler.to_offset);
+
+            if covers_instruction && self.is_caught_by(method, context, handler, value)? {
+                return Ok(Some(handler));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Determine if a thrown value matches the type an exception handler
+    /// catches.
+    #[allow(clippy::float_cmp)]
+    fn is_caught_by(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handler: &AbcException,
+        value: &Value<'gc>,
+    ) -> Result<bool, Error> {
+        // A handler without a type (such as a `finally` block) catches
+        // everything.
+        if handler.type_name.0 == 0 {
+            return Ok(true);
+        }
+
+        let type_name =
+            self.pool_multiname_static(method, handler.type_name.clone(), context.gc_context)?;
+
+        if let Value::Object(object) = value {
+            let type_object: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
+                scope
+                    .write(context.gc_context)
+                    .resolve(&type_name, self, context)?
+            } else {
+                None
             }
+            .ok_or_else(|| {
+                format!(
+                    "Attempted to catch nonexistent type {:?}",
+                    type_name.local_name()
+                )
+                .into()
+            });
+
+            return object.is_instance_of(self, context, type_object?.as_object()?, true);
         }
+
+        //TODO: Should primitive types be resolved as classes, too?
+        Ok(match type_name.local_name() {
+            Some(name) if name == "Object" => value != &Value::Undefined && value != &Value::Null,
+            Some(name) if name == "Number" => value.is_number(),
+            Some(name) if name == "int" => match value {
+                Value::Integer(_) => true,
+                Value::Unsigned(u) => *u <= i32::MAX as u32,
+                Value::Number(n) => f64::from(*n as i32) == *n,
+                _ => false,
+            },
+            Some(name) if name == "uint" => match value {
+                Value::Integer(i) => *i >= 0,
+                Value::Unsigned(_) => true,
+                Value::Number(n) => f64::from(*n as u32) == *n,
+                _ => false,
+            },
+            Some(name) if name == "String" => value.is_string(),
+            Some(name) if name == "Boolean" => match value {
+                Value::Bool(_) => true,
+                _ => false,
+            },
+            _ => false,
+        })
     }
 
     /// Run a single action from a given action reader.
@@ -412,6 +607,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         context: &mut UpdateContext<'_, 'gc, '_>,
         reader: &mut Reader<Cursor<&[u8]>>,
+        instruction_start: u64,
     ) -> Result<FrameControl<'gc>, Error> {
         let op = reader.read_op();
         if let Ok(Some(op)) = op {
@@ -432,6 +628,8 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
                 Op::PushUndefined => self.op_push_undefined(),
                 Op::Pop => self.op_pop(),
                 Op::Dup => self.op_dup(),
+                Op::Swap => self.op_swap(),
+                Op::Nop => Ok(FrameControl::Continue),
                 Op::GetLocal { index } => self.op_get_local(index),
                 Op::SetLocal { index } => self.op_set_local(context, index),
                 Op::Kill { index } => self.op_kill(context, index),
@@ -455,6 +653,8 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
                 Op::CallSuperVoid { index, num_args } => {
                     self.op_call_super_void(method, context, index, num_args)
                 }
+                Op::Throw => self.op_throw(),
+                Op::NewCatch { index } => self.op_new_catch(method, context, index),
                 Op::ReturnValue => self.op_return_value(),
                 Op::ReturnVoid => self.op_return_void(),
                 Op::GetProperty { index } => self.op_get_property(method, context, index),
@@ -482,15 +682,75 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
                 Op::ConstructSuper { num_args } => self.op_construct_super(context, num_args),
                 Op::NewActivation => self.op_new_activation(context),
                 Op::NewObject { num_args } => self.op_new_object(context, num_args),
+                Op::NewArray { num_args } => self.op_new_array(context, num_args),
                 Op::NewFunction { index } => self.op_new_function(method, context, index),
                 Op::NewClass { index } => self.op_new_class(method, context, index),
+                Op::Coerce { index } => self.op_coerce(method, context, index),
                 Op::CoerceA => self.op_coerce_a(),
+                Op::CoerceS => self.op_coerce_s(context),
+                Op::ConvertB => self.op_convert_b(),
+                Op::ConvertD => self.op_convert_d(context),
+                Op::ConvertI => self.op_convert_i(context),
+                Op::ConvertO => self.op_convert_o(),
+                Op::ConvertS => self.op_convert_s(context),
+                Op::ConvertU => self.op_convert_u(context),
+                Op::Add => self.op_add(context),
+                Op::AddI => self.op_add_i(context),
+                Op::Subtract => self.op_subtract(context),
+                Op::SubtractI => self.op_subtract_i(context),
+                Op::Multiply => self.op_multiply(context),
+                Op::MultiplyI => self.op_multiply_i(context),
+                Op::Divide => self.op_divide(context),
+                Op::Modulo => self.op_modulo(context),
+                Op::Increment => self.op_increment(context),
+                Op::IncrementI => self.op_increment_i(context),
+                Op::Decrement => self.op_decrement(context),
+                Op::DecrementI => self.op_decrement_i(context),
+                Op::IncLocal { index } => self.op_inc_local(context, index),
+                Op::IncLocalI { index } => self.op_inc_local_i(context, index),
+                Op::DecLocal { index } => self.op_dec_local(context, index),
+                Op::DecLocalI { index } => self.op_dec_local_i(context, index),
+                Op::Negate => self.op_negate(context),
+                Op::NegateI => self.op_negate_i(context),
+                Op::BitAnd => self.op_bitand(context),
+                Op::BitNot => self.op_bitnot(context),
+                Op::BitOr => self.op_bitor(context),
+                Op::BitXor => self.op_bitxor(context),
+                Op::LShift => self.op_lshift(context),
+                Op::RShift => self.op_rshift(context),
+                Op::URShift => self.op_urshift(context),
+                Op::Not => self.op_not(),
                 Op::Jump { offset } => self.op_jump(offset, reader),
                 Op::IfTrue { offset } => self.op_if_true(offset, reader),
                 Op::IfFalse { offset } => self.op_if_false(offset, reader),
                 Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, reader),
                 Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, reader),
+                Op::IfEq { offset } => self.op_if_eq(context, offset, reader),
+                Op::IfNe { offset } => self.op_if_ne(context, offset, reader),
+                Op::IfGe { offset } => self.op_if_ge(context, offset, reader),
+                Op::IfGt { offset } => self.op_if_gt(context, offset, reader),
+                Op::IfLe { offset } => self.op_if_le(context, offset, reader),
+                Op::IfLt { offset } => self.op_if_lt(context, offset, reader),
+                Op::IfNge { offset } => self.op_if_nge(context, offset, reader),
+                Op::IfNgt { offset } => self.op_if_ngt(context, offset, reader),
+                Op::IfNle { offset } => self.op_if_nle(context, offset, reader),
+                Op::IfNlt { offset } => self.op_if_nlt(context, offset, reader),
+                Op::LookupSwitch {
+                    default_offset,
+                    case_offsets,
+                } => self.op_lookup_switch(
+                    context,
+                    instruction_start,
+                    default_offset,
+                    &case_offsets,
+                    reader,
+                ),
                 Op::StrictEquals => self.op_strict_equals(),
+                Op::Equals => self.op_equals(context),
+                Op::GreaterEquals => self.op_greater_equals(context),
+                Op::GreaterThan => self.op_greater_than(context),
+                Op::LessEquals => self.op_less_equals(context),
+                Op::LessThan => self.op_less_than(context),
                 Op::HasNext => self.op_has_next(),
                 Op::HasNext2 {
                     object_register,
@@ -513,7 +773,9 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
             };
 
             if let Err(e) = result {
-                log::error!("AVM2 error: {}", e);
+                if !e.is::<ThrownValue>() {
+                    log::error!("AVM2 error: {}", e);
+                }
                 return Err(e);
             }
             result
@@ -534,7 +796,9 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     }
 
     fn op_push_byte(&mut self, value: u8) -> Result<FrameControl<'gc>, Error> {
-        self.avm2.push(value);
+        //TODO: Adobe Animate CC appears to generate signed byte values, and
+        //JPEXS appears to generate unsigned values.
+        self.avm2.push(value as i8 as i32);
         Ok(FrameControl::Continue)
     }
 
@@ -583,7 +847,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     }
 
     fn op_push_short(&mut self, value: u32) -> Result<FrameControl<'gc>, Error> {
-        self.avm2.push(value);
+        self.avm2.push(value as i16 as i32);
         Ok(FrameControl::Continue)
     }
 
@@ -630,6 +894,16 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_swap(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        self.avm2.push(value2);
+        self.avm2.push(value1);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_get_local(&mut self, register_index: u32) -> Result<FrameControl<'gc>, Error> {
         self.avm2.push(self.local_register(register_index)?);
         Ok(FrameControl::Continue)
@@ -701,10 +975,14 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
-        let mut receiver = self.avm2.pop().as_object()?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let mut receiver = self.avm2.pop().coerce_to_object(self, context)?;
+        let name: Result<QName, Error> = receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+            format!(
+                "TypeError: Could not find method {:?}",
+                multiname.local_name()
+            )
+            .into()
+        });
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
@@ -726,10 +1004,14 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
-        let mut receiver = self.avm2.pop().as_object()?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let mut receiver = self.avm2.pop().coerce_to_object(self, context)?;
+        let name: Result<QName, Error> = receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+            format!(
+                "TypeError: Could not find method {:?}",
+                multiname.local_name()
+            )
+            .into()
+        });
         let function = receiver
             .get_property(receiver, &name?, self, context)?
             .as_object()?;
@@ -749,10 +1031,14 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
-        let mut receiver = self.avm2.pop().as_object()?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let mut receiver = self.avm2.pop().coerce_to_object(self, context)?;
+        let name: Result<QName, Error> = receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+            format!(
+                "TypeError: Could not find method {:?}",
+                multiname.local_name()
+            )
+            .into()
+        });
         let name = name?;
         let base_proto = receiver.get_base_proto(&name)?;
         let function = receiver
@@ -799,9 +1085,13 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         let args = self.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
         let receiver = self.avm2.pop().as_object()?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error> = receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+            format!(
+                "TypeError: Could not find method {:?}",
+                multiname.local_name()
+            )
+            .into()
+        });
         let base_proto: Result<Object<'gc>, Error> =
             self.base_proto().and_then(|bp| bp.proto()).ok_or_else(|| {
                 "Attempted to call super method without a superclass."
@@ -832,9 +1122,13 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         let args = self.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
         let receiver = self.avm2.pop().as_object()?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
+        let name: Result<QName, Error> = receiver.resolve_multiname(&multiname)?.ok_or_else(|| {
+            format!(
+                "TypeError: Could not find method {:?}",
+                multiname.local_name()
+            )
+            .into()
+        });
         let base_proto: Result<Object<'gc>, Error> =
             self.base_proto().and_then(|bp| bp.proto()).ok_or_else(|| {
                 "Attempted to call super method without a superclass."
@@ -853,6 +1147,55 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_throw(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop();
+
+        Err(self.avm2.throw(value))
+    }
+
+    fn op_new_catch(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: Index<AbcException>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let body: Result<_, Error> = method
+            .body()
+            .ok_or_else(|| "Cannot execute non-native method without body".into());
+        let exception: Result<_, Error> = body?
+            .exceptions
+            .get(index.0 as usize)
+            .ok_or_else(|| format!("Exception handler {} does not exist", index.0).into());
+
+        // The catch variable name is a multiname, despite how it's typed in
+        // the ABC parser.
+        let variable_name = Index::new(exception?.variable_name.0);
+        let mut catch_scope = ScriptObject::bare_object(context.gc_context);
+
+        if variable_name.0 != 0 {
+            let name = self.pool_multiname_static(method, variable_name, context.gc_context)?;
+            let local_name: Result<_, Error> = name
+                .local_name()
+                .ok_or_else(|| "Catch variable must have a name".into());
+            let namespace = name
+                .namespace_set()
+                .next()
+                .cloned()
+                .unwrap_or_else(Namespace::public_namespace);
+
+            catch_scope.install_slot(
+                context.gc_context,
+                QName::new(namespace, local_name?),
+                1,
+                Value::Undefined,
+            );
+        }
+
+        self.avm2.push(catch_scope);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_return_value(&mut self) -> Result<FrameControl<'gc>, Error> {
         let return_value = self.avm2.pop();
 
@@ -870,13 +1213,15 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
-        let mut object = self.avm2.pop().as_object()?;
+        let mut object = self.avm2.pop().coerce_to_object(self, context)?;
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+        let value = if let Some(name) = object.resolve_multiname(&multiname)? {
+            object.get_property(object, &name, self, context)?
+        } else {
+            //TODO: Sealed objects should throw a ReferenceError here
+            Value::Undefined
+        };
 
-        let value = object.get_property(object, &name?, self, context)?;
         self.avm2.push(value);
 
         Ok(FrameControl::Continue)
@@ -890,7 +1235,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.avm2.pop();
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
-        let mut object = self.avm2.pop().as_object()?;
+        let mut object = self.avm2.pop().coerce_to_object(self, context)?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self, context)?;
@@ -939,7 +1284,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
         let multiname = self.pool_multiname(method, index, context.gc_context)?;
-        let object = self.avm2.pop().as_object()?;
+        let object = self.avm2.pop().coerce_to_object(self, context)?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
             self.avm2
@@ -1119,7 +1464,13 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname.local_name()).into());
+        .ok_or_else(|| {
+            format!(
+                "ReferenceError: Variable {} is not defined",
+                multiname.local_name().unwrap_or_else(|| "*".into())
+            )
+            .into()
+        });
         let result: Value<'gc> = found?.into();
 
         self.avm2.push(result);
@@ -1142,7 +1493,13 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname.local_name()).into());
+        .ok_or_else(|| {
+            format!(
+                "ReferenceError: Variable {} is not defined",
+                multiname.local_name().unwrap_or_else(|| "*".into())
+            )
+            .into()
+        });
         let result: Value<'gc> = found?;
 
         self.avm2.push(result);
@@ -1164,8 +1521,8 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         index: u32,
     ) -> Result<FrameControl<'gc>, Error> {
-        let object = self.avm2.pop().as_object()?;
         let value = self.avm2.pop();
+        let object = self.avm2.pop().as_object()?;
 
         object.set_slot(index, value, context.gc_context)?;
 
@@ -1214,7 +1571,13 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         let object = proto.construct(self, context, &args)?;
         ctor.call(Some(object), &args, self, context, object.proto())?;
 
-        self.avm2.push(object);
+        // Builtin primitive classes construct their primitive value, not a
+        // boxed object.
+        if let Some(primitive) = object.as_primitive() {
+            self.avm2.push(primitive);
+        } else {
+            self.avm2.push(object);
+        }
 
         Ok(FrameControl::Continue)
     }
@@ -1249,7 +1612,13 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         let object = proto.construct(self, context, &args)?;
         ctor.call(Some(object), &args, self, context, Some(proto))?;
 
-        self.avm2.push(object);
+        // Builtin primitive classes construct their primitive value, not a
+        // boxed object.
+        if let Some(primitive) = object.as_primitive() {
+            self.avm2.push(primitive);
+        } else {
+            self.avm2.push(object);
+        }
 
         Ok(FrameControl::Continue)
     }
@@ -1314,6 +1683,21 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_new_array(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        num_args: u32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let args = self.avm2.pop_args(num_args);
+        let array = ArrayStorage::from_args(&args[..]);
+        let array_obj =
+            ArrayObject::from_array(array, self.avm2.prototypes().array, context.gc_context);
+
+        self.avm2.push(array_obj);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_new_function(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1374,145 +1758,870 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_jump(
+    fn op_coerce(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        type_name_index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
-        reader.seek(offset as i64)?;
+        let value = self.avm2.pop();
+        let type_name = self.pool_multiname_static(method, type_name_index, context.gc_context)?;
+        let is_public = type_name
+            .namespace_set()
+            .any(|ns| *ns == Namespace::public_namespace());
+
+        let coerced = match type_name.local_name() {
+            Some(name) if is_public && name == "int" => value.coerce_to_i32(self, context)?.into(),
+            Some(name) if is_public && name == "uint" => value.coerce_to_u32(self, context)?.into(),
+            Some(name) if is_public && name == "Number" => {
+                value.coerce_to_number(self, context)?.into()
+            }
+            Some(name) if is_public && name == "Boolean" => value.coerce_to_boolean().into(),
+            Some(name) if is_public && name == "String" => match value {
+                Value::Undefined | Value::Null => Value::Null,
+                _ => value.coerce_to_string(self, context)?.into(),
+            },
+            _ => match value {
+                Value::Undefined => Value::Null,
+                Value::Object(object) => {
+                    // Types we don't implement yet can't be checked.
+                    let class = if let Some(scope) = self.scope() {
+                        scope
+                            .write(context.gc_context)
+                            .resolve(&type_name, self, context)?
+                    } else {
+                        None
+                    };
+
+                    if let Some(Value::Object(class)) = class {
+                        if !object.is_instance_of(self, context, class, true)? {
+                            return Err(format!(
+                                "TypeError: Type Coercion failed: cannot convert object to {}",
+                                type_name.local_name().unwrap_or_else(|| "*".into())
+                            )
+                            .into());
+                        }
+                    }
+
+                    value
+                }
+                _ => value,
+            },
+        };
+
+        self.avm2.push(coerced);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_true(
+    fn op_coerce_s(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let value = self.avm2.pop().as_bool()?;
+        let value = match self.avm2.pop() {
+            Value::Undefined | Value::Null => Value::Null,
+            value => value.coerce_to_string(self, context)?.into(),
+        };
 
-        if value {
-            reader.seek(offset as i64)?;
-        }
+        self.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_false(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        let value = self.avm2.pop().as_bool()?;
+    fn op_convert_b(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_boolean();
 
-        if !value {
-            reader.seek(offset as i64)?;
-        }
+        self.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_eq(
+    fn op_convert_d(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.avm2.pop();
-        let value1 = self.avm2.pop();
+        let value = self.avm2.pop().coerce_to_number(self, context)?;
 
-        if value1 == value2 {
-            reader.seek(offset as i64)?;
-        }
+        self.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_ne(
+    fn op_convert_i(
         &mut self,
-        offset: i32,
-        reader: &mut Reader<Cursor<&[u8]>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.avm2.pop();
-        let value1 = self.avm2.pop();
+        let value = self.avm2.pop().coerce_to_i32(self, context)?;
 
-        if value1 != value2 {
-            reader.seek(offset as i64)?;
-        }
+        self.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.avm2.pop();
-        let value1 = self.avm2.pop();
+    fn op_convert_o(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop();
 
-        self.avm2.push(value1 == value2);
+        if let Value::Undefined | Value::Null = value {
+            return Err(format!("TypeError: Cannot convert {:?} to Object", value).into());
+        }
+
+        self.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error> {
-        //TODO: After adding ints, change this to ints.
-        let cur_index = self.avm2.pop().as_number()?;
-        let object = self.avm2.pop().as_object()?;
-
-        let next_index = cur_index as u32 + 1;
+    fn op_convert_s(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_string(self, context)?;
 
-        if object.get_enumerant_name(next_index).is_some() {
-            self.avm2.push(next_index as f32);
-        } else {
-            self.avm2.push(0.0);
-        }
+        self.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_has_next_2(
+    fn op_convert_u(
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-        object_register: u32,
-        index_register: u32,
     ) -> Result<FrameControl<'gc>, Error> {
-        //TODO: After adding ints, change this to ints.
-        let cur_index = self.local_register(index_register)?.as_number()?;
-        let mut object = Some(self.local_register(object_register)?.as_object()?);
+        let value = self.avm2.pop().coerce_to_u32(self, context)?;
 
-        let mut next_index = cur_index as u32 + 1;
+        self.avm2.push(value);
 
-        while let Some(cur_object) = object {
-            if cur_object.get_enumerant_name(next_index).is_none() {
-                next_index = 1;
-                object = cur_object.proto();
-            } else {
-                break;
-            }
-        }
+        Ok(FrameControl::Continue)
+    }
 
-        if object.is_none() {
-            next_index = 0;
-        }
+    fn op_add(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
 
-        self.avm2.push(next_index != 0);
-        self.set_local_register(index_register, next_index, context.gc_context)?;
-        self.set_local_register(
-            object_register,
-            object.map(|v| v.into()).unwrap_or(Value::Null),
-            context.gc_context,
-        )?;
+        let prim_value1 = value1.coerce_to_primitive(Hint::Number, self, context)?;
+        let prim_value2 = value2.coerce_to_primitive(Hint::Number, self, context)?;
 
-        Ok(FrameControl::Continue)
-    }
+        let sum: Value<'gc> = if prim_value1.is_string() || prim_value2.is_string() {
+            let mut out_s = prim_value1.coerce_to_string(self, context)?.to_string();
+            out_s.push_str(&prim_value2.coerce_to_string(self, context)?);
 
-    fn op_next_name(&mut self) -> Result<FrameControl<'gc>, Error> {
-        //TODO: After adding ints, change this to ints.
-        let cur_index = self.avm2.pop().as_number()?;
-        let object = self.avm2.pop().as_object()?;
+            AvmString::new(context.gc_context, out_s).into()
+        } else {
+            let num1 = prim_value1.coerce_to_number(self, context)?;
+            let num2 = prim_value2.coerce_to_number(self, context)?;
 
-        let name = object
-            .get_enumerant_name(cur_index as u32)
-            .map(|n| n.local_name().into());
+            (num1 + num2).into()
+        };
 
-        self.avm2.push(name.unwrap_or(Value::Undefined));
+        self.avm2.push(sum);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_add_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_i32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1.wrapping_add(value2));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_subtract(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_number(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(value1 - value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_subtract_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_i32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1.wrapping_sub(value2));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_multiply(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_number(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(value1 * value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_multiply_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_i32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1.wrapping_mul(value2));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_divide(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_number(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(value1 / value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_modulo(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_number(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(value1 % value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_increment(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(value + 1.0);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_increment_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value.wrapping_add(1));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_decrement(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(value - 1.0);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_decrement_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value.wrapping_sub(1));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_inc_local(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: u32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self
+            .local_register(index)?
+            .coerce_to_number(self, context)?;
+
+        self.set_local_register(index, value + 1.0, context.gc_context)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_inc_local_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: u32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.local_register(index)?.coerce_to_i32(self, context)?;
+
+        self.set_local_register(index, value.wrapping_add(1), context.gc_context)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dec_local(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: u32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self
+            .local_register(index)?
+            .coerce_to_number(self, context)?;
+
+        self.set_local_register(index, value - 1.0, context.gc_context)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dec_local_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: u32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.local_register(index)?.coerce_to_i32(self, context)?;
+
+        self.set_local_register(index, value.wrapping_sub(1), context.gc_context)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_negate(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_number(self, context)?;
+
+        self.avm2.push(-value);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_negate_i(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value.wrapping_neg());
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_bitand(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_i32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1 & value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_bitnot(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(!value);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_bitor(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_i32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1 | value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_bitxor(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_i32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1 ^ value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lshift(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_u32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1 << (value2 & 0x1F));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_rshift(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_u32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        self.avm2.push(value1 >> (value2 & 0x1F));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_urshift(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop().coerce_to_u32(self, context)?;
+        let value1 = self.avm2.pop().coerce_to_u32(self, context)?;
+
+        self.avm2.push(value1 >> (value2 & 0x1F));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_not(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_boolean();
+
+        self.avm2.push(!value);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_jump(
+        &mut self,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        reader.seek(offset as i64)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_true(
+        &mut self,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_boolean();
+
+        if value {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_false(
+        &mut self,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.avm2.pop().coerce_to_boolean();
+
+        if !value {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_strict_eq(
+        &mut self,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        if value1 == value2 {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_strict_ne(
+        &mut self,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        if value1 != value2 {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_eq(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_equal = value1.abstract_eq(&value2, self, context)?;
+
+        if is_equal {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_ne(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_equal = value1.abstract_eq(&value2, self, context)?;
+
+        if !is_equal {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_ge(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_less = value1.abstract_lt(&value2, self, context)?;
+
+        if is_less == Some(false) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_gt(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_greater = value2.abstract_lt(&value1, self, context)?;
+
+        if is_greater == Some(true) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_le(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_greater = value2.abstract_lt(&value1, self, context)?;
+
+        if is_greater == Some(false) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_lt(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_less = value1.abstract_lt(&value2, self, context)?;
+
+        if is_less == Some(true) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_nge(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_less = value1.abstract_lt(&value2, self, context)?;
+
+        if is_less != Some(false) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_ngt(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_greater = value2.abstract_lt(&value1, self, context)?;
+
+        if is_greater != Some(true) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_nle(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_greater = value2.abstract_lt(&value1, self, context)?;
+
+        if is_greater != Some(false) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_if_nlt(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        offset: i32,
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let is_less = value1.abstract_lt(&value2, self, context)?;
+
+        if is_less != Some(true) {
+            reader.seek(offset as i64)?;
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lookup_switch(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        instruction_start: u64,
+        default_offset: i32,
+        case_offsets: &[i32],
+        reader: &mut Reader<Cursor<&[u8]>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let index = self.avm2.pop().coerce_to_i32(self, context)?;
+
+        // Unlike other branches, switch offsets are relative to the start of
+        // the `lookupswitch` instruction.
+        let offset = usize::try_from(index)
+            .ok()
+            .and_then(|index| case_offsets.get(index))
+            .copied()
+            .unwrap_or(default_offset);
+        let position = reader.seek(0)?;
+
+        reader.seek(instruction_start as i64 - position as i64 + offset as i64)?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_strict_equals(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        self.avm2.push(value1 == value2);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_equals(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let result = value1.abstract_eq(&value2, self, context)?;
+
+        self.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_greater_equals(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let result = value1.abstract_lt(&value2, self, context)? == Some(false);
+
+        self.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_greater_than(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let result = value2.abstract_lt(&value1, self, context)? == Some(true);
+
+        self.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_less_equals(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let result = value2.abstract_lt(&value1, self, context)? == Some(false);
+
+        self.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_less_than(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value2 = self.avm2.pop();
+        let value1 = self.avm2.pop();
+
+        let result = value1.abstract_lt(&value2, self, context)? == Some(true);
+
+        self.avm2.push(result);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_has_next(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let cur_index = self.avm2.pop().as_number()?;
+        let object = self.avm2.pop().as_object()?;
+
+        let next_index = cur_index as u32 + 1;
+
+        if object.get_enumerant_name(next_index).is_some() {
+            self.avm2.push(next_index);
+        } else {
+            self.avm2.push(0);
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_has_next_2(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object_register: u32,
+        index_register: u32,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let cur_index = self.local_register(index_register)?.as_number()?;
+        let mut object = Some(self.local_register(object_register)?.as_object()?);
+
+        let mut next_index = cur_index as u32 + 1;
+
+        while let Some(cur_object) = object {
+            if cur_object.get_enumerant_name(next_index).is_none() {
+                next_index = 1;
+                object = cur_object.proto();
+            } else {
+                break;
+            }
+        }
+
+        if object.is_none() {
+            next_index = 0;
+        }
+
+        self.avm2.push(next_index != 0);
+        self.set_local_register(index_register, next_index, context.gc_context)?;
+        self.set_local_register(
+            object_register,
+            object.map(|v| v.into()).unwrap_or(Value::Null),
+            context.gc_context,
+        )?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_next_name(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let cur_index = self.avm2.pop().as_number()?;
+        let object = self.avm2.pop().as_object()?;
+
+        let name = object
+            .get_enumerant_name(cur_index as u32)
+            .map(|n| n.local_name().into());
+
+        self.avm2.push(name.unwrap_or(Value::Undefined));
 
         Ok(FrameControl::Continue)
     }
@@ -1521,7 +2630,6 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<FrameControl<'gc>, Error> {
-        //TODO: After adding ints, change this to ints.
         let cur_index = self.avm2.pop().as_number()?;
         let mut object = self.avm2.pop().as_object()?;
 
@@ -1571,7 +2679,7 @@ impl<'a, 'gc: 'a> Activation<'a, 'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<FrameControl<'gc>, Error> {
         let type_object = self.avm2.pop().as_object()?;
-        let value = self.avm2.pop().as_object()?;
+        let value = self.avm2.pop().coerce_to_object(self, context)?;
 
         let is_instance_of = value.is_instance_of(self, context, type_object, true)?;
 
@@ -0,0 +1,127 @@
+//! Array support types
+
+use crate::avm2::value::Value;
+use gc_arena::Collect;
+
+/// The array storage portion of an array object.
+///
+/// Array values may consist of either standard `Value`s or "holes": values
+/// which are not properties of the associated object and must be resolved in
+/// the prototype.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct ArrayStorage<'gc> {
+    storage: Vec<Option<Value<'gc>>>,
+}
+
+impl<'gc> ArrayStorage<'gc> {
+    /// Construct new array storage.
+    ///
+    /// The length parameter indicates how big the array storage should start
+    /// out as. All array storage consists of holes.
+    pub fn new(length: usize) -> Self {
+        let mut storage = Vec::new();
+
+        storage.resize(length, None);
+
+        Self { storage }
+    }
+
+    /// Construct array storage from a list of values.
+    pub fn from_args(values: &[Value<'gc>]) -> Self {
+        let storage = values.iter().map(|v| Some(v.clone())).collect();
+
+        Self { storage }
+    }
+
+    /// Retrieve a value from array storage by index.
+    ///
+    /// Array holes will return `None`.
+    pub fn get(&self, item: usize) -> Option<Value<'gc>> {
+        self.storage.get(item).cloned().flatten()
+    }
+
+    /// Set an array storage slot to a particular value.
+    ///
+    /// If the item index extends beyond the length of the array, then the
+    /// array will be extended with holes.
+    pub fn set(&mut self, item: usize, value: Value<'gc>) {
+        if self.storage.len() < (item + 1) {
+            self.storage.resize(item + 1, None)
+        }
+
+        *self.storage.get_mut(item).unwrap() = Some(value)
+    }
+
+    /// Replace an array storage slot with a hole.
+    pub fn delete(&mut self, item: usize) {
+        if let Some(i) = self.storage.get_mut(item) {
+            *i = None;
+        }
+    }
+
+    /// Get the length of the array.
+    pub fn length(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Set the length of the array.
+    ///
+    /// Growing the array fills it with holes, while shrinking it discards
+    /// values past the end.
+    pub fn set_length(&mut self, size: usize) {
+        self.storage.resize(size, None)
+    }
+
+    /// Append a value to the end of the array.
+    pub fn push(&mut self, item: Value<'gc>) {
+        self.storage.push(Some(item))
+    }
+
+    /// Remove a value from the end of the array.
+    ///
+    /// This function returns `None` if the array is empty or the last element
+    /// is a hole.
+    pub fn pop(&mut self) -> Option<Value<'gc>> {
+        self.storage.pop().flatten()
+    }
+
+    /// Remove a value from the start of the array.
+    ///
+    /// This function returns `None` if the array is empty or the first
+    /// element is a hole.
+    pub fn shift(&mut self) -> Option<Value<'gc>> {
+        if self.storage.is_empty() {
+            None
+        } else {
+            self.storage.remove(0)
+        }
+    }
+
+    /// Insert a value at the start of the array.
+    pub fn unshift(&mut self, item: Value<'gc>) {
+        self.storage.insert(0, Some(item))
+    }
+
+    /// Replace a range of the array with a new set of values, returning the
+    /// values that were removed.
+    pub fn splice(
+        &mut self,
+        range: std::ops::Range<usize>,
+        replacement: &[Value<'gc>],
+    ) -> Vec<Option<Value<'gc>>> {
+        self.storage
+            .splice(range, replacement.iter().map(|v| Some(v.clone())))
+            .collect()
+    }
+
+    /// Reverse the order of the array's values in place.
+    pub fn reverse(&mut self) {
+        self.storage.reverse()
+    }
+
+    /// Iterate over array values.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = Option<Value<'gc>>> + 'a {
+        self.storage.iter().cloned()
+    }
+}
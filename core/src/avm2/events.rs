@@ -0,0 +1,545 @@
+//! Core event structure
+
+use crate::avm2::activation::Activation;
+use crate::avm2::dispatch_object::DispatchObject;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
+use gc_arena::Collect;
+use std::collections::{BTreeMap, HashMap};
+
+/// Which phase of the event dispatch process is currently running.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum EventPhase {
+    /// The event has not yet been dispatched, or has finished dispatching.
+    None = 0,
+
+    /// The event is being dispatched to the ancestors of its target, from
+    /// the outermost one inwards.
+    Capturing = 1,
+
+    /// The event is being dispatched to its target.
+    AtTarget = 2,
+
+    /// The event is being dispatched to the ancestors of its target, from
+    /// the innermost one outwards.
+    Bubbling = 3,
+}
+
+/// How far an event is allowed to propagate.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum PropagationMode {
+    /// The event may be dispatched to all remaining listeners.
+    AllowPropagation,
+
+    /// The event will not be dispatched to any further objects, but the
+    /// remaining listeners on the current object will still run.
+    Stop,
+
+    /// The event will not be dispatched to any further listeners.
+    StopImmediately,
+}
+
+/// Event-class-specific data.
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub enum EventData {
+    /// A plain `Event`.
+    Event,
+
+    /// A `MouseEvent`.
+    Mouse {
+        /// The position of the mouse, relative to the target.
+        local_x: f64,
+        local_y: f64,
+
+        /// Whether or not the primary mouse button was held down.
+        button_down: bool,
+        ctrl_key: bool,
+        alt_key: bool,
+        shift_key: bool,
+
+        /// The number of lines scrolled by the mouse wheel.
+        delta: i32,
+    },
+
+    /// A `KeyboardEvent`.
+    Keyboard {
+        /// The character value of the key, if it has one.
+        char_code: u32,
+
+        /// The Flash virtual keycode of the key.
+        key_code: u32,
+
+        /// Where the key is on the keyboard.
+        key_location: u32,
+        ctrl_key: bool,
+        alt_key: bool,
+        shift_key: bool,
+    },
+}
+
+/// Represents data fields of an event that can be fired on an object that
+/// implements `IEventDispatcher`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct Event<'gc> {
+    /// Whether or not the event "bubbles" - fires on it's parents after it
+    /// fires on the child.
+    bubbles: bool,
+
+    /// Whether or not the event has a default response that an event handler
+    /// can request to not occur.
+    cancelable: bool,
+
+    /// Whether or not the event's default response has been cancelled.
+    cancelled: bool,
+
+    /// Whether or not event propagation has stopped.
+    propagation: PropagationMode,
+
+    /// The object currently having it's event handlers invoked.
+    current_target: Option<Object<'gc>>,
+
+    /// The current event phase.
+    event_phase: EventPhase,
+
+    /// The object the event was dispatched to.
+    target: Option<Object<'gc>>,
+
+    /// The name of the event being triggered.
+    event_type: AvmString<'gc>,
+
+    /// Data specific to the class of this event.
+    event_data: EventData,
+}
+
+impl<'gc> Event<'gc> {
+    /// Construct a new event of a given type.
+    pub fn new<S>(event_type: S, event_data: EventData) -> Self
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        Event {
+            bubbles: false,
+            cancelable: false,
+            cancelled: false,
+            propagation: PropagationMode::AllowPropagation,
+            current_target: None,
+            event_phase: EventPhase::None,
+            target: None,
+            event_type: event_type.into(),
+            event_data,
+        }
+    }
+
+    /// Make a copy of this event that has not been dispatched yet.
+    pub fn duplicate(&self) -> Self {
+        let mut event = Event::new(self.event_type, self.event_data.clone());
+        event.bubbles = self.bubbles;
+        event.cancelable = self.cancelable;
+
+        event
+    }
+
+    pub fn event_type(&self) -> AvmString<'gc> {
+        self.event_type
+    }
+
+    pub fn set_event_type<S>(&mut self, event_type: S)
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        self.event_type = event_type.into();
+    }
+
+    pub fn is_bubbling(&self) -> bool {
+        self.bubbles
+    }
+
+    pub fn set_bubbles(&mut self, bubbling: bool) {
+        self.bubbles = bubbling;
+    }
+
+    pub fn is_cancelable(&self) -> bool {
+        self.cancelable
+    }
+
+    pub fn set_cancelable(&mut self, cancelable: bool) {
+        self.cancelable = cancelable;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Cancel the event's default response, if it is cancelable.
+    pub fn cancel(&mut self) {
+        if self.cancelable {
+            self.cancelled = true;
+        }
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation != PropagationMode::AllowPropagation
+    }
+
+    pub fn stop_propagation(&mut self) {
+        if self.propagation != PropagationMode::StopImmediately {
+            self.propagation = PropagationMode::Stop;
+        }
+    }
+
+    pub fn is_propagation_stopped_immediately(&self) -> bool {
+        self.propagation == PropagationMode::StopImmediately
+    }
+
+    pub fn stop_immediate_propagation(&mut self) {
+        self.propagation = PropagationMode::StopImmediately;
+    }
+
+    pub fn phase(&self) -> EventPhase {
+        self.event_phase
+    }
+
+    pub fn set_phase(&mut self, phase: EventPhase) {
+        self.event_phase = phase;
+    }
+
+    pub fn target(&self) -> Option<Object<'gc>> {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: Object<'gc>) {
+        self.target = Some(target)
+    }
+
+    pub fn current_target(&self) -> Option<Object<'gc>> {
+        self.current_target
+    }
+
+    pub fn set_current_target(&mut self, current_target: Option<Object<'gc>>) {
+        self.current_target = current_target
+    }
+
+    pub fn event_data(&self) -> &EventData {
+        &self.event_data
+    }
+
+    pub fn event_data_mut(&mut self) -> &mut EventData {
+        &mut self.event_data
+    }
+}
+
+/// A single event handler, as registered with `addEventListener`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+struct EventHandler<'gc> {
+    /// The event handler to call.
+    handler: Object<'gc>,
+
+    /// Whether or not this handler listens to the capture phase, rather than
+    /// the target and bubbling phases.
+    use_capture: bool,
+
+    /// Whether or not the handler was registered as a weak reference.
+    ///
+    /// TODO: `gc_arena` has no weak pointers, so such handlers are held
+    /// strongly like any other, and keep their closures alive. A warning is
+    /// logged whenever one is registered.
+    use_weak_reference: bool,
+}
+
+impl<'gc> EventHandler<'gc> {
+    fn is_handler(&self, handler: Object<'gc>, use_capture: bool) -> bool {
+        Object::ptr_eq(self.handler, handler) && self.use_capture == use_capture
+    }
+}
+
+/// A list of handlers, grouped by event type and then by priority.
+///
+/// Handlers of a higher priority are called first. Handlers of the same
+/// priority are called in the order they were added.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct DispatchList<'gc>(HashMap<AvmString<'gc>, BTreeMap<i32, Vec<EventHandler<'gc>>>>);
+
+impl<'gc> DispatchList<'gc> {
+    /// Construct a new dispatch list.
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Add an event handler to the dispatch list.
+    ///
+    /// Adding a handler that is already in the list for the same event type
+    /// and phase does nothing, even if the priority differs.
+    pub fn add_event_listener(
+        &mut self,
+        event_type: impl Into<AvmString<'gc>>,
+        priority: i32,
+        handler: Object<'gc>,
+        use_capture: bool,
+        use_weak_reference: bool,
+    ) {
+        let priorities = self
+            .0
+            .entry(event_type.into())
+            .or_insert_with(BTreeMap::new);
+
+        if priorities
+            .values()
+            .flatten()
+            .any(|h| h.is_handler(handler, use_capture))
+        {
+            return;
+        }
+
+        priorities
+            .entry(priority)
+            .or_insert_with(Vec::new)
+            .push(EventHandler {
+                handler,
+                use_capture,
+                use_weak_reference,
+            });
+    }
+
+    /// Remove an event handler from the dispatch list.
+    pub fn remove_event_listener(
+        &mut self,
+        event_type: impl Into<AvmString<'gc>>,
+        handler: Object<'gc>,
+        use_capture: bool,
+    ) {
+        let event_type = event_type.into();
+
+        if let Some(priorities) = self.0.get_mut(&event_type) {
+            let mut emptied = Vec::new();
+            for (priority, handlers) in priorities.iter_mut() {
+                handlers.retain(|h| !h.is_handler(handler, use_capture));
+                if handlers.is_empty() {
+                    emptied.push(*priority);
+                }
+            }
+
+            for priority in emptied {
+                priorities.remove(&priority);
+            }
+
+            if priorities.is_empty() {
+                self.0.remove(&event_type);
+            }
+        }
+    }
+
+    /// Determine if there are any handlers for a given event type.
+    pub fn has_event_listener(&self, event_type: impl Into<AvmString<'gc>>) -> bool {
+        self.0.contains_key(&event_type.into())
+    }
+
+    /// List the handlers for a given event type and phase, in the order they
+    /// should be called.
+    pub fn iter_event_handlers(
+        &self,
+        event_type: impl Into<AvmString<'gc>>,
+        use_capture: bool,
+    ) -> impl Iterator<Item = Object<'gc>> + '_ {
+        self.0
+            .get(&event_type.into())
+            .into_iter()
+            .flat_map(|priorities| priorities.values().rev().flatten())
+            .filter(move |h| h.use_capture == use_capture)
+            .map(|h| h.handler)
+    }
+}
+
+impl<'gc> Default for DispatchList<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Event types which are broadcast to every display object that listens to
+/// them, rather than dispatched along the display list.
+pub const BROADCAST_EVENTS: &[&str] = &["enterFrame"];
+
+/// The namespace of the hidden property that holds an object's dispatch list.
+const NS_EVENT_DISPATCHER: &str = "https://ruffle.rs/AS3/impl/EventDispatcher/";
+
+/// The name of the hidden property that holds an object's dispatch list.
+fn dispatch_list_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private(NS_EVENT_DISPATCHER.into()),
+        "dispatch_list",
+    )
+}
+
+/// Retrieve the dispatch list of an object, if it has one.
+pub fn existing_dispatch_list<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<Object<'gc>>, Error> {
+    let name = dispatch_list_name();
+
+    if this.has_own_property(&name)? {
+        Ok(Some(
+            this.get_property(this, &name, activation, context)?
+                .as_object()?,
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Retrieve the dispatch list of an object, creating it if it does not exist
+/// yet.
+///
+/// Objects which are only ever dispatched to, and never listened to, do not
+/// need a dispatch list; so it is created the first time it is needed.
+pub fn dispatch_list<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    if let Some(dispatch_list) = existing_dispatch_list(activation, context, this)? {
+        return Ok(dispatch_list);
+    }
+
+    let name = dispatch_list_name();
+
+    let dispatch_list = DispatchObject::empty_list(context.gc_context);
+    this.install_dynamic_property(context.gc_context, name.clone(), dispatch_list.into())?;
+    this.set_local_property_is_enumerable(context.gc_context, &name, false)?;
+
+    Ok(dispatch_list)
+}
+
+/// Call all of the event handlers on a given target.
+///
+/// The `target` is the current target of the `event`. `event` must be a valid
+/// `EventObject`, or this function will fail. Capture-phase handlers are only
+/// called during the capturing phase; other handlers are called during the
+/// target and bubbling phases.
+fn dispatch_event_to_target<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<(), Error> {
+    let dispatch_list = match existing_dispatch_list(activation, context, target)? {
+        Some(dispatch_list) => dispatch_list,
+        None => return Ok(()),
+    };
+    let (event_type, use_capture) = {
+        let mut evtmut = event
+            .as_event_mut(context.gc_context)
+            .ok_or("Attempted to dispatch a non-event object")?;
+
+        evtmut.set_current_target(Some(target));
+
+        (evtmut.event_type(), evtmut.phase() == EventPhase::Capturing)
+    };
+
+    // Handlers added or removed during dispatch do not affect the current
+    // dispatch, so the list is copied first.
+    let handlers: Vec<Object<'gc>> = dispatch_list
+        .as_dispatch()
+        .ok_or("Dispatch list is missing")?
+        .iter_event_handlers(event_type, use_capture)
+        .collect();
+
+    for handler in handlers {
+        if event
+            .as_event()
+            .map(|evt| evt.is_propagation_stopped_immediately())
+            .unwrap_or(true)
+        {
+            break;
+        }
+
+        handler.call(None, &[event.into()], activation, context, None)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch an event to an object and, if it is a display object, along the
+/// display list.
+///
+/// The event is first dispatched to the capture-phase handlers of all of the
+/// target's ancestors, starting from the outermost, then to the target, and
+/// then, if the event bubbles, to the ancestors again starting from the
+/// innermost.
+///
+/// Returns `false` if the event was cancelled.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<bool, Error> {
+    let mut ancestor_list = Vec::new();
+    let mut parent = this.as_display_object().and_then(|dobj| dobj.parent());
+    while let Some(ancestor) = parent {
+        if let Value::Object(object) = ancestor.object2() {
+            ancestor_list.push(object);
+        }
+
+        parent = ancestor.parent();
+    }
+
+    let mut evtmut = event
+        .as_event_mut(context.gc_context)
+        .ok_or("Attempted to dispatch a non-event object")?;
+
+    evtmut.set_phase(EventPhase::Capturing);
+    evtmut.set_target(this);
+
+    drop(evtmut);
+
+    for ancestor in ancestor_list.iter().rev() {
+        if event.as_event().unwrap().is_propagation_stopped() {
+            break;
+        }
+
+        dispatch_event_to_target(activation, context, *ancestor, event)?;
+    }
+
+    event
+        .as_event_mut(context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::AtTarget);
+
+    if !event.as_event().unwrap().is_propagation_stopped() {
+        dispatch_event_to_target(activation, context, this, event)?;
+    }
+
+    event
+        .as_event_mut(context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::Bubbling);
+
+    if event.as_event().unwrap().is_bubbling() {
+        for ancestor in ancestor_list.iter() {
+            if event.as_event().unwrap().is_propagation_stopped() {
+                break;
+            }
+
+            dispatch_event_to_target(activation, context, *ancestor, event)?;
+        }
+    }
+
+    let mut evtmut = event.as_event_mut(context.gc_context).unwrap();
+    evtmut.set_phase(EventPhase::None);
+    evtmut.set_current_target(None);
+
+    Ok(!evtmut.is_cancelled())
+}
@@ -100,14 +100,9 @@ impl<'gc> Executable<'gc> {
             ),
             Executable::Action(bm) => {
                 let reciever = bm.reciever.or(unbound_reciever);
+                let (avm1, avm2) = activation.avms();
                 let mut activation = Activation::from_method(
-                    activation.avm2(),
-                    context,
-                    bm.method,
-                    bm.scope,
-                    reciever,
-                    arguments,
-                    base_proto,
+                    avm1, avm2, context, bm.method, bm.scope, reciever, arguments, base_proto,
                 )?;
 
                 activation.run_actions(bm.method, context)
@@ -11,26 +11,258 @@ use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
 use gc_arena::{Collect, MutationContext};
-use std::f64::NAN;
+use std::f64::{INFINITY, NAN};
 
+mod array;
+mod boolean;
 mod class;
+pub mod error;
 mod flash;
 mod function;
+mod int;
+mod math;
+mod number;
 mod object;
+mod string;
+mod uint;
 
 fn trace<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _action_context: &mut UpdateContext<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    if let Some(s) = args.get(0) {
-        log::info!(target: "avm_trace", "{}", s.clone().coerce_string());
+    let mut message = vec![];
+    for arg in args {
+        message.push(
+            arg.coerce_to_string(activation, action_context)?
+                .to_string(),
+        );
     }
 
+    log::info!(target: "avm_trace", "{}", message.join(" "));
+
     Ok(Value::Undefined)
 }
 
+/// Implements `isNaN`
+fn is_nan<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let number = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_number(activation, action_context)?;
+
+    Ok(number.is_nan().into())
+}
+
+/// Implements `isFinite`
+fn is_finite<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let number = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_number(activation, action_context)?;
+
+    Ok(number.is_finite().into())
+}
+
+/// Implements `parseInt`
+fn parse_int<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let string = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, action_context)?;
+    let mut radix = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation, action_context)?;
+
+    let mut string = string.trim_start();
+    let sign = if let Some(rest) = string.strip_prefix('-') {
+        string = rest;
+        -1.0
+    } else {
+        string = string.strip_prefix('+').unwrap_or(string);
+        1.0
+    };
+
+    if radix == 0 || radix == 16 {
+        if let Some(rest) = string
+            .strip_prefix("0x")
+            .or_else(|| string.strip_prefix("0X"))
+        {
+            string = rest;
+            radix = 16;
+        }
+    }
+
+    if radix == 0 {
+        radix = 10;
+    } else if radix < 2 || radix > 36 {
+        return Ok(NAN.into());
+    }
+
+    let mut result: Option<f64> = None;
+    for c in string.chars() {
+        match c.to_digit(radix as u32) {
+            Some(digit) => {
+                result = Some(result.unwrap_or(0.0) * f64::from(radix) + f64::from(digit))
+            }
+            None => break,
+        }
+    }
+
+    Ok(result.map(|n| sign * n).unwrap_or(NAN).into())
+}
+
+/// Implements `parseFloat`
+fn parse_float<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let string = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, action_context)?;
+    let string = string.trim_start();
+    let bytes = string.as_bytes();
+
+    // Find the longest prefix of the string which is a decimal literal.
+    let mut end = 0;
+    if matches!(bytes.get(end), Some(b'+') | Some(b'-')) {
+        end += 1;
+    }
+
+    if string[end..].starts_with("Infinity") {
+        return Ok(if bytes[0] == b'-' {
+            std::f64::NEG_INFINITY
+        } else {
+            std::f64::INFINITY
+        }
+        .into());
+    }
+
+    let mut has_digits = false;
+    let mut has_point = false;
+    while let Some(c) = bytes.get(end) {
+        match c {
+            b'0'..=b'9' => has_digits = true,
+            b'.' if !has_point => has_point = true,
+            _ => break,
+        }
+        end += 1;
+    }
+
+    if !has_digits {
+        return Ok(NAN.into());
+    }
+
+    // Only consume an exponent if it has at least one digit.
+    if matches!(bytes.get(end), Some(b'e') | Some(b'E')) {
+        let mut exponent_end = end + 1;
+        if matches!(bytes.get(exponent_end), Some(b'+') | Some(b'-')) {
+            exponent_end += 1;
+        }
+
+        if matches!(bytes.get(exponent_end), Some(b'0'..=b'9')) {
+            while matches!(bytes.get(exponent_end), Some(b'0'..=b'9')) {
+                exponent_end += 1;
+            }
+            end = exponent_end;
+        }
+    }
+
+    Ok(string[..end].parse().unwrap_or(NAN).into())
+}
+
+/// Implements `escape`
+///
+/// All characters except ASCII letters, digits, and `@-_.*+/` are replaced
+/// with `%XX` or `%uXXXX` escape sequences.
+fn escape<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let string = match args.get(0) {
+        None | Some(Value::Undefined) => return Ok("undefined".into()),
+        Some(value) => value.coerce_to_string(activation, action_context)?,
+    };
+
+    let mut out = String::with_capacity(string.len());
+    for unit in string.encode_utf16() {
+        match unit {
+            0x30..=0x39 | 0x41..=0x5A | 0x61..=0x7A => out.push(unit as u8 as char),
+            0x40 | 0x2D | 0x5F | 0x2E | 0x2A | 0x2B | 0x2F => out.push(unit as u8 as char),
+            0..=0xFF => out.push_str(&format!("%{:02X}", unit)),
+            _ => out.push_str(&format!("%u{:04X}", unit)),
+        }
+    }
+
+    Ok(AvmString::new(action_context.gc_context, out).into())
+}
+
+/// Implements `unescape`
+fn unescape<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let string = match args.get(0) {
+        None | Some(Value::Undefined) => return Ok("undefined".into()),
+        Some(value) => value.coerce_to_string(activation, action_context)?,
+    };
+
+    let units: Vec<u16> = string.encode_utf16().collect();
+    let mut out = Vec::with_capacity(units.len());
+    let mut i = 0;
+    while i < units.len() {
+        let hex_at = |start: usize, len: usize| {
+            units
+                .get(start..start + len)
+                .and_then(|digits| String::from_utf16(digits).ok())
+                .and_then(|digits| u16::from_str_radix(&digits, 16).ok())
+        };
+
+        if units[i] == u16::from(b'%') {
+            if units.get(i + 1) == Some(&u16::from(b'u')) {
+                if let Some(unit) = hex_at(i + 2, 4) {
+                    out.push(unit);
+                    i += 6;
+                    continue;
+                }
+            } else if let Some(unit) = hex_at(i + 1, 2) {
+                out.push(unit);
+                i += 3;
+                continue;
+            }
+        }
+
+        out.push(units[i]);
+        i += 1;
+    }
+
+    Ok(AvmString::new(action_context.gc_context, String::from_utf16_lossy(&out)).into())
+}
+
 /// This structure represents all system builtins' prototypes.
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
@@ -38,6 +270,18 @@ pub struct SystemPrototypes<'gc> {
     pub object: Object<'gc>,
     pub function: Object<'gc>,
     pub class: Object<'gc>,
+    pub string: Object<'gc>,
+    pub boolean: Object<'gc>,
+    pub number: Object<'gc>,
+    pub int: Object<'gc>,
+    pub uint: Object<'gc>,
+    pub array: Object<'gc>,
+    pub display_object: Object<'gc>,
+    pub movieclip: Object<'gc>,
+    pub event: Object<'gc>,
+    pub mouse_event: Object<'gc>,
+    pub keyboard_event: Object<'gc>,
+    pub loader_info: Object<'gc>,
 }
 
 /// Add a free-function builtin to the global scope.
@@ -59,6 +303,9 @@ fn function<'gc>(
 }
 
 /// Add a class builtin to the global scope.
+///
+/// The class's constructor is returned, so that static properties can be
+/// added to it.
 fn class<'gc>(
     mc: MutationContext<'gc, '_>,
     mut global_scope: Object<'gc>,
@@ -67,16 +314,80 @@ fn class<'gc>(
     constr: NativeMethod<'gc>,
     proto: Object<'gc>,
     fn_proto: Object<'gc>,
-) {
+) -> Object<'gc> {
+    let constr = FunctionObject::from_builtin_constr(mc, constr, proto, fn_proto).unwrap();
+
     global_scope
         .install_dynamic_property(
             mc,
             QName::new(Namespace::package(package), name),
-            FunctionObject::from_builtin_constr(mc, constr, proto, fn_proto)
-                .unwrap()
-                .into(),
+            constr.into(),
+        )
+        .unwrap();
+
+    constr
+}
+
+/// Add a builtin method to a prototype.
+///
+/// The method is installed under both the public namespace, for dynamic
+/// lookups, and the `AS3` namespace, which compiled code uses to call methods
+/// on receivers of a known builtin type.
+fn method<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut proto: Object<'gc>,
+    name: &'static str,
+    nf: NativeMethod<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    let function = FunctionObject::from_builtin(mc, nf, fn_proto);
+
+    proto.install_method(
+        mc,
+        QName::new(Namespace::public_namespace(), name),
+        0,
+        function,
+    );
+    proto.install_method(
+        mc,
+        QName::new(Namespace::as3_namespace(), name),
+        0,
+        function,
+    );
+}
+
+/// Add a builtin accessor property to a prototype.
+///
+/// Read-only properties have no setter.
+fn property<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut proto: Object<'gc>,
+    name: &'static str,
+    getter: NativeMethod<'gc>,
+    setter: Option<NativeMethod<'gc>>,
+    fn_proto: Object<'gc>,
+) {
+    let name = QName::new(Namespace::public_namespace(), name);
+
+    proto
+        .install_getter(
+            mc,
+            name.clone(),
+            0,
+            FunctionObject::from_builtin(mc, getter, fn_proto),
         )
         .unwrap();
+
+    if let Some(setter) = setter {
+        proto
+            .install_setter(
+                mc,
+                name,
+                0,
+                FunctionObject::from_builtin(mc, setter, fn_proto),
+            )
+            .unwrap();
+    }
 }
 
 /// Add a builtin constant to the global scope.
@@ -133,10 +444,103 @@ pub fn construct_global_scope<'gc>(
         class_proto,
         fn_proto,
     );
+
+    let string_proto = string::create_proto(mc, object_proto, fn_proto);
+    let boolean_proto = boolean::create_proto(mc, object_proto, fn_proto);
+    let number_proto = number::create_proto(mc, object_proto, fn_proto);
+    let int_proto = int::create_proto(mc, object_proto, fn_proto);
+    let uint_proto = uint::create_proto(mc, object_proto, fn_proto);
+    let array_proto = array::create_proto(mc, object_proto, fn_proto);
+
+    let string_class = class(
+        mc,
+        gs,
+        "",
+        "String",
+        string::constructor,
+        string_proto,
+        fn_proto,
+    );
+    string::fill_class(mc, string_class, fn_proto);
+    class(
+        mc,
+        gs,
+        "",
+        "Boolean",
+        boolean::constructor,
+        boolean_proto,
+        fn_proto,
+    );
+    let number_class = class(
+        mc,
+        gs,
+        "",
+        "Number",
+        number::constructor,
+        number_proto,
+        fn_proto,
+    );
+    number::fill_class(mc, number_class);
+    let int_class = class(mc, gs, "", "int", int::constructor, int_proto, fn_proto);
+    int::fill_class(mc, int_class);
+    let uint_class = class(mc, gs, "", "uint", uint::constructor, uint_proto, fn_proto);
+    uint::fill_class(mc, uint_class);
+    let array_class = class(
+        mc,
+        gs,
+        "",
+        "Array",
+        array::constructor,
+        array_proto,
+        fn_proto,
+    );
+    array::fill_class(mc, array_class);
+    let math_class = class(
+        mc,
+        gs,
+        "",
+        "Math",
+        math::constructor,
+        ScriptObject::object(mc, object_proto),
+        fn_proto,
+    );
+    math::fill_class(mc, math_class, fn_proto);
+
     function(mc, gs, "", "trace", trace, fn_proto);
+    function(mc, gs, "", "isNaN", is_nan, fn_proto);
+    function(mc, gs, "", "isFinite", is_finite, fn_proto);
+    function(mc, gs, "", "parseInt", parse_int, fn_proto);
+    function(mc, gs, "", "parseFloat", parse_float, fn_proto);
+    function(mc, gs, "", "escape", escape, fn_proto);
+    function(mc, gs, "", "unescape", unescape, fn_proto);
     constant(mc, gs, "", "undefined", Value::Undefined);
     constant(mc, gs, "", "null", Value::Null);
     constant(mc, gs, "", "NaN", NAN.into());
+    constant(mc, gs, "", "Infinity", INFINITY.into());
+
+    let error_proto = error::create_proto(mc, object_proto, fn_proto);
+
+    class(
+        mc,
+        gs,
+        "",
+        "Error",
+        error::constructor,
+        error_proto,
+        fn_proto,
+    );
+
+    for name in error::SUBCLASSES {
+        class(
+            mc,
+            gs,
+            "",
+            *name,
+            error::constructor,
+            error::create_subclass_proto(mc, error_proto, name),
+            fn_proto,
+        );
+    }
 
     // package `flash.events`
     let eventdispatcher_proto =
@@ -152,6 +556,41 @@ pub fn construct_global_scope<'gc>(
         fn_proto,
     );
 
+    let event_proto = flash::events::event::create_proto(mc, object_proto, fn_proto);
+    let mouseevent_proto = flash::events::mouseevent::create_proto(mc, event_proto, fn_proto);
+    let keyboardevent_proto = flash::events::keyboardevent::create_proto(mc, event_proto, fn_proto);
+
+    let event_class = class(
+        mc,
+        gs,
+        "flash.events",
+        "Event",
+        flash::events::event::constructor,
+        event_proto,
+        fn_proto,
+    );
+    flash::events::event::fill_class(mc, event_class);
+    let mouseevent_class = class(
+        mc,
+        gs,
+        "flash.events",
+        "MouseEvent",
+        flash::events::mouseevent::constructor,
+        mouseevent_proto,
+        fn_proto,
+    );
+    flash::events::mouseevent::fill_class(mc, mouseevent_class);
+    let keyboardevent_class = class(
+        mc,
+        gs,
+        "flash.events",
+        "KeyboardEvent",
+        flash::events::keyboardevent::constructor,
+        keyboardevent_proto,
+        fn_proto,
+    );
+    flash::events::keyboardevent::fill_class(mc, keyboardevent_class);
+
     // package `flash.display`
     let displayobject_proto =
         flash::display::displayobject::create_proto(mc, eventdispatcher_proto, fn_proto);
@@ -187,7 +626,7 @@ pub fn construct_global_scope<'gc>(
         "flash.display",
         "DisplayObjectContainer",
         flash::display::displayobjectcontainer::constructor,
-        sprite_proto,
+        displayobjectcontainer_proto,
         fn_proto,
     );
     class(
@@ -208,11 +647,66 @@ pub fn construct_global_scope<'gc>(
         movieclip_proto,
         fn_proto,
     );
+    let blendmode_class = class(
+        mc,
+        gs,
+        "flash.display",
+        "BlendMode",
+        flash::display::blendmode::constructor,
+        flash::display::blendmode::create_proto(mc, object_proto, fn_proto),
+        fn_proto,
+    );
+    flash::display::blendmode::fill_class(mc, blendmode_class);
+    let loaderinfo_proto =
+        flash::display::loaderinfo::create_proto(mc, eventdispatcher_proto, fn_proto);
+    class(
+        mc,
+        gs,
+        "flash.display",
+        "LoaderInfo",
+        flash::display::loaderinfo::constructor,
+        loaderinfo_proto,
+        fn_proto,
+    );
+
+    // package `flash.external`
+    let externalinterface_class = class(
+        mc,
+        gs,
+        "flash.external",
+        "ExternalInterface",
+        flash::external::externalinterface::constructor,
+        ScriptObject::object(mc, object_proto),
+        fn_proto,
+    );
+    flash::external::externalinterface::fill_class(mc, externalinterface_class, fn_proto);
+
+    // package `flash.system`
+    function(
+        mc,
+        gs,
+        "flash.system",
+        "fscommand",
+        flash::system::fscommand,
+        fn_proto,
+    );
 
     let system_prototypes = SystemPrototypes {
         object: object_proto,
         function: fn_proto,
         class: class_proto,
+        string: string_proto,
+        boolean: boolean_proto,
+        number: number_proto,
+        int: int_proto,
+        uint: uint_proto,
+        array: array_proto,
+        display_object: displayobject_proto,
+        movieclip: movieclip_proto,
+        event: event_proto,
+        mouse_event: mouseevent_proto,
+        keyboard_event: keyboardevent_proto,
+        loader_info: loaderinfo_proto,
     };
 
     (gs, system_prototypes)
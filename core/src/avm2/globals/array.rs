@@ -0,0 +1,842 @@
+//! `Array` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::array_object::ArrayObject;
+use crate::avm2::function::FunctionObject;
+use crate::avm2::globals::method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::string_utils::swf_string_cmp_ignore_case;
+use gc_arena::MutationContext;
+use std::cmp::Ordering;
+
+// Flags used by `Array.sort` and `sortOn`.
+const CASE_INSENSITIVE: u32 = 1;
+const DESCENDING: u32 = 2;
+const UNIQUE_SORT: u32 = 4;
+const RETURN_INDEXED_ARRAY: u32 = 8;
+const NUMERIC: u32 = 16;
+
+/// Implements `Array`
+///
+/// When called as a function, this constructs a new array.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let storage = match args {
+        [length] if length.is_number() => {
+            let length = length.as_number()?;
+
+            if length < 0.0 || length.fract() != 0.0 || length > f64::from(std::u32::MAX) {
+                return Err(format!(
+                    "RangeError: Array index is not a positive integer ({}).",
+                    length
+                )
+                .into());
+            }
+
+            ArrayStorage::new(length as usize)
+        }
+        _ => ArrayStorage::from_args(args),
+    };
+
+    if let Some(this) = this {
+        if let Some(mut array) = this.as_array_storage_mut(context.gc_context) {
+            *array = storage;
+
+            return Ok(Value::Undefined);
+        }
+    }
+
+    Ok(new_array(activation, context, storage).into())
+}
+
+/// Construct a new instance of the system `Array` class.
+fn new_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    storage: ArrayStorage<'gc>,
+) -> Object<'gc> {
+    ArrayObject::from_array(
+        storage,
+        activation.avm2().prototypes().array,
+        context.gc_context,
+    )
+}
+
+/// Copy the elements of `this` out of the array, treating holes as
+/// `undefined`.
+///
+/// Methods that call back into user code must not hold a borrow on the array
+/// while doing so.
+fn array_values<'gc>(this: Option<Object<'gc>>) -> Vec<Value<'gc>> {
+    this.and_then(|this| {
+        this.as_array_storage().map(|array| {
+            array
+                .iter()
+                .map(|v| v.unwrap_or(Value::Undefined))
+                .collect()
+        })
+    })
+    .unwrap_or_default()
+}
+
+/// Retrieve the length of `this`, or zero if it is not an array.
+fn array_length(this: Option<Object<'_>>) -> usize {
+    this.and_then(|this| this.as_array_storage().map(|array| array.length()))
+        .unwrap_or(0)
+}
+
+/// Normalizes an index parameter used in `Array` functions such as `slice`.
+/// Negative values will count backwards from `len`.
+/// The returned index will be within the range of `[0, len]`.
+fn wrapping_index(i: f64, len: usize) -> usize {
+    let i = if i < 0.0 { len as f64 + i.trunc() } else { i };
+
+    if i.is_nan() || i < 0.0 {
+        0
+    } else {
+        i.min(len as f64) as usize
+    }
+}
+
+/// Retrieve a numerical argument, or the given default if it is missing or
+/// `undefined`.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    match args.get(index) {
+        None | Some(Value::Undefined) => Ok(default),
+        Some(value) => value.coerce_to_number(activation, context),
+    }
+}
+
+/// Implements `Array.length`'s getter
+fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((array_length(this) as u32).into())
+}
+
+/// Implements `Array.length`'s setter
+fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let size = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_u32(activation, context)?;
+
+    if let Some(mut array) = this
+        .as_ref()
+        .and_then(|this| this.as_array_storage_mut(context.gc_context))
+    {
+        array.set_length(size as usize);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.prototype.join`
+fn join<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let separator = match args.get(0) {
+        None | Some(Value::Undefined) => ",".into(),
+        Some(separator) => separator.coerce_to_string(activation, context)?,
+    };
+
+    let mut pieces = vec![];
+    for value in array_values(this) {
+        match value {
+            Value::Undefined | Value::Null => pieces.push("".to_string()),
+            value => pieces.push(value.coerce_to_string(activation, context)?.to_string()),
+        }
+    }
+
+    Ok(AvmString::new(context.gc_context, pieces.join(&separator)).into())
+}
+
+/// Implements `Array.prototype.toString` and `toLocaleString`
+fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    join(activation, context, this, &[])
+}
+
+/// Implements `Array.prototype.push`
+fn push<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut array) = this
+        .as_ref()
+        .and_then(|this| this.as_array_storage_mut(context.gc_context))
+    {
+        for arg in args {
+            array.push(arg.clone());
+        }
+
+        return Ok((array.length() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.prototype.pop`
+fn pop<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(this
+        .as_ref()
+        .and_then(|this| this.as_array_storage_mut(context.gc_context))
+        .and_then(|mut array| array.pop())
+        .unwrap_or(Value::Undefined))
+}
+
+/// Implements `Array.prototype.shift`
+fn shift<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(this
+        .as_ref()
+        .and_then(|this| this.as_array_storage_mut(context.gc_context))
+        .and_then(|mut array| array.shift())
+        .unwrap_or(Value::Undefined))
+}
+
+/// Implements `Array.prototype.unshift`
+fn unshift<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut array) = this
+        .as_ref()
+        .and_then(|this| this.as_array_storage_mut(context.gc_context))
+    {
+        for arg in args.iter().rev() {
+            array.unshift(arg.clone());
+        }
+
+        return Ok((array.length() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.prototype.concat`
+fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut values = array_values(this);
+
+    for arg in args {
+        if let Value::Object(other) = arg {
+            if other.as_array_storage().is_some() {
+                values.extend(array_values(Some(*other)));
+                continue;
+            }
+        }
+
+        values.push(arg.clone());
+    }
+
+    Ok(new_array(activation, context, ArrayStorage::from_args(&values)).into())
+}
+
+/// Implements `Array.prototype.slice`
+fn slice<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = array_values(this);
+    let start = wrapping_index(number_arg(activation, context, args, 0, 0.0)?, values.len());
+    let end = wrapping_index(
+        number_arg(activation, context, args, 1, f64::from(std::u32::MAX))?,
+        values.len(),
+    );
+    let values = if start < end {
+        &values[start..end]
+    } else {
+        &[]
+    };
+
+    Ok(new_array(activation, context, ArrayStorage::from_args(values)).into())
+}
+
+/// Implements `Array.prototype.splice`
+fn splice<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if args.is_empty() {
+        return Ok(Value::Undefined);
+    }
+
+    let length = array_length(this);
+    let start = wrapping_index(number_arg(activation, context, args, 0, 0.0)?, length);
+    let delete_count = number_arg(activation, context, args, 1, (length - start) as f64)?;
+    let end = start + wrapping_index(delete_count.max(0.0), length - start);
+
+    let removed = match this
+        .as_ref()
+        .and_then(|this| this.as_array_storage_mut(context.gc_context))
+    {
+        Some(mut array) => array.splice(start..end, args.get(2..).unwrap_or(&[])),
+        None => return Ok(Value::Undefined),
+    };
+    let removed: Vec<Value<'gc>> = removed
+        .into_iter()
+        .map(|v| v.unwrap_or(Value::Undefined))
+        .collect();
+
+    Ok(new_array(activation, context, ArrayStorage::from_args(&removed)).into())
+}
+
+/// Implements `Array.prototype.reverse`
+fn reverse<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut array) = this.as_array_storage_mut(context.gc_context) {
+            array.reverse();
+        }
+
+        return Ok(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.prototype.indexOf`
+fn index_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = array_values(this);
+    let search = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let from = wrapping_index(number_arg(activation, context, args, 1, 0.0)?, values.len());
+
+    Ok(values
+        .iter()
+        .enumerate()
+        .skip(from)
+        .find(|(_, v)| **v == search)
+        .map(|(i, _)| i as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `Array.prototype.lastIndexOf`
+fn last_index_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = array_values(this);
+    let search = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let from = number_arg(activation, context, args, 1, f64::from(std::i32::MAX))?.trunc();
+    let from = if from < 0.0 {
+        values.len() as f64 + from
+    } else {
+        from
+    };
+
+    if values.is_empty() || from.is_nan() || from < 0.0 {
+        return Ok((-1).into());
+    }
+
+    let last = from.min((values.len() - 1) as f64) as usize;
+
+    Ok(values
+        .iter()
+        .enumerate()
+        .take(last + 1)
+        .rev()
+        .find(|(_, v)| **v == search)
+        .map(|(i, _)| i as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Call the callback function given to `every`, `filter`, `forEach`, `map`,
+/// or `some` on each element of the array.
+///
+/// `visit` is given each element and the value the callback returned for it,
+/// and returns `false` to stop iterating early.
+fn for_each_element<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    mut visit: impl FnMut(Value<'gc>, Value<'gc>) -> bool,
+) -> Result<(), Error> {
+    let callback = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => return Ok(()),
+        Some(callback) => callback.as_object()?,
+    };
+    let receiver = args.get(1).and_then(|r| r.as_object().ok());
+    let base_proto = receiver.and_then(|r| r.proto());
+    let this_value = this.map(Value::from).unwrap_or(Value::Undefined);
+
+    for (i, item) in array_values(this).into_iter().enumerate() {
+        let result = callback.call(
+            receiver,
+            &[item.clone(), (i as i32).into(), this_value.clone()],
+            activation,
+            context,
+            base_proto,
+        )?;
+
+        if !visit(item, result) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Implements `Array.prototype.every`
+fn every<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut is_every = true;
+
+    for_each_element(activation, context, this, args, |_, result| {
+        is_every = result.coerce_to_boolean();
+        is_every
+    })?;
+
+    Ok(is_every.into())
+}
+
+/// Implements `Array.prototype.some`
+fn some<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut is_some = false;
+
+    for_each_element(activation, context, this, args, |_, result| {
+        is_some = result.coerce_to_boolean();
+        !is_some
+    })?;
+
+    Ok(is_some.into())
+}
+
+/// Implements `Array.prototype.forEach`
+fn for_each<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    for_each_element(activation, context, this, args, |_, _| true)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.prototype.map`
+fn map<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut results = vec![];
+
+    for_each_element(activation, context, this, args, |_, result| {
+        results.push(result);
+        true
+    })?;
+
+    Ok(new_array(activation, context, ArrayStorage::from_args(&results)).into())
+}
+
+/// Implements `Array.prototype.filter`
+fn filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut results = vec![];
+
+    for_each_element(activation, context, this, args, |item, result| {
+        if result.coerce_to_boolean() {
+            results.push(item);
+        }
+        true
+    })?;
+
+    Ok(new_array(activation, context, ArrayStorage::from_args(&results)).into())
+}
+
+/// Compare two values as `Array.sort` would with the given flags.
+///
+/// The `DESCENDING` flag is not handled here.
+fn compare_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    a: &Value<'gc>,
+    b: &Value<'gc>,
+    flags: u32,
+) -> Result<Ordering, Error> {
+    if flags & NUMERIC != 0 {
+        let a = a.coerce_to_number(activation, context)?;
+        let b = b.coerce_to_number(activation, context)?;
+
+        Ok(a.partial_cmp(&b).unwrap_or(Ordering::Equal))
+    } else {
+        let a = a.coerce_to_string(activation, context)?;
+        let b = b.coerce_to_string(activation, context)?;
+
+        if flags & CASE_INSENSITIVE != 0 {
+            Ok(swf_string_cmp_ignore_case(&a, &b))
+        } else {
+            Ok(a.as_str().cmp(b.as_str()))
+        }
+    }
+}
+
+/// Sort the elements of `this` with a comparison function, and then either
+/// store them back into the array or return their indices, depending on
+/// `flags`.
+///
+/// `undefined` values and holes are always sorted to the end of the array.
+fn sort_elements<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    flags: u32,
+    mut compare: impl FnMut(
+        &mut Activation<'_, 'gc>,
+        &mut UpdateContext<'_, 'gc, '_>,
+        &Value<'gc>,
+        &Value<'gc>,
+    ) -> Result<Ordering, Error>,
+) -> Result<Value<'gc>, Error> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+
+    let (mut values, undefined): (Vec<_>, Vec<_>) = array_values(Some(this))
+        .into_iter()
+        .enumerate()
+        .partition(|(_, v)| *v != Value::Undefined);
+
+    let mut is_unique = true;
+    let mut error = None;
+
+    values.sort_by(|(_, a), (_, b)| {
+        if error.is_some() {
+            return Ordering::Equal;
+        }
+
+        match compare(activation, context, a, b) {
+            Ok(ordering) => {
+                if ordering == Ordering::Equal {
+                    is_unique = false;
+                }
+                ordering
+            }
+            Err(e) => {
+                error = Some(e);
+                Ordering::Equal
+            }
+        }
+    });
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    if flags & UNIQUE_SORT != 0 && !is_unique {
+        return Ok(0.into());
+    }
+
+    values.extend(undefined);
+
+    if flags & RETURN_INDEXED_ARRAY != 0 {
+        // Array.RETURNINDEXEDARRAY returns an array containing the sorted
+        // indices, and does not modify the original array.
+        let indices: Vec<Value<'gc>> = values.iter().map(|(i, _)| (*i as i32).into()).collect();
+
+        return Ok(new_array(activation, context, ArrayStorage::from_args(&indices)).into());
+    }
+
+    if let Some(mut array) = this.as_array_storage_mut(context.gc_context) {
+        for (i, (_, value)) in values.into_iter().enumerate() {
+            array.set(i, value);
+        }
+    }
+
+    Ok(this.into())
+}
+
+/// Implements `Array.prototype.sort`
+fn sort<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Overloads:
+    // 1) a.sort(flags: uint = 0): Sorts with the given flags.
+    // 2) a.sort(compare_fn: Function, flags: uint = 0): Sorts using the given compare function and flags.
+    let (compare_fn, flags) = match args {
+        [Value::Object(f), rest @ ..] => (Some(*f), rest.get(0)),
+        _ => (None, args.get(0)),
+    };
+    let flags = match flags {
+        Some(flags) => flags.coerce_to_u32(activation, context)?,
+        None => 0,
+    };
+    let descending = flags & DESCENDING != 0;
+
+    sort_elements(
+        activation,
+        context,
+        this,
+        flags,
+        |activation, context, a, b| {
+            let ordering = if let Some(compare_fn) = compare_fn {
+                let result = compare_fn
+                    .call(None, &[a.clone(), b.clone()], activation, context, None)?
+                    .coerce_to_number(activation, context)?;
+
+                result.partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+            } else {
+                compare_values(activation, context, a, b, flags)?
+            };
+
+            Ok(if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            })
+        },
+    )
+}
+
+/// Retrieve the value of a field of an array element for `sortOn`.
+fn field_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    value: &Value<'gc>,
+    name: &QName<'gc>,
+) -> Result<Value<'gc>, Error> {
+    match value {
+        Value::Undefined | Value::Null => Ok(Value::Undefined),
+        value => {
+            let mut object = value.coerce_to_object(activation, context)?;
+
+            object.get_property(object, name, activation, context)
+        }
+    }
+}
+
+/// Implements `Array.prototype.sortOn`
+fn sort_on<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // a.sortOn(field_name, flags: uint = 0): Sorts with the given flags.
+    // a.sortOn(field_names: Array, flags: uint = 0): Sorts with fields in order of precedence with the given flags.
+    // a.sortOn(field_names: Array, flags: Array): Sorts with fields in order of precedence with the given flags respectively.
+    let field_values = match args.get(0) {
+        Some(Value::Object(array)) if array.as_array_storage().is_some() => {
+            array_values(Some(*array))
+        }
+        Some(field_name) => vec![field_name.clone()],
+        None => return Ok(Value::Undefined),
+    };
+
+    let mut fields = vec![];
+    for field in field_values {
+        fields.push(QName::dynamic_name(
+            field.coerce_to_string(activation, context)?,
+        ));
+    }
+
+    // Bail out if we don't have any fields.
+    if fields.is_empty() {
+        return Ok(this.map(Value::from).unwrap_or(Value::Undefined));
+    }
+
+    let mut flags = vec![];
+    match args.get(1) {
+        Some(Value::Object(array)) if array.as_array_storage().is_some() => {
+            let flag_values = array_values(Some(*array));
+
+            // If the lengths of the flags and fields array do not match, the
+            // flags array is ignored.
+            if flag_values.len() == fields.len() {
+                for flag in flag_values {
+                    flags.push(flag.coerce_to_u32(activation, context)?);
+                }
+            } else {
+                flags.resize(fields.len(), 0);
+            }
+        }
+        Some(flag) => {
+            let flag = flag.coerce_to_u32(activation, context)?;
+            flags.resize(fields.len(), flag);
+        }
+        None => flags.resize(fields.len(), 0),
+    }
+
+    // UNIQUESORT and RETURNINDEXEDARRAY are taken from the first set of
+    // flags in the array.
+    let main_flags = flags[0];
+
+    sort_elements(
+        activation,
+        context,
+        this,
+        main_flags,
+        |activation, context, a, b| {
+            for (name, flags) in fields.iter().zip(flags.iter()) {
+                let a_field = field_value(activation, context, a, name)?;
+                let b_field = field_value(activation, context, b, name)?;
+                let ordering = compare_values(activation, context, &a_field, &b_field, *flags)?;
+
+                if ordering != Ordering::Equal {
+                    return Ok(if flags & DESCENDING != 0 {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    });
+                }
+            }
+
+            // Got through all fields; must be equal.
+            Ok(Ordering::Equal)
+        },
+    )
+}
+
+/// Construct `Array.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut proto = ArrayObject::construct(mc, super_proto);
+
+    proto
+        .install_getter(
+            mc,
+            QName::new(Namespace::public_namespace(), "length"),
+            0,
+            FunctionObject::from_builtin(mc, length, fn_proto),
+        )
+        .unwrap();
+    proto
+        .install_setter(
+            mc,
+            QName::new(Namespace::public_namespace(), "length"),
+            0,
+            FunctionObject::from_builtin(mc, set_length, fn_proto),
+        )
+        .unwrap();
+
+    method(mc, proto, "concat", concat, fn_proto);
+    method(mc, proto, "every", every, fn_proto);
+    method(mc, proto, "filter", filter, fn_proto);
+    method(mc, proto, "forEach", for_each, fn_proto);
+    method(mc, proto, "indexOf", index_of, fn_proto);
+    method(mc, proto, "join", join, fn_proto);
+    method(mc, proto, "lastIndexOf", last_index_of, fn_proto);
+    method(mc, proto, "map", map, fn_proto);
+    method(mc, proto, "pop", pop, fn_proto);
+    method(mc, proto, "push", push, fn_proto);
+    method(mc, proto, "reverse", reverse, fn_proto);
+    method(mc, proto, "shift", shift, fn_proto);
+    method(mc, proto, "slice", slice, fn_proto);
+    method(mc, proto, "some", some, fn_proto);
+    method(mc, proto, "sort", sort, fn_proto);
+    method(mc, proto, "sortOn", sort_on, fn_proto);
+    method(mc, proto, "splice", splice, fn_proto);
+    method(mc, proto, "toLocaleString", to_string, fn_proto);
+    method(mc, proto, "toString", to_string, fn_proto);
+    method(mc, proto, "unshift", unshift, fn_proto);
+
+    proto
+}
+
+/// Install the sorting constants of the `Array` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, mut class: Object<'gc>) {
+    let constants: [(&'static str, u32); 5] = [
+        ("CASEINSENSITIVE", CASE_INSENSITIVE),
+        ("DESCENDING", DESCENDING),
+        ("NUMERIC", NUMERIC),
+        ("RETURNINDEXEDARRAY", RETURN_INDEXED_ARRAY),
+        ("UNIQUESORT", UNIQUE_SORT),
+    ];
+
+    for (name, value) in constants.iter() {
+        class.install_const(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            (*value).into(),
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! `Boolean` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::method;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::primitive_object::PrimitiveObject;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+
+/// Implements `Boolean`
+///
+/// When called as a function, this converts its argument to a boolean.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value: Value<'gc> = args
+        .get(0)
+        .map(|v| v.coerce_to_boolean())
+        .unwrap_or(false)
+        .into();
+
+    if let Some(Object::PrimitiveObject(this)) = this {
+        this.set_primitive(context.gc_context, value.clone());
+    }
+
+    Ok(value)
+}
+
+/// Retrieve the boolean value of `this`.
+fn this_boolean(this: Option<Object<'_>>) -> Result<bool, Error> {
+    match this.and_then(|this| this.as_primitive()) {
+        Some(Value::Bool(b)) => Ok(b),
+        _ => Err("TypeError: Boolean method called on an incompatible object".into()),
+    }
+}
+
+/// Implements `Boolean.prototype.toString`
+fn to_string<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(if this_boolean(this)? { "true" } else { "false" }.into())
+}
+
+/// Implements `Boolean.prototype.valueOf`
+fn value_of<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(this_boolean(this)?.into())
+}
+
+/// Construct `Boolean.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let proto = PrimitiveObject::from_primitive(false.into(), super_proto, mc).unwrap();
+
+    method(mc, proto, "toString", to_string, fn_proto);
+    method(mc, proto, "valueOf", value_of, fn_proto);
+
+    proto
+}
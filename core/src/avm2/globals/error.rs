@@ -0,0 +1,179 @@
+//! `Error` builtin, its subclasses, and their prototypes
+
+use crate::avm2::activation::Activation;
+use crate::avm2::function::FunctionObject;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::script_object::ScriptObject;
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+
+/// The names of every builtin subclass of `Error` in the root package.
+pub const SUBCLASSES: &[&str] = &[
+    "ArgumentError",
+    "DefinitionError",
+    "EvalError",
+    "RangeError",
+    "ReferenceError",
+    "SecurityError",
+    "SyntaxError",
+    "TypeError",
+    "URIError",
+    "VerifyError",
+];
+
+/// Split an interpreter error's description, such as `TypeError: message`,
+/// into the name of the `Error` class it should be raised as and its message.
+///
+/// Descriptions without a class name prefix are raised as plain `Error`s.
+pub fn split_error_message(description: &str) -> (&'static str, &str) {
+    for name in std::iter::once(&"Error").chain(SUBCLASSES) {
+        if let Some(message) = description
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix(": "))
+        {
+            return (name, message);
+        }
+    }
+
+    ("Error", description)
+}
+
+/// Implements `Error`'s constructor, which is shared by all of it's
+/// subclasses.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let message: Value<'gc> = match args.get(0) {
+            Some(message) if message != &Value::Undefined => {
+                message.coerce_to_string(activation, context)?.into()
+            }
+            _ => "".into(),
+        };
+        let id = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation, context)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "message"),
+            message,
+            activation,
+            context,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "errorID"),
+            id.into(),
+            activation,
+            context,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.prototype.toString`
+fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "name"),
+                activation,
+                context,
+            )?
+            .coerce_to_string(activation, context)?;
+        let message = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "message"),
+                activation,
+                context,
+            )?
+            .coerce_to_string(activation, context)?;
+
+        if message.is_empty() {
+            return Ok(name.into());
+        }
+
+        return Ok(AvmString::new(context.gc_context, format!("{}: {}", name, message)).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.prototype.getStackTrace`
+fn get_stack_trace<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Stack traces are only available in debugger players.
+    Ok(Value::Null)
+}
+
+/// Construct `Error.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut proto = create_subclass_proto(mc, super_proto, "Error");
+
+    proto
+        .install_dynamic_property(
+            mc,
+            QName::new(Namespace::public_namespace(), "message"),
+            "".into(),
+        )
+        .unwrap();
+    proto.install_method(
+        mc,
+        QName::new(Namespace::public_namespace(), "toString"),
+        0,
+        FunctionObject::from_builtin(mc, to_string, fn_proto),
+    );
+    proto.install_method(
+        mc,
+        QName::new(Namespace::public_namespace(), "getStackTrace"),
+        0,
+        FunctionObject::from_builtin(mc, get_stack_trace, fn_proto),
+    );
+
+    proto
+}
+
+/// Construct the prototype of a subclass of `Error` with the given name.
+pub fn create_subclass_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    name: &'static str,
+) -> Object<'gc> {
+    let mut proto = ScriptObject::object(mc, super_proto);
+
+    proto
+        .install_dynamic_property(
+            mc,
+            QName::new(Namespace::public_namespace(), "name"),
+            name.into(),
+        )
+        .unwrap();
+
+    proto
+}
@@ -2,3 +2,5 @@
 
 pub mod display;
 pub mod events;
+pub mod external;
+pub mod system;
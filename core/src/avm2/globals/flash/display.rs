@@ -1,7 +1,9 @@
 //! `flash.display` namespace
 
+pub mod blendmode;
 pub mod displayobject;
 pub mod displayobjectcontainer;
 pub mod interactiveobject;
+pub mod loaderinfo;
 pub mod movieclip;
 pub mod sprite;
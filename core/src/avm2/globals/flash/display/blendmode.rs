@@ -0,0 +1,59 @@
+//! `flash.display.BlendMode` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::script_object::ScriptObject;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+
+/// The blend mode constants of `BlendMode`, and their values.
+const BLEND_MODES: &[(&str, &str)] = &[
+    ("ADD", "add"),
+    ("ALPHA", "alpha"),
+    ("DARKEN", "darken"),
+    ("DIFFERENCE", "difference"),
+    ("ERASE", "erase"),
+    ("HARDLIGHT", "hardlight"),
+    ("INVERT", "invert"),
+    ("LAYER", "layer"),
+    ("LIGHTEN", "lighten"),
+    ("MULTIPLY", "multiply"),
+    ("NORMAL", "normal"),
+    ("OVERLAY", "overlay"),
+    ("SCREEN", "screen"),
+    ("SUBTRACT", "subtract"),
+];
+
+/// Implements `flash.display.BlendMode`'s constructor.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `BlendMode.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    ScriptObject::object(mc, super_proto)
+}
+
+/// Add the blend mode constants to the `BlendMode` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, mut class: Object<'gc>) {
+    for (name, value) in BLEND_MODES {
+        class.install_const(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            (*value).into(),
+        );
+    }
+}
@@ -1,29 +1,541 @@
 //! `flash.display.DisplayObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
-use crate::avm2::script_object::ScriptObject;
+use crate::avm2::globals::flash::display::loaderinfo;
+use crate::avm2::globals::property;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::stage_object::{display_object_to_object, StageObject};
+use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
+use crate::display_object::{blend_mode_from_name, blend_mode_name, DisplayObject, TDisplayObject};
 use gc_arena::MutationContext;
 
 /// Implements `flash.display.DisplayObject`'s constructor.
+///
+/// `DisplayObject` is abstract: it can only be constructed as part of a
+/// concrete display object class.
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     _action_context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    abstract_constructor(this, "DisplayObject")
+}
+
+/// Refuse to construct an abstract display object class directly.
+///
+/// Objects which already represent a display object (such as those created
+/// for timeline symbols) are allowed through.
+pub fn abstract_constructor<'gc>(
+    this: Option<Object<'gc>>,
+    class_name: &str,
+) -> Result<Value<'gc>, Error> {
+    if this.and_then(|this| this.as_display_object()).is_none() {
+        return Err(format!(
+            "ArgumentError: Error #2012: {}$ class cannot be instantiated.",
+            class_name
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Get the AVM2 object for an optional display object, or `null`.
+pub fn display_object_or_null<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    display_object: Option<DisplayObject<'gc>>,
+) -> Result<Value<'gc>, Error> {
+    match display_object {
+        Some(display_object) => {
+            Ok(display_object_to_object(activation, context, display_object)?.into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `x`'s getter.
+pub fn x<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.x().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `x`'s setter.
+pub fn set_x<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_x = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        dobj.set_x(context.gc_context, new_x);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `y`'s getter.
+pub fn y<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.y().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `y`'s setter.
+pub fn set_y<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_y = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        dobj.set_y(context.gc_context, new_y);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `scaleX`'s getter.
+pub fn scale_x<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.scale_x(context.gc_context).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleX`'s setter.
+pub fn set_scale_x<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_scale = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        dobj.set_scale_x(context.gc_context, new_scale);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleY`'s getter.
+pub fn scale_y<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.scale_y(context.gc_context).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scaleY`'s setter.
+pub fn set_scale_y<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_scale = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        dobj.set_scale_y(context.gc_context, new_scale);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotation`'s getter.
+pub fn rotation<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.rotation(context.gc_context).to_degrees().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `rotation`'s setter.
+pub fn set_rotation<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let mut degrees = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        // Normalize into the range of [-180, 180].
+        degrees %= 360.0;
+        if degrees < -180.0 {
+            degrees += 360.0
+        } else if degrees > 180.0 {
+            degrees -= 360.0
+        }
+
+        dobj.set_rotation(context.gc_context, degrees.to_radians());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `alpha`'s getter.
+pub fn alpha<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.alpha().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `alpha`'s setter.
+pub fn set_alpha<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_alpha = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        dobj.set_alpha(context.gc_context, new_alpha);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `visible`'s getter.
+pub fn visible<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.visible().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `visible`'s setter.
+pub fn set_visible<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_visible = args.get(0).unwrap_or(&Value::Undefined).coerce_to_boolean();
+
+        dobj.set_visible(context.gc_context, new_visible);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s getter.
+pub fn blend_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(blend_mode_name(dobj.blend_mode()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s setter.
+pub fn set_blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_blend_mode = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation, context)?;
+
+        match blend_mode_from_name(&new_blend_mode) {
+            Some(blend_mode) => dobj.set_blend_mode(context.gc_context, blend_mode),
+            None => {
+                return Err(
+                    "ArgumentError: Error #2008: Parameter blendMode must be one of the accepted values."
+                        .into(),
+                )
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `width`'s setter.
+pub fn set_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_width = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        if new_width >= 0.0 {
+            dobj.set_width(context.gc_context, new_width);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `height`'s setter.
+pub fn set_height<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_height = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_number(activation, context)?;
+
+        if new_height >= 0.0 {
+            dobj.set_height(context.gc_context, new_height);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `name`'s getter.
+pub fn name<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(AvmString::new(context.gc_context, dobj.name().to_string()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `name`'s setter.
+pub fn set_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        let new_name = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation, context)?;
+
+        dobj.set_name(context.gc_context, &new_name);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `parent`.
+pub fn parent<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return display_object_or_null(activation, context, dobj.parent());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `root`.
+pub fn root<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut dobj) = this.and_then(|this| this.as_display_object()) {
+        while let Some(parent) = dobj.parent() {
+            dobj = parent;
+        }
+
+        return display_object_or_null(activation, context, Some(dobj));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `loaderInfo`.
+///
+/// Every display object in a movie shares the movie's `LoaderInfo`, which is
+/// created the first time it is asked for.
+pub fn loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(movie) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.movie())
+    {
+        let library = context.library.library_for_movie_mut(movie.clone());
+        if let Some(loader_info) = library.loader_info() {
+            return Ok(loader_info.into());
+        }
+
+        let loader_info = loaderinfo::from_movie(activation, context, &movie);
+        context
+            .library
+            .library_for_movie_mut(movie)
+            .set_loader_info(loader_info);
+        return Ok(loader_info.into());
+    }
+
+    Ok(Value::Null)
+}
+
 /// Construct `DisplayObject.prototype`.
 pub fn create_proto<'gc>(
     mc: MutationContext<'gc, '_>,
     super_proto: Object<'gc>,
-    _fn_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    // TODO: Use `StageObject` here.
-    ScriptObject::object(mc, super_proto)
+    let proto = StageObject::bare_object(mc, super_proto);
+
+    property(mc, proto, "x", x, Some(set_x), fn_proto);
+    property(mc, proto, "y", y, Some(set_y), fn_proto);
+    property(mc, proto, "scaleX", scale_x, Some(set_scale_x), fn_proto);
+    property(mc, proto, "scaleY", scale_y, Some(set_scale_y), fn_proto);
+    property(
+        mc,
+        proto,
+        "rotation",
+        rotation,
+        Some(set_rotation),
+        fn_proto,
+    );
+    property(mc, proto, "alpha", alpha, Some(set_alpha), fn_proto);
+    property(mc, proto, "visible", visible, Some(set_visible), fn_proto);
+    property(
+        mc,
+        proto,
+        "blendMode",
+        blend_mode,
+        Some(set_blend_mode),
+        fn_proto,
+    );
+    property(mc, proto, "width", width, Some(set_width), fn_proto);
+    property(mc, proto, "height", height, Some(set_height), fn_proto);
+    property(mc, proto, "name", name, Some(set_name), fn_proto);
+    property(mc, proto, "parent", parent, None, fn_proto);
+    property(mc, proto, "root", root, None, fn_proto);
+    property(mc, proto, "loaderInfo", loader_info, None, fn_proto);
+
+    proto
 }
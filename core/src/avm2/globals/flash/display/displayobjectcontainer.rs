@@ -1,20 +1,293 @@
 //! `flash.display.DisplayObjectContainer` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
-use crate::avm2::script_object::ScriptObject;
+use crate::avm2::globals::flash::display::displayobject::{
+    abstract_constructor, display_object_or_null,
+};
+use crate::avm2::globals::{method, property};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::stage_object::StageObject;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
 use gc_arena::MutationContext;
 
 /// Implements `flash.display.DisplayObjectContainer`'s constructor.
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     _action_context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    abstract_constructor(this, "DisplayObjectContainer")
+}
+
+/// Get the clip that holds the children of a container object.
+fn container(this: Option<Object<'_>>) -> Option<MovieClip<'_>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+}
+
+/// Extract the display object passed as a `child` parameter.
+fn child_param<'gc>(args: &[Value<'gc>], index: usize) -> Result<DisplayObject<'gc>, Error> {
+    match args.get(index) {
+        Some(Value::Object(child)) => child.as_display_object().ok_or_else(|| {
+            "TypeError: Error #1034: Type Coercion failed: child is not a DisplayObject.".into()
+        }),
+        _ => Err("TypeError: Error #2007: Parameter child must be non-null.".into()),
+    }
+}
+
+/// Extract a child index parameter, which must be within `0..=max`.
+fn index_param<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    max: usize,
+) -> Result<usize, Error> {
+    let child_index = args
+        .get(index)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation, context)?;
+
+    if child_index < 0 || child_index as usize > max {
+        return Err("RangeError: Error #2006: The supplied index is out of bounds.".into());
+    }
+
+    Ok(child_index as usize)
+}
+
+/// Ensure that a display object is a child of the given container.
+fn check_is_child<'gc>(parent: MovieClip<'gc>, child: DisplayObject<'gc>) -> Result<(), Error> {
+    match child.parent() {
+        Some(child_parent) if DisplayObject::ptr_eq(child_parent, parent.into()) => Ok(()),
+        _ => Err(
+            "ArgumentError: Error #2025: The supplied DisplayObject must be a child of the caller."
+                .into(),
+        ),
+    }
+}
+
+/// Place a child at a given position in a container's display list, removing
+/// it from its current container first.
+fn add_child_at_index<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mut parent: MovieClip<'gc>,
+    child: DisplayObject<'gc>,
+    index: usize,
+) -> Result<(), Error> {
+    if DisplayObject::ptr_eq(child, parent.into()) {
+        return Err(
+            "ArgumentError: Error #2024: An object cannot be added as a child of itself.".into(),
+        );
+    }
+
+    if let Some(mut old_parent) = child.parent().and_then(|p| p.as_movie_clip()) {
+        old_parent.detach_child(context, child);
+    }
+
+    let depth = parent.highest_depth().map(|d| d + 1).unwrap_or(0);
+    parent.add_child_from_avm(context, child, depth);
+    parent.set_child_index(context, child, index);
+
+    Ok(())
+}
+
+/// Implements `numChildren`.
+pub fn num_children<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        return Ok((parent.num_children() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.addChild`.
+pub fn add_child<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let child = child_param(args, 0)?;
+        let index = parent.num_children();
+
+        add_child_at_index(context, parent, child, index)?;
+
+        return Ok(args[0].clone());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.addChildAt`.
+pub fn add_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let child = child_param(args, 0)?;
+        let index = index_param(activation, context, args, 1, parent.num_children())?;
+
+        add_child_at_index(context, parent, child, index)?;
+
+        return Ok(args[0].clone());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.removeChild`.
+pub fn remove_child<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut parent) = container(this) {
+        let child = child_param(args, 0)?;
+        check_is_child(parent, child)?;
+
+        parent.detach_child(context, child);
+
+        return Ok(args[0].clone());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.removeChildAt`.
+pub fn remove_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut parent) = container(this) {
+        let max = parent.num_children().saturating_sub(1);
+        let index = index_param(activation, context, args, 0, max)?;
+        let child = parent.child_by_index(index).ok_or_else(|| {
+            Error::from("RangeError: Error #2006: The supplied index is out of bounds.")
+        })?;
+
+        let object = display_object_or_null(activation, context, Some(child))?;
+        parent.detach_child(context, child);
+
+        return Ok(object);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.getChildAt`.
+pub fn get_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let max = parent.num_children().saturating_sub(1);
+        let index = index_param(activation, context, args, 0, max)?;
+        let child = parent.child_by_index(index).ok_or_else(|| {
+            Error::from("RangeError: Error #2006: The supplied index is out of bounds.")
+        })?;
+
+        return display_object_or_null(activation, context, Some(child));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.getChildByName`.
+pub fn get_child_by_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let name = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        let child = parent.get_child_by_name(&name, true);
+
+        return display_object_or_null(activation, context, child);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.getChildIndex`.
+pub fn get_child_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let child = child_param(args, 0)?;
+        check_is_child(parent, child)?;
+
+        if let Some(index) = parent.child_index(child) {
+            return Ok((index as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.setChildIndex`.
+pub fn set_child_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let child = child_param(args, 0)?;
+        check_is_child(parent, child)?;
+
+        let max = parent.num_children().saturating_sub(1);
+        let index = index_param(activation, context, args, 1, max)?;
+
+        parent.set_child_index(context, child, index);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.contains`.
+pub fn contains<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent) = container(this) {
+        let mut ancestor = Some(child_param(args, 0)?);
+        while let Some(dobj) = ancestor {
+            if DisplayObject::ptr_eq(dobj, parent.into()) {
+                return Ok(true.into());
+            }
+
+            ancestor = dobj.parent();
+        }
+
+        return Ok(false.into());
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -22,8 +295,21 @@ pub fn constructor<'gc>(
 pub fn create_proto<'gc>(
     mc: MutationContext<'gc, '_>,
     super_proto: Object<'gc>,
-    _fn_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    // TODO: Use `StageObject` here.
-    ScriptObject::object(mc, super_proto)
+    let proto = StageObject::bare_object(mc, super_proto);
+
+    property(mc, proto, "numChildren", num_children, None, fn_proto);
+
+    method(mc, proto, "addChild", add_child, fn_proto);
+    method(mc, proto, "addChildAt", add_child_at, fn_proto);
+    method(mc, proto, "contains", contains, fn_proto);
+    method(mc, proto, "getChildAt", get_child_at, fn_proto);
+    method(mc, proto, "getChildByName", get_child_by_name, fn_proto);
+    method(mc, proto, "getChildIndex", get_child_index, fn_proto);
+    method(mc, proto, "removeChild", remove_child, fn_proto);
+    method(mc, proto, "removeChildAt", remove_child_at, fn_proto);
+    method(mc, proto, "setChildIndex", set_child_index, fn_proto);
+
+    proto
 }
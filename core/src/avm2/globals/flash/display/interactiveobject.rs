@@ -1,8 +1,9 @@
 //! `flash.display.InteractiveObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::displayobject::abstract_constructor;
 use crate::avm2::object::Object;
-use crate::avm2::script_object::ScriptObject;
+use crate::avm2::stage_object::StageObject;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
@@ -12,10 +13,10 @@ use gc_arena::MutationContext;
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     _action_context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Ok(Value::Undefined)
+    abstract_constructor(this, "InteractiveObject")
 }
 
 /// Construct `InteractiveObject.prototype`.
@@ -24,6 +25,5 @@ pub fn create_proto<'gc>(
     super_proto: Object<'gc>,
     _fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    // TODO: Use `StageObject` here.
-    ScriptObject::object(mc, super_proto)
+    StageObject::bare_object(mc, super_proto)
 }
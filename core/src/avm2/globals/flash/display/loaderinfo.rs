@@ -0,0 +1,93 @@
+//! `flash.display.LoaderInfo` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::script_object::ScriptObject;
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::tag_utils::SwfMovie;
+use gc_arena::MutationContext;
+
+/// Implements `flash.display.LoaderInfo`'s constructor.
+///
+/// `LoaderInfo` objects are only ever created by the player.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _action_context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: LoaderInfo$ class cannot be instantiated.".into())
+}
+
+/// Construct `LoaderInfo.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    _fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    ScriptObject::object(mc, super_proto)
+}
+
+/// Create a `LoaderInfo` describing a loaded movie.
+///
+/// The information a `LoaderInfo` holds never changes once a movie has been
+/// loaded, so it is stored as constant properties.
+pub fn from_movie<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    movie: &SwfMovie,
+) -> Object<'gc> {
+    let mc = context.gc_context;
+    let prototypes = activation.avm2().prototypes();
+
+    let mut parameters = ScriptObject::object(mc, prototypes.object);
+    for (key, value) in movie.parameters() {
+        parameters
+            .install_dynamic_property(
+                mc,
+                QName::new(
+                    Namespace::public_namespace(),
+                    AvmString::new(mc, key.clone()),
+                ),
+                AvmString::new(mc, value.clone()).into(),
+            )
+            .unwrap();
+    }
+
+    let url: Value<'gc> = match movie.url() {
+        Some(url) => AvmString::new(mc, url.to_string()).into(),
+        None => Value::Null,
+    };
+    // The movie the player started with counts as having loaded itself.
+    let loader_url = match movie.loader_url() {
+        Some(loader_url) => AvmString::new(mc, loader_url.to_string()).into(),
+        None => url.clone(),
+    };
+
+    let mut loader_info = ScriptObject::object(mc, prototypes.loader_info);
+    let properties = [
+        ("url", url.clone()),
+        ("loaderURL", loader_url),
+        ("parameters", parameters.into()),
+        ("swfVersion", movie.version().into()),
+        ("frameRate", movie.header().frame_rate.into()),
+        ("width", movie.width().into()),
+        ("height", movie.height().into()),
+        ("bytesLoaded", (movie.compressed_length() as u32).into()),
+        ("bytesTotal", (movie.compressed_length() as u32).into()),
+    ];
+    for (name, value) in properties.iter() {
+        loader_info.install_const(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            value.clone(),
+        );
+    }
+
+    loader_info
+}
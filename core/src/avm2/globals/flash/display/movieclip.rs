@@ -1,20 +1,198 @@
 //! `flash.display.MovieClip` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
-use crate::avm2::script_object::ScriptObject;
+use crate::avm2::globals::flash::display::sprite;
+use crate::avm2::globals::{method, property};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::stage_object::StageObject;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
+use crate::display_object::{MovieClip, TDisplayObject};
 use gc_arena::MutationContext;
 
 /// Implements `flash.display.MovieClip`'s constructor.
+///
+/// Display objects are created the same way as for `Sprite`.
 pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    sprite::constructor(activation, context, this, args)
+}
+
+/// Get the clip represented by a `MovieClip` object.
+fn movie_clip(this: Option<Object<'_>>) -> Option<MovieClip<'_>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+}
+
+/// Resolve the `frame` parameter of a goto method, which may be either a
+/// frame number or a frame label.
+fn frame_param<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mc: MovieClip<'gc>,
+    frame: &Value<'gc>,
+) -> Result<u16, Error> {
+    match frame {
+        Value::String(label) => mc.frame_label_to_number(label).ok_or_else(|| {
+            format!(
+                "ArgumentError: Error #2109: Frame label {} not found in scene.",
+                label
+            )
+            .into()
+        }),
+        frame => {
+            let frame = frame.coerce_to_number(activation, context)?;
+            if frame.is_finite() && frame >= 1.0 {
+                Ok(frame.min(f64::from(std::u16::MAX)) as u16)
+            } else {
+                Ok(1)
+            }
+        }
+    }
+}
+
+/// Implements `currentFrame`.
+pub fn current_frame<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        return Ok(i32::from(mc.current_frame()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `totalFrames`.
+pub fn total_frames<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        return Ok(i32::from(mc.total_frames()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `framesLoaded`.
+pub fn frames_loaded<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        return Ok(i32::from(mc.frames_loaded()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.play`.
+pub fn play<'gc>(
     _activation: &mut Activation<'_, 'gc>,
-    _action_context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        mc.play(context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.stop`.
+pub fn stop<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        mc.stop(context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.gotoAndPlay`.
+pub fn goto_and_play<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        let frame = frame_param(
+            activation,
+            context,
+            mc,
+            args.get(0).unwrap_or(&Value::Undefined),
+        )?;
+
+        mc.goto_frame(activation.avm1(), context, frame, false);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.gotoAndStop`.
+pub fn goto_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        let frame = frame_param(
+            activation,
+            context,
+            mc,
+            args.get(0).unwrap_or(&Value::Undefined),
+        )?;
+
+        mc.goto_frame(activation.avm1(), context, frame, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.nextFrame`.
+pub fn next_frame<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        mc.next_frame(activation.avm1(), context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.prevFrame`.
+pub fn prev_frame<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = movie_clip(this) {
+        mc.prev_frame(activation.avm1(), context);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -22,8 +200,20 @@ pub fn constructor<'gc>(
 pub fn create_proto<'gc>(
     mc: MutationContext<'gc, '_>,
     super_proto: Object<'gc>,
-    _fn_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    // TODO: Use `StageObject` here.
-    ScriptObject::object(mc, super_proto)
+    let proto = StageObject::bare_object(mc, super_proto);
+
+    property(mc, proto, "currentFrame", current_frame, None, fn_proto);
+    property(mc, proto, "totalFrames", total_frames, None, fn_proto);
+    property(mc, proto, "framesLoaded", frames_loaded, None, fn_proto);
+
+    method(mc, proto, "play", play, fn_proto);
+    method(mc, proto, "stop", stop, fn_proto);
+    method(mc, proto, "gotoAndPlay", goto_and_play, fn_proto);
+    method(mc, proto, "gotoAndStop", goto_and_stop, fn_proto);
+    method(mc, proto, "nextFrame", next_frame, fn_proto);
+    method(mc, proto, "prevFrame", prev_frame, fn_proto);
+
+    proto
 }
@@ -1,20 +1,58 @@
 //! `flash.display.Sprite` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
-use crate::avm2::script_object::ScriptObject;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::stage_object::StageObject;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::tag_utils::SwfSlice;
 use gc_arena::MutationContext;
 
 /// Implements `flash.display.Sprite`'s constructor.
+///
+/// If `this` does not represent a display object yet, one is created for it:
+/// either an instance of the library symbol linked to its class, or an empty
+/// clip.
 pub fn constructor<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _action_context: &mut UpdateContext<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    let this = match this {
+        Some(this) if this.as_display_object().is_none() => this,
+        _ => return Ok(Value::Undefined),
+    };
+
+    let library = context.library.library_for_movie(context.swf.clone());
+    let mut symbol = None;
+    let mut proto = this.proto();
+    while let (Some(p), Some(library)) = (proto, library) {
+        if let Some(class) = p.as_class() {
+            symbol = library.avm2_class_character(&class.read().name().to_qualified_name());
+            if symbol.is_some() {
+                break;
+            }
+        }
+
+        proto = p.proto();
+    }
+
+    let mut display_object: DisplayObject<'gc> = match (symbol, library) {
+        (Some(id), Some(library)) => library.instantiate_by_id(id, context.gc_context)?,
+        _ => MovieClip::new(SwfSlice::empty(context.swf.clone()), context.gc_context).into(),
+    };
+
+    display_object.set_object2(context.gc_context, this);
+    this.init_display_object(context.gc_context, display_object);
+    display_object.post_instantiation(activation.avm1(), context, display_object, None, true);
+
+    if symbol.is_some() {
+        display_object.run_frame(activation.avm1(), context);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -24,6 +62,5 @@ pub fn create_proto<'gc>(
     super_proto: Object<'gc>,
     _fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    // TODO: Use `StageObject` here.
-    ScriptObject::object(mc, super_proto)
+    StageObject::bare_object(mc, super_proto)
 }
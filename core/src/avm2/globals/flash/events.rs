@@ -1,3 +1,6 @@
 //! `flash.events` namespace
 
+pub mod event;
 pub mod eventdispatcher;
+pub mod keyboardevent;
+pub mod mouseevent;
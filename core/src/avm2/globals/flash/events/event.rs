@@ -0,0 +1,412 @@
+//! `flash.events.Event` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::event_object::EventObject;
+use crate::avm2::events::{Event, EventData};
+use crate::avm2::globals::{method, property};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+
+/// The event type constants of `Event`.
+const EVENT_TYPES: &[(&str, &str)] = &[
+    ("ACTIVATE", "activate"),
+    ("ADDED", "added"),
+    ("ADDED_TO_STAGE", "addedToStage"),
+    ("CANCEL", "cancel"),
+    ("CHANGE", "change"),
+    ("CLOSE", "close"),
+    ("COMPLETE", "complete"),
+    ("DEACTIVATE", "deactivate"),
+    ("ENTER_FRAME", "enterFrame"),
+    ("EXIT_FRAME", "exitFrame"),
+    ("FRAME_CONSTRUCTED", "frameConstructed"),
+    ("INIT", "init"),
+    ("OPEN", "open"),
+    ("REMOVED", "removed"),
+    ("REMOVED_FROM_STAGE", "removedFromStage"),
+    ("RENDER", "render"),
+    ("RESIZE", "resize"),
+    ("SCROLL", "scroll"),
+    ("SELECT", "select"),
+    ("UNLOAD", "unload"),
+];
+
+/// Initialize the common properties of an event from the `type`, `bubbles`
+/// and `cancelable` constructor parameters.
+///
+/// `bubbles` defaults to the given value when it is not passed.
+pub fn init_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    default_bubbles: bool,
+) -> Result<(), Error> {
+    let event_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation, context)?;
+    let bubbles = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Bool(default_bubbles))
+        .coerce_to_boolean();
+    let cancelable = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Bool(false))
+        .coerce_to_boolean();
+
+    if let Some(mut evt) = this.as_event_mut(context.gc_context) {
+        evt.set_event_type(event_type);
+        evt.set_bubbles(bubbles);
+        evt.set_cancelable(cancelable);
+    }
+
+    Ok(())
+}
+
+/// Implements `flash.events.Event`'s constructor.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        init_event(activation, context, this, args, false)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Produce the `[ClassName name=value ...]` string representation of an
+/// event, as used by `formatToString` and `toString`.
+///
+/// String values are quoted.
+pub fn format_to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    class_name: &str,
+    property_names: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut string = format!("[{}", class_name);
+
+    for name in property_names {
+        let name = name.coerce_to_string(activation, context)?;
+        let value = this.get_property(
+            this,
+            &QName::new(Namespace::public_namespace(), name),
+            activation,
+            context,
+        )?;
+
+        match value {
+            Value::String(value) => string.push_str(&format!(" {}=\"{}\"", name, value)),
+            value => string.push_str(&format!(
+                " {}={}",
+                name,
+                value.coerce_to_string(activation, context)?
+            )),
+        }
+    }
+
+    string.push(']');
+
+    Ok(AvmString::new(context.gc_context, string).into())
+}
+
+/// Implements `type`.
+pub fn event_type<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.event_type().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `bubbles`.
+pub fn bubbles<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.is_bubbling().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cancelable`.
+pub fn cancelable<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.is_cancelable().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `eventPhase`.
+pub fn event_phase<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok((evt.phase() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `target`.
+pub fn target<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.target().map(|o| o.into()).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `currentTarget`.
+pub fn current_target<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt
+            .current_target()
+            .map(|o| o.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.clone`.
+///
+/// The copy has the same prototype as the original, so that subclasses of
+/// builtin event classes that do not override `clone` still produce an
+/// instance of their own class.
+pub fn clone<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let (Some(evt), Some(proto)) = (this.as_event(), this.proto()) {
+            return Ok(EventObject::from_event(context.gc_context, proto, evt.duplicate()).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.formatToString`.
+pub fn format_to_string_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let class_name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+
+        return format_to_string(
+            activation,
+            context,
+            this,
+            &class_name,
+            args.get(1..).unwrap_or(&[]),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.isDefaultPrevented`.
+pub fn is_default_prevented<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.is_cancelled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.preventDefault`.
+pub fn prevent_default<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut evt) = this
+        .as_ref()
+        .and_then(|this| this.as_event_mut(context.gc_context))
+    {
+        evt.cancel();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.stopPropagation`.
+pub fn stop_propagation<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut evt) = this
+        .as_ref()
+        .and_then(|this| this.as_event_mut(context.gc_context))
+    {
+        evt.stop_propagation();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.stopImmediatePropagation`.
+pub fn stop_immediate_propagation<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut evt) = this
+        .as_ref()
+        .and_then(|this| this.as_event_mut(context.gc_context))
+    {
+        evt.stop_immediate_propagation();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Event.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return format_to_string(
+            activation,
+            context,
+            this,
+            "Event",
+            &[
+                "type".into(),
+                "bubbles".into(),
+                "cancelable".into(),
+                "eventPhase".into(),
+            ],
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Event.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let proto = EventObject::from_event(mc, super_proto, Event::new("", EventData::Event));
+
+    property(mc, proto, "type", event_type, None, fn_proto);
+    property(mc, proto, "bubbles", bubbles, None, fn_proto);
+    property(mc, proto, "cancelable", cancelable, None, fn_proto);
+    property(mc, proto, "eventPhase", event_phase, None, fn_proto);
+    property(mc, proto, "target", target, None, fn_proto);
+    property(mc, proto, "currentTarget", current_target, None, fn_proto);
+
+    method(mc, proto, "clone", clone, fn_proto);
+    method(
+        mc,
+        proto,
+        "formatToString",
+        format_to_string_method,
+        fn_proto,
+    );
+    method(
+        mc,
+        proto,
+        "isDefaultPrevented",
+        is_default_prevented,
+        fn_proto,
+    );
+    method(mc, proto, "preventDefault", prevent_default, fn_proto);
+    method(mc, proto, "stopPropagation", stop_propagation, fn_proto);
+    method(
+        mc,
+        proto,
+        "stopImmediatePropagation",
+        stop_immediate_propagation,
+        fn_proto,
+    );
+    method(mc, proto, "toString", to_string, fn_proto);
+
+    proto
+}
+
+/// Add the event type constants to the `Event` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, class: Object<'gc>) {
+    install_event_types(mc, class, EVENT_TYPES);
+}
+
+/// Add a list of event type constants to an event class.
+pub fn install_event_types<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut class: Object<'gc>,
+    event_types: &[(&'static str, &'static str)],
+) {
+    for (name, value) in event_types {
+        class.install_const(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            (*value).into(),
+        );
+    }
+}
@@ -1,11 +1,16 @@
 //! `flash.events.EventDispatcher` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
+use crate::avm2::events::{dispatch_event as dispatch, dispatch_list, existing_dispatch_list};
+use crate::avm2::globals::method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::script_object::ScriptObject;
+use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 
 /// Implements `flash.events.EventDispatcher`'s constructor.
@@ -15,6 +20,232 @@ pub fn constructor<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    // The dispatch list is created when the first listener is added.
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.addEventListener`.
+pub fn add_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        let listener = match args.get(1) {
+            Some(Value::Object(listener)) => *listener,
+            _ => return Err("TypeError: Error #2007: Parameter listener must be non-null.".into()),
+        };
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        let priority = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation, context)?;
+        let use_weak_reference = args
+            .get(4)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+        if use_weak_reference {
+            log::warn!(
+                "addEventListener: weak references are not supported, so the {} listener will be held strongly",
+                event_type
+            );
+        }
+
+        let dispatch_list = dispatch_list(activation, context, this)?;
+        dispatch_list
+            .as_dispatch_mut(context.gc_context)
+            .ok_or("Dispatch list is missing")?
+            .add_event_listener(
+                event_type,
+                priority,
+                listener,
+                use_capture,
+                use_weak_reference,
+            );
+
+        if this.as_display_object().is_some() {
+            activation
+                .avm2()
+                .register_broadcast_listener(this, event_type);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.removeEventListener`.
+pub fn remove_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+        let listener = match args.get(1) {
+            Some(Value::Object(listener)) => *listener,
+            _ => return Err("TypeError: Error #2007: Parameter listener must be non-null.".into()),
+        };
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        if let Some(dispatch_list) = existing_dispatch_list(activation, context, this)? {
+            let mut dispatch = dispatch_list
+                .as_dispatch_mut(context.gc_context)
+                .ok_or("Dispatch list is missing")?;
+
+            dispatch.remove_event_listener(event_type, listener, use_capture);
+
+            if !dispatch.has_event_listener(event_type) {
+                drop(dispatch);
+
+                activation
+                    .avm2()
+                    .unregister_broadcast_listener(this, event_type);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Determine if an object has any listeners for a given event type.
+fn has_listener<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: Object<'gc>,
+    event_type: AvmString<'gc>,
+) -> Result<bool, Error> {
+    if let Some(dispatch_list) = existing_dispatch_list(activation, context, object)? {
+        Ok(dispatch_list
+            .as_dispatch()
+            .ok_or("Dispatch list is missing")?
+            .has_event_listener(event_type))
+    } else {
+        Ok(false)
+    }
+}
+
+/// Implements `EventDispatcher.hasEventListener`.
+pub fn has_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+
+        return Ok(has_listener(activation, context, this, event_type)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.willTrigger`.
+///
+/// Unlike `hasEventListener`, this also checks the ancestors of display
+/// objects, which an event could reach by capturing or bubbling.
+pub fn will_trigger<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation, context)?;
+
+        if has_listener(activation, context, this, event_type)? {
+            return Ok(true.into());
+        }
+
+        let mut parent = this.as_display_object().and_then(|dobj| dobj.parent());
+        while let Some(ancestor) = parent {
+            if let Value::Object(object) = ancestor.object2() {
+                if has_listener(activation, context, object, event_type)? {
+                    return Ok(true.into());
+                }
+            }
+
+            parent = ancestor.parent();
+        }
+
+        return Ok(false.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.dispatchEvent`.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut event = match args.get(0) {
+            Some(Value::Object(event)) if event.as_event().is_some() => *event,
+            Some(Value::Object(_)) => {
+                return Err(
+                    "TypeError: Error #1034: Type Coercion failed: event is not an Event.".into(),
+                )
+            }
+            _ => return Err("TypeError: Error #2007: Parameter event must be non-null.".into()),
+        };
+
+        // Events that have already been dispatched are cloned, so that
+        // handlers of the original dispatch keep seeing the original event.
+        if event.as_event().unwrap().target().is_some() {
+            let clone = event.get_property(
+                event,
+                &QName::new(Namespace::public_namespace(), "clone"),
+                activation,
+                context,
+            )?;
+            event = clone
+                .as_object()?
+                .call(Some(event), &[], activation, context, None)?
+                .as_object()?;
+
+            if event.as_event().is_none() {
+                return Err(
+                    "TypeError: Error #1034: Type Coercion failed: clone() did not return an Event."
+                        .into(),
+                );
+            }
+        }
+
+        return Ok(dispatch(activation, context, this, event)?.into());
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -22,8 +253,21 @@ pub fn constructor<'gc>(
 pub fn create_proto<'gc>(
     mc: MutationContext<'gc, '_>,
     super_proto: Object<'gc>,
-    _fn_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    // TODO: Use `StageObject` here.
-    ScriptObject::object(mc, super_proto)
+    let proto = ScriptObject::object(mc, super_proto);
+
+    method(mc, proto, "addEventListener", add_event_listener, fn_proto);
+    method(
+        mc,
+        proto,
+        "removeEventListener",
+        remove_event_listener,
+        fn_proto,
+    );
+    method(mc, proto, "hasEventListener", has_event_listener, fn_proto);
+    method(mc, proto, "willTrigger", will_trigger, fn_proto);
+    method(mc, proto, "dispatchEvent", dispatch_event, fn_proto);
+
+    proto
 }
@@ -0,0 +1,196 @@
+//! `flash.events.KeyboardEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::event_object::EventObject;
+use crate::avm2::events::{Event, EventData};
+use crate::avm2::globals::flash::events::event::{
+    format_to_string, init_event, install_event_types,
+};
+use crate::avm2::globals::{method, property};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+
+/// The event type constants of `KeyboardEvent`.
+const EVENT_TYPES: &[(&str, &str)] = &[("KEY_DOWN", "keyDown"), ("KEY_UP", "keyUp")];
+
+/// Implements `flash.events.KeyboardEvent`'s constructor.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        init_event(activation, context, this, args, true)?;
+
+        let mut u32_arg = |index: usize| match args.get(index) {
+            Some(value) => value.coerce_to_u32(activation, context),
+            None => Ok(0),
+        };
+        let char_code = u32_arg(3)?;
+        let key_code = u32_arg(4)?;
+        let key_location = u32_arg(5)?;
+        let bool_arg = |index: usize| {
+            args.get(index)
+                .map(|value| value.coerce_to_boolean())
+                .unwrap_or(false)
+        };
+
+        if let Some(mut evt) = this.as_event_mut(context.gc_context) {
+            *evt.event_data_mut() = EventData::Keyboard {
+                char_code,
+                key_code,
+                key_location,
+                ctrl_key: bool_arg(6),
+                alt_key: bool_arg(7),
+                shift_key: bool_arg(8),
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define a getter and setter for a field of `EventData::Keyboard`.
+macro_rules! keyboard_event_property {
+    ($getter:ident, $setter:ident, $field:ident, $coerce:expr) => {
+        pub fn $getter<'gc>(
+            _activation: &mut Activation<'_, 'gc>,
+            _context: &mut UpdateContext<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+                if let EventData::Keyboard { $field, .. } = evt.event_data() {
+                    return Ok((*$field).into());
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+
+        pub fn $setter<'gc>(
+            activation: &mut Activation<'_, 'gc>,
+            context: &mut UpdateContext<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+            let value = $coerce(value, activation, context)?;
+
+            if let Some(mut evt) = this
+                .as_ref()
+                .and_then(|this| this.as_event_mut(context.gc_context))
+            {
+                if let EventData::Keyboard { $field, .. } = evt.event_data_mut() {
+                    *$field = value;
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+fn to_u32<'gc>(
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<u32, Error> {
+    value.coerce_to_u32(activation, context)
+}
+
+fn to_boolean<'gc>(
+    value: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<bool, Error> {
+    Ok(value.coerce_to_boolean())
+}
+
+keyboard_event_property!(char_code, set_char_code, char_code, to_u32);
+keyboard_event_property!(key_code, set_key_code, key_code, to_u32);
+keyboard_event_property!(key_location, set_key_location, key_location, to_u32);
+keyboard_event_property!(ctrl_key, set_ctrl_key, ctrl_key, to_boolean);
+keyboard_event_property!(alt_key, set_alt_key, alt_key, to_boolean);
+keyboard_event_property!(shift_key, set_shift_key, shift_key, to_boolean);
+
+/// Implements `KeyboardEvent.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return format_to_string(
+            activation,
+            context,
+            this,
+            "KeyboardEvent",
+            &[
+                "type".into(),
+                "bubbles".into(),
+                "cancelable".into(),
+                "eventPhase".into(),
+                "charCode".into(),
+                "keyCode".into(),
+                "keyLocation".into(),
+                "ctrlKey".into(),
+                "altKey".into(),
+                "shiftKey".into(),
+            ],
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `KeyboardEvent.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let proto = EventObject::from_event(mc, super_proto, Event::new("", EventData::Event));
+
+    property(
+        mc,
+        proto,
+        "charCode",
+        char_code,
+        Some(set_char_code),
+        fn_proto,
+    );
+    property(mc, proto, "keyCode", key_code, Some(set_key_code), fn_proto);
+    property(
+        mc,
+        proto,
+        "keyLocation",
+        key_location,
+        Some(set_key_location),
+        fn_proto,
+    );
+    property(mc, proto, "ctrlKey", ctrl_key, Some(set_ctrl_key), fn_proto);
+    property(mc, proto, "altKey", alt_key, Some(set_alt_key), fn_proto);
+    property(
+        mc,
+        proto,
+        "shiftKey",
+        shift_key,
+        Some(set_shift_key),
+        fn_proto,
+    );
+
+    method(mc, proto, "toString", to_string, fn_proto);
+
+    proto
+}
+
+/// Add the event type constants to the `KeyboardEvent` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, class: Object<'gc>) {
+    install_event_types(mc, class, EVENT_TYPES);
+}
@@ -0,0 +1,286 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::event_object::EventObject;
+use crate::avm2::events::{Event, EventData};
+use crate::avm2::globals::flash::events::event::{
+    format_to_string, init_event, install_event_types,
+};
+use crate::avm2::globals::{method, property};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
+use gc_arena::MutationContext;
+use swf::Twips;
+
+/// The event type constants of `MouseEvent`.
+const EVENT_TYPES: &[(&str, &str)] = &[
+    ("CLICK", "click"),
+    ("DOUBLE_CLICK", "doubleClick"),
+    ("MOUSE_DOWN", "mouseDown"),
+    ("MOUSE_MOVE", "mouseMove"),
+    ("MOUSE_OUT", "mouseOut"),
+    ("MOUSE_OVER", "mouseOver"),
+    ("MOUSE_UP", "mouseUp"),
+    ("MOUSE_WHEEL", "mouseWheel"),
+    ("ROLL_OUT", "rollOut"),
+    ("ROLL_OVER", "rollOver"),
+];
+
+/// Implements `flash.events.MouseEvent`'s constructor.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        init_event(activation, context, this, args, true)?;
+
+        let mut number_arg = |index: usize, default: f64| match args.get(index) {
+            Some(value) => value.coerce_to_number(activation, context),
+            None => Ok(default),
+        };
+        let local_x = number_arg(3, std::f64::NAN)?;
+        let local_y = number_arg(4, std::f64::NAN)?;
+        let delta = args
+            .get(10)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation, context)?;
+        let bool_arg = |index: usize| {
+            args.get(index)
+                .map(|value| value.coerce_to_boolean())
+                .unwrap_or(false)
+        };
+
+        if let Some(mut evt) = this.as_event_mut(context.gc_context) {
+            *evt.event_data_mut() = EventData::Mouse {
+                local_x,
+                local_y,
+                ctrl_key: bool_arg(6),
+                alt_key: bool_arg(7),
+                shift_key: bool_arg(8),
+                button_down: bool_arg(9),
+                delta,
+            };
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Define a getter and setter for a field of `EventData::Mouse`.
+macro_rules! mouse_event_property {
+    ($getter:ident, $setter:ident, $field:ident, $coerce:expr) => {
+        pub fn $getter<'gc>(
+            _activation: &mut Activation<'_, 'gc>,
+            _context: &mut UpdateContext<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+                if let EventData::Mouse { $field, .. } = evt.event_data() {
+                    return Ok((*$field).into());
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+
+        pub fn $setter<'gc>(
+            activation: &mut Activation<'_, 'gc>,
+            context: &mut UpdateContext<'_, 'gc, '_>,
+            this: Option<Object<'gc>>,
+            args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+            let value = $coerce(value, activation, context)?;
+
+            if let Some(mut evt) = this
+                .as_ref()
+                .and_then(|this| this.as_event_mut(context.gc_context))
+            {
+                if let EventData::Mouse { $field, .. } = evt.event_data_mut() {
+                    *$field = value;
+                }
+            }
+
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+fn to_number<'gc>(
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<f64, Error> {
+    value.coerce_to_number(activation, context)
+}
+
+fn to_i32<'gc>(
+    value: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<i32, Error> {
+    value.coerce_to_i32(activation, context)
+}
+
+fn to_boolean<'gc>(
+    value: Value<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+) -> Result<bool, Error> {
+    Ok(value.coerce_to_boolean())
+}
+
+mouse_event_property!(local_x, set_local_x, local_x, to_number);
+mouse_event_property!(local_y, set_local_y, local_y, to_number);
+mouse_event_property!(ctrl_key, set_ctrl_key, ctrl_key, to_boolean);
+mouse_event_property!(alt_key, set_alt_key, alt_key, to_boolean);
+mouse_event_property!(shift_key, set_shift_key, shift_key, to_boolean);
+mouse_event_property!(button_down, set_button_down, button_down, to_boolean);
+mouse_event_property!(delta, set_delta, delta, to_i32);
+
+/// Get the position of a mouse event on the stage.
+///
+/// The local position is transformed by the event's target, if it is a
+/// display object.
+fn stage_position(this: Option<Object<'_>>) -> Option<(f64, f64)> {
+    let evt = this.as_ref()?.as_event()?;
+
+    if let EventData::Mouse {
+        local_x, local_y, ..
+    } = evt.event_data()
+    {
+        match evt.target().and_then(|target| target.as_display_object()) {
+            Some(dobj) => {
+                let (x, y) = dobj
+                    .local_to_global((Twips::from_pixels(*local_x), Twips::from_pixels(*local_y)));
+
+                Some((x.to_pixels(), y.to_pixels()))
+            }
+            None => Some((*local_x, *local_y)),
+        }
+    } else {
+        None
+    }
+}
+
+/// Implements `stageX`.
+pub fn stage_x<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(stage_position(this)
+        .map(|(x, _)| x.into())
+        .unwrap_or(Value::Undefined))
+}
+
+/// Implements `stageY`.
+pub fn stage_y<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(stage_position(this)
+        .map(|(_, y)| y.into())
+        .unwrap_or(Value::Undefined))
+}
+
+/// Implements `relatedObject`.
+///
+/// Roll over and roll out events are not dispatched yet, so there is never a
+/// related object.
+pub fn related_object<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `MouseEvent.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return format_to_string(
+            activation,
+            context,
+            this,
+            "MouseEvent",
+            &[
+                "type".into(),
+                "bubbles".into(),
+                "cancelable".into(),
+                "eventPhase".into(),
+                "localX".into(),
+                "localY".into(),
+                "stageX".into(),
+                "stageY".into(),
+                "relatedObject".into(),
+                "ctrlKey".into(),
+                "altKey".into(),
+                "shiftKey".into(),
+                "buttonDown".into(),
+                "delta".into(),
+            ],
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `MouseEvent.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let proto = EventObject::from_event(mc, super_proto, Event::new("", EventData::Event));
+
+    property(mc, proto, "localX", local_x, Some(set_local_x), fn_proto);
+    property(mc, proto, "localY", local_y, Some(set_local_y), fn_proto);
+    property(mc, proto, "stageX", stage_x, None, fn_proto);
+    property(mc, proto, "stageY", stage_y, None, fn_proto);
+    property(mc, proto, "relatedObject", related_object, None, fn_proto);
+    property(mc, proto, "ctrlKey", ctrl_key, Some(set_ctrl_key), fn_proto);
+    property(mc, proto, "altKey", alt_key, Some(set_alt_key), fn_proto);
+    property(
+        mc,
+        proto,
+        "shiftKey",
+        shift_key,
+        Some(set_shift_key),
+        fn_proto,
+    );
+    property(
+        mc,
+        proto,
+        "buttonDown",
+        button_down,
+        Some(set_button_down),
+        fn_proto,
+    );
+    property(mc, proto, "delta", delta, Some(set_delta), fn_proto);
+
+    method(mc, proto, "toString", to_string, fn_proto);
+
+    proto
+}
+
+/// Add the event type constants to the `MouseEvent` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, class: Object<'gc>) {
+    install_event_types(mc, class, EVENT_TYPES);
+}
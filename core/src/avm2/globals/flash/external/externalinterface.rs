@@ -0,0 +1,129 @@
+//! `flash.external.ExternalInterface` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::function::FunctionObject;
+use crate::avm2::method::NativeMethod;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::external::{Callback, Value as ExternalValue};
+use gc_arena::MutationContext;
+
+/// Implements `flash.external.ExternalInterface`'s constructor.
+///
+/// `ExternalInterface` only has static properties.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Fail with the error Flash Player throws when there is no host to talk to.
+fn check_available(context: &UpdateContext<'_, '_, '_>) -> Result<(), Error> {
+    if context.external_interface.available() {
+        Ok(())
+    } else {
+        Err("Error: Error #2067: The ExternalInterface is not available in this container.".into())
+    }
+}
+
+/// Implements `ExternalInterface.available`.
+pub fn available<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(context.external_interface.available().into())
+}
+
+/// Implements `ExternalInterface.addCallback`.
+pub fn add_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_available(context)?;
+
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?
+        .to_string();
+    let method = match args.get(1) {
+        Some(Value::Object(method)) if method.as_executable().is_some() => *method,
+        _ => return Err("TypeError: Error #2007: Parameter closure must be non-null.".into()),
+    };
+
+    context
+        .external_interface
+        .add_callback(name, Callback::Avm2 { method });
+    Ok(Value::Undefined)
+}
+
+/// Implements `ExternalInterface.call`.
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_available(context)?;
+
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?
+        .to_string();
+
+    if !context.system.security.can_call_host(&context.swf) {
+        return Err(format!(
+            "SecurityError: Error #2060: Security sandbox violation: ExternalInterface caller {} cannot access the host.",
+            context.swf.url().unwrap_or("")
+        )
+        .into());
+    }
+
+    let mut external_args = Vec::with_capacity(args.len().saturating_sub(1));
+    for arg in args.iter().skip(1) {
+        external_args.push(ExternalValue::from_avm2(activation, context, arg.clone())?);
+    }
+
+    let result = context
+        .external_interface
+        .call_method(&name, &external_args);
+    result.into_avm2(activation, context)
+}
+
+/// Install the static properties and methods of the `ExternalInterface` class.
+pub fn fill_class<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut class: Object<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    class
+        .install_getter(
+            mc,
+            QName::new(Namespace::public_namespace(), "available"),
+            0,
+            FunctionObject::from_builtin(mc, available, fn_proto),
+        )
+        .unwrap();
+
+    let methods: [(&'static str, NativeMethod<'gc>); 2] =
+        [("addCallback", add_callback), ("call", call)];
+    for (name, nf) in methods.iter() {
+        class.install_method(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            FunctionObject::from_builtin(mc, *nf, fn_proto),
+        );
+    }
+}
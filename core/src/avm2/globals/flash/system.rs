@@ -0,0 +1,33 @@
+//! `flash.system` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+
+/// Implements `flash.system.fscommand`
+pub fn fscommand<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let command = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+    let fs_args = match args.get(1) {
+        Some(fs_args) => fs_args.coerce_to_string(activation, context)?.to_string(),
+        None => String::new(),
+    };
+
+    if !context
+        .external_interface
+        .invoke_fs_command(&command, &fs_args)
+    {
+        log::warn!("Unhandled FSCommand: {}", command);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,63 @@
+//! `int` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::number;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::primitive_object::PrimitiveObject;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+
+/// Implements `int`
+///
+/// When called as a function, this converts its argument to an `int`.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value: Value<'gc> = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_i32(activation, context)?
+        .into();
+
+    if let Some(Object::PrimitiveObject(this)) = this {
+        this.set_primitive(context.gc_context, value.clone());
+    }
+
+    Ok(value)
+}
+
+/// Construct `int.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let proto = PrimitiveObject::from_primitive(0i32.into(), super_proto, mc).unwrap();
+
+    number::fill_proto(mc, proto, fn_proto);
+
+    proto
+}
+
+/// Install the constants of the `int` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, mut class: Object<'gc>) {
+    class.install_const(
+        mc,
+        QName::new(Namespace::public_namespace(), "MAX_VALUE"),
+        0,
+        std::i32::MAX.into(),
+    );
+    class.install_const(
+        mc,
+        QName::new(Namespace::public_namespace(), "MIN_VALUE"),
+        0,
+        std::i32::MIN.into(),
+    );
+}
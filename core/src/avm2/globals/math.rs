@@ -0,0 +1,220 @@
+//! `Math` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::function::FunctionObject;
+use crate::avm2::method::NativeMethod;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use rand::Rng;
+use std::f64::{consts, INFINITY, NAN, NEG_INFINITY};
+
+/// Implements `Math`
+///
+/// `Math` only has static properties, and cannot be meaningfully constructed.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Retrieve a numerical argument, treating missing arguments as `NaN`.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<f64, Error> {
+    args.get(index)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_number(activation, context)
+}
+
+macro_rules! wrap_std {
+    ( $( $name:ident => $std:expr ),* ) => {
+        $(
+            fn $name<'gc>(
+                activation: &mut Activation<'_, 'gc>,
+                context: &mut UpdateContext<'_, 'gc, '_>,
+                _this: Option<Object<'gc>>,
+                args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error> {
+                let input = number_arg(activation, context, args, 0)?;
+
+                Ok($std(input).into())
+            }
+        )*
+    };
+}
+
+wrap_std!(
+    abs => f64::abs,
+    acos => f64::acos,
+    asin => f64::asin,
+    atan => f64::atan,
+    ceil => f64::ceil,
+    cos => f64::cos,
+    exp => f64::exp,
+    floor => f64::floor,
+    log => f64::ln,
+    sin => f64::sin,
+    sqrt => f64::sqrt,
+    tan => f64::tan
+);
+
+/// Implements `Math.atan2`
+fn atan2<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let y = number_arg(activation, context, args, 0)?;
+    let x = number_arg(activation, context, args, 1)?;
+
+    Ok(y.atan2(x).into())
+}
+
+/// Implements `Math.pow`
+fn pow<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let base = number_arg(activation, context, args, 0)?;
+    let exponent = number_arg(activation, context, args, 1)?;
+
+    Ok(base.powf(exponent).into())
+}
+
+/// Implements `Math.round`
+///
+/// Halfway values are always rounded up, e.g. `Math.round(-2.5)` is `-2`.
+fn round<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = number_arg(activation, context, args, 0)?;
+
+    Ok((x + 0.5).floor().into())
+}
+
+/// Implements `Math.max`
+fn max<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut result = NEG_INFINITY;
+
+    for arg in args {
+        let value = arg.coerce_to_number(activation, context)?;
+
+        if value.is_nan() {
+            return Ok(NAN.into());
+        } else if value > result {
+            result = value;
+        }
+    }
+
+    Ok(result.into())
+}
+
+/// Implements `Math.min`
+fn min<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut result = INFINITY;
+
+    for arg in args {
+        let value = arg.coerce_to_number(activation, context)?;
+
+        if value.is_nan() {
+            return Ok(NAN.into());
+        } else if value < result {
+            result = value;
+        }
+    }
+
+    Ok(result.into())
+}
+
+/// Implements `Math.random`
+fn random<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(context.rng.gen_range(0.0f64, 1.0f64).into())
+}
+
+/// Install the constants and static methods of the `Math` class.
+pub fn fill_class<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut class: Object<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    let constants: [(&'static str, f64); 8] = [
+        ("E", consts::E),
+        ("LN10", consts::LN_10),
+        ("LN2", consts::LN_2),
+        ("LOG10E", consts::LOG10_E),
+        ("LOG2E", consts::LOG2_E),
+        ("PI", consts::PI),
+        ("SQRT1_2", consts::FRAC_1_SQRT_2),
+        ("SQRT2", consts::SQRT_2),
+    ];
+
+    for (name, value) in constants.iter() {
+        class.install_const(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            (*value).into(),
+        );
+    }
+
+    let methods: [(&'static str, NativeMethod<'gc>); 18] = [
+        ("abs", abs),
+        ("acos", acos),
+        ("asin", asin),
+        ("atan", atan),
+        ("atan2", atan2),
+        ("ceil", ceil),
+        ("cos", cos),
+        ("exp", exp),
+        ("floor", floor),
+        ("log", log),
+        ("max", max),
+        ("min", min),
+        ("pow", pow),
+        ("random", random),
+        ("round", round),
+        ("sin", sin),
+        ("sqrt", sqrt),
+        ("tan", tan),
+    ];
+
+    for (name, nf) in methods.iter() {
+        class.install_method(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            FunctionObject::from_builtin(mc, *nf, fn_proto),
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! `Number` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::primitive_object::PrimitiveObject;
+use crate::avm2::string::AvmString;
+use crate::avm2::value::{f64_to_string, Value};
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use gc_arena::MutationContext;
+use std::f64::{INFINITY, MAX, NAN, NEG_INFINITY};
+
+/// Implements `Number`
+///
+/// When called as a function, this converts its argument to a number.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value: Value<'gc> = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_number(activation, context)?
+        .into();
+
+    if let Some(Object::PrimitiveObject(this)) = this {
+        this.set_primitive(context.gc_context, value.clone());
+    }
+
+    Ok(value)
+}
+
+/// Retrieve the numerical value of `this`.
+///
+/// The methods of `Number.prototype` are shared with `int` and `uint`, so any
+/// boxed numerical primitive is accepted.
+fn this_number(this: Option<Object<'_>>) -> Result<f64, Error> {
+    match this.and_then(|this| this.as_primitive()) {
+        Some(value) if value.is_number() => value.as_number(),
+        _ => Err("TypeError: Number method called on an incompatible object".into()),
+    }
+}
+
+/// Retrieve an optional integer argument, checking that it is within range.
+fn digits_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    arg: Option<&Value<'gc>>,
+    method_name: &str,
+    range: std::ops::RangeInclusive<i32>,
+) -> Result<Option<i32>, Error> {
+    let digits = match arg {
+        None | Some(Value::Undefined) => return Ok(None),
+        Some(digits) => digits.coerce_to_i32(activation, context)?,
+    };
+
+    if !range.contains(&digits) {
+        return Err(format!(
+            "RangeError: The {} argument must be between {} and {}.",
+            method_name,
+            range.start(),
+            range.end()
+        )
+        .into());
+    }
+
+    Ok(Some(digits))
+}
+
+/// Flash prints a sign in front of positive exponents, e.g. `1e+21`.
+fn sign_exponent(mut s: String) -> String {
+    if let Some(i) = s.find('e') {
+        if s.as_bytes().get(i + 1) != Some(&b'-') {
+            s.insert(i + 1, '+');
+        }
+    }
+
+    s
+}
+
+/// Converts an `f64` to a string in the given radix.
+///
+/// Fractions which do not terminate in the given radix are cut off after 20
+/// digits.
+fn f64_to_string_radix(n: f64, radix: u32) -> String {
+    if radix == 10 || !n.is_finite() {
+        return f64_to_string(n);
+    }
+
+    let radix_f = f64::from(radix);
+    let mut integer = n.abs().trunc();
+    let mut fraction = n.abs().fract();
+    let mut digits = vec![];
+
+    loop {
+        digits.push(std::char::from_digit((integer % radix_f) as u32, radix).unwrap());
+        integer = (integer / radix_f).trunc();
+
+        if integer < 1.0 {
+            break;
+        }
+    }
+
+    if n < 0.0 {
+        digits.push('-');
+    }
+
+    let mut out: String = digits.into_iter().rev().collect();
+
+    if fraction > 0.0 {
+        out.push('.');
+
+        for _ in 0..20 {
+            fraction *= radix_f;
+            out.push(std::char::from_digit(fraction.trunc() as u32, radix).unwrap());
+            fraction = fraction.fract();
+
+            if fraction <= 0.0 {
+                break;
+            }
+        }
+    }
+
+    out
+}
+
+/// Formats an `f64` with a fixed number of digits after the decimal point.
+///
+/// Values exactly halfway between two results are rounded away from zero;
+/// Rust's own formatting would round them to even.
+fn f64_to_fixed(n: f64, digits: usize) -> String {
+    let extended = format!("{:.*}", digits + 1, n);
+
+    // `extended` is only exact if the value has no more fractional binary
+    // digits than it has decimal digits.
+    let is_tie = extended.ends_with('5') && (n * 2f64.powi(digits as i32 + 1)).fract() == 0.0;
+
+    if !is_tie {
+        return format!("{:.*}", digits, n);
+    }
+
+    let mut out = extended.into_bytes();
+    out.pop();
+
+    if out.last() == Some(&b'.') {
+        out.pop();
+    }
+
+    let mut i = out.len();
+    loop {
+        if i == 0 || out[i - 1] == b'-' {
+            out.insert(i, b'1');
+            break;
+        }
+
+        i -= 1;
+        match out[i] {
+            b'.' => continue,
+            b'9' => out[i] = b'0',
+            d => {
+                out[i] = d + 1;
+                break;
+            }
+        }
+    }
+
+    String::from_utf8(out).unwrap()
+}
+
+/// Formats an `f64` with a given number of significant digits.
+fn f64_to_precision(n: f64, precision: usize) -> String {
+    let exponential = format!("{:.*e}", precision - 1, n);
+    let exponent: i32 = exponential
+        .find('e')
+        .and_then(|i| exponential[i + 1..].parse().ok())
+        .unwrap_or(0);
+
+    if exponent < -6 || exponent >= precision as i32 {
+        sign_exponent(exponential)
+    } else {
+        format!("{:.*}", (precision as i32 - 1 - exponent) as usize, n)
+    }
+}
+
+/// Implements `Number.prototype.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let number = this_number(this)?;
+    let radix = digits_arg(activation, context, args.get(0), "radix", 2..=36)?.unwrap_or(10);
+
+    Ok(AvmString::new(
+        context.gc_context,
+        f64_to_string_radix(number, radix as u32),
+    )
+    .into())
+}
+
+/// Implements `Number.prototype.toFixed`
+pub fn to_fixed<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let number = this_number(this)?;
+    let digits = digits_arg(activation, context, args.get(0), "toFixed", 0..=20)?.unwrap_or(0);
+
+    let string = if !number.is_finite() || number.abs() >= 1e21 {
+        f64_to_string(number)
+    } else {
+        f64_to_fixed(number, digits as usize)
+    };
+
+    Ok(AvmString::new(context.gc_context, string).into())
+}
+
+/// Implements `Number.prototype.toExponential`
+pub fn to_exponential<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let number = this_number(this)?;
+    let digits = digits_arg(activation, context, args.get(0), "toExponential", 0..=20)?;
+
+    let string = if !number.is_finite() {
+        f64_to_string(number)
+    } else if let Some(digits) = digits {
+        sign_exponent(format!("{:.*e}", digits as usize, number))
+    } else {
+        sign_exponent(format!("{:e}", number))
+    };
+
+    Ok(AvmString::new(context.gc_context, string).into())
+}
+
+/// Implements `Number.prototype.toPrecision`
+pub fn to_precision<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let number = this_number(this)?;
+    let precision = digits_arg(activation, context, args.get(0), "toPrecision", 1..=21)?;
+
+    let string = match precision {
+        Some(precision) if number.is_finite() => f64_to_precision(number, precision as usize),
+        _ => f64_to_string(number),
+    };
+
+    Ok(AvmString::new(context.gc_context, string).into())
+}
+
+/// Implements `Number.prototype.valueOf`
+pub fn value_of<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    this_number(this)?;
+
+    Ok(this.and_then(|this| this.as_primitive()).unwrap())
+}
+
+/// Install the methods shared by `Number`, `int`, and `uint` into a
+/// prototype.
+pub fn fill_proto<'gc>(mc: MutationContext<'gc, '_>, proto: Object<'gc>, fn_proto: Object<'gc>) {
+    method(mc, proto, "toString", to_string, fn_proto);
+    method(mc, proto, "toLocaleString", to_string, fn_proto);
+    method(mc, proto, "toFixed", to_fixed, fn_proto);
+    method(mc, proto, "toExponential", to_exponential, fn_proto);
+    method(mc, proto, "toPrecision", to_precision, fn_proto);
+    method(mc, proto, "valueOf", value_of, fn_proto);
+}
+
+/// Construct `Number.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let proto = PrimitiveObject::from_primitive(0.0.into(), super_proto, mc).unwrap();
+
+    fill_proto(mc, proto, fn_proto);
+
+    proto
+}
+
+/// Install the constants of the `Number` class.
+pub fn fill_class<'gc>(mc: MutationContext<'gc, '_>, mut class: Object<'gc>) {
+    let constants: [(&'static str, f64); 5] = [
+        ("MAX_VALUE", MAX),
+        ("MIN_VALUE", f64::from_bits(1)),
+        ("NaN", NAN),
+        ("NEGATIVE_INFINITY", NEG_INFINITY),
+        ("POSITIVE_INFINITY", INFINITY),
+    ];
+
+    for (name, value) in constants.iter() {
+        class.install_const(
+            mc,
+            QName::new(Namespace::public_namespace(), *name),
+            0,
+            (*value).into(),
+        );
+    }
+}
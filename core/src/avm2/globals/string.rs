@@ -0,0 +1,602 @@
+//! `String` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::array_object::ArrayObject;
+use crate::avm2::function::FunctionObject;
+use crate::avm2::globals::method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::primitive_object::PrimitiveObject;
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::string_utils;
+use gc_arena::MutationContext;
+use std::cmp::Ordering;
+
+/// Implements `String`
+///
+/// When called as a function, this converts its argument to a string.
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value: Value<'gc> = match args.get(0) {
+        Some(value) => value.coerce_to_string(activation, context)?.into(),
+        None => "".into(),
+    };
+
+    if let Some(Object::PrimitiveObject(this)) = this {
+        this.set_primitive(context.gc_context, value.clone());
+    }
+
+    Ok(value)
+}
+
+/// Retrieve the string value of `this`.
+///
+/// Most `String` methods are generic, and will convert any other receiver to
+/// a string.
+fn this_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+) -> Result<AvmString<'gc>, Error> {
+    let this = this.ok_or("TypeError: String method called without a receiver")?;
+
+    this.as_primitive()
+        .unwrap_or_else(|| this.into())
+        .coerce_to_string(activation, context)
+}
+
+/// Retrieve a numerical argument, or the given default if it is missing or
+/// `undefined`.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    match args.get(index) {
+        None | Some(Value::Undefined) => Ok(default),
+        Some(value) => value.coerce_to_number(activation, context),
+    }
+}
+
+/// Normalizes an index parameter used in `String` functions such as
+/// `substring`. The returned index will be within the range of `[0, len]`.
+fn string_index(i: f64, len: usize) -> usize {
+    if i.is_nan() || i < 0.0 {
+        0
+    } else {
+        i.min(len as f64) as usize
+    }
+}
+
+/// Normalizes a wrapping index parameter used in `String` functions such as
+/// `slice`. Negative values will count backwards from `len`.
+/// The returned index will be within the range of `[0, len]`.
+fn string_wrapping_index(i: f64, len: usize) -> usize {
+    if i < 0.0 {
+        string_index(len as f64 + i.trunc(), len)
+    } else {
+        string_index(i, len)
+    }
+}
+
+/// Creates an `AvmString` from a slice of UTF-16 code units.
+/// TODO: Unpaired surrogates will get replaced with the Unicode replacement character.
+fn utf16_to_string<'gc>(mc: MutationContext<'gc, '_>, units: &[u16]) -> AvmString<'gc> {
+    AvmString::new(mc, String::from_utf16_lossy(units))
+}
+
+/// Find the first occurrence of `pattern` in `string` at or after `start`.
+fn find_utf16(string: &[u16], pattern: &[u16], start: usize) -> Option<usize> {
+    if start > string.len() {
+        return None;
+    }
+
+    if pattern.is_empty() {
+        return Some(start);
+    }
+
+    string[start..]
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+        .map(|pos| pos + start)
+}
+
+/// Implements `String.length`
+fn length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+
+    Ok((this.encode_utf16().count() as i32).into())
+}
+
+/// Implements `String.prototype.charAt`
+fn char_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+    let index = number_arg(activation, context, args, 0, 0.0)?.trunc();
+    let this: Vec<u16> = this.encode_utf16().collect();
+
+    if index >= 0.0 && index < this.len() as f64 {
+        let i = index as usize;
+        Ok(utf16_to_string(context.gc_context, &this[i..=i]).into())
+    } else {
+        Ok("".into())
+    }
+}
+
+/// Implements `String.prototype.charCodeAt`
+fn char_code_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+    let index = number_arg(activation, context, args, 0, 0.0)?.trunc();
+
+    if index >= 0.0 {
+        if let Some(unit) = this.encode_utf16().nth(index as usize) {
+            return Ok(f64::from(unit).into());
+        }
+    }
+
+    Ok(std::f64::NAN.into())
+}
+
+/// Implements `String.prototype.concat`
+fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut ret = this_string(activation, context, this)?.to_string();
+
+    for arg in args {
+        ret.push_str(&arg.coerce_to_string(activation, context)?);
+    }
+
+    Ok(AvmString::new(context.gc_context, ret).into())
+}
+
+/// Implements `String.fromCharCode`
+fn from_char_code<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut out = Vec::with_capacity(args.len());
+
+    for arg in args {
+        out.push(arg.coerce_to_u32(activation, context)? as u16);
+    }
+
+    Ok(utf16_to_string(context.gc_context, &out).into())
+}
+
+/// Implements `String.prototype.indexOf`
+fn index_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this: Vec<u16> = this_string(activation, context, this)?
+        .encode_utf16()
+        .collect();
+    let pattern: Vec<u16> = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?
+        .encode_utf16()
+        .collect();
+    let start = string_index(number_arg(activation, context, args, 1, 0.0)?, this.len());
+
+    Ok(find_utf16(&this, &pattern, start)
+        .map(|i| i as i32)
+        .unwrap_or(-1)
+        .into())
+}
+
+/// Implements `String.prototype.lastIndexOf`
+fn last_index_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this: Vec<u16> = this_string(activation, context, this)?
+        .encode_utf16()
+        .collect();
+    let pattern: Vec<u16> = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?
+        .encode_utf16()
+        .collect();
+    let start = string_index(
+        number_arg(activation, context, args, 1, f64::from(std::i32::MAX))?,
+        this.len(),
+    );
+
+    if pattern.len() > this.len() {
+        return Ok((-1).into());
+    }
+
+    let last_possible = (this.len() - pattern.len()).min(start);
+    let found = (0..=last_possible)
+        .rev()
+        .find(|&i| this[i..i + pattern.len()] == pattern[..]);
+
+    Ok(found.map(|i| i as i32).unwrap_or(-1).into())
+}
+
+/// Implements `String.prototype.localeCompare`
+fn locale_compare<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+    let other = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+
+    Ok(match this.as_str().cmp(other.as_str()) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+    .into())
+}
+
+/// Implements `String.prototype.match`
+///
+/// Regular expressions are not yet supported, so the pattern is always
+/// matched literally.
+fn match_s<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this: Vec<u16> = this_string(activation, context, this)?
+        .encode_utf16()
+        .collect();
+    let pattern = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+    let pattern_utf16: Vec<u16> = pattern.encode_utf16().collect();
+
+    if find_utf16(&this, &pattern_utf16, 0).is_none() {
+        return Ok(Value::Null);
+    }
+
+    let array = ArrayStorage::from_args(&[pattern.into()]);
+
+    Ok(ArrayObject::from_array(
+        array,
+        activation.avm2().prototypes().array,
+        context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `String.prototype.replace`
+///
+/// Regular expressions are not yet supported, so only the first literal
+/// occurrence of the pattern is replaced.
+fn replace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let string = this_string(activation, context, this)?;
+    let this: Vec<u16> = string.encode_utf16().collect();
+    let pattern = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation, context)?;
+    let pattern_utf16: Vec<u16> = pattern.encode_utf16().collect();
+
+    let start = match find_utf16(&this, &pattern_utf16, 0) {
+        Some(start) => start,
+        None => return Ok(string.into()),
+    };
+    let end = start + pattern_utf16.len();
+
+    let replacement: Vec<u16> = match args.get(1) {
+        Some(Value::Object(f)) if f.as_executable().is_some() => {
+            let args = [pattern.into(), (start as i32).into(), string.into()];
+
+            f.call(None, &args, activation, context, None)?
+                .coerce_to_string(activation, context)?
+                .encode_utf16()
+                .collect()
+        }
+        replacement => {
+            let replacement: Vec<u16> = replacement
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_string(activation, context)?
+                .encode_utf16()
+                .collect();
+            let mut out = Vec::with_capacity(replacement.len());
+            let mut chars = replacement.iter().peekable();
+
+            while let Some(&c) = chars.next() {
+                if c != u16::from(b'$') {
+                    out.push(c);
+                    continue;
+                }
+
+                match chars.peek().filter(|&&&c| c < 0x80).map(|&&c| c as u8) {
+                    Some(b'$') => out.push(c),
+                    Some(b'&') => out.extend_from_slice(&this[start..end]),
+                    Some(b'`') => out.extend_from_slice(&this[..start]),
+                    Some(b'\'') => out.extend_from_slice(&this[end..]),
+                    _ => {
+                        out.push(c);
+                        continue;
+                    }
+                }
+
+                chars.next();
+            }
+
+            out
+        }
+    };
+
+    let mut out = this[..start].to_vec();
+    out.extend(replacement);
+    out.extend_from_slice(&this[end..]);
+
+    Ok(utf16_to_string(context.gc_context, &out).into())
+}
+
+/// Implements `String.prototype.search`
+///
+/// Regular expressions are not yet supported, so the pattern is always
+/// matched literally.
+fn search<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    index_of(activation, context, this, args.get(0..1).unwrap_or(&[]))
+}
+
+/// Implements `String.prototype.slice`
+fn slice<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this: Vec<u16> = this_string(activation, context, this)?
+        .encode_utf16()
+        .collect();
+    let start = string_wrapping_index(number_arg(activation, context, args, 0, 0.0)?, this.len());
+    let end = string_wrapping_index(
+        number_arg(activation, context, args, 1, f64::from(std::i32::MAX))?,
+        this.len(),
+    );
+
+    if start < end {
+        Ok(utf16_to_string(context.gc_context, &this[start..end]).into())
+    } else {
+        Ok("".into())
+    }
+}
+
+/// Implements `String.prototype.split`
+fn split<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+    let limit = match args.get(1) {
+        None | Some(Value::Undefined) => std::u32::MAX,
+        Some(limit) => limit.coerce_to_u32(activation, context)?,
+    } as usize;
+
+    let mut pieces: Vec<Value<'gc>> = vec![];
+
+    match args.get(0) {
+        None | Some(Value::Undefined) => pieces.push(this.into()),
+        Some(delimiter) => {
+            let delimiter = delimiter.coerce_to_string(activation, context)?;
+
+            if delimiter.is_empty() {
+                // Rust's str::split would add an empty first and last item
+                // here, but Flash splits the string into its code units.
+                for unit in this.encode_utf16().take(limit) {
+                    pieces.push(utf16_to_string(context.gc_context, &[unit]).into());
+                }
+            } else {
+                for piece in this.split(delimiter.as_str()).take(limit) {
+                    pieces.push(AvmString::new(context.gc_context, piece.to_string()).into());
+                }
+            }
+        }
+    }
+
+    Ok(ArrayObject::from_array(
+        ArrayStorage::from_args(&pieces),
+        activation.avm2().prototypes().array,
+        context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `String.prototype.substr`
+fn substr<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this: Vec<u16> = this_string(activation, context, this)?
+        .encode_utf16()
+        .collect();
+    let start = string_wrapping_index(number_arg(activation, context, args, 0, 0.0)?, this.len());
+    let len = string_index(
+        number_arg(activation, context, args, 1, f64::from(std::i32::MAX))?,
+        this.len() - start,
+    );
+
+    Ok(utf16_to_string(context.gc_context, &this[start..start + len]).into())
+}
+
+/// Implements `String.prototype.substring`
+fn substring<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this: Vec<u16> = this_string(activation, context, this)?
+        .encode_utf16()
+        .collect();
+    let mut start = string_index(number_arg(activation, context, args, 0, 0.0)?, this.len());
+    let mut end = string_index(
+        number_arg(activation, context, args, 1, f64::from(std::i32::MAX))?,
+        this.len(),
+    );
+
+    // substring automatically swaps the start/end if they are flipped.
+    if end < start {
+        std::mem::swap(&mut end, &mut start);
+    }
+
+    Ok(utf16_to_string(context.gc_context, &this[start..end]).into())
+}
+
+/// Implements `String.prototype.toLowerCase`
+fn to_lower_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+
+    Ok(AvmString::new(
+        context.gc_context,
+        this.chars()
+            .map(string_utils::swf_char_to_lowercase)
+            .collect::<String>(),
+    )
+    .into())
+}
+
+/// Implements `String.prototype.toUpperCase`
+fn to_upper_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = this_string(activation, context, this)?;
+
+    Ok(AvmString::new(
+        context.gc_context,
+        this.chars()
+            .map(string_utils::swf_char_to_uppercase)
+            .collect::<String>(),
+    )
+    .into())
+}
+
+/// Implements `String.prototype.toString` and `String.prototype.valueOf`
+fn to_string_value_of<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _context: &mut UpdateContext<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match this.and_then(|this| this.as_primitive()) {
+        Some(Value::String(s)) => Ok(s.into()),
+        _ => Err("TypeError: String method called on an incompatible object".into()),
+    }
+}
+
+/// Construct `String.prototype`.
+pub fn create_proto<'gc>(
+    mc: MutationContext<'gc, '_>,
+    super_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut proto = PrimitiveObject::from_primitive("".into(), super_proto, mc).unwrap();
+
+    proto
+        .install_getter(
+            mc,
+            QName::new(Namespace::public_namespace(), "length"),
+            0,
+            FunctionObject::from_builtin(mc, length, fn_proto),
+        )
+        .unwrap();
+
+    method(mc, proto, "charAt", char_at, fn_proto);
+    method(mc, proto, "charCodeAt", char_code_at, fn_proto);
+    method(mc, proto, "concat", concat, fn_proto);
+    method(mc, proto, "indexOf", index_of, fn_proto);
+    method(mc, proto, "lastIndexOf", last_index_of, fn_proto);
+    method(mc, proto, "localeCompare", locale_compare, fn_proto);
+    method(mc, proto, "match", match_s, fn_proto);
+    method(mc, proto, "replace", replace, fn_proto);
+    method(mc, proto, "search", search, fn_proto);
+    method(mc, proto, "slice", slice, fn_proto);
+    method(mc, proto, "split", split, fn_proto);
+    method(mc, proto, "substr", substr, fn_proto);
+    method(mc, proto, "substring", substring, fn_proto);
+    method(mc, proto, "toLowerCase", to_lower_case, fn_proto);
+    method(mc, proto, "toLocaleLowerCase", to_lower_case, fn_proto);
+    method(mc, proto, "toUpperCase", to_upper_case, fn_proto);
+    method(mc, proto, "toLocaleUpperCase", to_upper_case, fn_proto);
+    method(mc, proto, "toString", to_string_value_of, fn_proto);
+    method(mc, proto, "valueOf", to_string_value_of, fn_proto);
+
+    proto
+}
+
+/// Install the static methods of the `String` class.
+pub fn fill_class<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut class: Object<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    class.install_method(
+        mc,
+        QName::new(Namespace::public_namespace(), "fromCharCode"),
+        0,
+        FunctionObject::from_builtin(mc, from_char_code, fn_proto),
+    );
+}
@@ -2,6 +2,7 @@
 
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
+use crate::avm2::value::{f64_to_string, Value};
 use crate::avm2::{Avm2, Error};
 use gc_arena::{Collect, MutationContext};
 use swf::avm2::types::{
@@ -149,6 +150,35 @@ impl<'gc> QName<'gc> {
         })
     }
 
+    /// Parse a fully-qualified class name, such as `flash.display.MovieClip`,
+    /// as used by the `SymbolClass` tag.
+    pub fn from_qualified_name(name: &str, mc: MutationContext<'gc, '_>) -> Self {
+        if let Some(dot) = name.rfind('.') {
+            Self {
+                ns: Namespace::package(AvmString::new(mc, name[..dot].to_string())),
+                name: AvmString::new(mc, name[dot + 1..].to_string()),
+            }
+        } else {
+            Self {
+                ns: Namespace::public_namespace(),
+                name: AvmString::new(mc, name.to_string()),
+            }
+        }
+    }
+
+    /// Format this name as a fully-qualified class name.
+    ///
+    /// Names outside of a package namespace are formatted as their local
+    /// name alone.
+    pub fn to_qualified_name(&self) -> String {
+        match &self.ns {
+            Namespace::Package(package) if !package.is_empty() => {
+                format!("{}.{}", package, self.name)
+            }
+            _ => self.name.to_string(),
+        }
+    }
+
     pub fn local_name(&self) -> AvmString<'gc> {
         self.name
     }
@@ -245,8 +275,8 @@ impl<'gc> Multiname<'gc> {
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
+                let name = Self::runtime_name(avm.pop(), mc)?;
                 let ns = avm.pop().as_namespace()?.clone();
-                let name = avm.pop().as_string()?;
                 Self {
                     ns: vec![ns],
                     name: Some(name),
@@ -265,7 +295,7 @@ impl<'gc> Multiname<'gc> {
             },
             AbcMultiname::MultinameL { namespace_set }
             | AbcMultiname::MultinameLA { namespace_set } => {
-                let name = avm.pop().as_string()?;
+                let name = Self::runtime_name(avm.pop(), mc)?;
                 Self {
                     ns: Self::abc_namespace_set(translation_unit, namespace_set.clone(), mc)?,
                     name: Some(name),
@@ -274,6 +304,28 @@ impl<'gc> Multiname<'gc> {
         })
     }
 
+    /// Convert a value popped off the stack into the local name of a
+    /// late-bound multiname.
+    ///
+    /// Numeric names are common when indexing arrays, and are converted to
+    /// their string form.
+    fn runtime_name(
+        name: Value<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<AvmString<'gc>, Error> {
+        Ok(match name {
+            Value::String(s) => s,
+            Value::Number(n) => AvmString::new(mc, f64_to_string(n)),
+            Value::Integer(i) => AvmString::new(mc, i.to_string()),
+            Value::Unsigned(u) => AvmString::new(mc, u.to_string()),
+            Value::Bool(true) => "true".into(),
+            Value::Bool(false) => "false".into(),
+            Value::Undefined => "undefined".into(),
+            Value::Null => "null".into(),
+            _ => return Err(format!("Cannot use {:?} as a property name", name).into()),
+        })
+    }
+
     /// Read a static multiname from the ABC constant pool
     ///
     /// This function prohibits the use of runtime-qualified and late-bound
@@ -1,18 +1,27 @@
 //! AVM2 objects.
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::array_object::ArrayObject;
 use crate::avm2::class::Class;
+use crate::avm2::dispatch_object::DispatchObject;
+use crate::avm2::event_object::EventObject;
+use crate::avm2::events::{DispatchList, Event};
 use crate::avm2::function::{Executable, FunctionObject};
 use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::primitive_object::PrimitiveObject;
 use crate::avm2::r#trait::{Trait, TraitKind};
 use crate::avm2::scope::Scope;
 use crate::avm2::script_object::ScriptObject;
+use crate::avm2::stage_object::StageObject;
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::context::UpdateContext;
+use crate::display_object::DisplayObject;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
+use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 
 /// Represents an object that can be directly interacted with by the AVM2
@@ -22,7 +31,12 @@ use std::fmt::Debug;
     #[collect(no_drop)]
     pub enum Object<'gc> {
         ScriptObject(ScriptObject<'gc>),
-        FunctionObject(FunctionObject<'gc>)
+        FunctionObject(FunctionObject<'gc>),
+        PrimitiveObject(PrimitiveObject<'gc>),
+        ArrayObject(ArrayObject<'gc>),
+        StageObject(StageObject<'gc>),
+        EventObject(EventObject<'gc>),
+        DispatchObject(DispatchObject<'gc>)
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -561,7 +575,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         _context: &mut UpdateContext<'_, 'gc, '_>,
         _base_proto: Option<Object<'gc>>,
     ) -> Result<Value<'gc>, Error> {
-        Err("Object is not callable".into())
+        Err("TypeError: Object is not callable".into())
     }
 
     /// Construct a host object of some kind and return it's cell.
@@ -675,10 +689,65 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Get a raw pointer value for this object.
     fn as_ptr(&self) -> *const ObjectPtr;
 
+    /// Unwrap this object's primitive value, if it is a boxed primitive.
+    fn as_primitive(&self) -> Option<Value<'gc>> {
+        None
+    }
+
+    /// Unwrap this object as array storage, if it is an array.
+    fn as_array_storage(&self) -> Option<Ref<ArrayStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as mutable array storage, if it is an array.
+    fn as_array_storage_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<ArrayStorage<'gc>>> {
+        None
+    }
+
     /// Get this object's `Executable`, if it has one.
     fn as_executable(&self) -> Option<Executable<'gc>> {
         None
     }
+
+    /// Get the class this object is an instance prototype for, if it was
+    /// derived from one.
+    fn as_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's display object, if it is a stage object that has
+    /// one attached.
+    fn as_display_object(&self) -> Option<DisplayObject<'gc>> {
+        None
+    }
+
+    /// Attach a display object to this object.
+    ///
+    /// This does nothing for objects that cannot represent display objects.
+    fn init_display_object(&self, _mc: MutationContext<'gc, '_>, _obj: DisplayObject<'gc>) {}
+
+    /// Unwrap this object as an event.
+    fn as_event(&self) -> Option<Ref<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable event.
+    fn as_event_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a list of event handlers.
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable list of event handlers.
+    fn as_dispatch_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -243,6 +243,10 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.as_ptr() as *const ObjectPtr
     }
 
+    fn as_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
+        self.0.read().as_class()
+    }
+
     fn construct(
         &self,
         _activation: &mut Activation<'_, 'gc>,
@@ -761,6 +765,15 @@ impl<'gc> ScriptObjectData<'gc> {
         &self.class
     }
 
+    /// Get the class this object is an instance prototype for, if any.
+    pub fn as_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
+        if let ScriptObjectClass::InstancePrototype(class, _) = self.class {
+            Some(class)
+        } else {
+            None
+        }
+    }
+
     /// Install a method into the object.
     pub fn install_method(&mut self, name: QName<'gc>, disp_id: u32, function: Object<'gc>) {
         if disp_id > 0 {
@@ -0,0 +1,450 @@
+//! AVM2 object impl for the display hierarchy.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::r#trait::Trait;
+use crate::avm2::scope::Scope;
+use crate::avm2::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which represents a display object on the stage.
+///
+/// The display object is attached when the object is constructed by a
+/// display object class, or when a display object first needs an AS3 object
+/// to represent it.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct StageObject<'gc>(GcCell<'gc, StageObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct StageObjectData<'gc> {
+    /// The base data common to all AVM2 objects.
+    base: ScriptObjectData<'gc>,
+
+    /// The associated display object, if one has been attached yet.
+    display_object: Option<DisplayObject<'gc>>,
+}
+
+impl<'gc> StageObject<'gc> {
+    /// Create a stage object for a given display object.
+    pub fn for_display_object(
+        mc: MutationContext<'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        proto: Object<'gc>,
+    ) -> Self {
+        Self(GcCell::allocate(
+            mc,
+            StageObjectData {
+                base: ScriptObjectData::base_new(Some(proto), ScriptObjectClass::NoClass),
+                display_object: Some(display_object),
+            },
+        ))
+    }
+
+    /// Construct a bare stage object with no attached display object, for use
+    /// as a prototype.
+    pub fn bare_object(mc: MutationContext<'gc, '_>, proto: Object<'gc>) -> Object<'gc> {
+        Self(GcCell::allocate(
+            mc,
+            StageObjectData {
+                base: ScriptObjectData::base_new(Some(proto), ScriptObjectClass::NoClass),
+                display_object: None,
+            },
+        ))
+        .into()
+    }
+}
+
+/// Resolve the constructor of the AS3 class linked to a display object's
+/// library symbol by a `SymbolClass` tag, if there is one.
+pub fn linked_class<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+) -> Result<Option<Object<'gc>>, Error> {
+    let class_name = display_object
+        .movie()
+        .and_then(|movie| context.library.library_for_movie(movie))
+        .and_then(|library| library.avm2_class_name(display_object.id()))
+        .map(|name| name.to_string());
+
+    if let Some(class_name) = class_name {
+        let name = QName::from_qualified_name(&class_name, context.gc_context);
+        let mut globals = activation.avm2().globals();
+        let class = globals
+            .get_property(globals, &name, activation, context)?
+            .as_object()
+            .map_err(|_| format!("Linked class {} does not exist", class_name))?;
+
+        Ok(Some(class))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Get the AVM2 object representing a display object.
+///
+/// If the display object has not been given an object yet, one is created
+/// with the prototype of its linked class, or of the builtin class matching
+/// its type.
+pub fn display_object_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    mut display_object: DisplayObject<'gc>,
+) -> Result<Object<'gc>, Error> {
+    if let Value::Object(object) = display_object.object2() {
+        return Ok(object);
+    }
+
+    let proto = if let Some(mut class) = linked_class(activation, context, display_object)? {
+        class
+            .get_property(
+                class,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                activation,
+                context,
+            )?
+            .as_object()?
+    } else if display_object.as_movie_clip().is_some() {
+        activation.avm2().prototypes().movieclip
+    } else {
+        activation.avm2().prototypes().display_object
+    };
+
+    let object: Object<'gc> =
+        StageObject::for_display_object(context.gc_context, display_object, proto).into();
+    display_object.set_object2(context.gc_context, object);
+
+    Ok(object)
+}
+
+impl<'gc> TObject<'gc> for StageObject<'gc> {
+    fn get_property_local(
+        self,
+        reciever: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let read = self.0.read();
+        let rv = read.base.get_property_local(reciever, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation, context)
+    }
+
+    fn set_property_local(
+        self,
+        reciever: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut write = self.0.write(context.gc_context);
+        let rv = write
+            .base
+            .set_property_local(reciever, name, value, activation, context)?;
+
+        drop(write);
+
+        rv.resolve(activation, context)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        reciever: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut write = self.0.write(context.gc_context);
+        let rv = write
+            .base
+            .init_property_local(reciever, name, value, activation, context)?;
+
+        drop(write);
+
+        rv.resolve(activation, context)?;
+
+        Ok(())
+    }
+
+    fn is_property_overwritable(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+    ) -> bool {
+        self.0.write(gc_context).base.is_property_overwritable(name)
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn get_slot(self, id: u32) -> Result<Value<'gc>, Error> {
+        self.0.read().base.get_slot(id)
+    }
+
+    fn set_slot(
+        self,
+        id: u32,
+        value: Value<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<(), Error> {
+        self.0.write(mc).base.set_slot(id, value, mc)
+    }
+
+    fn init_slot(
+        self,
+        id: u32,
+        value: Value<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Result<(), Error> {
+        self.0.write(mc).base.init_slot(id, value, mc)
+    }
+
+    fn get_method(self, id: u32) -> Option<Object<'gc>> {
+        self.0.read().base.get_method(id)
+    }
+
+    fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
+        self.0.read().base.get_trait(name)
+    }
+
+    fn get_provided_trait(
+        &self,
+        name: &QName<'gc>,
+        known_traits: &mut Vec<Trait<'gc>>,
+    ) -> Result<(), Error> {
+        self.0.read().base.get_provided_trait(name, known_traits)
+    }
+
+    fn get_scope(self) -> Option<GcCell<'gc, Scope<'gc>>> {
+        self.0.read().base.get_scope()
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn resolve_any_trait(
+        self,
+        local_name: AvmString<'gc>,
+    ) -> Result<Option<Namespace<'gc>>, Error> {
+        self.0.read().base.resolve_any_trait(local_name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+        self.0.read().base.has_trait(name)
+    }
+
+    fn provides_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
+        self.0.read().base.provides_trait(name)
+    }
+
+    fn has_instantiated_property(self, name: &QName<'gc>) -> bool {
+        self.0.read().base.has_instantiated_property(name)
+    }
+
+    fn has_own_virtual_getter(self, name: &QName<'gc>) -> bool {
+        self.0.read().base.has_own_virtual_getter(name)
+    }
+
+    fn has_own_virtual_setter(self, name: &QName<'gc>) -> bool {
+        self.0.read().base.has_own_virtual_setter(name)
+    }
+
+    fn proto(&self) -> Option<Object<'gc>> {
+        self.0.read().base.proto()
+    }
+
+    fn get_enumerant_name(&self, index: u32) -> Option<QName<'gc>> {
+        self.0.read().base.get_enumerant_name(index)
+    }
+
+    fn property_is_enumerable(&self, name: &QName<'gc>) -> bool {
+        self.0.read().base.property_is_enumerable(name)
+    }
+
+    fn set_local_property_is_enumerable(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        name: &QName<'gc>,
+        is_enumerable: bool,
+    ) -> Result<(), Error> {
+        self.0
+            .write(mc)
+            .base
+            .set_local_property_is_enumerable(name, is_enumerable)
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        self.0.as_ptr() as *const ObjectPtr
+    }
+
+    fn construct(
+        &self,
+        _activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::StageObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(StageObject(GcCell::allocate(
+            context.gc_context,
+            StageObjectData {
+                base,
+                display_object: None,
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        _activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::StageObject(*self);
+        let base = ScriptObjectData::base_new(
+            Some(this),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(StageObject(GcCell::allocate(
+            context.gc_context,
+            StageObjectData {
+                base,
+                display_object: None,
+            },
+        ))
+        .into())
+    }
+
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        let mut class_name = "Object".to_string();
+        let mut proto = Some(Object::from(*self));
+        while let Some(p) = proto {
+            if let Some(class) = p.as_class() {
+                class_name = class.read().name().local_name().to_string();
+                break;
+            }
+
+            proto = p.proto();
+        }
+
+        Ok(AvmString::new(mc, format!("[object {}]", class_name)).into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn install_method(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        name: QName<'gc>,
+        disp_id: u32,
+        function: Object<'gc>,
+    ) {
+        self.0
+            .write(mc)
+            .base
+            .install_method(name, disp_id, function)
+    }
+
+    fn install_getter(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        name: QName<'gc>,
+        disp_id: u32,
+        function: Object<'gc>,
+    ) -> Result<(), Error> {
+        self.0
+            .write(mc)
+            .base
+            .install_getter(name, disp_id, function)
+    }
+
+    fn install_setter(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        name: QName<'gc>,
+        disp_id: u32,
+        function: Object<'gc>,
+    ) -> Result<(), Error> {
+        self.0
+            .write(mc)
+            .base
+            .install_setter(name, disp_id, function)
+    }
+
+    fn install_dynamic_property(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        name: QName<'gc>,
+        value: Value<'gc>,
+    ) -> Result<(), Error> {
+        self.0.write(mc).base.install_dynamic_property(name, value)
+    }
+
+    fn install_slot(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        name: QName<'gc>,
+        id: u32,
+        value: Value<'gc>,
+    ) {
+        self.0.write(mc).base.install_slot(name, id, value)
+    }
+
+    fn install_const(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        name: QName<'gc>,
+        id: u32,
+        value: Value<'gc>,
+    ) {
+        self.0.write(mc).base.install_const(name, id, value)
+    }
+
+    fn interfaces(&self) -> Vec<Object<'gc>> {
+        self.0.read().base.interfaces()
+    }
+
+    fn set_interfaces(&self, context: MutationContext<'gc, '_>, iface_list: Vec<Object<'gc>>) {
+        self.0.write(context).base.set_interfaces(iface_list)
+    }
+
+    fn as_display_object(&self) -> Option<DisplayObject<'gc>> {
+        self.0.read().display_object
+    }
+
+    fn init_display_object(&self, mc: MutationContext<'gc, '_>, obj: DisplayObject<'gc>) {
+        self.0.write(mc).display_object = Some(obj);
+    }
+
+    fn as_class(&self) -> Option<GcCell<'gc, Class<'gc>>> {
+        self.0.read().base.as_class()
+    }
+}
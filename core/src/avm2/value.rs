@@ -1,11 +1,15 @@
 //! AVM2 values
 
-use crate::avm2::names::Namespace;
-use crate::avm2::object::Object;
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::primitive_object::PrimitiveObject;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
 use crate::avm2::Error;
+use crate::context::UpdateContext;
 use gc_arena::{Collect, MutationContext};
+use std::cmp::Ordering;
 use std::f64::NAN;
 use swf::avm2::types::{DefaultValue as AbcDefaultValue, Index};
 
@@ -19,6 +23,8 @@ pub enum Value<'gc> {
     Null,
     Bool(bool),
     Number(f64),
+    Integer(i32),
+    Unsigned(u32),
     String(AvmString<'gc>),
     Namespace(Namespace<'gc>),
     Object(Object<'gc>),
@@ -65,31 +71,31 @@ impl<'gc> From<f32> for Value<'gc> {
 
 impl<'gc> From<u8> for Value<'gc> {
     fn from(value: u8) -> Self {
-        Value::Number(f64::from(value))
+        Value::Integer(i32::from(value))
     }
 }
 
 impl<'gc> From<i16> for Value<'gc> {
     fn from(value: i16) -> Self {
-        Value::Number(f64::from(value))
+        Value::Integer(i32::from(value))
     }
 }
 
 impl<'gc> From<u16> for Value<'gc> {
     fn from(value: u16) -> Self {
-        Value::Number(f64::from(value))
+        Value::Integer(i32::from(value))
     }
 }
 
 impl<'gc> From<i32> for Value<'gc> {
     fn from(value: i32) -> Self {
-        Value::Number(f64::from(value))
+        Value::Integer(value)
     }
 }
 
 impl<'gc> From<u32> for Value<'gc> {
     fn from(value: u32) -> Self {
-        Value::Number(f64::from(value))
+        Value::Unsigned(value)
     }
 }
 
@@ -105,6 +111,10 @@ impl<'gc> From<Namespace<'gc>> for Value<'gc> {
     }
 }
 
+/// Strict equality, as implemented by the `===` operator.
+///
+/// `int`, `uint`, and `Number` values are all considered to be of the same
+/// type, and compare by their numerical value.
 impl PartialEq for Value<'_> {
     fn eq(&self, other: &Self) -> bool {
         match self {
@@ -120,10 +130,12 @@ impl PartialEq for Value<'_> {
                 Value::Bool(other_value) => value == other_value,
                 _ => false,
             },
-            Value::Number(value) => match other {
-                Value::Number(other_value) => value == other_value,
-                _ => false,
-            },
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => {
+                match (self.as_number(), other.as_number()) {
+                    (Ok(value), Ok(other_value)) => value == other_value,
+                    _ => false,
+                }
+            }
             Value::String(value) => match other {
                 Value::String(other_value) => value == other_value,
                 _ => false,
@@ -140,6 +152,90 @@ impl PartialEq for Value<'_> {
     }
 }
 
+/// The preferred type of a primitive value produced by `coerce_to_primitive`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Hint {
+    String,
+    Number,
+}
+
+/// Converts an `f64` to a String with the same output as Flash Player.
+///
+/// This follows ECMA-262 3rd edition `ToString` applied to the Number type:
+/// exponential notation is used for very large and very small magnitudes.
+pub fn f64_to_string(n: f64) -> String {
+    if n.is_nan() {
+        "NaN".to_string()
+    } else if n == std::f64::INFINITY {
+        "Infinity".to_string()
+    } else if n == std::f64::NEG_INFINITY {
+        "-Infinity".to_string()
+    } else if n != 0.0 && (n.abs() >= 1e21 || n.abs() < 1e-6) {
+        // Rust doesn't put a sign in front of positive exponents, but Flash
+        // does, e.g. 1e+21.
+        let mut s = format!("{:e}", n);
+        if let Some(i) = s.find('e') {
+            if s.as_bytes().get(i + 1) != Some(&b'-') {
+                s.insert(i + 1, '+');
+            }
+        }
+        s
+    } else {
+        // `-0` is printed as `0`.
+        (n + 0.0).to_string()
+    }
+}
+
+/// Converts a string to an `f64` with ECMA-262 `ToNumber` semantics.
+///
+/// Leading and trailing whitespace is ignored, the empty string is `0`, and
+/// strings that do not form a valid numeric literal are `NaN`.
+pub fn string_to_f64(s: &str) -> f64 {
+    let s = s.trim();
+
+    if s.is_empty() {
+        return 0.0;
+    }
+
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return u64::from_str_radix(hex, 16)
+            .map(|n| n as f64)
+            .unwrap_or(NAN);
+    }
+
+    match s {
+        "Infinity" | "+Infinity" => return std::f64::INFINITY,
+        "-Infinity" => return std::f64::NEG_INFINITY,
+        _ => {}
+    }
+
+    // Rust also accepts spellings of infinity and NaN that ECMAScript doesn't.
+    if s.chars()
+        .any(|c| !c.is_ascii_digit() && !matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return NAN;
+    }
+
+    s.parse().unwrap_or(NAN)
+}
+
+/// Converts an `f64` to an `u32` with ECMA-262 `ToUint32` wrapping behavior.
+/// The value will be wrapped modulo 2^32.
+#[allow(clippy::unreadable_literal)]
+pub fn f64_to_wrapping_u32(n: f64) -> u32 {
+    if !n.is_finite() {
+        0
+    } else {
+        n.trunc().rem_euclid(4294967296.0) as u32
+    }
+}
+
+/// Converts an `f64` to an `i32` with ECMA-262 `ToInt32` wrapping behavior.
+/// The value will be wrapped in the range [-2^31, 2^31).
+pub fn f64_to_wrapping_i32(n: f64) -> i32 {
+    f64_to_wrapping_u32(n) as i32
+}
+
 pub fn abc_int(translation_unit: TranslationUnit<'_>, index: Index<i32>) -> Result<i32, Error> {
     if index.0 == 0 {
         return Ok(0);
@@ -214,10 +310,40 @@ impl<'gc> Value<'gc> {
         if let Value::Object(object) = self {
             Ok(*object)
         } else {
-            Err(format!("Expected Object, found {:?}", self).into())
+            Err(format!("TypeError: Expected Object, found {:?}", self).into())
         }
     }
 
+    /// Coerce the value to an object.
+    ///
+    /// Primitive values are boxed into an object of their respective class,
+    /// so that their methods may be looked up. `undefined` and `null` cannot
+    /// be coerced to an object.
+    pub fn coerce_to_object(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<Object<'gc>, Error> {
+        let proto = match self {
+            Value::Object(object) => return Ok(*object),
+            Value::Undefined | Value::Null => {
+                return Err(format!(
+                    "TypeError: Cannot access a property or method of {:?}",
+                    self
+                )
+                .into())
+            }
+            Value::Namespace(_) => return Err("Namespace objects are not yet supported".into()),
+            Value::Bool(_) => activation.avm2().prototypes().boolean,
+            Value::Number(_) => activation.avm2().prototypes().number,
+            Value::Integer(_) => activation.avm2().prototypes().int,
+            Value::Unsigned(_) => activation.avm2().prototypes().uint,
+            Value::String(_) => activation.avm2().prototypes().string,
+        };
+
+        PrimitiveObject::from_primitive(self.clone(), proto, context.gc_context)
+    }
+
     /// Demand a string value, erroring out if one is not found.
     ///
     /// TODO: This should be replaced with `coerce_string` where possible.
@@ -228,19 +354,14 @@ impl<'gc> Value<'gc> {
         }
     }
 
-    /// Coerce a value into a string.
-    pub fn coerce_string(self) -> AvmString<'gc> {
-        match self {
-            Value::String(s) => s,
-            Value::Bool(true) => "true".into(),
-            Value::Bool(false) => "false".into(),
-            _ => "".into(),
-        }
-    }
-
+    /// Demand a numerical value, erroring out if one is not found.
+    ///
+    /// `int` and `uint` values are widened to a `Number`.
     pub fn as_number(&self) -> Result<f64, Error> {
         match self {
             Value::Number(f) => Ok(*f),
+            Value::Integer(i) => Ok(f64::from(*i)),
+            Value::Unsigned(u) => Ok(f64::from(*u)),
             _ => Err(format!("Expected Number, found {:?}", self).into()),
         }
     }
@@ -259,4 +380,269 @@ impl<'gc> Value<'gc> {
             _ => Err(format!("Expected Namespace, found {:?}", self).into()),
         }
     }
+
+    /// Determine if this value is a primitive, i.e. anything but an object.
+    pub fn is_primitive(&self) -> bool {
+        match self {
+            Value::Object(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Coerce the value to a boolean.
+    ///
+    /// Boolean coercion happens according to the rules specified in the ES4
+    /// draft proposals, which appear to be identical to ECMA-262 Edition 3.
+    pub fn coerce_to_boolean(&self) -> bool {
+        match self {
+            Value::Undefined | Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(f) => !f.is_nan() && *f != 0.0,
+            Value::Integer(i) => *i != 0,
+            Value::Unsigned(u) => *u != 0,
+            Value::String(s) => !s.is_empty(),
+            Value::Namespace(_) => true,
+            Value::Object(_) => true,
+        }
+    }
+
+    /// Coerce the value to a primitive.
+    ///
+    /// This function is guaranteed to return either a primitive value, or a
+    /// `TypeError`.
+    ///
+    /// The `hint` parameter selects if we call `valueOf` or `toString` first.
+    /// Objects that yield a primitive from neither method cannot be coerced.
+    pub fn coerce_to_primitive(
+        &self,
+        hint: Hint,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let object = match self {
+            Value::Object(o) => *o,
+            _ => return Ok(self.clone()),
+        };
+
+        let methods = match hint {
+            Hint::String => ["toString", "valueOf"],
+            Hint::Number => ["valueOf", "toString"],
+        };
+
+        for method_name in methods.iter() {
+            let name = QName::new(Namespace::public_namespace(), *method_name);
+            let base_proto = object.get_base_proto(&name)?;
+            let method = object
+                .clone()
+                .get_property(object, &name, activation, context)?;
+
+            if let Value::Object(method) = method {
+                let primitive = method.call(Some(object), &[], activation, context, base_proto)?;
+
+                if primitive.is_primitive() {
+                    return Ok(primitive);
+                }
+            }
+        }
+
+        Err("TypeError: Cannot convert object to primitive value.".into())
+    }
+
+    /// Coerce the value to a floating-point number.
+    ///
+    /// This function returns the resulting floating-point directly; or a
+    /// TypeError if the value is an `Object` that cannot be converted to a
+    /// primitive value.
+    ///
+    /// Numerical conversions occur according to ECMA-262 3rd Edition's
+    /// `ToNumber` algorithm.
+    pub fn coerce_to_number(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<f64, Error> {
+        Ok(match self {
+            Value::Undefined => NAN,
+            Value::Null => 0.0,
+            Value::Bool(true) => 1.0,
+            Value::Bool(false) => 0.0,
+            Value::Number(f) => *f,
+            Value::Integer(i) => f64::from(*i),
+            Value::Unsigned(u) => f64::from(*u),
+            Value::String(s) => string_to_f64(s),
+            Value::Namespace(ns) => string_to_f64(&namespace_uri(ns)),
+            Value::Object(_) => self
+                .coerce_to_primitive(Hint::Number, activation, context)?
+                .coerce_to_number(activation, context)?,
+        })
+    }
+
+    /// Coerce the value to a 32-bit unsigned integer.
+    ///
+    /// This function returns the resulting u32 directly; or a TypeError if the
+    /// value is an `Object` that cannot be converted to a primitive value.
+    ///
+    /// Numerical conversions occur according to ECMA-262 3rd Edition's
+    /// `ToUint32` algorithm.
+    pub fn coerce_to_u32(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<u32, Error> {
+        Ok(match self {
+            Value::Integer(i) => *i as u32,
+            Value::Unsigned(u) => *u,
+            _ => f64_to_wrapping_u32(self.coerce_to_number(activation, context)?),
+        })
+    }
+
+    /// Coerce the value to a 32-bit signed integer.
+    ///
+    /// This function returns the resulting i32 directly; or a TypeError if the
+    /// value is an `Object` that cannot be converted to a primitive value.
+    ///
+    /// Numerical conversions occur according to ECMA-262 3rd Edition's
+    /// `ToInt32` algorithm.
+    pub fn coerce_to_i32(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<i32, Error> {
+        Ok(match self {
+            Value::Integer(i) => *i,
+            Value::Unsigned(u) => *u as i32,
+            _ => f64_to_wrapping_i32(self.coerce_to_number(activation, context)?),
+        })
+    }
+
+    /// Coerce the value to a String.
+    ///
+    /// This function returns the resulting String directly; or a TypeError if
+    /// the value is an `Object` that cannot be converted to a primitive value.
+    ///
+    /// String conversions generally occur according to ECMA-262 3rd Edition's
+    /// `ToString` algorithm.
+    pub fn coerce_to_string(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<AvmString<'gc>, Error> {
+        Ok(match self {
+            Value::Undefined => "undefined".into(),
+            Value::Null => "null".into(),
+            Value::Bool(true) => "true".into(),
+            Value::Bool(false) => "false".into(),
+            Value::Number(n) => AvmString::new(context.gc_context, f64_to_string(*n)),
+            Value::Integer(i) => AvmString::new(context.gc_context, i.to_string()),
+            Value::Unsigned(u) => AvmString::new(context.gc_context, u.to_string()),
+            Value::String(s) => *s,
+            Value::Namespace(ns) => namespace_uri(ns),
+            Value::Object(_) => self
+                .coerce_to_primitive(Hint::String, activation, context)?
+                .coerce_to_string(activation, context)?,
+        })
+    }
+
+    /// Determine if two values are abstractly equal to each other.
+    ///
+    /// This abstract equality algorithm is intended to match ECMA-262 3rd
+    /// edition, section 11.9.3. `int`, `uint`, and `Number` values are all
+    /// treated as the Number type.
+    pub fn abstract_eq(
+        &self,
+        other: &Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<bool, Error> {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined)
+            | (Value::Null, Value::Null)
+            | (Value::Undefined, Value::Null)
+            | (Value::Null, Value::Undefined) => Ok(true),
+            (Value::Namespace(ns1), Value::Namespace(ns2)) => {
+                Ok(namespace_uri(ns1) == namespace_uri(ns2))
+            }
+            (a, b) if a.is_number() && b.is_number() => Ok(a.as_number()? == b.as_number()?),
+            (Value::String(_), Value::String(_))
+            | (Value::Bool(_), Value::Bool(_))
+            | (Value::Object(_), Value::Object(_)) => Ok(self == other),
+            (a, Value::String(b)) if a.is_number() => Ok(a.as_number()? == string_to_f64(b)),
+            (Value::String(a), b) if b.is_number() => Ok(string_to_f64(a) == b.as_number()?),
+            (Value::Bool(a), _) => {
+                Value::Number(if *a { 1.0 } else { 0.0 }).abstract_eq(other, activation, context)
+            }
+            (_, Value::Bool(b)) => self.abstract_eq(
+                &Value::Number(if *b { 1.0 } else { 0.0 }),
+                activation,
+                context,
+            ),
+            (_, Value::Object(_)) if self.is_number() || self.is_string() => {
+                let primitive = other.coerce_to_primitive(Hint::Number, activation, context)?;
+
+                self.abstract_eq(&primitive, activation, context)
+            }
+            (Value::Object(_), _) if other.is_number() || other.is_string() => {
+                let primitive = self.coerce_to_primitive(Hint::Number, activation, context)?;
+
+                primitive.abstract_eq(other, activation, context)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Determine if this value is abstractly less than the other.
+    ///
+    /// This abstract relational comparison algorithm is intended to match
+    /// ECMA-262 3rd edition, section 11.8.5. It returns `None` if either
+    /// operand is `NaN`, which all relational operators treat as `false`.
+    pub fn abstract_lt(
+        &self,
+        other: &Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<Option<bool>, Error> {
+        let prim_self = self.coerce_to_primitive(Hint::Number, activation, context)?;
+        let prim_other = other.coerce_to_primitive(Hint::Number, activation, context)?;
+
+        if let (Value::String(s), Value::String(o)) = (&prim_self, &prim_other) {
+            return Ok(Some(s.as_str() < o.as_str()));
+        }
+
+        let num_self = prim_self.coerce_to_number(activation, context)?;
+        let num_other = prim_other.coerce_to_number(activation, context)?;
+
+        Ok(num_self
+            .partial_cmp(&num_other)
+            .map(|ord| ord == Ordering::Less))
+    }
+
+    /// Determine if this value is a `String`.
+    pub fn is_string(&self) -> bool {
+        match self {
+            Value::String(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Determine if this value is an `int`, `uint`, or `Number`.
+    pub fn is_number(&self) -> bool {
+        match self {
+            Value::Number(_) | Value::Integer(_) | Value::Unsigned(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Retrieve the URI of a namespace, which is also its string value.
+fn namespace_uri<'gc>(ns: &Namespace<'gc>) -> AvmString<'gc> {
+    match ns {
+        Namespace::Namespace(uri)
+        | Namespace::Package(uri)
+        | Namespace::PackageInternal(uri)
+        | Namespace::Protected(uri)
+        | Namespace::Explicit(uri)
+        | Namespace::StaticProtected(uri)
+        | Namespace::Private(uri) => *uri,
+        Namespace::Any => "*".into(),
+    }
 }
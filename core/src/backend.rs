@@ -1,5 +1,7 @@
 pub mod audio;
 pub mod input;
+pub mod locale;
 pub mod navigator;
 pub mod render;
 pub mod storage;
+pub mod video;
@@ -17,6 +17,9 @@ pub trait InputBackend: Downcast {
 
     /// Set the clipboard to the given content
     fn set_clipboard_content(&mut self, content: String);
+
+    /// Get the current content of the clipboard
+    fn clipboard_content(&mut self) -> String;
 }
 impl_downcast!(InputBackend);
 
@@ -49,6 +52,10 @@ impl InputBackend for NullInputBackend {
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
     fn set_clipboard_content(&mut self, _content: String) {}
+
+    fn clipboard_content(&mut self) -> String {
+        String::new()
+    }
 }
 
 impl Default for NullInputBackend {
@@ -0,0 +1,53 @@
+use downcast_rs::Downcast;
+
+/// Provides the current time and local time zone to the player.
+///
+/// All times are expressed as milliseconds since the Unix epoch, in UTC.
+pub trait LocaleBackend: Downcast {
+    /// The current time.
+    fn current_time(&self) -> f64;
+
+    /// The offset of local time from UTC at the given time, in minutes.
+    ///
+    /// Positive offsets are east of UTC, so `GMT+0100` is `60`.
+    fn local_utc_offset(&self, utc_time: f64) -> i32;
+}
+impl_downcast!(LocaleBackend);
+
+/// Locale backend with a fixed clock and time zone.
+///
+/// Used by tests and frontends that have no access to the system clock.
+pub struct NullLocaleBackend {
+    current_time: f64,
+    utc_offset: i32,
+}
+
+impl NullLocaleBackend {
+    pub fn new() -> Self {
+        Self::with_time(0.0, 0)
+    }
+
+    /// Creates a backend that always reports the given time and UTC offset.
+    pub fn with_time(current_time: f64, utc_offset: i32) -> Self {
+        Self {
+            current_time,
+            utc_offset,
+        }
+    }
+}
+
+impl LocaleBackend for NullLocaleBackend {
+    fn current_time(&self) -> f64 {
+        self.current_time
+    }
+
+    fn local_utc_offset(&self, _utc_time: f64) -> i32 {
+        self.utc_offset
+    }
+}
+
+impl Default for NullLocaleBackend {
+    fn default() -> Self {
+        NullLocaleBackend::new()
+    }
+}
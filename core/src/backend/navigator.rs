@@ -51,6 +51,9 @@ pub struct RequestOptions {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// Additional HTTP headers to send with the request.
+    headers: Vec<(String, String)>,
 }
 
 impl RequestOptions {
@@ -59,6 +62,7 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::GET,
             body: None,
+            headers: Vec::new(),
         }
     }
 
@@ -67,9 +71,16 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::POST,
             body,
+            headers: Vec::new(),
         }
     }
 
+    /// Add HTTP headers to be sent with this request.
+    pub fn with_headers(mut self, headers: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
     /// Retrieve the navigation method for this request.
     pub fn method(&self) -> NavigationMethod {
         self.method
@@ -79,6 +90,11 @@ impl RequestOptions {
     pub fn body(&self) -> &Option<(Vec<u8>, String)> {
         &self.body
     }
+
+    /// Retrieve the additional HTTP headers of this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
 }
 
 /// Type alias for pinned, boxed, and owned futures that output a falliable
@@ -288,6 +304,11 @@ impl NavigatorBackend for NullNavigatorBackend {
     }
 
     fn fetch(&self, url: &str, _opts: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        // Query strings and fragments have no meaning for local files.
+        let url = url
+            .split(|c| c == '?' || c == '#')
+            .next()
+            .unwrap_or_default();
         let mut path = self.relative_base_path.clone();
         path.push(url);
 
@@ -4,10 +4,21 @@ use downcast_rs::Downcast;
 use std::io::Read;
 pub use swf;
 
+pub mod filters;
+
 pub trait RenderBackend: Downcast {
     fn set_viewport_dimensions(&mut self, width: u32, height: u32);
-    fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle;
-    fn replace_shape(&mut self, shape: DistilledShape, handle: ShapeHandle);
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle;
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    );
     fn register_glyph_shape(&mut self, shape: &swf::Glyph) -> ShapeHandle;
     fn register_bitmap_jpeg(
         &mut self,
@@ -31,6 +42,27 @@ pub trait RenderBackend: Downcast {
         swf_tag: &swf::DefineBitsLossless,
     ) -> Result<BitmapInfo, Error>;
 
+    /// Registers a bitmap from raw RGBA pixels with premultiplied alpha.
+    ///
+    /// Unlike the other `register_bitmap` methods, this bitmap isn't tied to a
+    /// character, and its pixels can later be replaced with `update_texture`.
+    /// This is used for content generated at runtime, such as video frames.
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapInfo, Error>;
+
+    /// Replaces the pixels of a bitmap registered with `register_bitmap_raw`.
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error>;
+
     fn begin_frame(&mut self, clear: Color);
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform);
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
@@ -39,9 +71,53 @@ pub trait RenderBackend: Downcast {
     fn push_mask(&mut self);
     fn activate_mask(&mut self);
     fn pop_mask(&mut self);
+
+    /// Begins drawing a display object with filters.
+    ///
+    /// Everything rendered until the matching `pop_filters` call is drawn
+    /// to an offscreen layer, which has the filters applied to it before it
+    /// is composited onto the previous layer.
+    fn push_filters(&mut self, filters: &[swf::Filter]);
+    fn pop_filters(&mut self);
+
+    /// Begins drawing a display object with a non-normal blend mode.
+    ///
+    /// Everything rendered until the matching `pop_blend_mode` call is
+    /// composited onto the content below it using the given blend mode.
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode);
+    fn pop_blend_mode(&mut self);
+
+    /// Renders content into an offscreen buffer of the given size.
+    ///
+    /// Everything drawn by `draw` is rendered onto a transparent buffer
+    /// instead of the stage, and the result is returned as RGBA rows with
+    /// premultiplied alpha. This is used by `BitmapData.draw`.
+    fn render_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        draw: &mut dyn FnMut(&mut dyn RenderBackend),
+    ) -> Result<Vec<u8>, Error>;
 }
 impl_downcast!(RenderBackend);
 
+/// Resolves the bitmaps used by the bitmap fills of a shape.
+///
+/// The `id` of a `FillStyle::Bitmap` is usually a character in the movie
+/// library, but shapes drawn at runtime may use their own numbering.
+pub trait BitmapSource {
+    fn bitmap(&self, id: swf::CharacterId) -> Option<BitmapInfo>;
+}
+
+/// A `BitmapSource` without any bitmaps, for shapes that have no bitmap fills.
+pub struct NullBitmapSource;
+
+impl BitmapSource for NullBitmapSource {
+    fn bitmap(&self, _id: swf::CharacterId) -> Option<BitmapInfo> {
+        None
+    }
+}
+
 type Error = Box<dyn std::error::Error>;
 
 #[derive(Copy, Clone, Debug)]
@@ -81,10 +157,20 @@ impl Default for NullRenderer {
 
 impl RenderBackend for NullRenderer {
     fn set_viewport_dimensions(&mut self, _width: u32, _height: u32) {}
-    fn register_shape(&mut self, _shape: DistilledShape) -> ShapeHandle {
+    fn register_shape(
+        &mut self,
+        _shape: DistilledShape,
+        _bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
         ShapeHandle(0)
     }
-    fn replace_shape(&mut self, _shape: DistilledShape, _handle: ShapeHandle) {}
+    fn replace_shape(
+        &mut self,
+        _shape: DistilledShape,
+        _bitmap_source: &dyn BitmapSource,
+        _handle: ShapeHandle,
+    ) {
+    }
     fn register_glyph_shape(&mut self, _shape: &swf::Glyph) -> ShapeHandle {
         ShapeHandle(0)
     }
@@ -133,6 +219,27 @@ impl RenderBackend for NullRenderer {
             height: 0,
         })
     }
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapInfo, Error> {
+        Ok(BitmapInfo {
+            handle: BitmapHandle(0),
+            width: width as u16,
+            height: height as u16,
+        })
+    }
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(bitmap)
+    }
     fn begin_frame(&mut self, _clear: Color) {}
     fn end_frame(&mut self) {}
     fn render_bitmap(&mut self, _bitmap: BitmapHandle, _transform: &Transform) {}
@@ -141,6 +248,19 @@ impl RenderBackend for NullRenderer {
     fn push_mask(&mut self) {}
     fn activate_mask(&mut self) {}
     fn pop_mask(&mut self) {}
+    fn push_filters(&mut self, _filters: &[swf::Filter]) {}
+    fn pop_filters(&mut self) {}
+    fn push_blend_mode(&mut self, _blend_mode: swf::BlendMode) {}
+    fn pop_blend_mode(&mut self) {}
+    fn render_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        _draw: &mut dyn FnMut(&mut dyn RenderBackend),
+    ) -> Result<Vec<u8>, Error> {
+        // Nothing is ever drawn, so the result is fully transparent.
+        Ok(vec![0; width as usize * height as usize * 4])
+    }
 }
 
 /// The format of image data in a DefineBitsJpeg2/3 tag.
@@ -0,0 +1,509 @@
+//! Software implementations of display object filters.
+//!
+//! Render backends draw a filtered display object into an offscreen layer,
+//! read the layer back into a `FilterImage`, and apply the object's filters
+//! to it before compositing the result onto the parent layer.
+
+use swf::{Color, Filter, GradientRecord};
+
+/// An RGBA image with premultiplied alpha and components in the range `0.0..=1.0`.
+#[derive(Clone, Debug)]
+pub struct FilterImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl FilterImage {
+    /// Creates a fully transparent image.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0.0; 4]; width * height],
+        }
+    }
+
+    /// Creates an image from 8-bit RGBA data with premultiplied alpha.
+    pub fn from_premultiplied_rgba(width: usize, height: usize, data: &[u8]) -> Self {
+        let pixels = data
+            .chunks_exact(4)
+            .map(|c| {
+                [
+                    f32::from(c[0]) / 255.0,
+                    f32::from(c[1]) / 255.0,
+                    f32::from(c[2]) / 255.0,
+                    f32::from(c[3]) / 255.0,
+                ]
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Creates an image from 8-bit RGBA data with straight alpha, such as the
+    /// data returned by an HTML canvas.
+    pub fn from_rgba(width: usize, height: usize, data: &[u8]) -> Self {
+        let mut image = Self::from_premultiplied_rgba(width, height, data);
+        for pixel in &mut image.pixels {
+            *pixel = premultiply(*pixel);
+        }
+        image
+    }
+
+    /// Returns the image as 8-bit RGBA data with premultiplied alpha.
+    pub fn to_premultiplied_rgba(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in &self.pixels {
+            data.extend(pixel.iter().map(|&c| to_u8(c)));
+        }
+        data
+    }
+
+    /// Returns the image as 8-bit RGBA data with straight alpha.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in &self.pixels {
+            data.extend(unpremultiply(*pixel).iter().map(|&c| to_u8(c)));
+        }
+        data
+    }
+
+    /// Applies a list of filters to the image, in order.
+    pub fn apply_filters(&mut self, filters: &[Filter]) {
+        for filter in filters {
+            self.apply_filter(filter);
+        }
+    }
+
+    /// Applies a single filter to the image.
+    pub fn apply_filter(&mut self, filter: &Filter) {
+        match filter {
+            Filter::BlurFilter(filter) => {
+                self.blur(filter.blur_x, filter.blur_y, filter.num_passes);
+            }
+            Filter::DropShadowFilter(filter) => {
+                let mut effect = self.effect_plane(
+                    filter.is_inner,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.num_passes,
+                );
+                effect = self.offset(
+                    &effect,
+                    filter.angle,
+                    filter.distance,
+                    if filter.is_inner { 1.0 } else { 0.0 },
+                );
+                let color = color_to_f32(&filter.color);
+                self.composite_effect(filter.is_inner, filter.is_knockout, false, |i| {
+                    tint(color, clamp(effect[i] * filter.strength))
+                });
+            }
+            Filter::GlowFilter(filter) => {
+                let effect = self.effect_plane(
+                    filter.is_inner,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.num_passes,
+                );
+                let color = color_to_f32(&filter.color);
+                self.composite_effect(filter.is_inner, filter.is_knockout, false, |i| {
+                    tint(color, clamp(effect[i] * filter.strength))
+                });
+            }
+            Filter::GradientGlowFilter(filter) => {
+                let mut effect = self.effect_plane(
+                    filter.is_inner,
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.num_passes,
+                );
+                effect = self.offset(
+                    &effect,
+                    filter.angle,
+                    filter.distance,
+                    if filter.is_inner { 1.0 } else { 0.0 },
+                );
+                let gradient = GradientLookup::new(&filter.colors);
+                self.composite_effect(filter.is_inner, filter.is_knockout, filter.is_on_top, |i| {
+                    gradient.sample(clamp(effect[i] * filter.strength))
+                });
+            }
+            Filter::BevelFilter(filter) => {
+                let bevel = self.bevel_plane(
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.angle,
+                    filter.distance,
+                    filter.num_passes,
+                );
+                let shadow = color_to_f32(&filter.shadow_color);
+                let highlight = color_to_f32(&filter.highlight_color);
+                self.composite_effect(filter.is_inner, filter.is_knockout, filter.is_on_top, |i| {
+                    let value = (bevel[i] * filter.strength).max(-1.0).min(1.0);
+                    if value >= 0.0 {
+                        tint(highlight, value)
+                    } else {
+                        tint(shadow, -value)
+                    }
+                });
+            }
+            Filter::GradientBevelFilter(filter) => {
+                let bevel = self.bevel_plane(
+                    filter.blur_x,
+                    filter.blur_y,
+                    filter.angle,
+                    filter.distance,
+                    filter.num_passes,
+                );
+                let gradient = GradientLookup::new(&filter.colors);
+                self.composite_effect(filter.is_inner, filter.is_knockout, filter.is_on_top, |i| {
+                    let value = (bevel[i] * filter.strength).max(-1.0).min(1.0);
+                    // The middle of the gradient is used where there is no bevel.
+                    gradient.sample((value + 1.0) / 2.0)
+                });
+            }
+            Filter::ColorMatrixFilter(filter) => self.color_matrix(&filter.matrix),
+            Filter::ConvolutionFilter(filter) => self.convolution(filter),
+        }
+    }
+
+    /// Blurs every channel of the image.
+    fn blur(&mut self, blur_x: f64, blur_y: f64, num_passes: u8) {
+        let mut planes: Vec<Vec<f32>> = (0..4)
+            .map(|c| self.pixels.iter().map(|p| p[c]).collect())
+            .collect();
+        for plane in &mut planes {
+            box_blur(plane, self.width, self.height, blur_x, blur_y, num_passes);
+        }
+        for (i, pixel) in self.pixels.iter_mut().enumerate() {
+            *pixel = [planes[0][i], planes[1][i], planes[2][i], planes[3][i]];
+        }
+    }
+
+    /// Returns the blurred alpha channel of the image, or its inverse for
+    /// inner effects.
+    fn effect_plane(&self, is_inner: bool, blur_x: f64, blur_y: f64, num_passes: u8) -> Vec<f32> {
+        let mut plane: Vec<f32> = self
+            .pixels
+            .iter()
+            .map(|p| if is_inner { 1.0 - p[3] } else { p[3] })
+            .collect();
+        box_blur(
+            &mut plane,
+            self.width,
+            self.height,
+            blur_x,
+            blur_y,
+            num_passes,
+        );
+        plane
+    }
+
+    /// Returns the bevel intensity of each pixel, from -1.0 (fully shadowed)
+    /// to 1.0 (fully highlighted).
+    fn bevel_plane(
+        &self,
+        blur_x: f64,
+        blur_y: f64,
+        angle: f64,
+        distance: f64,
+        num_passes: u8,
+    ) -> Vec<f32> {
+        let plane = self.effect_plane(false, blur_x, blur_y, num_passes);
+        // The highlight is on the side facing away from the light direction.
+        let lit = self.offset(&plane, angle, -distance, 0.0);
+        let shaded = self.offset(&plane, angle, distance, 0.0);
+        lit.iter().zip(shaded.iter()).map(|(l, s)| l - s).collect()
+    }
+
+    /// Moves a plane by `distance` pixels in the direction of `angle` (in
+    /// radians), filling uncovered pixels with `fill`.
+    fn offset(&self, plane: &[f32], angle: f64, distance: f64, fill: f32) -> Vec<f32> {
+        let dx = (angle.cos() * distance).round() as isize;
+        let dy = (angle.sin() * distance).round() as isize;
+        if dx == 0 && dy == 0 {
+            return plane.to_vec();
+        }
+
+        let (width, height) = (self.width as isize, self.height as isize);
+        let mut out = vec![fill; plane.len()];
+        for y in 0..height {
+            let src_y = y - dy;
+            if src_y < 0 || src_y >= height {
+                continue;
+            }
+            for x in 0..width {
+                let src_x = x - dx;
+                if src_x >= 0 && src_x < width {
+                    out[(y * width + x) as usize] = plane[(src_y * width + src_x) as usize];
+                }
+            }
+        }
+        out
+    }
+
+    /// Composites a generated effect color with the image.
+    ///
+    /// Inner effects are clipped to the object, outer effects appear behind
+    /// it, and effects on top cover the entire object. Knockout effects hide
+    /// the object itself.
+    fn composite_effect<F>(&mut self, is_inner: bool, is_knockout: bool, is_on_top: bool, effect: F)
+    where
+        F: Fn(usize) -> [f32; 4],
+    {
+        for (i, pixel) in self.pixels.iter_mut().enumerate() {
+            let source = *pixel;
+            let color = effect(i);
+            *pixel = if is_on_top {
+                if is_knockout {
+                    scale(color, source[3])
+                } else {
+                    over(scale(color, source[3]), source)
+                }
+            } else if is_inner {
+                let color = scale(color, source[3]);
+                if is_knockout {
+                    color
+                } else {
+                    over(color, source)
+                }
+            } else if is_knockout {
+                scale(color, 1.0 - source[3])
+            } else {
+                over(source, color)
+            };
+        }
+    }
+
+    /// Transforms the straight alpha color of each pixel by a 4x5 matrix.
+    /// The offsets in the last column are in the range `0..=255`.
+    fn color_matrix(&mut self, matrix: &[f64; 20]) {
+        let matrix: Vec<f32> = matrix.iter().map(|&n| n as f32).collect();
+        for pixel in &mut self.pixels {
+            let color = unpremultiply(*pixel);
+            let row = |i: usize| {
+                let m = &matrix[i..i + 5];
+                clamp(
+                    m[0] * color[0]
+                        + m[1] * color[1]
+                        + m[2] * color[2]
+                        + m[3] * color[3]
+                        + m[4] / 255.0,
+                )
+            };
+            *pixel = premultiply([row(0), row(5), row(10), row(15)]);
+        }
+    }
+
+    /// Applies a convolution kernel to the straight alpha colors of the image.
+    fn convolution(&mut self, filter: &swf::ConvolutionFilter) {
+        let cols = usize::from(filter.num_matrix_cols);
+        let rows = usize::from(filter.num_matrix_rows);
+        if cols == 0 || rows == 0 || filter.matrix.len() < cols * rows {
+            return;
+        }
+
+        let divisor = if filter.divisor == 0.0 {
+            1.0
+        } else {
+            filter.divisor as f32
+        };
+        let bias = filter.bias as f32 / 255.0;
+        let default_color = color_to_f32(&filter.default_color);
+        let source: Vec<[f32; 4]> = self.pixels.iter().map(|&p| unpremultiply(p)).collect();
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0.0; 4];
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let weight = filter.matrix[row * cols + col] as f32;
+                        let mut src_x = x + col as isize - (cols / 2) as isize;
+                        let mut src_y = y + row as isize - (rows / 2) as isize;
+                        let sample = if src_x >= 0 && src_x < width && src_y >= 0 && src_y < height
+                        {
+                            source[(src_y * width + src_x) as usize]
+                        } else if filter.is_clamped {
+                            src_x = src_x.max(0).min(width - 1);
+                            src_y = src_y.max(0).min(height - 1);
+                            source[(src_y * width + src_x) as usize]
+                        } else {
+                            default_color
+                        };
+                        for c in 0..4 {
+                            sum[c] += sample[c] * weight;
+                        }
+                    }
+                }
+
+                let i = (y * width + x) as usize;
+                let mut color = [0.0; 4];
+                for c in 0..4 {
+                    color[c] = clamp(sum[c] / divisor + bias);
+                }
+                if filter.is_preserve_alpha {
+                    color[3] = source[i][3];
+                }
+                self.pixels[i] = premultiply(color);
+            }
+        }
+    }
+}
+
+/// Maps a value in the range `0.0..=1.0` to a color of a filter gradient.
+struct GradientLookup {
+    records: Vec<(f32, [f32; 4])>,
+}
+
+impl GradientLookup {
+    fn new(records: &[GradientRecord]) -> Self {
+        Self {
+            records: records
+                .iter()
+                .map(|r| (f32::from(r.ratio) / 255.0, color_to_f32(&r.color)))
+                .collect(),
+        }
+    }
+
+    /// Returns the premultiplied color of the gradient at `ratio`.
+    fn sample(&self, ratio: f32) -> [f32; 4] {
+        let color = match self.records.iter().position(|(r, _)| *r >= ratio) {
+            None => self.records.last().map(|(_, c)| *c).unwrap_or([0.0; 4]),
+            Some(0) => self.records[0].1,
+            Some(i) => {
+                let (start_ratio, start) = self.records[i - 1];
+                let (end_ratio, end) = self.records[i];
+                let t = if end_ratio > start_ratio {
+                    (ratio - start_ratio) / (end_ratio - start_ratio)
+                } else {
+                    0.0
+                };
+                let mut color = [0.0; 4];
+                for c in 0..4 {
+                    color[c] = start[c] + (end[c] - start[c]) * t;
+                }
+                color
+            }
+        };
+        premultiply(color)
+    }
+}
+
+/// Blurs a single channel with repeated horizontal and vertical box blurs.
+///
+/// Pixels outside of the image are treated as zero.
+fn box_blur(
+    plane: &mut [f32],
+    width: usize,
+    height: usize,
+    blur_x: f64,
+    blur_y: f64,
+    num_passes: u8,
+) {
+    let radius_x = (blur_x.max(0.0) / 2.0).floor() as usize;
+    let radius_y = (blur_y.max(0.0) / 2.0).floor() as usize;
+    let mut line = Vec::with_capacity(width.max(height));
+    for _ in 0..num_passes {
+        if radius_x > 0 {
+            for y in 0..height {
+                line.clear();
+                line.extend_from_slice(&plane[y * width..(y + 1) * width]);
+                blur_line(&line, radius_x, |x, value| plane[y * width + x] = value);
+            }
+        }
+        if radius_y > 0 {
+            for x in 0..width {
+                line.clear();
+                line.extend((0..height).map(|y| plane[y * width + x]));
+                blur_line(&line, radius_y, |y, value| plane[y * width + x] = value);
+            }
+        }
+    }
+}
+
+/// Box blurs a line of values with a running sum, passing each result to `output`.
+fn blur_line<F: FnMut(usize, f32)>(line: &[f32], radius: usize, mut output: F) {
+    let len = line.len();
+    let size = (radius * 2 + 1) as f32;
+    let mut sum: f32 = line.iter().take(radius).sum();
+    for i in 0..len {
+        if i + radius < len {
+            sum += line[i + radius];
+        }
+        if i > radius {
+            sum -= line[i - radius - 1];
+        }
+        output(i, (sum / size).max(0.0));
+    }
+}
+
+fn color_to_f32(color: &Color) -> [f32; 4] {
+    [
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ]
+}
+
+/// Returns a premultiplied `color` with its alpha scaled by `amount`.
+fn tint(color: [f32; 4], amount: f32) -> [f32; 4] {
+    let alpha = color[3] * amount;
+    [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha]
+}
+
+/// Composites the premultiplied color `top` over `bottom`.
+fn over(top: [f32; 4], bottom: [f32; 4]) -> [f32; 4] {
+    let inv = 1.0 - top[3];
+    [
+        top[0] + bottom[0] * inv,
+        top[1] + bottom[1] * inv,
+        top[2] + bottom[2] * inv,
+        top[3] + bottom[3] * inv,
+    ]
+}
+
+fn scale(color: [f32; 4], amount: f32) -> [f32; 4] {
+    [
+        color[0] * amount,
+        color[1] * amount,
+        color[2] * amount,
+        color[3] * amount,
+    ]
+}
+
+fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+fn unpremultiply(color: [f32; 4]) -> [f32; 4] {
+    if color[3] > 0.0 {
+        [
+            clamp(color[0] / color[3]),
+            clamp(color[1] / color[3]),
+            clamp(color[2] / color[3]),
+            color[3],
+        ]
+    } else {
+        [0.0; 4]
+    }
+}
+
+fn clamp(value: f32) -> f32 {
+    value.max(0.0).min(1.0)
+}
+
+fn to_u8(value: f32) -> u8 {
+    (clamp(value) * 255.0 + 0.5) as u8
+}
@@ -0,0 +1,128 @@
+//! Video decoding and playback.
+
+use crate::backend::render::{BitmapInfo, RenderBackend};
+use generational_arena::{Arena, Index};
+use swf::{VideoCodec, VideoDeblocking};
+
+pub mod decoders;
+pub mod software;
+
+pub use software::SoftwareVideoBackend;
+
+pub type VideoStreamHandle = Index;
+
+type Error = Box<dyn std::error::Error>;
+
+/// A single frame of an encoded video stream, as found in a `VideoFrame` tag.
+#[derive(Copy, Clone, Debug)]
+pub struct EncodedFrame<'a> {
+    /// The codec used to encode the frame.
+    pub codec: VideoCodec,
+
+    /// The encoded frame data.
+    pub data: &'a [u8],
+
+    /// The index of this frame within its stream.
+    pub frame_id: u32,
+}
+
+/// What other frames a frame needs to have been decoded before it can be
+/// decoded itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameDependency {
+    /// The frame can be decoded on its own.
+    None,
+
+    /// The frame depends on the previously decoded frame of its stream.
+    Past,
+}
+
+impl FrameDependency {
+    /// Whether a stream can be seeked to this frame without decoding the
+    /// frames before it.
+    pub fn is_keyframe(self) -> bool {
+        self == FrameDependency::None
+    }
+}
+
+pub trait VideoBackend {
+    /// Registers a new video stream.
+    ///
+    /// Frames of the stream must be preloaded with `preload_video_stream_frame`
+    /// before they are decoded.
+    fn register_video_stream(
+        &mut self,
+        num_frames: u32,
+        size: (u16, u16),
+        codec: VideoCodec,
+        deblocking: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error>;
+
+    /// Inspects a frame of a video stream without decoding it, and returns
+    /// the frames that it depends on.
+    fn preload_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error>;
+
+    /// Decodes a frame of a video stream and uploads it to the renderer.
+    ///
+    /// Frames must be decoded in an order that satisfies their dependencies,
+    /// e.g. starting from a keyframe. The returned bitmap is owned by the
+    /// stream and will be overwritten by the next decoded frame.
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error>;
+}
+
+/// Video backend that doesn't decode any video.
+pub struct NullVideoBackend {
+    streams: Arena<()>,
+}
+
+impl NullVideoBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl Default for NullVideoBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VideoBackend for NullVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        _size: (u16, u16),
+        _codec: VideoCodec,
+        _deblocking: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        Ok(self.streams.insert(()))
+    }
+
+    fn preload_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        Ok(FrameDependency::None)
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+        _renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error> {
+        Err("Video decoding is not supported by the null video backend".into())
+    }
+}
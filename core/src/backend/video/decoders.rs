@@ -0,0 +1,33 @@
+//! Video decoders.
+
+mod h263;
+mod screen;
+
+pub use h263::H263Decoder;
+pub use screen::ScreenVideoDecoder;
+
+use crate::backend::video::{EncodedFrame, Error, FrameDependency};
+
+/// A decoder for a single video stream.
+///
+/// Decoders may keep state between frames, such as the reference picture that
+/// inter frames are predicted from.
+pub trait VideoDecoder {
+    /// Inspects a frame without decoding it, and returns the frames that it
+    /// depends on.
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error>;
+
+    /// Decodes a frame into RGBA pixels.
+    ///
+    /// Frames that depend on a past frame must be decoded right after it.
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+}
+
+/// A decoded video frame.
+pub struct DecodedFrame {
+    pub width: u16,
+    pub height: u16,
+
+    /// The pixels of the frame as opaque RGBA rows.
+    pub rgba: Vec<u8>,
+}
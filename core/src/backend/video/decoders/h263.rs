@@ -0,0 +1,835 @@
+//! Sorenson H.263 (a.k.a. Sorenson Spark) decoder.
+//!
+//! Sorenson H.263 is a subset of baseline H.263 (ITU-T H.263 (01/2005)) with
+//! a simplified picture header, no GOB layer and an extended escape code for
+//! transform coefficients. Annex J deblocking is not implemented, so streams
+//! that request it decode without the loop filter.
+
+use crate::backend::video::decoders::{DecodedFrame, VideoDecoder};
+use crate::backend::video::{EncodedFrame, Error, FrameDependency};
+
+/// Decoder for Sorenson H.263 streams.
+pub struct H263Decoder {
+    /// The last decoded non-disposable picture, which inter pictures are
+    /// predicted from.
+    reference: Option<Picture>,
+}
+
+impl H263Decoder {
+    pub fn new() -> Self {
+        Self { reference: None }
+    }
+}
+
+impl Default for H263Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VideoDecoder for H263Decoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let header = PictureHeader::read(&mut BitReader::new(encoded_frame.data))?;
+        Ok(match header.picture_type {
+            PictureType::Intra => FrameDependency::None,
+            PictureType::Inter | PictureType::DisposableInter => FrameDependency::Past,
+        })
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let mut reader = BitReader::new(encoded_frame.data);
+        let header = PictureHeader::read(&mut reader)?;
+        let reference = match header.picture_type {
+            PictureType::Intra => None,
+            PictureType::Inter | PictureType::DisposableInter => match &self.reference {
+                Some(reference)
+                    if reference.width == header.width && reference.height == header.height =>
+                {
+                    Some(reference)
+                }
+                _ => return Err("H.263 inter picture has no reference picture".into()),
+            },
+        };
+
+        let mut decoder = PictureDecoder::new(reader, &header, reference);
+        decoder.decode()?;
+        let picture = decoder.picture;
+
+        let frame = DecodedFrame {
+            width: header.width,
+            height: header.height,
+            rgba: picture.to_rgba(),
+        };
+
+        // Disposable pictures are never used as a reference.
+        if header.picture_type != PictureType::DisposableInter {
+            self.reference = Some(picture);
+        }
+
+        Ok(frame)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PictureType {
+    Intra,
+    Inter,
+    DisposableInter,
+}
+
+struct PictureHeader {
+    /// The Sorenson H.263 format version, which determines the escape code
+    /// used for transform coefficients.
+    version: u32,
+    width: u16,
+    height: u16,
+    picture_type: PictureType,
+    quantizer: i32,
+}
+
+impl PictureHeader {
+    fn read(reader: &mut BitReader<'_>) -> Result<Self, Error> {
+        if reader.read_bits(17)? != 1 {
+            return Err("Invalid H.263 picture start code".into());
+        }
+        let version = reader.read_bits(5)?;
+        if version > 1 {
+            return Err(format!("Unsupported Sorenson H.263 version {}", version).into());
+        }
+        let _temporal_reference = reader.read_bits(8)?;
+        let (width, height) = match reader.read_bits(3)? {
+            0 => (reader.read_bits(8)? as u16, reader.read_bits(8)? as u16),
+            1 => (reader.read_bits(16)? as u16, reader.read_bits(16)? as u16),
+            2 => (352, 288),
+            3 => (176, 144),
+            4 => (128, 96),
+            5 => (320, 240),
+            6 => (160, 120),
+            _ => return Err("Invalid H.263 picture size".into()),
+        };
+        if width == 0 || height == 0 {
+            return Err("Invalid H.263 picture size".into());
+        }
+        let picture_type = match reader.read_bits(2)? {
+            0 => PictureType::Intra,
+            1 => PictureType::Inter,
+            2 => PictureType::DisposableInter,
+            _ => return Err("Invalid H.263 picture type".into()),
+        };
+        let _deblocking = reader.read_bit()?;
+        let quantizer = reader.read_bits(5)? as i32;
+        while reader.read_bit()? {
+            let _extra_information = reader.read_bits(8)?;
+        }
+
+        Ok(Self {
+            version,
+            width,
+            height,
+            picture_type,
+            quantizer,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MacroblockType {
+    Inter,
+    InterQ,
+    Inter4V,
+    Intra,
+    IntraQ,
+}
+
+impl MacroblockType {
+    fn is_intra(self) -> bool {
+        self == MacroblockType::Intra || self == MacroblockType::IntraQ
+    }
+
+    fn has_quantizer(self) -> bool {
+        self == MacroblockType::InterQ || self == MacroblockType::IntraQ
+    }
+}
+
+/// Decodes the macroblocks of a single picture.
+struct PictureDecoder<'a> {
+    reader: BitReader<'a>,
+    version: u32,
+    reference: Option<&'a Picture>,
+    picture: Picture,
+    quantizer: i32,
+
+    /// The motion vector of each 8x8 luma block of the picture, in half pixels.
+    motion_vectors: Vec<(i32, i32)>,
+}
+
+impl<'a> PictureDecoder<'a> {
+    fn new(reader: BitReader<'a>, header: &PictureHeader, reference: Option<&'a Picture>) -> Self {
+        let picture = Picture::new(header.width, header.height);
+        let motion_vectors = vec![(0, 0); picture.mb_width * picture.mb_height * 4];
+        Self {
+            reader,
+            version: header.version,
+            reference,
+            picture,
+            quantizer: header.quantizer,
+            motion_vectors,
+        }
+    }
+
+    fn decode(&mut self) -> Result<(), Error> {
+        for mb_y in 0..self.picture.mb_height {
+            for mb_x in 0..self.picture.mb_width {
+                self.decode_macroblock(mb_x, mb_y)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_macroblock(&mut self, mb_x: usize, mb_y: usize) -> Result<(), Error> {
+        let (mb_type, cbpc) = loop {
+            if let Some(reference) = self.reference {
+                // Macroblocks that aren't coded are copied from the reference picture.
+                if self.reader.read_bit()? {
+                    for block in 0..6 {
+                        let mut prediction = [0; 64];
+                        let (plane, x, y) = block_position(mb_x, mb_y, block);
+                        reference.planes[plane].predict(x, y, (0, 0), &mut prediction);
+                        self.picture.planes[plane].write_block(x, y, &prediction, None);
+                    }
+                    self.set_motion_vectors(mb_x, mb_y, (0, 0));
+                    return Ok(());
+                }
+                let index = self.reader.read_vlc(&INTER_MCBPC)?;
+                if index == INTER_MCBPC_STUFFING {
+                    continue;
+                }
+                let mb_type = match index >> 2 {
+                    0 => MacroblockType::Inter,
+                    1 => MacroblockType::Intra,
+                    2 => MacroblockType::InterQ,
+                    3 => MacroblockType::IntraQ,
+                    _ => MacroblockType::Inter4V,
+                };
+                break (mb_type, index & 3);
+            } else {
+                let index = self.reader.read_vlc(&INTRA_MCBPC)?;
+                if index == INTRA_MCBPC_STUFFING {
+                    continue;
+                }
+                let mb_type = if index < 4 {
+                    MacroblockType::Intra
+                } else {
+                    MacroblockType::IntraQ
+                };
+                break (mb_type, index & 3);
+            }
+        };
+
+        let mut cbpy = self.reader.read_vlc(&CBPY)?;
+        if !mb_type.is_intra() {
+            cbpy ^= 0xf;
+        }
+        let coded_blocks = cbpy << 2 | cbpc;
+
+        if mb_type.has_quantizer() {
+            let dquant = [-1, -2, 1, 2][self.reader.read_bits(2)? as usize];
+            self.quantizer = (self.quantizer + dquant).max(1).min(31);
+        }
+
+        // Motion vectors; intra macroblocks are treated as having zero motion
+        // when predicting the vectors of their neighbors.
+        match mb_type {
+            MacroblockType::Inter | MacroblockType::InterQ => {
+                let predictor = self.predict_motion_vector(mb_x, mb_y, 0);
+                let motion_vector = self.read_motion_vector(predictor)?;
+                self.set_motion_vectors(mb_x, mb_y, motion_vector);
+            }
+            MacroblockType::Inter4V => {
+                for block in 0..4 {
+                    let predictor = self.predict_motion_vector(mb_x, mb_y, block);
+                    let motion_vector = self.read_motion_vector(predictor)?;
+                    let index = self.motion_vector_index(mb_x, mb_y, block);
+                    self.motion_vectors[index] = motion_vector;
+                }
+            }
+            MacroblockType::Intra | MacroblockType::IntraQ => {
+                self.set_motion_vectors(mb_x, mb_y, (0, 0));
+            }
+        }
+
+        for block in 0..6 {
+            let is_coded = coded_blocks & (32 >> block) != 0;
+            let (plane, x, y) = block_position(mb_x, mb_y, block);
+
+            let mut coefficients = [0; 64];
+            if mb_type.is_intra() {
+                coefficients[0] = match self.reader.read_bits(8)? {
+                    255 => 1024,
+                    dc => dc as i32 * 8,
+                };
+                if is_coded {
+                    self.read_coefficients(&mut coefficients, 1)?;
+                }
+                let residual = idct(&coefficients);
+                self.picture.planes[plane].write_block(x, y, &[0; 64], Some(&residual));
+            } else {
+                let reference = self
+                    .reference
+                    .ok_or("H.263 inter macroblock has no reference picture")?;
+                let motion_vector = if block < 4 {
+                    self.motion_vectors[self.motion_vector_index(mb_x, mb_y, block)]
+                } else {
+                    self.chroma_motion_vector(mb_x, mb_y, mb_type)
+                };
+                let mut prediction = [0; 64];
+                reference.planes[plane].predict(x, y, motion_vector, &mut prediction);
+                if is_coded {
+                    self.read_coefficients(&mut coefficients, 0)?;
+                    let residual = idct(&coefficients);
+                    self.picture.planes[plane].write_block(x, y, &prediction, Some(&residual));
+                } else {
+                    self.picture.planes[plane].write_block(x, y, &prediction, None);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the transform coefficients of a block, starting at the given
+    /// index in zigzag order, and dequantizes them.
+    fn read_coefficients(
+        &mut self,
+        coefficients: &mut [i32; 64],
+        start: usize,
+    ) -> Result<(), Error> {
+        let quantizer = self.quantizer;
+        let mut i = start;
+        loop {
+            let index = self.reader.read_vlc(&TCOEF)?;
+            let (last, run, level) = if index == TCOEF_ESCAPE {
+                if self.version == 0 {
+                    let last = self.reader.read_bit()?;
+                    let run = self.reader.read_bits(6)? as usize;
+                    let mut level = self.reader.read_signed_bits(8)?;
+                    if level == -128 {
+                        level = self.reader.read_bits(5)? as i32;
+                        level |= self.reader.read_signed_bits(6)? << 5;
+                    }
+                    (last, run, level)
+                } else {
+                    let is_11_bit = self.reader.read_bit()?;
+                    let last = self.reader.read_bit()?;
+                    let run = self.reader.read_bits(6)? as usize;
+                    let level = self
+                        .reader
+                        .read_signed_bits(if is_11_bit { 11 } else { 7 })?;
+                    (last, run, level)
+                }
+            } else {
+                let level = i32::from(TCOEF_LEVEL[index]);
+                let level = if self.reader.read_bit()? {
+                    -level
+                } else {
+                    level
+                };
+                (
+                    index >= TCOEF_LAST_START,
+                    usize::from(TCOEF_RUN[index]),
+                    level,
+                )
+            };
+
+            i += run;
+            if i >= 64 {
+                return Err("H.263 block has too many coefficients".into());
+            }
+            if level != 0 {
+                // Reconstruction per H.263 6.2.1.
+                let magnitude = quantizer * (2 * level.abs() + 1) - (1 - quantizer % 2);
+                let value = if level < 0 { -magnitude } else { magnitude };
+                coefficients[ZIGZAG[i]] = value.max(-2048).min(2047);
+            }
+            i += 1;
+
+            if last {
+                return Ok(());
+            }
+        }
+    }
+
+    fn read_motion_vector(&mut self, predictor: (i32, i32)) -> Result<(i32, i32), Error> {
+        let x = self.read_motion_vector_component(predictor.0)?;
+        let y = self.read_motion_vector_component(predictor.1)?;
+        Ok((x, y))
+    }
+
+    fn read_motion_vector_component(&mut self, predictor: i32) -> Result<i32, Error> {
+        let code = self.reader.read_vlc(&MVD)? as i32;
+        if code == 0 {
+            return Ok(predictor);
+        }
+        let difference = if self.reader.read_bit()? { -code } else { code };
+        // Vectors wrap around to stay within [-16, 15.5] pixels.
+        Ok(((predictor + difference + 32) & 63) - 32)
+    }
+
+    fn motion_vector_index(&self, mb_x: usize, mb_y: usize, block: usize) -> usize {
+        let x = mb_x * 2 + (block & 1);
+        let y = mb_y * 2 + (block >> 1);
+        y * self.picture.mb_width * 2 + x
+    }
+
+    /// Returns the motion vector of an 8x8 luma block, or zero if the
+    /// position is outside the picture.
+    fn motion_vector_at(&self, x: isize, y: isize) -> (i32, i32) {
+        let width = self.picture.mb_width as isize * 2;
+        let height = self.picture.mb_height as isize * 2;
+        if x < 0 || y < 0 || x >= width || y >= height {
+            (0, 0)
+        } else {
+            self.motion_vectors[(y * width + x) as usize]
+        }
+    }
+
+    fn set_motion_vectors(&mut self, mb_x: usize, mb_y: usize, motion_vector: (i32, i32)) {
+        for block in 0..4 {
+            let index = self.motion_vector_index(mb_x, mb_y, block);
+            self.motion_vectors[index] = motion_vector;
+        }
+    }
+
+    /// Predicts the motion vector of a luma block from the median of its
+    /// neighbors (H.263 6.1.1). Macroblocks with a single motion vector use
+    /// the prediction of block 0.
+    fn predict_motion_vector(&self, mb_x: usize, mb_y: usize, block: usize) -> (i32, i32) {
+        let x = (mb_x * 2 + (block & 1)) as isize;
+        let y = (mb_y * 2 + (block >> 1)) as isize;
+        let left = self.motion_vector_at(x - 1, y);
+
+        // There are no candidates above the first row of macroblocks.
+        if mb_y == 0 && block < 2 {
+            return if block == 0 && mb_x == 0 {
+                (0, 0)
+            } else {
+                left
+            };
+        }
+
+        let above = self.motion_vector_at(x, y - 1);
+        let above_right = match block {
+            0 => self.motion_vector_at(x + 2, y - 1),
+            1 | 2 => self.motion_vector_at(x + 1, y - 1),
+            _ => self.motion_vector_at(x - 1, y - 1),
+        };
+        (
+            median(left.0, above.0, above_right.0),
+            median(left.1, above.1, above_right.1),
+        )
+    }
+
+    /// Derives the motion vector of the chroma blocks from the luma blocks.
+    fn chroma_motion_vector(
+        &self,
+        mb_x: usize,
+        mb_y: usize,
+        mb_type: MacroblockType,
+    ) -> (i32, i32) {
+        if mb_type == MacroblockType::Inter4V {
+            // The sum of the four luma vectors is rounded to the nearest half pixel
+            // using H.263 Table 16.
+            const ROUNDING: [i32; 16] = [0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2];
+            let (mut x, mut y) = (0, 0);
+            for block in 0..4 {
+                let motion_vector =
+                    self.motion_vectors[self.motion_vector_index(mb_x, mb_y, block)];
+                x += motion_vector.0;
+                y += motion_vector.1;
+            }
+            let round = |sum: i32| ROUNDING[(sum & 15) as usize] + ((sum >> 3) & !1);
+            (round(x), round(y))
+        } else {
+            // Halve the luma vector, rounding quarter pixels to half pixels.
+            let motion_vector = self.motion_vectors[self.motion_vector_index(mb_x, mb_y, 0)];
+            let halve = |v: i32| (v >> 1) | (v & 1);
+            (halve(motion_vector.0), halve(motion_vector.1))
+        }
+    }
+}
+
+/// Returns the plane and the top-left pixel of a block of a macroblock.
+/// Blocks 0-3 are luma, 4 is Cb and 5 is Cr.
+fn block_position(mb_x: usize, mb_y: usize, block: usize) -> (usize, usize, usize) {
+    if block < 4 {
+        (0, mb_x * 16 + (block & 1) * 8, mb_y * 16 + (block >> 1) * 8)
+    } else {
+        (block - 3, mb_x * 8, mb_y * 8)
+    }
+}
+
+fn median(a: i32, b: i32, c: i32) -> i32 {
+    a.min(b).max(a.max(b).min(c))
+}
+
+/// A decoded YCbCr 4:2:0 picture, padded to a whole number of macroblocks.
+struct Picture {
+    width: u16,
+    height: u16,
+    mb_width: usize,
+    mb_height: usize,
+
+    /// The Y, Cb and Cr planes.
+    planes: [Plane; 3],
+}
+
+impl Picture {
+    fn new(width: u16, height: u16) -> Self {
+        let mb_width = (usize::from(width) + 15) / 16;
+        let mb_height = (usize::from(height) + 15) / 16;
+        Self {
+            width,
+            height,
+            mb_width,
+            mb_height,
+            planes: [
+                Plane::new(mb_width * 16, mb_height * 16),
+                Plane::new(mb_width * 8, mb_height * 8),
+                Plane::new(mb_width * 8, mb_height * 8),
+            ],
+        }
+    }
+
+    /// Converts the picture to RGBA using the ITU-R BT.601 color matrix.
+    fn to_rgba(&self) -> Vec<u8> {
+        let (width, height) = (usize::from(self.width), usize::from(self.height));
+        let [luma, cb, cr] = &self.planes;
+        let mut rgba = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let c = 298 * (i32::from(luma.data[y * luma.width + x]) - 16);
+                let chroma_index = (y / 2) * cb.width + x / 2;
+                let d = i32::from(cb.data[chroma_index]) - 128;
+                let e = i32::from(cr.data[chroma_index]) - 128;
+                let clamp = |v: i32| ((v + 128) >> 8).max(0).min(255) as u8;
+                rgba.push(clamp(c + 409 * e));
+                rgba.push(clamp(c - 100 * d - 208 * e));
+                rgba.push(clamp(c + 516 * d));
+                rgba.push(255);
+            }
+        }
+        rgba
+    }
+}
+
+struct Plane {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+impl Plane {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width * height],
+        }
+    }
+
+    /// Returns the sample at the given position, clamped to the edges of the plane.
+    fn sample(&self, x: i32, y: i32) -> i32 {
+        let x = x.max(0).min(self.width as i32 - 1) as usize;
+        let y = y.max(0).min(self.height as i32 - 1) as usize;
+        i32::from(self.data[y * self.width + x])
+    }
+
+    /// Predicts an 8x8 block at the given position from this plane, offset
+    /// by a motion vector in half pixels.
+    fn predict(&self, x: usize, y: usize, motion_vector: (i32, i32), out: &mut [i32; 64]) {
+        let (dx, dy) = motion_vector;
+        let (half_x, half_y) = (dx & 1 != 0, dy & 1 != 0);
+        for j in 0..8 {
+            for i in 0..8 {
+                let sx = (x + i) as i32 + (dx >> 1);
+                let sy = (y + j) as i32 + (dy >> 1);
+                let a = self.sample(sx, sy);
+                out[j * 8 + i] = match (half_x, half_y) {
+                    (false, false) => a,
+                    (true, false) => (a + self.sample(sx + 1, sy) + 1) >> 1,
+                    (false, true) => (a + self.sample(sx, sy + 1) + 1) >> 1,
+                    (true, true) => {
+                        (a + self.sample(sx + 1, sy)
+                            + self.sample(sx, sy + 1)
+                            + self.sample(sx + 1, sy + 1)
+                            + 2)
+                            >> 2
+                    }
+                };
+            }
+        }
+    }
+
+    /// Writes an 8x8 block of samples, adding an optional residual to the prediction.
+    fn write_block(
+        &mut self,
+        x: usize,
+        y: usize,
+        prediction: &[i32; 64],
+        residual: Option<&[i32; 64]>,
+    ) {
+        for j in 0..8 {
+            for i in 0..8 {
+                let mut value = prediction[j * 8 + i];
+                if let Some(residual) = residual {
+                    value += residual[j * 8 + i];
+                }
+                self.data[(y + j) * self.width + x + i] = value.max(0).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Performs an 8x8 inverse discrete cosine transform.
+fn idct(coefficients: &[i32; 64]) -> [i32; 64] {
+    // basis[u][x] = C(u) / 2 * cos((2x + 1) * u * pi / 16)
+    let mut basis = [[0.0f32; 8]; 8];
+    for (u, row) in basis.iter_mut().enumerate() {
+        let scale = if u == 0 { 0.5 / 2.0f32.sqrt() } else { 0.5 };
+        for (x, value) in row.iter_mut().enumerate() {
+            *value = scale * ((2 * x + 1) as f32 * u as f32 * std::f32::consts::PI / 16.0).cos();
+        }
+    }
+
+    let mut rows = [0.0f32; 64];
+    for y in 0..8 {
+        for x in 0..8 {
+            rows[y * 8 + x] = (0..8)
+                .map(|u| basis[u][x] * coefficients[y * 8 + u] as f32)
+                .sum();
+        }
+    }
+
+    let mut out = [0; 64];
+    for y in 0..8 {
+        for x in 0..8 {
+            let value: f32 = (0..8).map(|v| basis[v][y] * rows[v * 8 + x]).sum();
+            out[y * 8 + x] = value.round() as i32;
+        }
+    }
+    out
+}
+
+/// A big-endian bit reader.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Returns the next bits without consuming them. Bits past the end of
+    /// the data are read as zero.
+    fn peek_bits(&self, num_bits: u32) -> u32 {
+        let mut value = 0;
+        for i in 0..num_bits as usize {
+            let position = self.position + i;
+            let byte = self.data.get(position / 8).copied().unwrap_or(0);
+            value = value << 1 | u32::from(byte >> (7 - position % 8) & 1);
+        }
+        value
+    }
+
+    fn skip_bits(&mut self, num_bits: u32) -> Result<(), Error> {
+        self.position += num_bits as usize;
+        if self.position > self.data.len() * 8 {
+            return Err("H.263 picture is truncated".into());
+        }
+        Ok(())
+    }
+
+    fn read_bits(&mut self, num_bits: u32) -> Result<u32, Error> {
+        let value = self.peek_bits(num_bits);
+        self.skip_bits(num_bits)?;
+        Ok(value)
+    }
+
+    fn read_signed_bits(&mut self, num_bits: u32) -> Result<i32, Error> {
+        let value = self.read_bits(num_bits)?;
+        Ok((value << (32 - num_bits)) as i32 >> (32 - num_bits))
+    }
+
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        Ok(self.read_bits(1)? != 0)
+    }
+
+    /// Reads a variable-length code, returning its index in the table.
+    fn read_vlc(&mut self, table: &[(u16, u8)]) -> Result<usize, Error> {
+        const MAX_BITS: u32 = 13;
+        let bits = self.peek_bits(MAX_BITS);
+        for (index, &(code, len)) in table.iter().enumerate() {
+            if bits >> (MAX_BITS - u32::from(len)) == u32::from(code) {
+                self.skip_bits(len.into())?;
+                return Ok(index);
+            }
+        }
+        Err("Invalid H.263 variable-length code".into())
+    }
+}
+
+/// The order that transform coefficients are stored in.
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+/// MCBPC codes for intra pictures (H.263 Table 7), as `(code, length)`.
+/// Indices 0-3 are INTRA macroblocks and 4-7 are INTRA+Q macroblocks, with
+/// the chroma coded block pattern in the low two bits.
+const INTRA_MCBPC: [(u16, u8); 9] = [
+    (1, 1),
+    (1, 3),
+    (2, 3),
+    (3, 3),
+    (1, 4),
+    (1, 6),
+    (2, 6),
+    (3, 6),
+    (1, 9),
+];
+const INTRA_MCBPC_STUFFING: usize = 8;
+
+/// MCBPC codes for inter pictures (H.263 Table 8). Indices 0-3 are INTER,
+/// 4-7 INTRA, 8-11 INTER+Q, 12-15 INTRA+Q and 16-19 INTER4V macroblocks.
+const INTER_MCBPC: [(u16, u8); 21] = [
+    (1, 1),
+    (3, 4),
+    (2, 4),
+    (5, 6),
+    (3, 5),
+    (4, 8),
+    (3, 8),
+    (3, 7),
+    (3, 3),
+    (7, 7),
+    (6, 7),
+    (5, 9),
+    (4, 6),
+    (4, 9),
+    (3, 9),
+    (2, 9),
+    (2, 3),
+    (5, 7),
+    (4, 7),
+    (5, 8),
+    (1, 9),
+];
+const INTER_MCBPC_STUFFING: usize = 20;
+
+/// CBPY codes for intra macroblocks (H.263 Table 13), indexed by the luma
+/// coded block pattern.
+const CBPY: [(u16, u8); 16] = [
+    (3, 4),
+    (5, 5),
+    (4, 5),
+    (9, 4),
+    (3, 5),
+    (7, 4),
+    (2, 6),
+    (11, 4),
+    (2, 5),
+    (3, 6),
+    (5, 4),
+    (10, 4),
+    (4, 4),
+    (8, 4),
+    (6, 4),
+    (3, 2),
+];
+
+/// Motion vector difference codes (H.263 Table 14), indexed by the magnitude
+/// of the difference in half pixels. Non-zero differences are followed by a
+/// sign bit.
+const MVD: [(u16, u8); 33] = [
+    (1, 1),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (3, 6),
+    (5, 7),
+    (4, 7),
+    (3, 7),
+    (11, 9),
+    (10, 9),
+    (9, 9),
+    (17, 10),
+    (16, 10),
+    (15, 10),
+    (14, 10),
+    (13, 10),
+    (12, 10),
+    (11, 10),
+    (10, 10),
+    (9, 10),
+    (8, 10),
+    (7, 10),
+    (6, 10),
+    (5, 10),
+    (4, 10),
+    (7, 11),
+    (6, 11),
+    (5, 11),
+    (4, 11),
+    (3, 11),
+    (2, 11),
+    (3, 12),
+    (2, 12),
+];
+
+/// Transform coefficient codes (H.263 Table 16), followed by a sign bit.
+/// Indices from `TCOEF_LAST_START` are the last coefficient of their block,
+/// and `TCOEF_ESCAPE` is followed by a fixed-length coefficient.
+#[rustfmt::skip]
+const TCOEF: [(u16, u8); 103] = [
+    (0x2, 2), (0xf, 4), (0x15, 6), (0x17, 7), (0x1f, 8), (0x25, 9), (0x24, 9), (0x21, 10),
+    (0x20, 10), (0x7, 11), (0x6, 11), (0x20, 11), (0x6, 3), (0x14, 6), (0x1e, 8), (0xf, 10),
+    (0x21, 11), (0x50, 12), (0xe, 4), (0x1d, 8), (0xe, 10), (0x51, 12), (0xd, 5), (0x23, 9),
+    (0xd, 10), (0xc, 5), (0x22, 9), (0x52, 12), (0xb, 5), (0xc, 10), (0x53, 12), (0x13, 6),
+    (0xb, 10), (0x54, 12), (0x12, 6), (0xa, 10), (0x11, 6), (0x9, 10), (0x10, 6), (0x8, 10),
+    (0x16, 7), (0x55, 12), (0x15, 7), (0x14, 7), (0x1c, 8), (0x1b, 8), (0x21, 9), (0x20, 9),
+    (0x1f, 9), (0x1e, 9), (0x1d, 9), (0x1c, 9), (0x1b, 9), (0x1a, 9), (0x22, 11), (0x23, 11),
+    (0x56, 12), (0x57, 12), (0x7, 4), (0x19, 9), (0x5, 11), (0xf, 6), (0x4, 11), (0xe, 6),
+    (0xd, 6), (0xc, 6), (0x13, 7), (0x12, 7), (0x11, 7), (0x10, 7), (0x1a, 8), (0x19, 8),
+    (0x18, 8), (0x17, 8), (0x16, 8), (0x15, 8), (0x14, 8), (0x13, 8), (0x18, 9), (0x17, 9),
+    (0x16, 9), (0x15, 9), (0x14, 9), (0x13, 9), (0x12, 9), (0x11, 9), (0x7, 10), (0x6, 10),
+    (0x5, 10), (0x4, 10), (0x24, 11), (0x25, 11), (0x26, 11), (0x27, 11), (0x58, 12), (0x59, 12),
+    (0x5a, 12), (0x5b, 12), (0x5c, 12), (0x5d, 12), (0x5e, 12), (0x5f, 12), (0x3, 7),
+];
+const TCOEF_LAST_START: usize = 58;
+const TCOEF_ESCAPE: usize = 102;
+
+#[rustfmt::skip]
+const TCOEF_RUN: [u8; 102] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1,
+    1, 1, 2, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6,
+    6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 12, 13, 14, 15, 16,
+    17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 0, 0, 0, 1, 1, 2,
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+    19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34,
+    35, 36, 37, 38, 39, 40,
+];
+
+#[rustfmt::skip]
+const TCOEF_LEVEL: [u8; 102] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 1, 2, 3, 4,
+    5, 6, 1, 2, 3, 4, 1, 2, 3, 1, 2, 3, 1, 2, 3, 1,
+    2, 3, 1, 2, 1, 2, 1, 2, 1, 2, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 2, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+    1, 1, 1, 1, 1, 1,
+];
@@ -0,0 +1,166 @@
+//! Screen Video (a.k.a. Screen Video v1) decoder.
+//!
+//! Each frame divides the picture into a grid of blocks, each of which is
+//! either a zlib-compressed BGR bitmap, or empty if the block is unchanged
+//! since the previous frame. SWF19 p.209
+
+use crate::backend::video::decoders::{DecodedFrame, VideoDecoder};
+use crate::backend::video::{EncodedFrame, Error, FrameDependency};
+use std::io::Read;
+
+/// Decoder for Screen Video streams.
+pub struct ScreenVideoDecoder {
+    width: usize,
+    height: usize,
+
+    /// The last decoded frame as RGBA rows, which empty blocks are copied from.
+    last_frame: Option<Vec<u8>>,
+}
+
+/// The header of a Screen Video packet.
+struct ScreenVideoHeader {
+    block_width: usize,
+    image_width: usize,
+    block_height: usize,
+    image_height: usize,
+}
+
+impl ScreenVideoHeader {
+    fn read(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < 4 {
+            return Err("Screen Video frame is truncated".into());
+        }
+        let header = Self {
+            block_width: (usize::from(data[0] >> 4) + 1) * 16,
+            image_width: usize::from(data[0] & 0xf) << 8 | usize::from(data[1]),
+            block_height: (usize::from(data[2] >> 4) + 1) * 16,
+            image_height: usize::from(data[2] & 0xf) << 8 | usize::from(data[3]),
+        };
+        Ok(header)
+    }
+
+    /// The number of block columns and rows in the picture.
+    fn num_blocks(&self) -> (usize, usize) {
+        (
+            (self.image_width + self.block_width - 1) / self.block_width,
+            (self.image_height + self.block_height - 1) / self.block_height,
+        )
+    }
+}
+
+impl ScreenVideoDecoder {
+    pub fn new() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            last_frame: None,
+        }
+    }
+}
+
+impl Default for ScreenVideoDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VideoDecoder for ScreenVideoDecoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let data = encoded_frame.data;
+        let header = ScreenVideoHeader::read(data)?;
+        let (columns, rows) = header.num_blocks();
+
+        // A frame is a keyframe if every block has data.
+        let mut pos = 4;
+        for _ in 0..columns * rows {
+            let size = match data.get(pos..pos + 2) {
+                Some(size) => usize::from(size[0]) << 8 | usize::from(size[1]),
+                None => return Err("Screen Video frame is truncated".into()),
+            };
+            if size == 0 {
+                return Ok(FrameDependency::Past);
+            }
+            pos += 2 + size;
+        }
+        Ok(FrameDependency::None)
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let data = encoded_frame.data;
+        let header = ScreenVideoHeader::read(data)?;
+        let (width, height) = (header.image_width, header.image_height);
+        let (columns, rows) = header.num_blocks();
+
+        // Start from a copy of the previous frame, so that empty blocks keep its
+        // contents, and so that it is still there for later frames if this one
+        // fails to decode.
+        let mut rgba = match &self.last_frame {
+            Some(last_frame) if self.width == width && self.height == height => last_frame.clone(),
+            _ => {
+                let mut rgba = vec![0; width * height * 4];
+                for pixel in rgba.chunks_exact_mut(4) {
+                    pixel[3] = 255;
+                }
+                rgba
+            }
+        };
+
+        // Blocks are stored from the bottom left of the picture, row by row.
+        let mut pos = 4;
+        let mut block_data = Vec::with_capacity(header.block_width * header.block_height * 3);
+        for row in 0..rows {
+            for column in 0..columns {
+                let size = match data.get(pos..pos + 2) {
+                    Some(size) => usize::from(size[0]) << 8 | usize::from(size[1]),
+                    None => return Err("Screen Video frame is truncated".into()),
+                };
+                pos += 2;
+                if size == 0 {
+                    continue;
+                }
+                let compressed = data
+                    .get(pos..pos + size)
+                    .ok_or("Screen Video frame is truncated")?;
+                pos += size;
+
+                let x = column * header.block_width;
+                let y = row * header.block_height;
+                let block_width = header.block_width.min(width - x);
+                let block_height = header.block_height.min(height - y);
+
+                block_data.clear();
+                libflate::zlib::Decoder::new(compressed)?.read_to_end(&mut block_data)?;
+                if block_data.len() < block_width * block_height * 3 {
+                    return Err("Screen Video block is truncated".into());
+                }
+
+                // Block pixels are BGR, with rows stored bottom to top.
+                for (i, bgr_row) in block_data
+                    .chunks_exact(block_width * 3)
+                    .take(block_height)
+                    .enumerate()
+                {
+                    let image_y = height - 1 - (y + i);
+                    let start = (image_y * width + x) * 4;
+                    let rgba_row = &mut rgba[start..start + block_width * 4];
+                    for (bgr, pixel) in bgr_row.chunks_exact(3).zip(rgba_row.chunks_exact_mut(4)) {
+                        pixel[0] = bgr[2];
+                        pixel[1] = bgr[1];
+                        pixel[2] = bgr[0];
+                        pixel[3] = 255;
+                    }
+                }
+            }
+        }
+
+        self.width = width;
+        self.height = height;
+        self.last_frame = Some(rgba.clone());
+
+        Ok(DecodedFrame {
+            width: width as u16,
+            height: height as u16,
+            rgba,
+        })
+    }
+}
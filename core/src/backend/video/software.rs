@@ -0,0 +1,98 @@
+//! Pure software video backend.
+
+use crate::backend::render::{BitmapHandle, BitmapInfo, RenderBackend};
+use crate::backend::video::decoders::{H263Decoder, ScreenVideoDecoder, VideoDecoder};
+use crate::backend::video::{
+    EncodedFrame, Error, FrameDependency, VideoBackend, VideoStreamHandle,
+};
+use generational_arena::Arena;
+use swf::{VideoCodec, VideoDeblocking};
+
+/// A video backend that decodes frames on the CPU using the decoders in
+/// `video::decoders`, and uploads them to the renderer as bitmaps.
+pub struct SoftwareVideoBackend {
+    streams: Arena<VideoStream>,
+}
+
+impl SoftwareVideoBackend {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+}
+
+impl Default for SoftwareVideoBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VideoBackend for SoftwareVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        _size: (u16, u16),
+        codec: VideoCodec,
+        _deblocking: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        let decoder: Box<dyn VideoDecoder> = match codec {
+            VideoCodec::H263 => Box::new(H263Decoder::new()),
+            VideoCodec::ScreenVideo => Box::new(ScreenVideoDecoder::new()),
+            _ => return Err(format!("Unsupported video codec {:?}", codec).into()),
+        };
+        Ok(self.streams.insert(VideoStream {
+            bitmap: None,
+            decoder,
+        }))
+    }
+
+    fn preload_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<FrameDependency, Error> {
+        let stream = self
+            .streams
+            .get_mut(stream)
+            .ok_or("Unregistered video stream")?;
+        stream.decoder.preload_frame(encoded_frame)
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error> {
+        let stream = self
+            .streams
+            .get_mut(stream)
+            .ok_or("Unregistered video stream")?;
+        let frame = stream.decoder.decode_frame(encoded_frame)?;
+        let (width, height) = (u32::from(frame.width), u32::from(frame.height));
+        let handle = match stream.bitmap {
+            Some(bitmap) => renderer.update_texture(bitmap, width, height, frame.rgba)?,
+            None => {
+                renderer
+                    .register_bitmap_raw(width, height, frame.rgba)?
+                    .handle
+            }
+        };
+        stream.bitmap = Some(handle);
+
+        Ok(BitmapInfo {
+            handle,
+            width: frame.width,
+            height: frame.height,
+        })
+    }
+}
+
+/// A registered video stream.
+struct VideoStream {
+    /// The bitmap that decoded frames are uploaded to.
+    bitmap: Option<BitmapHandle>,
+
+    decoder: Box<dyn VideoDecoder>,
+}
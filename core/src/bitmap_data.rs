@@ -0,0 +1,948 @@
+//! Pixel storage for the `BitmapData` class.
+
+use crate::backend::render::{Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, RenderBackend};
+use crate::color_transform::ColorTransform;
+use gc_arena::Collect;
+use std::cell::Cell;
+
+/// The largest width or height of a bitmap, as in Flash Player 9.
+pub const MAX_SIZE: u32 = 2880;
+
+/// Channel flags used by `noise`, `perlinNoise` and friends
+/// (`flash.display.BitmapDataChannel`).
+pub const CHANNEL_RED: u8 = 1;
+pub const CHANNEL_GREEN: u8 = 2;
+pub const CHANNEL_BLUE: u8 = 4;
+pub const CHANNEL_ALPHA: u8 = 8;
+
+/// A pixel of a bitmap, stored as `0xAARRGGBB` with premultiplied alpha.
+///
+/// Flash Player stores bitmaps premultiplied as well, so colors with low
+/// alpha values lose precision in the same way when they are read back.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pixel(u32);
+
+impl Pixel {
+    fn from_channels(a: u8, r: u8, g: u8, b: u8) -> Self {
+        Pixel(u32::from(a) << 24 | u32::from(r) << 16 | u32::from(g) << 8 | u32::from(b))
+    }
+
+    /// Creates a pixel from a `0xAARRGGBB` color without premultiplied alpha.
+    pub fn from_argb(argb: u32) -> Self {
+        let a = (argb >> 24) as u8;
+        let premultiply = |c: u32| ((c & 0xFF) * u32::from(a) / 255) as u8;
+        Self::from_channels(
+            a,
+            premultiply(argb >> 16),
+            premultiply(argb >> 8),
+            premultiply(argb),
+        )
+    }
+
+    /// Returns this pixel as a `0xAARRGGBB` color without premultiplied alpha.
+    pub fn to_argb(self) -> u32 {
+        let a = self.alpha();
+        if a == 0 {
+            return 0;
+        }
+        let unmultiply = |c: u8| (u32::from(c) * 255 / u32::from(a)).min(255);
+        u32::from(a) << 24
+            | unmultiply(self.red()) << 16
+            | unmultiply(self.green()) << 8
+            | unmultiply(self.blue())
+    }
+
+    pub fn alpha(self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+
+    pub fn red(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    pub fn green(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    pub fn blue(self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Scales every channel of this pixel by `alpha / 255`.
+    fn multiply_alpha(self, alpha: u8) -> Self {
+        let scale = |c: u8| (u32::from(c) * u32::from(alpha) / 255) as u8;
+        Self::from_channels(
+            scale(self.alpha()),
+            scale(self.red()),
+            scale(self.green()),
+            scale(self.blue()),
+        )
+    }
+
+    /// Composites this pixel over `dest`.
+    fn blend_over(self, dest: Self) -> Self {
+        let inverse_alpha = 255 - u32::from(self.alpha());
+        let blend = |src: u8, dest: u8| {
+            (u32::from(src) + u32::from(dest) * inverse_alpha / 255).min(255) as u8
+        };
+        Self::from_channels(
+            blend(self.alpha(), dest.alpha()),
+            blend(self.red(), dest.red()),
+            blend(self.green(), dest.green()),
+            blend(self.blue(), dest.blue()),
+        )
+    }
+
+    /// Returns this pixel with its color kept and its alpha set to 255.
+    fn to_opaque(self) -> Self {
+        Self::from_argb(self.to_argb() | 0xFF00_0000)
+    }
+}
+
+/// A rectangle of pixels. It may extend past the edges of a bitmap.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PixelRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl PixelRect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// The comparison made by `BitmapData.threshold`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ThresholdOperation {
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl ThresholdOperation {
+    pub fn from_str(operation: &str) -> Option<Self> {
+        Some(match operation {
+            "<" => Self::LessThan,
+            "<=" => Self::LessThanOrEqual,
+            ">" => Self::GreaterThan,
+            ">=" => Self::GreaterThanOrEqual,
+            "==" => Self::Equal,
+            "!=" => Self::NotEqual,
+            _ => return None,
+        })
+    }
+
+    fn matches(self, value: u32, threshold: u32) -> bool {
+        match self {
+            Self::LessThan => value < threshold,
+            Self::LessThanOrEqual => value <= threshold,
+            Self::GreaterThan => value > threshold,
+            Self::GreaterThanOrEqual => value >= threshold,
+            Self::Equal => value == threshold,
+            Self::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// The options of `BitmapData.perlinNoise`.
+#[derive(Clone, Debug)]
+pub struct PerlinNoiseOptions {
+    pub base_x: f64,
+    pub base_y: f64,
+    pub num_octaves: usize,
+    pub seed: i32,
+    pub stitch: bool,
+    pub fractal_noise: bool,
+    pub channels: u8,
+    pub gray_scale: bool,
+    /// The offset of each octave, in pixels.
+    pub offsets: Vec<(f64, f64)>,
+}
+
+/// The pixels of a bitmap that can be modified at runtime.
+///
+/// The pixels are uploaded to the renderer lazily: every change marks the
+/// bitmap as dirty, and the texture is updated the next time the bitmap is
+/// drawn.
+#[derive(Debug, Collect)]
+#[collect(require_static)]
+pub struct BitmapData {
+    width: u32,
+    height: u32,
+    transparent: bool,
+    disposed: bool,
+
+    /// The pixels of the bitmap, row by row.
+    pixels: Vec<Pixel>,
+
+    /// The texture holding the pixels, once it has been registered.
+    bitmap_handle: Cell<Option<BitmapHandle>>,
+
+    /// Whether the pixels have changed since the texture was last updated.
+    dirty: Cell<bool>,
+}
+
+impl Clone for BitmapData {
+    /// Copies the pixels of this bitmap. The copy gets its own texture.
+    fn clone(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            transparent: self.transparent,
+            disposed: self.disposed,
+            pixels: self.pixels.clone(),
+            bitmap_handle: Cell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+}
+
+impl BitmapData {
+    /// Creates a bitmap filled with a `0xAARRGGBB` color.
+    ///
+    /// The fill color is made opaque if the bitmap isn't transparent.
+    pub fn new(width: u32, height: u32, transparent: bool, fill_color: u32) -> Self {
+        let fill_color = if transparent {
+            fill_color
+        } else {
+            fill_color | 0xFF00_0000
+        };
+        Self {
+            width,
+            height,
+            transparent,
+            disposed: false,
+            pixels: vec![Pixel::from_argb(fill_color); width as usize * height as usize],
+            bitmap_handle: Cell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Creates a bitmap from the decoded pixels of a bitmap character.
+    pub fn from_bitmap(bitmap: Bitmap) -> Self {
+        let (transparent, pixels) = match bitmap.data {
+            BitmapFormat::Rgb(data) => (
+                false,
+                data.chunks_exact(3)
+                    .map(|rgb| Pixel::from_channels(255, rgb[0], rgb[1], rgb[2]))
+                    .collect(),
+            ),
+            // The decoded data is already premultiplied.
+            BitmapFormat::Rgba(data) => (
+                true,
+                data.chunks_exact(4)
+                    .map(|rgba| Pixel::from_channels(rgba[3], rgba[0], rgba[1], rgba[2]))
+                    .collect(),
+            ),
+        };
+        Self {
+            width: bitmap.width,
+            height: bitmap.height,
+            transparent,
+            disposed: false,
+            pixels,
+            bitmap_handle: Cell::new(None),
+            dirty: Cell::new(true),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    pub fn disposed(&self) -> bool {
+        self.disposed
+    }
+
+    /// Frees the pixels of this bitmap. Every later operation on it does nothing.
+    pub fn dispose(&mut self) {
+        self.width = 0;
+        self.height = 0;
+        self.pixels = Vec::new();
+        self.disposed = true;
+        self.dirty.set(true);
+    }
+
+    fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    fn pixel(&self, x: i32, y: i32) -> Pixel {
+        self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
+    fn set_pixel_internal(&mut self, x: i32, y: i32, pixel: Pixel) {
+        let index = y as usize * self.width as usize + x as usize;
+        self.pixels[index] = pixel;
+    }
+
+    /// Returns the `0xRRGGBB` color of a pixel, or 0 outside of the bitmap.
+    pub fn get_pixel(&self, x: i32, y: i32) -> u32 {
+        self.get_pixel32(x, y) & 0xFF_FFFF
+    }
+
+    /// Returns the `0xAARRGGBB` color of a pixel, or 0 outside of the bitmap.
+    pub fn get_pixel32(&self, x: i32, y: i32) -> u32 {
+        if self.in_bounds(x, y) {
+            self.pixel(x, y).to_argb()
+        } else {
+            0
+        }
+    }
+
+    /// Sets the `0xRRGGBB` color of a pixel, keeping its alpha.
+    pub fn set_pixel(&mut self, x: i32, y: i32, rgb: u32) {
+        if self.in_bounds(x, y) {
+            let alpha = self.pixel(x, y).alpha();
+            let argb = u32::from(alpha) << 24 | (rgb & 0xFF_FFFF);
+            self.set_pixel_internal(x, y, Pixel::from_argb(argb));
+            self.dirty.set(true);
+        }
+    }
+
+    /// Sets the `0xAARRGGBB` color of a pixel.
+    pub fn set_pixel32(&mut self, x: i32, y: i32, argb: u32) {
+        if self.in_bounds(x, y) {
+            let pixel = self.color_to_pixel(argb);
+            self.set_pixel_internal(x, y, pixel);
+            self.dirty.set(true);
+        }
+    }
+
+    /// Converts a `0xAARRGGBB` color to a pixel of this bitmap. Opaque
+    /// bitmaps ignore the alpha of the color.
+    fn color_to_pixel(&self, argb: u32) -> Pixel {
+        if self.transparent {
+            Pixel::from_argb(argb)
+        } else {
+            Pixel::from_argb(argb | 0xFF00_0000)
+        }
+    }
+
+    /// Opaque bitmaps ignore the alpha of the pixels copied to them.
+    fn opaque_if_needed(&self, pixel: Pixel) -> Pixel {
+        if self.transparent {
+            pixel
+        } else {
+            pixel.to_opaque()
+        }
+    }
+
+    /// Clips a rectangle to the bounds of this bitmap, returning the ranges
+    /// of pixel coordinates it covers.
+    fn clip(&self, rect: PixelRect) -> (std::ops::Range<i32>, std::ops::Range<i32>) {
+        let clip_axis = |start: i32, len: i32, limit: u32| {
+            let end = (i64::from(start) + i64::from(len.max(0))).min(i64::from(limit));
+            let start = i64::from(start).max(0);
+            (start as i32)..(end.max(start) as i32)
+        };
+        (
+            clip_axis(rect.x, rect.width, self.width),
+            clip_axis(rect.y, rect.height, self.height),
+        )
+    }
+
+    /// Fills a rectangle with a `0xAARRGGBB` color.
+    pub fn fill_rect(&mut self, rect: PixelRect, argb: u32) {
+        let pixel = self.color_to_pixel(argb);
+        let (xs, ys) = self.clip(rect);
+        for y in ys {
+            for x in xs.clone() {
+                self.set_pixel_internal(x, y, pixel);
+            }
+        }
+        self.dirty.set(true);
+    }
+
+    /// Copies a rectangle of `source` to `dest_point` in this bitmap.
+    ///
+    /// If `alpha_source` is given, the copied pixels are multiplied by the
+    /// alpha channel of that bitmap, starting at the given point. If
+    /// `merge_alpha` is set, the pixels are composited over this bitmap
+    /// instead of replacing its pixels.
+    pub fn copy_pixels(
+        &mut self,
+        source: &BitmapData,
+        source_rect: PixelRect,
+        dest_point: (i32, i32),
+        alpha_source: Option<(&BitmapData, (i32, i32))>,
+        merge_alpha: bool,
+    ) {
+        let (xs, ys) = source.clip(source_rect);
+        for src_y in ys {
+            for src_x in xs.clone() {
+                let offset = (src_x - source_rect.x, src_y - source_rect.y);
+                let (dest_x, dest_y) = (dest_point.0 + offset.0, dest_point.1 + offset.1);
+                if !self.in_bounds(dest_x, dest_y) {
+                    continue;
+                }
+
+                let mut pixel = source.pixel(src_x, src_y);
+                if let Some((alpha_bitmap, alpha_point)) = alpha_source {
+                    let (alpha_x, alpha_y) = (alpha_point.0 + offset.0, alpha_point.1 + offset.1);
+                    let alpha = if alpha_bitmap.in_bounds(alpha_x, alpha_y) {
+                        alpha_bitmap.pixel(alpha_x, alpha_y).alpha()
+                    } else {
+                        0
+                    };
+                    pixel = pixel.multiply_alpha(alpha);
+                }
+                if merge_alpha {
+                    pixel = pixel.blend_over(self.pixel(dest_x, dest_y));
+                }
+                let pixel = self.opaque_if_needed(pixel);
+                self.set_pixel_internal(dest_x, dest_y, pixel);
+            }
+        }
+        self.dirty.set(true);
+    }
+
+    /// Applies a color transform to a rectangle of this bitmap.
+    pub fn color_transform(&mut self, rect: PixelRect, color_transform: &ColorTransform) {
+        let apply = |c: u32, mult: f32, add: f32| {
+            (c as f32 * mult + add * 255.0).max(0.0).min(255.0) as u32
+        };
+        let (xs, ys) = self.clip(rect);
+        for y in ys {
+            for x in xs.clone() {
+                let argb = self.pixel(x, y).to_argb();
+                let a = apply(argb >> 24, color_transform.a_mult, color_transform.a_add);
+                let r = apply(
+                    argb >> 16 & 0xFF,
+                    color_transform.r_mult,
+                    color_transform.r_add,
+                );
+                let g = apply(
+                    argb >> 8 & 0xFF,
+                    color_transform.g_mult,
+                    color_transform.g_add,
+                );
+                let b = apply(argb & 0xFF, color_transform.b_mult, color_transform.b_add);
+                let pixel = self.color_to_pixel(a << 24 | r << 16 | g << 8 | b);
+                self.set_pixel_internal(x, y, pixel);
+            }
+        }
+        self.dirty.set(true);
+    }
+
+    /// Fills the bitmap with random noise between `low` and `high` in the
+    /// given channels. Channels without noise are set to 0, or 255 for alpha.
+    pub fn noise(&mut self, seed: i32, low: u8, high: u8, channels: u8, gray_scale: bool) {
+        let (low, high) = (u32::from(low), u32::from(high.max(low)));
+        let mut rng = LehmerRng::with_seed(seed);
+        let mut random = || low + rng.gen() % (high - low + 1);
+
+        for i in 0..self.pixels.len() {
+            let (r, g, b) = if gray_scale {
+                let v = random();
+                (v, v, v)
+            } else {
+                let mut channel = |flag| if channels & flag != 0 { random() } else { 0 };
+                (
+                    channel(CHANNEL_RED),
+                    channel(CHANNEL_GREEN),
+                    channel(CHANNEL_BLUE),
+                )
+            };
+            let a = if channels & CHANNEL_ALPHA != 0 {
+                random()
+            } else {
+                255
+            };
+            self.pixels[i] = self.color_to_pixel(a << 24 | r << 16 | g << 8 | b);
+        }
+        self.dirty.set(true);
+    }
+
+    /// Fills the bitmap with Perlin noise in the given channels.
+    ///
+    /// This uses the turbulence function of the SVG `feTurbulence` filter,
+    /// which is equivalent to the noise generated by Flash Player.
+    pub fn perlin_noise(&mut self, options: &PerlinNoiseOptions) {
+        let turbulence = Turbulence::new(options.seed);
+        let frequency = |base: f64| if base != 0.0 { 1.0 / base } else { 0.0 };
+        let base_frequency = (frequency(options.base_x), frequency(options.base_y));
+        let tile = if options.stitch {
+            Some((self.width as f64, self.height as f64))
+        } else {
+            None
+        };
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let point = (x as f64, y as f64);
+                let noise = |channel| {
+                    let sum = turbulence.turbulence(channel, point, base_frequency, tile, options);
+                    let value = if options.fractal_noise {
+                        (sum * 255.0 + 255.0) / 2.0
+                    } else {
+                        sum * 255.0
+                    };
+                    value.max(0.0).min(255.0) as u32
+                };
+
+                let (r, g, b) = if options.gray_scale {
+                    let v = noise(0);
+                    (v, v, v)
+                } else {
+                    let channel = |flag, index| {
+                        if options.channels & flag != 0 {
+                            noise(index)
+                        } else {
+                            0
+                        }
+                    };
+                    (
+                        channel(CHANNEL_RED, 0),
+                        channel(CHANNEL_GREEN, 1),
+                        channel(CHANNEL_BLUE, 2),
+                    )
+                };
+                let a = if options.channels & CHANNEL_ALPHA != 0 {
+                    noise(3)
+                } else {
+                    255
+                };
+                let pixel = self.color_to_pixel(a << 24 | r << 16 | g << 8 | b);
+                self.set_pixel_internal(x as i32, y as i32, pixel);
+            }
+        }
+        self.dirty.set(true);
+    }
+
+    /// Tests the pixels of a rectangle of `source` against a threshold,
+    /// setting the matching pixels of this bitmap to `color`.
+    ///
+    /// Both the pixels and the threshold are masked by `mask` before the
+    /// comparison. Pixels that don't match are copied from the source if
+    /// `copy_source` is set. Returns the number of matching pixels.
+    #[allow(clippy::too_many_arguments)]
+    pub fn threshold(
+        &mut self,
+        source: &BitmapData,
+        source_rect: PixelRect,
+        dest_point: (i32, i32),
+        operation: ThresholdOperation,
+        threshold: u32,
+        color: u32,
+        mask: u32,
+        copy_source: bool,
+    ) -> u32 {
+        let color = self.color_to_pixel(color);
+        let mut count = 0;
+        let (xs, ys) = source.clip(source_rect);
+        for src_y in ys {
+            for src_x in xs.clone() {
+                let dest_x = dest_point.0 + src_x - source_rect.x;
+                let dest_y = dest_point.1 + src_y - source_rect.y;
+                if !self.in_bounds(dest_x, dest_y) {
+                    continue;
+                }
+
+                let pixel = source.pixel(src_x, src_y);
+                if operation.matches(pixel.to_argb() & mask, threshold & mask) {
+                    self.set_pixel_internal(dest_x, dest_y, color);
+                    count += 1;
+                } else if copy_source {
+                    let pixel = self.opaque_if_needed(pixel);
+                    self.set_pixel_internal(dest_x, dest_y, pixel);
+                }
+            }
+        }
+        self.dirty.set(true);
+        count
+    }
+
+    /// Fills the area of same-colored pixels around a point with a
+    /// `0xAARRGGBB` color.
+    pub fn flood_fill(&mut self, x: i32, y: i32, argb: u32) {
+        if !self.in_bounds(x, y) {
+            return;
+        }
+        let target = self.pixel(x, y);
+        let color = self.color_to_pixel(argb);
+        if target == color {
+            return;
+        }
+
+        let mut pending = vec![(x, y)];
+        while let Some((x, y)) = pending.pop() {
+            if !self.in_bounds(x, y) || self.pixel(x, y) != target {
+                continue;
+            }
+            self.set_pixel_internal(x, y, color);
+            pending.extend_from_slice(&[(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]);
+        }
+        self.dirty.set(true);
+    }
+
+    /// Composites rendered pixels over this bitmap, within `clip_rect`.
+    ///
+    /// `rgba` holds premultiplied RGBA rows of the same size as this bitmap,
+    /// as returned by `RenderBackend::render_offscreen`.
+    pub fn draw_rgba(&mut self, rgba: &[u8], clip_rect: PixelRect) {
+        let (xs, ys) = self.clip(clip_rect);
+        for y in ys {
+            for x in xs.clone() {
+                let i = (y as usize * self.width as usize + x as usize) * 4;
+                if let Some(color) = rgba.get(i..i + 4) {
+                    let src = Pixel::from_channels(color[3], color[0], color[1], color[2]);
+                    let pixel = self.opaque_if_needed(src.blend_over(self.pixel(x, y)));
+                    self.set_pixel_internal(x, y, pixel);
+                }
+            }
+        }
+        self.dirty.set(true);
+    }
+
+    /// Returns the texture holding the pixels of this bitmap, registering or
+    /// updating it if needed. Returns `None` if the bitmap has no pixels.
+    pub fn bitmap_handle(&self, renderer: &mut dyn RenderBackend) -> Option<BitmapInfo> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        let handle = match self.bitmap_handle.get() {
+            Some(handle) if !self.dirty.get() => handle,
+            Some(handle) => {
+                match renderer.update_texture(handle, self.width, self.height, self.to_rgba()) {
+                    Ok(handle) => handle,
+                    Err(e) => {
+                        log::error!("Unable to update bitmap texture: {}", e);
+                        return None;
+                    }
+                }
+            }
+            None => match renderer.register_bitmap_raw(self.width, self.height, self.to_rgba()) {
+                Ok(info) => info.handle,
+                Err(e) => {
+                    log::error!("Unable to register bitmap texture: {}", e);
+                    return None;
+                }
+            },
+        };
+        self.bitmap_handle.set(Some(handle));
+        self.dirty.set(false);
+
+        Some(BitmapInfo {
+            handle,
+            width: self.width as u16,
+            height: self.height as u16,
+        })
+    }
+
+    /// The pixels as RGBA rows with premultiplied alpha.
+    fn to_rgba(&self) -> Vec<u8> {
+        let mut rgba = Vec::with_capacity(self.pixels.len() * 4);
+        for pixel in &self.pixels {
+            rgba.extend_from_slice(&[pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]);
+        }
+        rgba
+    }
+}
+
+/// The "minimal standard" random number generator, as used by Flash Player
+/// for `BitmapData.noise`.
+struct LehmerRng(u32);
+
+impl LehmerRng {
+    const MODULUS: u32 = 2_147_483_647;
+
+    fn with_seed(seed: i32) -> Self {
+        // The generator would be stuck at zero for multiples of the modulus.
+        let seed = i64::from(seed).rem_euclid(i64::from(Self::MODULUS)) as u32;
+        LehmerRng(seed.max(1))
+    }
+
+    fn gen(&mut self) -> u32 {
+        self.0 = (u64::from(self.0) * 16807 % u64::from(Self::MODULUS)) as u32;
+        self.0
+    }
+}
+
+/// Perlin noise generator from the reference implementation of the SVG
+/// `feTurbulence` filter.
+struct Turbulence {
+    lattice: Vec<usize>,
+    /// The gradient vectors of the red, green, blue and alpha channels.
+    gradients: [Vec<[f64; 2]>; 4],
+}
+
+impl Turbulence {
+    const BSIZE: usize = 0x100;
+    const BM: usize = 0xFF;
+    const PERLIN_N: f64 = 4096.0;
+
+    fn new(seed: i32) -> Self {
+        const RAND_M: i64 = 2_147_483_647;
+        const RAND_A: i64 = 16807;
+        const RAND_Q: i64 = 127_773;
+        const RAND_R: i64 = 2836;
+        let mut seed = i64::from(seed);
+        if seed <= 0 {
+            seed = -(seed % (RAND_M - 1)) + 1;
+        }
+        if seed > RAND_M - 1 {
+            seed = RAND_M - 1;
+        }
+        let mut random = || {
+            seed = RAND_A * (seed % RAND_Q) - RAND_R * (seed / RAND_Q);
+            if seed <= 0 {
+                seed += RAND_M;
+            }
+            seed
+        };
+
+        let size = Self::BSIZE + Self::BSIZE + 2;
+        let mut lattice = vec![0; size];
+        let mut gradients = [
+            vec![[0.0; 2]; size],
+            vec![[0.0; 2]; size],
+            vec![[0.0; 2]; size],
+            vec![[0.0; 2]; size],
+        ];
+        for channel in gradients.iter_mut() {
+            for (i, gradient) in channel.iter_mut().enumerate().take(Self::BSIZE) {
+                lattice[i] = i;
+                for component in gradient.iter_mut() {
+                    let r = random() % (Self::BSIZE as i64 * 2) - Self::BSIZE as i64;
+                    *component = r as f64 / Self::BSIZE as f64;
+                }
+                let length = (gradient[0] * gradient[0] + gradient[1] * gradient[1]).sqrt();
+                if length != 0.0 {
+                    gradient[0] /= length;
+                    gradient[1] /= length;
+                }
+            }
+        }
+        for i in (1..Self::BSIZE).rev() {
+            let j = (random() % Self::BSIZE as i64) as usize;
+            lattice.swap(i, j);
+        }
+        for i in 0..Self::BSIZE + 2 {
+            lattice[Self::BSIZE + i] = lattice[i];
+            for channel in gradients.iter_mut() {
+                channel[Self::BSIZE + i] = channel[i];
+            }
+        }
+
+        Self { lattice, gradients }
+    }
+
+    fn noise2(&self, channel: usize, vec: (f64, f64), stitch: Option<&Stitch>) -> f64 {
+        let tx = vec.0 + Self::PERLIN_N;
+        let mut bx0 = tx as usize;
+        let mut bx1 = bx0 + 1;
+        let rx0 = tx - tx.trunc();
+        let rx1 = rx0 - 1.0;
+        let ty = vec.1 + Self::PERLIN_N;
+        let mut by0 = ty as usize;
+        let mut by1 = by0 + 1;
+        let ry0 = ty - ty.trunc();
+        let ry1 = ry0 - 1.0;
+
+        // If stitching, adjust the lattice points accordingly.
+        if let Some(stitch) = stitch {
+            let wrap = |b: &mut usize, wrap: usize, size: usize| {
+                if *b >= wrap {
+                    *b = b.wrapping_sub(size);
+                }
+            };
+            wrap(&mut bx0, stitch.wrap_x, stitch.width);
+            wrap(&mut bx1, stitch.wrap_x, stitch.width);
+            wrap(&mut by0, stitch.wrap_y, stitch.height);
+            wrap(&mut by1, stitch.wrap_y, stitch.height);
+        }
+        let (bx0, bx1) = (bx0 & Self::BM, bx1 & Self::BM);
+        let (by0, by1) = (by0 & Self::BM, by1 & Self::BM);
+
+        let lattice_x0 = self.lattice[bx0];
+        let lattice_x1 = self.lattice[bx1];
+        let gradients = &self.gradients[channel];
+        let q00 = gradients[self.lattice[lattice_x0 + by0]];
+        let q10 = gradients[self.lattice[lattice_x1 + by0]];
+        let q01 = gradients[self.lattice[lattice_x0 + by1]];
+        let q11 = gradients[self.lattice[lattice_x1 + by1]];
+
+        let s_curve = |t: f64| t * t * (3.0 - 2.0 * t);
+        let lerp = |t: f64, from: f64, to: f64| from + t * (to - from);
+        let sx = s_curve(rx0);
+        let sy = s_curve(ry0);
+        let top = lerp(sx, rx0 * q00[0] + ry0 * q00[1], rx1 * q10[0] + ry0 * q10[1]);
+        let bottom = lerp(sx, rx0 * q01[0] + ry1 * q01[1], rx1 * q11[0] + ry1 * q11[1]);
+        lerp(sy, top, bottom)
+    }
+
+    /// Sums the noise of each octave at a point.
+    ///
+    /// `tile` is the size of the area that tiles seamlessly when stitching.
+    fn turbulence(
+        &self,
+        channel: usize,
+        point: (f64, f64),
+        mut base_frequency: (f64, f64),
+        tile: Option<(f64, f64)>,
+        options: &PerlinNoiseOptions,
+    ) -> f64 {
+        // Adjust the base frequencies if necessary for stitching.
+        let mut stitch = tile.map(|(tile_width, tile_height)| {
+            base_frequency.0 = stitch_frequency(base_frequency.0, tile_width);
+            base_frequency.1 = stitch_frequency(base_frequency.1, tile_height);
+            let width = (tile_width * base_frequency.0 + 0.5) as usize;
+            let height = (tile_height * base_frequency.1 + 0.5) as usize;
+            Stitch {
+                width,
+                height,
+                wrap_x: (Self::PERLIN_N as usize) + width,
+                wrap_y: (Self::PERLIN_N as usize) + height,
+            }
+        });
+
+        let mut sum = 0.0;
+        let mut ratio = 1.0;
+        for octave in 0..options.num_octaves {
+            let offset = options.offsets.get(octave).copied().unwrap_or((0.0, 0.0));
+            let vec = (
+                (point.0 + offset.0) * base_frequency.0 * ratio,
+                (point.1 + offset.1) * base_frequency.1 * ratio,
+            );
+            let noise = self.noise2(channel, vec, stitch.as_ref());
+            sum += if options.fractal_noise {
+                noise
+            } else {
+                noise.abs()
+            } / ratio;
+            ratio *= 2.0;
+            if let Some(stitch) = &mut stitch {
+                let perlin_n = Self::PERLIN_N as usize;
+                stitch.width *= 2;
+                stitch.wrap_x = 2 * stitch.wrap_x - perlin_n;
+                stitch.height *= 2;
+                stitch.wrap_y = 2 * stitch.wrap_y - perlin_n;
+            }
+        }
+        sum
+    }
+}
+
+/// Rounds a frequency so that a whole number of noise periods fits in `size`.
+fn stitch_frequency(frequency: f64, size: f64) -> f64 {
+    if frequency == 0.0 {
+        return frequency;
+    }
+    let lo = (size * frequency).floor() / size;
+    let hi = (size * frequency).ceil() / size;
+    if lo > 0.0 && frequency / lo < hi / frequency {
+        lo
+    } else {
+        hi
+    }
+}
+
+/// The lattice size and wrapping points used to stitch Perlin noise.
+struct Stitch {
+    width: usize,
+    height: usize,
+    wrap_x: usize,
+    wrap_y: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn premultiplied_pixels() {
+        assert_eq!(Pixel::from_argb(0x80FF_0000).to_argb(), 0x80FF_0000);
+        assert_eq!(Pixel::from_argb(0xFF12_3456).to_argb(), 0xFF12_3456);
+        assert_eq!(Pixel::from_argb(0x0012_3456).to_argb(), 0);
+        // Low alpha values lose precision, as in Flash Player.
+        assert_eq!(Pixel::from_argb(0x0133_6699).to_argb(), 0x0100_0000);
+    }
+
+    #[test]
+    fn opaque_bitmaps_ignore_alpha() {
+        let mut bitmap = BitmapData::new(2, 2, false, 0x0000_FF00);
+        assert_eq!(bitmap.get_pixel32(0, 0), 0xFF00_FF00);
+        bitmap.set_pixel32(1, 1, 0x8012_3456);
+        assert_eq!(bitmap.get_pixel32(1, 1), 0xFF12_3456);
+        assert_eq!(bitmap.get_pixel32(2, 0), 0);
+    }
+
+    #[test]
+    fn fill_rect_is_clipped() {
+        let mut bitmap = BitmapData::new(4, 4, true, 0);
+        bitmap.fill_rect(PixelRect::new(2, -1, 100, 2), 0xFFFF_0000);
+        assert_eq!(bitmap.get_pixel32(1, 0), 0);
+        assert_eq!(bitmap.get_pixel32(2, 0), 0xFFFF_0000);
+        assert_eq!(bitmap.get_pixel32(3, 0), 0xFFFF_0000);
+        assert_eq!(bitmap.get_pixel32(3, 1), 0);
+    }
+
+    #[test]
+    fn copy_pixels_with_merge_alpha() {
+        let source = BitmapData::new(2, 2, true, 0x80FF_0000);
+        let mut dest = BitmapData::new(3, 3, true, 0xFF00_00FF);
+        dest.copy_pixels(&source, PixelRect::new(0, 0, 2, 2), (2, 2), None, false);
+        assert_eq!(dest.get_pixel32(2, 2), 0x80FF_0000);
+        assert_eq!(dest.get_pixel32(1, 1), 0xFF00_00FF);
+        dest.copy_pixels(&source, PixelRect::new(0, 0, 2, 2), (0, 0), None, true);
+        assert_eq!(dest.get_pixel32(0, 0), 0xFF80_007F);
+    }
+
+    #[test]
+    fn flood_fill_and_threshold() {
+        let mut bitmap = BitmapData::new(5, 5, false, 0xFFFF_FFFF);
+        bitmap.fill_rect(PixelRect::new(2, 0, 1, 5), 0xFF00_0000);
+        bitmap.flood_fill(0, 0, 0xFFFF_0000);
+        assert_eq!(bitmap.get_pixel(1, 4), 0xFF_0000);
+        assert_eq!(bitmap.get_pixel(3, 0), 0xFF_FFFF);
+
+        let source = bitmap.clone();
+        let count = bitmap.threshold(
+            &source,
+            PixelRect::new(0, 0, 5, 5),
+            (0, 0),
+            ThresholdOperation::Equal,
+            0x00FF_0000,
+            0xFF00_FF00,
+            0x00FF_FFFF,
+            false,
+        );
+        assert_eq!(count, 10);
+        assert_eq!(bitmap.get_pixel(0, 0), 0x00_FF00);
+    }
+
+    #[test]
+    fn noise_is_deterministic() {
+        let mut a = BitmapData::new(8, 8, true, 0);
+        let mut b = BitmapData::new(8, 8, true, 0);
+        a.noise(42, 10, 20, CHANNEL_RED | CHANNEL_BLUE, false);
+        b.noise(42, 10, 20, CHANNEL_RED | CHANNEL_BLUE, false);
+        assert_eq!(a.pixels, b.pixels);
+        for pixel in &a.pixels {
+            let argb = pixel.to_argb();
+            assert_eq!(argb & 0xFF00_FF00, 0xFF00_0000);
+            assert!((10..=20).contains(&(argb >> 16 & 0xFF)));
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Player configuration options
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Controls how the movie is scaled to fit the viewport.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StageScaleMode {
+    /// The movie is stretched to fill the viewport, ignoring its aspect ratio.
+    ExactFit,
+
+    /// The movie is scaled to cover the entire viewport, preserving its
+    /// aspect ratio. Parts of the movie may be cropped.
+    NoBorder,
+
+    /// The movie is not scaled, and is centered in the viewport.
+    NoScale,
+
+    /// The movie is scaled to fit entirely inside the viewport, preserving
+    /// its aspect ratio. Any remaining area is letterboxed.
+    ShowAll,
+}
+
+impl Default for StageScaleMode {
+    fn default() -> Self {
+        StageScaleMode::ShowAll
+    }
+}
+
+impl fmt::Display for StageScaleMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StageScaleMode::ExactFit => "exactFit",
+            StageScaleMode::NoBorder => "noBorder",
+            StageScaleMode::NoScale => "noScale",
+            StageScaleMode::ShowAll => "showAll",
+        })
+    }
+}
+
+impl FromStr for StageScaleMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "exactfit" => Ok(StageScaleMode::ExactFit),
+            "noborder" => Ok(StageScaleMode::NoBorder),
+            "noscale" => Ok(StageScaleMode::NoScale),
+            "showall" => Ok(StageScaleMode::ShowAll),
+            _ => Err(format!(
+                "Unknown scale mode {} (expected exactFit, noBorder, noScale or showAll)",
+                s
+            )),
+        }
+    }
+}
+
+/// The rendering quality of the stage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StageQuality {
+    Low,
+    Medium,
+    High,
+    Best,
+}
+
+impl Default for StageQuality {
+    fn default() -> Self {
+        StageQuality::High
+    }
+}
+
+impl fmt::Display for StageQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StageQuality::Low => "LOW",
+            StageQuality::Medium => "MEDIUM",
+            StageQuality::High => "HIGH",
+            StageQuality::Best => "BEST",
+        })
+    }
+}
+
+impl FromStr for StageQuality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(StageQuality::Low),
+            "medium" => Ok(StageQuality::Medium),
+            "high" => Ok(StageQuality::High),
+            "best" => Ok(StageQuality::Best),
+            _ => Err(format!(
+                "Unknown quality {} (expected low, medium, high or best)",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scale_mode() {
+        assert_eq!("showAll".parse(), Ok(StageScaleMode::ShowAll));
+        assert_eq!("NOSCALE".parse(), Ok(StageScaleMode::NoScale));
+        assert!("stretch".parse::<StageScaleMode>().is_err());
+        assert_eq!(StageScaleMode::ExactFit.to_string(), "exactFit");
+    }
+
+    #[test]
+    fn parse_quality() {
+        assert_eq!("low".parse(), Ok(StageQuality::Low));
+        assert_eq!("Best".parse(), Ok(StageQuality::Best));
+        assert!("ultra".parse::<StageQuality>().is_err());
+        assert_eq!(StageQuality::Medium.to_string(), "MEDIUM");
+    }
+}
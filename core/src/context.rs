@@ -5,9 +5,15 @@ use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::listeners::SystemListener;
 use crate::avm1::{Object, Timers, Value};
 use crate::backend::input::InputBackend;
+use crate::backend::locale::LocaleBackend;
 use crate::backend::storage::StorageBackend;
-use crate::backend::{audio::AudioBackend, navigator::NavigatorBackend, render::RenderBackend};
+use crate::backend::{
+    audio::AudioBackend, navigator::NavigatorBackend, render::RenderBackend, video::VideoBackend,
+};
 use crate::display_object::EditText;
+use crate::external::ExternalInterface;
+use crate::focus_tracker::FocusTracker;
+use crate::font::FontSource;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::player::Player;
@@ -19,6 +25,7 @@ use gc_arena::{Collect, MutationContext};
 use rand::rngs::SmallRng;
 use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 
 /// `UpdateContext` holds shared data that is used by the various subsystems of Ruffle.
 /// `Player` crates this when it begins a tick and passes it through the call stack to
@@ -39,6 +46,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// Used to instantiate a `DisplayObject` of a given ID.
     pub library: &'a mut Library<'gc>,
 
+    /// The fonts that device text is rendered with.
+    pub font_source: &'a FontSource,
+
     /// The version of the Flash Player we are emulating.
     /// TODO: This is a little confusing because this represents the player's max SWF version,
     /// which is an integer (e.g. 13), but "Flash Player version" is a triplet (11.6.0), and these
@@ -55,6 +65,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The audio backend, used by display objects and AVM to play audio.
     pub audio: &'a mut (dyn AudioBackend + 'a),
 
+    /// The video backend, used by video display objects to decode frames.
+    pub video: &'a mut (dyn VideoBackend + 'a),
+
     /// The navigator backend, used by the AVM to make HTTP requests and visit webpages.
     pub navigator: &'a mut (dyn NavigatorBackend + 'a),
 
@@ -67,6 +80,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The locale backend, used to get the current time and time zone.
+    pub locale: &'a dyn LocaleBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -80,6 +96,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The display object that the mouse is currently hovering over.
     pub mouse_hovered_object: Option<DisplayObject<'gc>>,
 
+    /// The display object that has keyboard focus.
+    pub focus_tracker: FocusTracker<'gc>,
+
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
@@ -115,6 +134,35 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// Timed callbacks created with `setInterval`/`setTimeout`.
     pub timers: &'a mut Timers<'gc>,
+
+    /// The connection to the host of the movie.
+    pub external_interface: &'a mut ExternalInterface<'gc>,
+
+    /// The time since launch at which this update began, as reported by the
+    /// navigator backend.
+    pub update_start: Duration,
+
+    /// The maximum amount of time scripts may run for in this update.
+    pub max_execution_duration: Duration,
+}
+
+/// How many actions or opcodes a script runs between calls to
+/// `is_execution_timed_out`, which has to read the clock.
+pub const ACTIONS_PER_TIMEOUT_CHECK: u16 = 1000;
+
+impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
+    /// Returns `true` if scripts have run for longer than allowed in this
+    /// update, and must be aborted.
+    ///
+    /// Reading the clock is slow, so interpreters only call this once every
+    /// `ACTIONS_PER_TIMEOUT_CHECK` actions.
+    pub fn is_execution_timed_out(&mut self) -> bool {
+        self.navigator
+            .time_since_launch()
+            .checked_sub(self.update_start)
+            .map(|elapsed| elapsed > self.max_execution_duration)
+            .unwrap_or(false)
+    }
 }
 
 /// A queued ActionScript call.
@@ -253,6 +301,10 @@ pub enum ActionType<'gc> {
         is_lazy_initialize: bool,
         abc: SwfSlice,
     },
+
+    /// Construct the AS3 object of a display object whose symbol is linked to
+    /// an AS3 class.
+    Avm2Construct,
 }
 
 impl fmt::Debug for ActionType<'_> {
@@ -296,6 +348,7 @@ impl fmt::Debug for ActionType<'_> {
                 .field("is_lazy_initialize", is_lazy_initialize)
                 .field("bytecode", abc)
                 .finish(),
+            ActionType::Avm2Construct => f.debug_struct("ActionType::Avm2Construct").finish(),
         }
     }
 }
@@ -1,4 +1,6 @@
 use crate::avm1::{Avm1, Object, TObject, Value};
+use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
+use crate::backend::input::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
@@ -11,6 +13,7 @@ use std::cell::{Ref, RefMut};
 use std::cmp::min;
 use std::fmt::Debug;
 use std::sync::Arc;
+use swf::BlendMode;
 
 mod bitmap;
 mod button;
@@ -19,16 +22,18 @@ mod graphic;
 mod morph_shape;
 mod movie_clip;
 mod text;
+mod video;
 
 use crate::avm1::activation::Activation;
 use crate::events::{ClipEvent, ClipEventResult};
-pub use bitmap::Bitmap;
+pub use bitmap::{Bitmap, BitmapTag};
 pub use button::Button;
-pub use edit_text::{AutoSizeMode, EditText};
+pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::MovieClip;
 pub use text::Text;
+pub use video::Video;
 
 #[derive(Clone, Debug)]
 pub struct DisplayObjectBase<'gc> {
@@ -59,6 +64,20 @@ pub struct DisplayObjectBase<'gc> {
 
     /// Bit flags for various display object properites.
     flags: EnumSet<DisplayObjectFlags>,
+
+    /// The AVM2 object representing this display object, if one has been
+    /// created.
+    avm2_object: Option<Avm2Object<'gc>>,
+
+    /// The filters applied to this display object when it is rendered.
+    filters: Vec<swf::Filter>,
+
+    /// The blend mode used when compositing this display object.
+    blend_mode: BlendMode,
+
+    /// Whether a focus rectangle is drawn around this display object when it
+    /// has keyboard focus, or `None` to use the setting of the root.
+    focus_rect: Option<bool>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -78,6 +97,10 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             prev_sibling: None,
             next_sibling: None,
             flags: DisplayObjectFlags::Visible.into(),
+            avm2_object: None,
+            filters: Vec::new(),
+            blend_mode: BlendMode::Normal,
+            focus_rect: None,
         }
     }
 }
@@ -89,6 +112,7 @@ unsafe impl<'gc> Collect for DisplayObjectBase<'gc> {
         self.first_child.trace(cc);
         self.prev_sibling.trace(cc);
         self.next_sibling.trace(cc);
+        self.avm2_object.trace(cc);
     }
 }
 
@@ -256,6 +280,12 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.d = (cos * value) as f32;
     }
 
+    fn avm2_object(&self) -> Option<Avm2Object<'gc>> {
+        self.avm2_object
+    }
+    fn set_avm2_object(&mut self, object: Avm2Object<'gc>) {
+        self.avm2_object = Some(object);
+    }
     fn name(&self) -> &str {
         &self.name
     }
@@ -275,6 +305,24 @@ impl<'gc> DisplayObjectBase<'gc> {
     fn set_clip_depth(&mut self, _context: MutationContext<'gc, '_>, depth: Depth) {
         self.clip_depth = depth;
     }
+    fn filters(&self) -> &[swf::Filter] {
+        &self.filters
+    }
+    fn set_filters(&mut self, _context: MutationContext<'gc, '_>, filters: Vec<swf::Filter>) {
+        self.filters = filters;
+    }
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+    fn set_blend_mode(&mut self, _context: MutationContext<'gc, '_>, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+    fn focus_rect(&self) -> Option<bool> {
+        self.focus_rect
+    }
+    fn set_focus_rect(&mut self, _context: MutationContext<'gc, '_>, focus_rect: Option<bool>) {
+        self.focus_rect = focus_rect;
+    }
     fn parent(&self) -> Option<DisplayObject<'gc>> {
         self.parent
     }
@@ -372,6 +420,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         MorphShape(MorphShape<'gc>),
         MovieClip(MovieClip<'gc>),
         Text(Text<'gc>),
+        Video(Video<'gc>),
     }
 )]
 pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>> {
@@ -631,6 +680,21 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
 
     fn clip_depth(&self) -> Depth;
     fn set_clip_depth(&mut self, context: MutationContext<'gc, '_>, depth: Depth);
+
+    /// The filters applied to this display object when it is rendered.
+    fn filters(&self) -> Ref<[swf::Filter]>;
+    fn set_filters(&mut self, context: MutationContext<'gc, '_>, filters: Vec<swf::Filter>);
+
+    /// The blend mode used when compositing this display object onto the
+    /// content below it.
+    fn blend_mode(&self) -> BlendMode;
+    fn set_blend_mode(&mut self, context: MutationContext<'gc, '_>, blend_mode: BlendMode);
+
+    /// Whether a focus rectangle is drawn around this object when it has
+    /// keyboard focus (`_focusrect` in AVM1), or `None` to use the setting
+    /// of the root.
+    fn focus_rect(&self) -> Option<bool>;
+    fn set_focus_rect(&mut self, context: MutationContext<'gc, '_>, focus_rect: Option<bool>);
     fn parent(&self) -> Option<DisplayObject<'gc>>;
     fn set_parent(&mut self, context: MutationContext<'gc, '_>, parent: Option<DisplayObject<'gc>>);
     fn first_child(&self) -> Option<DisplayObject<'gc>>;
@@ -761,28 +825,39 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
     fn as_morph_shape(&self) -> Option<MorphShape<'gc>> {
         None
     }
+    fn as_video(&self) -> Option<Video<'gc>> {
+        None
+    }
     fn apply_place_object(
         &mut self,
-        gc_context: MutationContext<'gc, '_>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
         place_object: &swf::PlaceObject,
     ) {
         // PlaceObject tags only apply if this onject has not been dynamically moved by AS code.
         if !self.transformed_by_script() {
             if let Some(matrix) = &place_object.matrix {
-                self.set_matrix(gc_context, &matrix);
+                self.set_matrix(context.gc_context, &matrix);
             }
             if let Some(color_transform) = &place_object.color_transform {
-                self.set_color_transform(gc_context, &color_transform.clone().into());
+                self.set_color_transform(context.gc_context, &color_transform.clone().into());
             }
             if let Some(name) = &place_object.name {
-                self.set_name(gc_context, name);
+                self.set_name(context.gc_context, name);
             }
             if let Some(clip_depth) = place_object.clip_depth {
-                self.set_clip_depth(gc_context, clip_depth.into());
+                self.set_clip_depth(context.gc_context, clip_depth.into());
+            }
+            if !place_object.filters.is_empty() {
+                self.set_filters(context.gc_context, place_object.filters.clone());
+            }
+            if place_object.blend_mode != BlendMode::Normal {
+                self.set_blend_mode(context.gc_context, place_object.blend_mode);
             }
             if let Some(ratio) = place_object.ratio {
                 if let Some(mut morph_shape) = self.as_morph_shape() {
-                    morph_shape.set_ratio(gc_context, ratio);
+                    morph_shape.set_ratio(context.gc_context, ratio);
+                } else if let Some(video) = self.as_video() {
+                    video.seek(context, ratio.into());
                 }
             }
             // Clip events only apply to movie clips.
@@ -790,7 +865,7 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
                 // Convert from `swf::ClipAction` to Ruffle's `ClipAction`.
                 use crate::display_object::movie_clip::ClipAction;
                 clip.set_clip_actions(
-                    gc_context,
+                    context.gc_context,
                     place_object
                         .clip_actions
                         .iter()
@@ -812,6 +887,8 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         self.set_matrix(gc_context, &*other.matrix());
         self.set_color_transform(gc_context, &*other.color_transform());
         self.set_clip_depth(gc_context, other.clip_depth());
+        self.set_filters(gc_context, other.filters().to_vec());
+        self.set_blend_mode(gc_context, other.blend_mode());
         self.set_name(gc_context, &*other.name());
         if let (Some(mut me), Some(other)) = (self.as_morph_shape(), other.as_morph_shape()) {
             me.set_ratio(gc_context, other.ratio());
@@ -827,6 +904,14 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         Value::Undefined // todo: impl for every type and delete this fallback
     }
 
+    /// The AVM2 object representing this display object.
+    ///
+    /// Returns `Undefined` if no object has been attached yet.
+    fn object2(&self) -> Avm2Value<'gc>;
+
+    /// Attach an AVM2 object to this display object.
+    fn set_object2(&mut self, context: MutationContext<'gc, '_>, to: Avm2Object<'gc>);
+
     /// Tests if a given stage position point intersects with the world bounds of this object.
     fn hit_test(&self, _pos: (Twips, Twips)) -> bool {
         false
@@ -842,6 +927,28 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         None
     }
 
+    /// The mouse cursor to display while this object is hovered.
+    fn mouse_cursor(&self) -> MouseCursor {
+        MouseCursor::Hand
+    }
+
+    /// Whether this object can take keyboard focus, such as when it is
+    /// clicked.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Called when this object gains or loses keyboard focus.
+    ///
+    /// `other` is the object that focus moved from, or is moving to.
+    fn on_focus_changed(
+        &self,
+        _context: &mut UpdateContext<'_, 'gc, '_>,
+        _focused: bool,
+        _other: Option<DisplayObject<'gc>>,
+    ) {
+    }
+
     fn post_instantiation(
         &mut self,
         _avm: &mut Avm1<'gc>,
@@ -1023,6 +1130,42 @@ macro_rules! impl_display_object_sansbounds {
         ) {
             self.0.write(context).$field.set_clip_depth(context, depth)
         }
+        fn filters(&self) -> std::cell::Ref<[swf::Filter]> {
+            std::cell::Ref::map(self.0.read(), |o| o.$field.filters())
+        }
+        fn set_filters(
+            &mut self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            filters: Vec<swf::Filter>,
+        ) {
+            self.0.write(context).$field.set_filters(context, filters)
+        }
+        fn blend_mode(&self) -> swf::BlendMode {
+            self.0.read().$field.blend_mode()
+        }
+        fn set_blend_mode(
+            &mut self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            blend_mode: swf::BlendMode,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_blend_mode(context, blend_mode)
+        }
+        fn focus_rect(&self) -> Option<bool> {
+            self.0.read().$field.focus_rect()
+        }
+        fn set_focus_rect(
+            &mut self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            focus_rect: Option<bool>,
+        ) {
+            self.0
+                .write(context)
+                .$field
+                .set_focus_rect(context, focus_rect)
+        }
         fn parent(&self) -> Option<crate::display_object::DisplayObject<'gc>> {
             self.0.read().$field.parent()
         }
@@ -1091,6 +1234,21 @@ macro_rules! impl_display_object_sansbounds {
         fn swf_version(&self) -> u8 {
             self.0.read().$field.swf_version()
         }
+        fn object2(&self) -> crate::avm2::Value<'gc> {
+            self.0
+                .read()
+                .$field
+                .avm2_object()
+                .map(crate::avm2::Value::from)
+                .unwrap_or(crate::avm2::Value::Undefined)
+        }
+        fn set_object2(
+            &mut self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            to: crate::avm2::Object<'gc>,
+        ) {
+            self.0.write(context).$field.set_avm2_object(to)
+        }
         fn instantiate(
             &self,
             gc_context: gc_arena::MutationContext<'gc, '_>,
@@ -1161,7 +1319,21 @@ pub fn render_children<'gc>(
             context.renderer.activate_mask();
         } else if child.visible() {
             // Normal child.
+            let blend_mode = child.blend_mode();
+            if blend_mode != BlendMode::Normal {
+                context.renderer.push_blend_mode(blend_mode);
+            }
+            let has_filters = !child.filters().is_empty();
+            if has_filters {
+                context.renderer.push_filters(&child.filters());
+            }
             child.render(context);
+            if has_filters {
+                context.renderer.pop_filters();
+            }
+            if blend_mode != BlendMode::Normal {
+                context.renderer.pop_blend_mode();
+            }
         }
     }
 
@@ -1177,6 +1349,53 @@ impl<'gc> DisplayObject<'gc> {
     }
 }
 
+/// The blend modes in the order of their numeric values, starting at 1.
+///
+/// This is the order used by `PlaceObject3` tags and by the numeric form of
+/// the AVM1 `blendMode` property.
+const BLEND_MODES: [(BlendMode, &str); 14] = [
+    (BlendMode::Normal, "normal"),
+    (BlendMode::Layer, "layer"),
+    (BlendMode::Multiply, "multiply"),
+    (BlendMode::Screen, "screen"),
+    (BlendMode::Lighten, "lighten"),
+    (BlendMode::Darken, "darken"),
+    (BlendMode::Difference, "difference"),
+    (BlendMode::Add, "add"),
+    (BlendMode::Subtract, "subtract"),
+    (BlendMode::Invert, "invert"),
+    (BlendMode::Alpha, "alpha"),
+    (BlendMode::Erase, "erase"),
+    (BlendMode::Overlay, "overlay"),
+    (BlendMode::HardLight, "hardlight"),
+];
+
+/// Returns the ActionScript name of a blend mode, such as `"multiply"`.
+pub fn blend_mode_name(blend_mode: BlendMode) -> &'static str {
+    BLEND_MODES
+        .iter()
+        .find(|(mode, _)| *mode == blend_mode)
+        .map(|(_, name)| *name)
+        .unwrap_or("normal")
+}
+
+/// Parses the ActionScript name of a blend mode.
+pub fn blend_mode_from_name(name: &str) -> Option<BlendMode> {
+    BLEND_MODES
+        .iter()
+        .find(|(_, mode_name)| *mode_name == name)
+        .map(|(mode, _)| *mode)
+}
+
+/// Converts the numeric value of a blend mode, as stored in SWF files.
+pub fn blend_mode_from_index(index: i32) -> Option<BlendMode> {
+    if index >= 1 {
+        BLEND_MODES.get(index as usize - 1).map(|(mode, _)| *mode)
+    } else {
+        None
+    }
+}
+
 /// Bit flags used by `DisplayObject`.
 #[derive(Collect, EnumSetType, Debug)]
 #[collect(no_drop)]
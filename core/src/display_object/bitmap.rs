@@ -1,7 +1,8 @@
 //! Bitmap display object
 
 use crate::avm1::Avm1;
-use crate::backend::render::BitmapHandle;
+use crate::backend::render::{self, BitmapHandle, BitmapInfo};
+use crate::bitmap_data::BitmapData;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
@@ -9,19 +10,66 @@ use gc_arena::{Collect, Gc, GcCell};
 
 /// A Bitmap display object is a raw bitamp on the stage.
 /// This can only be instanitated on the display list in SWFv9 AVM2 files.
-/// In AVM1, this is only a library symbol that is referenced by `Graphic`.
+/// In AVM1, this is only a library symbol that is referenced by `Graphic`,
+/// or a `BitmapData` attached to a clip with `MovieClip.attachBitmap`.
 /// Normally bitmaps are drawn in Flash as part of a Shape tag (`Graphic`),
 /// but starting in AVM2, a raw `Bitmap` display object can be crated
 /// with the `PlaceObject3` tag.
 /// It can also be crated in ActionScript using the `Bitmap` class.
 #[derive(Clone, Debug, Collect, Copy)]
 #[collect(no_drop)]
-pub struct Bitmap<'gc>(GcCell<'gc, BitmapData<'gc>>);
+pub struct Bitmap<'gc>(GcCell<'gc, BitmapGraphicData<'gc>>);
 
 #[derive(Clone, Debug)]
-pub struct BitmapData<'gc> {
+pub struct BitmapGraphicData<'gc> {
     base: DisplayObjectBase<'gc>,
-    static_data: Gc<'gc, BitmapStatic>,
+    content: BitmapContent<'gc>,
+}
+
+/// Where the pixels of a bitmap come from.
+#[derive(Clone, Debug)]
+enum BitmapContent<'gc> {
+    /// A bitmap character defined in a SWF.
+    Static(Gc<'gc, BitmapStatic>),
+
+    /// Pixels created by ActionScript, which may change at any time.
+    Dynamic(GcCell<'gc, BitmapData>),
+}
+
+/// The tag that defined a bitmap character, kept to decode its pixels
+/// when they are needed by `BitmapData.loadBitmap`.
+#[derive(Clone, Debug)]
+pub enum BitmapTag {
+    /// A `DefineBits` tag, which uses the JPEG tables of its movie.
+    DefineBits(Vec<u8>),
+
+    /// A `DefineBitsJPEG2`, `DefineBitsJPEG3` or `DefineBitsJPEG4` tag.
+    DefineBitsJpeg {
+        data: Vec<u8>,
+        alpha_data: Option<Vec<u8>>,
+    },
+
+    /// A `DefineBitsLossless` or `DefineBitsLossless2` tag.
+    DefineBitsLossless(swf::DefineBitsLossless),
+}
+
+impl BitmapTag {
+    /// Decodes the pixels of the bitmap.
+    pub fn decode(
+        &self,
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<render::Bitmap, Box<dyn std::error::Error>> {
+        match self {
+            BitmapTag::DefineBits(data) => {
+                let data = render::glue_tables_to_jpeg(data, jpeg_tables);
+                render::decode_define_bits_jpeg(&data, None)
+            }
+            BitmapTag::DefineBitsJpeg { data, alpha_data } => {
+                render::decode_define_bits_jpeg(data, alpha_data.as_deref())
+            }
+            BitmapTag::DefineBitsLossless(tag) => render::decode_define_bits_lossless(tag),
+        }
+    }
 }
 
 impl<'gc> Bitmap<'gc> {
@@ -31,35 +79,72 @@ impl<'gc> Bitmap<'gc> {
         bitmap_handle: BitmapHandle,
         width: u16,
         height: u16,
+        tag: BitmapTag,
     ) -> Self {
         Bitmap(GcCell::allocate(
             context.gc_context,
-            BitmapData {
+            BitmapGraphicData {
                 base: Default::default(),
-                static_data: Gc::allocate(
+                content: BitmapContent::Static(Gc::allocate(
                     context.gc_context,
                     BitmapStatic {
                         id,
                         bitmap_handle,
                         width,
                         height,
+                        tag,
                     },
-                ),
+                )),
+            },
+        ))
+    }
+
+    /// Creates a bitmap showing the pixels of a `BitmapData`.
+    pub fn new_with_bitmap_data(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        bitmap_data: GcCell<'gc, BitmapData>,
+    ) -> Self {
+        Bitmap(GcCell::allocate(
+            context.gc_context,
+            BitmapGraphicData {
+                base: Default::default(),
+                content: BitmapContent::Dynamic(bitmap_data),
             },
         ))
     }
 
-    #[allow(dead_code)]
-    pub fn bitmap_handle(self) -> BitmapHandle {
-        self.0.read().static_data.bitmap_handle
+    /// The texture of a bitmap character, or `None` for a `BitmapData`.
+    pub fn static_bitmap_info(self) -> Option<BitmapInfo> {
+        match &self.0.read().content {
+            BitmapContent::Static(static_data) => Some(BitmapInfo {
+                handle: static_data.bitmap_handle,
+                width: static_data.width,
+                height: static_data.height,
+            }),
+            BitmapContent::Dynamic(_) => None,
+        }
+    }
+
+    /// The tag that defined this bitmap, or `None` for a `BitmapData`.
+    pub fn tag(self) -> Option<BitmapTag> {
+        match &self.0.read().content {
+            BitmapContent::Static(static_data) => Some(static_data.tag.clone()),
+            BitmapContent::Dynamic(_) => None,
+        }
     }
 
     pub fn width(self) -> u16 {
-        self.0.read().static_data.width
+        match &self.0.read().content {
+            BitmapContent::Static(static_data) => static_data.width,
+            BitmapContent::Dynamic(bitmap_data) => bitmap_data.read().width() as u16,
+        }
     }
 
     pub fn height(self) -> u16 {
-        self.0.read().static_data.height
+        match &self.0.read().content {
+            BitmapContent::Static(static_data) => static_data.height,
+            BitmapContent::Dynamic(bitmap_data) => bitmap_data.read().height() as u16,
+        }
     }
 }
 
@@ -67,15 +152,18 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
     impl_display_object!(base);
 
     fn id(&self) -> CharacterId {
-        self.0.read().static_data.id
+        match &self.0.read().content {
+            BitmapContent::Static(static_data) => static_data.id,
+            BitmapContent::Dynamic(_) => 0,
+        }
     }
 
     fn self_bounds(&self) -> BoundingBox {
         BoundingBox {
             x_min: Twips::new(0),
             y_min: Twips::new(0),
-            x_max: Twips::new(Bitmap::width(*self)),
-            y_max: Twips::new(Bitmap::height(*self)),
+            x_max: Twips::from_pixels(Bitmap::width(*self).into()),
+            y_max: Twips::from_pixels(Bitmap::height(*self).into()),
             valid: true,
         }
     }
@@ -90,31 +178,45 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
             return;
         }
 
+        let bitmap_handle = match &self.0.read().content {
+            BitmapContent::Static(static_data) => static_data.bitmap_handle,
+            BitmapContent::Dynamic(bitmap_data) => {
+                match bitmap_data.read().bitmap_handle(context.renderer) {
+                    Some(info) => info.handle,
+                    // Disposed bitmaps draw nothing.
+                    None => return,
+                }
+            }
+        };
+
         context.transform_stack.push(&*self.transform());
 
-        context.renderer.render_bitmap(
-            self.0.read().static_data.bitmap_handle,
-            context.transform_stack.transform(),
-        );
+        context
+            .renderer
+            .render_bitmap(bitmap_handle, context.transform_stack.transform());
 
         context.transform_stack.pop();
     }
 }
 
-unsafe impl<'gc> gc_arena::Collect for BitmapData<'gc> {
+unsafe impl<'gc> gc_arena::Collect for BitmapGraphicData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
-        self.static_data.trace(cc);
+        match &self.content {
+            BitmapContent::Static(static_data) => static_data.trace(cc),
+            BitmapContent::Dynamic(bitmap_data) => bitmap_data.trace(cc),
+        }
     }
 }
 
 /// Static data shared between all instances of a bitmap.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 struct BitmapStatic {
     id: CharacterId,
     bitmap_handle: BitmapHandle,
     width: u16,
     height: u16,
+    tag: BitmapTag,
 }
 
 unsafe impl<'gc> gc_arena::Collect for BitmapStatic {
@@ -181,6 +181,10 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         }
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn object(&self) -> Value<'gc> {
         self.0
             .read()
@@ -248,6 +252,7 @@ impl<'gc> ButtonData<'gc> {
                         context.gc_context,
                         &record.color_transform.clone().into(),
                     );
+                    child.set_blend_mode(context.gc_context, record.blend_mode);
                     child.set_depth(context.gc_context, record.depth.into());
                     child.post_instantiation(avm, context, child, None, false);
                     child.run_frame(avm, context);
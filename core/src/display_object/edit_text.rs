@@ -1,18 +1,23 @@
 //! `EditText` display object and support code.
 use crate::avm1::activation::Activation;
 use crate::avm1::globals::text_field::attach_virtual_properties;
+use crate::avm1::object::style_sheet_object::StyleSheetObject;
 use crate::avm1::{Avm1, AvmString, Object, StageObject, TObject, Value};
-use crate::context::{RenderContext, UpdateContext};
+use crate::backend::input::MouseCursor;
+use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::drawing::Drawing;
+use crate::events::{ClipEvent, ClipEventResult, KeyCode};
 use crate::font::{round_down_to_pixel, Glyph};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, TextFormat};
+use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutLine, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
+use crate::string_utils;
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
 use crate::xml::XMLDocument;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::borrow::Cow;
 use std::{cell::Ref, sync::Arc};
 use swf::Twips;
 
@@ -69,6 +74,15 @@ pub struct EditTextData<'gc> {
     /// rendering.
     text_spans: FormatSpans,
 
+    /// The style sheet that styles the HTML document, if any.
+    style_sheet: Option<StyleSheetObject<'gc>>,
+
+    /// The text range of each link in the lowered HTML document.
+    link_ranges: Vec<(usize, usize)>,
+
+    /// The link that the mouse is over, which is styled with `a:hover`.
+    hovered_link: Option<usize>,
+
     /// If the text is in multi-line mode or single-line mode.
     is_multiline: bool,
 
@@ -78,14 +92,46 @@ pub struct EditTextData<'gc> {
     /// If the text field should have a border.
     has_border: bool,
 
+    /// The color of the border, as 0xRRGGBB.
+    border_color: u32,
+
+    /// If the text field should have a background.
+    has_background: bool,
+
+    /// The color of the background, as 0xRRGGBB.
+    background_color: u32,
+
     /// If the text field is required to use device fonts only.
     is_device_font: bool,
 
     /// If the text field renders as HTML.
     is_html: bool,
 
-    /// The current border drawing.
-    drawing: Drawing,
+    /// If runs of whitespace in HTML text are collapsed into a single space.
+    is_condense_white: bool,
+
+    /// If the text can be edited by the user.
+    is_editable: bool,
+
+    /// If the text can be selected by the user.
+    is_selectable: bool,
+
+    /// If the text is displayed as asterisks.
+    is_password: bool,
+
+    /// The maximum number of characters that the user can enter, or 0 if
+    /// there is no limit.
+    max_chars: i32,
+
+    /// The characters that the user is allowed to enter.
+    restrict: EditTextRestrict,
+
+    /// The current background and border drawing.
+    drawing: Drawing<'gc>,
+
+    /// A drawing of the bounds of the text field, used to clip the text
+    /// when it overflows.
+    clip_drawing: Drawing<'gc>,
 
     /// Whether or not the width of the field should change in response to text
     /// changes, and in what direction should added or removed width should
@@ -95,12 +141,32 @@ pub struct EditTextData<'gc> {
     /// The calculated layout box.
     layout: Vec<LayoutBox<'gc>>,
 
+    /// The lines of the calculated layout.
+    lines: Vec<LayoutLine>,
+
+    /// The line shown at the top of the text field, starting from 1.
+    scroll: usize,
+
+    /// How far the text is scrolled to the left, in pixels.
+    hscroll: f64,
+
     /// The intrinsic bounds of the laid-out text.
     intrinsic_bounds: BoxBounds<Twips>,
 
     /// The current intrinsic bounds of the text field.
     bounds: BoundingBox,
 
+    /// The selected text, or the position of the caret.
+    ///
+    /// This is only set while the text field has focus.
+    selection: Option<TextSelection>,
+
+    /// The current drawing of the selection highlight or the caret.
+    selection_drawing: Drawing<'gc>,
+
+    /// Whether the user is selecting text by dragging the mouse.
+    is_selecting_with_mouse: bool,
+
     /// The AVM1 object handle
     object: Option<Object<'gc>>,
 
@@ -136,15 +202,15 @@ impl<'gc> EditText<'gc> {
                 .as_node()
                 .replace_with_str(context.gc_context, &text, false)
                 .unwrap();
-            text_spans.lower_from_html(document);
+            text_spans.lower_from_html(document, false);
         } else {
             text_spans.replace_text(0, text_spans.text().len(), &text, Some(&default_format));
         }
 
         let bounds: BoundingBox = swf_tag.bounds.clone().into();
 
-        let (layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
-            &text_spans,
+        let (layout, lines, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
+            &displayed_spans(&text_spans, swf_tag.is_password),
             context,
             swf_movie.clone(),
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
@@ -154,6 +220,10 @@ impl<'gc> EditText<'gc> {
 
         let has_border = swf_tag.has_border;
         let is_device_font = swf_tag.is_device_font;
+        let is_editable = !swf_tag.is_read_only;
+        let is_selectable = swf_tag.is_selectable;
+        let is_password = swf_tag.is_password;
+        let max_chars = swf_tag.max_length.map(i32::from).unwrap_or(0);
 
         let mut base = DisplayObjectBase::default();
 
@@ -172,6 +242,9 @@ impl<'gc> EditText<'gc> {
                 base,
                 document,
                 text_spans,
+                style_sheet: None,
+                link_ranges: vec![],
+                hovered_link: None,
                 static_data: gc_arena::Gc::allocate(
                     context.gc_context,
                     EditTextStatic {
@@ -182,13 +255,30 @@ impl<'gc> EditText<'gc> {
                 is_multiline,
                 is_word_wrap,
                 has_border,
+                border_color: 0,
+                // Text fields with a border also have a white background.
+                has_background: has_border,
+                background_color: 0xFFFFFF,
                 is_device_font,
                 is_html,
+                is_condense_white: false,
+                is_editable,
+                is_selectable,
+                is_password,
+                max_chars,
+                restrict: EditTextRestrict::default(),
                 drawing: Drawing::new(),
+                clip_drawing: Drawing::new(),
                 object: None,
                 layout,
+                lines,
+                scroll: 1,
+                hscroll: 0.0,
                 intrinsic_bounds,
                 bounds,
+                selection: None,
+                selection_drawing: Drawing::new(),
+                is_selecting_with_mouse: false,
                 autosize: AutoSizeMode::None,
                 variable,
                 bound_stage_object: None,
@@ -227,7 +317,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -274,6 +364,7 @@ impl<'gc> EditText<'gc> {
         let tf = edit_text.text_spans.default_format().clone();
 
         edit_text.text_spans.replace_text(0, len, &text, Some(&tf));
+        edit_text.text_changed(context.gc_context);
 
         drop(edit_text);
 
@@ -326,7 +417,12 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn html_tree(self, context: &mut UpdateContext<'_, 'gc, '_>) -> XMLDocument<'gc> {
-        self.0.read().text_spans.raise_to_html(context.gc_context)
+        let edit_text = self.0.read();
+        if edit_text.style_sheet.is_some() {
+            edit_text.document
+        } else {
+            edit_text.text_spans.raise_to_html(context.gc_context)
+        }
     }
 
     /// Set the HTML tree for the given display object.
@@ -343,7 +439,8 @@ impl<'gc> EditText<'gc> {
         let mut write = self.0.write(context.gc_context);
 
         write.document = doc;
-        write.text_spans.lower_from_html(doc);
+        write.hovered_link = None;
+        write.lower_document();
 
         drop(write);
 
@@ -419,6 +516,60 @@ impl<'gc> EditText<'gc> {
         self.redraw_border(context);
     }
 
+    pub fn border_color(self) -> u32 {
+        self.0.read().border_color
+    }
+
+    pub fn set_border_color(self, context: MutationContext<'gc, '_>, border_color: u32) {
+        self.0.write(context).border_color = border_color;
+        self.redraw_border(context);
+    }
+
+    pub fn has_background(self) -> bool {
+        self.0.read().has_background
+    }
+
+    pub fn set_has_background(self, context: MutationContext<'gc, '_>, has_background: bool) {
+        self.0.write(context).has_background = has_background;
+        self.redraw_border(context);
+    }
+
+    pub fn background_color(self) -> u32 {
+        self.0.read().background_color
+    }
+
+    pub fn set_background_color(self, context: MutationContext<'gc, '_>, background_color: u32) {
+        self.0.write(context).background_color = background_color;
+        self.redraw_border(context);
+    }
+
+    /// Returns the color of the text, or the color of new text if the text
+    /// has more than one color.
+    pub fn text_color(self) -> Option<Color> {
+        let edit_text = self.0.read();
+        let text_length = edit_text.text_spans.text().len();
+        edit_text
+            .text_spans
+            .get_text_format(0, text_length)
+            .color
+            .or_else(|| edit_text.text_spans.default_format().color.clone())
+    }
+
+    /// Sets the color of all of the text, including new text.
+    pub fn set_text_color(self, color: Color, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut edit_text = self.0.write(context.gc_context);
+        let text_length = edit_text.text_spans.text().len();
+        let tf = TextFormat {
+            color: Some(color),
+            ..Default::default()
+        };
+        edit_text.text_spans.set_text_format(0, text_length, &tf);
+        let default_format = tf.mix_with(edit_text.text_spans.default_format().clone());
+        edit_text.text_spans.set_default_format(default_format);
+        drop(edit_text);
+        self.relayout(context);
+    }
+
     pub fn is_device_font(self) -> bool {
         self.0.read().is_device_font
     }
@@ -440,6 +591,227 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).is_html = is_html;
     }
 
+    pub fn is_condense_white(self) -> bool {
+        self.0.read().is_condense_white
+    }
+
+    /// Sets whether whitespace is condensed in HTML text.
+    ///
+    /// This only applies to HTML text that is set afterwards.
+    pub fn set_condense_white(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        is_condense_white: bool,
+    ) {
+        self.0.write(context.gc_context).is_condense_white = is_condense_white;
+    }
+
+    pub fn style_sheet(self) -> Option<StyleSheetObject<'gc>> {
+        self.0.read().style_sheet
+    }
+
+    /// Sets the style sheet that styles the HTML text.
+    ///
+    /// The current HTML text is styled again with the new style sheet. Text
+    /// fields with a style sheet can't be edited by the user.
+    pub fn set_style_sheet(
+        self,
+        style_sheet: Option<StyleSheetObject<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        write.style_sheet = style_sheet;
+        if style_sheet.is_none() {
+            return;
+        }
+
+        write.is_editable = false;
+        write.hovered_link = None;
+        if write.is_html {
+            write.lower_document();
+            drop(write);
+            self.relayout(context);
+        }
+    }
+
+    /// Updates which link is under the mouse, and restyles the text if it
+    /// changed so that the hovered link is styled with `a:hover`.
+    fn update_hovered_link(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mouse_position = *context.mouse_position;
+        let hovered_link = if self.0.read().style_sheet.is_some()
+            && self.visible()
+            && self.world_bounds().contains(mouse_position)
+        {
+            let position = self.screen_position_to_text_position(mouse_position);
+            let edit_text = self.0.read();
+            edit_text.char_index_at(position).and_then(|index| {
+                edit_text
+                    .link_ranges
+                    .iter()
+                    .position(|&(start, end)| start <= index && index < end)
+            })
+        } else {
+            None
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        if write.hovered_link != hovered_link {
+            write.hovered_link = hovered_link;
+            write.lower_document();
+            drop(write);
+            self.relayout(context);
+        }
+    }
+
+    pub fn is_editable(self) -> bool {
+        self.0.read().is_editable
+    }
+
+    pub fn set_editable(self, is_editable: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut write = self.0.write(context.gc_context);
+        write.is_editable = is_editable;
+        write.redraw_selection();
+    }
+
+    pub fn is_selectable(self) -> bool {
+        self.0.read().is_selectable
+    }
+
+    pub fn set_selectable(self, is_selectable: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).is_selectable = is_selectable;
+    }
+
+    pub fn is_password(self) -> bool {
+        self.0.read().is_password
+    }
+
+    pub fn set_password(self, is_password: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).is_password = is_password;
+        self.relayout(context);
+    }
+
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).max_chars = max_chars;
+    }
+
+    pub fn restrict(self) -> Option<String> {
+        self.0.read().restrict.value().map(str::to_string)
+    }
+
+    pub fn set_restrict(self, restrict: Option<&str>, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).restrict = EditTextRestrict::from(restrict);
+    }
+
+    /// Returns the selected text, or the position of the caret, if this text
+    /// field has focus.
+    pub fn selection(self) -> Option<TextSelection> {
+        self.0.read().selection
+    }
+
+    pub fn set_selection(
+        self,
+        selection: Option<TextSelection>,
+        gc_context: MutationContext<'gc, '_>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.selection = selection.map(|selection| selection.clamped(write.text_spans.text()));
+        write.redraw_selection();
+    }
+
+    /// Returns the line shown at the top of the text field, starting from 1.
+    pub fn scroll(self) -> usize {
+        self.0.read().scroll
+    }
+
+    /// Scrolls the text so that the given line is shown at the top of the
+    /// text field.
+    pub fn set_scroll(self, scroll: usize, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut edit_text = self.0.write(context.gc_context);
+        let old_scroll_properties = edit_text.scroll_properties();
+        edit_text.scroll = scroll;
+        edit_text.clamp_scroll();
+        drop(edit_text);
+        self.notify_scroll_change(old_scroll_properties, context);
+    }
+
+    /// Returns the highest value of `scroll`, which shows the last line of
+    /// text at the bottom of the text field.
+    pub fn maxscroll(self) -> usize {
+        self.0.read().maxscroll()
+    }
+
+    /// Returns the line shown at the bottom of the text field, starting
+    /// from 1.
+    pub fn bottom_scroll(self) -> usize {
+        self.0.read().bottom_scroll()
+    }
+
+    /// Returns how far the text is scrolled to the left, in pixels.
+    pub fn hscroll(self) -> f64 {
+        self.0.read().hscroll
+    }
+
+    pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut edit_text = self.0.write(context.gc_context);
+        let old_scroll_properties = edit_text.scroll_properties();
+        edit_text.hscroll = hscroll;
+        edit_text.clamp_scroll();
+        drop(edit_text);
+        self.notify_scroll_change(old_scroll_properties, context);
+    }
+
+    /// Returns the highest value of `hscroll`, in pixels.
+    pub fn maxhscroll(self) -> f64 {
+        round_down_to_pixel(self.0.read().maxhscroll()).to_pixels()
+    }
+
+    /// Returns the number of lines of the laid-out text.
+    pub fn num_lines(self) -> usize {
+        self.0.read().lines.len()
+    }
+
+    /// Returns the position and measurements of a line of the laid-out
+    /// text, starting from 0.
+    pub fn line_metrics(self, line: usize) -> Option<LayoutLine> {
+        self.0.read().lines.get(line).copied()
+    }
+
+    /// Scrolls the text so that the caret is visible.
+    fn scroll_to_caret(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut edit_text = self.0.write(context.gc_context);
+        let old_scroll_properties = edit_text.scroll_properties();
+        edit_text.scroll_to_caret();
+        drop(edit_text);
+        self.notify_scroll_change(old_scroll_properties, context);
+    }
+
+    /// Calls `onScroller` if any of the scroll properties have changed from
+    /// the given values.
+    fn notify_scroll_change(
+        self,
+        old_scroll_properties: ScrollProperties,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        if self.0.read().scroll_properties() == old_scroll_properties {
+            return;
+        }
+        if let Value::Object(object) = self.object() {
+            context.action_queue.queue_actions(
+                self.into(),
+                ActionType::Method {
+                    object,
+                    name: "onScroller",
+                    args: vec![object.into()],
+                },
+                false,
+            );
+        }
+    }
+
     pub fn replace_text(
         self,
         from: usize,
@@ -447,11 +819,222 @@ impl<'gc> EditText<'gc> {
         text: &str,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
-        self.0
-            .write(context.gc_context)
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.text_spans.replace_text(from, to, text, None);
+        edit_text.text_changed(context.gc_context);
+        drop(edit_text);
+        self.relayout(context);
+    }
+
+    /// Handles a character typed by the user while this text field has focus.
+    pub fn text_input(
+        self,
+        character: char,
+        avm: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        // Shortcuts like Ctrl+C are handled by `key_down` instead.
+        if context.input.is_key_down(KeyCode::Control) && !context.input.is_key_down(KeyCode::Alt) {
+            return;
+        }
+
+        let edit_text = self.0.read();
+        if !edit_text.is_editable || edit_text.selection.is_none() {
+            return;
+        }
+        let character = match character {
+            '\r' | '\n' if edit_text.is_multiline => '\n',
+            c if c.is_control() => return,
+            c => match edit_text.restrict.to_allowed(c) {
+                Some(c) => c,
+                None => return,
+            },
+        };
+        drop(edit_text);
+
+        self.replace_selection_by_user(character.encode_utf8(&mut [0; 4]), avm, context);
+    }
+
+    /// Handles a key pressed by the user while this text field has focus.
+    ///
+    /// This moves the caret, deletes text, and handles the clipboard
+    /// shortcuts.
+    pub fn key_down(
+        self,
+        key_code: KeyCode,
+        avm: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+        let is_editable = self.is_editable();
+        let is_shift_down = context.input.is_key_down(KeyCode::Shift);
+        let is_control_down = context.input.is_key_down(KeyCode::Control);
+        let text = self.text();
+        let caret = selection.to();
+
+        match key_code {
+            KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Home
+            | KeyCode::End
+            | KeyCode::Up
+            | KeyCode::Down => {
+                let new_caret = match key_code {
+                    KeyCode::Left if !is_shift_down && !selection.is_caret() => selection.start(),
+                    KeyCode::Right if !is_shift_down && !selection.is_caret() => selection.end(),
+                    KeyCode::Left => previous_char_boundary(&text, caret),
+                    KeyCode::Right => next_char_boundary(&text, caret),
+                    KeyCode::Home => text[..caret].rfind('\n').map(|i| i + 1).unwrap_or(0),
+                    KeyCode::End => text[caret..]
+                        .find('\n')
+                        .map(|i| caret + i)
+                        .unwrap_or_else(|| text.len()),
+                    _ => self
+                        .0
+                        .read()
+                        .vertical_neighbour(caret, key_code == KeyCode::Up),
+                };
+                let new_selection = if is_shift_down {
+                    TextSelection::for_range(selection.from(), new_caret)
+                } else {
+                    TextSelection::for_position(new_caret)
+                };
+                self.set_selection(Some(new_selection), context.gc_context);
+            }
+            KeyCode::Backspace | KeyCode::Delete if is_editable => {
+                let range = if !selection.is_caret() {
+                    selection
+                } else if key_code == KeyCode::Backspace {
+                    TextSelection::for_range(previous_char_boundary(&text, caret), caret)
+                } else {
+                    TextSelection::for_range(caret, next_char_boundary(&text, caret))
+                };
+                if !range.is_caret() {
+                    self.set_selection(Some(range), context.gc_context);
+                    self.replace_selection_by_user("", avm, context);
+                }
+            }
+            KeyCode::A if is_control_down => {
+                self.set_selection(
+                    Some(TextSelection::for_range(0, text.len())),
+                    context.gc_context,
+                );
+            }
+            KeyCode::C | KeyCode::X if is_control_down => {
+                // Password fields can't be copied from.
+                if !selection.is_caret() && !self.is_password() {
+                    let selected_text = text[selection.start()..selection.end()].to_string();
+                    context.input.set_clipboard_content(selected_text);
+                    if key_code == KeyCode::X && is_editable {
+                        self.replace_selection_by_user("", avm, context);
+                    }
+                }
+            }
+            KeyCode::V if is_control_down && is_editable => {
+                let content = context.input.clipboard_content().replace("\r\n", "\n");
+                let edit_text = self.0.read();
+                let pasted: String = content
+                    .chars()
+                    .filter_map(|c| match c {
+                        '\r' | '\n' if edit_text.is_multiline => Some('\n'),
+                        c if c.is_control() => None,
+                        c => edit_text.restrict.to_allowed(c),
+                    })
+                    .collect();
+                drop(edit_text);
+                self.replace_selection_by_user(&pasted, avm, context);
+            }
+            _ => (),
+        }
+
+        self.scroll_to_caret(context);
+    }
+
+    /// Replaces the selected text with text entered by the user, and notifies
+    /// ActionScript of the change.
+    ///
+    /// The entered text is truncated to fit within `maxChars`.
+    fn replace_selection_by_user(
+        self,
+        text: &str,
+        avm: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        let mut edit_text = self.0.write(context.gc_context);
+        let text = if edit_text.max_chars > 0 {
+            // `maxChars` counts UTF-16 code units, like all Flash string lengths.
+            let current_text = edit_text.text_spans.text();
+            let num_units = current_text.encode_utf16().count()
+                - current_text[selection.start()..selection.end()]
+                    .encode_utf16()
+                    .count();
+            let num_allowed = (edit_text.max_chars as usize).saturating_sub(num_units);
+            &text[..string_utils::utf16_index_to_byte_offset(text, num_allowed)]
+        } else {
+            text
+        };
+        if text.is_empty() && selection.is_caret() {
+            return;
+        }
+
+        edit_text
             .text_spans
-            .replace_text(from, to, text, None);
+            .replace_text(selection.start(), selection.end(), text, None);
+        edit_text.text_changed(context.gc_context);
+        edit_text.selection = Some(TextSelection::for_position(selection.start() + text.len()));
+        drop(edit_text);
         self.relayout(context);
+        self.scroll_to_caret(context);
+
+        avm.run_with_stack_frame_for_display_object(
+            self.into(),
+            context.swf.version(),
+            context,
+            |activation, context| {
+                self.propagate_text_binding(activation, context);
+            },
+        );
+        if let Value::Object(object) = self.object() {
+            context.action_queue.queue_actions(
+                self.into(),
+                ActionType::Method {
+                    object,
+                    name: "onChanged",
+                    args: vec![object.into()],
+                },
+                false,
+            );
+        }
+    }
+
+    /// Returns the position in the text that is closest to the given point
+    /// on the stage.
+    fn screen_position_to_index(self, position: (Twips, Twips)) -> usize {
+        let position = self.screen_position_to_text_position(position);
+        let edit_text = self.0.read();
+        let layout_index = edit_text.layout_index_at(position);
+        edit_text.from_layout_index(layout_index)
+    }
+
+    /// Converts a point on the stage to a point relative to the laid-out
+    /// text.
+    fn screen_position_to_text_position(self, position: (Twips, Twips)) -> (Twips, Twips) {
+        let (x, y) = self.global_to_local(position);
+        let edit_text = self.0.read();
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+        let (scroll_x, scroll_y) = edit_text.scroll_offset();
+        (
+            x - edit_text.bounds.x_min - padding + scroll_x,
+            y - edit_text.bounds.y_min - padding + scroll_y,
+        )
     }
 
     /// Construct a base text transform for a particular `EditText` span.
@@ -536,39 +1119,60 @@ impl<'gc> EditText<'gc> {
     /// The `text_transform` constitutes the base transform that all text is
     /// written into.
 
-    /// Redraw the border of this `EditText`.
+    /// Redraw the background and border of this `EditText`.
     fn redraw_border(self, context: MutationContext<'gc, '_>) {
         let mut write = self.0.write(context);
+        let bounds = write.bounds.clone();
+        let rect = [
+            (Twips::new(0), bounds.y_max - bounds.y_min),
+            (bounds.x_max - bounds.x_min, bounds.y_max - bounds.y_min),
+            (bounds.x_max - bounds.x_min, Twips::new(0)),
+            (Twips::new(0), Twips::new(0)),
+        ];
 
         write.drawing.clear();
+        write.clip_drawing.clear();
+
+        write
+            .clip_drawing
+            .set_fill_style(Some(swf::FillStyle::Color(Color::from_rgb(0, 0xFF))));
+        write.clip_drawing.draw_command(DrawCommand::MoveTo {
+            x: Twips::new(0),
+            y: Twips::new(0),
+        });
+        for &(x, y) in &rect {
+            write
+                .clip_drawing
+                .draw_command(DrawCommand::LineTo { x, y });
+        }
 
-        if write.has_border {
-            let bounds = write.bounds.clone();
-
-            write.drawing.set_line_style(Some(swf::LineStyle::new_v1(
-                Twips::new(1),
-                swf::Color::from_rgb(0, 0xFF),
-            )));
+        if write.has_background {
+            let background_color = Color::from_rgb(write.background_color, 0xFF);
+            write
+                .drawing
+                .set_fill_style(Some(swf::FillStyle::Color(background_color)));
             write.drawing.draw_command(DrawCommand::MoveTo {
                 x: Twips::new(0),
                 y: Twips::new(0),
             });
-            write.drawing.draw_command(DrawCommand::LineTo {
-                x: Twips::new(0),
-                y: bounds.y_max - bounds.y_min,
-            });
-            write.drawing.draw_command(DrawCommand::LineTo {
-                x: bounds.x_max - bounds.x_min,
-                y: bounds.y_max - bounds.y_min,
-            });
-            write.drawing.draw_command(DrawCommand::LineTo {
-                x: bounds.x_max - bounds.x_min,
-                y: Twips::new(0),
-            });
-            write.drawing.draw_command(DrawCommand::LineTo {
+            for &(x, y) in &rect {
+                write.drawing.draw_command(DrawCommand::LineTo { x, y });
+            }
+            write.drawing.set_fill_style(None);
+        }
+
+        if write.has_border {
+            let border_color = Color::from_rgb(write.border_color, 0xFF);
+            write
+                .drawing
+                .set_line_style(Some(swf::LineStyle::new_v1(Twips::new(1), border_color)));
+            write.drawing.draw_command(DrawCommand::MoveTo {
                 x: Twips::new(0),
                 y: Twips::new(0),
             });
+            for &(x, y) in &rect {
+                write.drawing.draw_command(DrawCommand::LineTo { x, y });
+            }
         }
     }
 
@@ -584,13 +1188,14 @@ impl<'gc> EditText<'gc> {
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let mut edit_text = self.0.write(context.gc_context);
+        let old_scroll_properties = edit_text.scroll_properties();
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.is_word_wrap;
         let movie = edit_text.static_data.swf.clone();
         let width = edit_text.bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0);
 
-        let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
-            &edit_text.text_spans,
+        let (new_layout, lines, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
+            &displayed_spans(&edit_text.text_spans, edit_text.is_password),
             context,
             movie,
             width,
@@ -599,8 +1204,14 @@ impl<'gc> EditText<'gc> {
         );
 
         edit_text.layout = new_layout;
+        edit_text.lines = lines;
         edit_text.intrinsic_bounds = intrinsic_bounds;
 
+        // The text may have changed underneath the selection.
+        let selection = edit_text.selection;
+        edit_text.selection = selection.map(|s| s.clamped(edit_text.text_spans.text()));
+        edit_text.redraw_selection();
+
         match autosize {
             AutoSizeMode::None => {}
             AutoSizeMode::Left => {
@@ -639,6 +1250,11 @@ impl<'gc> EditText<'gc> {
                 edit_text.base.set_transformed_by_script(true);
             }
         }
+
+        // The text may no longer reach as far as it was scrolled.
+        edit_text.clamp_scroll();
+        drop(edit_text);
+        self.notify_scroll_change(old_scroll_properties, context);
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -659,29 +1275,55 @@ impl<'gc> EditText<'gc> {
         context.transform_stack.push(&box_transform);
 
         let edit_text = self.0.read();
+        let displayed_text = displayed_text(edit_text.text_spans.text(), edit_text.is_password);
 
         // If the font can't be found or has no glyph information, use the "device font" instead.
         // We're cheating a bit and not actually rendering text using the OS/web.
         // Instead, we embed an SWF version of Noto Sans to use as the "device font", and render
         // it the same as any other SWF outline text.
-        if let Some((text, _tf, font, params, color)) =
-            lbox.as_renderable_text(edit_text.text_spans.text())
-        {
+        if let Some((text, _tf, font, params, color)) = lbox.as_renderable_text(&displayed_text) {
             let baseline_adjustmnet =
                 font.get_baseline_for_height(params.height()) - params.height();
-            font.evaluate(
-                text,
-                self.text_transform(color, baseline_adjustmnet),
-                params,
-                |transform, glyph: &Glyph, _advance| {
-                    // Render glyph.
-                    context.transform_stack.push(transform);
-                    context
-                        .renderer
-                        .render_shape(glyph.shape, context.transform_stack.transform());
-                    context.transform_stack.pop();
-                },
-            );
+
+            // Selected text is drawn in white, over the selection highlight.
+            let (selection_start, selection_end) = match (edit_text.selection, lbox.text_range()) {
+                (Some(selection), Some((start, end))) if !selection.is_caret() => {
+                    let clamp = |i: usize| i.max(start).min(end) - start;
+                    (
+                        clamp(edit_text.to_layout_index(selection.start())),
+                        clamp(edit_text.to_layout_index(selection.end())),
+                    )
+                }
+                _ => (0, 0),
+            };
+            let segments = [
+                (&text[..selection_start], color.clone()),
+                (
+                    &text[selection_start..selection_end],
+                    swf::Color::from_rgb(0xFFFFFF, color.a),
+                ),
+                (&text[selection_end..], color),
+            ];
+
+            let mut x = Twips::new(0);
+            for (segment, color) in segments.iter().filter(|(s, _)| !s.is_empty()) {
+                let mut transform = self.text_transform(color.clone(), baseline_adjustmnet);
+                transform.matrix.tx = x;
+                font.evaluate(
+                    segment,
+                    transform,
+                    params,
+                    |transform, glyph: &Glyph, _advance| {
+                        // Render glyph.
+                        context.transform_stack.push(transform);
+                        context
+                            .renderer
+                            .render_shape(glyph.shape, context.transform_stack.transform());
+                        context.transform_stack.pop();
+                    },
+                );
+                x += font.measure(segment, params, false).0;
+            }
         }
 
         if let Some(drawing) = lbox.as_renderable_drawing() {
@@ -949,6 +1591,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         write.bounds.set_width(Twips::from_pixels(value));
         write.base.set_transformed_by_script(true);
+        write.clamp_scroll();
 
         drop(write);
         self.redraw_border(gc_context);
@@ -963,6 +1606,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         write.bounds.set_height(Twips::from_pixels(value));
         write.base.set_transformed_by_script(true);
+        write.clamp_scroll();
 
         drop(write);
         self.redraw_border(gc_context);
@@ -993,22 +1637,38 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         self.0.read().drawing.render(context);
 
+        // Text that overflows the text field is clipped to its bounds.
+        let is_clipped = self.0.read().is_clipped();
+        if is_clipped {
+            context.renderer.push_mask();
+            self.0.read().clip_drawing.render(context);
+            context.renderer.activate_mask();
+        }
+
         // TODO: Where does this come from? How is this different than INTERNAL_PADDING? Does this apply to y as well?
         // If this is actually right, offset the border in `redraw_border` instead of doing an extra push.
+        let (scroll_x, scroll_y) = self.0.read().scroll_offset();
         context.transform_stack.push(&Transform {
             matrix: Matrix {
-                tx: Twips::from_pixels(Self::INTERNAL_PADDING),
-                ty: Twips::from_pixels(Self::INTERNAL_PADDING),
+                tx: Twips::from_pixels(Self::INTERNAL_PADDING) - scroll_x,
+                ty: Twips::from_pixels(Self::INTERNAL_PADDING) - scroll_y,
                 ..Default::default()
             },
             ..Default::default()
         });
 
+        self.0.read().selection_drawing.render(context);
+
         for layout_box in self.0.read().layout.iter() {
             self.render_layout_box(context, layout_box);
         }
 
         context.transform_stack.pop();
+
+        if is_clipped {
+            context.renderer.pop_mask();
+        }
+
         context.transform_stack.pop();
         context.transform_stack.pop();
     }
@@ -1017,7 +1677,98 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         false
     }
 
+    fn mouse_pick(
+        &self,
+        _avm: &mut Avm1<'gc>,
+        _context: &mut UpdateContext<'_, 'gc, '_>,
+        self_node: DisplayObject<'gc>,
+        point: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        // Only selectable text fields respond to the mouse.
+        if self.visible() && self.is_selectable() && self.world_bounds().contains(point) {
+            Some(self_node)
+        } else {
+            None
+        }
+    }
+
+    fn mouse_cursor(&self) -> MouseCursor {
+        MouseCursor::IBeam
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.is_selectable()
+    }
+
+    fn on_focus_changed(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        focused: bool,
+        _other: Option<DisplayObject<'gc>>,
+    ) {
+        // Gaining focus selects all of the text.
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.selection = if focused {
+            Some(TextSelection::for_range(
+                0,
+                edit_text.text_spans.text().len(),
+            ))
+        } else {
+            None
+        };
+        edit_text.is_selecting_with_mouse = false;
+        edit_text.redraw_selection();
+    }
+
+    fn handle_clip_event(
+        &self,
+        _avm: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: ClipEvent,
+    ) -> ClipEventResult {
+        match event {
+            // Clicking places the caret, and dragging selects text from there.
+            ClipEvent::Press if self.is_selectable() => {
+                let index = self.screen_position_to_index(*context.mouse_position);
+                let mut edit_text = self.0.write(context.gc_context);
+                if edit_text.selection.is_some() {
+                    edit_text.selection = Some(TextSelection::for_position(index));
+                    edit_text.is_selecting_with_mouse = true;
+                    edit_text.redraw_selection();
+                }
+                ClipEventResult::Handled
+            }
+            ClipEvent::MouseMove => {
+                if self.0.read().is_selecting_with_mouse {
+                    let index = self.screen_position_to_index(*context.mouse_position);
+                    if let Some(selection) = self.selection() {
+                        self.set_selection(
+                            Some(TextSelection::for_range(selection.from(), index)),
+                            context.gc_context,
+                        );
+                        self.scroll_to_caret(context);
+                    }
+                }
+                self.update_hovered_link(context);
+                ClipEventResult::NotHandled
+            }
+            ClipEvent::MouseUp | ClipEvent::Release => {
+                self.0.write(context.gc_context).is_selecting_with_mouse = false;
+                ClipEventResult::NotHandled
+            }
+            _ => ClipEventResult::NotHandled,
+        }
+    }
+
     fn unload(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // Removed text fields lose focus.
+        let focus_tracker = context.focus_tracker;
+        if let Some(focus) = focus_tracker.get() {
+            if DisplayObject::ptr_eq(focus, (*self).into()) {
+                focus_tracker.set(None, context);
+            }
+        }
+
         // Unbind any display objects bound to this text.
         if let Some(stage_object) = self.0.write(context.gc_context).bound_stage_object.take() {
             stage_object.clear_text_field_binding(context.gc_context, *self);
@@ -1039,6 +1790,596 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     }
 }
 
+impl<'gc> EditTextData<'gc> {
+    /// Converts a position in the text into a position in the laid-out text.
+    ///
+    /// These only differ for password fields, where every character is laid
+    /// out as a single-byte asterisk.
+    fn to_layout_index(&self, index: usize) -> usize {
+        if self.is_password {
+            self.text_spans.text()[..index].chars().count()
+        } else {
+            index
+        }
+    }
+
+    /// Converts a position in the laid-out text into a position in the text.
+    fn from_layout_index(&self, layout_index: usize) -> usize {
+        if self.is_password {
+            let text = self.text_spans.text();
+            text.char_indices()
+                .nth(layout_index)
+                .map(|(i, _)| i)
+                .unwrap_or_else(|| text.len())
+        } else {
+            layout_index
+        }
+    }
+
+    /// Returns the position in the laid-out text that is closest to the
+    /// given point, relative to the text area.
+    fn layout_index_at(&self, (x, y): (Twips, Twips)) -> usize {
+        let text = displayed_text(self.text_spans.text(), self.is_password);
+        let distance = |position: Twips, min: Twips, max: Twips| {
+            if position < min {
+                min - position
+            } else if position >= max {
+                position - max
+            } else {
+                Twips::new(0)
+            }
+        };
+
+        let mut is_below_text = true;
+        let mut nearest: Option<((Twips, Twips), BoxBounds<Twips>, _, _, _, _)> = None;
+        for lbox in &self.layout {
+            if let (Some((start, end)), Some((_, _, font, params, _))) =
+                (lbox.text_range(), lbox.as_renderable_text(&text))
+            {
+                let bounds = lbox.bounds();
+                is_below_text &= y >= bounds.extent_y();
+                let key = (
+                    distance(y, bounds.offset_y(), bounds.extent_y()),
+                    distance(x, bounds.offset_x(), bounds.extent_x()),
+                );
+                if nearest
+                    .as_ref()
+                    .map(|(nearest_key, ..)| key < *nearest_key)
+                    .unwrap_or(true)
+                {
+                    nearest = Some((key, bounds, start, end, font, params));
+                }
+            }
+        }
+
+        let (bounds, start, end, font, params) = match nearest {
+            _ if is_below_text && !self.layout.is_empty() => return text.len(),
+            Some((_, bounds, start, end, font, params)) => (bounds, start, end, font, params),
+            None => return 0,
+        };
+
+        // Find the character boundary closest to the point.
+        let x = x - bounds.offset_x();
+        let mut result = start;
+        let mut result_distance = None;
+        for (i, _) in text[start..end]
+            .char_indices()
+            .map(|(i, c)| (start + i, c))
+            .chain(std::iter::once((end, ' ')))
+        {
+            let width = font.measure(&text[start..i], params, false).0;
+            let d = if width > x { width - x } else { x - width };
+            if result_distance.map(|rd| d < rd).unwrap_or(true) {
+                result = i;
+                result_distance = Some(d);
+            }
+        }
+        result
+    }
+
+    /// Returns the position in the text of the character under the given
+    /// point of the laid-out text, if there is one.
+    fn char_index_at(&self, (x, y): (Twips, Twips)) -> Option<usize> {
+        let text = displayed_text(self.text_spans.text(), self.is_password);
+        for lbox in &self.layout {
+            if let (Some((start, end)), Some((_, _, font, params, _))) =
+                (lbox.text_range(), lbox.as_renderable_text(&text))
+            {
+                let bounds = lbox.bounds();
+                if x < bounds.offset_x()
+                    || x >= bounds.extent_x()
+                    || y < bounds.offset_y()
+                    || y >= bounds.extent_y()
+                {
+                    continue;
+                }
+
+                let x = x - bounds.offset_x();
+                for (i, c) in text[start..end].char_indices() {
+                    let char_end = start + i + c.len_utf8();
+                    if x < font.measure(&text[start..char_end], params, false).0 {
+                        return Some(self.from_layout_index(start + i));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the position and height of a caret placed at the given
+    /// position in the laid-out text, relative to the text area.
+    fn caret_bounds(&self, layout_index: usize) -> (Twips, Twips, Twips) {
+        let text = displayed_text(self.text_spans.text(), self.is_password);
+
+        let mut preceding = None;
+        for lbox in &self.layout {
+            if let (Some((start, end)), Some((_, _, font, params, _))) =
+                (lbox.text_range(), lbox.as_renderable_text(&text))
+            {
+                let bounds = lbox.bounds();
+                if start <= layout_index && layout_index < end {
+                    let x = font.measure(&text[start..layout_index], params, false).0;
+                    return (bounds.offset_x() + x, bounds.offset_y(), bounds.height());
+                } else if end <= layout_index {
+                    preceding = Some((bounds, end));
+                }
+            }
+        }
+
+        match preceding {
+            // Newlines after the last box move the caret onto later lines.
+            Some((bounds, end)) => {
+                let newlines = text[end..layout_index].matches('\n').count() as i32;
+                if newlines > 0 {
+                    (
+                        Twips::new(0),
+                        bounds.offset_y() + bounds.height() * newlines,
+                        bounds.height(),
+                    )
+                } else {
+                    (bounds.extent_x(), bounds.offset_y(), bounds.height())
+                }
+            }
+            None => {
+                let size = self.text_spans.default_format().size.unwrap_or(12.0);
+                (Twips::new(0), Twips::new(0), Twips::from_pixels(size))
+            }
+        }
+    }
+
+    /// Returns the position in the text on the line above or below the
+    /// caret, used when moving the caret with the arrow keys.
+    fn vertical_neighbour(&self, caret: usize, up: bool) -> usize {
+        let (x, y, height) = self.caret_bounds(self.to_layout_index(caret));
+        let target_y = if up {
+            y - height / 2
+        } else {
+            y + height + height / 2
+        };
+        if target_y < Twips::new(0) {
+            return 0;
+        }
+        self.from_layout_index(self.layout_index_at((x, target_y)))
+    }
+
+    /// Lowers the HTML document into text spans, styled by the style sheet.
+    fn lower_document(&mut self) {
+        let style_sheet = self
+            .style_sheet
+            .map(|style_sheet| style_sheet.style_sheet().clone())
+            .unwrap_or_default();
+        self.link_ranges = self.text_spans.lower_from_css(
+            self.document,
+            &style_sheet,
+            self.hovered_link,
+            self.is_condense_white,
+        );
+    }
+
+    /// Regenerates the HTML document after the text spans were changed
+    /// directly, so that restyling the text doesn't undo the change.
+    fn text_changed(&mut self, gc_context: MutationContext<'gc, '_>) {
+        self.link_ranges.clear();
+        self.hovered_link = None;
+        if self.is_html {
+            self.document = self.text_spans.raise_to_html(gc_context);
+        }
+    }
+
+    /// Returns the size of the area that text is displayed in.
+    ///
+    /// Autosized text fields always grow to fit their text.
+    fn visible_size(&self) -> (Twips, Twips) {
+        let padding = Twips::from_pixels(EditText::INTERNAL_PADDING * 2.0);
+        let width = self.bounds.width() - padding;
+        let height = self.bounds.height() - padding;
+        match self.autosize {
+            AutoSizeMode::None => (width, height),
+            _ => (
+                width.max(self.intrinsic_bounds.width()),
+                height.max(self.intrinsic_bounds.height()),
+            ),
+        }
+    }
+
+    /// Returns the highest value of `scroll`, which shows the last line of
+    /// text at the bottom of the text field.
+    fn maxscroll(&self) -> usize {
+        let (_, height) = self.visible_size();
+        let last_line_extent = match self.lines.last() {
+            Some(line) => line.bounds().extent_y(),
+            None => return 1,
+        };
+        self.lines
+            .iter()
+            .position(|line| last_line_extent - line.bounds().offset_y() <= height)
+            .unwrap_or(self.lines.len() - 1)
+            + 1
+    }
+
+    /// Returns the last line that is entirely visible, starting from 1.
+    ///
+    /// At least the line at the top of the text field is always counted.
+    fn bottom_scroll(&self) -> usize {
+        let (_, height) = self.visible_size();
+        let (_, top) = self.scroll_offset();
+        let num_visible = self
+            .lines
+            .iter()
+            .skip(self.scroll - 1)
+            .take_while(|line| line.bounds().extent_y() - top <= height)
+            .count();
+        self.scroll - 1 + num_visible.max(1)
+    }
+
+    /// Returns the highest value of `hscroll`.
+    fn maxhscroll(&self) -> Twips {
+        let (width, _) = self.visible_size();
+        (self.intrinsic_bounds.width() - width).max(Twips::new(0))
+    }
+
+    /// Returns how far the laid-out text is moved left and up by scrolling.
+    fn scroll_offset(&self) -> (Twips, Twips) {
+        let y = self
+            .lines
+            .get(self.scroll - 1)
+            .map(|line| line.bounds().offset_y())
+            .unwrap_or_else(|| Twips::new(0));
+        (Twips::from_pixels(self.hscroll), y)
+    }
+
+    fn scroll_properties(&self) -> ScrollProperties {
+        (
+            self.scroll,
+            self.maxscroll(),
+            self.hscroll,
+            self.maxhscroll(),
+        )
+    }
+
+    /// Keeps the scroll position within the text.
+    fn clamp_scroll(&mut self) {
+        self.scroll = self.scroll.max(1).min(self.maxscroll());
+        self.hscroll = self
+            .hscroll
+            .max(0.0)
+            .min(round_down_to_pixel(self.maxhscroll()).to_pixels());
+    }
+
+    /// Scrolls the text so that the caret is visible.
+    fn scroll_to_caret(&mut self) {
+        let selection = match self.selection {
+            Some(selection) => selection,
+            None => return,
+        };
+        let (x, y, _) = self.caret_bounds(self.to_layout_index(selection.to()));
+        let (width, height) = self.visible_size();
+
+        let line = self
+            .lines
+            .iter()
+            .rposition(|line| line.bounds().offset_y() <= y)
+            .unwrap_or(0);
+        if line + 1 < self.scroll {
+            self.scroll = line + 1;
+        } else if let Some(extent) = self.lines.get(line).map(|line| line.bounds().extent_y()) {
+            while self.scroll <= line
+                && extent - self.lines[self.scroll - 1].bounds().offset_y() > height
+            {
+                self.scroll += 1;
+            }
+        }
+
+        let (hscroll, _) = self.scroll_offset();
+        if x < hscroll {
+            self.hscroll = round_down_to_pixel(x).to_pixels();
+        } else if x > hscroll + width {
+            self.hscroll = (x - width).to_pixels().ceil();
+        }
+
+        self.clamp_scroll();
+    }
+
+    /// Whether the text overflows the text field, and so has to be clipped.
+    fn is_clipped(&self) -> bool {
+        self.maxscroll() > 1 || self.maxhscroll() > Twips::new(0)
+    }
+
+    /// Redraws the selection highlight, or the caret if no text is selected.
+    fn redraw_selection(&mut self) {
+        self.selection_drawing.clear();
+
+        let selection = match self.selection {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        let rects = if selection.is_caret() {
+            if !self.is_editable {
+                return;
+            }
+            let (x, y, height) = self.caret_bounds(self.to_layout_index(selection.start()));
+            vec![(x, y, Twips::new(20), height)]
+        } else {
+            let text = displayed_text(self.text_spans.text(), self.is_password);
+            let selection_start = self.to_layout_index(selection.start());
+            let selection_end = self.to_layout_index(selection.end());
+            self.layout
+                .iter()
+                .filter_map(|lbox| {
+                    let (start, end) = lbox.text_range()?;
+                    let (_, _, font, params, _) = lbox.as_renderable_text(&text)?;
+                    let (from, to) = (selection_start.max(start), selection_end.min(end));
+                    if from >= to {
+                        return None;
+                    }
+                    let bounds = lbox.bounds();
+                    let x = font.measure(&text[start..from], params, false).0;
+                    let width = font.measure(&text[from..to], params, false).0;
+                    Some((
+                        bounds.offset_x() + x,
+                        bounds.offset_y(),
+                        width,
+                        bounds.height(),
+                    ))
+                })
+                .collect()
+        };
+
+        self.selection_drawing
+            .set_fill_style(Some(swf::FillStyle::Color(Color::from_rgb(0, 0xFF))));
+        for (x, y, width, height) in rects {
+            self.selection_drawing
+                .draw_command(DrawCommand::MoveTo { x, y });
+            self.selection_drawing
+                .draw_command(DrawCommand::LineTo { x: x + width, y });
+            self.selection_drawing.draw_command(DrawCommand::LineTo {
+                x: x + width,
+                y: y + height,
+            });
+            self.selection_drawing
+                .draw_command(DrawCommand::LineTo { x, y: y + height });
+            self.selection_drawing
+                .draw_command(DrawCommand::LineTo { x, y });
+        }
+    }
+}
+
+/// The values of the `scroll`, `maxscroll`, `hscroll` and `maxhscroll`
+/// properties of a text field, used to tell when they change.
+type ScrollProperties = (usize, usize, f64, Twips);
+
+/// Returns the format spans that are laid out for the given text, which are
+/// masked with asterisks in password fields.
+fn displayed_spans(text_spans: &FormatSpans, is_password: bool) -> Cow<'_, FormatSpans> {
+    if is_password {
+        Cow::Owned(text_spans.to_password_masked())
+    } else {
+        Cow::Borrowed(text_spans)
+    }
+}
+
+/// Returns the text that is laid out for the given text, which is masked
+/// with asterisks in password fields.
+fn displayed_text(text: &str, is_password: bool) -> Cow<'_, str> {
+    if is_password {
+        Cow::Owned("*".repeat(text.chars().count()))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Returns the character boundary before the given position in the text.
+fn previous_char_boundary(text: &str, index: usize) -> usize {
+    text[..index]
+        .char_indices()
+        .next_back()
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Returns the character boundary after the given position in the text.
+fn next_char_boundary(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map(|c| index + c.len_utf8())
+        .unwrap_or(index)
+}
+
+/// A range of selected text in an `EditText`.
+///
+/// `from` is where the selection was started, and `to` is where it ends and
+/// where the caret is placed. If both are equal, no text is selected and this
+/// is just the position of the caret.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Collect)]
+#[collect(require_static)]
+pub struct TextSelection {
+    from: usize,
+    to: usize,
+}
+
+impl TextSelection {
+    pub fn for_position(position: usize) -> Self {
+        Self {
+            from: position,
+            to: position,
+        }
+    }
+
+    pub fn for_range(from: usize, to: usize) -> Self {
+        Self { from, to }
+    }
+
+    /// The position where the selection was started.
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// The position of the caret.
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    /// The lowest position of the selection.
+    pub fn start(&self) -> usize {
+        self.from.min(self.to)
+    }
+
+    /// The highest position of the selection.
+    pub fn end(&self) -> usize {
+        self.from.max(self.to)
+    }
+
+    /// Returns whether no text is selected.
+    pub fn is_caret(&self) -> bool {
+        self.from == self.to
+    }
+
+    /// Clamps the selection to lie within the given text, on character
+    /// boundaries.
+    pub fn clamped(self, text: &str) -> Self {
+        let clamp = |mut i: usize| {
+            i = i.min(text.len());
+            while !text.is_char_boundary(i) {
+                i -= 1;
+            }
+            i
+        };
+        Self {
+            from: clamp(self.from),
+            to: clamp(self.to),
+        }
+    }
+}
+
+/// The characters that the user is allowed to enter into an `EditText`.
+///
+/// This is parsed from the AVM1 `TextField.restrict` syntax, such as
+/// `"A-Z0-9"` or `"^aeiou"`: `-` defines a range of characters, `^`
+/// switches between allowed and disallowed characters, and `\` escapes the
+/// following character.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct EditTextRestrict {
+    /// The original restriction string, or `None` if all characters are
+    /// allowed.
+    value: Option<String>,
+
+    /// Ranges of characters, and whether they're allowed. Later ranges take
+    /// precedence over earlier ones.
+    ranges: Vec<(char, char, bool)>,
+
+    /// Whether characters that aren't in any range are allowed.
+    allow_others: bool,
+}
+
+impl EditTextRestrict {
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns whether the given character can be entered.
+    pub fn is_allowed(&self, c: char) -> bool {
+        if self.value.is_none() {
+            return true;
+        }
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(start, end, _)| *start <= c && c <= *end)
+            .map(|(_, _, allowed)| *allowed)
+            .unwrap_or(self.allow_others)
+    }
+
+    /// Returns the character to enter for the given typed character.
+    ///
+    /// If the character itself isn't allowed, it is entered in the other case
+    /// if that is allowed.
+    pub fn to_allowed(&self, c: char) -> Option<char> {
+        if self.is_allowed(c) {
+            return Some(c);
+        }
+        let other_case: Vec<char> = if c.is_lowercase() {
+            c.to_uppercase().collect()
+        } else {
+            c.to_lowercase().collect()
+        };
+        match other_case[..] {
+            [other] if self.is_allowed(other) => Some(other),
+            _ => None,
+        }
+    }
+}
+
+impl From<Option<&str>> for EditTextRestrict {
+    fn from(value: Option<&str>) -> Self {
+        let value = match value {
+            Some(value) => value,
+            None => return Self::default(),
+        };
+
+        let mut ranges = Vec::new();
+        let mut allowed = true;
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            let start = match c {
+                '^' => {
+                    allowed = !allowed;
+                    continue;
+                }
+                '\\' => match chars.next() {
+                    Some(c) => c,
+                    None => break,
+                },
+                c => c,
+            };
+
+            let mut end = start;
+            if chars.peek() == Some(&'-') {
+                let mut rest = chars.clone();
+                rest.next();
+                let range_end = match rest.next() {
+                    Some('\\') => rest.next(),
+                    Some('^') | None => None,
+                    c => c,
+                };
+                if let Some(range_end) = range_end {
+                    end = range_end;
+                    chars = rest;
+                }
+            }
+            ranges.push((start.min(end), start.max(end), allowed));
+        }
+
+        Self {
+            value: Some(value.to_string()),
+            ranges,
+            allow_others: value.starts_with('^'),
+        }
+    }
+}
+
 /// Static data shared between all instances of a text object.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -3,7 +3,9 @@ use crate::backend::render::ShapeHandle;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, GcCell};
+use std::sync::Arc;
 
 #[derive(Clone, Debug, Collect, Copy)]
 #[collect(no_drop)]
@@ -16,10 +18,16 @@ pub struct GraphicData<'gc> {
 }
 
 impl<'gc> Graphic<'gc> {
-    pub fn from_swf_tag(context: &mut UpdateContext<'_, 'gc, '_>, swf_shape: &swf::Shape) -> Self {
+    /// Creates a graphic from a DefineShape tag of the given movie.
+    pub fn from_swf_tag(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        swf_shape: &swf::Shape,
+        movie: Arc<SwfMovie>,
+    ) -> Self {
+        let library = context.library.library_for_movie_mut(movie);
         let static_data = GraphicStatic {
             id: swf_shape.id,
-            render_handle: context.renderer.register_shape(swf_shape.into()),
+            render_handle: context.renderer.register_shape(swf_shape.into(), library),
             bounds: swf_shape.shape_bounds.clone().into(),
         };
         Graphic(GcCell::allocate(
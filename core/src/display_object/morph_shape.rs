@@ -1,5 +1,5 @@
 use crate::avm1::Avm1;
-use crate::backend::render::{RenderBackend, ShapeHandle};
+use crate::backend::render::{BitmapSource, RenderBackend, ShapeHandle};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
@@ -104,7 +104,11 @@ pub struct MorphShapeStatic {
 }
 
 impl MorphShapeStatic {
-    pub fn from_swf_tag(renderer: &mut dyn RenderBackend, swf_tag: &swf::DefineMorphShape) -> Self {
+    pub fn from_swf_tag(
+        renderer: &mut dyn RenderBackend,
+        bitmap_source: &dyn BitmapSource,
+        swf_tag: &swf::DefineMorphShape,
+    ) -> Self {
         let mut morph_shape = Self {
             id: swf_tag.id,
             start: swf_tag.start.clone(),
@@ -112,12 +116,17 @@ impl MorphShapeStatic {
             frames: fnv::FnvHashMap::default(),
         };
         // Pre-register the start and end states.
-        morph_shape.register_ratio(renderer, 0);
-        morph_shape.register_ratio(renderer, 65535);
+        morph_shape.register_ratio(renderer, bitmap_source, 0);
+        morph_shape.register_ratio(renderer, bitmap_source, 65535);
         morph_shape
     }
 
-    pub fn register_ratio(&mut self, renderer: &mut dyn RenderBackend, ratio: u16) {
+    pub fn register_ratio(
+        &mut self,
+        renderer: &mut dyn RenderBackend,
+        bitmap_source: &dyn BitmapSource,
+        ratio: u16,
+    ) {
         if self.frames.contains_key(&ratio) {
             // Already registered.
             return;
@@ -249,7 +258,7 @@ impl MorphShapeStatic {
         };
 
         let frame = Frame {
-            shape: renderer.register_shape((&shape).into()),
+            shape: renderer.register_shape((&shape).into(), bitmap_source),
             bounds: bounds.into(),
         };
         self.frames.insert(ratio, frame);
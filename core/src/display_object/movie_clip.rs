@@ -1,12 +1,14 @@
 //! `MovieClip` display object and support code.
-use crate::avm1::{Avm1, Object, StageObject, TObject, Value};
+use crate::avm1::{Avm1, AvmString, Object, StageObject, TObject, Value};
 use crate::backend::audio::AudioStreamHandle;
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::bitmap_data::BitmapData;
 use crate::character::Character;
 use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::{
-    Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic, TDisplayObject, Text,
+    Bitmap, BitmapTag, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic,
+    TDisplayObject, Text, Video,
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
@@ -48,7 +50,7 @@ pub struct MovieClipData<'gc> {
     has_button_clip_event: bool,
     flags: EnumSet<MovieClipFlags>,
     avm1_constructor: Option<Object<'gc>>,
-    drawing: Drawing,
+    drawing: Drawing<'gc>,
 }
 
 impl<'gc> MovieClip<'gc> {
@@ -132,6 +134,24 @@ impl<'gc> MovieClip<'gc> {
             .replace_with_movie(gc_context, movie)
     }
 
+    /// Define the parameters (flashvars) of this clip's movie as variables
+    /// on the clip.
+    ///
+    /// This should be done once the clip's AVM1 object has been created, and
+    /// before any of its frames run.
+    pub fn define_movie_parameters(self, gc_context: MutationContext<'gc, '_>) {
+        if let (Some(movie), Value::Object(object)) = (self.movie(), self.object()) {
+            for (key, value) in movie.parameters() {
+                object.define_value(
+                    gc_context,
+                    key,
+                    AvmString::new(gc_context, value.clone()).into(),
+                    EnumSet::empty(),
+                );
+            }
+        }
+    }
+
     pub fn preload(
         self,
         avm1: &mut Avm1<'gc>,
@@ -216,7 +236,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_font_3(context, reader),
-                TagCode::DefineFont4 => unimplemented!(),
+                TagCode::DefineFont4 => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_4(context, reader),
                 TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
                     context,
                     reader,
@@ -264,8 +287,13 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_text(context, reader, 2),
+                TagCode::DefineVideoStream => self
+                    .0
+                    .write(context.gc_context)
+                    .define_video_stream(context, reader),
                 TagCode::DoInitAction => self.do_init_action(avm1, context, reader, tag_len),
                 TagCode::DoAbc => self.do_abc(context, reader, tag_len),
+                TagCode::SymbolClass => self.symbol_class(context, reader),
                 TagCode::ExportAssets => self
                     .0
                     .write(context.gc_context)
@@ -346,6 +374,10 @@ impl<'gc> MovieClip<'gc> {
                         tag_len,
                     )
                 }
+                TagCode::VideoFrame => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_video_frame(context, reader),
                 _ => Ok(()),
             }
         };
@@ -437,6 +469,31 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn symbol_class(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&[u8]>,
+    ) -> DecodeResult {
+        let symbols = reader.read_symbol_class()?;
+        let movie = self.movie().unwrap();
+        for symbol in symbols {
+            context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .register_avm2_class(symbol.id, &symbol.class_name);
+
+            // Character ID 0 links the main timeline to the document class,
+            // which is constructed once the movie's ABC code has loaded.
+            if symbol.id == 0 {
+                context
+                    .action_queue
+                    .queue_actions(self.into(), ActionType::Avm2Construct, false);
+            }
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub fn playing(self) -> bool {
         self.0.read().playing()
@@ -593,6 +650,121 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Returns the number of children of this clip.
+    pub fn num_children(self) -> usize {
+        self.0.read().children.len()
+    }
+
+    /// Returns the child at the given position in depth order.
+    pub fn child_by_index(self, index: usize) -> Option<DisplayObject<'gc>> {
+        self.0.read().children.values().nth(index).copied()
+    }
+
+    /// Returns the position of the given child in depth order.
+    pub fn child_index(self, child: DisplayObject<'gc>) -> Option<usize> {
+        self.0
+            .read()
+            .children
+            .values()
+            .position(|c| DisplayObject::ptr_eq(*c, child))
+    }
+
+    /// Moves a child to the given position in depth order.
+    ///
+    /// The depths already in use by this clip are kept, and redistributed
+    /// among the children in their new order.
+    pub fn set_child_index(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
+        index: usize,
+    ) {
+        let mut parent = self.0.write(context.gc_context);
+        let depths: Vec<Depth> = parent.children.keys().copied().collect();
+        let mut children: Vec<DisplayObject<'gc>> = parent.children.values().copied().collect();
+
+        if let Some(old_index) = children
+            .iter()
+            .position(|c| DisplayObject::ptr_eq(*c, child))
+        {
+            children.remove(old_index);
+            children.insert(index.min(children.len()), child);
+        }
+
+        parent.children.clear();
+        for (depth, child) in depths.into_iter().zip(children) {
+            if child.depth() != depth {
+                child.set_depth(context.gc_context, depth);
+                child.set_transformed_by_script(context.gc_context, true);
+            }
+            parent.children.insert(depth, child);
+        }
+    }
+
+    /// Detaches a child from this clip without unloading it, so that it may
+    /// be placed on a display list again.
+    pub fn detach_child(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        mut child: DisplayObject<'gc>,
+    ) {
+        debug_assert!(DisplayObject::ptr_eq(
+            child.parent().unwrap(),
+            (*self).into()
+        ));
+        let mut parent = self.0.write(context.gc_context);
+        if let Some(child) = parent.children.remove(&child.depth()) {
+            parent.unlink_child_from_exec_list(context, child);
+        }
+        drop(parent);
+
+        child.set_parent(context.gc_context, None);
+        child.set_prev_sibling(context.gc_context, None);
+        child.set_next_sibling(context.gc_context, None);
+    }
+
+    /// Finds the innermost clip under a point on the stage, as the target of
+    /// AS3 mouse events.
+    ///
+    /// Other display objects are not interactive, so a hit on them is
+    /// attributed to the clip that contains them.
+    pub fn avm2_mouse_pick(self, point: (Twips, Twips)) -> Option<DisplayObject<'gc>> {
+        if !self.visible() {
+            return None;
+        }
+
+        let children: Vec<DisplayObject<'gc>> =
+            self.0.read().children.values().rev().copied().collect();
+        for child in children {
+            if let Some(clip) = child.as_movie_clip() {
+                if let Some(result) = clip.avm2_mouse_pick(point) {
+                    return Some(result);
+                }
+            } else if child.visible() && child.world_bounds().contains(point) {
+                return Some(self.into());
+            }
+        }
+
+        None
+    }
+
+    /// Whether this clip acts as a button, because it has button event
+    /// handlers such as `on(press)` or `onRelease`.
+    pub fn is_button_mode(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> bool {
+        if self.0.read().has_button_clip_event {
+            return true;
+        }
+
+        let object = self.object().coerce_to_object(activation, context);
+        ClipEvent::BUTTON_EVENT_METHODS
+            .iter()
+            .any(|handler| object.has_property(activation, context, handler))
+    }
+
     /// Returns an iterator of AVM1 `DoAction` blocks on the given frame number.
     /// Used by the AVM `Call` action.
     pub fn actions_on_frame(
@@ -644,6 +816,27 @@ impl<'gc> MovieClip<'gc> {
         mc.drawing.set_fill_style(style);
     }
 
+    /// Starts a fill with the pixels of a `BitmapData`.
+    ///
+    /// The `matrix` maps the bitmap into the clip, in twips.
+    pub fn set_bitmap_fill_style(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        bitmap_data: GcCell<'gc, BitmapData>,
+        matrix: swf::Matrix,
+        is_smoothed: bool,
+        is_repeating: bool,
+    ) {
+        let mut mc = self.0.write(context.gc_context);
+        let id = mc.drawing.add_bitmap(bitmap_data);
+        mc.drawing.set_fill_style(Some(FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed,
+            is_repeating,
+        }));
+    }
+
     pub fn clear(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.clear();
@@ -785,7 +978,7 @@ impl<'gc> MovieClip<'gc> {
                     }
                 }
                 // Run first frame.
-                child.apply_place_object(context.gc_context, place_object);
+                child.apply_place_object(context, place_object);
                 child.post_instantiation(avm, context, child, None, false);
                 child.run_frame(avm, context);
             }
@@ -956,7 +1149,7 @@ impl<'gc> MovieClip<'gc> {
                 // If it's a rewind, we removed any dead children above, so we always
                 // modify the previous child.
                 Some(mut prev_child) if params.id() == 0 || is_rewind => {
-                    prev_child.apply_place_object(context.gc_context, &params.place_object);
+                    prev_child.apply_place_object(context, &params.place_object);
                 }
                 _ => {
                     if let Some(mut child) = clip.instantiate_child(
@@ -1075,10 +1268,6 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     ) -> Option<DisplayObject<'gc>> {
         if self.visible() {
             if self.world_bounds().contains(point) {
-                if self.0.read().has_button_clip_event {
-                    return Some(self_node);
-                }
-
                 let mut activation = Activation::from_nothing(
                     avm,
                     ActivationIdentifier::root("[Mouse Pick]"),
@@ -1087,12 +1276,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                     context.gc_context,
                     *context.levels.get(&0).unwrap(),
                 );
-                let object = self.object().coerce_to_object(&mut activation, context);
-
-                if ClipEvent::BUTTON_EVENT_METHODS
-                    .iter()
-                    .any(|handler| object.has_property(&mut activation, context, handler))
-                {
+                if self.is_button_mode(&mut activation, context) {
                     return Some(self_node);
                 }
             }
@@ -1110,6 +1294,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         None
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn handle_clip_event(
         &self,
         avm: &mut Avm1<'gc>,
@@ -1141,6 +1329,25 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     ) {
         self.set_default_instance_name(context);
 
+        // Timeline symbols linked to an AS3 class have their AS3 object
+        // constructed after placement. Script-created clips are constructed
+        // by their class, and the main timeline is queued by the `SymbolClass`
+        // tag itself.
+        if !instantiated_from_avm && self.id() != 0 {
+            let is_linked = context
+                .library
+                .library_for_movie(self.movie().unwrap())
+                .and_then(|library| library.avm2_class_name(self.id()))
+                .is_some();
+            if is_linked {
+                context.action_queue.queue_actions(
+                    display_object,
+                    ActionType::Avm2Construct,
+                    false,
+                );
+            }
+        }
+
         if self.0.read().object.is_none() {
             // If we are running within the AVM, this must be an immediate action.
             // If we are not, then this must be queued to be ran first-thing
@@ -1277,6 +1484,7 @@ unsafe impl<'gc> Collect for MovieClipData<'gc> {
         self.static_data.trace(cc);
         self.object.trace(cc);
         self.avm1_constructor.trace(cc);
+        self.drawing.trace(cc);
     }
 }
 
@@ -1385,6 +1593,17 @@ impl<'gc> MovieClipData<'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         mut child: DisplayObject<'gc>,
+    ) {
+        self.unlink_child_from_exec_list(context, child);
+
+        // Flag child as removed.
+        child.unload(context);
+    }
+
+    fn unlink_child_from_exec_list(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        child: DisplayObject<'gc>,
     ) {
         // Remove from children linked list.
         let prev = child.prev_sibling();
@@ -1400,8 +1619,6 @@ impl<'gc> MovieClipData<'gc> {
                 self.set_first_child(context.gc_context, next);
             }
         }
-        // Flag child as removed.
-        child.unload(context);
     }
 
     /// Handles a PlaceObject tag when running a goto action.
@@ -1587,17 +1804,19 @@ impl<'gc, 'a> MovieClipData<'gc> {
         let bitmap_info = context
             .renderer
             .register_bitmap_png(&define_bits_lossless)?;
+        let id = define_bits_lossless.id;
         let bitmap = crate::display_object::Bitmap::new(
             context,
-            define_bits_lossless.id,
+            id,
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            BitmapTag::DefineBitsLossless(define_bits_lossless),
         );
         context
             .library
             .library_for_movie_mut(self.movie())
-            .register_character(define_bits_lossless.id, Character::Bitmap(bitmap));
+            .register_character(id, Character::Bitmap(bitmap));
         Ok(())
     }
 
@@ -1611,7 +1830,11 @@ impl<'gc, 'a> MovieClipData<'gc> {
     ) -> DecodeResult {
         // Certain backends may have to preload morph shape frames, so defer registering until the end.
         let swf_shape = reader.read_define_morph_shape(version)?;
-        let morph_shape = MorphShapeStatic::from_swf_tag(context.renderer, &swf_shape);
+        let morph_shape = MorphShapeStatic::from_swf_tag(
+            context.renderer,
+            context.library.library_for_movie_mut(self.movie()),
+            &swf_shape,
+        );
         morph_shapes.insert(swf_shape.id, morph_shape);
         Ok(())
     }
@@ -1624,7 +1847,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
         version: u8,
     ) -> DecodeResult {
         let swf_shape = reader.read_define_shape(version)?;
-        let graphic = Graphic::from_swf_tag(context, &swf_shape);
+        let graphic = Graphic::from_swf_tag(context, &swf_shape, self.movie());
         context
             .library
             .library_for_movie_mut(self.movie())
@@ -1653,7 +1876,11 @@ impl<'gc, 'a> MovieClipData<'gc> {
                 if let Some(morph_shape) = morph_shapes.get_mut(&id) {
                     ids.insert(place_object.depth.into(), id);
                     if let Some(ratio) = place_object.ratio {
-                        morph_shape.register_ratio(context.renderer, ratio);
+                        morph_shape.register_ratio(
+                            context.renderer,
+                            context.library.library_for_movie_mut(self.movie()),
+                            ratio,
+                        );
                     }
                 }
             }
@@ -1662,7 +1889,11 @@ impl<'gc, 'a> MovieClipData<'gc> {
                     if let Some(morph_shape) = morph_shapes.get_mut(&id) {
                         ids.insert(place_object.depth.into(), id);
                         if let Some(ratio) = place_object.ratio {
-                            morph_shape.register_ratio(context.renderer, ratio);
+                            morph_shape.register_ratio(
+                                context.renderer,
+                                context.library.library_for_movie_mut(self.movie()),
+                                ratio,
+                            );
                         }
                     }
                 }
@@ -1671,7 +1902,11 @@ impl<'gc, 'a> MovieClipData<'gc> {
                 if let Some(morph_shape) = morph_shapes.get_mut(&id) {
                     ids.insert(place_object.depth.into(), id);
                     if let Some(ratio) = place_object.ratio {
-                        morph_shape.register_ratio(context.renderer, ratio);
+                        morph_shape.register_ratio(
+                            context.renderer,
+                            context.library.library_for_movie_mut(self.movie()),
+                            ratio,
+                        );
                     }
                 } else {
                     ids.remove(&place_object.depth.into());
@@ -1746,6 +1981,7 @@ impl<'gc, 'a> MovieClipData<'gc> {
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            BitmapTag::DefineBits(jpeg_data),
         );
         context
             .library
@@ -1773,6 +2009,10 @@ impl<'gc, 'a> MovieClipData<'gc> {
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            BitmapTag::DefineBitsJpeg {
+                data: jpeg_data,
+                alpha_data: None,
+            },
         );
         context
             .library
@@ -1811,6 +2051,10 @@ impl<'gc, 'a> MovieClipData<'gc> {
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            BitmapTag::DefineBitsJpeg {
+                data: jpeg_data,
+                alpha_data: Some(alpha_data),
+            },
         );
         context
             .library
@@ -1850,6 +2094,10 @@ impl<'gc, 'a> MovieClipData<'gc> {
             bitmap_info.handle,
             bitmap_info.width,
             bitmap_info.height,
+            BitmapTag::DefineBitsJpeg {
+                data: jpeg_data,
+                alpha_data: Some(alpha_data),
+            },
         );
         context
             .library
@@ -2043,6 +2291,24 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_font_4(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let font = reader.read_define_font_4()?;
+        match Font::from_font4_tag(context.gc_context, context.renderer, &font) {
+            Ok(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font.id, Character::Font(font_object)),
+            Err(e) => log::error!("Unable to load DefineFont4 font {}: {}", font.id, e),
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn define_sound(
         &mut self,
@@ -2064,6 +2330,45 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_video_stream(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let define_video_stream = reader.read_define_video_stream()?;
+        let id = define_video_stream.id;
+        let video = Video::from_swf_tag(context, define_video_stream);
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_character(id, Character::Video(video));
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_video_frame(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let video_frame = reader.read_video_frame()?;
+        match context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(video_frame.stream_id)
+        {
+            Some(Character::Video(video)) => {
+                video.preload_swf_frame(context.gc_context, video_frame);
+            }
+            _ => log::error!(
+                "Attempted to preload video frames into non-video character {}",
+                video_frame.stream_id
+            ),
+        }
+        Ok(())
+    }
+
     fn define_sprite(
         &mut self,
         avm: &mut Avm1<'gc>,
@@ -2288,7 +2593,7 @@ impl<'gc, 'a> MovieClip<'gc> {
                     .get(&place_object.depth.into())
                     .copied()
                 {
-                    child.apply_place_object(context.gc_context, &place_object);
+                    child.apply_place_object(context, &place_object);
                     child
                 } else {
                     return Ok(());
@@ -0,0 +1,296 @@
+//! Video display object
+
+use crate::avm1::Avm1;
+use crate::backend::render::BitmapInfo;
+use crate::backend::video::{EncodedFrame, VideoStreamHandle};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::prelude::*;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use swf::{DefineVideoStream, Matrix, VideoFrame};
+
+/// A Video display object is a rectangle that displays frames of an embedded
+/// video stream, defined by a `DefineVideoStream` tag.
+///
+/// The frames of the stream are stored in `VideoFrame` tags on the timeline
+/// of the movie that defines it. The frame that is displayed is chosen by
+/// the ratio of the `PlaceObject` tag that places the video.
+#[derive(Clone, Debug, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Video<'gc>(GcCell<'gc, VideoData<'gc>>);
+
+#[derive(Clone, Debug)]
+pub struct VideoData<'gc> {
+    base: DisplayObjectBase<'gc>,
+    source: GcCell<'gc, VideoSource>,
+
+    /// The video backend stream that this instance decodes frames with.
+    stream: VideoStream,
+
+    /// The frames of the stream that can be decoded on their own.
+    keyframes: BTreeSet<u32>,
+
+    /// The last decoded frame, and the bitmap it was uploaded to.
+    decoded_frame: Option<(u32, BitmapInfo)>,
+}
+
+/// The state of the video backend stream of a `Video` instance.
+///
+/// Streams are registered the first time a frame is displayed, so that each
+/// instance has its own decoder state.
+#[derive(Copy, Clone, Debug)]
+enum VideoStream {
+    Unregistered,
+    Registered(VideoStreamHandle),
+    Failed,
+}
+
+impl<'gc> Video<'gc> {
+    pub fn from_swf_tag(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        definition: DefineVideoStream,
+    ) -> Self {
+        let source = VideoSource {
+            definition,
+            frames: BTreeMap::new(),
+        };
+        Video(GcCell::allocate(
+            context.gc_context,
+            VideoData {
+                base: Default::default(),
+                source: GcCell::allocate(context.gc_context, source),
+                stream: VideoStream::Unregistered,
+                keyframes: BTreeSet::new(),
+                decoded_frame: None,
+            },
+        ))
+    }
+
+    /// Adds the data of a `VideoFrame` tag to this video's stream.
+    pub fn preload_swf_frame(self, gc_context: MutationContext<'gc, '_>, tag: VideoFrame) {
+        let source = self.0.read().source;
+        source
+            .write(gc_context)
+            .frames
+            .insert(tag.frame_num.into(), tag.data);
+    }
+
+    /// Displays the given frame of the video stream.
+    ///
+    /// Frames that depend on previous frames are decoded starting from the
+    /// closest keyframe, or from the last displayed frame if it is closer.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc, '_>, frame_id: u32) {
+        let stream = match self.stream(context) {
+            Some(stream) => stream,
+            None => return,
+        };
+        let num_frames = u32::from(self.0.read().source.read().definition.num_frames);
+        if num_frames == 0 {
+            return;
+        }
+        let frame_id = frame_id % num_frames;
+
+        let read = self.0.read();
+        let last_frame = read.decoded_frame.map(|(frame, _)| frame);
+        if last_frame == Some(frame_id) {
+            return;
+        }
+        let keyframe = read
+            .keyframes
+            .range(..=frame_id)
+            .next_back()
+            .copied()
+            .unwrap_or(0);
+        let first_frame = match last_frame {
+            Some(last_frame) if last_frame < frame_id && last_frame >= keyframe => last_frame + 1,
+            _ => keyframe,
+        };
+        drop(read);
+
+        for frame in first_frame..=frame_id {
+            self.decode_frame(context, stream, frame);
+        }
+    }
+
+    /// Returns the video backend stream of this instance, registering it and
+    /// preloading its frames if necessary.
+    fn stream(self, context: &mut UpdateContext<'_, 'gc, '_>) -> Option<VideoStreamHandle> {
+        match self.0.read().stream {
+            VideoStream::Registered(stream) => return Some(stream),
+            VideoStream::Failed => return None,
+            VideoStream::Unregistered => (),
+        }
+
+        let source = self.0.read().source;
+        let source = source.read();
+        let definition = &source.definition;
+        let stream = match context.video.register_video_stream(
+            definition.num_frames.into(),
+            (definition.width, definition.height),
+            definition.codec,
+            definition.deblocking,
+        ) {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("Unable to register video stream {}: {}", definition.id, e);
+                self.0.write(context.gc_context).stream = VideoStream::Failed;
+                return None;
+            }
+        };
+
+        let mut keyframes = BTreeSet::new();
+        for (&frame_id, data) in &source.frames {
+            let encoded_frame = EncodedFrame {
+                codec: definition.codec,
+                data: &data[..],
+                frame_id,
+            };
+            match context
+                .video
+                .preload_video_stream_frame(stream, encoded_frame)
+            {
+                Ok(dependency) if dependency.is_keyframe() => {
+                    keyframes.insert(frame_id);
+                }
+                Ok(_) => (),
+                Err(e) => log::warn!(
+                    "Unable to preload frame {} of video stream {}: {}",
+                    frame_id,
+                    definition.id,
+                    e
+                ),
+            }
+        }
+
+        let mut write = self.0.write(context.gc_context);
+        write.stream = VideoStream::Registered(stream);
+        write.keyframes = keyframes;
+        Some(stream)
+    }
+
+    fn decode_frame(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        stream: VideoStreamHandle,
+        frame_id: u32,
+    ) {
+        let source = self.0.read().source;
+        let source = source.read();
+        let bitmap = match source.frames.get(&frame_id) {
+            Some(data) => {
+                let encoded_frame = EncodedFrame {
+                    codec: source.definition.codec,
+                    data: &data[..],
+                    frame_id,
+                };
+                match context.video.decode_video_stream_frame(
+                    stream,
+                    encoded_frame,
+                    context.renderer,
+                ) {
+                    Ok(bitmap) => Some(bitmap),
+                    Err(e) => {
+                        log::warn!(
+                            "Unable to decode frame {} of video stream {}: {}",
+                            frame_id,
+                            source.definition.id,
+                            e
+                        );
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // If the frame couldn't be decoded, keep showing the last frame.
+        let mut write = self.0.write(context.gc_context);
+        if let Some(bitmap) = bitmap.or_else(|| write.decoded_frame.map(|(_, bitmap)| bitmap)) {
+            write.decoded_frame = Some((frame_id, bitmap));
+        }
+    }
+}
+
+impl<'gc> TDisplayObject<'gc> for Video<'gc> {
+    impl_display_object!(base);
+
+    fn id(&self) -> CharacterId {
+        self.0.read().source.read().definition.id
+    }
+
+    fn as_video(&self) -> Option<Video<'gc>> {
+        Some(*self)
+    }
+
+    fn self_bounds(&self) -> BoundingBox {
+        let source = self.0.read().source;
+        let definition = &source.read().definition;
+        BoundingBox {
+            x_min: Twips::new(0),
+            y_min: Twips::new(0),
+            x_max: Twips::from_pixels(definition.width.into()),
+            y_max: Twips::from_pixels(definition.height.into()),
+            valid: true,
+        }
+    }
+
+    fn run_frame(&mut self, _avm: &mut Avm1<'gc>, _context: &mut UpdateContext) {
+        // Noop
+    }
+
+    fn render(&self, context: &mut RenderContext) {
+        if !self.world_bounds().intersects(&context.view_bounds) {
+            // Off-screen; culled
+            return;
+        }
+
+        let read = self.0.read();
+        if let Some((_frame, bitmap)) = read.decoded_frame {
+            context.transform_stack.push(&*self.transform());
+
+            // Stretch the frame to the size of the video.
+            let source = read.source.read();
+            let mut transform = context.transform_stack.transform().clone();
+            transform.matrix *= Matrix {
+                a: f32::from(source.definition.width) / f32::from(bitmap.width.max(1)),
+                d: f32::from(source.definition.height) / f32::from(bitmap.height.max(1)),
+                ..Default::default()
+            };
+            context.renderer.render_bitmap(bitmap.handle, &transform);
+
+            context.transform_stack.pop();
+        }
+    }
+}
+
+unsafe impl<'gc> gc_arena::Collect for VideoData<'gc> {
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        self.base.trace(cc);
+        self.source.trace(cc);
+    }
+}
+
+/// The definition and encoded frames of an embedded video stream, shared
+/// between all instances of it.
+struct VideoSource {
+    definition: DefineVideoStream,
+    frames: BTreeMap<u32, Vec<u8>>,
+}
+
+impl fmt::Debug for VideoSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VideoSource")
+            .field("definition", &self.definition)
+            .field("num_preloaded_frames", &self.frames.len())
+            .finish()
+    }
+}
+
+unsafe impl gc_arena::Collect for VideoSource {
+    #[inline]
+    fn needs_trace() -> bool {
+        false
+    }
+}
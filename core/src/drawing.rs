@@ -1,14 +1,14 @@
-use crate::backend::render::ShapeHandle;
+use crate::backend::render::{BitmapInfo, BitmapSource, ShapeHandle};
+use crate::bitmap_data::BitmapData;
 use crate::bounding_box::BoundingBox;
 use crate::context::RenderContext;
 use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
-use gc_arena::Collect;
+use gc_arena::{Collect, CollectionContext, GcCell};
 use std::cell::Cell;
-use swf::{FillStyle, LineStyle, Twips};
+use swf::{CharacterId, FillStyle, LineStyle, Twips};
 
-#[derive(Clone, Debug, Collect)]
-#[collect(require_static)]
-pub struct Drawing {
+#[derive(Clone, Debug)]
+pub struct Drawing<'gc> {
     render_handle: Cell<Option<ShapeHandle>>,
     shape_bounds: BoundingBox,
     edge_bounds: BoundingBox,
@@ -18,9 +18,19 @@ pub struct Drawing {
     current_fill: Option<(FillStyle, Vec<DrawCommand>)>,
     current_line: Option<(LineStyle, Vec<DrawCommand>)>,
     cursor: (Twips, Twips),
+
+    /// The bitmaps used by bitmap fills, indexed by the `id` of the fill.
+    bitmaps: Vec<GcCell<'gc, BitmapData>>,
+}
+
+unsafe impl<'gc> Collect for Drawing<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        self.bitmaps.trace(cc);
+    }
 }
 
-impl Drawing {
+impl<'gc> Drawing<'gc> {
     pub fn new() -> Self {
         Self {
             render_handle: Cell::new(None),
@@ -32,9 +42,18 @@ impl Drawing {
             current_fill: None,
             current_line: None,
             cursor: (Twips::zero(), Twips::zero()),
+            bitmaps: Vec::new(),
         }
     }
 
+    /// Adds a bitmap for use by a bitmap fill, returning the `id` to give
+    /// to the fill.
+    pub fn add_bitmap(&mut self, bitmap_data: GcCell<'gc, BitmapData>) -> CharacterId {
+        let id = self.bitmaps.len() as CharacterId;
+        self.bitmaps.push(bitmap_data);
+        id
+    }
+
     pub fn set_fill_style(&mut self, style: Option<FillStyle>) {
         // TODO: If current_fill is not closed, we should close it and also close current_line
 
@@ -59,6 +78,7 @@ impl Drawing {
         self.current_line = None;
         self.fills.clear();
         self.lines.clear();
+        self.bitmaps.clear();
         self.edge_bounds = BoundingBox::default();
         self.shape_bounds = BoundingBox::default();
         self.dirty.set(true);
@@ -137,6 +157,15 @@ impl Drawing {
     }
 
     pub fn render(&self, context: &mut RenderContext) {
+        // The pixels of the bitmaps may have changed, so their textures are
+        // updated on every frame. Unchanged bitmaps are skipped.
+        let bitmaps = DrawingBitmaps(
+            self.bitmaps
+                .iter()
+                .map(|bitmap| bitmap.read().bitmap_handle(context.renderer))
+                .collect(),
+        );
+
         if self.dirty.get() {
             self.dirty.set(false);
             let mut paths = Vec::new();
@@ -181,10 +210,10 @@ impl Drawing {
             };
 
             if let Some(handle) = self.render_handle.get() {
-                context.renderer.replace_shape(shape, handle);
+                context.renderer.replace_shape(shape, &bitmaps, handle);
             } else {
                 self.render_handle
-                    .set(Some(context.renderer.register_shape(shape)));
+                    .set(Some(context.renderer.register_shape(shape, &bitmaps)));
             }
         }
 
@@ -200,6 +229,16 @@ impl Drawing {
     }
 }
 
+/// The textures of the bitmaps of a drawing, looked up by the `id` of its
+/// bitmap fills.
+struct DrawingBitmaps(Vec<Option<BitmapInfo>>);
+
+impl BitmapSource for DrawingBitmaps {
+    fn bitmap(&self, id: CharacterId) -> Option<BitmapInfo> {
+        self.0.get(usize::from(id)).copied().flatten()
+    }
+}
+
 fn stretch_bounding_box(
     bounding_box: &mut BoundingBox,
     command: &DrawCommand,
@@ -1,7 +1,7 @@
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PlayerEvent {
     KeyDown { key_code: KeyCode },
     KeyUp { key_code: KeyCode },
@@ -102,6 +102,7 @@ impl ClipEvent {
 pub enum KeyCode {
     Unknown = 0,
     Backspace = 8,
+    Tab = 9,
     Return = 13,
     Shift = 16,
     Control = 17,
@@ -326,6 +327,7 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
         KeyCode::Insert => ButtonKeyCode::Insert,
         KeyCode::Delete => ButtonKeyCode::Delete,
         KeyCode::Backspace => ButtonKeyCode::Backspace,
+        KeyCode::Tab => ButtonKeyCode::Tab,
         KeyCode::Return => ButtonKeyCode::Return,
         KeyCode::Up => ButtonKeyCode::Up,
         KeyCode::Down => ButtonKeyCode::Down,
@@ -336,3 +338,71 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
     };
     Some(out)
 }
+
+/// Returns the character code typed by a key, as reported by AS3
+/// `KeyboardEvent.charCode`, or 0 if the key does not type a character.
+pub fn key_code_to_char_code(key_code: KeyCode, shift: bool) -> u32 {
+    let code = u32::from(u8::from(key_code));
+    match key_code {
+        KeyCode::A
+        | KeyCode::B
+        | KeyCode::C
+        | KeyCode::D
+        | KeyCode::E
+        | KeyCode::F
+        | KeyCode::G
+        | KeyCode::H
+        | KeyCode::I
+        | KeyCode::J
+        | KeyCode::K
+        | KeyCode::L
+        | KeyCode::M
+        | KeyCode::N
+        | KeyCode::O
+        | KeyCode::P
+        | KeyCode::Q
+        | KeyCode::R
+        | KeyCode::S
+        | KeyCode::T
+        | KeyCode::U
+        | KeyCode::V
+        | KeyCode::W
+        | KeyCode::X
+        | KeyCode::Y
+        | KeyCode::Z => {
+            if shift {
+                code
+            } else {
+                code + u32::from(b'a' - b'A')
+            }
+        }
+        KeyCode::Key0
+        | KeyCode::Key1
+        | KeyCode::Key2
+        | KeyCode::Key3
+        | KeyCode::Key4
+        | KeyCode::Key5
+        | KeyCode::Key6
+        | KeyCode::Key7
+        | KeyCode::Key8
+        | KeyCode::Key9
+            if !shift =>
+        {
+            code
+        }
+        KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9 => code - u32::from(KeyCode::Numpad0 as u8) + u32::from(b'0'),
+        KeyCode::Space | KeyCode::Tab | KeyCode::Return | KeyCode::Backspace | KeyCode::Escape => {
+            code
+        }
+        _ => 0,
+    }
+}
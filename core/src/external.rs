@@ -0,0 +1,453 @@
+//! Communication between a movie and the page or application hosting it.
+//!
+//! Movies talk to their host through `ExternalInterface`: they call methods
+//! of the host with `ExternalInterface.call`, and expose their own functions
+//! to it with `ExternalInterface.addCallback`. FSCommands are delivered to the
+//! host over the same channel.
+
+use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
+use crate::avm1::error::Error as Avm1Error;
+use crate::avm1::{
+    Avm1, AvmString, Object as Avm1Object, ScriptObject as Avm1ScriptObject, TObject as _,
+    Value as Avm1Value,
+};
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject, ArrayStorage, Avm2, Error as Avm2Error, Namespace,
+    Object as Avm2Object, QName, ScriptObject as Avm2ScriptObject, TObject as _,
+    Value as Avm2Value,
+};
+use crate::context::UpdateContext;
+use enumset::EnumSet;
+use gc_arena::{Collect, CollectionContext};
+use std::collections::BTreeMap;
+
+/// A value passed between the movie and its host.
+///
+/// Values are copied when they cross the boundary, so objects and arrays
+/// lose their identity (and their prototype). Functions cannot be passed and
+/// become `Null`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Object(BTreeMap<String, Value>),
+    List(Vec<Value>),
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(value: Vec<Value>) -> Self {
+        Value::List(value)
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Value {
+    fn from(value: BTreeMap<String, Value>) -> Self {
+        Value::Object(value)
+    }
+}
+
+impl Value {
+    /// Copy an AVM1 value so that it can be passed to the host.
+    pub fn from_avm1<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        value: Avm1Value<'gc>,
+    ) -> Result<Value, Avm1Error<'gc>> {
+        Self::from_avm1_inner(activation, context, value, &mut Vec::new())
+    }
+
+    /// Copy an AVM1 value, where `ancestors` are the objects currently being
+    /// copied. An object that contains itself is copied as `Null` where it
+    /// recurs.
+    fn from_avm1_inner<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        value: Avm1Value<'gc>,
+        ancestors: &mut Vec<Avm1Object<'gc>>,
+    ) -> Result<Value, Avm1Error<'gc>> {
+        Ok(match value {
+            Avm1Value::Undefined | Avm1Value::Null => Value::Null,
+            Avm1Value::Bool(value) => Value::Bool(value),
+            Avm1Value::Number(value) => Value::Number(value),
+            Avm1Value::String(value) => Value::String(value.to_string()),
+            Avm1Value::Object(object) => {
+                if object.as_executable().is_some()
+                    || ancestors
+                        .iter()
+                        .any(|ancestor| Avm1Object::ptr_eq(*ancestor, object))
+                {
+                    Value::Null
+                } else if activation.avm.prototypes().array.is_prototype_of(object) {
+                    ancestors.push(object);
+                    let mut values = Vec::with_capacity(object.length());
+                    for value in object.array() {
+                        values.push(Value::from_avm1_inner(
+                            activation, context, value, ancestors,
+                        )?);
+                    }
+                    ancestors.pop();
+                    Value::List(values)
+                } else {
+                    ancestors.push(object);
+                    let mut values = BTreeMap::new();
+                    for key in object.get_keys(activation) {
+                        let value = object.get(&key, activation, context)?;
+                        values.insert(
+                            key,
+                            Value::from_avm1_inner(activation, context, value, ancestors)?,
+                        );
+                    }
+                    ancestors.pop();
+                    Value::Object(values)
+                }
+            }
+        })
+    }
+
+    /// Create an AVM1 value from a value received from the host.
+    pub fn into_avm1<'gc>(
+        self,
+        activation: &mut Avm1Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Avm1Value<'gc> {
+        match self {
+            Value::Null => Avm1Value::Null,
+            Value::Bool(value) => Avm1Value::Bool(value),
+            Value::Number(value) => Avm1Value::Number(value),
+            Value::String(value) => AvmString::new(context.gc_context, value).into(),
+            Value::Object(values) => {
+                let object = Avm1ScriptObject::object(
+                    context.gc_context,
+                    Some(activation.avm.prototypes().object),
+                );
+                for (key, value) in values {
+                    let value = value.into_avm1(activation, context);
+                    object.define_value(context.gc_context, &key, value, EnumSet::empty());
+                }
+                object.into()
+            }
+            Value::List(values) => {
+                let array = Avm1ScriptObject::array(
+                    context.gc_context,
+                    Some(activation.avm.prototypes().array),
+                );
+                for (i, value) in values.into_iter().enumerate() {
+                    let value = value.into_avm1(activation, context);
+                    array.set_array_element(i, value, context.gc_context);
+                }
+                array.into()
+            }
+        }
+    }
+
+    /// Copy an AVM2 value so that it can be passed to the host.
+    pub fn from_avm2<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+    ) -> Result<Value, Avm2Error> {
+        Self::from_avm2_inner(activation, context, value, &mut Vec::new())
+    }
+
+    /// Copy an AVM2 value, where `ancestors` are the objects currently being
+    /// copied. An object that contains itself is copied as `Null` where it
+    /// recurs.
+    fn from_avm2_inner<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+        ancestors: &mut Vec<Avm2Object<'gc>>,
+    ) -> Result<Value, Avm2Error> {
+        Ok(match value {
+            Avm2Value::Undefined | Avm2Value::Null | Avm2Value::Namespace(_) => Value::Null,
+            Avm2Value::Bool(value) => Value::Bool(value),
+            Avm2Value::Number(value) => Value::Number(value),
+            Avm2Value::Integer(value) => Value::Number(value.into()),
+            Avm2Value::Unsigned(value) => Value::Number(value.into()),
+            Avm2Value::String(value) => Value::String(value.to_string()),
+            Avm2Value::Object(mut object) => {
+                let elements = object
+                    .as_array_storage()
+                    .map(|array| array.iter().collect::<Vec<_>>());
+
+                if object.as_executable().is_some()
+                    || ancestors
+                        .iter()
+                        .any(|ancestor| Avm2Object::ptr_eq(*ancestor, object))
+                {
+                    Value::Null
+                } else if let Some(elements) = elements {
+                    ancestors.push(object);
+                    let mut values = Vec::with_capacity(elements.len());
+                    for value in elements {
+                        let value = value.unwrap_or(Avm2Value::Null);
+                        values.push(Value::from_avm2_inner(
+                            activation, context, value, ancestors,
+                        )?);
+                    }
+                    ancestors.pop();
+                    Value::List(values)
+                } else {
+                    ancestors.push(object);
+                    let mut values = BTreeMap::new();
+                    // Enumerant indices start from one.
+                    let mut index = 1;
+                    while let Some(name) = object.get_enumerant_name(index) {
+                        let value = object.get_property(object, &name, activation, context)?;
+                        values.insert(
+                            name.local_name().to_string(),
+                            Value::from_avm2_inner(activation, context, value, ancestors)?,
+                        );
+                        index += 1;
+                    }
+                    ancestors.pop();
+                    Value::Object(values)
+                }
+            }
+        })
+    }
+
+    /// Create an AVM2 value from a value received from the host.
+    pub fn into_avm2<'gc>(
+        self,
+        activation: &mut Avm2Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<Avm2Value<'gc>, Avm2Error> {
+        Ok(match self {
+            Value::Null => Avm2Value::Null,
+            Value::Bool(value) => Avm2Value::Bool(value),
+            Value::Number(value) => Avm2Value::Number(value),
+            Value::String(value) => AvmString::new(context.gc_context, value).into(),
+            Value::Object(values) => {
+                let mut object = Avm2ScriptObject::object(
+                    context.gc_context,
+                    activation.avm2().prototypes().object,
+                );
+                for (key, value) in values {
+                    let value = value.into_avm2(activation, context)?;
+                    object.set_property(
+                        object,
+                        &QName::new(
+                            Namespace::public_namespace(),
+                            AvmString::new(context.gc_context, key),
+                        ),
+                        value,
+                        activation,
+                        context,
+                    )?;
+                }
+                object.into()
+            }
+            Value::List(values) => {
+                let mut elements = Vec::with_capacity(values.len());
+                for value in values {
+                    elements.push(value.into_avm2(activation, context)?);
+                }
+                ArrayObject::from_array(
+                    ArrayStorage::from_args(&elements),
+                    activation.avm2().prototypes().array,
+                    context.gc_context,
+                )
+                .into()
+            }
+        })
+    }
+}
+
+/// Implemented by the host of a movie to let the movie talk to it.
+///
+/// The player is busy while these methods run, so they must not call back
+/// into it.
+pub trait ExternalInterfaceProvider {
+    /// Call a method of the host, on behalf of `ExternalInterface.call`.
+    ///
+    /// Hosts return `Null` for methods they do not know.
+    fn call_method(&mut self, name: &str, args: &[Value]) -> Value;
+
+    /// Called when the movie exposes a function to the host with
+    /// `ExternalInterface.addCallback`. It may then be called with
+    /// `Player::call_internal_interface`.
+    fn on_callback_available(&mut self, name: &str);
+
+    /// Handle an FSCommand sent by the movie.
+    ///
+    /// Returns `false` if the host does not handle the command.
+    fn on_fs_command(&mut self, command: &str, args: &str) -> bool;
+}
+
+/// A function that the movie exposed to its host.
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+pub enum Callback<'gc> {
+    Avm1 {
+        this: Avm1Value<'gc>,
+        method: Avm1Object<'gc>,
+    },
+    Avm2 {
+        method: Avm2Object<'gc>,
+    },
+}
+
+impl<'gc> Callback<'gc> {
+    /// Call the function with arguments received from the host.
+    ///
+    /// Errors thrown by the function are logged, and `Null` is returned
+    /// to the host instead.
+    pub fn call(
+        &self,
+        avm1: &mut Avm1<'gc>,
+        avm2: &mut Avm2<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        name: &str,
+        args: Vec<Value>,
+    ) -> Value {
+        let result = match self {
+            Callback::Avm1 { this, method } => {
+                let base_clip = match context.levels.get(&0) {
+                    Some(base_clip) => *base_clip,
+                    None => {
+                        log::error!(
+                            "Can't call ExternalInterface callback {} without a root movie",
+                            name
+                        );
+                        return Value::Null;
+                    }
+                };
+                let mut activation = Avm1Activation::from_nothing(
+                    avm1,
+                    ActivationIdentifier::root("[ExternalInterface]"),
+                    context.swf.version(),
+                    avm1.global_object_cell(),
+                    context.gc_context,
+                    base_clip,
+                );
+                call_avm1(&mut activation, context, name, this, *method, args)
+                    .map_err(|e| e.to_string())
+            }
+            Callback::Avm2 { method } => {
+                let mut activation = Avm2Activation::from_nothing(avm1, avm2, context);
+                call_avm2(&mut activation, context, *method, args).map_err(|e| e.to_string())
+            }
+        };
+
+        result.unwrap_or_else(|e| {
+            log::error!("Error in ExternalInterface callback {}: {}", name, e);
+            Value::Null
+        })
+    }
+}
+
+fn call_avm1<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    name: &str,
+    this: &Avm1Value<'gc>,
+    method: Avm1Object<'gc>,
+    args: Vec<Value>,
+) -> Result<Value, Avm1Error<'gc>> {
+    let this = this.coerce_to_object(activation, context);
+    let args: Vec<_> = args
+        .into_iter()
+        .map(|arg| arg.into_avm1(activation, context))
+        .collect();
+    let result = method.call(name, activation, context, this, None, &args)?;
+    Value::from_avm1(activation, context, result)
+}
+
+fn call_avm2<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    method: Avm2Object<'gc>,
+    args: Vec<Value>,
+) -> Result<Value, Avm2Error> {
+    let mut avm2_args = Vec::with_capacity(args.len());
+    for arg in args {
+        avm2_args.push(arg.into_avm2(activation, context)?);
+    }
+    let result = method.call(None, &avm2_args, activation, context, None)?;
+    Value::from_avm2(activation, context, result)
+}
+
+/// The movie's side of the `ExternalInterface` connection.
+///
+/// This holds the host's provider, if any, and the functions that the movie
+/// exposed to the host.
+#[derive(Default)]
+pub struct ExternalInterface<'gc> {
+    provider: Option<Box<dyn ExternalInterfaceProvider>>,
+    callbacks: BTreeMap<String, Callback<'gc>>,
+}
+
+unsafe impl<'gc> Collect for ExternalInterface<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        self.callbacks.trace(cc);
+    }
+}
+
+impl<'gc> ExternalInterface<'gc> {
+    pub fn set_provider(&mut self, provider: Option<Box<dyn ExternalInterfaceProvider>>) {
+        self.provider = provider;
+    }
+
+    /// Whether the movie has a host to talk to.
+    pub fn available(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Call a method of the host. Returns `Null` if there is no host.
+    pub fn call_method(&mut self, name: &str, args: &[Value]) -> Value {
+        match &mut self.provider {
+            Some(provider) => provider.call_method(name, args),
+            None => Value::Null,
+        }
+    }
+
+    /// Expose a function of the movie to the host, replacing any function
+    /// previously exposed under the same name.
+    pub fn add_callback(&mut self, name: String, callback: Callback<'gc>) {
+        if let Some(provider) = &mut self.provider {
+            provider.on_callback_available(&name);
+        }
+        self.callbacks.insert(name, callback);
+    }
+
+    pub fn get_callback(&self, name: &str) -> Option<Callback<'gc>> {
+        self.callbacks.get(name).cloned()
+    }
+
+    /// Deliver an FSCommand to the host.
+    ///
+    /// Returns `false` if there is no host, or it does not handle the command.
+    pub fn invoke_fs_command(&mut self, command: &str, args: &str) -> bool {
+        match &mut self.provider {
+            Some(provider) => provider.on_fs_command(command, args),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Tracking of the display object that has keyboard focus.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::listeners::SystemListener;
+use crate::avm1::{Avm1, TObject, Value};
+use crate::context::{ActionType, RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::drawing::Drawing;
+use crate::prelude::*;
+use crate::shape_utils::DrawCommand;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// Keeps track of the display object that currently has keyboard focus.
+///
+/// Keyboard input, such as typed text, is sent to the focused object.
+#[derive(Clone, Copy, Collect, Debug)]
+#[collect(no_drop)]
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+struct FocusTrackerData<'gc> {
+    /// The display object that has focus, if any.
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether the focused object is highlighted with a focus rectangle.
+    ///
+    /// This is only the case when focus was moved by the keyboard or by
+    /// ActionScript, not when the object was clicked.
+    is_highlighted: bool,
+
+    /// The drawing of the focus rectangle.
+    highlight: Drawing<'gc>,
+
+    /// The bounds that the focus rectangle was last drawn around.
+    highlight_bounds: BoundingBox,
+}
+
+impl<'gc> FocusTracker<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            FocusTrackerData {
+                focus: None,
+                is_highlighted: false,
+                highlight: Drawing::new(),
+                highlight_bounds: BoundingBox::default(),
+            },
+        ))
+    }
+
+    /// Returns the display object that currently has focus, if any.
+    pub fn get(&self) -> Option<DisplayObject<'gc>> {
+        self.0.read().focus
+    }
+
+    /// Moves focus to the given display object, or removes focus if `None`.
+    ///
+    /// The previously focused object, the newly focused object and any
+    /// `Selection` listeners are all notified of the change, if it is a
+    /// change. The focus rectangle is hidden until `set_highlighted` is
+    /// called.
+    pub fn set(
+        &self,
+        focused_element: Option<DisplayObject<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        write.is_highlighted = false;
+        let old = std::mem::replace(&mut write.focus, focused_element);
+        drop(write);
+
+        let is_same = match (old, focused_element) {
+            (Some(old), Some(new)) => DisplayObject::ptr_eq(old, new),
+            (None, None) => true,
+            _ => false,
+        };
+        if is_same {
+            return;
+        }
+
+        if let Some(old) = old {
+            old.on_focus_changed(context, false, focused_element);
+            Self::queue_focus_method(context, old, "onKillFocus", focused_element);
+        }
+        if let Some(new) = focused_element {
+            new.on_focus_changed(context, true, old);
+            Self::queue_focus_method(context, new, "onSetFocus", old);
+        }
+
+        if let Some(root) = context.levels.get(&0).copied() {
+            context.action_queue.queue_actions(
+                root,
+                ActionType::NotifyListeners {
+                    listener: SystemListener::Selection,
+                    method: "onSetFocus",
+                    args: vec![avm1_value(old), avm1_value(focused_element)],
+                },
+                false,
+            );
+        }
+    }
+
+    /// Shows or hides the focus rectangle around the focused object.
+    pub fn set_highlighted(&self, gc_context: MutationContext<'gc, '_>, is_highlighted: bool) {
+        self.0.write(gc_context).is_highlighted = is_highlighted;
+    }
+
+    /// Moves focus to the next object in the tab order, or the previous one
+    /// if `reverse` is set, as when Tab or Shift+Tab is pressed.
+    pub fn cycle(
+        &self,
+        avm: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reverse: bool,
+    ) {
+        let tab_order = Self::tab_order(avm, context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let len = tab_order.len();
+        let current = self.get().and_then(|focus| {
+            tab_order
+                .iter()
+                .position(|object| DisplayObject::ptr_eq(*object, focus))
+        });
+        let next = match current {
+            Some(i) if reverse => (i + len - 1) % len,
+            Some(i) => (i + 1) % len,
+            None if reverse => len - 1,
+            None => 0,
+        };
+
+        self.set(Some(tab_order[next]), context);
+        self.set_highlighted(context.gc_context, true);
+    }
+
+    /// Returns the objects that Tab moves focus between, in order.
+    ///
+    /// If any object has a `tabIndex`, only objects with a `tabIndex` are
+    /// included, ordered by it. Otherwise, objects are ordered by their
+    /// position on the stage, from top to bottom and left to right.
+    fn tab_order(
+        avm: &mut Avm1<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Vec<DisplayObject<'gc>> {
+        let root = match context.levels.get(&0) {
+            Some(root) => *root,
+            None => return vec![],
+        };
+        let mut activation = Activation::from_nothing(
+            avm,
+            ActivationIdentifier::root("[Tab Order]"),
+            context.swf.version(),
+            avm.global_object_cell(),
+            context.gc_context,
+            root,
+        );
+
+        let mut candidates = vec![];
+        let levels: Vec<DisplayObject<'gc>> = context.levels.values().copied().collect();
+        for level in levels {
+            Self::collect_tab_candidates(&mut activation, context, level, &mut candidates);
+        }
+
+        if candidates.iter().any(|(_, tab_index)| tab_index.is_some()) {
+            let mut candidates: Vec<_> = candidates
+                .into_iter()
+                .filter_map(|(object, tab_index)| Some((object, tab_index?)))
+                .collect();
+            candidates.sort_by_key(|(_, tab_index)| *tab_index);
+            candidates.into_iter().map(|(object, _)| object).collect()
+        } else {
+            let mut candidates: Vec<_> = candidates
+                .into_iter()
+                .map(|(object, _)| (object, object.world_bounds()))
+                .collect();
+            candidates.sort_by_key(|(_, bounds)| (bounds.y_min, bounds.x_min));
+            candidates.into_iter().map(|(object, _)| object).collect()
+        }
+    }
+
+    /// Adds the tab-enabled descendants of a display object, and their
+    /// `tabIndex`, to the list of candidates.
+    fn collect_tab_candidates(
+        activation: &mut Activation<'_, 'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        parent: DisplayObject<'gc>,
+        candidates: &mut Vec<(DisplayObject<'gc>, Option<i32>)>,
+    ) {
+        for child in parent.children() {
+            if !child.visible() {
+                continue;
+            }
+            let object = match child.object() {
+                Value::Object(object) => object,
+                _ => continue,
+            };
+
+            // Input text fields, buttons and clips that act as buttons are
+            // tab-enabled, unless `tabEnabled` says otherwise.
+            let tab_enabled = match object.get("tabEnabled", activation, context) {
+                Ok(Value::Undefined) | Err(_) => {
+                    if let Some(text) = child.as_edit_text() {
+                        text.is_editable() && text.is_selectable()
+                    } else if child.as_button().is_some() {
+                        true
+                    } else if let Some(clip) = child.as_movie_clip() {
+                        clip.is_button_mode(activation, context)
+                    } else {
+                        false
+                    }
+                }
+                Ok(value) => value.as_bool(activation.current_swf_version()),
+            };
+            if tab_enabled && child.is_focusable() {
+                let tab_index = match object.get("tabIndex", activation, context) {
+                    Ok(Value::Undefined) | Ok(Value::Null) | Err(_) => None,
+                    Ok(value) => value
+                        .coerce_to_f64(activation, context)
+                        .ok()
+                        .filter(|n| n.is_finite())
+                        .map(|n| n as i32),
+                };
+                candidates.push((child, tab_index));
+            }
+
+            let tab_children = match object.get("tabChildren", activation, context) {
+                Ok(Value::Undefined) | Err(_) => true,
+                Ok(value) => value.as_bool(activation.current_swf_version()),
+            };
+            if tab_children {
+                Self::collect_tab_candidates(activation, context, child, candidates);
+            }
+        }
+    }
+
+    /// Renders the focus rectangle around the focused object, if it is
+    /// highlighted.
+    ///
+    /// Text fields are never highlighted, and other objects only if their
+    /// `_focusrect` allows it.
+    pub fn render_highlight(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        context: &mut RenderContext<'_, 'gc>,
+    ) {
+        let focus = match self.0.read().focus {
+            Some(focus) if self.0.read().is_highlighted => focus,
+            _ => return,
+        };
+        let has_focus_rect = focus
+            .focus_rect()
+            .or_else(|| focus.root().focus_rect())
+            .unwrap_or(true);
+        if focus.removed() || focus.as_edit_text().is_some() || !has_focus_rect {
+            return;
+        }
+
+        let bounds = focus.world_bounds();
+        if bounds != self.0.read().highlight_bounds {
+            let mut write = self.0.write(gc_context);
+            write.highlight.clear();
+            write.highlight.set_line_style(Some(swf::LineStyle::new_v1(
+                Twips::from_pixels(2.0),
+                Color::from_rgb(0xFFFF00, 0xFF),
+            )));
+            write.highlight.draw_command(DrawCommand::MoveTo {
+                x: bounds.x_min,
+                y: bounds.y_min,
+            });
+            write.highlight.draw_command(DrawCommand::LineTo {
+                x: bounds.x_max,
+                y: bounds.y_min,
+            });
+            write.highlight.draw_command(DrawCommand::LineTo {
+                x: bounds.x_max,
+                y: bounds.y_max,
+            });
+            write.highlight.draw_command(DrawCommand::LineTo {
+                x: bounds.x_min,
+                y: bounds.y_max,
+            });
+            write.highlight.draw_command(DrawCommand::LineTo {
+                x: bounds.x_min,
+                y: bounds.y_min,
+            });
+            write.highlight_bounds = bounds;
+        }
+
+        self.0.read().highlight.render(context);
+    }
+
+    /// Queues a call to the `onSetFocus` or `onKillFocus` method of a
+    /// display object.
+    fn queue_focus_method(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: DisplayObject<'gc>,
+        name: &'static str,
+        other: Option<DisplayObject<'gc>>,
+    ) {
+        if let Value::Object(object) = target.object() {
+            context.action_queue.queue_actions(
+                target,
+                ActionType::Method {
+                    object,
+                    name,
+                    args: vec![avm1_value(other)],
+                },
+                false,
+            );
+        }
+    }
+}
+
+/// Returns the AVM1 object of a display object, or `null`.
+fn avm1_value(object: Option<DisplayObject<'_>>) -> Value<'_> {
+    match object.map(|object| object.object()) {
+        Some(Value::Object(object)) => object.into(),
+        _ => Value::Null,
+    }
+}
@@ -3,6 +3,13 @@ use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::transform::Transform;
 use gc_arena::{Collect, Gc, MutationContext};
+use std::cell::RefCell;
+
+mod open_type;
+mod source;
+
+use open_type::OpenTypeFont;
+pub use source::{FallbackChain, FontFace, FontSource};
 
 /// Certain Flash routines measure text by rounding down to the nearest whole pixel.
 pub fn round_down_to_pixel(t: Twips) -> Twips {
@@ -94,6 +101,14 @@ struct FontData {
 
     /// The identity of the font.
     descriptor: FontDescriptor,
+
+    /// For device fonts, the font faces that glyphs are loaded from on
+    /// demand, which take priority over `glyphs`.
+    device_faces: FallbackChain,
+
+    /// The glyphs loaded from `device_faces`, keyed by code point.
+    /// `None` means that `glyphs` should be used for that character instead.
+    device_glyphs: RefCell<fnv::FnvHashMap<u16, Option<Glyph>>>,
 }
 
 impl<'gc> Font<'gc> {
@@ -144,14 +159,187 @@ impl<'gc> Font<'gc> {
                 descent,
                 leading,
                 descriptor,
+                device_faces: Default::default(),
+                device_glyphs: Default::default(),
             },
         )))
     }
 
+    /// Construct a font from a `DefineFont4` tag.
+    ///
+    /// The embedded OpenType font is decoded into glyph shapes in the same EM
+    /// square as `DefineFont3`. A tag without font data yields a font with no
+    /// glyphs, which is rendered as a device font.
+    pub fn from_font4_tag(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        tag: &swf::Font4,
+    ) -> Result<Font<'gc>, Error> {
+        let mut glyphs = vec![];
+        let mut code_point_to_glyph = fnv::FnvHashMap::default();
+        let (mut ascent, mut descent, mut leading) = (0, 0, 0);
+
+        if let Some(data) = &tag.data {
+            let font = OpenTypeFont::parse(data)?;
+            for (code, glyph_id) in font.character_map()? {
+                let swf_glyph = swf::Glyph {
+                    shape_records: font.glyph_shape_records(glyph_id)?,
+                    code,
+                    advance: Some(font.advance(glyph_id)),
+                    bounds: None,
+                };
+                let glyph = Glyph {
+                    shape: renderer.register_glyph_shape(&swf_glyph),
+                    advance: swf_glyph.advance.unwrap_or(0),
+                };
+                let index = glyphs.len();
+                glyphs.push(glyph);
+                code_point_to_glyph.insert(code, index);
+            }
+            ascent = font.ascent();
+            descent = font.descent();
+            leading = font.leading();
+        }
+
+        Ok(Font(Gc::allocate(
+            gc_context,
+            FontData {
+                glyphs,
+                code_point_to_glyph,
+                scale: open_type::EM_SQUARE_SIZE as f32,
+                // TODO: Read kerning from the OpenType `GPOS` or `kern` tables.
+                kerning_pairs: fnv::FnvHashMap::default(),
+                ascent,
+                descent,
+                leading,
+                descriptor: FontDescriptor::from_parts(&tag.name, tag.is_bold, tag.is_italic),
+                device_faces: Default::default(),
+                device_glyphs: Default::default(),
+            },
+        )))
+    }
+
+    /// Construct a device font from the faces of a `FontSource`.
+    ///
+    /// Glyphs are loaded from the faces on demand by `load_glyphs_for_text`.
+    /// The glyphs of `fallback_font`, the bundled device font, are used for
+    /// characters that the preferred faces don't support.
+    pub fn from_font_source(
+        gc_context: MutationContext<'gc, '_>,
+        descriptor: FontDescriptor,
+        device_faces: FallbackChain,
+        fallback_font: Option<Font<'gc>>,
+    ) -> Font<'gc> {
+        let (glyphs, code_point_to_glyph, fallback_metrics) = match fallback_font {
+            Some(font) => (
+                font.0.glyphs.clone(),
+                font.0.code_point_to_glyph.clone(),
+                (font.0.ascent, font.0.descent, font.0.leading),
+            ),
+            None => (vec![], Default::default(), (0, 0, 0)),
+        };
+        let (ascent, descent, leading) = device_faces
+            .preferred
+            .first()
+            .map(|face| face.metrics())
+            .unwrap_or(fallback_metrics);
+
+        Font(Gc::allocate(
+            gc_context,
+            FontData {
+                glyphs,
+                code_point_to_glyph,
+                scale: open_type::EM_SQUARE_SIZE as f32,
+                kerning_pairs: Default::default(),
+                ascent,
+                descent,
+                leading,
+                descriptor,
+                device_faces,
+                device_glyphs: Default::default(),
+            },
+        ))
+    }
+
+    /// Load the glyphs needed to render the given text from this font's
+    /// device font faces. This does nothing for embedded fonts.
+    pub fn load_glyphs_for_text(self, renderer: &mut dyn RenderBackend, text: &str) {
+        let faces = &self.0.device_faces;
+        if faces.preferred.is_empty() && faces.fallback.is_empty() {
+            return;
+        }
+
+        let mut device_glyphs = self.0.device_glyphs.borrow_mut();
+        let mut opened_faces = fnv::FnvHashMap::default();
+        for c in text.chars() {
+            // TODO: Properly handle UTF-16/out-of-bounds code points.
+            let code_point = c as u16;
+            if !device_glyphs.contains_key(&code_point) {
+                let glyph = self.load_device_glyph(renderer, code_point, &mut opened_faces);
+                device_glyphs.insert(code_point, glyph);
+            }
+        }
+    }
+
+    /// Load the glyph for a character from the first device font face that
+    /// supports it. Returns `None` if the glyph from `glyphs` should be used.
+    fn load_device_glyph<'a>(
+        &'a self,
+        renderer: &mut dyn RenderBackend,
+        code_point: u16,
+        opened_faces: &mut fnv::FnvHashMap<usize, OpenTypeFont<'a>>,
+    ) -> Option<Glyph> {
+        let faces = &self.0.device_faces;
+        for (i, face) in faces.preferred.iter().chain(&faces.fallback).enumerate() {
+            // The bundled glyphs are preferred over the fallback faces.
+            if i == faces.preferred.len() && self.0.code_point_to_glyph.contains_key(&code_point) {
+                return None;
+            }
+
+            let glyph_id = match face.glyph_index(code_point) {
+                Some(glyph_id) => glyph_id,
+                None => continue,
+            };
+            let font = match opened_faces.entry(i) {
+                std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                std::collections::hash_map::Entry::Vacant(entry) => match face.open() {
+                    Ok(font) => entry.insert(font),
+                    Err(e) => {
+                        log::warn!("Unable to open device font {:?}: {}", face, e);
+                        continue;
+                    }
+                },
+            };
+            match font.glyph_shape_records(glyph_id) {
+                Ok(shape_records) => {
+                    let swf_glyph = swf::Glyph {
+                        shape_records,
+                        code: code_point,
+                        advance: Some(font.advance(glyph_id)),
+                        bounds: None,
+                    };
+                    return Some(Glyph {
+                        shape: renderer.register_glyph_shape(&swf_glyph),
+                        advance: swf_glyph.advance.unwrap_or(0),
+                    });
+                }
+                Err(e) => log::warn!(
+                    "Unable to load glyph {} from device font {:?}: {}",
+                    glyph_id,
+                    face,
+                    e
+                ),
+            }
+        }
+        None
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(self) -> bool {
         !self.0.glyphs.is_empty()
+            || !self.0.device_faces.preferred.is_empty()
+            || !self.0.device_faces.fallback.is_empty()
     }
 
     /// Returns a glyph entry by index.
@@ -165,6 +353,9 @@ impl<'gc> Font<'gc> {
     pub fn get_glyph_for_char(self, c: char) -> Option<Glyph> {
         // TODO: Properly handle UTF-16/out-of-bounds code points.
         let code_point = c as u16;
+        if let Some(Some(glyph)) = self.0.device_glyphs.borrow().get(&code_point) {
+            return Some(glyph.clone());
+        }
         if let Some(index) = self.0.code_point_to_glyph.get(&code_point) {
             self.get_glyph(*index)
         } else {
@@ -0,0 +1,1546 @@
+//! OpenType font parsing.
+//!
+//! This is used both for `DefineFont4` tags, which embed an entire OpenType
+//! font with CFF (PostScript) outlines, and for the TrueType/OpenType files
+//! that device text is rendered with. We only extract what the text renderer
+//! needs: names and style, the character map, horizontal metrics and the
+//! glyph outlines, which are converted into SWF shape records.
+
+use std::convert::TryFrom;
+use swf::{ShapeRecord, StyleChangeData, Twips};
+
+type Error = Box<dyn std::error::Error>;
+
+/// The maximum depth of nested subroutine calls in a charstring.
+const MAX_SUBR_DEPTH: usize = 10;
+
+/// The maximum number of operands on the charstring argument stack.
+const MAX_STACK_SIZE: usize = 48;
+
+/// The maximum depth of nested components in a TrueType composite glyph.
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+/// The EM-square size used by `DefineFont3` and `DefineFont4` glyph shapes.
+pub const EM_SQUARE_SIZE: f64 = 20480.0;
+
+/// Returns the number of font faces in a font file.
+///
+/// This is 1 for a single font, or the number of fonts in a TrueType
+/// collection (`.ttc`).
+pub fn face_count(data: &[u8]) -> Result<u32, Error> {
+    let mut stream = Stream::new(data);
+    if stream.read_bytes(4)? == b"ttcf" {
+        stream.skip(4)?;
+        Ok(stream.read_u32()?)
+    } else {
+        Ok(1)
+    }
+}
+
+/// A parsed OpenType font face.
+pub struct OpenTypeFont<'a> {
+    tables: TableDirectory<'a>,
+    units_per_em: u16,
+    ascender: i16,
+    descender: i16,
+    line_gap: i16,
+    num_glyphs: u16,
+    advances: Vec<u16>,
+    outlines: Outlines<'a>,
+}
+
+/// The glyph outlines of a font, which are either PostScript or TrueType.
+enum Outlines<'a> {
+    Cff(Cff<'a>),
+    TrueType(TrueType<'a>),
+}
+
+impl<'a> OpenTypeFont<'a> {
+    /// Parse an OpenType font from its raw bytes.
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        Self::parse_face(data, 0)
+    }
+
+    /// Parse a font face from a font file. `index` selects the face in a
+    /// TrueType collection and must be 0 for other files.
+    pub fn parse_face(data: &'a [u8], index: u32) -> Result<Self, Error> {
+        let tables = TableDirectory::parse(data, index)?;
+
+        let head = tables.table(b"head")?;
+        let units_per_em = Stream::new_at(head, 18)?.read_u16()?;
+        if units_per_em == 0 {
+            return Err("Font has an invalid unitsPerEm".into());
+        }
+
+        let mut hhea = Stream::new_at(tables.table(b"hhea")?, 4)?;
+        let ascender = hhea.read_i16()?;
+        let descender = hhea.read_i16()?;
+        let line_gap = hhea.read_i16()?;
+        let num_h_metrics = Stream::new_at(tables.table(b"hhea")?, 34)?.read_u16()?;
+
+        let num_glyphs = Stream::new_at(tables.table(b"maxp")?, 4)?.read_u16()?;
+
+        let mut hmtx = Stream::new(tables.table(b"hmtx")?);
+        let mut advances = Vec::with_capacity(num_h_metrics.into());
+        for _ in 0..num_h_metrics {
+            advances.push(hmtx.read_u16()?);
+            hmtx.skip(2)?;
+        }
+
+        let outlines = if let Ok(cff) = tables.table(b"CFF ") {
+            Outlines::Cff(Cff::parse(cff)?)
+        } else {
+            let is_long_loca = Stream::new_at(head, 50)?.read_i16()? != 0;
+            Outlines::TrueType(TrueType {
+                glyf: tables.table(b"glyf")?,
+                loca: tables.table(b"loca")?,
+                is_long_loca,
+            })
+        };
+
+        Ok(Self {
+            tables,
+            units_per_em,
+            ascender,
+            descender,
+            line_gap,
+            num_glyphs,
+            advances,
+            outlines,
+        })
+    }
+
+    /// The pairs of (code point, glyph index) defined by this font.
+    ///
+    /// Only code points in the Basic Multilingual Plane are included.
+    pub fn character_map(&self) -> Result<Vec<(u16, u16)>, Error> {
+        parse_cmap(self.tables.table(b"cmap")?)
+    }
+
+    /// The family names of this font, in every language the font provides.
+    ///
+    /// Both the typographic family (e.g. "Noto Sans") and the legacy family
+    /// (e.g. "Noto Sans Light") are included.
+    pub fn family_names(&self) -> Vec<String> {
+        match self.tables.table(b"name") {
+            Ok(name) => parse_family_names(name).unwrap_or_default(),
+            Err(_) => vec![],
+        }
+    }
+
+    /// The style bits of this font: bit 0 for bold and bit 1 for italic.
+    fn style(&self) -> u16 {
+        if let Ok(fs_selection) = self
+            .tables
+            .table(b"OS/2")
+            .and_then(|os2| Stream::new_at(os2, 62)?.read_u16())
+        {
+            // `fsSelection` has italic in bit 0 and bold in bit 5.
+            ((fs_selection >> 5) & 1) | ((fs_selection & 1) << 1)
+        } else {
+            // `macStyle` already uses the same bits.
+            self.tables
+                .table(b"head")
+                .and_then(|head| Stream::new_at(head, 44)?.read_u16())
+                .unwrap_or(0)
+        }
+    }
+
+    /// Whether this is a bold face.
+    pub fn is_bold(&self) -> bool {
+        self.style() & 1 != 0
+    }
+
+    /// Whether this is an italic face.
+    pub fn is_italic(&self) -> bool {
+        self.style() & 2 != 0
+    }
+
+    /// The scale needed to convert from font units to the EM square of
+    /// `DefineFont3` glyphs.
+    fn scale(&self) -> f64 {
+        EM_SQUARE_SIZE / f64::from(self.units_per_em)
+    }
+
+    /// Convert a distance in font units to the `DefineFont3` EM square.
+    fn to_em_square(&self, value: f64) -> f64 {
+        (value * self.scale()).round()
+    }
+
+    /// The ascent of the font, in `DefineFont3` EM-square units.
+    pub fn ascent(&self) -> u16 {
+        self.to_em_square(f64::from(self.ascender).max(0.0))
+            .min(f64::from(u16::MAX)) as u16
+    }
+
+    /// The descent of the font, in `DefineFont3` EM-square units.
+    pub fn descent(&self) -> u16 {
+        self.to_em_square(-f64::from(self.descender).min(0.0))
+            .min(f64::from(u16::MAX)) as u16
+    }
+
+    /// The leading of the font, in `DefineFont3` EM-square units.
+    pub fn leading(&self) -> i16 {
+        self.to_em_square(f64::from(self.line_gap))
+            .max(f64::from(i16::MIN))
+            .min(f64::from(i16::MAX)) as i16
+    }
+
+    /// The horizontal advance of a glyph, in `DefineFont3` EM-square units.
+    pub fn advance(&self, glyph_id: u16) -> i16 {
+        let advance = self
+            .advances
+            .get(usize::from(glyph_id))
+            .or_else(|| self.advances.last())
+            .copied()
+            .unwrap_or(0);
+        self.to_em_square(f64::from(advance))
+            .min(f64::from(i16::MAX)) as i16
+    }
+
+    /// Decode the outline of a glyph into SWF shape records, in
+    /// `DefineFont3` EM-square units.
+    pub fn glyph_shape_records(&self, glyph_id: u16) -> Result<Vec<ShapeRecord>, Error> {
+        if glyph_id >= self.num_glyphs {
+            return Err("Glyph index is out of range".into());
+        }
+
+        let contours = match &self.outlines {
+            Outlines::Cff(cff) => cff.glyph_outline(glyph_id)?,
+            Outlines::TrueType(true_type) => true_type.glyph_outline(glyph_id, 0)?,
+        };
+        Ok(contours_to_shape_records(&contours, self.scale()))
+    }
+}
+
+/// A cursor over big-endian font data.
+struct Stream<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Stream<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn new_at(data: &'a [u8], pos: usize) -> Result<Self, Error> {
+        if pos > data.len() {
+            return Err("Unexpected end of font data".into());
+        }
+        Ok(Self { data, pos })
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or("Unexpected end of font data")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Error> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_i16(&mut self) -> Result<i16, Error> {
+        Ok(self.read_u16()? as i16)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read an unsigned offset of 1 to 4 bytes, as used by CFF.
+    fn read_offset(&mut self, size: u8) -> Result<usize, Error> {
+        if size == 0 || size > 4 {
+            return Err("Invalid CFF offset size".into());
+        }
+        let mut offset = 0usize;
+        for byte in self.read_bytes(size.into())? {
+            offset = (offset << 8) | usize::from(*byte);
+        }
+        Ok(offset)
+    }
+}
+
+/// The table directory at the start of an SFNT file.
+struct TableDirectory<'a> {
+    data: &'a [u8],
+    tables: Vec<([u8; 4], usize, usize)>,
+}
+
+impl<'a> TableDirectory<'a> {
+    fn parse(data: &'a [u8], index: u32) -> Result<Self, Error> {
+        let mut stream = Stream::new(data);
+        let offset = if stream.read_bytes(4)? == b"ttcf" {
+            // A TrueType collection, whose header lists the offset of each
+            // font's table directory.
+            stream.skip(4)?;
+            if index >= stream.read_u32()? {
+                return Err("Font collection index is out of range".into());
+            }
+            stream.skip(index as usize * 4)?;
+            stream.read_u32()? as usize
+        } else if index == 0 {
+            0
+        } else {
+            return Err("Font collection index is out of range".into());
+        };
+
+        let mut stream = Stream::new_at(data, offset)?;
+        match stream.read_bytes(4)? {
+            b"OTTO" | b"\0\x01\0\0" | b"true" => (),
+            _ => return Err("Font data is not an OpenType font".into()),
+        }
+
+        let num_tables = stream.read_u16()?;
+        stream.skip(6)?;
+        let mut tables = Vec::with_capacity(num_tables.into());
+        for _ in 0..num_tables {
+            let mut tag = [0; 4];
+            tag.copy_from_slice(stream.read_bytes(4)?);
+            stream.skip(4)?;
+            let offset = stream.read_u32()? as usize;
+            let length = stream.read_u32()? as usize;
+            tables.push((tag, offset, length));
+        }
+
+        Ok(Self { data, tables })
+    }
+
+    fn table(&self, tag: &[u8; 4]) -> Result<&'a [u8], Error> {
+        let (_, offset, length) = self
+            .tables
+            .iter()
+            .find(|(table_tag, _, _)| table_tag == tag)
+            .ok_or_else(|| format!("Missing '{}' table", String::from_utf8_lossy(tag)))?;
+        offset
+            .checked_add(*length)
+            .and_then(|end| self.data.get(*offset..end))
+            .ok_or_else(|| "Font table is out of bounds".into())
+    }
+}
+
+/// Parse the `cmap` table into (code point, glyph index) pairs.
+///
+/// A Unicode subtable is preferred, using format 12 if available and
+/// otherwise format 4.
+fn parse_cmap(data: &[u8]) -> Result<Vec<(u16, u16)>, Error> {
+    let mut stream = Stream::new(data);
+    stream.skip(2)?;
+    let num_subtables = stream.read_u16()?;
+
+    let mut best: Option<(u8, usize)> = None;
+    for _ in 0..num_subtables {
+        let platform_id = stream.read_u16()?;
+        let encoding_id = stream.read_u16()?;
+        let offset = stream.read_u32()? as usize;
+        let format = Stream::new_at(data, offset)?.read_u16()?;
+        let priority = match (platform_id, encoding_id, format) {
+            (3, 10, 12) | (0, _, 12) => 4,
+            (3, 1, 4) => 3,
+            (0, _, 4) => 2,
+            (3, 0, 4) => 1,
+            _ => continue,
+        };
+        if best.map(|(p, _)| priority > p).unwrap_or(true) {
+            best = Some((priority, offset));
+        }
+    }
+
+    let offset = match best {
+        Some((_, offset)) => offset,
+        None => return Err("Font has no supported character map".into()),
+    };
+    let mut stream = Stream::new_at(data, offset)?;
+    match stream.read_u16()? {
+        4 => parse_cmap_format_4(data, offset),
+        _ => parse_cmap_format_12(data, offset),
+    }
+}
+
+fn parse_cmap_format_4(data: &[u8], offset: usize) -> Result<Vec<(u16, u16)>, Error> {
+    let mut stream = Stream::new_at(data, offset + 6)?;
+    let seg_count = usize::from(stream.read_u16()? / 2);
+    let end_codes_pos = offset + 14;
+    let start_codes_pos = end_codes_pos + seg_count * 2 + 2;
+    let id_deltas_pos = start_codes_pos + seg_count * 2;
+    let id_range_offsets_pos = id_deltas_pos + seg_count * 2;
+
+    let read_u16_at = |pos: usize| Stream::new_at(data, pos).and_then(|mut s| s.read_u16());
+
+    let mut mapping = vec![];
+    for i in 0..seg_count {
+        let end_code = read_u16_at(end_codes_pos + i * 2)?;
+        let start_code = read_u16_at(start_codes_pos + i * 2)?;
+        let id_delta = read_u16_at(id_deltas_pos + i * 2)?;
+        let id_range_offset_pos = id_range_offsets_pos + i * 2;
+        let id_range_offset = read_u16_at(id_range_offset_pos)?;
+
+        for code in start_code..=end_code {
+            if code == 0xFFFF {
+                break;
+            }
+            let glyph_id = if id_range_offset == 0 {
+                code.wrapping_add(id_delta)
+            } else {
+                let pos = id_range_offset_pos
+                    + usize::from(id_range_offset)
+                    + usize::from(code - start_code) * 2;
+                match read_u16_at(pos)? {
+                    0 => 0,
+                    glyph_id => glyph_id.wrapping_add(id_delta),
+                }
+            };
+            if glyph_id != 0 {
+                mapping.push((code, glyph_id));
+            }
+        }
+    }
+    Ok(mapping)
+}
+
+fn parse_cmap_format_12(data: &[u8], offset: usize) -> Result<Vec<(u16, u16)>, Error> {
+    let mut stream = Stream::new_at(data, offset + 12)?;
+    let num_groups = stream.read_u32()?;
+
+    let mut mapping = vec![];
+    for _ in 0..num_groups {
+        let start_code = stream.read_u32()?;
+        let end_code = stream.read_u32()?.min(0xFFFF);
+        let start_glyph_id = stream.read_u32()?;
+        for code in start_code..=end_code {
+            let glyph_id = start_glyph_id + (code - start_code);
+            if glyph_id != 0 && glyph_id <= 0xFFFF {
+                mapping.push((code as u16, glyph_id as u16));
+            }
+        }
+    }
+    Ok(mapping)
+}
+
+/// Parse the family names (name IDs 16 and 1) from the `name` table.
+fn parse_family_names(data: &[u8]) -> Result<Vec<String>, Error> {
+    let mut stream = Stream::new(data);
+    stream.skip(2)?;
+    let count = stream.read_u16()?;
+    let storage_offset = usize::from(stream.read_u16()?);
+
+    let mut typographic_names = vec![];
+    let mut names = vec![];
+    for _ in 0..count {
+        let platform_id = stream.read_u16()?;
+        let encoding_id = stream.read_u16()?;
+        stream.skip(2)?;
+        let name_id = stream.read_u16()?;
+        let length = usize::from(stream.read_u16()?);
+        let offset = storage_offset + usize::from(stream.read_u16()?);
+        if name_id != 1 && name_id != 16 {
+            continue;
+        }
+
+        let bytes = Stream::new_at(data, offset)?.read_bytes(length)?;
+        let name = match (platform_id, encoding_id) {
+            // Unicode and Windows names are UTF-16BE.
+            (0, _) | (3, 1) | (3, 10) => {
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            // Mac Roman; only the ASCII subset is interpreted.
+            (1, 0) => bytes.iter().map(|b| char::from(*b)).collect(),
+            _ => continue,
+        };
+
+        let list = if name_id == 16 {
+            &mut typographic_names
+        } else {
+            &mut names
+        };
+        if !name.is_empty() && !list.contains(&name) {
+            list.push(name);
+        }
+    }
+
+    for name in names {
+        if !typographic_names.contains(&name) {
+            typographic_names.push(name);
+        }
+    }
+    Ok(typographic_names)
+}
+
+/// A CFF INDEX structure: an array of variable-sized objects.
+#[derive(Default)]
+struct Index<'a> {
+    items: Vec<&'a [u8]>,
+}
+
+impl<'a> Index<'a> {
+    /// Parse the INDEX at the stream's position, leaving the stream after it.
+    fn parse(stream: &mut Stream<'a>) -> Result<Self, Error> {
+        let count = usize::from(stream.read_u16()?);
+        if count == 0 {
+            return Ok(Self::default());
+        }
+
+        let offset_size = stream.read_u8()?;
+        let mut offsets = Vec::with_capacity(count + 1);
+        for _ in 0..=count {
+            offsets.push(stream.read_offset(offset_size)?);
+        }
+
+        let data = stream.read_bytes(offsets[count].saturating_sub(1))?;
+        let mut items = Vec::with_capacity(count);
+        for window in offsets.windows(2) {
+            // Offsets are relative to the byte before the object data.
+            let item = match (window[0].checked_sub(1), window[1].checked_sub(1)) {
+                (Some(start), Some(end)) if start <= end => data.get(start..end),
+                _ => None,
+            }
+            .ok_or("Invalid CFF INDEX offsets")?;
+            items.push(item);
+        }
+        Ok(Self { items })
+    }
+
+    fn parse_at(data: &'a [u8], offset: usize) -> Result<Self, Error> {
+        Self::parse(&mut Stream::new_at(data, offset)?)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn get(&self, i: usize) -> Option<&'a [u8]> {
+        self.items.get(i).copied()
+    }
+
+    /// The bias added to subroutine numbers when this INDEX holds subroutines.
+    fn subr_bias(&self) -> i32 {
+        if self.len() < 1240 {
+            107
+        } else if self.len() < 33900 {
+            1131
+        } else {
+            32768
+        }
+    }
+}
+
+/// Top DICT operators that we make use of.
+const OP_CHARSTRING_TYPE: u16 = 1206;
+const OP_CHAR_STRINGS: u16 = 17;
+const OP_PRIVATE: u16 = 18;
+const OP_SUBRS: u16 = 19;
+const OP_ROS: u16 = 1230;
+const OP_FD_ARRAY: u16 = 1236;
+const OP_FD_SELECT: u16 = 1237;
+
+/// A parsed CFF DICT, as a list of operators and their operands.
+struct Dict {
+    entries: Vec<(u16, Vec<f64>)>,
+}
+
+impl Dict {
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut stream = Stream::new(data);
+        let mut entries = vec![];
+        let mut operands = vec![];
+        while !stream.is_at_end() {
+            let b0 = stream.read_u8()?;
+            match b0 {
+                0..=11 | 13..=21 => entries.push((u16::from(b0), std::mem::take(&mut operands))),
+                12 => {
+                    let b1 = stream.read_u8()?;
+                    entries.push((1200 + u16::from(b1), std::mem::take(&mut operands)));
+                }
+                28 => operands.push(f64::from(stream.read_i16()?)),
+                29 => operands.push(f64::from(stream.read_u32()? as i32)),
+                30 => operands.push(Self::read_real(&mut stream)?),
+                32..=254 => operands.push(f64::from(read_short_int(b0, &mut stream)?)),
+                _ => return Err("Invalid CFF DICT data".into()),
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Read a real number operand, encoded as packed decimal nibbles.
+    fn read_real(stream: &mut Stream) -> Result<f64, Error> {
+        let mut text = String::new();
+        loop {
+            let byte = stream.read_u8()?;
+            for nibble in &[byte >> 4, byte & 0xF] {
+                match nibble {
+                    0..=9 => text.push((b'0' + nibble) as char),
+                    0xA => text.push('.'),
+                    0xB => text.push('E'),
+                    0xC => text.push_str("E-"),
+                    0xE => text.push('-'),
+                    0xF => return Ok(text.parse().unwrap_or(0.0)),
+                    _ => return Err("Invalid CFF real number".into()),
+                }
+            }
+        }
+    }
+
+    fn get(&self, op: u16) -> Option<&[f64]> {
+        self.entries
+            .iter()
+            .find(|(entry_op, _)| *entry_op == op)
+            .map(|(_, operands)| &operands[..])
+    }
+
+    fn get_offset(&self, op: u16) -> Option<usize> {
+        self.get(op)
+            .and_then(|operands| operands.first())
+            .filter(|offset| **offset >= 0.0)
+            .map(|offset| *offset as usize)
+    }
+
+    /// Load the local subroutines referenced by the Private DICT operator.
+    fn private_subrs<'a>(&self, cff: &'a [u8]) -> Result<Index<'a>, Error> {
+        let (size, offset) = match self.get(OP_PRIVATE) {
+            Some([size, offset]) if *size >= 0.0 && *offset >= 0.0 => {
+                (*size as usize, *offset as usize)
+            }
+            _ => return Ok(Index::default()),
+        };
+        let private_data = offset
+            .checked_add(size)
+            .and_then(|end| cff.get(offset..end))
+            .ok_or("Private DICT is out of bounds")?;
+        match Dict::parse(private_data)?.get_offset(OP_SUBRS) {
+            Some(subrs_offset) => Index::parse_at(cff, offset + subrs_offset),
+            None => Ok(Index::default()),
+        }
+    }
+}
+
+/// Read an integer encoded in one or two bytes, shared by DICTs and
+/// charstrings.
+fn read_short_int(b0: u8, stream: &mut Stream) -> Result<i32, Error> {
+    let b0 = i32::from(b0);
+    Ok(match b0 {
+        32..=246 => b0 - 139,
+        247..=250 => (b0 - 247) * 256 + i32::from(stream.read_u8()?) + 108,
+        _ => -(b0 - 251) * 256 - i32::from(stream.read_u8()?) - 108,
+    })
+}
+
+/// The parts of a CFF table needed to decode glyph outlines.
+struct Cff<'a> {
+    char_strings: Index<'a>,
+    global_subrs: Index<'a>,
+
+    /// The local subroutines of each Font DICT. Non-CID fonts have just one.
+    local_subrs: Vec<Index<'a>>,
+
+    /// For CID-keyed fonts, the Font DICT used by each glyph.
+    fd_select: Option<Vec<u8>>,
+}
+
+impl<'a> Cff<'a> {
+    fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let mut stream = Stream::new(data);
+        if stream.read_u8()? != 1 {
+            return Err("Unsupported CFF version".into());
+        }
+        stream.skip(1)?;
+        let header_size = stream.read_u8()?;
+
+        let mut stream = Stream::new_at(data, header_size.into())?;
+        let _names = Index::parse(&mut stream)?;
+        let top_dicts = Index::parse(&mut stream)?;
+        let _strings = Index::parse(&mut stream)?;
+        let global_subrs = Index::parse(&mut stream)?;
+
+        let top_dict = Dict::parse(top_dicts.get(0).ok_or("CFF has no Top DICT")?)?;
+        if let Some(&[charstring_type]) = top_dict.get(OP_CHARSTRING_TYPE) {
+            if charstring_type as i32 != 2 {
+                return Err("Only Type 2 charstrings are supported".into());
+            }
+        }
+
+        let char_strings = Index::parse_at(
+            data,
+            top_dict
+                .get_offset(OP_CHAR_STRINGS)
+                .ok_or("CFF has no CharStrings")?,
+        )?;
+
+        let (local_subrs, fd_select) = if top_dict.get(OP_ROS).is_some() {
+            let fd_array = Index::parse_at(
+                data,
+                top_dict
+                    .get_offset(OP_FD_ARRAY)
+                    .ok_or("CID font has no FDArray")?,
+            )?;
+            let mut local_subrs = Vec::with_capacity(fd_array.len());
+            for font_dict in &fd_array.items {
+                local_subrs.push(Dict::parse(font_dict)?.private_subrs(data)?);
+            }
+            let fd_select = parse_fd_select(
+                data,
+                top_dict
+                    .get_offset(OP_FD_SELECT)
+                    .ok_or("CID font has no FDSelect")?,
+                char_strings.len(),
+            )?;
+            (local_subrs, Some(fd_select))
+        } else {
+            (vec![top_dict.private_subrs(data)?], None)
+        };
+
+        Ok(Self {
+            char_strings,
+            global_subrs,
+            local_subrs,
+            fd_select,
+        })
+    }
+
+    fn glyph_outline(&self, glyph_id: u16) -> Result<Vec<Contour>, Error> {
+        let glyph_id = usize::from(glyph_id);
+        let char_string = self
+            .char_strings
+            .get(glyph_id)
+            .ok_or("Glyph has no charstring")?;
+        let font_dict = match &self.fd_select {
+            Some(fd_select) => usize::from(*fd_select.get(glyph_id).unwrap_or(&0)),
+            None => 0,
+        };
+        let local_subrs = self
+            .local_subrs
+            .get(font_dict)
+            .ok_or("Glyph references a missing Font DICT")?;
+
+        let mut interpreter = CharStringInterpreter {
+            global_subrs: &self.global_subrs,
+            local_subrs,
+            stack: Vec::with_capacity(MAX_STACK_SIZE),
+            num_stems: 0,
+            has_width: false,
+            x: 0.0,
+            y: 0.0,
+            contours: vec![],
+        };
+        interpreter.execute(char_string, 0)?;
+        interpreter.close_contour();
+        Ok(interpreter.contours)
+    }
+}
+
+fn parse_fd_select(data: &[u8], offset: usize, num_glyphs: usize) -> Result<Vec<u8>, Error> {
+    let mut stream = Stream::new_at(data, offset)?;
+    match stream.read_u8()? {
+        0 => Ok(stream.read_bytes(num_glyphs)?.to_vec()),
+        3 => {
+            let num_ranges = stream.read_u16()?;
+            let mut fd_select = vec![0; num_glyphs];
+            let mut first = usize::from(stream.read_u16()?);
+            for _ in 0..num_ranges {
+                let font_dict = stream.read_u8()?;
+                let next = usize::from(stream.read_u16()?);
+                for entry in fd_select.iter_mut().take(next).skip(first) {
+                    *entry = font_dict;
+                }
+                first = next;
+            }
+            Ok(fd_select)
+        }
+        _ => Err("Unsupported FDSelect format".into()),
+    }
+}
+
+/// TrueType (quadratic) glyph outlines, stored in the `glyf` table.
+struct TrueType<'a> {
+    glyf: &'a [u8],
+    loca: &'a [u8],
+    is_long_loca: bool,
+}
+
+impl TrueType<'_> {
+    /// The glyph data for a glyph, which is empty for glyphs without an outline.
+    fn glyph_data(&self, glyph_id: u16) -> Result<&[u8], Error> {
+        let i = usize::from(glyph_id);
+        let (start, end) = if self.is_long_loca {
+            let mut stream = Stream::new_at(self.loca, i * 4)?;
+            (stream.read_u32()? as usize, stream.read_u32()? as usize)
+        } else {
+            let mut stream = Stream::new_at(self.loca, i * 2)?;
+            (
+                usize::from(stream.read_u16()?) * 2,
+                usize::from(stream.read_u16()?) * 2,
+            )
+        };
+        if start == end {
+            return Ok(&[]);
+        }
+        self.glyf
+            .get(start..end)
+            .ok_or_else(|| "Glyph data is out of bounds".into())
+    }
+
+    fn glyph_outline(&self, glyph_id: u16, depth: usize) -> Result<Vec<Contour>, Error> {
+        if depth > MAX_COMPONENT_DEPTH {
+            return Err("Composite glyphs are nested too deeply".into());
+        }
+
+        let data = self.glyph_data(glyph_id)?;
+        if data.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut stream = Stream::new(data);
+        let num_contours = stream.read_i16()?;
+        stream.skip(8)?;
+        if num_contours >= 0 {
+            Self::simple_glyph_outline(&mut stream, num_contours as usize)
+        } else {
+            self.composite_glyph_outline(&mut stream, depth)
+        }
+    }
+
+    fn simple_glyph_outline(
+        stream: &mut Stream,
+        num_contours: usize,
+    ) -> Result<Vec<Contour>, Error> {
+        const ON_CURVE: u8 = 0x1;
+        const X_SHORT: u8 = 0x2;
+        const Y_SHORT: u8 = 0x4;
+        const REPEAT: u8 = 0x8;
+        const X_SAME_OR_POSITIVE: u8 = 0x10;
+        const Y_SAME_OR_POSITIVE: u8 = 0x20;
+
+        let mut end_points = Vec::with_capacity(num_contours);
+        for _ in 0..num_contours {
+            end_points.push(usize::from(stream.read_u16()?));
+        }
+        let num_points = end_points.last().map(|end| end + 1).unwrap_or(0);
+        let instructions_length = usize::from(stream.read_u16()?);
+        stream.skip(instructions_length)?;
+
+        let mut flags = Vec::with_capacity(num_points);
+        while flags.len() < num_points {
+            let flag = stream.read_u8()?;
+            flags.push(flag);
+            if flag & REPEAT != 0 {
+                for _ in 0..stream.read_u8()? {
+                    flags.push(flag);
+                }
+            }
+        }
+        flags.truncate(num_points);
+
+        let mut read_coordinates = |short: u8, same_or_positive: u8| -> Result<Vec<f64>, Error> {
+            let mut value = 0i32;
+            let mut values = Vec::with_capacity(num_points);
+            for flag in &flags {
+                if flag & short != 0 {
+                    let delta = i32::from(stream.read_u8()?);
+                    value += if flag & same_or_positive != 0 {
+                        delta
+                    } else {
+                        -delta
+                    };
+                } else if flag & same_or_positive == 0 {
+                    value += i32::from(stream.read_i16()?);
+                }
+                values.push(f64::from(value));
+            }
+            Ok(values)
+        };
+        let xs = read_coordinates(X_SHORT, X_SAME_OR_POSITIVE)?;
+        let ys = read_coordinates(Y_SHORT, Y_SAME_OR_POSITIVE)?;
+
+        let mut contours = Vec::with_capacity(num_contours);
+        let mut start = 0;
+        for end in end_points {
+            if end < start || end >= num_points {
+                return Err("Invalid glyph contour end point".into());
+            }
+            let points: Vec<(Point, bool)> = (start..=end)
+                .map(|i| (Point { x: xs[i], y: ys[i] }, flags[i] & ON_CURVE != 0))
+                .collect();
+            contours.push(Self::quadratic_contour(&points));
+            start = end + 1;
+        }
+        Ok(contours)
+    }
+
+    /// Build a contour from TrueType points. Two consecutive off-curve
+    /// points imply an on-curve point halfway between them.
+    fn quadratic_contour(points: &[(Point, bool)]) -> Contour {
+        let mid = |a: Point, b: Point| Point {
+            x: (a.x + b.x) / 2.0,
+            y: (a.y + b.y) / 2.0,
+        };
+
+        // Find an on-curve point to start from.
+        let (first, first_on_curve) = points[0];
+        let (last, last_on_curve) = points[points.len() - 1];
+        let (start, rest) = if first_on_curve {
+            (first, &points[1..])
+        } else if last_on_curve {
+            (last, &points[..points.len() - 1])
+        } else {
+            (mid(last, first), points)
+        };
+
+        let mut segments = vec![];
+        let mut control: Option<Point> = None;
+        for &(point, on_curve) in rest.iter().chain(std::iter::once(&(start, true))) {
+            match (control, on_curve) {
+                (None, true) => segments.push(Segment::Line(point)),
+                (None, false) => control = Some(point),
+                (Some(c), true) => {
+                    segments.push(Segment::Quadratic(c, point));
+                    control = None;
+                }
+                (Some(c), false) => {
+                    segments.push(Segment::Quadratic(c, mid(c, point)));
+                    control = Some(point);
+                }
+            }
+        }
+
+        Contour { start, segments }
+    }
+
+    fn composite_glyph_outline(
+        &self,
+        stream: &mut Stream,
+        depth: usize,
+    ) -> Result<Vec<Contour>, Error> {
+        const ARGS_ARE_WORDS: u16 = 0x1;
+        const ARGS_ARE_XY_VALUES: u16 = 0x2;
+        const HAS_SCALE: u16 = 0x8;
+        const MORE_COMPONENTS: u16 = 0x20;
+        const HAS_X_AND_Y_SCALE: u16 = 0x40;
+        const HAS_TWO_BY_TWO: u16 = 0x80;
+
+        let read_f2dot14 = |stream: &mut Stream| -> Result<f64, Error> {
+            Ok(f64::from(stream.read_i16()?) / 16384.0)
+        };
+
+        let mut contours = vec![];
+        loop {
+            let flags = stream.read_u16()?;
+            let glyph_id = stream.read_u16()?;
+            let (arg1, arg2) = if flags & ARGS_ARE_WORDS != 0 {
+                (f64::from(stream.read_i16()?), f64::from(stream.read_i16()?))
+            } else {
+                (
+                    f64::from(stream.read_u8()? as i8),
+                    f64::from(stream.read_u8()? as i8),
+                )
+            };
+            // Matching points instead of offsets is rare and not supported.
+            let (dx, dy) = if flags & ARGS_ARE_XY_VALUES != 0 {
+                (arg1, arg2)
+            } else {
+                (0.0, 0.0)
+            };
+
+            let (mut a, mut b, mut c, mut d) = (1.0, 0.0, 0.0, 1.0);
+            if flags & HAS_SCALE != 0 {
+                a = read_f2dot14(stream)?;
+                d = a;
+            } else if flags & HAS_X_AND_Y_SCALE != 0 {
+                a = read_f2dot14(stream)?;
+                d = read_f2dot14(stream)?;
+            } else if flags & HAS_TWO_BY_TWO != 0 {
+                a = read_f2dot14(stream)?;
+                b = read_f2dot14(stream)?;
+                c = read_f2dot14(stream)?;
+                d = read_f2dot14(stream)?;
+            }
+
+            let transform = |p: Point| Point {
+                x: a * p.x + c * p.y + dx,
+                y: b * p.x + d * p.y + dy,
+            };
+            for contour in self.glyph_outline(glyph_id, depth + 1)? {
+                contours.push(contour.transformed(transform));
+            }
+
+            if flags & MORE_COMPONENTS == 0 {
+                break;
+            }
+        }
+        Ok(contours)
+    }
+}
+
+/// A point in font units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+/// A segment of a glyph outline, ending at the given point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Segment {
+    Line(Point),
+    Quadratic(Point, Point),
+    Cubic(Point, Point, Point),
+}
+
+/// A closed glyph contour.
+#[derive(Clone, Debug, PartialEq)]
+struct Contour {
+    start: Point,
+    segments: Vec<Segment>,
+}
+
+impl Contour {
+    /// Apply a transformation to every point of this contour.
+    fn transformed(self, transform: impl Fn(Point) -> Point) -> Self {
+        let segments = self
+            .segments
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Line(end) => Segment::Line(transform(end)),
+                Segment::Quadratic(control, end) => {
+                    Segment::Quadratic(transform(control), transform(end))
+                }
+                Segment::Cubic(c1, c2, end) => {
+                    Segment::Cubic(transform(c1), transform(c2), transform(end))
+                }
+            })
+            .collect();
+        Self {
+            start: transform(self.start),
+            segments,
+        }
+    }
+}
+
+/// An interpreter for Type 2 charstrings (Adobe Technical Note #5177).
+struct CharStringInterpreter<'a, 'b> {
+    global_subrs: &'b Index<'a>,
+    local_subrs: &'b Index<'a>,
+    stack: Vec<f64>,
+    num_stems: usize,
+
+    /// Whether the optional glyph width at the start of the charstring has
+    /// been handled. Advances come from `hmtx`, so the width is discarded.
+    has_width: bool,
+    x: f64,
+    y: f64,
+    contours: Vec<Contour>,
+}
+
+impl CharStringInterpreter<'_, '_> {
+    /// Run a charstring. Returns `true` once `endchar` is reached.
+    fn execute(&mut self, data: &[u8], depth: usize) -> Result<bool, Error> {
+        if depth > MAX_SUBR_DEPTH {
+            return Err("Charstring subroutines are nested too deeply".into());
+        }
+
+        let mut stream = Stream::new(data);
+        while !stream.is_at_end() {
+            let b0 = stream.read_u8()?;
+            match b0 {
+                // hstem, vstem, hstemhm, vstemhm
+                1 | 3 | 18 | 23 => {
+                    self.take_width(self.stack.len() % 2 == 1);
+                    self.num_stems += self.stack.len() / 2;
+                    self.stack.clear();
+                }
+                // vmoveto
+                4 => {
+                    self.take_width(self.stack.len() > 1);
+                    let dy = self.arg(0)?;
+                    self.move_to(0.0, dy);
+                }
+                // rlineto
+                5 => {
+                    let args = std::mem::take(&mut self.stack);
+                    for pair in args.chunks_exact(2) {
+                        self.x += pair[0];
+                        self.y += pair[1];
+                        self.push_segment(Segment::Line(self.point()));
+                    }
+                }
+                // hlineto, vlineto
+                6 | 7 => {
+                    let args = std::mem::take(&mut self.stack);
+                    let mut horizontal = b0 == 6;
+                    for delta in args {
+                        if horizontal {
+                            self.x += delta;
+                        } else {
+                            self.y += delta;
+                        }
+                        self.push_segment(Segment::Line(self.point()));
+                        horizontal = !horizontal;
+                    }
+                }
+                // rrcurveto
+                8 => {
+                    let args = std::mem::take(&mut self.stack);
+                    for c in args.chunks_exact(6) {
+                        self.curve_to(c[0], c[1], c[2], c[3], c[4], c[5]);
+                    }
+                }
+                // callsubr, callgsubr
+                10 | 29 => {
+                    let subrs = if b0 == 10 {
+                        self.local_subrs
+                    } else {
+                        self.global_subrs
+                    };
+                    let index = self.stack.pop().ok_or("Charstring stack underflow")? as i32
+                        + subrs.subr_bias();
+                    let subr = usize::try_from(index)
+                        .ok()
+                        .and_then(|index| subrs.get(index))
+                        .ok_or("Charstring calls a missing subroutine")?;
+                    if self.execute(subr, depth + 1)? {
+                        return Ok(true);
+                    }
+                }
+                // return
+                11 => return Ok(false),
+                // endchar
+                14 => {
+                    self.take_width(self.stack.len() == 1 || self.stack.len() == 5);
+                    self.stack.clear();
+                    return Ok(true);
+                }
+                // hintmask, cntrmask
+                19 | 20 => {
+                    // Any remaining arguments are an implicit vstemhm.
+                    self.take_width(self.stack.len() % 2 == 1);
+                    self.num_stems += self.stack.len() / 2;
+                    self.stack.clear();
+                    stream.skip((self.num_stems + 7) / 8)?;
+                }
+                // rmoveto
+                21 => {
+                    self.take_width(self.stack.len() > 2);
+                    let dx = self.arg(0)?;
+                    let dy = self.arg(1)?;
+                    self.move_to(dx, dy);
+                }
+                // hmoveto
+                22 => {
+                    self.take_width(self.stack.len() > 1);
+                    let dx = self.arg(0)?;
+                    self.move_to(dx, 0.0);
+                }
+                // rcurveline
+                24 => {
+                    let args = std::mem::take(&mut self.stack);
+                    if args.len() < 8 {
+                        return Err("Charstring stack underflow".into());
+                    }
+                    let (curves, line) = args.split_at(args.len() - 2);
+                    for c in curves.chunks_exact(6) {
+                        self.curve_to(c[0], c[1], c[2], c[3], c[4], c[5]);
+                    }
+                    self.x += line[0];
+                    self.y += line[1];
+                    self.push_segment(Segment::Line(self.point()));
+                }
+                // rlinecurve
+                25 => {
+                    let args = std::mem::take(&mut self.stack);
+                    if args.len() < 8 {
+                        return Err("Charstring stack underflow".into());
+                    }
+                    let (lines, c) = args.split_at(args.len() - 6);
+                    for pair in lines.chunks_exact(2) {
+                        self.x += pair[0];
+                        self.y += pair[1];
+                        self.push_segment(Segment::Line(self.point()));
+                    }
+                    self.curve_to(c[0], c[1], c[2], c[3], c[4], c[5]);
+                }
+                // vvcurveto
+                26 => {
+                    let mut args = &std::mem::take(&mut self.stack)[..];
+                    let mut dx1 = 0.0;
+                    if args.len() % 4 == 1 {
+                        dx1 = args[0];
+                        args = &args[1..];
+                    }
+                    for c in args.chunks_exact(4) {
+                        self.curve_to(dx1, c[0], c[1], c[2], 0.0, c[3]);
+                        dx1 = 0.0;
+                    }
+                }
+                // hhcurveto
+                27 => {
+                    let mut args = &std::mem::take(&mut self.stack)[..];
+                    let mut dy1 = 0.0;
+                    if args.len() % 4 == 1 {
+                        dy1 = args[0];
+                        args = &args[1..];
+                    }
+                    for c in args.chunks_exact(4) {
+                        self.curve_to(c[0], dy1, c[1], c[2], c[3], 0.0);
+                        dy1 = 0.0;
+                    }
+                }
+                // vhcurveto, hvcurveto
+                30 | 31 => {
+                    let args = std::mem::take(&mut self.stack);
+                    let mut horizontal = b0 == 31;
+                    let mut i = 0;
+                    while i + 4 <= args.len() {
+                        let last = if args.len() - i == 5 {
+                            args[i + 4]
+                        } else {
+                            0.0
+                        };
+                        if horizontal {
+                            self.curve_to(
+                                args[i],
+                                0.0,
+                                args[i + 1],
+                                args[i + 2],
+                                last,
+                                args[i + 3],
+                            );
+                        } else {
+                            self.curve_to(
+                                0.0,
+                                args[i],
+                                args[i + 1],
+                                args[i + 2],
+                                args[i + 3],
+                                last,
+                            );
+                        }
+                        horizontal = !horizontal;
+                        i += 4;
+                    }
+                }
+                12 => {
+                    let b1 = stream.read_u8()?;
+                    self.execute_escape(b1)?;
+                }
+                28 => self.push(f64::from(stream.read_i16()?))?,
+                32..=254 => self.push(f64::from(read_short_int(b0, &mut stream)?))?,
+                255 => self.push(f64::from(stream.read_u32()? as i32) / 65536.0)?,
+                _ => return Err(format!("Unknown charstring operator {}", b0).into()),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Run a two-byte operator. Only the flex operators are supported.
+    fn execute_escape(&mut self, op: u8) -> Result<(), Error> {
+        let args = std::mem::take(&mut self.stack);
+        let arg = |i: usize| {
+            args.get(i)
+                .copied()
+                .ok_or_else(|| Error::from("Charstring stack underflow"))
+        };
+        match op {
+            // flex
+            35 => {
+                self.curve_to(arg(0)?, arg(1)?, arg(2)?, arg(3)?, arg(4)?, arg(5)?);
+                self.curve_to(arg(6)?, arg(7)?, arg(8)?, arg(9)?, arg(10)?, arg(11)?);
+            }
+            // hflex
+            34 => {
+                self.curve_to(arg(0)?, 0.0, arg(1)?, arg(2)?, arg(3)?, 0.0);
+                self.curve_to(arg(4)?, 0.0, arg(5)?, -arg(2)?, arg(6)?, 0.0);
+            }
+            // hflex1
+            36 => {
+                self.curve_to(arg(0)?, arg(1)?, arg(2)?, arg(3)?, arg(4)?, 0.0);
+                let dy6 = -(arg(1)? + arg(3)? + arg(7)?);
+                self.curve_to(arg(5)?, 0.0, arg(6)?, arg(7)?, arg(8)?, dy6);
+            }
+            // flex1
+            37 => {
+                let dx: f64 = (0..5).map(|i| args.get(i * 2).unwrap_or(&0.0)).sum();
+                let dy: f64 = (0..5).map(|i| args.get(i * 2 + 1).unwrap_or(&0.0)).sum();
+                let (dx6, dy6) = if dx.abs() > dy.abs() {
+                    (arg(10)?, -dy)
+                } else {
+                    (-dx, arg(10)?)
+                };
+                self.curve_to(arg(0)?, arg(1)?, arg(2)?, arg(3)?, arg(4)?, arg(5)?);
+                self.curve_to(arg(6)?, arg(7)?, arg(8)?, arg(9)?, dx6, dy6);
+            }
+            _ => return Err(format!("Unsupported charstring operator 12 {}", op).into()),
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, value: f64) -> Result<(), Error> {
+        if self.stack.len() >= MAX_STACK_SIZE {
+            return Err("Charstring stack overflow".into());
+        }
+        self.stack.push(value);
+        Ok(())
+    }
+
+    fn arg(&self, i: usize) -> Result<f64, Error> {
+        self.stack
+            .get(i)
+            .copied()
+            .ok_or_else(|| "Charstring stack underflow".into())
+    }
+
+    /// Discard the glyph width if this is the first stack-clearing operator
+    /// and it was given an extra argument.
+    fn take_width(&mut self, has_extra_argument: bool) {
+        if !self.has_width {
+            self.has_width = true;
+            if has_extra_argument {
+                self.stack.remove(0);
+            }
+        }
+    }
+
+    fn point(&self) -> Point {
+        Point {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    fn move_to(&mut self, dx: f64, dy: f64) {
+        self.stack.clear();
+        self.close_contour();
+        self.x += dx;
+        self.y += dy;
+        self.contours.push(Contour {
+            start: self.point(),
+            segments: vec![],
+        });
+    }
+
+    fn curve_to(&mut self, dx1: f64, dy1: f64, dx2: f64, dy2: f64, dx3: f64, dy3: f64) {
+        let c1 = Point {
+            x: self.x + dx1,
+            y: self.y + dy1,
+        };
+        let c2 = Point {
+            x: c1.x + dx2,
+            y: c1.y + dy2,
+        };
+        self.x = c2.x + dx3;
+        self.y = c2.y + dy3;
+        self.push_segment(Segment::Cubic(c1, c2, self.point()));
+    }
+
+    fn push_segment(&mut self, segment: Segment) {
+        if self.contours.is_empty() {
+            // Drawing without a moveto starts at the origin.
+            self.contours.push(Contour {
+                start: Point { x: 0.0, y: 0.0 },
+                segments: vec![],
+            });
+        }
+        if let Some(contour) = self.contours.last_mut() {
+            contour.segments.push(segment);
+        }
+    }
+
+    /// Contours in CFF are implicitly closed by the next moveto or endchar.
+    fn close_contour(&mut self) {
+        let current = self.point();
+        if let Some(contour) = self.contours.last_mut() {
+            if !contour.segments.is_empty() && contour.start != current {
+                contour.segments.push(Segment::Line(contour.start));
+            }
+        }
+        if let Some(contour) = self.contours.last() {
+            self.x = contour.start.x;
+            self.y = contour.start.y;
+        }
+    }
+}
+
+/// Convert glyph contours into shape records scaled by `scale`.
+///
+/// Font units have Y pointing up, while shapes have Y pointing down. SWF
+/// shapes only support quadratic curves, so each cubic curve is split in
+/// half and each half is approximated by a quadratic curve.
+fn contours_to_shape_records(contours: &[Contour], scale: f64) -> Vec<ShapeRecord> {
+    let to_twips = |p: Point| ((p.x * scale).round() as i32, (-p.y * scale).round() as i32);
+    let push_curve =
+        |records: &mut Vec<ShapeRecord>, cursor: &mut (i32, i32), control: Point, anchor: Point| {
+            let control = to_twips(control);
+            let anchor = to_twips(anchor);
+            records.push(ShapeRecord::CurvedEdge {
+                control_delta_x: Twips::new(control.0 - cursor.0),
+                control_delta_y: Twips::new(control.1 - cursor.1),
+                anchor_delta_x: Twips::new(anchor.0 - control.0),
+                anchor_delta_y: Twips::new(anchor.1 - control.1),
+            });
+            *cursor = anchor;
+        };
+
+    let mut records = vec![];
+    let mut is_first = true;
+    for contour in contours.iter().filter(|c| !c.segments.is_empty()) {
+        let mut cursor = to_twips(contour.start);
+        records.push(ShapeRecord::StyleChange(StyleChangeData {
+            move_to: Some((Twips::new(cursor.0), Twips::new(cursor.1))),
+            fill_style_0: None,
+            fill_style_1: if is_first { Some(1) } else { None },
+            line_style: None,
+            new_styles: None,
+        }));
+        is_first = false;
+
+        let mut start = contour.start;
+        for segment in &contour.segments {
+            match *segment {
+                Segment::Line(end) => {
+                    let anchor = to_twips(end);
+                    if anchor != cursor {
+                        records.push(ShapeRecord::StraightEdge {
+                            delta_x: Twips::new(anchor.0 - cursor.0),
+                            delta_y: Twips::new(anchor.1 - cursor.1),
+                        });
+                        cursor = anchor;
+                    }
+                    start = end;
+                }
+                Segment::Quadratic(control, end) => {
+                    push_curve(&mut records, &mut cursor, control, end);
+                    start = end;
+                }
+                Segment::Cubic(c1, c2, end) => {
+                    for (control, anchor) in &cubic_to_quadratics(start, c1, c2, end) {
+                        push_curve(&mut records, &mut cursor, *control, *anchor);
+                    }
+                    start = end;
+                }
+            }
+        }
+    }
+    records
+}
+
+/// Approximate a cubic Bézier curve with two quadratic curves, returning the
+/// (control, anchor) pair of each.
+fn cubic_to_quadratics(p0: Point, p1: Point, p2: Point, p3: Point) -> [(Point, Point); 2] {
+    let mid = |a: Point, b: Point| Point {
+        x: (a.x + b.x) / 2.0,
+        y: (a.y + b.y) / 2.0,
+    };
+    // Split the cubic at t = 0.5 using de Casteljau's algorithm.
+    let p01 = mid(p0, p1);
+    let p12 = mid(p1, p2);
+    let p23 = mid(p2, p3);
+    let p012 = mid(p01, p12);
+    let p123 = mid(p12, p23);
+    let split = mid(p012, p123);
+
+    // The best single quadratic control point for a cubic is
+    // (3 * (c1 + c2) - p0 - p3) / 4.
+    let control = |a: Point, c1: Point, c2: Point, b: Point| Point {
+        x: (3.0 * (c1.x + c2.x) - a.x - b.x) / 4.0,
+        y: (3.0 * (c1.y + c2.y) - a.y - b.y) / 4.0,
+    };
+    [
+        (control(p0, p01, p012, split), split),
+        (control(split, p123, p23, p3), p3),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cubic_to_quadratics_straight_line() {
+        let p = |x, y| Point { x, y };
+        let [(c1, a1), (c2, a2)] =
+            cubic_to_quadratics(p(0.0, 0.0), p(1.0, 0.0), p(2.0, 0.0), p(3.0, 0.0));
+        assert_eq!(a1, p(1.5, 0.0));
+        assert_eq!(a2, p(3.0, 0.0));
+        assert_eq!(c1, p(0.75, 0.0));
+        assert_eq!(c2, p(2.25, 0.0));
+    }
+
+    #[test]
+    fn charstring_closes_contours() {
+        let global_subrs = Index::default();
+        let local_subrs = Index::default();
+        let mut interpreter = CharStringInterpreter {
+            global_subrs: &global_subrs,
+            local_subrs: &local_subrs,
+            stack: vec![],
+            num_stems: 0,
+            has_width: false,
+            x: 0.0,
+            y: 0.0,
+            contours: vec![],
+        };
+        // width 500, 10 20 rmoveto, 100 hlineto, 50 vlineto, endchar
+        let char_string = [
+            248, 136, 149, 159, 21, 239, 6, 189, 7, 14, // endchar
+        ];
+        assert!(interpreter.execute(&char_string, 0).unwrap());
+        interpreter.close_contour();
+        let p = |x, y| Point { x, y };
+        assert_eq!(
+            interpreter.contours,
+            vec![Contour {
+                start: p(10.0, 20.0),
+                segments: vec![
+                    Segment::Line(p(110.0, 20.0)),
+                    Segment::Line(p(110.0, 70.0)),
+                    Segment::Line(p(10.0, 20.0)),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn quadratic_contour_implied_points() {
+        let p = |x, y| Point { x, y };
+        let points = [
+            (p(0.0, 0.0), false),
+            (p(2.0, 0.0), false),
+            (p(2.0, 2.0), false),
+            (p(0.0, 2.0), false),
+        ];
+        assert_eq!(
+            TrueType::quadratic_contour(&points),
+            Contour {
+                start: p(0.0, 1.0),
+                segments: vec![
+                    Segment::Quadratic(p(0.0, 0.0), p(1.0, 0.0)),
+                    Segment::Quadratic(p(2.0, 0.0), p(2.0, 1.0)),
+                    Segment::Quadratic(p(2.0, 2.0), p(1.0, 2.0)),
+                    Segment::Quadratic(p(0.0, 2.0), p(0.0, 1.0)),
+                ],
+            }
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Font files that device text is rendered with.
+
+use super::open_type::{self, OpenTypeFont};
+use super::FontDescriptor;
+use std::fmt;
+use std::sync::Arc;
+
+type Error = Box<dyn std::error::Error>;
+
+/// Families tried for the generic `_sans` font.
+const SANS_FAMILIES: &[&str] = &[
+    "Arial",
+    "Helvetica",
+    "Liberation Sans",
+    "DejaVu Sans",
+    "Noto Sans",
+    "MS PGothic",
+    "Noto Sans CJK JP",
+];
+
+/// Families tried for the generic `_serif` font.
+const SERIF_FAMILIES: &[&str] = &[
+    "Times New Roman",
+    "Times",
+    "Liberation Serif",
+    "DejaVu Serif",
+    "Noto Serif",
+    "MS PMincho",
+    "Noto Serif CJK JP",
+];
+
+/// Families tried for the generic `_typewriter` font.
+const TYPEWRITER_FAMILIES: &[&str] = &[
+    "Courier New",
+    "Courier",
+    "Liberation Mono",
+    "DejaVu Sans Mono",
+    "Noto Sans Mono",
+    "MS Gothic",
+];
+
+/// Returns the families that a generic Flash font name stands for.
+fn generic_families(name: &str) -> Option<&'static [&'static str]> {
+    match name {
+        "_sans" | "_ゴシック" => Some(SANS_FAMILIES),
+        "_serif" | "_明朝" => Some(SERIF_FAMILIES),
+        "_typewriter" | "_等幅" => Some(TYPEWRITER_FAMILIES),
+        _ => None,
+    }
+}
+
+/// A single font face from a font file registered with a `FontSource`.
+pub struct FontFace {
+    /// The font file, shared by every face in a TrueType collection.
+    data: Arc<Vec<u8>>,
+
+    /// The index of this face in a TrueType collection.
+    index: u32,
+
+    /// The family names of this face, in lowercase.
+    family_names: Vec<String>,
+
+    is_bold: bool,
+    is_italic: bool,
+
+    /// A map from a Unicode code point to a glyph index in this face.
+    code_point_to_glyph: fnv::FnvHashMap<u16, u16>,
+
+    /// The ascent of this face, in `DefineFont3` EM-square units.
+    ascent: u16,
+
+    /// The descent of this face, in `DefineFont3` EM-square units.
+    descent: u16,
+
+    /// The leading of this face, in `DefineFont3` EM-square units.
+    leading: i16,
+}
+
+impl FontFace {
+    fn parse(data: Arc<Vec<u8>>, index: u32) -> Result<Self, Error> {
+        let font = OpenTypeFont::parse_face(&data, index)?;
+        let family_names = font
+            .family_names()
+            .into_iter()
+            .map(|name| name.to_lowercase())
+            .collect();
+        let code_point_to_glyph = font.character_map()?.into_iter().collect();
+        let (is_bold, is_italic) = (font.is_bold(), font.is_italic());
+        let (ascent, descent, leading) = (font.ascent(), font.descent(), font.leading());
+
+        Ok(Self {
+            data,
+            index,
+            family_names,
+            is_bold,
+            is_italic,
+            code_point_to_glyph,
+            ascent,
+            descent,
+            leading,
+        })
+    }
+
+    /// Parse this face's font data, to load glyphs from it.
+    pub fn open(&self) -> Result<OpenTypeFont<'_>, Error> {
+        OpenTypeFont::parse_face(&self.data, self.index)
+    }
+
+    /// Returns the index of the glyph for a code point, if this face has one.
+    pub fn glyph_index(&self, code_point: u16) -> Option<u16> {
+        self.code_point_to_glyph.get(&code_point).copied()
+    }
+
+    /// Returns whether this face belongs to the given family.
+    /// Family names are compared case-insensitively.
+    pub fn has_family(&self, family: &str) -> bool {
+        let family = family.to_lowercase();
+        self.family_names.iter().any(|name| *name == family)
+    }
+
+    /// The ascent, descent and leading of this face, in `DefineFont3`
+    /// EM-square units.
+    pub fn metrics(&self) -> (u16, u16, i16) {
+        (self.ascent, self.descent, self.leading)
+    }
+
+    /// How far this face's style is from the requested one. Lower is better.
+    fn style_distance(&self, is_bold: bool, is_italic: bool) -> u8 {
+        2 * u8::from(self.is_bold != is_bold) + u8::from(self.is_italic != is_italic)
+    }
+}
+
+impl fmt::Debug for FontFace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FontFace")
+            .field("family_names", &self.family_names)
+            .field("index", &self.index)
+            .field("is_bold", &self.is_bold)
+            .field("is_italic", &self.is_italic)
+            .finish()
+    }
+}
+
+/// The font faces to render a device font with.
+#[derive(Clone, Debug, Default)]
+pub struct FallbackChain {
+    /// Faces matching the requested font, in order of preference.
+    pub preferred: Vec<Arc<FontFace>>,
+
+    /// Every other face, used for characters that neither the preferred
+    /// faces nor the bundled device font support (such as CJK text).
+    pub fallback: Vec<Arc<FontFace>>,
+}
+
+/// A collection of TrueType and OpenType fonts that device text is rendered
+/// with, such as the fonts installed on the system.
+#[derive(Debug, Default)]
+pub struct FontSource {
+    faces: Vec<Arc<FontFace>>,
+}
+
+impl FontSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether no fonts have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Register a TrueType or OpenType font file, or every font in a
+    /// TrueType collection.
+    ///
+    /// Returns the number of font faces that were registered.
+    pub fn register_font_file(&mut self, data: Vec<u8>) -> Result<usize, Error> {
+        let data = Arc::new(data);
+        let count = open_type::face_count(&data)?;
+        let mut registered = 0;
+        for index in 0..count {
+            match FontFace::parse(data.clone(), index) {
+                Ok(face) => {
+                    self.faces.push(Arc::new(face));
+                    registered += 1;
+                }
+                Err(e) if count == 1 => return Err(e),
+                Err(e) => log::warn!("Unable to load font {} in collection: {}", index, e),
+            }
+        }
+        Ok(registered)
+    }
+
+    /// Find the font faces to render text in the given font with.
+    ///
+    /// The font name may be a comma-separated list of families, and may use
+    /// the generic `_sans`, `_serif` and `_typewriter` names. If no family
+    /// matches, sans-serif faces are preferred.
+    pub fn fallback_chain(&self, descriptor: &FontDescriptor) -> FallbackChain {
+        let (is_bold, is_italic) = (descriptor.bold(), descriptor.italic());
+        let mut preferred = vec![];
+        let add_family = |preferred: &mut Vec<Arc<FontFace>>, family: &str| {
+            let mut faces: Vec<_> = self
+                .faces
+                .iter()
+                .filter(|face| face.has_family(family))
+                .filter(|face| !preferred.iter().any(|f| Arc::ptr_eq(f, face)))
+                .cloned()
+                .collect();
+            faces.sort_by_key(|face| face.style_distance(is_bold, is_italic));
+            preferred.extend(faces);
+        };
+
+        for family in descriptor.class().split(',').map(str::trim) {
+            match generic_families(family) {
+                Some(families) => families.iter().for_each(|f| add_family(&mut preferred, f)),
+                None => add_family(&mut preferred, family),
+            }
+        }
+        if preferred.is_empty() {
+            SANS_FAMILIES
+                .iter()
+                .for_each(|f| add_family(&mut preferred, f));
+        }
+
+        let mut fallback: Vec<_> = self
+            .faces
+            .iter()
+            .filter(|face| !preferred.iter().any(|f| Arc::ptr_eq(f, face)))
+            .cloned()
+            .collect();
+        fallback.sort_by_key(|face| face.style_distance(is_bold, is_italic));
+
+        FallbackChain {
+            preferred,
+            fallback,
+        }
+    }
+}
@@ -3,12 +3,14 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod style_sheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
-pub use layout::LayoutBox;
+pub use layout::{LayoutBox, LayoutLine};
+pub use style_sheet::{Display, Style, StyleSheet};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -15,7 +15,7 @@ use swf::Twips;
 /// Draw an underline on a particular drawing.
 ///
 /// This will not draw underlines shorter than a pixel in width.
-fn draw_underline(drawing: &mut Drawing, starting_pos: Position<Twips>, width: Twips) {
+fn draw_underline(drawing: &mut Drawing<'_>, starting_pos: Position<Twips>, width: Twips) {
     if width < Twips::from_pixels(1.0) {
         return;
     }
@@ -56,6 +56,9 @@ pub struct LayoutContext<'a, 'gc> {
     /// The growing list of layout boxes to return when layout has finished.
     boxes: Vec<LayoutBox<'gc>>,
 
+    /// The growing list of lines to return when layout has finished.
+    lines: Vec<LayoutLine>,
+
     /// The exterior bounds of all laid-out text, including left and right
     /// margins.
     ///
@@ -97,6 +100,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             text,
             max_font_size: Default::default(),
             boxes: Vec::new(),
+            lines: Vec::new(),
             exterior_bounds: None,
             is_first_line: true,
             has_line_break: false,
@@ -307,6 +311,21 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
         line_bounds +=
             Position::from((left_adjustment + align_adjustment, Twips::from_pixels(0.0)));
+
+        let ascent = self
+            .font
+            .map(|f| f.get_baseline_for_height(self.max_font_size))
+            .unwrap_or_else(|| Twips::new(0));
+        self.lines.push(LayoutLine {
+            bounds: BoxBounds::from_position_and_size(
+                Position::from((line_bounds.offset_x(), self.cursor.y())),
+                Size::from((line_bounds.width(), self.max_font_size)),
+            ),
+            leading: self.line_leading_adjustment(),
+            ascent,
+            descent: self.max_font_size - ascent,
+        });
+
         line_bounds += Size::from((Twips::from_pixels(0.0), font_leading_adjustment));
 
         self.current_line = self.boxes.len();
@@ -393,25 +412,56 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         span: &TextSpan,
+        text: &str,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
         let library = context.library.library_for_movie_mut(self.movie.clone());
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
-        // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
+        // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info when
+        // this text field is set to use device fonts (see #451).
         if let Some(font) = library
             .get_font_by_name(&span.font, span.bold, span.italic)
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| library.device_font())
         {
             self.font = Some(font);
             return self.font;
         }
 
+        if let Some(font) = Self::device_font(context, &self.movie, span, text) {
+            self.font = Some(font);
+            return self.font;
+        }
+
         None
     }
 
+    /// Find the device font to render the given text in, loading any glyphs
+    /// it needs.
+    ///
+    /// Device fonts are matched against the fonts registered with the
+    /// player, falling back to our embedded Noto Sans.
+    fn device_font(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        movie: &Arc<SwfMovie>,
+        span: &TextSpan,
+        text: &str,
+    ) -> Option<Font<'gc>> {
+        let fallback_font = context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .device_font();
+        let font = context.library.get_device_font(
+            context.gc_context,
+            context.font_source,
+            &span.font,
+            span.bold,
+            span.italic,
+            fallback_font,
+        )?;
+        font.load_glyphs_for_text(context.renderer, text);
+        Some(font)
+    }
+
     /// Append text to the current line of the ongoing layout operation.
     ///
     /// The text given may or may not be separated into fragments, depending on
@@ -461,7 +511,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         if let Some(bullet_font) = library
             .get_font_by_name(&span.font, span.bold, span.italic)
             .filter(|f| f.has_glyphs())
-            .or_else(|| library.device_font())
+            .or_else(|| Self::device_font(context, &self.movie, span, "\u{2022}"))
             .or(self.font)
         {
             let mut bullet_cursor = self.cursor;
@@ -520,15 +570,17 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         (width, offset + self.cursor.x())
     }
 
-    /// Destroy the layout context, returning the newly constructed layout list.
+    /// Destroy the layout context, returning the newly constructed layout
+    /// list and lines.
     fn end_layout(
         mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+    ) -> (Vec<LayoutBox<'gc>>, Vec<LayoutLine>, BoxBounds<Twips>) {
         self.fixup_line(context, !self.has_line_break, true);
 
         (
             self.boxes,
+            self.lines,
             self.exterior_bounds.unwrap_or_else(Default::default),
         )
     }
@@ -556,6 +608,43 @@ pub struct LayoutBox<'gc> {
 #[collect(require_static)]
 pub struct CollectWrapper<T>(T);
 
+/// A `LayoutLine` describes a single line of text within a fully laid-out
+/// `EditText`.
+#[derive(Clone, Copy, Debug, Collect)]
+#[collect(require_static)]
+pub struct LayoutLine {
+    /// The bounds of the text on this line, not including the leading below
+    /// it.
+    bounds: BoxBounds<Twips>,
+
+    /// The space between the bottom of this line and the top of the next.
+    leading: Twips,
+
+    /// The distance from the top of the line to the baseline of its text.
+    ascent: Twips,
+
+    /// The distance from the baseline of the text to the bottom of the line.
+    descent: Twips,
+}
+
+impl LayoutLine {
+    pub fn bounds(&self) -> BoxBounds<Twips> {
+        self.bounds
+    }
+
+    pub fn leading(&self) -> Twips {
+        self.leading
+    }
+
+    pub fn ascent(&self) -> Twips {
+        self.ascent
+    }
+
+    pub fn descent(&self) -> Twips {
+        self.descent
+    }
+}
+
 /// Represents different content modes of a given `LayoutBox`.
 ///
 /// Currently, a `LayoutBox` can contain `Text`, `Bullet`s, or a `Drawing`.
@@ -611,7 +700,7 @@ pub enum LayoutContent<'gc> {
     /// The drawing will be rendered with it's origin at the position of the
     /// layout box's bounds. The size of those bounds do not affect the
     /// rendering of the drawing.
-    Drawing(Drawing),
+    Drawing(Drawing<'gc>),
 }
 
 impl<'gc> LayoutBox<'gc> {
@@ -648,7 +737,7 @@ impl<'gc> LayoutBox<'gc> {
     }
 
     /// Construct a drawing.
-    pub fn from_drawing(drawing: Drawing) -> Self {
+    pub fn from_drawing(drawing: Drawing<'gc>) -> Self {
         Self {
             bounds: Default::default(),
             content: LayoutContent::Drawing(drawing),
@@ -657,8 +746,9 @@ impl<'gc> LayoutBox<'gc> {
 
     /// Construct a new layout hierarchy from text spans.
     ///
-    /// The returned bounds will include both the text bounds itself, as well
-    /// as left and right margins on any of the lines.
+    /// The laid-out lines are returned alongside the boxes. The returned
+    /// bounds will include both the text bounds itself, as well as left and
+    /// right margins on any of the lines.
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -666,11 +756,13 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
-    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+    ) -> (Vec<LayoutBox<'gc>>, Vec<LayoutLine>, BoxBounds<Twips>) {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.text());
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
-            if let Some(font) = layout_context.resolve_font(context, &span, is_device_font) {
+            if let Some(font) =
+                layout_context.resolve_font(context, &span, span_text, is_device_font)
+            {
                 layout_context.newspan(span);
 
                 let params = EvalParameters::from_span(span);
@@ -797,7 +889,7 @@ impl<'gc> LayoutBox<'gc> {
     }
 
     /// Returns a reference to the drawing this box contains, if it has one.
-    pub fn as_renderable_drawing(&self) -> Option<&Drawing> {
+    pub fn as_renderable_drawing(&self) -> Option<&Drawing<'gc>> {
         match &self.content {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
@@ -805,6 +897,16 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Returns the range of text positions this box contains, if it is a
+    /// text box.
+    pub fn text_range(&self) -> Option<(usize, usize)> {
+        match &self.content {
+            LayoutContent::Text { start, end, .. } => Some((*start, *end)),
+            LayoutContent::Bullet { .. } => None,
+            LayoutContent::Drawing(..) => None,
+        }
+    }
+
     pub fn is_text_box(&self) -> bool {
         match &self.content {
             LayoutContent::Text { .. } => true,
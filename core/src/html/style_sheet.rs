@@ -0,0 +1,225 @@
+//! CSS style sheets for HTML text
+
+use gc_arena::Collect;
+use std::collections::BTreeMap;
+
+/// The declarations of a single CSS rule.
+///
+/// Property names are stored in the camel case form exposed to ActionScript
+/// (e.g. `fontSize` for `font-size`), and values are kept as written.
+pub type Style = BTreeMap<String, String>;
+
+/// How an element is laid out, as set by the CSS `display` property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Display {
+    /// The element is followed by a line break.
+    Block,
+
+    /// The element flows with the surrounding text.
+    Inline,
+
+    /// The element and its contents are not displayed.
+    None,
+}
+
+impl Display {
+    /// Read the `display` property of a style, if it is set to a known value.
+    pub fn from_style(style: &Style) -> Option<Self> {
+        match style.get("display").map(|v| v.trim()) {
+            Some("block") => Some(Display::Block),
+            Some("inline") => Some(Display::Inline),
+            Some("none") => Some(Display::None),
+            _ => None,
+        }
+    }
+}
+
+/// A set of CSS rules that style HTML text, as used by `TextField.StyleSheet`.
+///
+/// Only simple selectors are supported: tag names (`p`), class names
+/// (`.heading`) and the link pseudo-classes `a:link` and `a:hover`. Selector
+/// names are case-insensitive, and are stored in lower case.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: BTreeMap<String, Style>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the style of the given selector, if any.
+    pub fn style(&self, name: &str) -> Option<&Style> {
+        self.styles.get(&name.to_lowercase())
+    }
+
+    /// Replace the style of the given selector, or remove it if `style` is
+    /// `None`.
+    pub fn set_style(&mut self, name: &str, style: Option<Style>) {
+        let name = name.to_lowercase();
+        match style {
+            Some(style) => self.styles.insert(name, style),
+            None => self.styles.remove(&name),
+        };
+    }
+
+    /// List the selectors that have a style.
+    pub fn style_names(&self) -> impl Iterator<Item = &str> {
+        self.styles.keys().map(|name| name.as_str())
+    }
+
+    /// Remove all styles.
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Parse a CSS document and add its rules to this style sheet.
+    ///
+    /// Declarations for a selector that already has a style are merged into
+    /// that style. Returns `false` and leaves the style sheet unchanged if the
+    /// CSS could not be parsed.
+    pub fn parse_css(&mut self, css: &str) -> bool {
+        let rules = match parse_rules(&strip_comments(css)) {
+            Some(rules) => rules,
+            None => return false,
+        };
+
+        for (selectors, style) in rules {
+            for selector in selectors {
+                self.styles
+                    .entry(selector)
+                    .or_insert_with(Style::new)
+                    .extend(style.clone());
+            }
+        }
+
+        true
+    }
+
+    /// Compute the style of an HTML element from the rules matching its tag
+    /// name and classes.
+    ///
+    /// Tag selectors are applied first, then class selectors, and finally
+    /// `a:hover` if the element is a hovered link.
+    pub fn element_style<'a>(
+        &self,
+        tag_name: &str,
+        classes: impl Iterator<Item = &'a str>,
+        is_hovered: bool,
+    ) -> Style {
+        let tag_name = tag_name.to_lowercase();
+        let mut style = Style::new();
+
+        if let Some(tag_style) = self.styles.get(&tag_name) {
+            style.extend(tag_style.clone());
+        }
+
+        if tag_name == "a" {
+            if let Some(link_style) = self.styles.get("a:link") {
+                style.extend(link_style.clone());
+            }
+        }
+
+        for class in classes {
+            if let Some(class_style) = self.styles.get(&format!(".{}", class.to_lowercase())) {
+                style.extend(class_style.clone());
+            }
+        }
+
+        if tag_name == "a" && is_hovered {
+            if let Some(hover_style) = self.styles.get("a:hover") {
+                style.extend(hover_style.clone());
+            }
+        }
+
+        style
+    }
+}
+
+/// Remove `/* ... */` comments from a CSS document.
+fn strip_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Parse the rules of a CSS document into their selectors and declarations.
+///
+/// Returns `None` if the document is malformed.
+fn parse_rules(css: &str) -> Option<Vec<(Vec<String>, Style)>> {
+    let mut rules = Vec::new();
+    let mut rest = css;
+
+    while !rest.trim().is_empty() {
+        let open = rest.find('{')?;
+        let close = open + rest[open..].find('}')?;
+
+        let selectors: Vec<String> = rest[..open]
+            .split(',')
+            .map(|selector| selector.trim().to_lowercase())
+            .collect();
+        if selectors.iter().any(|selector| selector.is_empty()) {
+            return None;
+        }
+
+        let body = &rest[open + 1..close];
+        if body.contains('{') {
+            return None;
+        }
+
+        let mut style = Style::new();
+        for declaration in body.split(';') {
+            let declaration = declaration.trim();
+            if declaration.is_empty() {
+                continue;
+            }
+
+            let colon = declaration.find(':')?;
+            let name = declaration[..colon].trim();
+            if name.is_empty() {
+                return None;
+            }
+
+            style.insert(
+                to_camel_case(name),
+                declaration[colon + 1..].trim().to_string(),
+            );
+        }
+
+        rules.push((selectors, style));
+        rest = &rest[close + 1..];
+    }
+
+    Some(rules)
+}
+
+/// Convert a hyphenated CSS property name to camel case.
+fn to_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+
+    for ch in name.chars() {
+        if ch == '-' {
+            capitalize_next = !result.is_empty();
+        } else if capitalize_next {
+            result.extend(ch.to_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{Display, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use swf::{Rectangle, Twips};
 
@@ -793,3 +794,122 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_to_password_masked() {
+    let mut tf1 = TextFormat::default();
+    tf1.font = Some("Same!".to_string());
+
+    let mut tf2 = TextFormat::default();
+    tf2.font = Some("Difference!".to_string());
+
+    let fs = FormatSpans::from_str_and_spans(
+        "abcdé日本",
+        &[
+            TextSpan::with_length_and_format(6, tf1),
+            TextSpan::with_length_and_format(6, tf2),
+        ],
+    );
+
+    let masked = fs.to_password_masked();
+
+    assert_eq!("*******", masked.text());
+
+    assert_eq!((0, 1), masked.get_span_boundaries(0, 5));
+    assert_eq!((1, 2), masked.get_span_boundaries(5, 7));
+}
+
+#[test]
+fn stylesheet_parse_css() {
+    let mut style_sheet = StyleSheet::new();
+
+    assert!(style_sheet.parse_css(
+        "/* heading */ H1, .Title { font-size: 20px; font-weight: bold; }\np{color:#FF0000}"
+    ));
+
+    let names: Vec<&str> = style_sheet.style_names().collect();
+    assert_eq!(names, vec![".title", "h1", "p"]);
+
+    let h1 = style_sheet.style("h1").unwrap();
+    assert_eq!(h1.get("fontSize").map(String::as_str), Some("20px"));
+    assert_eq!(h1.get("fontWeight").map(String::as_str), Some("bold"));
+    assert_eq!(
+        style_sheet
+            .style("P")
+            .and_then(|p| p.get("color"))
+            .map(String::as_str),
+        Some("#FF0000")
+    );
+}
+
+#[test]
+fn stylesheet_parse_css_merges_rules() {
+    let mut style_sheet = StyleSheet::new();
+
+    assert!(style_sheet.parse_css("p { color: #FF0000; }"));
+    assert!(style_sheet.parse_css("p { display: block; }"));
+
+    let p = style_sheet.style("p").unwrap();
+    assert_eq!(p.len(), 2);
+    assert_eq!(Display::from_style(p), Some(Display::Block));
+}
+
+#[test]
+fn stylesheet_parse_css_invalid() {
+    let mut style_sheet = StyleSheet::new();
+
+    assert!(!style_sheet.parse_css("p { color #FF0000 }"));
+    assert!(!style_sheet.parse_css("p { color: #FF0000;"));
+    assert!(!style_sheet.parse_css(", p { color: #FF0000; }"));
+    assert_eq!(style_sheet.style_names().count(), 0);
+}
+
+#[test]
+fn stylesheet_element_style() {
+    let mut style_sheet = StyleSheet::new();
+    style_sheet.parse_css(
+        "a { color: #0000FF; text-decoration: none; } \
+         .important { color: #FF0000; } \
+         a:hover { text-decoration: underline; }",
+    );
+
+    let style = style_sheet.element_style("A", "important".split_whitespace(), false);
+    assert_eq!(style.get("color").map(String::as_str), Some("#FF0000"));
+    assert_eq!(
+        style.get("textDecoration").map(String::as_str),
+        Some("none")
+    );
+
+    let style = style_sheet.element_style("a", "".split_whitespace(), true);
+    assert_eq!(style.get("color").map(String::as_str), Some("#0000FF"));
+    assert_eq!(
+        style.get("textDecoration").map(String::as_str),
+        Some("underline")
+    );
+}
+
+#[test]
+fn textformat_from_css_style() {
+    let mut style_sheet = StyleSheet::new();
+    style_sheet.parse_css(
+        "p { color: #336699; font-family: 'Comic Sans', serif; font-size: 14px; \
+         font-style: italic; text-align: center; margin-left: 5; text-indent: bogus; }",
+    );
+
+    let tf = TextFormat::from_css_style(
+        style_sheet.style("p").unwrap(),
+        TextFormat {
+            indent: Some(3.0),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(tf.color, Some(swf::Color::from_rgb(0x336699, 0xFF)));
+    assert_eq!(tf.font.as_deref(), Some("Comic Sans"));
+    assert_eq!(tf.size, Some(14.0));
+    assert_eq!(tf.italic, Some(true));
+    assert_eq!(tf.align, Some(swf::TextAlign::Center));
+    assert_eq!(tf.left_margin, Some(5.0));
+    assert_eq!(tf.indent, Some(3.0));
+    assert_eq!(tf.bold, None);
+}
@@ -3,6 +3,7 @@ use crate::avm1::activation::Activation;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::{Display, Style, StyleSheet};
 use crate::tag_utils::SwfMovie;
 use crate::xml::{Step, XMLDocument, XMLName, XMLNode};
 use gc_arena::{Collect, MutationContext};
@@ -51,6 +52,68 @@ fn process_html_entity(src: &str) -> Cow<str> {
     }
 }
 
+/// Collapse every run of whitespace into a single space, as done for HTML
+/// text fields with `condenseWhite` set.
+fn condense_white(src: &str) -> Cow<str> {
+    let is_white = |c: char| matches!(c, ' ' | '\t' | '\n' | '\r');
+    if !src.contains(|c| matches!(c, '\t' | '\n' | '\r')) && !src.contains("  ") {
+        return Cow::Borrowed(src);
+    }
+
+    let mut result_str = String::with_capacity(src.len());
+    let mut last_was_white = false;
+    for ch in src.chars() {
+        if is_white(ch) {
+            if !last_was_white {
+                result_str.push(' ');
+            }
+            last_was_white = true;
+        } else {
+            result_str.push(ch);
+            last_was_white = false;
+        }
+    }
+
+    Cow::Owned(result_str)
+}
+
+/// Parse a CSS color of the form `#RRGGBB`.
+fn css_color(value: &str) -> Option<swf::Color> {
+    if value.len() != 7 || !value.starts_with('#') {
+        return None;
+    }
+
+    u32::from_str_radix(&value[1..], 16)
+        .ok()
+        .map(|rgb| swf::Color::from_rgb(rgb, 0xFF))
+}
+
+/// Parse a CSS length in pixels, such as `12px` or `12`.
+fn css_length(value: &str) -> Option<f64> {
+    let value = value.trim_end_matches("px").trim_end();
+    value.parse().ok()
+}
+
+/// Pick the font of a CSS `font-family` list.
+///
+/// Only the first font of the list is used. The generic families map to the
+/// corresponding Flash device fonts.
+fn css_font_family(value: &str) -> Option<String> {
+    let font = value
+        .split(',')
+        .next()?
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'');
+
+    match font {
+        "" => None,
+        "sans-serif" => Some("_sans".to_string()),
+        "serif" => Some("_serif".to_string()),
+        "mono" | "monospace" => Some("_typewriter".to_string()),
+        font => Some(font.to_string()),
+    }
+}
+
 /// A set of text formatting options to be applied to some part, or the whole
 /// of, a given text field.
 ///
@@ -349,6 +412,66 @@ impl TextFormat {
         tf
     }
 
+    /// Extract text format parameters from a CSS style.
+    ///
+    /// Like `from_presentational_markup`, this accepts the text format that
+    /// is in effect before the style is applied. Properties that the style
+    /// does not set, or that have a value Flash does not understand, are
+    /// retained from this format.
+    pub fn from_css_style(style: &Style, mut tf: TextFormat) -> Self {
+        for (name, value) in style {
+            let value = value.trim();
+            match name.as_str() {
+                "color" => {
+                    if let Some(color) = css_color(value) {
+                        tf.color = Some(color);
+                    }
+                }
+                "fontFamily" => {
+                    if let Some(font) = css_font_family(value) {
+                        tf.font = Some(font);
+                    }
+                }
+                "fontSize" => tf.size = css_length(value).or(tf.size),
+                "fontStyle" => match value {
+                    "italic" => tf.italic = Some(true),
+                    "normal" => tf.italic = Some(false),
+                    _ => {}
+                },
+                "fontWeight" => match value {
+                    "bold" => tf.bold = Some(true),
+                    "normal" => tf.bold = Some(false),
+                    _ => {}
+                },
+                "kerning" => match value {
+                    "true" => tf.kerning = Some(true),
+                    "false" => tf.kerning = Some(false),
+                    _ => {}
+                },
+                "leading" => tf.leading = css_length(value).or(tf.leading),
+                "letterSpacing" => tf.letter_spacing = css_length(value).or(tf.letter_spacing),
+                "marginLeft" => tf.left_margin = css_length(value).or(tf.left_margin),
+                "marginRight" => tf.right_margin = css_length(value).or(tf.right_margin),
+                "textAlign" => match value {
+                    "left" => tf.align = Some(swf::TextAlign::Left),
+                    "center" => tf.align = Some(swf::TextAlign::Center),
+                    "right" => tf.align = Some(swf::TextAlign::Right),
+                    "justify" => tf.align = Some(swf::TextAlign::Justify),
+                    _ => {}
+                },
+                "textDecoration" => match value {
+                    "underline" => tf.underline = Some(true),
+                    "none" => tf.underline = Some(false),
+                    _ => {}
+                },
+                "textIndent" => tf.indent = css_length(value).or(tf.indent),
+                _ => {}
+            }
+        }
+
+        tf
+    }
+
     /// Construct a `TextFormat` AVM1 object from this text format object.
     pub fn as_avm1_object<'gc>(
         &self,
@@ -1188,20 +1311,83 @@ impl FormatSpans {
         TextSpanIter::for_format_spans(self)
     }
 
+    /// Construct a copy of these format spans with every character replaced
+    /// by an asterisk, as displayed by password text fields.
+    ///
+    /// Each character of the original text becomes a single byte of the
+    /// masked text, so positions in the copy are character indices of the
+    /// original text.
+    pub fn to_password_masked(&self) -> Self {
+        let mut spans = Vec::with_capacity(self.spans.len());
+        let mut length = 0;
+        for (_start, _end, text, span) in self.iter_spans() {
+            let mut span = span.clone();
+            span.span_length = text.chars().count();
+            length += span.span_length;
+            spans.push(span);
+        }
+
+        FormatSpans {
+            text: "*".repeat(length),
+            spans,
+            default_format: self.default_format.clone(),
+        }
+    }
+
     /// Lower an HTML tree into text-span representation.
     ///
     /// This is the "legacy" implementation of this process: it only looks for
     /// a handful of presentational attributes in the HTML tree to generate
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
-    pub fn lower_from_html<'gc>(&mut self, tree: XMLDocument<'gc>) {
+    ///
+    /// If `is_condense_white` is set, runs of whitespace in the text are
+    /// collapsed into a single space, and only tags such as `<br>` and `<p>`
+    /// produce line breaks.
+    pub fn lower_from_html<'gc>(&mut self, tree: XMLDocument<'gc>, is_condense_white: bool) {
+        self.lower_from_css(tree, &StyleSheet::new(), None, is_condense_white);
+    }
+
+    /// Lower an HTML tree into text-span representation, styling it with a
+    /// CSS stylesheet.
+    ///
+    /// Each element is first styled by its presentational markup, and then by
+    /// the stylesheet rules that match its tag name and `class` attribute.
+    /// The `display` property decides whether an element ends its line or is
+    /// hidden entirely.
+    ///
+    /// Links are numbered in document order, and the link numbered
+    /// `hovered_link` is additionally styled with `a:hover`. Returns the text
+    /// range covered by each link, so that the caller can tell which link is
+    /// under the mouse.
+    pub fn lower_from_css<'gc>(
+        &mut self,
+        tree: XMLDocument<'gc>,
+        style_sheet: &StyleSheet,
+        hovered_link: Option<usize>,
+        is_condense_white: bool,
+    ) -> Vec<(usize, usize)> {
         let mut format_stack = vec![self.default_format.clone()];
+        let mut block_stack = vec![];
+        let mut link_ranges = vec![];
+        let mut open_links = vec![];
+        let mut hidden_depth = 0;
         let mut last_successful_format = None;
 
         self.text = "".to_string();
         self.spans = vec![];
 
         for step in tree.as_node().walk().unwrap() {
+            // Skip everything inside of an element styled with `display: none`.
+            if hidden_depth > 0 {
+                match step {
+                    Step::In(_) => hidden_depth += 1,
+                    Step::Out(_) => hidden_depth -= 1,
+                    Step::Around(_) => {}
+                }
+                continue;
+            }
+
             match step {
                 Step::In(node)
                     if node.tag_name().unwrap().node_name() == "sbr"
@@ -1212,40 +1398,79 @@ impl FormatSpans {
                 Step::Out(node)
                     if node.tag_name().unwrap().node_name() == "sbr"
                         || node.tag_name().unwrap().node_name() == "br" => {}
-                Step::In(node) => format_stack.push(TextFormat::from_presentational_markup(
-                    node,
-                    format_stack
-                        .last()
-                        .cloned()
-                        .unwrap_or_else(Default::default),
-                )),
+                Step::In(node) => {
+                    let tag_name = node.tag_name().unwrap().node_name().to_lowercase();
+                    let class = node
+                        .attribute_value(&XMLName::from_str("class"))
+                        .unwrap_or_default();
+                    let is_link = tag_name == "a";
+                    let style = style_sheet.element_style(
+                        &tag_name,
+                        class.split_whitespace(),
+                        is_link && hovered_link == Some(link_ranges.len()),
+                    );
+
+                    let is_block = match Display::from_style(&style) {
+                        Some(Display::None) => {
+                            hidden_depth = 1;
+                            continue;
+                        }
+                        Some(display) => display == Display::Block,
+                        None => tag_name == "p" || tag_name == "li",
+                    };
+
+                    if is_link {
+                        open_links.push(link_ranges.len());
+                        link_ranges.push((self.text.len(), self.text.len()));
+                    }
+
+                    let tf = TextFormat::from_presentational_markup(
+                        node,
+                        format_stack
+                            .last()
+                            .cloned()
+                            .unwrap_or_else(Default::default),
+                    );
+                    format_stack.push(TextFormat::from_css_style(&style, tf));
+                    block_stack.push(is_block);
+                }
                 Step::Around(node) if node.is_text() => {
+                    let value = node.node_value().unwrap();
+                    let value = if is_condense_white {
+                        condense_white(&value)
+                    } else {
+                        Cow::Borrowed(value.as_str())
+                    };
                     self.replace_text(
                         self.text.len(),
                         self.text.len(),
-                        &process_html_entity(&node.node_value().unwrap()),
+                        &process_html_entity(&value),
                         format_stack.last(),
                     );
                     last_successful_format = format_stack.last().cloned();
                 }
-                Step::Out(node)
-                    if node.tag_name().unwrap().node_name() == "p"
-                        || node.tag_name().unwrap().node_name() == "li" =>
-                {
-                    self.replace_text(
-                        self.text.len(),
-                        self.text.len(),
-                        "\n",
-                        last_successful_format.as_ref(),
-                    );
-                    format_stack.pop();
-                }
-                Step::Out(_) => {
+                Step::Out(node) => {
+                    if node.tag_name().unwrap().node_name().to_lowercase() == "a" {
+                        if let Some(link) = open_links.pop() {
+                            link_ranges[link].1 = self.text.len();
+                        }
+                    }
+
+                    if block_stack.pop().unwrap_or(false) {
+                        self.replace_text(
+                            self.text.len(),
+                            self.text.len(),
+                            "\n",
+                            last_successful_format.as_ref(),
+                        );
+                    }
                     format_stack.pop();
                 }
                 _ => {}
             };
         }
+
+        link_ranges
     }
 
     #[allow(clippy::float_cmp)]
@@ -11,12 +11,16 @@ extern crate downcast_rs;
 
 mod avm1;
 mod avm2;
+mod bitmap_data;
 mod bounding_box;
 mod character;
 pub mod color_transform;
+pub mod config;
 mod context;
 mod drawing;
 pub mod events;
+pub mod external;
+mod focus_tracker;
 mod font;
 mod html;
 mod library;
@@ -24,6 +28,7 @@ pub mod loader;
 mod player;
 mod prelude;
 mod property_map;
+pub mod security;
 pub mod shape_utils;
 pub mod string_utils;
 pub mod tag_utils;
@@ -1,7 +1,8 @@
+use crate::avm2::Object as Avm2Object;
 use crate::backend::audio::SoundHandle;
+use crate::backend::render::{BitmapInfo, BitmapSource};
 use crate::character::Character;
-use crate::display_object::TDisplayObject;
-use crate::font::{Font, FontDescriptor};
+use crate::font::{Font, FontDescriptor, FontSource};
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, MutationContext};
@@ -16,9 +17,11 @@ use weak_table::PtrWeakKeyHashMap;
 pub struct MovieLibrary<'gc> {
     characters: HashMap<CharacterId, Character<'gc>>,
     export_characters: HashMap<String, Character<'gc>>,
+    avm2_classes: HashMap<CharacterId, String>,
     jpeg_tables: Option<Vec<u8>>,
     device_font: Option<Font<'gc>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
+    loader_info: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -26,9 +29,11 @@ impl<'gc> MovieLibrary<'gc> {
         MovieLibrary {
             characters: HashMap::new(),
             export_characters: HashMap::new(),
+            avm2_classes: HashMap::new(),
             jpeg_tables: None,
             device_font: None,
             fonts: HashMap::new(),
+            loader_info: None,
         }
     }
 
@@ -70,6 +75,25 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Links a character ID to a fully-qualified AS3 class name.
+    /// This is done by the `SymbolClass` tag.
+    pub fn register_avm2_class(&mut self, id: CharacterId, class_name: &str) {
+        self.avm2_classes.insert(id, class_name.to_string());
+    }
+
+    /// Returns the name of the AS3 class linked to the given character ID.
+    pub fn avm2_class_name(&self, id: CharacterId) -> Option<&str> {
+        self.avm2_classes.get(&id).map(|name| name.as_str())
+    }
+
+    /// Returns the ID of the character linked to the given AS3 class name.
+    pub fn avm2_class_character(&self, class_name: &str) -> Option<CharacterId> {
+        self.avm2_classes
+            .iter()
+            .find(|(_, name)| *name == class_name)
+            .map(|(id, _)| *id)
+    }
+
     pub fn contains_character(&self, id: CharacterId) -> bool {
         self.characters.contains_key(&id)
     }
@@ -132,6 +156,7 @@ impl<'gc> MovieLibrary<'gc> {
             Character::MovieClip(movie_clip) => Ok(movie_clip.instantiate(gc_context)),
             Character::Button(button) => Ok(button.instantiate(gc_context)),
             Character::Text(text) => Ok(text.instantiate(gc_context)),
+            Character::Video(video) => Ok(video.instantiate(gc_context)),
             _ => Err("Not a DisplayObject".into()),
         }
     }
@@ -193,6 +218,27 @@ impl<'gc> MovieLibrary<'gc> {
     pub fn set_device_font(&mut self, font: Option<Font<'gc>>) {
         self.device_font = font;
     }
+
+    /// The `LoaderInfo` that describes this movie to AVM2 code, if it has
+    /// been created yet.
+    pub fn loader_info(&self) -> Option<Avm2Object<'gc>> {
+        self.loader_info
+    }
+
+    /// Sets the `LoaderInfo` that describes this movie.
+    pub fn set_loader_info(&mut self, loader_info: Avm2Object<'gc>) {
+        self.loader_info = Some(loader_info);
+    }
+}
+
+impl<'gc> BitmapSource for MovieLibrary<'gc> {
+    fn bitmap(&self, id: CharacterId) -> Option<BitmapInfo> {
+        if let Some(Character::Bitmap(bitmap)) = self.characters.get(&id) {
+            bitmap.static_bitmap_info()
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for MovieLibrary<'_> {
@@ -205,6 +251,9 @@ impl Default for MovieLibrary<'_> {
 pub struct Library<'gc> {
     /// All the movie libraries.
     movie_libraries: PtrWeakKeyHashMap<Weak<SwfMovie>, MovieLibrary<'gc>>,
+
+    /// The device fonts created from the player's `FontSource`.
+    device_fonts: HashMap<FontDescriptor, Font<'gc>>,
 }
 
 unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
@@ -213,6 +262,7 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
         for (_, val) in self.movie_libraries.iter() {
             val.trace(cc);
         }
+        self.device_fonts.trace(cc);
     }
 }
 
@@ -229,12 +279,47 @@ impl<'gc> Library<'gc> {
 
         self.movie_libraries.get_mut(&movie).unwrap()
     }
+
+    /// Returns the device font to render text in the given font with.
+    ///
+    /// The font is created from the faces of `font_source` on first use. If
+    /// no fonts were registered, the bundled `fallback_font` is used instead.
+    pub fn get_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        font_source: &FontSource,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+        fallback_font: Option<Font<'gc>>,
+    ) -> Option<Font<'gc>> {
+        if font_source.is_empty() {
+            return fallback_font;
+        }
+
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        let font = self
+            .device_fonts
+            .entry(descriptor.clone())
+            .or_insert_with(|| {
+                let device_faces = font_source.fallback_chain(&descriptor);
+                Font::from_font_source(gc_context, descriptor, device_faces, fallback_font)
+            });
+        Some(*font)
+    }
+
+    /// Forget all device fonts, so that they are recreated from the
+    /// `FontSource` when next used.
+    pub fn clear_device_fonts(&mut self) {
+        self.device_fonts.clear();
+    }
 }
 
 impl<'gc> Default for Library<'gc> {
     fn default() -> Self {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
+            device_fonts: HashMap::new(),
         }
     }
 }
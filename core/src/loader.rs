@@ -1,6 +1,7 @@
 //! Management of async loaders
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::globals::load_vars;
 use crate::avm1::{AvmString, Object, TObject, Value};
 use crate::backend::navigator::OwnedFuture;
 use crate::context::{ActionQueue, ActionType};
@@ -31,6 +32,12 @@ pub enum Error {
     #[error("Non-XML loader spawned as XML loader")]
     NotXmlLoader,
 
+    #[error("Non-text loader spawned as text loader")]
+    NotTextLoader,
+
+    #[error("Non-LoadVars loader spawned as LoadVars loader")]
+    NotLoadVarsLoader,
+
     #[error("Invalid SWF")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
@@ -40,6 +47,15 @@ pub enum Error {
     #[error("Network error")]
     NetworkError(#[from] std::io::Error),
 
+    #[error("Could not fetch {0}: {1}")]
+    FetchError(String, String),
+
+    #[error("Request for {0} failed with HTTP status {1}")]
+    HttpNotOk(String, u16),
+
+    #[error("Security sandbox violation: {0}")]
+    SecurityViolation(String),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
@@ -95,14 +111,23 @@ impl<'gc> LoadManager<'gc> {
         self.0.get_mut(handle)
     }
 
+    /// Remove a finished loader, invalidating its handle.
+    pub fn remove_loader(&mut self, handle: Handle) {
+        self.0.remove(handle);
+    }
+
     /// Kick off a movie clip load.
     ///
+    /// `url` is the location the movie is fetched from, which determines its
+    /// security sandbox.
+    ///
     /// Returns the loader's async process, which you will need to spawn.
     pub fn load_movie_into_clip(
         &mut self,
         player: Weak<Mutex<Player>>,
         target_clip: DisplayObject<'gc>,
         fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
         target_broadcaster: Option<Object<'gc>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
@@ -116,7 +141,7 @@ impl<'gc> LoadManager<'gc> {
         let loader = self.get_loader_mut(handle).unwrap();
         loader.introduce_loader_handle(handle);
 
-        loader.movie_loader(player, fetch)
+        loader.movie_loader(player, fetch, url)
     }
 
     /// Indicates that a movie clip has initialized (ran it's first frame).
@@ -185,6 +210,52 @@ impl<'gc> LoadManager<'gc> {
 
         loader.xml_loader(player, fetch)
     }
+
+    /// Kick off a text data load into an AVM1 object.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_text_into_object(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Object<'gc>,
+        active_clip: DisplayObject<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::Text {
+            self_handle: None,
+            active_clip,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.text_loader(player, fetch)
+    }
+
+    /// Kick off a load of variables into a `LoadVars` object.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_vars_into_object(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Object<'gc>,
+        active_clip: DisplayObject<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::LoadVars {
+            self_handle: None,
+            active_clip,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.load_vars_loader(player, fetch)
+    }
 }
 
 impl<'gc> Default for LoadManager<'gc> {
@@ -243,6 +314,32 @@ pub enum Loader<'gc> {
         /// The target node whose contents will be replaced with the parsed XML.
         target_node: XMLNode<'gc>,
     },
+
+    /// Loader that is loading text data into an AVM1 object, which handles it
+    /// in its `onData` method.
+    Text {
+        /// The handle to refer to this loader instance.
+        self_handle: Option<Handle>,
+
+        /// The active movie clip at the time of load invocation.
+        active_clip: DisplayObject<'gc>,
+
+        /// The target AVM1 object to pass the loaded text to.
+        target_object: Object<'gc>,
+    },
+
+    /// Loader that is loading variables into a `LoadVars` object, which
+    /// handles them in its `onHTTPStatus` and `onData` methods.
+    LoadVars {
+        /// The handle to refer to this loader instance.
+        self_handle: Option<Handle>,
+
+        /// The active movie clip at the time of load invocation.
+        active_clip: DisplayObject<'gc>,
+
+        /// The target `LoadVars` object.
+        target_object: Object<'gc>,
+    },
 }
 
 unsafe impl<'gc> Collect for Loader<'gc> {
@@ -258,6 +355,19 @@ unsafe impl<'gc> Collect for Loader<'gc> {
             }
             Loader::Form { target_object, .. } => target_object.trace(cc),
             Loader::XML { target_node, .. } => target_node.trace(cc),
+            Loader::Text {
+                active_clip,
+                target_object,
+                ..
+            }
+            | Loader::LoadVars {
+                active_clip,
+                target_object,
+                ..
+            } => {
+                active_clip.trace(cc);
+                target_object.trace(cc);
+            }
         }
     }
 }
@@ -272,6 +382,8 @@ impl<'gc> Loader<'gc> {
             Loader::Movie { self_handle, .. } => *self_handle = Some(handle),
             Loader::Form { self_handle, .. } => *self_handle = Some(handle),
             Loader::XML { self_handle, .. } => *self_handle = Some(handle),
+            Loader::Text { self_handle, .. } => *self_handle = Some(handle),
+            Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
         }
     }
 
@@ -286,6 +398,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::Movie { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -297,8 +410,8 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            player.lock().expect("Could not lock player!!").update(
-                |avm1, _avm2, uc| -> Result<(), Error> {
+            let loader_url = player.lock().expect("Could not lock player!!").update(
+                |avm1, _avm2, uc| -> Result<Option<String>, Error> {
                     let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
                         Some(Loader::Movie {
                             target_clip,
@@ -309,6 +422,12 @@ impl<'gc> Loader<'gc> {
                         _ => unreachable!(),
                     };
 
+                    // The movie that the target clip belongs to is the one
+                    // loading the new movie into it.
+                    let loader_url = clip
+                        .movie()
+                        .and_then(|movie| movie.url().map(str::to_string));
+
                     clip.as_movie_clip().unwrap().unload(uc);
 
                     clip.as_movie_clip()
@@ -326,12 +445,14 @@ impl<'gc> Loader<'gc> {
                         );
                     }
 
-                    Ok(())
+                    Ok(loader_url)
                 },
             )?;
 
-            let data = (fetch.await).and_then(|data| Ok((data.len(), SwfMovie::from_data(&data)?)));
-            if let Ok((length, movie)) = data {
+            let data = (fetch.await)
+                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url))?)));
+            if let Ok((length, mut movie)) = data {
+                movie.set_loader_url(loader_url);
                 let movie = Arc::new(movie);
 
                 player
@@ -370,6 +491,7 @@ impl<'gc> Loader<'gc> {
 
                         mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
                         mc.post_instantiation(avm1, uc, clip, None, false);
+                        mc.define_movie_parameters(uc.gc_context);
 
                         let mut morph_shapes = fnv::FnvHashMap::default();
                         mc.preload(avm1, uc, &mut morph_shapes);
@@ -638,4 +760,137 @@ impl<'gc> Loader<'gc> {
             Ok(())
         })
     }
+
+    /// Construct a future for the given text loader.
+    ///
+    /// Once loaded, the text is passed to the `onData` method of the target
+    /// object. If the load fails, `onData` is called without arguments.
+    pub fn text_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::Text { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
+            _ => return Box::pin(async { Err(Error::NotTextLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let text = match fetch.await {
+                Ok(data) => Some(String::from_utf8_lossy(&data).into_owned()),
+                Err(_) => None,
+            };
+
+            player.lock().expect("Could not lock player!!").update(
+                |avm1, _avm2, uc| -> Result<(), Error> {
+                    let (object, active_clip) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Text {
+                            target_object,
+                            active_clip,
+                            ..
+                        }) => (*target_object, *active_clip),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
+
+                    let args = match text {
+                        Some(text) => vec![AvmString::new(uc.gc_context, text).into()],
+                        None => vec![],
+                    };
+                    avm1.run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onData",
+                        &args,
+                    );
+
+                    uc.load_manager.remove_loader(handle);
+
+                    Ok(())
+                },
+            )
+        })
+    }
+    /// Construct a future for the given `LoadVars` loader.
+    ///
+    /// Once the load finishes, the HTTP status is passed to the target's
+    /// `onHTTPStatus` method, and the loaded text to its `onData` method. If
+    /// the load fails, `onData` is called without arguments.
+    pub fn load_vars_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::LoadVars { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotLoadVarsLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let (status, data) = match fetch.await {
+                Ok(data) => (200, Some(data)),
+                Err(Error::HttpNotOk(_, status)) => (status, None),
+                Err(_) => (0, None),
+            };
+
+            player.lock().expect("Could not lock player!!").update(
+                |avm1, _avm2, uc| -> Result<(), Error> {
+                    let (object, active_clip) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::LoadVars {
+                            target_object,
+                            active_clip,
+                            ..
+                        }) => (*target_object, *active_clip),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
+
+                    let length = data.as_ref().map(|data| data.len()).unwrap_or(0);
+                    load_vars::set_bytes_loaded(object, length, uc.gc_context);
+
+                    avm1.run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onHTTPStatus",
+                        &[status.into()],
+                    );
+
+                    let args = match data {
+                        Some(data) => vec![AvmString::new(
+                            uc.gc_context,
+                            String::from_utf8_lossy(&data).into_owned(),
+                        )
+                        .into()],
+                        None => vec![],
+                    };
+                    avm1.run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onData",
+                        &args,
+                    );
+
+                    uc.load_manager.remove_loader(handle);
+
+                    Ok(())
+                },
+            )
+        })
+    }
 }
@@ -4,18 +4,25 @@ use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::listeners::SystemListener;
 use crate::avm1::object::Object;
 use crate::avm1::{Avm1, AvmString, TObject, Timers, Value};
-use crate::avm2::Avm2;
+use crate::avm2::{Avm2, Event as Avm2Event, EventData as Avm2EventData, Value as Avm2Value};
 use crate::backend::input::{InputBackend, MouseCursor};
+use crate::backend::locale::LocaleBackend;
 use crate::backend::storage::StorageBackend;
 use crate::backend::{
     audio::AudioBackend, navigator::NavigatorBackend, render::Letterbox, render::RenderBackend,
+    video::VideoBackend,
 };
+use crate::config::StageScaleMode;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::display_object::{EditText, MorphShape, MovieClip};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
+use crate::external::{ExternalInterface, ExternalInterfaceProvider, Value as ExternalValue};
+use crate::focus_tracker::FocusTracker;
+use crate::font::FontSource;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
+use crate::security::ScriptAccess;
 use crate::tag_utils::SwfMovie;
 use crate::transform::TransformStack;
 use enumset::EnumSet;
@@ -24,8 +31,10 @@ use log::info;
 use rand::{rngs::SmallRng, SeedableRng};
 use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use url::Url;
 
 pub static DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefont3.bin");
 
@@ -51,6 +60,9 @@ struct GcRootData<'gc> {
 
     mouse_hovered_object: Option<DisplayObject<'gc>>, // TODO: Remove GcCell wrapped inside GcCell.
 
+    /// The display object that has keyboard focus.
+    focus_tracker: FocusTracker<'gc>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -73,6 +85,10 @@ struct GcRootData<'gc> {
 
     /// Timed callbacks created with `setInterval`/`setTimeout`.
     timers: Timers<'gc>,
+
+    /// The connection to the host of the movie, and the functions that the
+    /// movie exposed to it.
+    external_interface: ExternalInterface<'gc>,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -92,6 +108,7 @@ impl<'gc> GcRootData<'gc> {
         &mut HashMap<String, Object<'gc>>,
         &mut Vec<EditText<'gc>>,
         &mut Timers<'gc>,
+        &mut ExternalInterface<'gc>,
     ) {
         (
             &mut self.levels,
@@ -104,6 +121,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.shared_objects,
             &mut self.unbound_text_fields,
             &mut self.timers,
+            &mut self.external_interface,
         )
     }
 }
@@ -112,10 +130,12 @@ type Error = Box<dyn std::error::Error>;
 make_arena!(GcArena, GcRoot);
 
 type Audio = Box<dyn AudioBackend>;
+type Video = Box<dyn VideoBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Input = Box<dyn InputBackend>;
 type Storage = Box<dyn StorageBackend>;
+type Locale = Box<dyn LocaleBackend>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -136,6 +156,7 @@ pub struct Player {
     needs_render: bool,
 
     audio: Audio,
+    video: Video,
     renderer: Renderer,
     pub navigator: Navigator,
     input: Input,
@@ -144,6 +165,11 @@ pub struct Player {
     inverse_view_matrix: Matrix,
 
     storage: Storage,
+    locale: Locale,
+
+    /// The fonts that device text is rendered with, in addition to the
+    /// bundled device font.
+    font_source: FontSource,
 
     rng: SmallRng,
 
@@ -158,6 +184,7 @@ pub struct Player {
     movie_width: u32,
     movie_height: u32,
     letterbox: Letterbox,
+    scale_mode: StageScaleMode,
 
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
@@ -173,6 +200,9 @@ pub struct Player {
     /// Time remaining until the next timer will fire.
     time_til_next_timer: Option<f64>,
 
+    /// The maximum amount of time a script may run for before it is aborted.
+    max_execution_duration: Duration,
+
     /// Self-reference to ourselves.
     ///
     /// This is a weak reference that is upgraded and handed out in various
@@ -182,13 +212,16 @@ pub struct Player {
 }
 
 impl Player {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         mut renderer: Renderer,
         audio: Audio,
+        video: Video,
         navigator: Navigator,
         input: Input,
         movie: SwfMovie,
         storage: Storage,
+        locale: Locale,
     ) -> Result<Arc<Mutex<Self>>, Error> {
         let movie = Arc::new(movie);
 
@@ -220,6 +253,8 @@ impl Player {
             view_matrix: Default::default(),
             inverse_view_matrix: Default::default(),
 
+            font_source: FontSource::new(),
+
             rng: SmallRng::from_seed([0u8; 16]), // TODO(Herschel): Get a proper seed on all platforms.
 
             gc_arena: GcArena::new(ArenaParameters::default(), |gc_context| {
@@ -245,6 +280,7 @@ impl Player {
                         library,
                         levels: BTreeMap::new(),
                         mouse_hovered_object: None,
+                        focus_tracker: FocusTracker::new(gc_context),
                         drag_object: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
@@ -253,6 +289,7 @@ impl Player {
                         shared_objects: HashMap::new(),
                         unbound_text_fields: Vec::new(),
                         timers: Timers::new(),
+                        external_interface: ExternalInterface::default(),
                     },
                 ))
             }),
@@ -265,6 +302,7 @@ impl Player {
             viewport_width: movie_width,
             viewport_height: movie_height,
             letterbox: Letterbox::None,
+            scale_mode: StageScaleMode::default(),
 
             mouse_pos: (Twips::new(0), Twips::new(0)),
             is_mouse_down: false,
@@ -272,18 +310,21 @@ impl Player {
 
             renderer,
             audio,
+            video,
             navigator,
             input,
             self_reference: None,
             system: SystemProperties::default(),
             instance_counter: 0,
             time_til_next_timer: None,
+            max_execution_duration: Duration::from_secs(15),
             storage,
+            locale,
         };
 
         player.mutate_with_update_context(|avm1, _avm2, context| {
-            let mut root: DisplayObject =
-                MovieClip::from_movie(context.gc_context, movie.clone()).into();
+            let root_clip = MovieClip::from_movie(context.gc_context, movie.clone());
+            let mut root: DisplayObject = root_clip.into();
             root.set_depth(context.gc_context, 0);
             root.post_instantiation(avm1, context, root, None, false);
             root.set_name(context.gc_context, "");
@@ -308,6 +349,8 @@ impl Player {
                 .into(),
                 EnumSet::empty(),
             );
+
+            root_clip.define_movie_parameters(context.gc_context);
         });
 
         player.build_matrices();
@@ -406,6 +449,26 @@ impl Player {
         self.build_matrices();
     }
 
+    pub fn scale_mode(&self) -> StageScaleMode {
+        self.scale_mode
+    }
+
+    /// Set how the movie is scaled to fit the viewport.
+    pub fn set_scale_mode(&mut self, scale_mode: StageScaleMode) {
+        self.scale_mode = scale_mode;
+        self.build_matrices();
+    }
+
+    pub fn max_execution_duration(&self) -> Duration {
+        self.max_execution_duration
+    }
+
+    /// Set how long scripts may run in a single update before they are
+    /// aborted. Flash Player defaults to 15 seconds.
+    pub fn set_max_execution_duration(&mut self, max_execution_duration: Duration) {
+        self.max_execution_duration = max_execution_duration;
+    }
+
     pub fn handle_event(&mut self, event: PlayerEvent) {
         let mut needs_render = self.needs_render;
 
@@ -463,6 +526,40 @@ impl Player {
             }
         }
 
+        // Tab moves focus between objects.
+        if let PlayerEvent::KeyDown {
+            key_code: KeyCode::Tab,
+        } = event
+        {
+            self.mutate_with_update_context(|avm1, _avm2, context| {
+                let reverse = context.input.is_key_down(KeyCode::Shift);
+                let focus_tracker = context.focus_tracker;
+                focus_tracker.cycle(avm1, context, reverse);
+            });
+            needs_render = true;
+        }
+
+        // Keyboard input is sent to the focused text field.
+        if let PlayerEvent::TextInput { .. } | PlayerEvent::KeyDown { .. } = event {
+            let handled = self.mutate_with_update_context(|avm1, _avm2, context| {
+                let text = match context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                    Some(text) => text,
+                    None => return false,
+                };
+                match event {
+                    PlayerEvent::TextInput { codepoint } => {
+                        text.text_input(codepoint, avm1, context)
+                    }
+                    PlayerEvent::KeyDown { key_code } => text.key_down(key_code, avm1, context),
+                    _ => (),
+                }
+                true
+            });
+            if handled {
+                needs_render = true;
+            }
+        }
+
         // Propagate button events.
         let button_event = match event {
             // ASCII characters convert directly to keyPress button events.
@@ -545,6 +642,14 @@ impl Player {
                 PlayerEvent::MouseDown { .. } => {
                     is_mouse_down = true;
                     needs_render = true;
+                    // Clicking moves focus to the clicked object, if it can take it.
+                    let focus_tracker = context.focus_tracker;
+                    focus_tracker.set(
+                        context
+                            .mouse_hovered_object
+                            .filter(|node| node.is_focusable()),
+                        context,
+                    );
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(avm1, context, ClipEvent::Press);
                     }
@@ -561,6 +666,8 @@ impl Player {
                 _ => (),
             }
 
+            Self::dispatch_avm2_input_event(avm1, avm2, context, event, is_mouse_down);
+
             Self::run_actions(avm1, avm2, context);
         });
         self.is_mouse_down = is_mouse_down;
@@ -569,6 +676,107 @@ impl Player {
         }
     }
 
+    /// Dispatch the AS3 mouse or keyboard events for an input event.
+    ///
+    /// Mouse events target the innermost clip under the mouse, and keyboard
+    /// events target the root movie. Nothing is dispatched to AVM1 movies.
+    fn dispatch_avm2_input_event<'gc>(
+        avm1: &mut Avm1<'gc>,
+        avm2: &mut Avm2<'gc>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        event: PlayerEvent,
+        is_mouse_down: bool,
+    ) {
+        let root = match context.levels.get(&0) {
+            Some(root) => *root,
+            None => return,
+        };
+        if !matches!(root.object2(), Avm2Value::Object(_)) {
+            return;
+        }
+
+        let ctrl_key = context.input.is_key_down(KeyCode::Control);
+        let alt_key = context.input.is_key_down(KeyCode::Alt);
+        let shift_key = context.input.is_key_down(KeyCode::Shift);
+
+        let mut events = vec![];
+        match event {
+            PlayerEvent::MouseMove { .. }
+            | PlayerEvent::MouseDown { .. }
+            | PlayerEvent::MouseUp { .. } => {
+                let mouse_pos = *context.mouse_position;
+                let target = context
+                    .levels
+                    .values()
+                    .rev()
+                    .filter_map(|level| level.as_movie_clip())
+                    .find_map(|clip| clip.avm2_mouse_pick(mouse_pos))
+                    .unwrap_or(root);
+
+                let event_types: &[&str] = match event {
+                    PlayerEvent::MouseDown { .. } => {
+                        avm2.set_mouse_down_target(Some(target));
+                        &["mouseDown"]
+                    }
+                    PlayerEvent::MouseUp { .. } => match avm2.take_mouse_down_target() {
+                        Some(pressed) if DisplayObject::ptr_eq(pressed, target) => {
+                            &["mouseUp", "click"]
+                        }
+                        _ => &["mouseUp"],
+                    },
+                    _ => &["mouseMove"],
+                };
+
+                let (local_x, local_y) = target.global_to_local(mouse_pos);
+                for event_type in event_types {
+                    events.push((
+                        target,
+                        *event_type,
+                        Avm2EventData::Mouse {
+                            local_x: local_x.to_pixels(),
+                            local_y: local_y.to_pixels(),
+                            ctrl_key,
+                            alt_key,
+                            shift_key,
+                            button_down: is_mouse_down,
+                            delta: 0,
+                        },
+                    ));
+                }
+            }
+            PlayerEvent::KeyDown { key_code } | PlayerEvent::KeyUp { key_code } => {
+                let event_type = if let PlayerEvent::KeyDown { .. } = event {
+                    "keyDown"
+                } else {
+                    "keyUp"
+                };
+
+                events.push((
+                    root,
+                    event_type,
+                    Avm2EventData::Keyboard {
+                        char_code: crate::events::key_code_to_char_code(key_code, shift_key),
+                        key_code: u8::from(key_code).into(),
+                        key_location: 0,
+                        ctrl_key,
+                        alt_key,
+                        shift_key,
+                    },
+                ));
+            }
+            _ => (),
+        }
+
+        for (target, event_type, event_data) in events {
+            let mut event = Avm2Event::new(event_type, event_data);
+            event.set_bubbles(true);
+
+            if let Err(e) = avm2.dispatch_display_object_event(avm1, context, event, target) {
+                log::warn!("Error dispatching {} event: {}", event_type, e);
+            }
+        }
+    }
+
     /// Update dragged object, if any.
     fn update_drag(&mut self) {
         let mouse_pos = self.mouse_pos;
@@ -632,7 +840,7 @@ impl Player {
                 // RollOver on new node.I stil
                 new_cursor = MouseCursor::Arrow;
                 if let Some(node) = new_hovered {
-                    new_cursor = MouseCursor::Hand;
+                    new_cursor = node.mouse_cursor();
                     node.handle_clip_event(avm1, context, ClipEvent::RollOver);
                 }
 
@@ -678,7 +886,7 @@ impl Player {
     }
 
     pub fn run_frame(&mut self) {
-        self.update(|avm1, _avm2, update_context| {
+        self.update(|avm1, avm2, update_context| {
             // TODO: In what order are levels run?
             // NOTE: We have to copy all the layer pointers into a separate list
             // because level updates can create more levels, which we don't
@@ -688,6 +896,12 @@ impl Player {
             for mut level in levels {
                 level.run_frame(avm1, update_context);
             }
+
+            avm2.broadcast_event(
+                avm1,
+                update_context,
+                Avm2Event::new("enterFrame", Avm2EventData::Event),
+            );
         });
         self.needs_render = true;
     }
@@ -709,7 +923,7 @@ impl Player {
             matrix: self.view_matrix,
             ..Default::default()
         });
-        self.gc_arena.mutate(|_gc_context, gc_root| {
+        self.gc_arena.mutate(|gc_context, gc_root| {
             let root_data = gc_root.0.read();
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
@@ -722,6 +936,10 @@ impl Player {
             for (_depth, level) in root_data.levels.iter() {
                 level.render(&mut render_context);
             }
+
+            root_data
+                .focus_tracker
+                .render_highlight(gc_context, &mut render_context);
         });
         transform_stack.pop();
 
@@ -873,10 +1091,17 @@ impl Player {
                     is_lazy_initialize,
                     abc,
                 } => {
-                    if let Err(e) = avm2.load_abc(abc, &name, is_lazy_initialize, context) {
+                    if let Err(e) = avm2.load_abc(avm1, abc, &name, is_lazy_initialize, context) {
                         log::warn!("Error loading ABC file: {}", e);
                     }
                 }
+
+                // AS3 class constructor for a linked symbol
+                ActionType::Avm2Construct => {
+                    if let Err(e) = avm2.construct_display_object(avm1, actions.clip, context) {
+                        log::warn!("Error constructing display object: {}", e);
+                    }
+                }
             }
         }
     }
@@ -888,18 +1113,62 @@ impl Player {
             (self.viewport_width as f32, self.viewport_height as f32);
         let movie_aspect = movie_width / movie_height;
         let viewport_aspect = viewport_width / viewport_height;
-        let (scale, margin_width, margin_height) = if viewport_aspect > movie_aspect {
-            let scale = viewport_height / movie_height;
-            (scale, (viewport_width - movie_width * scale) / 2.0, 0.0)
-        } else {
-            let scale = viewport_width / movie_width;
-            (scale, 0.0, (viewport_height - movie_height * scale) / 2.0)
+        let fit_height = viewport_aspect > movie_aspect;
+        let (scale_x, scale_y, margin_width, margin_height) = match self.scale_mode {
+            StageScaleMode::ShowAll if fit_height => {
+                let scale = viewport_height / movie_height;
+                (
+                    scale,
+                    scale,
+                    (viewport_width - movie_width * scale) / 2.0,
+                    0.0,
+                )
+            }
+            StageScaleMode::ShowAll => {
+                let scale = viewport_width / movie_width;
+                (
+                    scale,
+                    scale,
+                    0.0,
+                    (viewport_height - movie_height * scale) / 2.0,
+                )
+            }
+            StageScaleMode::NoBorder if fit_height => {
+                let scale = viewport_width / movie_width;
+                (
+                    scale,
+                    scale,
+                    0.0,
+                    (viewport_height - movie_height * scale) / 2.0,
+                )
+            }
+            StageScaleMode::NoBorder => {
+                let scale = viewport_height / movie_height;
+                (
+                    scale,
+                    scale,
+                    (viewport_width - movie_width * scale) / 2.0,
+                    0.0,
+                )
+            }
+            StageScaleMode::ExactFit => (
+                viewport_width / movie_width,
+                viewport_height / movie_height,
+                0.0,
+                0.0,
+            ),
+            StageScaleMode::NoScale => (
+                1.0,
+                1.0,
+                (viewport_width - movie_width) / 2.0,
+                (viewport_height - movie_height) / 2.0,
+            ),
         };
         self.view_matrix = Matrix {
-            a: scale,
+            a: scale_x,
             b: 0.0,
             c: 0.0,
-            d: scale,
+            d: scale_y,
             tx: Twips::from_pixels(margin_width.into()),
             ty: Twips::from_pixels(margin_height.into()),
         };
@@ -907,9 +1176,11 @@ impl Player {
         self.inverse_view_matrix.invert();
 
         // Calculate letterbox dimensions.
-        // TODO: Letterbox should be an option; the original Flash Player defaults to showing content
-        // in the extra margins.
-        self.letterbox = if margin_width > 0.0 {
+        // Only `showAll` letterboxes; the other modes either fill the viewport
+        // or show content in the margins, like the original Flash Player.
+        self.letterbox = if self.scale_mode != StageScaleMode::ShowAll {
+            Letterbox::None
+        } else if margin_width > 0.0 {
             Letterbox::Pillarbox(margin_width)
         } else if margin_height > 0.0 {
             Letterbox::Letterbox(margin_height)
@@ -930,12 +1201,15 @@ impl Player {
     {
         // We have to do this piecewise borrowing of fields before the closure to avoid
         // completely borrowing `self`.
+        let update_start = self.navigator.time_since_launch();
+        let max_execution_duration = self.max_execution_duration;
         let (
             player_version,
             swf,
             background_color,
             renderer,
             audio,
+            video,
             navigator,
             input,
             rng,
@@ -946,13 +1220,16 @@ impl Player {
             system_properties,
             instance_counter,
             storage,
+            locale,
             needs_render,
+            font_source,
         ) = (
             self.player_version,
             &self.swf,
             &mut self.background_color,
             self.renderer.deref_mut(),
             self.audio.deref_mut(),
+            self.video.deref_mut(),
             self.navigator.deref_mut(),
             self.input.deref_mut(),
             &mut self.rng,
@@ -963,12 +1240,15 @@ impl Player {
             &mut self.system,
             &mut self.instance_counter,
             self.storage.deref_mut(),
+            self.locale.deref(),
             &mut self.needs_render,
+            &self.font_source,
         );
 
         self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
+            let focus_tracker = root_data.focus_tracker;
             let (
                 levels,
                 library,
@@ -980,22 +1260,26 @@ impl Player {
                 shared_objects,
                 unbound_text_fields,
                 timers,
+                external_interface,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
                 player_version,
                 swf,
                 library,
+                font_source,
                 background_color,
                 rng,
                 renderer,
                 audio,
+                video,
                 navigator,
                 input,
                 action_queue,
                 gc_context,
                 levels,
                 mouse_hovered_object,
+                focus_tracker,
                 mouse_position,
                 drag_object,
                 stage_size: (stage_width, stage_height),
@@ -1005,10 +1289,14 @@ impl Player {
                 system: system_properties,
                 instance_counter,
                 storage,
+                locale,
                 shared_objects,
                 unbound_text_fields,
                 timers,
+                external_interface,
                 needs_render,
+                update_start,
+                max_execution_duration,
             };
 
             let ret = f(avm1, avm2, &mut update_context);
@@ -1036,6 +1324,20 @@ impl Player {
         Ok(device_font)
     }
 
+    /// Registers a TrueType or OpenType font file (or a collection of them)
+    /// to render device text with.
+    ///
+    /// Returns the number of font faces that were registered.
+    pub fn register_device_font(&mut self, data: Vec<u8>) -> Result<usize, Error> {
+        let count = self.font_source.register_font_file(data)?;
+
+        // Device fonts are resolved against the registered faces, so any
+        // cached fonts are now stale.
+        self.gc_arena
+            .mutate(|gc_context, gc_root| gc_root.0.write(gc_context).library.clear_device_fonts());
+        Ok(count)
+    }
+
     /// Update the current state of the player.
     ///
     /// The given function will be called with the current stage root, current
@@ -1088,6 +1390,48 @@ impl Player {
         });
     }
 
+    /// Set whether the movie may call methods of its host, and the URL of
+    /// the page embedding it, if any.
+    pub fn set_script_access(&mut self, script_access: ScriptAccess, host_url: Option<Url>) {
+        self.system
+            .security
+            .set_script_access(script_access, host_url);
+    }
+
+    /// Connect the movie to its host, replacing any previous provider.
+    pub fn set_external_interface_provider(
+        &mut self,
+        provider: Option<Box<dyn ExternalInterfaceProvider>>,
+    ) {
+        self.gc_arena.mutate(|gc_context, gc_root| {
+            gc_root
+                .0
+                .write(gc_context)
+                .external_interface
+                .set_provider(provider)
+        });
+    }
+
+    /// Call a function that the movie exposed to its host with
+    /// `ExternalInterface.addCallback`.
+    ///
+    /// Returns `Null` if the movie has no function with the given name.
+    pub fn call_internal_interface(
+        &mut self,
+        name: &str,
+        args: Vec<ExternalValue>,
+    ) -> ExternalValue {
+        self.update(
+            |avm1, avm2, context| match context.external_interface.get_callback(name) {
+                Some(callback) => callback.call(avm1, avm2, context, name, args),
+                None => {
+                    log::warn!("Unknown ExternalInterface callback {}", name);
+                    ExternalValue::Null
+                }
+            },
+        )
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
@@ -0,0 +1,701 @@
+//! Security sandbox model for local and network content
+//!
+//! Every movie plays in a sandbox determined by where it was loaded from.
+//! The sandbox decides which URLs a movie may load from, and which other
+//! movies it may script.
+
+use crate::avm1::activation::Activation;
+use crate::backend::navigator::{OwnedFuture, RequestOptions};
+use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
+use crate::loader::Error;
+use crate::tag_utils::SwfMovie;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::fmt;
+use url::Url;
+
+/// Available type of sandbox for a given SWF
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SandboxType {
+    Remote,
+    LocalWithFile,
+    LocalWithNetwork,
+    LocalTrusted,
+}
+
+impl SandboxType {
+    /// Whether this is one of the sandboxes for local content.
+    pub fn is_local(self) -> bool {
+        self != SandboxType::Remote
+    }
+}
+
+impl fmt::Display for SandboxType {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(match self {
+            SandboxType::Remote => "remote",
+            SandboxType::LocalWithFile => "localWithFile",
+            SandboxType::LocalWithNetwork => "localWithNetwork",
+            SandboxType::LocalTrusted => "localTrusted",
+        })
+    }
+}
+
+/// What a load is going to be used for.
+///
+/// Movies may be loaded across domains freely (they just can't script each
+/// other), but data may only be read from another domain if that domain's
+/// policy file permits it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LoadKind {
+    Movie,
+    Data,
+}
+
+/// The outcome of checking a load against the sandbox.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadAccess {
+    /// The load may proceed.
+    Allowed,
+
+    /// The load is not permitted, for the given reason.
+    Denied(String),
+
+    /// The load may only proceed if the policy file at the given URL
+    /// permits it.
+    RequiresPolicy(Url),
+}
+
+/// Whether movies may call out to the page embedding them, as set by the
+/// `allowScriptAccess` embed parameter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ScriptAccess {
+    Always,
+    SameDomain,
+    Never,
+}
+
+impl ScriptAccess {
+    /// Parse the value of an `allowScriptAccess` parameter.
+    pub fn from_parameter(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "always" => Some(ScriptAccess::Always),
+            "samedomain" => Some(ScriptAccess::SameDomain),
+            "never" => Some(ScriptAccess::Never),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if the URL refers to content on the network.
+fn is_network_url(url: &Url) -> bool {
+    url.scheme() == "http" || url.scheme() == "https"
+}
+
+/// The absolute URL of a movie, if it is known.
+fn movie_url(movie: &SwfMovie) -> Option<Url> {
+    movie.url().and_then(|url| Url::parse(url).ok())
+}
+
+/// Resolve a URL requested by a movie against the movie's own URL.
+///
+/// Returns `None` if the URL is relative and the movie's URL is unknown.
+pub fn resolve_url(movie: &SwfMovie, url: &str) -> Option<Url> {
+    match movie_url(movie) {
+        Some(base) => base.join(url).ok(),
+        None => Url::parse(url).ok(),
+    }
+}
+
+/// Resolve a URL requested by a movie, falling back to the URL as given if it
+/// cannot be made absolute.
+pub fn absolute_url(movie: &SwfMovie, url: &str) -> String {
+    resolve_url(movie, url)
+        .map(Url::into_string)
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Security state of the player, including settings made at runtime by
+/// movies through `System.security`.
+pub struct SecurityState {
+    /// The sandbox that movies loaded from the local filesystem (or from an
+    /// unknown location) play in.
+    local_sandbox_type: SandboxType,
+
+    /// The domains each movie has permitted to script it via `allowDomain`,
+    /// keyed by the URL of the permitting movie.
+    allowed_domains: HashMap<String, Vec<String>>,
+
+    /// Policy files registered via `loadPolicyFile`, to be consulted instead
+    /// of the default `/crossdomain.xml` of their domain.
+    policy_files: Vec<Url>,
+
+    /// Whether movies may call methods of their host.
+    script_access: ScriptAccess,
+
+    /// The URL of the page embedding the movie, if any.
+    host_url: Option<Url>,
+}
+
+impl Default for SecurityState {
+    fn default() -> Self {
+        Self {
+            local_sandbox_type: SandboxType::LocalTrusted,
+            allowed_domains: HashMap::new(),
+            policy_files: Vec::new(),
+            script_access: ScriptAccess::Always,
+            host_url: None,
+        }
+    }
+}
+
+impl SecurityState {
+    /// Set the sandbox that local movies play in.
+    pub fn set_local_sandbox_type(&mut self, sandbox_type: SandboxType) {
+        self.local_sandbox_type = sandbox_type;
+    }
+
+    /// Set whether movies may call methods of their host, and the URL of
+    /// the page embedding them for `ScriptAccess::SameDomain`.
+    pub fn set_script_access(&mut self, script_access: ScriptAccess, host_url: Option<Url>) {
+        self.script_access = script_access;
+        self.host_url = host_url;
+    }
+
+    /// Check whether a movie may call methods of its host through
+    /// `ExternalInterface.call`.
+    ///
+    /// Local movies may only do so from the local-trusted sandbox.
+    pub fn can_call_host(&self, movie: &SwfMovie) -> bool {
+        let sandbox_type = self.sandbox_type(movie);
+        match self.script_access {
+            ScriptAccess::Never => false,
+            _ if sandbox_type == SandboxType::LocalTrusted => true,
+            _ if sandbox_type.is_local() => false,
+            ScriptAccess::Always => true,
+            ScriptAccess::SameDomain => match (movie_url(movie), self.host_url.as_ref()) {
+                (Some(movie_url), Some(host_url)) => movie_url.origin() == host_url.origin(),
+                _ => false,
+            },
+        }
+    }
+
+    /// The sandbox a given movie plays in.
+    pub fn sandbox_type(&self, movie: &SwfMovie) -> SandboxType {
+        match movie_url(movie) {
+            Some(url) if is_network_url(&url) => SandboxType::Remote,
+            _ => self.local_sandbox_type,
+        }
+    }
+
+    /// Check whether a movie may load the given URL.
+    pub fn check_load(&self, movie: &SwfMovie, url: &str, kind: LoadKind) -> LoadAccess {
+        let sandbox_type = self.sandbox_type(movie);
+        let target = resolve_url(movie, url);
+        let target_is_network = target.as_ref().map(is_network_url).unwrap_or(false);
+
+        match sandbox_type {
+            SandboxType::LocalTrusted => LoadAccess::Allowed,
+            SandboxType::LocalWithFile if target_is_network => LoadAccess::Denied(format!(
+                "Movies in the localWithFile sandbox may not access the network ({})",
+                url
+            )),
+            SandboxType::LocalWithFile => LoadAccess::Allowed,
+            SandboxType::LocalWithNetwork | SandboxType::Remote if !target_is_network => {
+                LoadAccess::Denied(format!(
+                    "Movies in the {} sandbox may not access local files ({})",
+                    sandbox_type, url
+                ))
+            }
+            SandboxType::LocalWithNetwork | SandboxType::Remote => {
+                let target = target.expect("network URLs are absolute");
+                let same_origin = movie_url(movie)
+                    .map(|movie_url| movie_url.origin() == target.origin())
+                    .unwrap_or(false);
+
+                if kind == LoadKind::Movie || same_origin {
+                    LoadAccess::Allowed
+                } else {
+                    LoadAccess::RequiresPolicy(self.policy_file_url(&target))
+                }
+            }
+        }
+    }
+
+    /// The policy file that governs access to a URL.
+    ///
+    /// This is the most specific policy file registered for the URL's
+    /// location, or otherwise the `/crossdomain.xml` file of its domain.
+    fn policy_file_url(&self, target: &Url) -> Url {
+        self.policy_files
+            .iter()
+            .filter(|policy| {
+                let directory = &policy.path()[..=policy.path().rfind('/').unwrap_or(0)];
+                policy.origin() == target.origin() && target.path().starts_with(directory)
+            })
+            .max_by_key(|policy| policy.path().len())
+            .cloned()
+            .unwrap_or_else(|| target.join("/crossdomain.xml").expect("valid policy URL"))
+    }
+
+    /// Register a policy file to consult for loads from its location.
+    pub fn add_policy_file(&mut self, url: Url) {
+        if !self.policy_files.contains(&url) {
+            self.policy_files.push(url);
+        }
+    }
+
+    /// Permit movies from `domain` to script `movie`.
+    ///
+    /// The domain may also be given as a URL, or be `*` to permit all
+    /// domains.
+    pub fn allow_domain(&mut self, movie: &SwfMovie, domain: &str) {
+        let domain = Url::parse(domain)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| domain.to_string())
+            .to_ascii_lowercase();
+
+        let domains = self
+            .allowed_domains
+            .entry(movie.url().unwrap_or("").to_string())
+            .or_insert_with(Vec::new);
+        if !domains.contains(&domain) {
+            domains.push(domain);
+        }
+    }
+
+    /// Check whether code from the `requester` movie may script `target`.
+    pub fn can_script(&self, requester: &SwfMovie, target: &SwfMovie) -> bool {
+        let requester_sandbox = self.sandbox_type(requester);
+        let target_sandbox = self.sandbox_type(target);
+        let requester_url = movie_url(requester);
+
+        if requester_sandbox == SandboxType::LocalTrusted {
+            return true;
+        }
+
+        let same_domain = if requester_sandbox.is_local() {
+            requester_sandbox == target_sandbox
+        } else {
+            target_sandbox == SandboxType::Remote
+                && requester_url.as_ref().and_then(|url| url.host_str())
+                    == movie_url(target).as_ref().and_then(|url| url.host_str())
+        };
+        if same_domain {
+            return true;
+        }
+
+        let requester_domain = if requester_sandbox.is_local() {
+            None
+        } else {
+            requester_url
+                .as_ref()
+                .and_then(|url| url.host_str())
+                .map(str::to_ascii_lowercase)
+        };
+        self.allowed_domains
+            .get(target.url().unwrap_or(""))
+            .map(|domains| {
+                domains
+                    .iter()
+                    .any(|domain| domain == "*" || Some(domain) == requester_domain.as_ref())
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// A parsed `crossdomain.xml` policy file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CrossDomainPolicy {
+    /// Whether the policy file disables all policies for its domain through
+    /// `<site-control permitted-cross-domain-policies="none"/>`.
+    disabled: bool,
+
+    /// The domain patterns granted access, and whether each grant requires
+    /// the requester to be loaded securely when the policy is served over
+    /// HTTPS.
+    allow_access_from: Vec<(String, bool)>,
+}
+
+impl CrossDomainPolicy {
+    /// Parse a policy file.
+    ///
+    /// Returns `None` if the data is not a valid policy file.
+    pub fn from_xml(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut buf = Vec::new();
+        let mut policy = Self::default();
+        let mut has_root = false;
+
+        loop {
+            match reader.read_event(&mut buf).ok()? {
+                Event::Start(element) | Event::Empty(element) => {
+                    let attribute = |name: &[u8]| {
+                        element
+                            .attributes()
+                            .filter_map(Result::ok)
+                            .find(|attribute| attribute.key == name)
+                            .and_then(|attribute| {
+                                String::from_utf8(attribute.value.into_owned()).ok()
+                            })
+                    };
+
+                    match element.name() {
+                        b"cross-domain-policy" => has_root = true,
+                        b"site-control" => {
+                            policy.disabled = attribute(b"permitted-cross-domain-policies")
+                                .map(|value| value == "none")
+                                .unwrap_or(false);
+                        }
+                        b"allow-access-from" => {
+                            if let Some(domain) = attribute(b"domain") {
+                                let secure = attribute(b"secure")
+                                    .map(|value| value != "false")
+                                    .unwrap_or(true);
+                                policy
+                                    .allow_access_from
+                                    .push((domain.to_ascii_lowercase(), secure));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        if has_root {
+            Some(policy)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether this policy, served from `policy_url`, grants access to
+    /// a movie loaded from `requester`.
+    ///
+    /// Local movies have no domain, so only a `*` grant applies to them.
+    pub fn allows(&self, policy_url: &Url, requester: Option<&Url>) -> bool {
+        if self.disabled {
+            return false;
+        }
+
+        let requester_host = requester
+            .and_then(|url| url.host_str())
+            .map(str::to_ascii_lowercase);
+        let requester_is_secure = requester.map(|url| url.scheme() == "https");
+
+        self.allow_access_from.iter().any(|(pattern, secure)| {
+            let domain_matches = match (pattern.as_str(), requester_host.as_deref()) {
+                ("*", _) => true,
+                (_, None) => false,
+                (pattern, Some(host)) if pattern.starts_with("*.") => {
+                    host == &pattern[2..] || host.ends_with(&pattern[1..])
+                }
+                (pattern, Some(host)) => pattern == host,
+            };
+            let security_matches =
+                !secure || policy_url.scheme() != "https" || requester_is_secure.unwrap_or(true);
+
+            domain_matches && security_matches
+        })
+    }
+}
+
+/// The URL that a movie loaded by the currently running code will have.
+pub fn loaded_movie_url(activation: &Activation<'_, '_>, url: &str) -> String {
+    match activation.base_clip().movie() {
+        Some(movie) => absolute_url(&movie, url),
+        None => url.to_string(),
+    }
+}
+
+/// Fetch a URL on behalf of the movie whose code is currently running,
+/// enforcing its sandbox.
+///
+/// Loads that require a policy file only start once the policy file has been
+/// fetched and found to permit them.
+pub fn fetch<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    url: &str,
+    options: RequestOptions,
+    kind: LoadKind,
+) -> OwnedFuture<Vec<u8>, Error> {
+    let movie = match activation.base_clip().movie() {
+        Some(movie) => movie,
+        None => return context.navigator.fetch(url, options),
+    };
+
+    match context.system.security.check_load(&movie, url, kind) {
+        LoadAccess::Allowed => context.navigator.fetch(url, options),
+        LoadAccess::Denied(reason) => {
+            log::warn!("Security sandbox violation: {}", reason);
+            Box::pin(async move { Err(Error::SecurityViolation(reason)) })
+        }
+        LoadAccess::RequiresPolicy(policy_url) => {
+            let policy = context
+                .navigator
+                .fetch(policy_url.as_str(), RequestOptions::get());
+            let player = context.player.clone();
+            let requester = movie_url(&movie);
+            let url = absolute_url(&movie, url);
+
+            Box::pin(async move {
+                let allowed = match policy.await {
+                    Ok(data) => CrossDomainPolicy::from_xml(&data)
+                        .map(|policy| policy.allows(&policy_url, requester.as_ref()))
+                        .unwrap_or(false),
+                    Err(_) => false,
+                };
+
+                if !allowed {
+                    let reason = format!("{} does not permit access to {}", policy_url, url);
+                    log::warn!("Security sandbox violation: {}", reason);
+                    return Err(Error::SecurityViolation(reason));
+                }
+
+                let player = player
+                    .and_then(|player| player.upgrade())
+                    .ok_or(Error::Cancelled)?;
+                let fetch = player
+                    .lock()
+                    .expect("Could not lock player!!")
+                    .navigator
+                    .fetch(&url, options);
+                fetch.await
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(url: Option<&str>) -> SwfMovie {
+        // An empty SWF 8 movie: header, zero-sized stage, and an End tag.
+        let data = b"FWS\x08\x0f\x00\x00\x00\x00\x00\x0c\x01\x00\x00\x00";
+        SwfMovie::from_data(data, url.map(str::to_string)).unwrap()
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn sandbox_types() {
+        let security = SecurityState::default();
+        assert_eq!(
+            security.sandbox_type(&movie(Some("http://example.com/a.swf"))),
+            SandboxType::Remote
+        );
+        assert_eq!(
+            security.sandbox_type(&movie(Some("file:///movies/a.swf"))),
+            SandboxType::LocalTrusted
+        );
+        assert_eq!(
+            security.sandbox_type(&movie(None)),
+            SandboxType::LocalTrusted
+        );
+
+        let mut security = SecurityState::default();
+        security.set_local_sandbox_type(SandboxType::LocalWithFile);
+        assert_eq!(
+            security.sandbox_type(&movie(Some("file:///movies/a.swf"))),
+            SandboxType::LocalWithFile
+        );
+    }
+
+    #[test]
+    fn remote_loads() {
+        let security = SecurityState::default();
+        let remote = movie(Some("http://example.com/movies/a.swf"));
+
+        assert_eq!(
+            security.check_load(&remote, "data.txt", LoadKind::Data),
+            LoadAccess::Allowed
+        );
+        assert_eq!(
+            security.check_load(&remote, "http://other.com/b.swf", LoadKind::Movie),
+            LoadAccess::Allowed
+        );
+        assert_eq!(
+            security.check_load(&remote, "http://other.com/data/c.txt", LoadKind::Data),
+            LoadAccess::RequiresPolicy(url("http://other.com/crossdomain.xml"))
+        );
+        assert_eq!(
+            security.check_load(&remote, "https://example.com/data.txt", LoadKind::Data),
+            LoadAccess::RequiresPolicy(url("https://example.com/crossdomain.xml"))
+        );
+        assert!(matches!(
+            security.check_load(&remote, "file:///etc/passwd", LoadKind::Data),
+            LoadAccess::Denied(_)
+        ));
+    }
+
+    #[test]
+    fn local_loads() {
+        let mut security = SecurityState::default();
+        let local = movie(Some("file:///movies/a.swf"));
+
+        security.set_local_sandbox_type(SandboxType::LocalWithFile);
+        assert_eq!(
+            security.check_load(&local, "data.txt", LoadKind::Data),
+            LoadAccess::Allowed
+        );
+        assert!(matches!(
+            security.check_load(&local, "http://example.com/b.swf", LoadKind::Movie),
+            LoadAccess::Denied(_)
+        ));
+
+        security.set_local_sandbox_type(SandboxType::LocalWithNetwork);
+        assert!(matches!(
+            security.check_load(&local, "data.txt", LoadKind::Data),
+            LoadAccess::Denied(_)
+        ));
+        assert_eq!(
+            security.check_load(&local, "http://example.com/b.swf", LoadKind::Movie),
+            LoadAccess::Allowed
+        );
+        assert_eq!(
+            security.check_load(&local, "http://example.com/data.txt", LoadKind::Data),
+            LoadAccess::RequiresPolicy(url("http://example.com/crossdomain.xml"))
+        );
+
+        security.set_local_sandbox_type(SandboxType::LocalTrusted);
+        assert_eq!(
+            security.check_load(&local, "http://example.com/data.txt", LoadKind::Data),
+            LoadAccess::Allowed
+        );
+    }
+
+    #[test]
+    fn registered_policy_files() {
+        let mut security = SecurityState::default();
+        let remote = movie(Some("http://example.com/a.swf"));
+        security.add_policy_file(url("http://other.com/api/policy.xml"));
+        security.add_policy_file(url("http://other.com/api/v2/policy.xml"));
+
+        assert_eq!(
+            security.check_load(&remote, "http://other.com/api/v2/data", LoadKind::Data),
+            LoadAccess::RequiresPolicy(url("http://other.com/api/v2/policy.xml"))
+        );
+        assert_eq!(
+            security.check_load(&remote, "http://other.com/api/data", LoadKind::Data),
+            LoadAccess::RequiresPolicy(url("http://other.com/api/policy.xml"))
+        );
+        assert_eq!(
+            security.check_load(&remote, "http://other.com/data", LoadKind::Data),
+            LoadAccess::RequiresPolicy(url("http://other.com/crossdomain.xml"))
+        );
+    }
+
+    #[test]
+    fn policy_file_parsing() {
+        let policy = CrossDomainPolicy::from_xml(
+            br#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <allow-access-from domain="*.example.com" />
+                <allow-access-from domain="www.test.org" secure="false" />
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+        let policy_url = url("http://other.com/crossdomain.xml");
+        let secure_policy_url = url("https://other.com/crossdomain.xml");
+
+        assert!(policy.allows(&policy_url, Some(&url("http://example.com/a.swf"))));
+        assert!(policy.allows(&policy_url, Some(&url("http://www.example.com/a.swf"))));
+        assert!(!policy.allows(&policy_url, Some(&url("http://badexample.com/a.swf"))));
+        assert!(policy.allows(&policy_url, Some(&url("http://www.test.org/a.swf"))));
+        assert!(!policy.allows(&policy_url, Some(&url("http://test.org/a.swf"))));
+        assert!(!policy.allows(&policy_url, None));
+
+        assert!(!policy.allows(&secure_policy_url, Some(&url("http://example.com/a.swf"))));
+        assert!(policy.allows(&secure_policy_url, Some(&url("https://example.com/a.swf"))));
+        assert!(policy.allows(&secure_policy_url, Some(&url("http://www.test.org/a.swf"))));
+
+        let open = CrossDomainPolicy::from_xml(
+            br#"<cross-domain-policy><allow-access-from domain="*"/></cross-domain-policy>"#,
+        )
+        .unwrap();
+        assert!(open.allows(&policy_url, None));
+
+        let disabled = CrossDomainPolicy::from_xml(
+            br#"<cross-domain-policy>
+                <site-control permitted-cross-domain-policies="none"/>
+                <allow-access-from domain="*"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+        assert!(!disabled.allows(&policy_url, None));
+
+        assert_eq!(CrossDomainPolicy::from_xml(b"<html></html>"), None);
+        assert_eq!(
+            CrossDomainPolicy::from_xml(b"<cross-domain-policy></site-control>"),
+            None
+        );
+    }
+
+    #[test]
+    fn cross_movie_scripting() {
+        let mut security = SecurityState::default();
+        let a = movie(Some("http://a.com/a.swf"));
+        let a2 = movie(Some("http://a.com/other/a2.swf"));
+        let b = movie(Some("http://b.com/b.swf"));
+        let local = movie(Some("file:///movies/local.swf"));
+
+        assert!(security.can_script(&a, &a2));
+        assert!(!security.can_script(&a, &b));
+        assert!(!security.can_script(&b, &a));
+        assert!(!security.can_script(&b, &local));
+        assert!(security.can_script(&local, &b));
+
+        security.allow_domain(&b, "http://a.com/");
+        assert!(security.can_script(&a, &b));
+        assert!(!security.can_script(&b, &a));
+
+        security.set_local_sandbox_type(SandboxType::LocalWithNetwork);
+        assert!(!security.can_script(&local, &a));
+        security.allow_domain(&a, "*");
+        assert!(security.can_script(&local, &a));
+        assert!(security.can_script(&b, &a));
+    }
+
+    #[test]
+    fn host_script_access() {
+        let mut security = SecurityState::default();
+        let a = movie(Some("http://a.com/a.swf"));
+        let b = movie(Some("http://b.com/b.swf"));
+        let local = movie(Some("file:///movies/local.swf"));
+
+        assert!(security.can_call_host(&a));
+        assert!(security.can_call_host(&local));
+
+        security.set_script_access(
+            ScriptAccess::SameDomain,
+            Some(url("http://a.com/page.html")),
+        );
+        assert!(security.can_call_host(&a));
+        assert!(!security.can_call_host(&b));
+        assert!(security.can_call_host(&local));
+
+        security.set_local_sandbox_type(SandboxType::LocalWithFile);
+        assert!(!security.can_call_host(&local));
+
+        security.set_script_access(ScriptAccess::Never, None);
+        assert!(!security.can_call_host(&a));
+
+        assert_eq!(
+            ScriptAccess::from_parameter("sameDomain"),
+            Some(ScriptAccess::SameDomain)
+        );
+        assert_eq!(ScriptAccess::from_parameter("sometimes"), None);
+    }
+}
@@ -52,6 +52,27 @@ pub fn swf_string_cmp_ignore_case(a: &str, b: &str) -> std::cmp::Ordering {
         .cmp(b.chars().map(swf_char_to_lowercase))
 }
 
+/// Converts an index in UTF-16 code units, as used by ActionScript, into a
+/// byte offset in the given string.
+/// Indices past the end of the string are clamped to its length, and an index
+/// inside a surrogate pair maps to the start of that character.
+pub fn utf16_index_to_byte_offset(s: &str, index: usize) -> usize {
+    let mut units = 0;
+    for (offset, c) in s.char_indices() {
+        units += c.len_utf16();
+        if units > index {
+            return offset;
+        }
+    }
+    s.len()
+}
+
+/// Converts a byte offset in the given string into an index in UTF-16 code
+/// units, as used by ActionScript.
+pub fn byte_offset_to_utf16_index(s: &str, offset: usize) -> usize {
+    s.get(..offset).unwrap_or(s).encode_utf16().count()
+}
+
 static UPPERCASE_TABLE: &[(u16, u16)] = &[
     (97, 65),
     (98, 66),
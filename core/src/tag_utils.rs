@@ -2,6 +2,7 @@ use gc_arena::Collect;
 use std::path::Path;
 use std::sync::Arc;
 use swf::{Header, TagCode};
+use url::Url;
 
 pub type Error = Box<dyn std::error::Error>;
 pub type DecodeResult = Result<(), Error>;
@@ -17,6 +18,20 @@ pub struct SwfMovie {
 
     /// Uncompressed SWF data.
     data: Vec<u8>,
+
+    /// The URL the SWF was downloaded from, if known.
+    url: Option<String>,
+
+    /// The URL of the movie that loaded this SWF, if it was loaded by
+    /// another movie.
+    loader_url: Option<String>,
+
+    /// The size of the SWF file, before decompression.
+    compressed_length: usize,
+
+    /// Any parameters provided when loading this movie (also known as
+    /// 'flashvars'), in the order they were given.
+    parameters: Vec<(String, String)>,
 }
 
 impl SwfMovie {
@@ -31,6 +46,10 @@ impl SwfMovie {
                 num_frames: 0,
             },
             data: vec![],
+            url: None,
+            loader_url: None,
+            compressed_length: 0,
+            parameters: Vec::new(),
         }
     }
 
@@ -39,17 +58,29 @@ impl SwfMovie {
         Self {
             header: self.header.clone(),
             data,
+            url: self.url.clone(),
+            loader_url: self.loader_url.clone(),
+            compressed_length: self.compressed_length,
+            parameters: self.parameters.clone(),
         }
     }
 
     /// Utility method to construct a movie from a file on disk.
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let data = std::fs::read(path)?;
-        Self::from_data(&data)
+        let data = std::fs::read(&path)?;
+        let url = path
+            .as_ref()
+            .canonicalize()
+            .ok()
+            .and_then(|path| Url::from_file_path(path).ok())
+            .map(Url::into_string);
+        Self::from_data(&data, url)
     }
 
     /// Construct a movie based on the contents of the SWF datastream.
-    pub fn from_data(swf_data: &[u8]) -> Result<Self, Error> {
+    ///
+    /// The query string of the URL, if any, becomes the movie's parameters.
+    pub fn from_data(swf_data: &[u8], url: Option<String>) -> Result<Self, Error> {
         let swf_stream = swf::read::read_swf_header(&swf_data[..])?;
         let header = swf_stream.header;
         let mut reader = swf_stream.reader;
@@ -74,7 +105,20 @@ impl SwfMovie {
             data
         };
 
-        Ok(Self { header, data })
+        let parameters = url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .map(|url| url.query_pairs().into_owned().collect())
+            .unwrap_or_default();
+
+        Ok(Self {
+            header,
+            data,
+            url,
+            loader_url: None,
+            compressed_length: swf_data.len(),
+            parameters,
+        })
     }
 
     pub fn header(&self) -> &Header {
@@ -90,6 +134,38 @@ impl SwfMovie {
         &self.data
     }
 
+    /// The URL the SWF was downloaded from, if known.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// The URL of the movie that loaded this SWF, if it was loaded by
+    /// another movie.
+    pub fn loader_url(&self) -> Option<&str> {
+        self.loader_url.as_deref()
+    }
+
+    /// Set the URL of the movie that loaded this SWF.
+    pub fn set_loader_url(&mut self, loader_url: Option<String>) {
+        self.loader_url = loader_url;
+    }
+
+    /// The size of the SWF file, before decompression.
+    pub fn compressed_length(&self) -> usize {
+        self.compressed_length
+    }
+
+    /// The parameters (flashvars) the movie was loaded with.
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+
+    /// Add parameters (flashvars) to the movie, such as those given by the
+    /// embedding page.
+    pub fn append_parameters(&mut self, parameters: impl IntoIterator<Item = (String, String)>) {
+        self.parameters.extend(parameters);
+    }
+
     pub fn width(&self) -> u32 {
         (self.header.stage_size.x_max - self.header.stage_size.x_min).to_pixels() as u32
     }
@@ -1,17 +1,24 @@
 //! Tests running SWFs in a headless Ruffle instance.
 //!
 //! Trace output can be compared with correct output from the official Flash Payer.
+//! Rendered output can be compared with a reference image using a software renderer.
 
 use approx::assert_abs_diff_eq;
 use log::{Metadata, Record};
+use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::{
     audio::NullAudioBackend, input::NullInputBackend, render::NullRenderer,
+    video::SoftwareVideoBackend,
 };
+use ruffle_core::events::{KeyCode, PlayerEvent};
+use ruffle_core::external::{ExternalInterfaceProvider, Value as ExternalValue};
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 type Error = Box<dyn std::error::Error>;
@@ -51,12 +58,32 @@ macro_rules! swf_tests_approx {
     };
 }
 
+// This macro generates test cases for a given list of SWFs using `test_swf_image`.
+macro_rules! swf_image_tests {
+    ($($(#[$attr:meta])* ($name:ident, $path:expr, $num_frames:literal, $tolerance:literal $(, [$($capture_frame:literal),*])?),)*) => {
+        $(
+        #[test]
+        $(#[$attr])*
+        fn $name() -> Result<(), Error> {
+            test_swf_image(
+                concat!("tests/swfs/", $path, "/test.swf"),
+                $num_frames,
+                &[$($($capture_frame),*)?],
+                $tolerance
+            )
+        }
+        )*
+    };
+}
+
 // List of SWFs to test.
 // Format: (test_name, test_folder, number_of_frames_to_run)
 // The test folder is a relative to core/tests/swfs
 // Inside the folder is expected to be "test.swf" and "output.txt" with the correct output.
 swf_tests! {
     (add_property, "avm1/add_property", 1),
+    (filters, "avm1/filters", 1),
+    (blend_mode, "avm1/blend_mode", 1),
     (as_transformed_flag, "avm1/as_transformed_flag", 3),
     (attach_movie, "avm1/attach_movie", 1),
     (function_base_clip, "avm1/function_base_clip", 2),
@@ -182,6 +209,7 @@ swf_tests! {
     (loadmovie, "avm1/loadmovie", 2),
     (loadmovienum, "avm1/loadmovienum", 2),
     (loadmovie_method, "avm1/loadmovie_method", 2),
+    (loadmovie_parameters, "avm1/loadmovie_parameters", 2),
     (unloadmovie, "avm1/unloadmovie", 11),
     (unloadmovienum, "avm1/unloadmovienum", 11),
     (unloadmovie_method, "avm1/unloadmovie_method", 11),
@@ -191,6 +219,9 @@ swf_tests! {
     (loadvariables, "avm1/loadvariables", 3),
     (loadvariablesnum, "avm1/loadvariablesnum", 3),
     (loadvariables_method, "avm1/loadvariables_method", 3),
+    (loadvars, "avm1/loadvars", 2),
+    (date, "avm1/date", 1),
+    (bitmap_data, "avm1/bitmap_data", 1),
     (xml_load, "avm1/xml_load", 1),
     (with_return, "avm1/with_return", 1),
     (watch, "avm1/watch", 1),
@@ -228,6 +259,9 @@ swf_tests! {
     (set_interval, "avm1/set_interval", 20),
     (context_menu, "avm1/context_menu", 1),
     (context_menu_item, "avm1/context_menu_item", 1),
+    (text_field_scroll, "avm1/text_field_scroll", 4),
+    (define_font_4, "avm1/define_font_4", 1),
+    (device_fonts, "avm1/device_fonts", 1),
     (as3_hello_world, "avm2/hello_world", 1),
     (as3_function_call, "avm2/function_call", 1),
     (as3_function_call_via_call, "avm2/function_call_via_call", 1),
@@ -262,6 +296,29 @@ swf_tests! {
     (as3_es4_interfaces, "avm2/es4_interfaces", 1),
     (as3_istype, "avm2/istype", 1),
     (as3_instanceof, "avm2/instanceof", 1),
+    (as3_add, "avm2/add", 1),
+    (as3_arithmetic, "avm2/arithmetic", 1),
+    (as3_int_arithmetic, "avm2/int_arithmetic", 1),
+    (as3_bitwise, "avm2/bitwise", 1),
+    (as3_equals, "avm2/equals", 1),
+    (as3_comparison, "avm2/comparison", 1),
+    (as3_if_comparison, "avm2/if_comparison", 1),
+    (as3_convert, "avm2/convert", 1),
+    (as3_lookupswitch, "avm2/lookupswitch", 1),
+    (as3_for_loop, "avm2/for_loop", 1),
+    (as3_try_catch, "avm2/try_catch", 1),
+    (as3_error, "avm2/error", 1),
+    (as3_array, "avm2/array", 1),
+    (as3_array_sort, "avm2/array_sort", 1),
+    (as3_array_iteration, "avm2/array_iteration", 1),
+    (as3_string, "avm2/string", 1),
+    (as3_number, "avm2/number", 1),
+    (as3_boolean, "avm2/boolean", 1),
+    (as3_math, "avm2/math", 1),
+    (as3_global_functions, "avm2/global_functions", 1),
+    (as3_display_object, "avm2/display_object", 1),
+    (as3_events, "avm2/events", 6),
+    (as3_loader_info, "avm2/loader_info", 1),
 }
 
 // TODO: These tests have some inaccuracies currently, so we use approx_eq to test that numeric values are close enough.
@@ -280,6 +337,164 @@ swf_tests_approx! {
     (edittext_underline, "avm1/edittext_underline", 1, 4.0),
 }
 
+// List of SWFs to render and compare against a reference image.
+// Format: (test_name, test_folder, number_of_frames_to_run, per_channel_tolerance[, [earlier_frames_to_capture]])
+// Inside the folder is expected to be "test.swf" and "expected.png" with the correct output
+// after the last frame. Only the last frame is compared, unless earlier frames (counted from 1)
+// are listed; each of those is compared against "expected-<frame>.png".
+// On failure, "actual.png" and "difference.png" (or "actual-<frame>.png" and
+// "difference-<frame>.png") are written to the folder.
+swf_image_tests! {
+    (visual_shapes, "visual/shapes", 1, 2),
+    (visual_gradients, "visual/gradients", 1, 2),
+    (visual_morph_shape, "visual/morph_shape", 2, 2, [1]),
+    (visual_masks, "visual/masks", 1, 2),
+    (visual_bitmap_fill, "visual/bitmap_fill", 1, 2),
+    (visual_filters, "visual/filters", 1, 2),
+    (visual_blend_modes, "visual/blend_modes", 1, 2),
+    (visual_video, "visual/video", 4, 2, [1, 2]),
+    (visual_focus_rect, "visual/focus_rect", 1, 2),
+    (visual_text_scroll, "visual/text_scroll", 1, 2),
+    (visual_define_font_4, "visual/define_font_4", 1, 2),
+    (visual_device_fonts, "visual/device_fonts", 1, 2),
+}
+
+// Tests that send input events to the player.
+// Each event is sent after the frame with the given index (starting at 0) has run.
+#[test]
+fn edittext_input() -> Result<(), Error> {
+    let click = |x, y| {
+        vec![
+            PlayerEvent::MouseMove { x, y },
+            PlayerEvent::MouseDown { x, y },
+            PlayerEvent::MouseUp { x, y },
+        ]
+    };
+    let text = |text: &str| {
+        text.chars()
+            .map(|codepoint| PlayerEvent::TextInput { codepoint })
+            .collect::<Vec<_>>()
+    };
+    let key = |key_code| vec![PlayerEvent::KeyDown { key_code }];
+
+    let frames = vec![
+        vec![
+            click(50.0, 20.0),
+            text("abc"),
+            key(KeyCode::Backspace),
+            key(KeyCode::Left),
+            text("X"),
+            key(KeyCode::Home),
+            text("Y"),
+            key(KeyCode::End),
+            key(KeyCode::Delete),
+        ],
+        vec![text("Q1rs")],
+        vec![key(KeyCode::Backspace), text("!"), click(400.0, 300.0)],
+        vec![],
+        // `maxChars` counts UTF-16 code units, so the emoji takes up two.
+        vec![text("é😀a")],
+    ];
+    let events: Vec<(u32, PlayerEvent)> = frames
+        .into_iter()
+        .enumerate()
+        .flat_map(|(frame, events)| {
+            events
+                .into_iter()
+                .flatten()
+                .map(move |event| (frame as u32, event))
+        })
+        .collect();
+
+    test_swf_with_events(
+        "tests/swfs/avm1/edittext_input/test.swf",
+        6,
+        "tests/swfs/avm1/edittext_input/output.txt",
+        &events,
+    )
+}
+
+#[test]
+fn selection() -> Result<(), Error> {
+    let tab = PlayerEvent::KeyDown {
+        key_code: KeyCode::Tab,
+    };
+    let events = [
+        (0, tab),
+        (0, tab),
+        (0, tab),
+        (0, tab),
+        (1, tab),
+        (1, tab),
+        (2, tab),
+        (3, PlayerEvent::TextInput { codepoint: 'é' }),
+        (3, PlayerEvent::TextInput { codepoint: '😀' }),
+    ];
+
+    test_swf_with_events(
+        "tests/swfs/avm1/selection/test.swf",
+        5,
+        "tests/swfs/avm1/selection/output.txt",
+        &events,
+    )
+}
+
+#[test]
+fn style_sheet() -> Result<(), Error> {
+    let events = [
+        (0, PlayerEvent::MouseMove { x: 18.0, y: 24.0 }),
+        (1, PlayerEvent::MouseMove { x: 400.0, y: 300.0 }),
+    ];
+
+    test_swf_with_events(
+        "tests/swfs/avm1/style_sheet/test.swf",
+        3,
+        "tests/swfs/avm1/style_sheet/output.txt",
+        &events,
+    )
+}
+
+#[test]
+fn external_interface() -> Result<(), Error> {
+    test_swf_with_external_interface(
+        "tests/swfs/avm1/external_interface/test.swf",
+        1,
+        "tests/swfs/avm1/external_interface/output.txt",
+    )
+}
+
+#[test]
+fn as3_external_interface() -> Result<(), Error> {
+    test_swf_with_external_interface(
+        "tests/swfs/avm2/external_interface/test.swf",
+        1,
+        "tests/swfs/avm2/external_interface/output.txt",
+    )
+}
+
+/// An `ExternalInterface` host that traces what the movie asks of it.
+struct TestExternalInterface;
+
+impl ExternalInterfaceProvider for TestExternalInterface {
+    fn call_method(&mut self, name: &str, args: &[ExternalValue]) -> ExternalValue {
+        log::info!(target: "avm_trace", "host: call {}{:?}", name, args);
+        if name == "echo" {
+            args.to_vec().into()
+        } else {
+            ExternalValue::Null
+        }
+    }
+
+    fn on_callback_available(&mut self, name: &str) {
+        log::info!(target: "avm_trace", "host: callback {} available", name);
+    }
+
+    fn on_fs_command(&mut self, command: &str, args: &str) -> bool {
+        log::info!(target: "avm_trace", "host: fscommand {} {:?}", command, args);
+        command != "unhandled"
+    }
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
@@ -313,7 +528,7 @@ macro_rules! assert_eq {
 fn test_swf(swf_path: &str, num_frames: u32, expected_output_path: &str) -> Result<(), Error> {
     let expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
 
-    let trace_log = run_swf(swf_path, num_frames)?;
+    let trace_log = run_swf(swf_path, num_frames, |_| (), |_, _| ())?;
     assert_eq!(
         trace_log, expected_output,
         "ruffle output != flash player output"
@@ -331,7 +546,7 @@ fn test_swf_approx(
     expected_output_path: &str,
     epsilon: f64,
 ) -> Result<(), Error> {
-    let trace_log = run_swf(swf_path, num_frames)?;
+    let trace_log = run_swf(swf_path, num_frames, |_| (), |_, _| ())?;
     let expected_data = std::fs::read_to_string(expected_output_path)?;
     std::assert_eq!(
         trace_log.lines().count(),
@@ -351,9 +566,87 @@ fn test_swf_approx(
     Ok(())
 }
 
-/// Loads an SWF and runs it through the Ruffle core for a number of frames.
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// sending it the given input events.
+/// Tests that the trace output matches the given expected output.
+fn test_swf_with_events(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+    events: &[(u32, PlayerEvent)],
+) -> Result<(), Error> {
+    let expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
+
+    let trace_log = run_swf(
+        swf_path,
+        num_frames,
+        |_| (),
+        |player, frame| {
+            for (_, event) in events
+                .iter()
+                .filter(|(event_frame, _)| *event_frame == frame)
+            {
+                player.handle_event(*event);
+            }
+        },
+    )?;
+    assert_eq!(
+        trace_log, expected_output,
+        "ruffle output != flash player output"
+    );
+
+    Ok(())
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// with a `TestExternalInterface` as its host. After each frame, the host
+/// calls the `add`, `describe` and `missing` callbacks of the movie.
 /// Tests that the trace output matches the given expected output.
-fn run_swf(swf_path: &str, num_frames: u32) -> Result<String, Error> {
+fn test_swf_with_external_interface(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+) -> Result<(), Error> {
+    let expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
+
+    let trace_log = run_swf(
+        swf_path,
+        num_frames,
+        |player| player.set_external_interface_provider(Some(Box::new(TestExternalInterface))),
+        |player, _| {
+            let mut call = |name: &str, args: Vec<ExternalValue>| {
+                let result = player.call_internal_interface(name, args);
+                log::info!(target: "avm_trace", "host: {} returned {:?}", name, result);
+            };
+            call("add", vec![2.0.into(), 3.0.into()]);
+            let describe_arg: BTreeMap<_, _> = vec![
+                ("items".to_string(), vec!["a".into(), "b".into()].into()),
+                ("flag".to_string(), true.into()),
+            ]
+            .into_iter()
+            .collect();
+            call("describe", vec![describe_arg.into()]);
+            call("missing", vec![]);
+        },
+    )?;
+    assert_eq!(
+        trace_log, expected_output,
+        "ruffle output != flash player output"
+    );
+
+    Ok(())
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames.
+/// `before_start` is called before the first frame runs, and `after_frame`
+/// after each frame with its index (starting at 0).
+/// Returns the trace output.
+fn run_swf(
+    swf_path: &str,
+    num_frames: u32,
+    before_start: impl FnOnce(&mut Player),
+    mut after_frame: impl FnMut(&mut Player, u32),
+) -> Result<String, Error> {
     let _ = log::set_logger(&TRACE_LOGGER).map(|()| log::set_max_level(log::LevelFilter::Info));
 
     let base_path = Path::new(swf_path).parent().unwrap();
@@ -363,16 +656,22 @@ fn run_swf(swf_path: &str, num_frames: u32) -> Result<String, Error> {
     let player = Player::new(
         Box::new(NullRenderer),
         Box::new(NullAudioBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
         Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
         Box::new(NullInputBackend::new()),
         movie,
         Box::new(MemoryStorageBackend::default()),
+        Box::new(test_locale()),
     )?;
+    register_test_fonts(&mut player.lock().unwrap())?;
+    before_start(&mut player.lock().unwrap());
 
-    for _ in 0..num_frames {
+    for frame in 0..num_frames {
         player.lock().unwrap().run_frame();
         player.lock().unwrap().update_timers(frame_time);
         executor.poll_all().unwrap();
+
+        after_frame(&mut player.lock().unwrap(), frame);
     }
 
     executor.block_all().unwrap();
@@ -380,6 +679,203 @@ fn run_swf(swf_path: &str, num_frames: u32) -> Result<String, Error> {
     Ok(trace_log())
 }
 
+/// Loads an SWF, runs it through the Ruffle core for a number of frames and renders the final frame,
+/// as well as each frame in `capture_frames`.
+/// Tests that each rendered image matches the expected image in the folder of the SWF:
+/// "expected.png" for the final frame, and "expected-<frame>.png" for the others.
+/// Each color channel of each pixel may differ by at most `tolerance`.
+fn test_swf_image(
+    swf_path: &str,
+    num_frames: u32,
+    capture_frames: &[u32],
+    tolerance: u8,
+) -> Result<(), Error> {
+    let test_path = Path::new(swf_path).parent().unwrap();
+    for image in render_swf(swf_path, num_frames, capture_frames)? {
+        let suffix = if image.frame == num_frames {
+            String::new()
+        } else {
+            format!("-{}", image.frame)
+        };
+        compare_image(test_path, &suffix, &image, tolerance)?;
+    }
+    Ok(())
+}
+
+/// Tests that a rendered image matches "expected<suffix>.png" in the test folder.
+/// On failure, writes "actual<suffix>.png" and "difference<suffix>.png" to the folder.
+fn compare_image(
+    test_path: &Path,
+    suffix: &str,
+    image: &RenderedFrame,
+    tolerance: u8,
+) -> Result<(), Error> {
+    let (width, height, actual) = (image.width, image.height, &image.pixels[..]);
+    let expected_image_path = test_path.join(format!("expected{}.png", suffix));
+    let (expected_width, expected_height, expected) =
+        read_png(expected_image_path.to_str().unwrap())?;
+    std::assert_eq!(
+        (width, height),
+        (expected_width, expected_height),
+        "rendered image size != expected image size"
+    );
+
+    let mut difference = Vec::with_capacity(actual.len());
+    let mut num_mismatched = 0;
+    for (actual, expected) in actual.chunks_exact(4).zip(expected.chunks_exact(4)) {
+        let max_diff = actual
+            .iter()
+            .zip(expected)
+            .map(|(a, b)| (i16::from(*a) - i16::from(*b)).abs() as u8)
+            .max()
+            .unwrap_or(0);
+        if max_diff > tolerance {
+            num_mismatched += 1;
+            difference.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // Show matching pixels as a faded version of the expected image.
+            let gray = ((u16::from(expected[0]) + u16::from(expected[1]) + u16::from(expected[2]))
+                / 3) as u8;
+            let faded = 192 + gray / 4;
+            difference.extend_from_slice(&[faded, faded, faded, 255]);
+        }
+    }
+
+    if num_mismatched > 0 {
+        write_png(
+            &test_path.join(format!("actual{}.png", suffix)),
+            width,
+            height,
+            actual,
+        )?;
+        write_png(
+            &test_path.join(format!("difference{}.png", suffix)),
+            width,
+            height,
+            &difference,
+        )?;
+        panic!(
+            "{} pixels differ from {} by more than {}; see difference{}.png",
+            num_mismatched,
+            expected_image_path.display(),
+            tolerance,
+            suffix
+        );
+    }
+
+    Ok(())
+}
+
+/// A frame of an SWF, rendered by `render_swf`.
+struct RenderedFrame {
+    /// The number of frames that had run, counting from 1.
+    frame: u32,
+    width: u32,
+    height: u32,
+    /// RGBA pixels.
+    pixels: Vec<u8>,
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// rendering it with the software renderer after each frame in `capture_frames`
+/// and after the final frame.
+fn render_swf(
+    swf_path: &str,
+    num_frames: u32,
+    capture_frames: &[u32],
+) -> Result<Vec<RenderedFrame>, Error> {
+    let base_path = Path::new(swf_path).parent().unwrap();
+    let (mut executor, channel) = NullExecutor::new();
+    let movie = SwfMovie::from_path(swf_path)?;
+    let frame_time = 1000.0 / movie.header().frame_rate as f64;
+    let (width, height) = (movie.width(), movie.height());
+    let player = Player::new(
+        Box::new(SoftwareRenderBackend::new(width, height)),
+        Box::new(NullAudioBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
+        Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
+        Box::new(NullInputBackend::new()),
+        movie,
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(test_locale()),
+    )?;
+    register_test_fonts(&mut player.lock().unwrap())?;
+    player
+        .lock()
+        .unwrap()
+        .set_viewport_dimensions(width, height);
+
+    let mut images = Vec::new();
+    for frame in 1..=num_frames {
+        player.lock().unwrap().run_frame();
+        player.lock().unwrap().update_timers(frame_time);
+        executor.poll_all().unwrap();
+        if frame == num_frames {
+            executor.block_all().unwrap();
+        }
+
+        if frame == num_frames || capture_frames.contains(&frame) {
+            let mut player = player.lock().unwrap();
+            player.render();
+            let renderer = player
+                .renderer()
+                .downcast_ref::<SoftwareRenderBackend>()
+                .unwrap();
+            images.push(RenderedFrame {
+                frame,
+                width,
+                height,
+                pixels: renderer.pixels().to_vec(),
+            });
+        }
+    }
+
+    Ok(images)
+}
+
+/// The clock and time zone that tests run in.
+///
+/// A fixed time keeps `Date` output stable. The time zone is deliberately not
+/// UTC, so local and UTC time can be told apart.
+fn test_locale() -> NullLocaleBackend {
+    // 2020-09-13 12:26:40 UTC, in GMT-0500.
+    NullLocaleBackend::with_time(1_600_000_000_000.0, -300)
+}
+
+/// Registers the font files in `core/tests/fonts`, which device text in the
+/// tests is rendered with.
+fn register_test_fonts(player: &mut Player) -> Result<(), Error> {
+    let mut paths = std::fs::read_dir("tests/fonts")?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    for path in paths {
+        player.register_device_font(std::fs::read(path)?)?;
+    }
+    Ok(())
+}
+
+/// Reads an RGBA PNG, returning its width, height and pixels.
+fn read_png(path: &str) -> Result<(u32, u32, Vec<u8>), Error> {
+    let decoder = png::Decoder::new(std::fs::File::open(path)?);
+    let (info, mut reader) = decoder.read_info()?;
+    if info.color_type != png::ColorType::RGBA || info.bit_depth != png::BitDepth::Eight {
+        return Err(format!("{} is not an 8-bit RGBA image", path).into());
+    }
+    let mut data = vec![0; info.buffer_size()];
+    reader.next_frame(&mut data)?;
+    Ok((info.width, info.height, data))
+}
+
+fn write_png(path: &Path, width: u32, height: u32, data: &[u8]) -> Result<(), Error> {
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(data)?;
+    Ok(())
+}
+
 thread_local! {
     static TRACE_LOG: RefCell<String> = RefCell::new(String::new());
 }
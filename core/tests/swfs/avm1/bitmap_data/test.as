@@ -0,0 +1,98 @@
+﻿var BitmapData = flash.display.BitmapData;
+var Rectangle = flash.geom.Rectangle;
+var Point = flash.geom.Point;
+var ColorTransform = flash.geom.ColorTransform;
+
+function argb(c) {
+	return ((c >> 24) & 0xFF).toString(16) + ":" + (c & 0xFFFFFF).toString(16);
+}
+
+var bmp = new BitmapData(10, 8);
+trace(bmp.width + "x" + bmp.height + " " + bmp.transparent);
+trace(bmp.rectangle);
+trace(bmp.getPixel(0, 0).toString(16));
+trace(bmp.getPixel32(0, 0));
+trace(bmp.getPixel(20, 0));
+
+bmp.setPixel(1, 1, 0x123456);
+trace(bmp.getPixel(1, 1).toString(16));
+bmp.setPixel32(2, 2, 0x80FF0000);
+trace(argb(bmp.getPixel32(2, 2)));
+bmp.setPixel32(3, 3, 0x00FF0000);
+trace(bmp.getPixel32(3, 3));
+
+var opaque = new BitmapData(4, 4, false, 0x00FF00);
+trace(argb(opaque.getPixel32(0, 0)));
+opaque.setPixel32(0, 0, 0x800000FF);
+trace(argb(opaque.getPixel32(0, 0)));
+
+bmp.fillRect(new Rectangle(5, 5, 100, 100), 0xFF0000FF);
+trace(bmp.getPixel(4, 4).toString(16) + " " + bmp.getPixel(5, 5).toString(16) + " " + bmp.getPixel(9, 7).toString(16));
+
+var dest = new BitmapData(10, 10, true, 0);
+dest.copyPixels(bmp, new Rectangle(4, 4, 3, 3), new Point(0, 0));
+trace(argb(dest.getPixel32(0, 0)) + " " + argb(dest.getPixel32(1, 1)) + " " + dest.getPixel32(3, 3));
+var half = new BitmapData(2, 2, true, 0x80000000);
+dest.copyPixels(bmp, new Rectangle(5, 5, 2, 2), new Point(5, 5), half, new Point(0, 0), true);
+trace(argb(dest.getPixel32(5, 5)));
+dest.copyPixels(dest, new Rectangle(0, 0, 3, 3), new Point(1, 0));
+trace(argb(dest.getPixel32(1, 0)) + " " + argb(dest.getPixel32(2, 1)));
+
+var colors = new BitmapData(2, 1, false, 0x808080);
+colors.colorTransform(colors.rectangle, new ColorTransform(0.5, 1, 1, 1, 0, 0, 255, 0));
+trace(colors.getPixel(0, 0).toString(16));
+
+var grid = new BitmapData(5, 5, false, 0xFFFFFF);
+grid.fillRect(new Rectangle(2, 0, 1, 5), 0x000000);
+grid.floodFill(0, 0, 0xFF0000);
+trace(grid.getPixel(1, 4).toString(16) + " " + grid.getPixel(2, 2).toString(16) + " " + grid.getPixel(3, 0).toString(16));
+
+var result = new BitmapData(5, 5, false, 0);
+trace(result.threshold(grid, grid.rectangle, new Point(0, 0), "==", 0xFF0000, 0xFF00FF00, 0xFFFFFF, false));
+trace(result.getPixel(0, 0).toString(16) + " " + result.getPixel(2, 0).toString(16));
+trace(result.threshold(grid, grid.rectangle, new Point(0, 0), "<", 0x800000, 0xFF0000FF, 0xFF0000, true));
+trace(result.getPixel(2, 0).toString(16) + " " + result.getPixel(3, 0).toString(16));
+
+var noisy = new BitmapData(4, 4, false, 0);
+noisy.noise(5, 10, 20, 1, false);
+var inRange = true;
+for (var y = 0; y < 4; y++) {
+	for (var x = 0; x < 4; x++) {
+		var c = noisy.getPixel(x, y);
+		if ((c >> 16) < 10 || (c >> 16) > 20 || (c & 0xFFFF) != 0) {
+			inRange = false;
+		}
+	}
+}
+trace(inRange);
+var noisy2 = new BitmapData(4, 4, false, 0);
+noisy2.noise(5, 10, 20, 1, false);
+trace(noisy.getPixel(3, 3) == noisy2.getPixel(3, 3));
+
+var copy = grid.clone();
+trace(copy.getPixel(1, 4).toString(16));
+copy.setPixel(1, 4, 0);
+trace(grid.getPixel(1, 4).toString(16));
+
+copy.dispose();
+trace(copy.width + " " + copy.height + " " + copy.getPixel(0, 0));
+
+var invalid = new BitmapData(0, 10);
+trace(invalid.width);
+var tooBig = new BitmapData(3000, 10);
+trace(tooBig.width);
+
+trace(BitmapData.loadBitmap("missing"));
+
+var holder = this.createEmptyMovieClip("holder", 1);
+holder.attachBitmap(bmp, 1);
+trace(holder._width + " " + holder._height);
+
+var filled = this.createEmptyMovieClip("filled", 2);
+filled.beginBitmapFill(grid);
+filled.moveTo(0, 0);
+filled.lineTo(20, 0);
+filled.lineTo(20, 20);
+filled.lineTo(0, 20);
+filled.endFill();
+trace(filled._width + " " + filled._height);
@@ -0,0 +1,26 @@
+﻿var clip = _root.createEmptyMovieClip("clip", 1);
+trace(clip.blendMode);
+clip.blendMode = "multiply";
+trace(clip.blendMode);
+clip.blendMode = "hardlight";
+trace(clip.blendMode);
+clip.blendMode = 4;
+trace(clip.blendMode);
+clip.blendMode = 14;
+trace(clip.blendMode);
+clip.blendMode = "bogus";
+trace(clip.blendMode);
+clip.blendMode = 0;
+trace(clip.blendMode);
+clip.blendMode = 15;
+trace(clip.blendMode);
+clip.blendMode = "NORMAL";
+trace(clip.blendMode);
+clip.blendMode = "normal";
+trace(clip.blendMode);
+var names = "";
+for (var i = 1; i <= 14; i++) {
+  clip.blendMode = i;
+  names += clip.blendMode + " ";
+}
+trace(names);
@@ -0,0 +1,50 @@
+﻿var now = new Date();
+trace(now.toString());
+trace(now.getTime());
+trace(now.getTimezoneOffset());
+trace(typeof Date());
+trace(Date() == now.toString());
+var epoch = new Date(0);
+trace(epoch.toString());
+trace(epoch.getFullYear() + " " + epoch.getMonth() + " " + epoch.getDate() + " " + epoch.getDay() + " " + epoch.getHours());
+trace(epoch.getUTCFullYear() + " " + epoch.getUTCMonth() + " " + epoch.getUTCDate() + " " + epoch.getUTCDay() + " " + epoch.getUTCHours());
+var d = new Date(2000, 1, 29, 12, 30, 15, 500);
+trace(d.toString());
+trace(d.valueOf());
+trace(d.getYear());
+trace(d.getMinutes() + ":" + d.getSeconds() + "." + d.getMilliseconds());
+trace(d.getUTCHours() + ":" + d.getUTCMinutes());
+var short = new Date(99, 11, 31);
+trace(short.toString());
+trace(Date.UTC(2000, 0));
+trace(Date.UTC(99, 11, 31, 23, 59, 59, 999));
+trace(d.setMonth(12));
+trace(d.toString());
+trace(d.setDate(0));
+trace(d.toString());
+trace(d.setHours(25, 61));
+trace(d.toString());
+trace(d.setMinutes(-1, 30, 250));
+trace(d.toString());
+trace(d.setSeconds(5));
+trace(d.setMilliseconds(999));
+trace(d.getMilliseconds());
+trace(d.setFullYear(1999, 5, 15));
+trace(d.toString());
+trace(d.setYear(85));
+trace(d.getFullYear());
+trace(d.setUTCHours(0));
+trace(d.getUTCHours() + " " + d.getHours());
+trace(d.setUTCFullYear(2010, 0, 1));
+trace(d.toString());
+trace(d.setTime(86400000));
+trace(d.toString());
+var invalid = new Date(NaN);
+trace(invalid.toString());
+trace(invalid.getFullYear());
+trace(invalid.getTimezoneOffset());
+trace(invalid.setHours(1));
+trace(invalid.setFullYear(2005));
+trace(invalid.toString());
+trace(new Date(8.64e15 + 1).getTime());
+trace(d - epoch);
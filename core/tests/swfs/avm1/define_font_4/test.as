@@ -0,0 +1,19 @@
+﻿this.createTextField("field", 1, 10, 10, 180, 40);
+var format = new TextFormat();
+format.font = "TestCFF";
+format.size = 20;
+field.setNewTextFormat(format);
+field.embedFonts = true;
+field.text = "AOIS";
+trace("// embedFonts = true");
+trace(field.textWidth);
+trace(field.textHeight);
+field.text = "A A";
+trace(field.textWidth);
+field.text = "AZ";
+trace("// missing glyph");
+trace(field.textWidth);
+field.embedFonts = false;
+field.text = "AOIS";
+trace("// embedFonts = false");
+trace(field.textWidth == 44);
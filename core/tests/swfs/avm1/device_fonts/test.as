@@ -0,0 +1,17 @@
+﻿function measure(font, bold, text) {
+    this.createTextField("field", 1, 0, 0, 400, 100);
+    var format = new TextFormat();
+    format.font = font;
+    format.bold = bold;
+    format.size = 20;
+    field.setNewTextFormat(format);
+    field.text = text;
+    trace("// " + font + (bold ? " bold" : "") + ": " + text);
+    trace(field.textWidth);
+}
+measure("TestSans", false, "AO-=");
+measure("TestSans", true, "AO-=");
+measure("testsans", false, "AA");
+measure("Missing, TestSans", false, "A A");
+measure("TestSans", false, "日日");
+measure("TestCJK", false, "日");
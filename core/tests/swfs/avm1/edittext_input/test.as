@@ -0,0 +1,46 @@
+﻿this.createTextField("field", 1, 10, 10, 200, 20);
+trace("type: " + field.type);
+trace("maxChars: " + field.maxChars);
+trace("restrict: " + field.restrict);
+trace("password: " + field.password);
+trace("selectable: " + field.selectable);
+field.type = "input";
+trace("type: " + field.type);
+field.onSetFocus = function(oldFocus) {
+	trace("onSetFocus: " + oldFocus);
+};
+field.onKillFocus = function(newFocus) {
+	trace("onKillFocus: " + newFocus);
+};
+field.onChanged = function(tf) {
+	trace("onChanged: " + tf.text);
+};
+//frame
+trace("text: " + field.text);
+field.maxChars = 6;
+field.restrict = "a-z";
+trace("maxChars: " + field.maxChars);
+trace("restrict: " + field.restrict);
+//frame
+trace("text: " + field.text);
+field.password = true;
+trace("password: " + field.password);
+trace("text: " + field.text);
+field.restrict = "^a-z";
+trace("restrict: " + field.restrict);
+//frame
+trace("text: " + field.text);
+field.restrict = null;
+field.maxChars = null;
+trace("maxChars: " + field.maxChars);
+trace("restrict: " + field.restrict);
+field.type = "dynamic";
+trace("type: " + field.type);
+//frame
+field.type = "input";
+field.maxChars = 3;
+field.text = "";
+Selection.setFocus(field);
+//frame
+trace("text: " + field.text);
+stop();
@@ -0,0 +1,47 @@
+﻿var ExternalInterface = flash.external.ExternalInterface;
+
+trace("// ExternalInterface.available");
+trace(ExternalInterface.available);
+
+trace("// ExternalInterface.call");
+var result = ExternalInterface.call("echo", 1.5, "two", true, null, [1, "a", [2]], {x: 5, y: "z"}, function() {});
+trace(typeof result);
+trace(result.length);
+trace(result[0]);
+trace(result[1]);
+trace(result[2]);
+trace(result[3]);
+trace(result[4].length);
+trace(result[4][1]);
+trace(result[4][2][0]);
+trace(result[5].x);
+trace(result[5].y);
+trace(result[6]);
+trace(ExternalInterface.call("unknown"));
+trace(ExternalInterface.call());
+
+trace("// ExternalInterface.call with a cyclic object");
+var cyclic = {name: "cyclic"};
+cyclic.self = cyclic;
+var shared = [1];
+result = ExternalInterface.call("echo", cyclic, [shared, shared]);
+trace(result[0].name);
+trace(result[0].self);
+trace(result[1][1][0]);
+
+trace("// ExternalInterface.addCallback");
+var obj = {name: "obj"};
+trace(ExternalInterface.addCallback("add", obj, function(a, b) {
+    trace("add called on " + this.name);
+    return a + b;
+}));
+trace(ExternalInterface.addCallback("describe", null, function(o) {
+    return {count: o.items.length, first: o.items[0], flag: !o.flag};
+}));
+trace(ExternalInterface.addCallback("notAFunction", null, 5));
+trace(ExternalInterface.addCallback("missingArguments"));
+
+trace("// fscommand");
+fscommand("handled", "some args");
+fscommand("unhandled", "");
+_root.getURL("FSCommand:fromGetURL", "more args");
@@ -0,0 +1,96 @@
+﻿// Default values and constructor arguments.
+var blur = new flash.filters.BlurFilter(10, 20, 2);
+trace("// new BlurFilter(10, 20, 2)");
+trace(blur.blurX);
+trace(blur.blurY);
+trace(blur.quality);
+trace(blur instanceof flash.filters.BitmapFilter);
+
+trace("// blurX = 300, quality = 20");
+blur.blurX = 300;
+blur.quality = 20;
+trace(blur.blurX);
+trace(blur.quality);
+
+var shadow = new flash.filters.DropShadowFilter();
+trace("// new DropShadowFilter()");
+trace(shadow.distance);
+trace(shadow.angle);
+trace(shadow.color);
+trace(shadow.alpha);
+trace(shadow.blurX);
+trace(shadow.strength);
+trace(shadow.quality);
+trace(shadow.inner);
+trace(shadow.knockout);
+
+var glow = new flash.filters.GlowFilter(0x00ff00, 1, 2, 3, 4, 1, true, true);
+trace("// new GlowFilter(0x00ff00, 1, 2, 3, 4, 1, true, true)");
+trace(glow.color);
+trace(glow.alpha);
+trace(glow.blurX);
+trace(glow.blurY);
+trace(glow.strength);
+trace(glow.inner);
+trace(glow.knockout);
+
+var bevel = new flash.filters.BevelFilter();
+trace("// new BevelFilter()");
+trace(bevel.highlightColor);
+trace(bevel.shadowColor);
+trace(bevel.type);
+bevel.type = "full";
+trace(bevel.type);
+bevel.type = "outer";
+trace(bevel.type);
+
+var gradient = new flash.filters.GradientGlowFilter(4, 90, [0xff0000, 0x0000ff], [1, 0], [0, 255]);
+trace("// new GradientGlowFilter(4, 90, [0xff0000, 0x0000ff], [1, 0], [0, 255])");
+trace(gradient.angle);
+trace(gradient.colors);
+trace(gradient.alphas);
+trace(gradient.ratios);
+trace(gradient.type);
+
+var matrix = new flash.filters.ColorMatrixFilter([0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0]);
+trace("// new ColorMatrixFilter(...)");
+trace(matrix.matrix);
+
+var convolution = new flash.filters.ConvolutionFilter(3, 3, [1, 1, 1, 1, 1, 1, 1, 1, 1], 9);
+trace("// new ConvolutionFilter(3, 3, [1, 1, 1, 1, 1, 1, 1, 1, 1], 9)");
+trace(convolution.matrixX);
+trace(convolution.matrixY);
+trace(convolution.matrix);
+trace(convolution.divisor);
+trace(convolution.bias);
+trace(convolution.preserveAlpha);
+trace(convolution.clamp);
+
+// Cloning.
+var copy = blur.clone();
+trace("// blur.clone()");
+trace(copy instanceof flash.filters.BlurFilter);
+trace(copy == blur);
+trace(copy.blurX);
+copy.blurX = 1;
+trace(blur.blurX);
+
+// Filters on a display object.
+_root.createEmptyMovieClip("clip", 1);
+trace("// clip.filters");
+trace(clip.filters.length);
+clip.filters = [blur, shadow];
+var filters = clip.filters;
+trace("// clip.filters = [blur, shadow]");
+trace(filters.length);
+trace(filters[0] instanceof flash.filters.BlurFilter);
+trace(filters[1] instanceof flash.filters.DropShadowFilter);
+trace(filters[0] == blur);
+trace(filters[0].blurY);
+filters[0].blurY = 1;
+trace(clip.filters[0].blurY);
+blur.blurY = 2;
+trace(clip.filters[0].blurY);
+clip.filters = [];
+trace("// clip.filters = []");
+trace(clip.filters.length);
@@ -0,0 +1,5 @@
+﻿trace("foo: " + foo);
+trace("n: " + n);
+trace("text: " + text);
+trace("url: " + _url.substr(_url.lastIndexOf("/") + 1));
+stop();
@@ -0,0 +1,6 @@
+﻿trace("root url: " + _url.substr(_url.lastIndexOf("/") + 1));
+trace("root foo: " + foo);
+this.createEmptyMovieClip("child", 1);
+child.loadMovie("child.swf?foo=bar&n=42&text=a%20b");
+//frame
+trace("child foo: " + child.foo);
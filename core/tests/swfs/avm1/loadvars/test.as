@@ -0,0 +1,31 @@
+﻿var lv = new LoadVars();
+trace(lv.contentType);
+lv.a = "hello world";
+lv.b = 5;
+trace(lv.toString());
+lv.decode("c=1&d=two+words%21");
+trace(lv.c);
+trace(lv.d);
+trace(lv.loaded);
+trace(lv.getBytesLoaded());
+var target = new LoadVars();
+target.onHTTPStatus = function(status) {
+	trace("onHTTPStatus: " + status);
+};
+target.onLoad = function(success) {
+	trace("onLoad: " + success);
+	trace("x: " + this.x);
+	trace("y: " + this.y);
+	trace("loaded: " + this.loaded);
+	trace("bytes: " + this.getBytesLoaded() + "/" + this.getBytesTotal());
+};
+trace(target.load("data.txt"));
+trace(target.loaded);
+trace(target.load());
+var missing = new LoadVars();
+missing.onLoad = function(success) {
+	trace("missing onLoad: " + success);
+};
+missing.load("missing.txt");
+//frame
+//frame
@@ -0,0 +1,75 @@
+﻿function name(object) {
+	if (object == null) {
+		return "null";
+	}
+	return object._name;
+}
+
+this.createTextField("a", 1, 10, 10, 100, 20);
+a.type = "input";
+a.text = "hello";
+this.createTextField("b", 2, 10, 50, 100, 20);
+b.type = "input";
+b.text = "world";
+this.createEmptyMovieClip("c", 3);
+c.beginFill(0xFF0000);
+c.moveTo(10, 130);
+c.lineTo(50, 130);
+c.lineTo(50, 150);
+c.lineTo(10, 150);
+c.lineTo(10, 130);
+c.endFill();
+c.onPress = function() {
+};
+c.onSetFocus = function(oldFocus) {
+	trace("c.onSetFocus: " + name(oldFocus));
+};
+c.onKillFocus = function(newFocus) {
+	trace("c.onKillFocus: " + name(newFocus));
+};
+this.createTextField("d", 4, 10, 90, 100, 20);
+d.text = "dynamic";
+
+var listener = {};
+listener.onSetFocus = function(oldFocus, newFocus) {
+	trace("Selection.onSetFocus: " + name(oldFocus) + " -> " + name(newFocus));
+};
+Selection.addListener(listener);
+
+trace("getFocus: " + Selection.getFocus());
+trace("indices: " + Selection.getBeginIndex() + " " + Selection.getEndIndex() + " " + Selection.getCaretIndex());
+trace("setFocus(a): " + Selection.setFocus(a));
+trace("getFocus: " + Selection.getFocus());
+trace("indices: " + Selection.getBeginIndex() + " " + Selection.getEndIndex() + " " + Selection.getCaretIndex());
+Selection.setSelection(4, 1);
+trace("indices: " + Selection.getBeginIndex() + " " + Selection.getEndIndex() + " " + Selection.getCaretIndex());
+a.text = "héllo😀!";
+Selection.setSelection(2, 7);
+trace("indices: " + Selection.getBeginIndex() + " " + Selection.getEndIndex() + " " + Selection.getCaretIndex());
+trace("setFocus(\"b\"): " + Selection.setFocus("b"));
+trace("getFocus: " + Selection.getFocus());
+trace("setFocus(\"missing\"): " + Selection.setFocus("missing"));
+trace("getFocus: " + Selection.getFocus());
+trace("setFocus(null): " + Selection.setFocus(null));
+trace("getFocus: " + Selection.getFocus());
+//frame
+trace("getFocus: " + Selection.getFocus());
+a.tabIndex = 2;
+c.tabIndex = 1;
+//frame
+trace("getFocus: " + Selection.getFocus());
+c.tabEnabled = false;
+trace("_focusrect: " + _focusrect);
+trace("c._focusrect: " + c._focusrect);
+c._focusrect = false;
+trace("c._focusrect: " + c._focusrect);
+//frame
+trace("getFocus: " + Selection.getFocus());
+Selection.removeListener(listener);
+Selection.setFocus(b);
+trace("getFocus: " + Selection.getFocus());
+b.text = "";
+//frame
+trace("typed: " + b.text);
+trace("indices: " + Selection.getBeginIndex() + " " + Selection.getEndIndex() + " " + Selection.getCaretIndex());
+stop();
@@ -0,0 +1,51 @@
+﻿function fmt(i) {
+	var f = field.getTextFormat(i);
+	return f.color + " " + f.size + " " + f.bold + " " + f.align + " " + f.underline;
+}
+
+var css = new TextField.StyleSheet();
+trace(css instanceof TextField.StyleSheet);
+trace(css.parseCSS("p { color: #FF0000; font-size: 20px; } /* comment */ .Title, h1 { font-weight: bold; text-align: center; } h1 { display: block; } a { color: #0000FF; } a:hover { color: #00FF00; text-decoration: underline; } .hidden { display: none; }"));
+trace(css.getStyleNames().join(","));
+var p = css.getStyle("p");
+trace(p.color);
+trace(p.fontSize);
+trace(css.getStyle("h1").display);
+trace(css.getStyle("missing"));
+trace(css.parseCSS("p { color #00FF00 }"));
+trace(css.getStyle("p").color);
+css.setStyle("em", {fontStyle: "italic", color: "#123456"});
+trace(css.getStyle("EM").fontStyle);
+css.setStyle("em", null);
+trace(css.getStyle("em"));
+var tf = css.transform(css.getStyle("p"));
+trace(tf.color);
+trace(tf.size);
+trace(tf.bold);
+
+this.createTextField("field", 1, 10, 10, 300, 200);
+field.html = true;
+field.multiline = true;
+field.wordWrap = true;
+field.styleSheet = css;
+trace(field.styleSheet == css);
+field.htmlText = "<a href='x'>link</a><p>Hello</p><h1>Head</h1>text<span class='hidden'>gone</span><p class='title'>T</p>";
+trace(field.text);
+trace(field.htmlText);
+trace(fmt(0));
+trace(fmt(5));
+trace(fmt(11));
+trace(fmt(15));
+trace(fmt(20));
+
+var loaded = new TextField.StyleSheet();
+loaded.onLoad = function(success) {
+	trace("onLoad: " + success);
+	trace(this.getStyleNames().join(","));
+	trace(this.getStyle(".note").fontFamily);
+};
+trace(loaded.load("style.css"));
+//frame
+trace("hover: " + fmt(0));
+//frame
+trace("out: " + fmt(0));
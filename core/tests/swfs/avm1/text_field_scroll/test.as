@@ -0,0 +1,61 @@
+﻿this.createTextField("field", 1, 10, 10, 100, 50);
+field.multiline = true;
+field.onScroller = function(tf) {
+	trace("onScroller: scroll " + tf.scroll + ", maxscroll " + tf.maxscroll + ", hscroll " + tf.hscroll);
+};
+trace("scroll: " + field.scroll);
+trace("maxscroll: " + field.maxscroll);
+trace("bottomScroll: " + field.bottomScroll);
+trace("hscroll: " + field.hscroll);
+trace("maxhscroll: " + field.maxhscroll);
+field.text = "1\n2\n3\n4\n5\n6\n7\n8";
+trace("maxscroll: " + field.maxscroll);
+trace("bottomScroll: " + field.bottomScroll);
+field.scroll = 3;
+trace("scroll: " + field.scroll);
+trace("bottomScroll: " + field.bottomScroll);
+field.scroll = 100;
+trace("scroll: " + field.scroll);
+trace("bottomScroll: " + field.bottomScroll);
+field.scroll = -5;
+trace("scroll: " + field.scroll);
+field.scroll = field.maxscroll;
+field.text = "1\n2";
+trace("scroll: " + field.scroll);
+trace("maxscroll: " + field.maxscroll);
+//frame
+this.createTextField("line", 2, 10, 70, 50, 20);
+line.text = "This line is too long for the text field";
+trace("maxhscroll > 0: " + (line.maxhscroll > 0));
+line.hscroll = 20;
+trace("hscroll: " + line.hscroll);
+line.hscroll = 100000;
+trace("hscroll is maxhscroll: " + (line.hscroll == line.maxhscroll));
+line.hscroll = -10;
+trace("hscroll: " + line.hscroll);
+//frame
+trace("background: " + field.background);
+trace("backgroundColor: " + field.backgroundColor);
+trace("borderColor: " + field.borderColor);
+field.background = true;
+field.backgroundColor = 0xFF8000;
+field.borderColor = 0x123456;
+trace("background: " + field.background);
+trace("backgroundColor: " + field.backgroundColor);
+trace("borderColor: " + field.borderColor);
+trace("textColor: " + field.textColor);
+field.textColor = 0x00FF00;
+trace("textColor: " + field.textColor);
+trace("format color: " + field.getTextFormat().color);
+trace("new format color: " + field.getNewTextFormat().color);
+//frame
+this.createTextField("html", 3, 10, 100, 200, 50);
+html.html = true;
+trace("condenseWhite: " + html.condenseWhite);
+html.htmlText = "a   b\n\n<b>c</b>";
+trace("length: " + html.text.length);
+html.condenseWhite = true;
+trace("condenseWhite: " + html.condenseWhite);
+html.htmlText = "a   b\n\n<b>c</b>";
+trace("text: " + html.text);
+trace("length: " + html.text.length);
@@ -0,0 +1,51 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//1 + 2");
+trace(1 + 2);
+
+trace("//1.5 + 2");
+trace(1.5 + 2);
+
+trace("//0.1 + 0.2");
+trace(0.1 + 0.2);
+
+trace("//2147483647 + 1");
+trace(2147483647 + 1);
+
+trace("//\"a\" + 1");
+trace("a" + 1);
+
+trace("//1 + \"2\"");
+trace(1 + "2");
+
+trace("//\"x\" + true");
+trace("x" + true);
+
+trace("//\"a\" + null");
+trace("a" + null);
+
+trace("//\"a\" + undefined");
+trace("a" + undefined);
+
+trace("//\"a\" + 1.5");
+trace("a" + 1.5);
+
+trace("//true + 1");
+trace(true + 1);
+
+trace("//true + true");
+trace(true + true);
+
+trace("//null + 1");
+trace(null + 1);
+
+trace("//undefined + 1");
+trace(undefined + 1);
+
+trace("//NaN + 1");
+trace(NaN + 1);
+
+trace("//{} + \"!\"");
+trace({} + "!");
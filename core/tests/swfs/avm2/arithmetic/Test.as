@@ -0,0 +1,79 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//5 - 3");
+trace(5 - 3);
+
+trace("//\"5\" - 2");
+trace("5" - 2);
+
+trace("//\"abc\" - 1");
+trace("abc" - 1);
+
+trace("//null - 1");
+trace(null - 1);
+
+trace("//4 * 2.5");
+trace(4 * 2.5);
+
+trace("//\"3\" * \"4\"");
+trace("3" * "4");
+
+trace("//undefined * 2");
+trace(undefined * 2);
+
+trace("//7 / 2");
+trace(7 / 2);
+
+trace("//1 / 3");
+trace(1 / 3);
+
+trace("//1 / 0");
+trace(1 / 0);
+
+trace("//-1 / 0");
+trace(-1 / 0);
+
+trace("//0 / 0");
+trace(0 / 0);
+
+trace("//7 % 3");
+trace(7 % 3);
+
+trace("//-7 % 3");
+trace(-7 % 3);
+
+trace("//5.5 % 2");
+trace(5.5 % 2);
+
+trace("//5 % 0");
+trace(5 % 0);
+
+trace("//-(5)");
+trace(-(5));
+
+trace("//-(\"3\")");
+trace(-("3"));
+
+trace("//-(true)");
+trace(-(true));
+
+trace("//x = \"5\"; ++x");
+var x1 = "5";
+trace(++x1);
+
+trace("//x = 1.5; --x");
+var x2 = 1.5;
+trace(--x2);
+
+trace("//x = undefined; ++x");
+var x3 = undefined;
+trace(++x3);
+
+trace("//var x = 1.5; x++; x++; x--;");
+var y = 1.5;
+y++;
+y++;
+y--;
+trace(y);
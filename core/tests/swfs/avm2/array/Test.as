@@ -0,0 +1,140 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//new Array().length");
+trace(new Array().length);
+
+var arr = new Array(3);
+trace("//new Array(3)");
+trace(new Array(3));
+
+trace("//new Array(3).length");
+trace(new Array(3).length);
+
+trace("//new Array(1, 2, 3)");
+trace(new Array(1, 2, 3));
+
+trace("//new Array(\"x\").length");
+trace(new Array("x").length);
+
+trace("//Array(2).length");
+trace(Array(2).length);
+
+arr = [1, 2, 3];
+trace("//arr");
+trace(arr);
+
+trace("//arr[0]");
+trace(arr[0]);
+
+trace("//arr[5]");
+trace(arr[5]);
+
+trace("//arr[4] = \"e\"");
+arr[4] = "e";
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.length");
+trace(arr.length);
+
+trace("//arr.length = 2");
+arr.length = 2;
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.push(3, 4)");
+trace(arr.push(3, 4));
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.pop()");
+trace(arr.pop());
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.unshift(-1, 0)");
+trace(arr.unshift(-1, 0));
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.shift()");
+trace(arr.shift());
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.reverse()");
+trace(arr.reverse());
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.join(\" - \")");
+trace(arr.join(" - "));
+
+trace("//arr.join()");
+trace(arr.join());
+
+trace("//arr.toString()");
+trace(arr.toString());
+
+trace("//arr.slice(1, 3)");
+trace(arr.slice(1, 3));
+
+trace("//arr.slice(-2)");
+trace(arr.slice(-2));
+
+trace("//arr.slice()");
+trace(arr.slice());
+
+trace("//arr.concat(4, [5, 6], \"x\")");
+trace(arr.concat(4, [5, 6], "x"));
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.splice(1, 2, \"a\", \"b\", \"c\")");
+trace(arr.splice(1, 2, "a", "b", "c"));
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.splice(-1)");
+trace(arr.splice(-1));
+
+trace("//arr");
+trace(arr);
+
+trace("//arr.indexOf(\"b\")");
+trace(arr.indexOf("b"));
+
+trace("//arr.indexOf(\"z\")");
+trace(arr.indexOf("z"));
+
+trace("//arr.indexOf(\"a\", 2)");
+trace(arr.indexOf("a", 2));
+
+trace("//arr.indexOf(3)");
+trace(arr.indexOf(3));
+
+trace("//arr.indexOf(\"3\")");
+trace(arr.indexOf("3"));
+
+trace("//arr.lastIndexOf(\"a\")");
+trace(arr.lastIndexOf("a"));
+
+trace("//arr.lastIndexOf(\"c\", -2)");
+trace(arr.lastIndexOf("c", -2));
+
+trace("//[1, [2, 3], null, undefined]");
+trace([1, [2, 3], null, undefined]);
+
+trace("//[] is Array");
+trace([] is Array);
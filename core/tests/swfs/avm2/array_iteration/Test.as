@@ -0,0 +1,44 @@
+﻿package {
+	public class Test {}
+}
+
+var arr = [1, 2, 3, 4];
+trace("//arr.forEach(...)");
+arr.forEach(function (item, index, array) {
+	trace(index + ": " + item + " of " + array);
+});
+
+trace("//arr.map(function (x) { return x * 2; })");
+trace(arr.map(function (x) { return x * 2; }));
+
+trace("//arr.filter(function (x) { return x % 2 == 0; })");
+trace(arr.filter(function (x) { return x % 2 == 0; }));
+
+trace("//arr.every(function (x) { return x > 0; })");
+trace(arr.every(function (x) { return x > 0; }));
+
+trace("//arr.every(function (x) { return x > 1; })");
+trace(arr.every(function (x) { return x > 1; }));
+
+trace("//arr.some(function (x) { return x > 3; })");
+trace(arr.some(function (x) { return x > 3; }));
+
+trace("//arr.some(function (x) { return x > 4; })");
+trace(arr.some(function (x) { return x > 4; }));
+
+trace("//arr.every(function (x) { trace(x); return x < 2; })");
+trace(arr.every(function (x) { trace(x); return x < 2; }));
+
+trace("//arr.some(function (x) { trace(x); return x == 3; })");
+trace(arr.some(function (x) { trace(x); return x == 3; }));
+
+trace("//[1, 2].forEach(..., {tag: \"receiver\"})");
+[1, 2].forEach(function () {
+	trace(this.tag);
+}, {tag: "receiver"});
+
+trace("//arr.forEach(function (x) { trace(x); })");
+arr.forEach(function (x) { trace(x); });
+
+trace("//arr");
+trace(arr);
@@ -0,0 +1,96 @@
+﻿package {
+	public class Test {}
+}
+
+var arr;
+trace("//Array.CASEINSENSITIVE");
+trace(Array.CASEINSENSITIVE);
+
+trace("//Array.DESCENDING");
+trace(Array.DESCENDING);
+
+trace("//Array.UNIQUESORT");
+trace(Array.UNIQUESORT);
+
+trace("//Array.RETURNINDEXEDARRAY");
+trace(Array.RETURNINDEXEDARRAY);
+
+trace("//Array.NUMERIC");
+trace(Array.NUMERIC);
+
+trace("//[\"b\", \"C\", \"a\", \"D\"].sort()");
+trace(["b", "C", "a", "D"].sort());
+
+trace("//[\"b\", \"C\", \"a\", \"D\"].sort(Array.CASEINSENSITIVE)");
+trace(["b", "C", "a", "D"].sort(Array.CASEINSENSITIVE));
+
+trace("//[\"b\", \"C\", \"a\", \"D\"].sort(Array.CASEINSENSITIVE | Array.DESCENDING)");
+trace(["b", "C", "a", "D"].sort(Array.CASEINSENSITIVE | Array.DESCENDING));
+
+trace("//[\"b\", \"C\", \"a\", \"D\"].sort(Array.DESCENDING)");
+trace(["b", "C", "a", "D"].sort(Array.DESCENDING));
+
+trace("//[10, 9, 100, 1].sort()");
+trace([10, 9, 100, 1].sort());
+
+trace("//[10, 9, 100, 1].sort(Array.NUMERIC)");
+trace([10, 9, 100, 1].sort(Array.NUMERIC));
+
+trace("//[10, 9, 100, 1].sort(Array.NUMERIC | Array.DESCENDING)");
+trace([10, 9, 100, 1].sort(Array.NUMERIC | Array.DESCENDING));
+
+trace("//[10, 9, 100, 1].sort(Array.NUMERIC | Array.RETURNINDEXEDARRAY)");
+arr = [10, 9, 100, 1];
+trace(arr.sort(Array.NUMERIC | Array.RETURNINDEXEDARRAY));
+
+trace("//arr");
+trace(arr);
+
+trace("//[1, 2, 1].sort(Array.UNIQUESORT)");
+arr = [1, 2, 1];
+trace(arr.sort(Array.UNIQUESORT));
+
+trace("//arr");
+trace(arr);
+
+trace("//[3, 1, 2].sort(Array.UNIQUESORT)");
+trace([3, 1, 2].sort(Array.UNIQUESORT));
+
+trace("//[undefined, \"b\", \"a\"].sort()");
+trace([undefined, "b", "a"].sort());
+
+trace("//[1, 3, 2].sort(function (a, b) { return b - a; })");
+trace([1, 3, 2].sort(function (a, b) { return b - a; }));
+
+trace("//[1, 3, 2].sort(function (a, b) { return a - b; }, Array.DESCENDING)");
+trace([1, 3, 2].sort(function (a, b) { return a - b; }, Array.DESCENDING));
+
+arr = [{name: "b", n: 2}, {name: "a", n: 10}, {name: "c", n: 1}];
+trace("//arr.sortOn(\"name\")");
+arr.sortOn("name");
+trace(arr[0].name + arr[1].name + arr[2].name);
+
+arr = [{name: "b", n: 2}, {name: "a", n: 10}, {name: "c", n: 1}];
+trace("//arr.sortOn(\"n\")");
+arr.sortOn("n");
+trace(arr[0].name + arr[1].name + arr[2].name);
+
+arr = [{name: "b", n: 2}, {name: "a", n: 10}, {name: "c", n: 1}];
+trace("//arr.sortOn(\"n\", Array.NUMERIC)");
+arr.sortOn("n", Array.NUMERIC);
+trace(arr[0].name + arr[1].name + arr[2].name);
+
+arr = [{name: "b", n: 2}, {name: "a", n: 10}, {name: "c", n: 1}];
+trace("//arr.sortOn(\"n\", Array.NUMERIC | Array.DESCENDING)");
+arr.sortOn("n", Array.NUMERIC | Array.DESCENDING);
+trace(arr[0].name + arr[1].name + arr[2].name);
+
+arr = [{name: "b", n: 2}, {name: "a", n: 10}, {name: "c", n: 1}];
+trace("//arr.sortOn([\"name\"], [Array.DESCENDING])");
+arr.sortOn(["name"], [Array.DESCENDING]);
+trace(arr[0].name + arr[1].name + arr[2].name);
+
+arr = [{name: "b", n: 2}, {name: "a", n: 10}, {name: "c", n: 1}];
+trace("//arr.sortOn(\"n\", Array.NUMERIC | Array.RETURNINDEXEDARRAY)");
+trace(arr.sortOn("n", Array.NUMERIC | Array.RETURNINDEXEDARRAY));
+trace(arr[0].name + arr[1].name + arr[2].name);
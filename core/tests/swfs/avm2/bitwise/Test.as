@@ -0,0 +1,57 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//5 & 3");
+trace(5 & 3);
+
+trace("//5 | 3");
+trace(5 | 3);
+
+trace("//5 ^ 3");
+trace(5 ^ 3);
+
+trace("//~5");
+trace(~5);
+
+trace("//~-1");
+trace(~-1);
+
+trace("//\"12\" & 10");
+trace("12" & 10);
+
+trace("//4294967295 & 1");
+trace(4294967295 & 1);
+
+trace("//4294967296 | 5");
+trace(4294967296 | 5);
+
+trace("//1.9 | 0");
+trace(1.9 | 0);
+
+trace("//-1.9 | 0");
+trace(-1.9 | 0);
+
+trace("//NaN | 0");
+trace(NaN | 0);
+
+trace("//1 << 31");
+trace(1 << 31);
+
+trace("//1 << 32");
+trace(1 << 32);
+
+trace("//3 << -1");
+trace(3 << -1);
+
+trace("//-16 >> 2");
+trace(-16 >> 2);
+
+trace("//-16 >>> 2");
+trace(-16 >>> 2);
+
+trace("//-1 >>> 0");
+trace(-1 >>> 0);
+
+trace("//256 >> 33");
+trace(256 >> 33);
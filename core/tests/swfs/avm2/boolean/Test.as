@@ -0,0 +1,36 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//new Boolean()");
+trace(new Boolean());
+
+trace("//new Boolean(1)");
+trace(new Boolean(1));
+
+trace("//Boolean(\"\")");
+trace(Boolean(""));
+
+trace("//Boolean(\"false\")");
+trace(Boolean("false"));
+
+trace("//Boolean(NaN)");
+trace(Boolean(NaN));
+
+trace("//Boolean(null)");
+trace(Boolean(null));
+
+trace("//Boolean({})");
+trace(Boolean({}));
+
+trace("//true.toString()");
+trace(true.toString());
+
+trace("//false.valueOf()");
+trace(false.valueOf());
+
+trace("//new Boolean(true) === true");
+trace(new Boolean(true) === true);
+
+trace("//true is Boolean");
+trace(true is Boolean);
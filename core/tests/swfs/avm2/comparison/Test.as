@@ -0,0 +1,135 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//1 < 2");
+trace(1 < 2);
+
+trace("//1 <= 2");
+trace(1 <= 2);
+
+trace("//1 > 2");
+trace(1 > 2);
+
+trace("//1 >= 2");
+trace(1 >= 2);
+
+trace("//2 < 1");
+trace(2 < 1);
+
+trace("//2 <= 1");
+trace(2 <= 1);
+
+trace("//2 > 1");
+trace(2 > 1);
+
+trace("//2 >= 1");
+trace(2 >= 1);
+
+trace("//1 < 1");
+trace(1 < 1);
+
+trace("//1 <= 1");
+trace(1 <= 1);
+
+trace("//1 > 1");
+trace(1 > 1);
+
+trace("//1 >= 1");
+trace(1 >= 1);
+
+trace("//NaN < 1");
+trace(NaN < 1);
+
+trace("//NaN <= 1");
+trace(NaN <= 1);
+
+trace("//NaN > 1");
+trace(NaN > 1);
+
+trace("//NaN >= 1");
+trace(NaN >= 1);
+
+trace("//\"a\" < \"b\"");
+trace("a" < "b");
+
+trace("//\"a\" <= \"b\"");
+trace("a" <= "b");
+
+trace("//\"a\" > \"b\"");
+trace("a" > "b");
+
+trace("//\"a\" >= \"b\"");
+trace("a" >= "b");
+
+trace("//\"10\" < \"9\"");
+trace("10" < "9");
+
+trace("//\"10\" <= \"9\"");
+trace("10" <= "9");
+
+trace("//\"10\" > \"9\"");
+trace("10" > "9");
+
+trace("//\"10\" >= \"9\"");
+trace("10" >= "9");
+
+trace("//\"10\" < 9");
+trace("10" < 9);
+
+trace("//\"10\" <= 9");
+trace("10" <= 9);
+
+trace("//\"10\" > 9");
+trace("10" > 9);
+
+trace("//\"10\" >= 9");
+trace("10" >= 9);
+
+trace("//null < 0");
+trace(null < 0);
+
+trace("//null <= 0");
+trace(null <= 0);
+
+trace("//null > 0");
+trace(null > 0);
+
+trace("//null >= 0");
+trace(null >= 0);
+
+trace("//undefined < 0");
+trace(undefined < 0);
+
+trace("//undefined <= 0");
+trace(undefined <= 0);
+
+trace("//undefined > 0");
+trace(undefined > 0);
+
+trace("//undefined >= 0");
+trace(undefined >= 0);
+
+trace("//true < false");
+trace(true < false);
+
+trace("//true <= false");
+trace(true <= false);
+
+trace("//true > false");
+trace(true > false);
+
+trace("//true >= false");
+trace(true >= false);
+
+trace("//-1 < uint(1)");
+trace(-1 < uint(1));
+
+trace("//-1 <= uint(1)");
+trace(-1 <= uint(1));
+
+trace("//-1 > uint(1)");
+trace(-1 > uint(1));
+
+trace("//-1 >= uint(1)");
+trace(-1 >= uint(1));
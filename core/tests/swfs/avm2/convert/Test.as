@@ -0,0 +1,145 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//int(3.7)");
+trace(int(3.7));
+
+trace("//int(-3.7)");
+trace(int(-3.7));
+
+trace("//int(4294967301)");
+trace(int(4294967301));
+
+trace("//int(2147483648)");
+trace(int(2147483648));
+
+trace("//int(\"0x10\")");
+trace(int("0x10"));
+
+trace("//int(\"  42  \")");
+trace(int("  42  "));
+
+trace("//int(\"12abc\")");
+trace(int("12abc"));
+
+trace("//int(undefined)");
+trace(int(undefined));
+
+trace("//int(Infinity)");
+trace(int(Infinity));
+
+trace("//uint(-1)");
+trace(uint(-1));
+
+trace("//uint(3.9)");
+trace(uint(3.9));
+
+trace("//uint(true)");
+trace(uint(true));
+
+trace("//Number(\"\")");
+trace(Number(""));
+
+trace("//Number(\"abc\")");
+trace(Number("abc"));
+
+trace("//Number(\" 12.5 \")");
+trace(Number(" 12.5 "));
+
+trace("//Number(\".5\")");
+trace(Number(".5"));
+
+trace("//Number(\"1e3\")");
+trace(Number("1e3"));
+
+trace("//Number(\"-Infinity\")");
+trace(Number("-Infinity"));
+
+trace("//Number(\"infinity\")");
+trace(Number("infinity"));
+
+trace("//Number(null)");
+trace(Number(null));
+
+trace("//Number(undefined)");
+trace(Number(undefined));
+
+trace("//Number(true)");
+trace(Number(true));
+
+trace("//String(1e21)");
+trace(String(1e21));
+
+trace("//String(1e20)");
+trace(String(1e20));
+
+trace("//String(1e-7)");
+trace(String(1e-7));
+
+trace("//String(0.000001)");
+trace(String(0.000001));
+
+trace("//String(-123.456)");
+trace(String(-123.456));
+
+trace("//String(-0)");
+trace(String(-0));
+
+trace("//String(undefined)");
+trace(String(undefined));
+
+trace("//String(null)");
+trace(String(null));
+
+trace("//String({})");
+trace(String({}));
+
+trace("//Boolean(0)");
+trace(Boolean(0));
+
+trace("//Boolean(-1)");
+trace(Boolean(-1));
+
+trace("//Boolean(NaN)");
+trace(Boolean(NaN));
+
+trace("//Boolean(\"\")");
+trace(Boolean(""));
+
+trace("//Boolean(\"0\")");
+trace(Boolean("0"));
+
+trace("//Boolean(null)");
+trace(Boolean(null));
+
+trace("//Boolean({})");
+trace(Boolean({}));
+
+trace("//var s:String = undefined");
+var s1:String = undefined;
+trace(s1);
+
+trace("//var s:String = 5");
+var s2:String = 5;
+trace(s2);
+
+trace("//var i:int = \"7.5\"");
+var i1:int = "7.5";
+trace(i1);
+
+trace("//var u:uint = -2");
+var u1:uint = -2;
+trace(u1);
+
+trace("//var n:Number = \"2.5\"");
+var n1:Number = "2.5";
+trace(n1);
+
+trace("//var b:Boolean = \"false\"");
+var b1:Boolean = "false";
+trace(b1);
+
+trace("//var s:String = null");
+var s3:String = null;
+trace(s3);
@@ -0,0 +1,8 @@
+﻿package {
+	import flash.display.MovieClip;
+
+	public class Symbol1 extends MovieClip {
+		public function Symbol1() {
+		}
+	}
+}
@@ -0,0 +1,213 @@
+﻿package {
+	import flash.display.MovieClip;
+
+	// The stage holds an instance of `Symbol1` named "timeline_clip" at (10, 20).
+	// `Symbol1` has three frames, with frames 2 and 3 labelled "second" and "third".
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("//this.numChildren");
+			trace(this.numChildren);
+
+			trace("//this.totalFrames");
+			trace(this.totalFrames);
+
+			trace("//this.root == this");
+			trace(this.root == this);
+
+			trace("//this.parent");
+			trace(this.parent);
+
+			trace("//var c = this.getChildAt(0);");
+			var c = this.getChildAt(0);
+
+			trace("//c.name");
+			trace(c.name);
+
+			trace("//c.x");
+			trace(c.x);
+
+			trace("//c.y");
+			trace(c.y);
+
+			trace("//c is Symbol1");
+			trace(c is Symbol1);
+
+			trace("//c.parent == this");
+			trace(c.parent == this);
+
+			trace("//c.root == this");
+			trace(c.root == this);
+
+			trace("//this.getChildByName(\"timeline_clip\") == c");
+			trace(this.getChildByName("timeline_clip") == c);
+
+			trace("//c.totalFrames");
+			trace(c.totalFrames);
+
+			trace("//c.currentFrame");
+			trace(c.currentFrame);
+
+			trace("//c.gotoAndStop(2);");
+			c.gotoAndStop(2);
+
+			trace("//c.currentFrame");
+			trace(c.currentFrame);
+
+			trace("//c.gotoAndStop(\"third\");");
+			c.gotoAndStop("third");
+
+			trace("//c.currentFrame");
+			trace(c.currentFrame);
+
+			trace("//c.prevFrame();");
+			c.prevFrame();
+
+			trace("//c.currentFrame");
+			trace(c.currentFrame);
+
+			trace("//c.gotoAndPlay(1);");
+			c.gotoAndPlay(1);
+
+			trace("//c.currentFrame");
+			trace(c.currentFrame);
+
+			trace("//c.nextFrame();");
+			c.nextFrame();
+
+			trace("//c.currentFrame");
+			trace(c.currentFrame);
+
+			trace("//var s = new Symbol1();");
+			var s = new Symbol1();
+
+			trace("//s.totalFrames");
+			trace(s.totalFrames);
+
+			trace("//s.parent");
+			trace(s.parent);
+
+			trace("//this.addChild(s) == s");
+			trace(this.addChild(s) == s);
+
+			trace("//this.numChildren");
+			trace(this.numChildren);
+
+			trace("//s.parent == this");
+			trace(s.parent == this);
+
+			trace("//this.getChildIndex(s)");
+			trace(this.getChildIndex(s));
+
+			trace("//this.setChildIndex(s, 0);");
+			this.setChildIndex(s, 0);
+
+			trace("//this.getChildIndex(s)");
+			trace(this.getChildIndex(s));
+
+			trace("//this.getChildIndex(c)");
+			trace(this.getChildIndex(c));
+
+			trace("//this.getChildAt(0) == s");
+			trace(this.getChildAt(0) == s);
+
+			trace("//this.contains(s)");
+			trace(this.contains(s));
+
+			trace("//s.x = 15; s.y = -7.5;");
+			s.x = 15; s.y = -7.5;
+
+			trace("//s.x");
+			trace(s.x);
+
+			trace("//s.y");
+			trace(s.y);
+
+			trace("//s.scaleX = 2; s.scaleY = 0.5;");
+			s.scaleX = 2; s.scaleY = 0.5;
+
+			trace("//s.scaleX");
+			trace(s.scaleX);
+
+			trace("//s.scaleY");
+			trace(s.scaleY);
+
+			trace("//s.rotation = 270;");
+			s.rotation = 270;
+
+			trace("//s.rotation");
+			trace(s.rotation);
+
+			trace("//s.alpha = 0.5;");
+			s.alpha = 0.5;
+
+			trace("//s.alpha");
+			trace(s.alpha);
+
+			trace("//s.visible = false;");
+			s.visible = false;
+
+			trace("//s.visible");
+			trace(s.visible);
+
+			trace("//s.name = \"dynamic\";");
+			s.name = "dynamic";
+
+			trace("//s.name");
+			trace(s.name);
+
+			trace("//this.getChildByName(\"dynamic\") == s");
+			trace(this.getChildByName("dynamic") == s);
+
+			trace("//this.removeChild(s) == s");
+			trace(this.removeChild(s) == s);
+
+			trace("//this.numChildren");
+			trace(this.numChildren);
+
+			trace("//s.parent");
+			trace(s.parent);
+
+			trace("//this.contains(s)");
+			trace(this.contains(s));
+
+			trace("//this.getChildIndex(c)");
+			trace(this.getChildIndex(c));
+
+			trace("//var m = new MovieClip();");
+			var m = new MovieClip();
+
+			trace("//m.numChildren");
+			trace(m.numChildren);
+
+			trace("//m.addChild(s);");
+			m.addChild(s);
+
+			trace("//m.numChildren");
+			trace(m.numChildren);
+
+			trace("//s.parent == m");
+			trace(s.parent == m);
+
+			trace("//this.addChildAt(m, 0);");
+			this.addChildAt(m, 0);
+
+			trace("//this.getChildIndex(m)");
+			trace(this.getChildIndex(m));
+
+			trace("//this.getChildIndex(c)");
+			trace(this.getChildIndex(c));
+
+			trace("//s.root == this");
+			trace(s.root == this);
+
+			trace("//this.contains(s)");
+			trace(this.contains(s));
+
+			trace("//this.removeChildAt(0) == m");
+			trace(this.removeChildAt(0) == m);
+
+			trace("//this.numChildren");
+			trace(this.numChildren);
+		}
+	}
+}
@@ -0,0 +1,63 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//2 == \"2\"");
+trace(2 == "2");
+
+trace("//2 == 2.0");
+trace(2 == 2.0);
+
+trace("//uint(4294967295) == -1");
+trace(uint(4294967295) == -1);
+
+trace("//1 == true");
+trace(1 == true);
+
+trace("//2 == true");
+trace(2 == true);
+
+trace("//0 == false");
+trace(0 == false);
+
+trace("//\"\" == 0");
+trace("" == 0);
+
+trace("//\"1\" == true");
+trace("1" == true);
+
+trace("//\"true\" == true");
+trace("true" == true);
+
+trace("//\"abc\" == \"abc\"");
+trace("abc" == "abc");
+
+trace("//\"abc\" == \"ABC\"");
+trace("abc" == "ABC");
+
+trace("//null == undefined");
+trace(null == undefined);
+
+trace("//null == null");
+trace(null == null);
+
+trace("//null == 0");
+trace(null == 0);
+
+trace("//undefined == 0");
+trace(undefined == 0);
+
+trace("//null == false");
+trace(null == false);
+
+trace("//NaN == NaN");
+trace(NaN == NaN);
+
+trace("//{} == \"[object Object]\"");
+trace({} == "[object Object]");
+
+trace("//{} == {}");
+trace({} == {});
+
+trace("//!(1 == 2)");
+trace(!(1 == 2));
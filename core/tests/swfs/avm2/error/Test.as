@@ -0,0 +1,102 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//new Error()");
+var e = new Error();
+trace(e);
+trace(e.name);
+trace("[" + e.message + "]");
+trace(e.errorID);
+trace(e.getStackTrace());
+
+trace("//new Error(\"message\", 1234)");
+e = new Error("message", 1234);
+trace(e);
+trace(e.message);
+trace(e.errorID);
+
+trace("//new ArgumentError(\"oops\")");
+e = new ArgumentError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new DefinitionError(\"oops\")");
+e = new DefinitionError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new EvalError(\"oops\")");
+e = new EvalError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new RangeError(\"oops\")");
+e = new RangeError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new ReferenceError(\"oops\")");
+e = new ReferenceError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new SecurityError(\"oops\")");
+e = new SecurityError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new SyntaxError(\"oops\")");
+e = new SyntaxError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new TypeError(\"oops\")");
+e = new TypeError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new URIError(\"oops\")");
+e = new URIError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//new VerifyError(\"oops\")");
+e = new VerifyError("oops");
+trace(e);
+trace(e instanceof Error);
+
+trace("//typed handlers");
+try {
+	throw new TypeError("bad type", 1009);
+} catch (e:RangeError) {
+	trace("RangeError handler");
+} catch (e:TypeError) {
+	trace("TypeError handler");
+	trace(e);
+	trace(e.errorID);
+} catch (e:Error) {
+	trace("Error handler");
+}
+
+trace("//superclass handler");
+try {
+	throw new ReferenceError("bad reference");
+} catch (e:TypeError) {
+	trace("TypeError handler");
+	trace(e);
+} catch (e:Error) {
+	trace("Error handler");
+	trace(e);
+}
+
+trace("//primitive is not an Error");
+try {
+	throw "string";
+} catch (e:Error) {
+	trace("Error handler");
+	trace(e);
+} catch (e:String) {
+	trace("String handler");
+	trace(e);
+}
@@ -0,0 +1,250 @@
+﻿package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.EventDispatcher;
+	import flash.events.KeyboardEvent;
+	import flash.events.MouseEvent;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			trace("//var e = new Event(\"custom\", true, true);");
+			var e = new Event("custom", true, true);
+			trace("//e.type");
+			trace(e.type);
+			trace("//e.bubbles");
+			trace(e.bubbles);
+			trace("//e.cancelable");
+			trace(e.cancelable);
+			trace("//e.eventPhase");
+			trace(e.eventPhase);
+			trace("//e.target");
+			trace(e.target);
+			trace("//e.currentTarget");
+			trace(e.currentTarget);
+			trace("//e.toString()");
+			trace(e.toString());
+			trace("//e.isDefaultPrevented()");
+			trace(e.isDefaultPrevented());
+			trace("//e.preventDefault();");
+			e.preventDefault();
+			trace("//e.isDefaultPrevented()");
+			trace(e.isDefaultPrevented());
+			trace("//e.clone().toString()");
+			trace(e.clone().toString());
+			trace("//e.clone() == e");
+			trace(e.clone() == e);
+			trace("//e.formatToString(\"Custom\", \"type\", \"bubbles\")");
+			trace(e.formatToString("Custom", "type", "bubbles"));
+
+			trace("//var e2 = new Event(\"plain\");");
+			var e2 = new Event("plain");
+			trace("//e2.bubbles");
+			trace(e2.bubbles);
+			trace("//e2.cancelable");
+			trace(e2.cancelable);
+			trace("//e2.preventDefault();");
+			e2.preventDefault();
+			trace("//e2.isDefaultPrevented()");
+			trace(e2.isDefaultPrevented());
+			trace("//Event.ENTER_FRAME");
+			trace(Event.ENTER_FRAME);
+			trace("//Event.COMPLETE");
+			trace(Event.COMPLETE);
+
+			trace("//var d = new EventDispatcher();");
+			var d = new EventDispatcher();
+			trace("//d.hasEventListener(\"custom\")");
+			trace(d.hasEventListener("custom"));
+			var f1 = function (e) { trace("f1 (priority 0) phase=" + e.eventPhase); };
+			var f2 = function (e) { trace("f2 (priority 10) phase=" + e.eventPhase); };
+			var f3 = function (e) { trace("f3 (priority -5) phase=" + e.eventPhase); };
+			trace("//d.addEventListener(\"custom\", f1); f2 at 10; f3 at -5; f2 again at 10");
+			d.addEventListener("custom", f1);
+			d.addEventListener("custom", f2, false, 10);
+			d.addEventListener("custom", f3, false, -5);
+			d.addEventListener("custom", f2, false, 10);
+			trace("//d.hasEventListener(\"custom\")");
+			trace(d.hasEventListener("custom"));
+			trace("//d.willTrigger(\"custom\")");
+			trace(d.willTrigger("custom"));
+			trace("//d.hasEventListener(\"other\")");
+			trace(d.hasEventListener("other"));
+			trace("//d.dispatchEvent(new Event(\"custom\"))");
+			trace(d.dispatchEvent(new Event("custom", false, false)));
+			trace("//d.removeEventListener(\"custom\", f1);");
+			d.removeEventListener("custom", f1);
+			trace("//d.dispatchEvent(new Event(\"custom\"))");
+			trace(d.dispatchEvent(new Event("custom", false, false)));
+			trace("//d.removeEventListener(\"custom\", f2); d.removeEventListener(\"custom\", f3);");
+			d.removeEventListener("custom", f2);
+			d.removeEventListener("custom", f3);
+			trace("//d.hasEventListener(\"custom\")");
+			trace(d.hasEventListener("custom"));
+
+			d.addEventListener("target", function (e) {
+				trace("target listener");
+				trace("//e.target == e.currentTarget");
+				trace(e.target == e.currentTarget);
+				trace("//e.eventPhase");
+				trace(e.eventPhase);
+			});
+			trace("//var t = new Event(\"target\");");
+			var t = new Event("target", false, false);
+			trace("//d.dispatchEvent(t)");
+			trace(d.dispatchEvent(t));
+			trace("//t.target == d");
+			trace(t.target == d);
+			trace("//t.currentTarget");
+			trace(t.currentTarget);
+			trace("//t.eventPhase");
+			trace(t.eventPhase);
+			trace("//d.dispatchEvent(t)");
+			trace(d.dispatchEvent(t));
+
+			d.addEventListener("cancel", function (e) {
+				trace("cancelling phase=" + e.eventPhase);
+				e.preventDefault();
+			});
+			trace("//d.dispatchEvent(new Event(\"cancel\", false, true))");
+			trace(d.dispatchEvent(new Event("cancel", false, true)));
+			trace("//d.dispatchEvent(new Event(\"cancel\", false, false))");
+			trace(d.dispatchEvent(new Event("cancel", false, false)));
+
+			d.addEventListener("immediate", function (e) {
+				trace("stopping immediately (priority 1) phase=" + e.eventPhase);
+				e.stopImmediatePropagation();
+			}, false, 1);
+			d.addEventListener("immediate", function (e) {
+				trace("not reached (priority 0) phase=" + e.eventPhase);
+			});
+			trace("//d.dispatchEvent(new Event(\"immediate\"))");
+			trace(d.dispatchEvent(new Event("immediate", false, false)));
+
+			trace("//var p = new Sprite(); var c = new Sprite(); this.addChild(p); p.addChild(c);");
+			var p = new Sprite();
+			var c = new Sprite();
+			p.name = "p";
+			c.name = "c";
+			this.addChild(p);
+			p.addChild(c);
+
+			this.addEventListener("flow", function (e) { trace("root capture phase=" + e.eventPhase); }, true);
+			this.addEventListener("flow", function (e) { trace("root bubble phase=" + e.eventPhase); });
+			p.addEventListener("flow", function (e) { trace("p capture phase=" + e.eventPhase + " current=" + e.currentTarget.name); }, true);
+			p.addEventListener("flow", function (e) { trace("p bubble phase=" + e.eventPhase + " current=" + e.currentTarget.name); });
+			c.addEventListener("flow", function (e) { trace("c capture (not called at target) phase=" + e.eventPhase + " current=" + e.currentTarget.name); }, true);
+			c.addEventListener("flow", function (e) { trace("c phase=" + e.eventPhase + " current=" + e.currentTarget.name); });
+
+			trace("//c.dispatchEvent(new Event(\"flow\", true))");
+			trace(c.dispatchEvent(new Event("flow", true, false)));
+			trace("//c.dispatchEvent(new Event(\"flow\", false))");
+			trace(c.dispatchEvent(new Event("flow", false, false)));
+			trace("//p.dispatchEvent(new Event(\"flow\", true))");
+			trace(p.dispatchEvent(new Event("flow", true, false)));
+
+			this.addEventListener("stop", function (e) { trace("root bubble (not reached) phase=" + e.eventPhase); });
+			p.addEventListener("stop", function (e) {
+				trace("p bubble, stopping phase=" + e.eventPhase + " current=" + e.currentTarget.name);
+				e.stopPropagation();
+			});
+			p.addEventListener("stop", function (e) { trace("p bubble, still called phase=" + e.eventPhase + " current=" + e.currentTarget.name); }, false, -1);
+			trace("//c.dispatchEvent(new Event(\"stop\", true))");
+			trace(c.dispatchEvent(new Event("stop", true, false)));
+
+			trace("//var s = new Sprite(); p.addChild(s);");
+			var s = new Sprite();
+			p.addChild(s);
+			trace("//s.hasEventListener(\"flow\")");
+			trace(s.hasEventListener("flow"));
+			trace("//s.willTrigger(\"flow\")");
+			trace(s.willTrigger("flow"));
+			trace("//s.willTrigger(\"nothing\")");
+			trace(s.willTrigger("nothing"));
+
+			trace("//var m = new MouseEvent(\"click\");");
+			var m = new MouseEvent("click");
+			trace("//m.bubbles");
+			trace(m.bubbles);
+			trace("//m.localX");
+			trace(m.localX);
+			trace("//m.buttonDown");
+			trace(m.buttonDown);
+			trace("//m is Event");
+			trace(m is Event);
+			trace("//m = new MouseEvent(\"mouseDown\", true, false, 10, 20, null, true, false, true, true, 3);");
+			m = new MouseEvent("mouseDown", true, false, 10, 20, null, true, false, true, true, 3);
+			trace("//m.localX");
+			trace(m.localX);
+			trace("//m.localY");
+			trace(m.localY);
+			trace("//m.stageX");
+			trace(m.stageX);
+			trace("//m.ctrlKey");
+			trace(m.ctrlKey);
+			trace("//m.altKey");
+			trace(m.altKey);
+			trace("//m.shiftKey");
+			trace(m.shiftKey);
+			trace("//m.buttonDown");
+			trace(m.buttonDown);
+			trace("//m.delta");
+			trace(m.delta);
+			trace("//m.relatedObject");
+			trace(m.relatedObject);
+			trace("//m.toString()");
+			trace(m.toString());
+			trace("//m.localX = 2.5;");
+			m.localX = 2.5;
+			trace("//m.localX");
+			trace(m.localX);
+			trace("//m.clone().localY");
+			trace(m.clone().localY);
+			trace("//MouseEvent.CLICK");
+			trace(MouseEvent.CLICK);
+			c.addEventListener("mouseDown", function (e) {
+				trace("//e.stageX");
+				trace(e.stageX);
+				trace("//e.stageY");
+				trace(e.stageY);
+			});
+			trace("//c.x = 100; c.y = 50; c.dispatchEvent(m);");
+			c.x = 100;
+			c.y = 50;
+			c.dispatchEvent(m);
+
+			trace("//var k = new KeyboardEvent(\"keyDown\", true, false, 97, 65, 0, true);");
+			var k = new KeyboardEvent("keyDown", true, false, 97, 65, 0, true);
+			trace("//k.charCode");
+			trace(k.charCode);
+			trace("//k.keyCode");
+			trace(k.keyCode);
+			trace("//k.keyLocation");
+			trace(k.keyLocation);
+			trace("//k.ctrlKey");
+			trace(k.ctrlKey);
+			trace("//k.shiftKey");
+			trace(k.shiftKey);
+			trace("//k.toString()");
+			trace(k.toString());
+			trace("//KeyboardEvent.KEY_UP");
+			trace(KeyboardEvent.KEY_UP);
+
+			// `enterFrame` is broadcast every frame; the listener removes itself
+			// after three frames.
+			trace("//this.frames = 0; this.addEventListener(Event.ENTER_FRAME, onFrame);");
+			this.frames = 0;
+			this.onFrame = function (e) {
+				e.currentTarget.frames++;
+				trace("enterFrame " + e.currentTarget.frames + " phase=" + e.eventPhase);
+				if (e.currentTarget.frames == 3) {
+					trace("removing enterFrame listener");
+					e.currentTarget.removeEventListener("enterFrame", e.currentTarget.onFrame);
+				}
+			};
+			this.addEventListener("enterFrame", this.onFrame);
+			trace("//this.hasEventListener(\"enterFrame\")");
+			trace(this.hasEventListener("enterFrame"));
+		}
+	}
+}
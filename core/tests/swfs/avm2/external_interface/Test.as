@@ -0,0 +1,64 @@
+﻿package {
+	public class Test {}
+}
+
+import flash.external.ExternalInterface;
+import flash.system.fscommand;
+
+trace("//ExternalInterface.available");
+trace(ExternalInterface.available);
+
+trace("//var result = ExternalInterface.call(\"echo\", 1.5, \"two\", 7, null, [1, \"a\"], {x: 5});");
+var result = ExternalInterface.call("echo", 1.5, "two", 7, null, [1, "a"], {x: 5});
+
+trace("//result.length");
+trace(result.length);
+
+trace("//result[0]");
+trace(result[0]);
+
+trace("//result[1]");
+trace(result[1]);
+
+trace("//result[2]");
+trace(result[2]);
+
+trace("//result[3]");
+trace(result[3]);
+
+trace("//result[4][1]");
+trace(result[4][1]);
+
+trace("//result[5].x");
+trace(result[5].x);
+
+trace("//var cyclic = {name: \"cyclic\"}; cyclic.self = cyclic;");
+var cyclic = {name: "cyclic"};
+cyclic.self = cyclic;
+
+trace("//var shared = [1];");
+var shared = [1];
+
+trace("//result = ExternalInterface.call(\"echo\", cyclic, [shared, shared]);");
+result = ExternalInterface.call("echo", cyclic, [shared, shared]);
+
+trace("//result[0].name");
+trace(result[0].name);
+
+trace("//result[0].self");
+trace(result[0].self);
+
+trace("//result[1][1][0]");
+trace(result[1][1][0]);
+
+trace("//ExternalInterface.call(\"unknown\")");
+trace(ExternalInterface.call("unknown"));
+
+trace("//ExternalInterface.addCallback(\"add\", function (a, b) { trace(\"add called\"); return a + b; });");
+ExternalInterface.addCallback("add", function (a, b) {
+	trace("add called");
+	return a + b;
+});
+
+trace("//fscommand(\"handled\", \"some args\");");
+fscommand("handled", "some args");
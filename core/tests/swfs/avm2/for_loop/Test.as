@@ -0,0 +1,17 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//for (var i:int = 0; i < 5; i++)");
+for (var i:int = 0; i < 5; i++) {
+	trace(i);
+}
+
+trace("//while (j) { sum += j; j--; }");
+var sum:Number = 0;
+var j:Number = 10;
+while (j) {
+	sum += j;
+	j--;
+}
+trace(sum);
@@ -0,0 +1,114 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//isNaN(NaN)");
+trace(isNaN(NaN));
+
+trace("//isNaN(\"abc\")");
+trace(isNaN("abc"));
+
+trace("//isNaN(\"12\")");
+trace(isNaN("12"));
+
+trace("//isNaN(undefined)");
+trace(isNaN(undefined));
+
+trace("//isNaN(null)");
+trace(isNaN(null));
+
+trace("//isFinite(1)");
+trace(isFinite(1));
+
+trace("//isFinite(Infinity)");
+trace(isFinite(Infinity));
+
+trace("//isFinite(\"x\")");
+trace(isFinite("x"));
+
+trace("//parseInt(\"42px\")");
+trace(parseInt("42px"));
+
+trace("//parseInt(\"  -17\")");
+trace(parseInt("  -17"));
+
+trace("//parseInt(\"0x1F\")");
+trace(parseInt("0x1F"));
+
+trace("//parseInt(\"ff\", 16)");
+trace(parseInt("ff", 16));
+
+trace("//parseInt(\"101\", 2)");
+trace(parseInt("101", 2));
+
+trace("//parseInt(\"z\", 36)");
+trace(parseInt("z", 36));
+
+trace("//parseInt(\"abc\")");
+trace(parseInt("abc"));
+
+trace("//parseInt(\"12\", 1)");
+trace(parseInt("12", 1));
+
+trace("//parseInt(\"9\", 8)");
+trace(parseInt("9", 8));
+
+trace("//parseInt(\"3.9\")");
+trace(parseInt("3.9"));
+
+trace("//parseInt(\"\")");
+trace(parseInt(""));
+
+trace("//parseFloat(\"3.14abc\")");
+trace(parseFloat("3.14abc"));
+
+trace("//parseFloat(\"  -.5\")");
+trace(parseFloat("  -.5"));
+
+trace("//parseFloat(\"1e3\")");
+trace(parseFloat("1e3"));
+
+trace("//parseFloat(\"1e\")");
+trace(parseFloat("1e"));
+
+trace("//parseFloat(\"2.5E-1x\")");
+trace(parseFloat("2.5E-1x"));
+
+trace("//parseFloat(\"Infinityx\")");
+trace(parseFloat("Infinityx"));
+
+trace("//parseFloat(\"abc\")");
+trace(parseFloat("abc"));
+
+trace("//parseFloat(\".\")");
+trace(parseFloat("."));
+
+trace("//escape(\"Hello World!\")");
+trace(escape("Hello World!"));
+
+trace("//escape(\"a+b@c.d/e_f-g*h\")");
+trace(escape("a+b@c.d/e_f-g*h"));
+
+trace("//escape(\"\\u00e9\\u20ac\")");
+trace(escape("\u00e9\u20ac"));
+
+trace("//unescape(\"Hello%20World%21\")");
+trace(unescape("Hello%20World%21"));
+
+trace("//unescape(\"%u20AC\")");
+trace(unescape("%u20AC"));
+
+trace("//unescape(\"%zz%4\")");
+trace(unescape("%zz%4"));
+
+trace("//Infinity");
+trace(Infinity);
+
+trace("//-Infinity");
+trace(-Infinity);
+
+trace("//trace(1, \"two\", true)");
+trace(1, "two", true);
+
+trace("//trace()");
+trace();
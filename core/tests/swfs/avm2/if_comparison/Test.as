@@ -0,0 +1,246 @@
+﻿package {
+	public class Test {}
+}
+
+// This movie was assembled by hand so that each case jumps with the named
+// conditional branch instruction. The equivalent comparisons are:
+
+if (1 == 2) {
+	trace("ifeq 1, 2: taken");
+} else {
+	trace("ifeq 1, 2: not taken");
+}
+
+if (2 == 1) {
+	trace("ifeq 2, 1: taken");
+} else {
+	trace("ifeq 2, 1: not taken");
+}
+
+if (2 == 2) {
+	trace("ifeq 2, 2: taken");
+} else {
+	trace("ifeq 2, 2: not taken");
+}
+
+if (NaN == 1) {
+	trace("ifeq NaN, 1: taken");
+} else {
+	trace("ifeq NaN, 1: not taken");
+}
+
+if (1 != 2) {
+	trace("ifne 1, 2: taken");
+} else {
+	trace("ifne 1, 2: not taken");
+}
+
+if (2 != 1) {
+	trace("ifne 2, 1: taken");
+} else {
+	trace("ifne 2, 1: not taken");
+}
+
+if (2 != 2) {
+	trace("ifne 2, 2: taken");
+} else {
+	trace("ifne 2, 2: not taken");
+}
+
+if (NaN != 1) {
+	trace("ifne NaN, 1: taken");
+} else {
+	trace("ifne NaN, 1: not taken");
+}
+
+if (1 < 2) {
+	trace("iflt 1, 2: taken");
+} else {
+	trace("iflt 1, 2: not taken");
+}
+
+if (2 < 1) {
+	trace("iflt 2, 1: taken");
+} else {
+	trace("iflt 2, 1: not taken");
+}
+
+if (2 < 2) {
+	trace("iflt 2, 2: taken");
+} else {
+	trace("iflt 2, 2: not taken");
+}
+
+if (NaN < 1) {
+	trace("iflt NaN, 1: taken");
+} else {
+	trace("iflt NaN, 1: not taken");
+}
+
+if (1 <= 2) {
+	trace("ifle 1, 2: taken");
+} else {
+	trace("ifle 1, 2: not taken");
+}
+
+if (2 <= 1) {
+	trace("ifle 2, 1: taken");
+} else {
+	trace("ifle 2, 1: not taken");
+}
+
+if (2 <= 2) {
+	trace("ifle 2, 2: taken");
+} else {
+	trace("ifle 2, 2: not taken");
+}
+
+if (NaN <= 1) {
+	trace("ifle NaN, 1: taken");
+} else {
+	trace("ifle NaN, 1: not taken");
+}
+
+if (1 > 2) {
+	trace("ifgt 1, 2: taken");
+} else {
+	trace("ifgt 1, 2: not taken");
+}
+
+if (2 > 1) {
+	trace("ifgt 2, 1: taken");
+} else {
+	trace("ifgt 2, 1: not taken");
+}
+
+if (2 > 2) {
+	trace("ifgt 2, 2: taken");
+} else {
+	trace("ifgt 2, 2: not taken");
+}
+
+if (NaN > 1) {
+	trace("ifgt NaN, 1: taken");
+} else {
+	trace("ifgt NaN, 1: not taken");
+}
+
+if (1 >= 2) {
+	trace("ifge 1, 2: taken");
+} else {
+	trace("ifge 1, 2: not taken");
+}
+
+if (2 >= 1) {
+	trace("ifge 2, 1: taken");
+} else {
+	trace("ifge 2, 1: not taken");
+}
+
+if (2 >= 2) {
+	trace("ifge 2, 2: taken");
+} else {
+	trace("ifge 2, 2: not taken");
+}
+
+if (NaN >= 1) {
+	trace("ifge NaN, 1: taken");
+} else {
+	trace("ifge NaN, 1: not taken");
+}
+
+if (!(1 < 2)) {
+	trace("ifnlt 1, 2: taken");
+} else {
+	trace("ifnlt 1, 2: not taken");
+}
+
+if (!(2 < 1)) {
+	trace("ifnlt 2, 1: taken");
+} else {
+	trace("ifnlt 2, 1: not taken");
+}
+
+if (!(2 < 2)) {
+	trace("ifnlt 2, 2: taken");
+} else {
+	trace("ifnlt 2, 2: not taken");
+}
+
+if (!(NaN < 1)) {
+	trace("ifnlt NaN, 1: taken");
+} else {
+	trace("ifnlt NaN, 1: not taken");
+}
+
+if (!(1 <= 2)) {
+	trace("ifnle 1, 2: taken");
+} else {
+	trace("ifnle 1, 2: not taken");
+}
+
+if (!(2 <= 1)) {
+	trace("ifnle 2, 1: taken");
+} else {
+	trace("ifnle 2, 1: not taken");
+}
+
+if (!(2 <= 2)) {
+	trace("ifnle 2, 2: taken");
+} else {
+	trace("ifnle 2, 2: not taken");
+}
+
+if (!(NaN <= 1)) {
+	trace("ifnle NaN, 1: taken");
+} else {
+	trace("ifnle NaN, 1: not taken");
+}
+
+if (!(1 > 2)) {
+	trace("ifngt 1, 2: taken");
+} else {
+	trace("ifngt 1, 2: not taken");
+}
+
+if (!(2 > 1)) {
+	trace("ifngt 2, 1: taken");
+} else {
+	trace("ifngt 2, 1: not taken");
+}
+
+if (!(2 > 2)) {
+	trace("ifngt 2, 2: taken");
+} else {
+	trace("ifngt 2, 2: not taken");
+}
+
+if (!(NaN > 1)) {
+	trace("ifngt NaN, 1: taken");
+} else {
+	trace("ifngt NaN, 1: not taken");
+}
+
+if (!(1 >= 2)) {
+	trace("ifnge 1, 2: taken");
+} else {
+	trace("ifnge 1, 2: not taken");
+}
+
+if (!(2 >= 1)) {
+	trace("ifnge 2, 1: taken");
+} else {
+	trace("ifnge 2, 1: not taken");
+}
+
+if (!(2 >= 2)) {
+	trace("ifnge 2, 2: taken");
+} else {
+	trace("ifnge 2, 2: not taken");
+}
+
+if (!(NaN >= 1)) {
+	trace("ifnge NaN, 1: taken");
+} else {
+	trace("ifnge NaN, 1: not taken");
+}
@@ -0,0 +1,51 @@
+﻿package {
+	public class Test {}
+}
+
+// The integer arithmetic opcodes (add_i, subtract_i, multiply_i, negate_i,
+// increment_i, decrement_i, inclocal_i, declocal_i) are only emitted by some
+// compilers, so this movie was assembled by hand. Each opcode is equivalent
+// to performing the Number operation on int operands, then truncating the
+// result back into an int.
+
+trace("//int(2147483647) + int(1)");
+trace(int(int(2147483647) + int(1)));
+
+trace("//int(-2147483648) - int(1)");
+trace(int(int(-2147483648) - int(1)));
+
+trace("//int(65536) * int(65536)");
+trace(int(int(65536) * int(65536)));
+
+trace("//int(3.9) * int(2)");
+trace(int(int(3.9) * int(2)));
+
+trace("//int(\"12\") + int(\"30\")");
+trace(int(int("12") + int("30")));
+
+trace("//-int(-2147483648)");
+trace(int(-int(-2147483648)));
+
+trace("//-int(7.5)");
+trace(int(-int(7.5)));
+
+trace("//x = int(2147483647); ++x");
+var x1:int = 2147483647;
+trace(int(++x1));
+
+trace("//x = int(\"10\"); --x");
+var x2:int = int("10");
+trace(int(--x2));
+
+trace("//var i:int = 5; i++; i++; i++; i--;");
+var j:int = 5;
+j++;
+j++;
+j++;
+j--;
+trace(j);
+
+trace("//var i:int = -2147483648; i--;");
+var k:int = -2147483648;
+k--;
+trace(k);
@@ -0,0 +1,49 @@
+﻿package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("//var info = this.loaderInfo;");
+			var info = this.loaderInfo;
+
+			trace("//info.swfVersion");
+			trace(info.swfVersion);
+
+			trace("//info.frameRate");
+			trace(info.frameRate);
+
+			trace("//info.width");
+			trace(info.width);
+
+			trace("//info.height");
+			trace(info.height);
+
+			trace("//info.url == info.loaderURL");
+			trace(info.url == info.loaderURL);
+
+			trace("//var url = info.url;");
+			var url = info.url;
+
+			trace("//url.substr(url.lastIndexOf(\"/\") + 1)");
+			trace(url.substr(url.lastIndexOf("/") + 1));
+
+			trace("//info.parameters.foo");
+			trace(info.parameters.foo);
+
+			trace("//info === this.loaderInfo");
+			trace(info === this.loaderInfo);
+
+			trace("//info.custom = \"kept\";");
+			info.custom = "kept";
+
+			trace("//this.loaderInfo.custom");
+			trace(this.loaderInfo.custom);
+
+			trace("//info.bytesTotal");
+			trace(info.bytesTotal);
+
+			trace("//info.bytesLoaded == info.bytesTotal");
+			trace(info.bytesLoaded == info.bytesTotal);
+		}
+	}
+}
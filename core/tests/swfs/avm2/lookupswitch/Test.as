@@ -0,0 +1,27 @@
+﻿package {
+	public class Test {}
+}
+
+function test_switch(value:int) {
+	trace("//switch (" + value + ")");
+	switch (value) {
+		case 0:
+			trace("case 0");
+			break;
+		case 1:
+			trace("case 1");
+			break;
+		case 2:
+			trace("case 2");
+			break;
+		default:
+			trace("default");
+			break;
+	}
+}
+
+test_switch(0);
+test_switch(1);
+test_switch(2);
+test_switch(5);
+test_switch(-1);
@@ -0,0 +1,109 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//Math.E");
+trace(Math.E);
+
+trace("//Math.LN10");
+trace(Math.LN10);
+
+trace("//Math.LN2");
+trace(Math.LN2);
+
+trace("//Math.LOG10E");
+trace(Math.LOG10E);
+
+trace("//Math.LOG2E");
+trace(Math.LOG2E);
+
+trace("//Math.PI");
+trace(Math.PI);
+
+trace("//Math.SQRT1_2");
+trace(Math.SQRT1_2);
+
+trace("//Math.SQRT2");
+trace(Math.SQRT2);
+
+trace("//Math.abs(-5)");
+trace(Math.abs(-5));
+
+trace("//Math.ceil(1.2)");
+trace(Math.ceil(1.2));
+
+trace("//Math.floor(-1.2)");
+trace(Math.floor(-1.2));
+
+trace("//Math.round(2.5)");
+trace(Math.round(2.5));
+
+trace("//Math.round(-2.5)");
+trace(Math.round(-2.5));
+
+trace("//Math.round(1.4)");
+trace(Math.round(1.4));
+
+trace("//Math.max(1, 5, 3)");
+trace(Math.max(1, 5, 3));
+
+trace("//Math.min(1, 5, 3)");
+trace(Math.min(1, 5, 3));
+
+trace("//Math.max()");
+trace(Math.max());
+
+trace("//Math.min()");
+trace(Math.min());
+
+trace("//Math.max(1, NaN)");
+trace(Math.max(1, NaN));
+
+trace("//Math.pow(2, 10)");
+trace(Math.pow(2, 10));
+
+trace("//Math.pow(4, 0.5)");
+trace(Math.pow(4, 0.5));
+
+trace("//Math.sqrt(16)");
+trace(Math.sqrt(16));
+
+trace("//Math.sqrt(-1)");
+trace(Math.sqrt(-1));
+
+trace("//Math.sin(0)");
+trace(Math.sin(0));
+
+trace("//Math.cos(0)");
+trace(Math.cos(0));
+
+trace("//Math.tan(0)");
+trace(Math.tan(0));
+
+trace("//Math.asin(1)");
+trace(Math.asin(1));
+
+trace("//Math.acos(1)");
+trace(Math.acos(1));
+
+trace("//Math.atan(0)");
+trace(Math.atan(0));
+
+trace("//Math.atan2(1, 1)");
+trace(Math.atan2(1, 1));
+
+trace("//Math.exp(0)");
+trace(Math.exp(0));
+
+trace("//Math.log(1)");
+trace(Math.log(1));
+
+trace("//Math.abs()");
+trace(Math.abs());
+
+var r = Math.random();
+trace("//r >= 0");
+trace(r >= 0);
+
+trace("//r < 1");
+trace(r < 1);
@@ -0,0 +1,147 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//new Number(5)");
+trace(new Number(5));
+
+trace("//new Number()");
+trace(new Number());
+
+trace("//Number(\"12.5\")");
+trace(Number("12.5"));
+
+trace("//Number(\"abc\")");
+trace(Number("abc"));
+
+trace("//Number(true)");
+trace(Number(true));
+
+trace("//Number.MAX_VALUE > 1e308");
+trace(Number.MAX_VALUE > 1e308);
+
+trace("//Number.MIN_VALUE > 0");
+trace(Number.MIN_VALUE > 0);
+
+trace("//Number.MIN_VALUE / 2");
+trace(Number.MIN_VALUE / 2);
+
+trace("//Number.NaN");
+trace(Number.NaN);
+
+trace("//Number.POSITIVE_INFINITY");
+trace(Number.POSITIVE_INFINITY);
+
+trace("//Number.NEGATIVE_INFINITY");
+trace(Number.NEGATIVE_INFINITY);
+
+trace("//(10).toString()");
+trace((10).toString());
+
+trace("//(255).toString(16)");
+trace((255).toString(16));
+
+trace("//(255).toString(2)");
+trace((255).toString(2));
+
+trace("//(-255).toString(36)");
+trace((-255).toString(36));
+
+trace("//(0.5).toString(2)");
+trace((0.5).toString(2));
+
+trace("//(0.1).toString(10)");
+trace((0.1).toString(10));
+
+trace("//(3.14159).toFixed(2)");
+trace((3.14159).toFixed(2));
+
+trace("//(2.5).toFixed()");
+trace((2.5).toFixed());
+
+trace("//(1.005).toFixed(2)");
+trace((1.005).toFixed(2));
+
+trace("//(0.125).toFixed(2)");
+trace((0.125).toFixed(2));
+
+trace("//(-1.5).toFixed(0)");
+trace((-1.5).toFixed(0));
+
+trace("//(9.5).toFixed(0)");
+trace((9.5).toFixed(0));
+
+trace("//(123.456).toFixed(10)");
+trace((123.456).toFixed(10));
+
+trace("//(0).toFixed(2)");
+trace((0).toFixed(2));
+
+trace("//(1e21).toFixed(2)");
+trace((1e21).toFixed(2));
+
+trace("//(123456).toExponential(2)");
+trace((123456).toExponential(2));
+
+trace("//(0.00015).toExponential()");
+trace((0.00015).toExponential());
+
+trace("//(0).toExponential()");
+trace((0).toExponential());
+
+trace("//(123.456).toPrecision(4)");
+trace((123.456).toPrecision(4));
+
+trace("//(0.000123).toPrecision(2)");
+trace((0.000123).toPrecision(2));
+
+trace("//(123456).toPrecision(2)");
+trace((123456).toPrecision(2));
+
+trace("//(0.0000001234).toPrecision(2)");
+trace((0.0000001234).toPrecision(2));
+
+trace("//(99.99).toPrecision(3)");
+trace((99.99).toPrecision(3));
+
+trace("//(42).valueOf()");
+trace((42).valueOf());
+
+trace("//int.MAX_VALUE");
+trace(int.MAX_VALUE);
+
+trace("//int.MIN_VALUE");
+trace(int.MIN_VALUE);
+
+trace("//uint.MAX_VALUE");
+trace(uint.MAX_VALUE);
+
+trace("//uint.MIN_VALUE");
+trace(uint.MIN_VALUE);
+
+trace("//int(3.7)");
+trace(int(3.7));
+
+trace("//int(\"-12.9\")");
+trace(int("-12.9"));
+
+trace("//int(2147483648)");
+trace(int(2147483648));
+
+trace("//uint(-1)");
+trace(uint(-1));
+
+trace("//new int(5)");
+trace(new int(5));
+
+trace("//new uint()");
+trace(new uint());
+
+trace("//int(255).toString(16)");
+trace(int(255).toString(16));
+
+trace("//int(7).toFixed(2)");
+trace(int(7).toFixed(2));
+
+trace("//uint(4294967295).toString(16)");
+trace(uint(4294967295).toString(16));
@@ -0,0 +1,169 @@
+﻿package {
+	public class Test {}
+}
+
+var s = "Hello, World";
+trace("//s.length");
+trace(s.length);
+
+trace("//s.charAt(4)");
+trace(s.charAt(4));
+
+trace("//s.charAt(20)");
+trace(s.charAt(20));
+
+trace("//s.charAt()");
+trace(s.charAt());
+
+trace("//s.charCodeAt(0)");
+trace(s.charCodeAt(0));
+
+trace("//s.charCodeAt(20)");
+trace(s.charCodeAt(20));
+
+trace("//s.concat(\"!\", 1)");
+trace(s.concat("!", 1));
+
+trace("//s.indexOf(\"o\")");
+trace(s.indexOf("o"));
+
+trace("//s.indexOf(\"o\", 5)");
+trace(s.indexOf("o", 5));
+
+trace("//s.indexOf(\"z\")");
+trace(s.indexOf("z"));
+
+trace("//s.indexOf(\"\")");
+trace(s.indexOf(""));
+
+trace("//s.lastIndexOf(\"o\")");
+trace(s.lastIndexOf("o"));
+
+trace("//s.lastIndexOf(\"o\", 7)");
+trace(s.lastIndexOf("o", 7));
+
+trace("//s.lastIndexOf(\"z\")");
+trace(s.lastIndexOf("z"));
+
+trace("//s.slice(7)");
+trace(s.slice(7));
+
+trace("//s.slice(-5, -1)");
+trace(s.slice(-5, -1));
+
+trace("//s.slice(3, 1)");
+trace(s.slice(3, 1));
+
+trace("//s.slice()");
+trace(s.slice());
+
+trace("//s.substr(7, 3)");
+trace(s.substr(7, 3));
+
+trace("//s.substr(-5)");
+trace(s.substr(-5));
+
+trace("//s.substr(5, 100)");
+trace(s.substr(5, 100));
+
+trace("//s.substring(7)");
+trace(s.substring(7));
+
+trace("//s.substring(5, 0)");
+trace(s.substring(5, 0));
+
+trace("//s.substring(-3, 2)");
+trace(s.substring(-3, 2));
+
+trace("//s.toUpperCase()");
+trace(s.toUpperCase());
+
+trace("//s.toLowerCase()");
+trace(s.toLowerCase());
+
+trace("//s.toLocaleUpperCase()");
+trace(s.toLocaleUpperCase());
+
+trace("//s.split(\", \")");
+trace(s.split(", "));
+
+trace("//s.split(\"\").length");
+trace(s.split("").length);
+
+trace("//s.split(\"l\")");
+trace(s.split("l"));
+
+trace("//s.split(\"l\", 2)");
+trace(s.split("l", 2));
+
+trace("//s.split()");
+trace(s.split());
+
+trace("//s.replace(\"World\", \"There\")");
+trace(s.replace("World", "There"));
+
+trace("//s.replace(\"o\", \"[$&]\")");
+trace(s.replace("o", "[$&]"));
+
+trace("//s.replace(\", \", \"$'$`\")");
+trace(s.replace(", ", "$'$`"));
+
+trace("//s.replace(\"l\", function (m) { return m.toUpperCase(); })");
+trace(s.replace("l", function (m) { return m.toUpperCase(); }));
+
+trace("//s.replace(\"z\", \"y\")");
+trace(s.replace("z", "y"));
+
+trace("//s.search(\"World\")");
+trace(s.search("World"));
+
+trace("//s.match(\"o\")");
+trace(s.match("o"));
+
+trace("//s.match(\"z\")");
+trace(s.match("z"));
+
+trace("//s.localeCompare(\"Hello\")");
+trace(s.localeCompare("Hello"));
+
+trace("//s.localeCompare(\"Hello, World\")");
+trace(s.localeCompare("Hello, World"));
+
+trace("//s.localeCompare(\"World\")");
+trace(s.localeCompare("World"));
+
+trace("//s.toString()");
+trace(s.toString());
+
+trace("//s.valueOf()");
+trace(s.valueOf());
+
+trace("//String.fromCharCode(72, 105)");
+trace(String.fromCharCode(72, 105));
+
+trace("//new String(\"abc\")");
+trace(new String("abc"));
+
+trace("//new String(\"abc\") === \"abc\"");
+trace(new String("abc") === "abc");
+
+trace("//new String().length");
+trace(new String().length);
+
+trace("//String(123)");
+trace(String(123));
+
+trace("//String(null)");
+trace(String(null));
+
+trace("//String([1, 2])");
+trace(String([1, 2]));
+
+trace("//\"abc\".toUpperCase()");
+trace("abc".toUpperCase());
+
+trace("//\"abc\".length");
+trace("abc".length);
+
+trace("//\"abc\" is String");
+trace("abc" is String);
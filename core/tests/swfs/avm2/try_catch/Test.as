@@ -0,0 +1,59 @@
+﻿package {
+	public class Test {}
+}
+
+trace("//throw a string");
+try {
+	throw "thrown string";
+	trace("not reached");
+} catch (e) {
+	trace(e);
+}
+
+trace("//catch scope");
+try {
+	throw 42;
+} catch (e) {
+	// The catch variable is read from the scope object created by `newcatch`.
+	trace(e);
+}
+
+trace("//nested handlers");
+try {
+	try {
+		throw 1.5;
+	} catch (e:String) {
+		trace("inner handler");
+	}
+} catch (e:Number) {
+	trace("outer handler: " + e);
+}
+
+trace("//rethrow");
+try {
+	try {
+		throw "a";
+	} catch (e) {
+		trace("inner caught " + e);
+		throw e + "b";
+	}
+} catch (e) {
+	trace("outer caught " + e);
+}
+
+trace("//throw from a function");
+var f = function() {
+	trace("in function");
+	throw "thrown from function";
+	trace("not reached");
+};
+try {
+	// The test SWF leaves extra values on the operand stack here, which must
+	// be discarded when the exception is caught.
+	f();
+	trace("not reached");
+} catch (e) {
+	trace(e);
+}
+
+trace("//done");
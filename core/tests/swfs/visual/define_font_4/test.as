@@ -0,0 +1,9 @@
+﻿this.createTextField("field", 1, 10, 10, 180, 130);
+var format = new TextFormat();
+format.font = "TestCFF";
+format.size = 60;
+format.color = 0x0000FF;
+field.setNewTextFormat(format);
+field.embedFonts = true;
+field.multiline = true;
+field.text = "AOIS\nSOA";
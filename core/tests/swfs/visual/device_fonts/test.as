@@ -0,0 +1,17 @@
+﻿var fields = [
+    ["TestSans", false, "AO-="],
+    ["TestSans", true, "AO-="],
+    ["Missing, TestSans", false, "A日A"],
+    ["_sans", false, "AO日"]
+];
+for (var i = 0; i < fields.length; i++) {
+    this.createTextField("field" + i, i + 1, 10, 5 + i * 36, 190, 36);
+    var field = this["field" + i];
+    var format = new TextFormat();
+    format.font = fields[i][0];
+    format.bold = fields[i][1];
+    format.size = 28;
+    format.color = 0x0000FF;
+    field.setNewTextFormat(format);
+    field.text = fields[i][2];
+}
@@ -0,0 +1,17 @@
+﻿function box(name, depth, x, y) {
+	var clip = _root.createEmptyMovieClip(name, depth);
+	clip.beginFill(0x3366CC);
+	clip.moveTo(x, y);
+	clip.lineTo(x + 80, y);
+	clip.lineTo(x + 80, y + 40);
+	clip.lineTo(x, y + 40);
+	clip.lineTo(x, y);
+	clip.endFill();
+	clip.onPress = function() {
+	};
+	return clip;
+}
+
+box("focused", 1, 40, 40);
+box("unfocused", 2, 160, 40);
+Selection.setFocus(focused);
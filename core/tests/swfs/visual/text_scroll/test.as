@@ -0,0 +1,13 @@
+﻿this.createTextField("field", 1, 10, 10, 80, 50);
+field.multiline = true;
+field.border = true;
+field.borderColor = 0xFF0000;
+field.background = true;
+field.backgroundColor = 0xFFFF80;
+field.textColor = 0x0000FF;
+field.text = "Line 1\nLine 2\nLine 3\nLine 4\nLine 5\nLine 6";
+field.scroll = 3;
+this.createTextField("line", 2, 110, 10, 80, 20);
+line.border = true;
+line.text = "The start of this line is scrolled out of view";
+line.hscroll = 40;
@@ -1,7 +1,12 @@
 //! Custom event type for desktop ruffle
 
+use crate::fscommand::FsCommand;
+
 /// User-defined events.
 pub enum RuffleEvent {
     /// Indicates that one or more tasks are ready to poll on our executor.
     TaskPoll,
+
+    /// The movie sent an FSCommand that the player should carry out.
+    FsCommand(FsCommand),
 }
@@ -200,6 +200,8 @@ impl GlutinAsyncExecutor {
     fn wake(&mut self, task: Index) {
         if let Some(task) = self.task_queue.get_mut(task) {
             if !task.is_completed() {
+                task.set_ready();
+
                 if !self.waiting_for_poll {
                     self.waiting_for_poll = true;
 
@@ -0,0 +1,110 @@
+use crate::custom_event::RuffleEvent;
+use crate::fscommand::FsCommand;
+use ruffle_core::external::{ExternalInterfaceProvider, Value};
+use std::collections::HashMap;
+use winit::event_loop::EventLoopProxy;
+
+/// Handles a call to a method of the host, returning its result.
+pub type MethodHandler = Box<dyn FnMut(&[Value]) -> Value>;
+
+/// Handles an FSCommand that the player does not know, returning whether it
+/// was handled.
+pub type FsCommandHandler = Box<dyn FnMut(&str, &str) -> bool>;
+
+/// Lets a movie call methods of the desktop player.
+///
+/// There is no web page to call into, so each method the player offers is
+/// handled by a Rust closure instead.
+///
+/// The FSCommands of standalone projectors are sent to the event loop to be
+/// carried out; any other FSCommand goes to the FSCommand handler.
+pub struct DesktopExternalInterfaceProvider {
+    methods: HashMap<String, MethodHandler>,
+    fs_command_handler: Option<FsCommandHandler>,
+    event_loop: EventLoopProxy<RuffleEvent>,
+}
+
+impl DesktopExternalInterfaceProvider {
+    pub fn new(event_loop: EventLoopProxy<RuffleEvent>) -> Self {
+        let mut provider = Self {
+            methods: HashMap::new(),
+            fs_command_handler: None,
+            event_loop,
+        };
+
+        // Movies commonly log to the browser console; send that to our log.
+        let console = [
+            ("console.log", log::Level::Info),
+            ("console.info", log::Level::Info),
+            ("console.debug", log::Level::Debug),
+            ("console.warn", log::Level::Warn),
+            ("console.error", log::Level::Error),
+        ];
+        for &(name, level) in console.iter() {
+            provider.add_method(
+                name,
+                Box::new(move |args| {
+                    let message: Vec<_> = args.iter().map(format_value).collect();
+                    log::log!(level, "{}", message.join(" "));
+                    Value::Null
+                }),
+            );
+        }
+
+        provider
+    }
+
+    /// Offer a method to the movie, replacing any method with the same name.
+    pub fn add_method(&mut self, name: &str, handler: MethodHandler) {
+        self.methods.insert(name.to_owned(), handler);
+    }
+
+    /// Set the handler of FSCommands that the player does not know.
+    pub fn set_fs_command_handler(&mut self, handler: Option<FsCommandHandler>) {
+        self.fs_command_handler = handler;
+    }
+}
+
+impl ExternalInterfaceProvider for DesktopExternalInterfaceProvider {
+    fn call_method(&mut self, name: &str, args: &[Value]) -> Value {
+        match self.methods.get_mut(name) {
+            Some(handler) => handler(args),
+            None => {
+                log::warn!("Unknown ExternalInterface method {}", name);
+                Value::Null
+            }
+        }
+    }
+
+    fn on_callback_available(&mut self, name: &str) {
+        log::info!("Movie exposed ExternalInterface callback {}", name);
+    }
+
+    fn on_fs_command(&mut self, command: &str, args: &str) -> bool {
+        if let Some(command) = FsCommand::parse(command, args) {
+            if self
+                .event_loop
+                .send_event(RuffleEvent::FsCommand(command))
+                .is_err()
+            {
+                log::warn!("An FSCommand was sent to an event loop that has already ended.");
+            }
+            return true;
+        }
+
+        match &mut self.fs_command_handler {
+            Some(handler) => handler(command, args),
+            None => false,
+        }
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => value.clone(),
+        Value::Object(_) | Value::List(_) => format!("{:?}", value),
+    }
+}
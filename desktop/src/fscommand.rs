@@ -0,0 +1,102 @@
+//! FSCommands understood by the standalone projector.
+
+use std::path::{Component, Path, PathBuf};
+
+/// An FSCommand that the desktop player handles itself.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FsCommand {
+    /// Close the player.
+    Quit,
+
+    /// Enter or leave fullscreen.
+    FullScreen(bool),
+
+    /// Whether the movie is scaled with the window, or always shown at its
+    /// original size.
+    AllowScale(bool),
+
+    /// Whether the context menu is offered.
+    ShowMenu(bool),
+
+    /// Whether the player keeps its own keyboard shortcuts, such as Escape
+    /// to leave fullscreen, or sends every key to the movie.
+    TrapAllKeys(bool),
+
+    /// Run a program from the `fscommand` directory next to the movie.
+    Exec(String),
+}
+
+impl FsCommand {
+    /// Parse an FSCommand sent by the movie.
+    ///
+    /// Returns `None` for commands that the projector does not know.
+    pub fn parse(command: &str, args: &str) -> Option<Self> {
+        let flag = || args.trim().eq_ignore_ascii_case("true");
+        match command.to_ascii_lowercase().as_str() {
+            "quit" => Some(FsCommand::Quit),
+            "fullscreen" => Some(FsCommand::FullScreen(flag())),
+            "allowscale" => Some(FsCommand::AllowScale(flag())),
+            "showmenu" => Some(FsCommand::ShowMenu(flag())),
+            "trapallkeys" => Some(FsCommand::TrapAllKeys(flag())),
+            "exec" => Some(FsCommand::Exec(args.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// Resolve the program that `exec` should run for a movie in `movie_dir`.
+///
+/// Like Flash projectors, only programs directly inside the `fscommand`
+/// directory next to the movie may be run.
+pub fn exec_path(movie_dir: &Path, program: &str) -> Option<PathBuf> {
+    let mut components = Path::new(program).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) => Some(movie_dir.join("fscommand").join(name)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(FsCommand::parse("quit", ""), Some(FsCommand::Quit));
+        assert_eq!(
+            FsCommand::parse("fullscreen", "true"),
+            Some(FsCommand::FullScreen(true))
+        );
+        assert_eq!(
+            FsCommand::parse("AllowScale", "FALSE"),
+            Some(FsCommand::AllowScale(false))
+        );
+        assert_eq!(
+            FsCommand::parse("showmenu", "0"),
+            Some(FsCommand::ShowMenu(false))
+        );
+        assert_eq!(
+            FsCommand::parse("trapallkeys", "true"),
+            Some(FsCommand::TrapAllKeys(true))
+        );
+        assert_eq!(
+            FsCommand::parse("exec", "setup.exe"),
+            Some(FsCommand::Exec("setup.exe".to_string()))
+        );
+        assert_eq!(FsCommand::parse("custom", "true"), None);
+    }
+
+    #[test]
+    fn exec_only_runs_fscommand_programs() {
+        let movie_dir = Path::new("movies");
+        assert_eq!(
+            exec_path(movie_dir, "setup.exe"),
+            Some(movie_dir.join("fscommand").join("setup.exe"))
+        );
+        assert_eq!(exec_path(movie_dir, ""), None);
+        assert_eq!(exec_path(movie_dir, ".."), None);
+        assert_eq!(exec_path(movie_dir, "../setup.exe"), None);
+        assert_eq!(exec_path(movie_dir, "tools/setup.exe"), None);
+        assert_eq!(exec_path(movie_dir, "/bin/sh"), None);
+    }
+}
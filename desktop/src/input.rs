@@ -66,6 +66,7 @@ impl InputBackend for WinitInputBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains(&VirtualKeyCode::Back),
+            KeyCode::Tab => self.keys_down.contains(&VirtualKeyCode::Tab),
             KeyCode::Return => self.keys_down.contains(&VirtualKeyCode::Return),
             KeyCode::Shift => {
                 self.keys_down.contains(&VirtualKeyCode::LShift)
@@ -203,6 +204,10 @@ impl InputBackend for WinitInputBackend {
     fn set_clipboard_content(&mut self, content: String) {
         self.clipboard.set_contents(content).unwrap();
     }
+
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.get_contents().unwrap_or_default()
+    }
 }
 
 /// Converts a winit `VirtualKeyCode` into a Ruffle `KeyCode`.
@@ -210,6 +215,7 @@ impl InputBackend for WinitInputBackend {
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
     let out = match key_code {
         VirtualKeyCode::Back => KeyCode::Backspace,
+        VirtualKeyCode::Tab => KeyCode::Tab,
         VirtualKeyCode::Return => KeyCode::Return,
         VirtualKeyCode::LShift | VirtualKeyCode::RShift => KeyCode::Shift,
         VirtualKeyCode::LControl | VirtualKeyCode::RControl => KeyCode::Control,
@@ -0,0 +1,81 @@
+use ruffle_core::backend::locale::LocaleBackend;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Locale backend that uses the system clock and time zone.
+pub struct DesktopLocaleBackend {}
+
+impl DesktopLocaleBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl LocaleBackend for DesktopLocaleBackend {
+    fn current_time(&self) -> f64 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs_f64() * 1000.0,
+            Err(e) => -e.duration().as_secs_f64() * 1000.0,
+        }
+    }
+
+    fn local_utc_offset(&self, utc_time: f64) -> i32 {
+        system_utc_offset(utc_time).unwrap_or(0)
+    }
+}
+
+#[cfg(unix)]
+fn system_utc_offset(utc_time: f64) -> Option<i32> {
+    let time = (utc_time / 1000.0).floor() as libc::time_t;
+    unsafe {
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&time, &mut tm).is_null() {
+            return None;
+        }
+        Some((tm.tm_gmtoff / 60) as i32)
+    }
+}
+
+#[cfg(windows)]
+fn system_utc_offset(utc_time: f64) -> Option<i32> {
+    use winapi::shared::minwindef::FILETIME;
+    use winapi::um::minwinbase::SYSTEMTIME;
+    use winapi::um::timezoneapi::{
+        FileTimeToSystemTime, SystemTimeToFileTime, SystemTimeToTzSpecificLocalTime,
+    };
+
+    // `FILETIME` counts 100ns intervals since 1601-01-01.
+    const EPOCH_DIFFERENCE_MS: f64 = 11_644_473_600_000.0;
+    const TICKS_PER_MINUTE: i64 = 600_000_000;
+
+    let utc_ms = utc_time + EPOCH_DIFFERENCE_MS;
+    if !utc_ms.is_finite() || utc_ms < 0.0 {
+        return None;
+    }
+    let utc_ticks = (utc_ms * 10_000.0) as u64;
+    unsafe {
+        let utc_file = FILETIME {
+            dwLowDateTime: utc_ticks as u32,
+            dwHighDateTime: (utc_ticks >> 32) as u32,
+        };
+        let mut utc_system: SYSTEMTIME = std::mem::zeroed();
+        if FileTimeToSystemTime(&utc_file, &mut utc_system) == 0 {
+            return None;
+        }
+        let mut local_system: SYSTEMTIME = std::mem::zeroed();
+        if SystemTimeToTzSpecificLocalTime(std::ptr::null(), &utc_system, &mut local_system) == 0 {
+            return None;
+        }
+        let mut local_file: FILETIME = std::mem::zeroed();
+        if SystemTimeToFileTime(&local_system, &mut local_file) == 0 {
+            return None;
+        }
+        let local_ticks =
+            (u64::from(local_file.dwHighDateTime) << 32) | u64::from(local_file.dwLowDateTime);
+        Some(((local_ticks as i64 - utc_ticks as i64) / TICKS_PER_MINUTE) as i32)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn system_utc_offset(_utc_time: f64) -> Option<i32> {
+    None
+}
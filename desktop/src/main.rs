@@ -3,35 +3,149 @@
 mod audio;
 mod custom_event;
 mod executor;
+mod external_interface;
+mod fscommand;
 mod input;
+mod locale;
 mod navigator;
 mod storage;
 mod task;
 
 use crate::custom_event::RuffleEvent;
 use crate::executor::GlutinAsyncExecutor;
+use crate::fscommand::FsCommand;
 use ruffle_core::{
     backend::audio::{AudioBackend, NullAudioBackend},
+    backend::video::SoftwareVideoBackend,
+    config::{StageQuality, StageScaleMode},
     Player,
 };
 use ruffle_render_wgpu::WgpuRenderBackend;
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
 use structopt::StructOpt;
 
 use crate::storage::DiskStorageBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use std::rc::Rc;
+use url::Url;
 use winit::dpi::{LogicalSize, PhysicalPosition};
-use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::event::{ElementState, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
-use winit::window::{Icon, WindowBuilder};
+use winit::window::{Fullscreen, Icon, WindowBuilder};
 
 #[derive(StructOpt, Debug)]
 #[structopt(name = "basic")]
 struct Opt {
-    #[structopt(name = "FILE", parse(from_os_str))]
-    input_path: PathBuf,
+    /// Path or http(s) URL of the SWF to play.
+    #[structopt(name = "FILE")]
+    input: String,
+
+    /// Directory of TrueType/OpenType fonts to render device text with.
+    /// May be given more than once.
+    #[structopt(long = "font-dir", name = "DIR", parse(from_os_str))]
+    font_dirs: Vec<PathBuf>,
+
+    /// Width of the window. Defaults to the width of the movie.
+    #[structopt(long)]
+    width: Option<u32>,
+
+    /// Height of the window. Defaults to the height of the movie.
+    #[structopt(long)]
+    height: Option<u32>,
+
+    /// How the movie is scaled to fit the window: exactFit, noBorder, noScale or showAll.
+    #[structopt(long = "scale-mode", default_value = "showAll")]
+    scale_mode: StageScaleMode,
+
+    /// Rendering quality: low, medium, high or best.
+    #[structopt(long, default_value = "high")]
+    quality: StageQuality,
+
+    /// Start in fullscreen.
+    #[structopt(long)]
+    fullscreen: bool,
+
+    /// Disable audio output.
+    #[structopt(long = "no-audio")]
+    no_audio: bool,
+
+    /// URL (or directory) that relative URLs requested by the movie are resolved against.
+    /// Defaults to the location of the movie.
+    #[structopt(long = "base-url")]
+    base_url: Option<String>,
+
+    /// A `key=value` variable to set on `_root`, like a FlashVars parameter.
+    /// May be given more than once.
+    #[structopt(long = "param", name = "KEY=VALUE", parse(try_from_str = parse_parameter))]
+    parameters: Vec<(String, String)>,
+
+    /// Directory to store shared objects in.
+    #[structopt(long = "storage-dir", parse(from_os_str))]
+    storage_dir: Option<PathBuf>,
+
+    /// Number of seconds a script may run for before it is aborted.
+    #[structopt(long = "max-execution-duration", default_value = "15")]
+    max_execution_duration: f64,
+
+    /// Program to run for FSCommands that the player does not handle itself.
+    /// It is given the command and its arguments as arguments.
+    #[structopt(long = "fscommand-handler", value_name = "PROGRAM")]
+    fscommand_handler: Option<String>,
+}
+
+/// Parse a `key=value` parameter given on the command line.
+fn parse_parameter(parameter: &str) -> Result<(String, String), String> {
+    let mut split = parameter.splitn(2, '=');
+    match (split.next(), split.next()) {
+        (Some(key), Some(value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("Expected key=value, got {}", parameter)),
+    }
+}
+
+/// Returns the number of MSAA samples to render with at the given quality.
+fn msaa_sample_count(quality: StageQuality) -> u32 {
+    match quality {
+        StageQuality::Low => 1,
+        StageQuality::Medium => 2,
+        StageQuality::High | StageQuality::Best => 4,
+    }
+}
+
+/// Resolve the movie given on the command line into a URL.
+fn movie_url(input: &str) -> Result<Url, Box<dyn std::error::Error>> {
+    match Url::parse(input) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Ok(url),
+        _ => {
+            let path = Path::new(input).canonicalize()?;
+            Url::from_file_path(&path)
+                .map_err(|_| format!("Invalid movie path {}", path.display()).into())
+        }
+    }
+}
+
+/// Resolve the `--base-url` option, which may be a URL or a local directory.
+fn base_url(base: &str) -> Url {
+    match Url::parse(base) {
+        Ok(url) if url.scheme().len() > 1 => url,
+        _ => navigator::directory_url(Path::new(base)),
+    }
+}
+
+/// Run a program from the `fscommand` directory next to the movie, on
+/// behalf of the `exec` FSCommand.
+fn exec_fscommand(movie_dir: Option<&Path>, program: &str) {
+    let path = match movie_dir.and_then(|dir| fscommand::exec_path(dir, program)) {
+        Some(path) => path,
+        None => {
+            log::warn!("FSCommand exec refused to run {}", program);
+            return;
+        }
+    };
+    if let Err(e) = Command::new(&path).spawn() {
+        log::warn!("FSCommand exec failed to run {}: {}", path.display(), e);
+    }
 }
 
 fn main() {
@@ -41,7 +155,7 @@ fn main() {
 
     let opt = Opt::from_args();
 
-    let ret = run_player(opt.input_path);
+    let ret = run_player(opt);
 
     if let Err(e) = ret {
         eprintln!("Fatal error:\n{}", e);
@@ -49,57 +163,162 @@ fn main() {
     }
 }
 
-fn run_player(input_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
-    let movie = SwfMovie::from_path(&input_path)?;
-    let movie_size = LogicalSize::new(movie.width(), movie.height());
+/// Registers every font file in the given directory and its subdirectories
+/// with the player, to render device text with.
+fn register_fonts(player: &mut Player, dir: &Path) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Unable to read font directory {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if path.is_dir() {
+            register_fonts(player, &path);
+            continue;
+        }
+
+        let is_font_file = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                let ext = ext.to_ascii_lowercase();
+                ext == "ttf" || ext == "otf" || ext == "ttc" || ext == "otc"
+            })
+            .unwrap_or(false);
+        if !is_font_file {
+            continue;
+        }
+
+        match std::fs::read(&path)
+            .map_err(|e| e.into())
+            .and_then(|data| player.register_device_font(data))
+        {
+            Ok(count) => log::info!("Loaded {} font(s) from {}", count, path.display()),
+            Err(e) => log::warn!("Unable to load font {}: {}", path.display(), e),
+        }
+    }
+}
+
+fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
+    let movie_url = movie_url(&opt.input)?;
+    let mut movie = if movie_url.scheme() == "file" {
+        SwfMovie::from_path(movie_url.to_file_path().unwrap_or_default())?
+    } else {
+        let data = navigator::fetch_http_blocking(&movie_url)?;
+        SwfMovie::from_data(&data, Some(movie_url.to_string()))?
+    };
+    movie.append_parameters(opt.parameters);
+
+    let movie_name = movie_url
+        .path_segments()
+        .and_then(|segments| segments.last())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| movie_url.host_str().unwrap_or_default())
+        .to_string();
+    let movie_size = LogicalSize::new(
+        opt.width.unwrap_or_else(|| movie.width()),
+        opt.height.unwrap_or_else(|| movie.height()),
+    );
 
     let icon_bytes = include_bytes!("../assets/favicon-32.rgba");
     let icon = Icon::from_rgba(icon_bytes.to_vec(), 32, 32)?;
 
     let event_loop: EventLoop<RuffleEvent> = EventLoop::with_user_event();
+    let fullscreen = if opt.fullscreen {
+        Some(Fullscreen::Borderless(event_loop.primary_monitor()))
+    } else {
+        None
+    };
     let window = Rc::new(
         WindowBuilder::new()
-            .with_title(format!(
-                "Ruffle - {}",
-                input_path.file_name().unwrap_or_default().to_string_lossy()
-            ))
+            .with_title(format!("Ruffle - {}", movie_name))
             .with_window_icon(Some(icon))
             .with_inner_size(movie_size)
+            .with_fullscreen(fullscreen)
             .build(&event_loop)?,
     );
-    let viewport_size = movie_size.to_physical(window.scale_factor());
+    let viewport_size = window.inner_size();
 
-    let audio: Box<dyn AudioBackend> = match audio::CpalAudioBackend::new() {
-        Ok(audio) => Box::new(audio),
-        Err(e) => {
-            log::error!("Unable to create audio device: {}", e);
-            Box::new(NullAudioBackend::new())
+    let audio: Box<dyn AudioBackend> = if opt.no_audio {
+        Box::new(NullAudioBackend::new())
+    } else {
+        match audio::CpalAudioBackend::new() {
+            Ok(audio) => Box::new(audio),
+            Err(e) => {
+                log::error!("Unable to create audio device: {}", e);
+                Box::new(NullAudioBackend::new())
+            }
         }
     };
     let renderer = Box::new(WgpuRenderBackend::for_window(
         window.as_ref(),
         (viewport_size.width, viewport_size.height),
+        msaa_sample_count(opt.quality),
     )?);
     let (executor, chan) = GlutinAsyncExecutor::new(event_loop.create_proxy());
-    let navigator = Box::new(navigator::ExternalNavigatorBackend::with_base_path(
-        input_path
-            .parent()
-            .unwrap_or_else(|| std::path::Path::new("")),
+    let navigator = Box::new(navigator::ExternalNavigatorBackend::with_base_url(
+        opt.base_url
+            .as_deref()
+            .map(base_url)
+            .unwrap_or_else(|| movie_url.clone()),
         chan,
         event_loop.create_proxy(),
-    )); //TODO: actually implement this backend type
-    let input = Box::new(input::WinitInputBackend::new(window.clone()));
-    let storage = Box::new(DiskStorageBackend::new(
-        input_path.file_name().unwrap_or_default().as_ref(),
     ));
-    let player = Player::new(renderer, audio, navigator, input, movie, storage)?;
-    player.lock().unwrap().set_is_playing(true); // Desktop player will auto-play.
+    let input = Box::new(input::WinitInputBackend::new(window.clone()));
+    let storage = Box::new(match &opt.storage_dir {
+        Some(storage_dir) => DiskStorageBackend::with_base_dir(storage_dir, movie_name.as_ref()),
+        None => DiskStorageBackend::new(movie_name.as_ref()),
+    });
+    let video = Box::new(SoftwareVideoBackend::new());
+    let locale = Box::new(locale::DesktopLocaleBackend::new());
+    let player = Player::new(
+        renderer, audio, video, navigator, input, movie, storage, locale,
+    )?;
+    {
+        let mut player_lock = player.lock().unwrap();
+        for font_dir in &opt.font_dirs {
+            register_fonts(&mut player_lock, font_dir);
+        }
+        let mut external_interface =
+            external_interface::DesktopExternalInterfaceProvider::new(event_loop.create_proxy());
+        if let Some(program) = opt.fscommand_handler.clone() {
+            external_interface.set_fs_command_handler(Some(Box::new(move |command, args| {
+                match Command::new(&program).arg(command).arg(args).spawn() {
+                    Ok(_) => true,
+                    Err(e) => {
+                        log::warn!("Unable to run FSCommand handler {}: {}", program, e);
+                        false
+                    }
+                }
+            })));
+        }
+        player_lock.set_external_interface_provider(Some(Box::new(external_interface)));
+        player_lock.set_scale_mode(opt.scale_mode);
+        player_lock.set_max_execution_duration(Duration::from_secs_f64(
+            opt.max_execution_duration.max(0.0),
+        ));
+        player_lock.set_is_playing(true); // Desktop player will auto-play.
+    }
 
     player
         .lock()
         .unwrap()
         .set_viewport_dimensions(viewport_size.width, viewport_size.height);
 
+    // Local movies may run programs from their `fscommand` directory.
+    let movie_dir = movie_url
+        .to_file_path()
+        .ok()
+        .and_then(|path| path.parent().map(Path::to_path_buf));
+    let scale_mode = opt.scale_mode;
+    let mut trap_all_keys = false;
+
     let mut mouse_pos = PhysicalPosition::new(0.0, 0.0);
     let mut time = Instant::now();
     let mut next_frame_time = Instant::now();
@@ -182,6 +401,22 @@ fn run_player(input_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
                     }
                     WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
                     WindowEvent::KeyboardInput { .. } | WindowEvent::ReceivedCharacter(_) => {
+                        // Escape leaves fullscreen, unless the movie wants every key.
+                        if let WindowEvent::KeyboardInput {
+                            input:
+                                KeyboardInput {
+                                    state: ElementState::Pressed,
+                                    virtual_keycode: Some(VirtualKeyCode::Escape),
+                                    ..
+                                },
+                            ..
+                        } = event
+                        {
+                            if !trap_all_keys && window.fullscreen().is_some() {
+                                window.set_fullscreen(None);
+                            }
+                        }
+
                         let mut player_lock = player.lock().unwrap();
                         if let Some(event) = player_lock
                             .input_mut()
@@ -201,6 +436,26 @@ fn run_player(input_path: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
                     .lock()
                     .expect("active executor reference")
                     .poll_all(),
+                winit::event::Event::UserEvent(RuffleEvent::FsCommand(command)) => match command {
+                    FsCommand::Quit => *control_flow = ControlFlow::Exit,
+                    FsCommand::FullScreen(fullscreen) => window.set_fullscreen(if fullscreen {
+                        Some(Fullscreen::Borderless(window.current_monitor()))
+                    } else {
+                        None
+                    }),
+                    FsCommand::AllowScale(allow_scale) => {
+                        player.lock().unwrap().set_scale_mode(if allow_scale {
+                            scale_mode
+                        } else {
+                            StageScaleMode::NoScale
+                        });
+                        window.request_redraw();
+                    }
+                    // The desktop player has no context menu to show or hide.
+                    FsCommand::ShowMenu(_) => (),
+                    FsCommand::TrapAllKeys(trap) => trap_all_keys = trap,
+                    FsCommand::Exec(program) => exec_fscommand(movie_dir.as_deref(), &program),
+                },
                 _ => (),
             }
 
@@ -230,3 +485,36 @@ fn win32_hide_console() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_parameters() {
+        assert_eq!(
+            parse_parameter("name=value"),
+            Ok(("name".to_string(), "value".to_string()))
+        );
+        assert_eq!(
+            parse_parameter("query=a=b"),
+            Ok(("query".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(
+            parse_parameter("empty="),
+            Ok(("empty".to_string(), "".to_string()))
+        );
+        assert!(parse_parameter("novalue").is_err());
+        assert!(parse_parameter("=value").is_err());
+    }
+
+    #[test]
+    fn resolve_base_url() {
+        assert_eq!(
+            base_url("https://example.com/movies/").as_str(),
+            "https://example.com/movies/"
+        );
+        assert_eq!(base_url("assets").scheme(), "file");
+        assert!(base_url("assets").path().ends_with("/assets/"));
+    }
+}
@@ -1,14 +1,17 @@
 //! Navigator backend for web
 
 use crate::custom_event::RuffleEvent;
+use futures::channel::oneshot;
 use ruffle_core::backend::navigator::{
     NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
 };
 use ruffle_core::loader::Error;
 use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::Read;
+use std::path::Path;
 use std::sync::mpsc::Sender;
+use std::thread;
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -22,8 +25,8 @@ pub struct ExternalNavigatorBackend {
     /// Event sink to trigger a new task poll.
     event_loop: EventLoopProxy<RuffleEvent>,
 
-    /// The base path for all relative fetches.
-    relative_base_path: PathBuf,
+    /// The base URL for all relative fetches.
+    base_url: Url,
 
     /// The time that the SWF was launched.
     start_time: Instant,
@@ -38,28 +41,105 @@ impl ExternalNavigatorBackend {
         Self {
             channel,
             event_loop,
-            relative_base_path: PathBuf::new(),
+            base_url: directory_url(Path::new("")),
             start_time: Instant::now(),
         }
     }
 
     /// Construct a navigator backend with fetch and async capability.
-    pub fn with_base_path<P: AsRef<Path>>(
-        path: P,
+    ///
+    /// Relative URLs requested by the movie are resolved against `base_url`.
+    pub fn with_base_url(
+        base_url: Url,
         channel: Sender<OwnedFuture<(), Error>>,
         event_loop: EventLoopProxy<RuffleEvent>,
     ) -> Self {
-        let mut relative_base_path = PathBuf::new();
-
-        relative_base_path.push(path);
-
         Self {
             channel,
             event_loop,
-            relative_base_path,
+            base_url,
             start_time: Instant::now(),
         }
     }
+
+    /// Resolve a URL requested by the movie against our base URL.
+    ///
+    /// Absolute filesystem paths are accepted as well, since movies running
+    /// locally may refer to them.
+    fn resolve_url(&self, url: &str) -> Result<Url, Error> {
+        let path = Path::new(url);
+        if path.is_absolute() {
+            if let Ok(file_url) = Url::from_file_path(path) {
+                return Ok(file_url);
+            }
+        }
+
+        self.base_url
+            .join(url)
+            .map_err(|e| Error::FetchError(url.to_string(), e.to_string()))
+    }
+}
+
+/// Convert a directory on the local filesystem into a `file:` URL that
+/// relative URLs can be joined onto.
+pub fn directory_url(path: &Path) -> Url {
+    let path = std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+
+    Url::from_directory_path(&path)
+        .or_else(|_| Url::parse("file:///"))
+        .expect("valid file URL")
+}
+
+/// Perform a blocking HTTP(S) request, following any redirects.
+///
+/// This yields the status and body of the final response. It runs off the
+/// main thread, so failures are reported as `ureq` errors (which, unlike
+/// `loader::Error`, can be sent back across threads).
+fn send_http_request(url: &Url, options: &RequestOptions) -> Result<(u16, Vec<u8>), ureq::Error> {
+    let mut request = match options.method() {
+        NavigationMethod::GET => ureq::get(url.as_str()),
+        NavigationMethod::POST => ureq::post(url.as_str()),
+    };
+    for (name, value) in options.headers() {
+        request.set(name, value);
+    }
+
+    let response = match (options.method(), options.body()) {
+        (NavigationMethod::GET, _) => request.call(),
+        (NavigationMethod::POST, Some((data, mime))) => {
+            request.set("Content-Type", mime).send_bytes(data)
+        }
+        (NavigationMethod::POST, None) => request.send_bytes(&[]),
+    };
+
+    if response.synthetic_error().is_some() {
+        return Err(response.into_synthetic_error().expect("synthetic error"));
+    }
+    let status = response.status();
+
+    let mut body = Vec::new();
+    response.into_reader().read_to_end(&mut body)?;
+    Ok((status, body))
+}
+
+/// Fetch the contents of an HTTP(S) URL, blocking until it has arrived.
+pub fn fetch_http_blocking(url: &Url) -> Result<Vec<u8>, Error> {
+    http_fetch_result(url, send_http_request(url, &RequestOptions::get()))
+}
+
+/// Map the outcome of an HTTP request onto the result of a fetch.
+fn http_fetch_result(
+    url: &Url,
+    outcome: Result<(u16, Vec<u8>), ureq::Error>,
+) -> Result<Vec<u8>, Error> {
+    match outcome {
+        Ok((status, body)) if (200..300).contains(&status) => Ok(body),
+        Ok((status, _)) => Err(Error::HttpNotOk(url.to_string(), status)),
+        Err(ureq::Error::Io(e)) => Err(Error::NetworkError(e)),
+        Err(e) => Err(Error::FetchError(url.to_string(), e.to_string())),
+    }
 }
 
 impl NavigatorBackend for ExternalNavigatorBackend {
@@ -111,13 +191,41 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         Instant::now().duration_since(self.start_time)
     }
 
-    fn fetch(&self, url: &str, _options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
-        // Load from local filesystem.
-        // TODO: Support network loads, honor sandbox type (local-with-filesystem, local-with-network, remote, ...)
-        let mut path = self.relative_base_path.clone();
-        path.push(url);
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        let url = match self.resolve_url(url) {
+            Ok(url) => url,
+            Err(e) => return Box::pin(async move { Err(e) }),
+        };
+
+        match url.scheme() {
+            "file" => Box::pin(async move {
+                let path = url
+                    .to_file_path()
+                    .map_err(|_| Error::FetchError(url.to_string(), "Invalid file URL".into()))?;
+                fs::read(path).map_err(Error::NetworkError)
+            }),
+            "http" | "https" => {
+                // The request blocks, so it runs on its own thread; the
+                // executor is woken up once the response arrives.
+                let (sender, receiver) = oneshot::channel();
+                let request_url = url.clone();
+                thread::spawn(move || {
+                    let _ = sender.send(send_http_request(&request_url, &options));
+                });
 
-        Box::pin(async move { fs::read(path).map_err(Error::NetworkError) })
+                Box::pin(async move {
+                    let outcome = receiver.await.map_err(|_| Error::Cancelled)?;
+                    http_fetch_result(&url, outcome)
+                })
+            }
+            scheme => {
+                let error = Error::FetchError(
+                    url.to_string(),
+                    format!("Unsupported URL scheme {}", scheme),
+                );
+                Box::pin(async move { Err(error) })
+            }
+        }
     }
 
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
@@ -130,3 +238,169 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    fn fetch_http(url: &Url, options: &RequestOptions) -> Result<Vec<u8>, Error> {
+        http_fetch_result(url, send_http_request(url, options))
+    }
+
+    /// Start a stand-in HTTP server on a local port.
+    ///
+    /// Each incoming connection is answered with the next canned response.
+    /// Once all responses are sent, the server thread returns the raw requests
+    /// it received.
+    fn serve(responses: Vec<&'static str>) -> (Url, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                let header_end = loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break end + 4;
+                    }
+                };
+                let request_text = String::from_utf8_lossy(&request).to_string();
+                let content_length = request_text
+                    .lines()
+                    .filter_map(|line| {
+                        let mut parts = line.splitn(2, ':');
+                        let name = parts.next()?;
+                        let value = parts.next()?;
+                        if name.eq_ignore_ascii_case("content-length") {
+                            value.trim().parse::<usize>().ok()
+                        } else {
+                            None
+                        }
+                    })
+                    .next()
+                    .unwrap_or(0);
+                while request.len() < header_end + content_length {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(String::from_utf8_lossy(&request).to_string());
+            }
+            requests
+        });
+
+        (url, server)
+    }
+
+    #[test]
+    fn http_get() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+        ]);
+
+        let body = fetch_http(&base.join("data.txt").unwrap(), &RequestOptions::get()).unwrap();
+        assert_eq!(body, b"hello");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /data.txt HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn http_post() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+        ]);
+
+        let options = RequestOptions::post(Some((
+            b"a=1&b=2".to_vec(),
+            "application/x-www-form-urlencoded".to_string(),
+        )));
+        let body = fetch_http(&base.join("submit").unwrap(), &options).unwrap();
+        assert_eq!(body, b"ok");
+
+        let requests = server.join().unwrap();
+        let request = requests[0].to_ascii_lowercase();
+        assert!(request.starts_with("post /submit http/1.1\r\n"));
+        assert!(request.contains("\r\ncontent-type: application/x-www-form-urlencoded\r\n"));
+        assert!(request.ends_with("\r\n\r\na=1&b=2"));
+    }
+
+    #[test]
+    fn http_post_with_headers() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+        ]);
+
+        let options = RequestOptions::post(Some((b"a=1".to_vec(), "text/plain".to_string())))
+            .with_headers(vec![("X-Custom".to_string(), "value".to_string())]);
+        fetch_http(&base.join("submit").unwrap(), &options).unwrap();
+
+        let requests = server.join().unwrap();
+        let request = requests[0].to_ascii_lowercase();
+        assert!(request.contains("\r\nx-custom: value\r\n"));
+        assert!(request.contains("\r\ncontent-type: text/plain\r\n"));
+    }
+
+    #[test]
+    fn http_redirect() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 302 Found\r\nLocation: /moved.txt\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nConnection: close\r\n\r\nmoved",
+        ]);
+
+        let body = fetch_http(&base.join("old.txt").unwrap(), &RequestOptions::get()).unwrap();
+        assert_eq!(body, b"moved");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /old.txt HTTP/1.1\r\n"));
+        assert!(requests[1].starts_with("GET /moved.txt HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn http_error_status() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+
+        let url = base.join("missing.txt").unwrap();
+        match fetch_http(&url, &RequestOptions::get()) {
+            Err(Error::HttpNotOk(failed_url, 404)) => assert_eq!(failed_url, url.to_string()),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn http_connection_failure() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let url = Url::parse(&format!("http://{}/", addr)).unwrap();
+        match fetch_http(&url, &RequestOptions::get()) {
+            Err(Error::FetchError(failed_url, _)) => assert_eq!(failed_url, url.to_string()),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn directory_url_is_joinable() {
+        let base = directory_url(Path::new("movies"));
+        assert_eq!(base.scheme(), "file");
+        assert!(base.path().ends_with("/movies/"));
+        assert!(base
+            .join("clip.swf")
+            .unwrap()
+            .path()
+            .ends_with("/movies/clip.swf"));
+    }
+}
@@ -9,11 +9,17 @@ pub struct DiskStorageBackend {
 }
 
 impl DiskStorageBackend {
+    /// Store data for the given scope in the default data directory.
     pub fn new(scope: &Path) -> Self {
-        let base_path = dirs::data_local_dir()
-            .unwrap()
-            .join(Path::new("ruffle"))
-            .join(scope);
+        Self::with_base_dir(
+            &dirs::data_local_dir().unwrap().join(Path::new("ruffle")),
+            scope,
+        )
+    }
+
+    /// Store data for the given scope in a custom data directory.
+    pub fn with_base_dir(base_dir: &Path, scope: &Path) -> Self {
+        let base_path = base_dir.join(scope);
 
         // Create a base dir if one doesn't exist yet
         if !base_path.exists() {
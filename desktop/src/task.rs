@@ -41,11 +41,17 @@ impl Task {
     }
 
     /// Returns `true` if the task is awaiting further progress.
-    #[allow(dead_code)]
     pub fn is_blocked(&self) -> bool {
         self.state == TaskState::Blocked
     }
 
+    /// Mark a blocked task as ready to be polled again.
+    pub fn set_ready(&mut self) {
+        if self.is_blocked() {
+            self.state = TaskState::Ready;
+        }
+    }
+
     /// Returns `true` if the task has completed and should not be polled again.
     pub fn is_completed(&self) -> bool {
         self.state == TaskState::Completed
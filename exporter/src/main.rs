@@ -3,10 +3,14 @@ use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use ruffle_core::backend::audio::NullAudioBackend;
 use ruffle_core::backend::input::NullInputBackend;
+use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::render::RenderBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::WgpuRenderBackend;
 use std::error::Error;
@@ -57,13 +61,26 @@ struct Opt {
     #[structopt(short, long)]
     silent: bool,
 
+    /// Render on the CPU instead of using a graphics device
+    #[structopt(long)]
+    software: bool,
+
     #[structopt(flatten)]
     size: SizeOpt,
 }
 
+/// The renderer used to capture frames.
+#[derive(Clone)]
+enum Renderer {
+    /// Hardware accelerated rendering with wgpu.
+    Wgpu(Rc<wgpu::Device>, Rc<wgpu::Queue>),
+
+    /// Rendering on the CPU, for machines without a usable GPU.
+    Software,
+}
+
 fn take_screenshot(
-    device: Rc<wgpu::Device>,
-    queue: Rc<wgpu::Queue>,
+    renderer: Renderer,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -78,14 +95,27 @@ fn take_screenshot(
     let height = size.height.unwrap_or_else(|| movie.height());
     let height = (height as f32 * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&device, (width, height));
+    let render_backend: Box<dyn RenderBackend> = match &renderer {
+        Renderer::Wgpu(device, queue) => {
+            let target = TextureTarget::new(&device, (width, height));
+            Box::new(WgpuRenderBackend::new(
+                device.clone(),
+                queue.clone(),
+                target,
+                4,
+            )?)
+        }
+        Renderer::Software => Box::new(SoftwareRenderBackend::new(width, height)),
+    };
     let player = Player::new(
-        Box::new(WgpuRenderBackend::new(device, queue, target)?),
+        render_backend,
         Box::new(NullAudioBackend::new()),
+        Box::new(SoftwareVideoBackend::new()),
         Box::new(NullNavigatorBackend::new()),
         Box::new(NullInputBackend::new()),
         movie,
         Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
     )?;
 
     player
@@ -108,12 +138,27 @@ fn take_screenshot(
         if i >= skipframes {
             player.lock().unwrap().render();
             let mut player = player.lock().unwrap();
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-            let target = renderer.target();
-            if let Some(image) = target.capture(renderer.device()) {
+            let image = match renderer {
+                Renderer::Wgpu(..) => {
+                    let renderer = player
+                        .renderer_mut()
+                        .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                        .unwrap();
+                    renderer.target().capture(renderer.device())
+                }
+                Renderer::Software => {
+                    let renderer = player
+                        .renderer_mut()
+                        .downcast_mut::<SoftwareRenderBackend>()
+                        .unwrap();
+                    RgbaImage::from_raw(
+                        renderer.width(),
+                        renderer.height(),
+                        renderer.pixels().to_vec(),
+                    )
+                }
+            };
+            if let Some(image) = image {
                 result.push(image);
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
@@ -158,11 +203,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(
-    device: Rc<wgpu::Device>,
-    queue: Rc<wgpu::Queue>,
-    opt: &Opt,
-) -> Result<(), Box<dyn Error>> {
+fn capture_single_swf(renderer: Renderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         if opt.frames == 1 {
@@ -193,8 +234,7 @@ fn capture_single_swf(
     };
 
     let frames = take_screenshot(
-        device,
-        queue,
+        renderer,
         &opt.swf,
         opt.frames,
         opt.skipframes,
@@ -240,11 +280,7 @@ fn capture_single_swf(
     Ok(())
 }
 
-fn capture_multiple_swfs(
-    device: Rc<wgpu::Device>,
-    queue: Rc<wgpu::Queue>,
-    opt: &Opt,
-) -> Result<(), Box<dyn Error>> {
+fn capture_multiple_swfs(renderer: Renderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -264,8 +300,7 @@ fn capture_multiple_swfs(
 
     for file in &files {
         let frames = take_screenshot(
-            device.clone(),
-            queue.clone(),
+            renderer.clone(),
             &file.path(),
             opt.frames,
             opt.skipframes,
@@ -330,28 +365,34 @@ fn capture_multiple_swfs(
 
 fn main() -> Result<(), Box<dyn Error>> {
     let opt: Opt = Opt::from_args();
-    let adapter = block_on(wgpu::Adapter::request(
-        &wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::Default,
-            compatible_surface: None,
-        },
-        wgpu::BackendBit::PRIMARY,
-    ))
-    .ok_or_else(|| {
-        "This tool requires hardware acceleration, but no compatible graphics device was found."
-    })?;
-
-    let (device, queue) = block_on(adapter.request_device(&wgpu::DeviceDescriptor {
-        extensions: wgpu::Extensions {
-            anisotropic_filtering: false,
-        },
-        limits: wgpu::Limits::default(),
-    }));
+    let renderer = if opt.software {
+        Renderer::Software
+    } else {
+        let adapter = block_on(wgpu::Adapter::request(
+            &wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::Default,
+                compatible_surface: None,
+            },
+            wgpu::BackendBit::PRIMARY,
+        ))
+        .ok_or_else(|| {
+            "This tool requires hardware acceleration, but no compatible graphics device was found. Use --software to render on the CPU instead."
+        })?;
+
+        let (device, queue) = block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            extensions: wgpu::Extensions {
+                anisotropic_filtering: false,
+            },
+            limits: wgpu::Limits::default(),
+        }));
+
+        Renderer::Wgpu(Rc::new(device), Rc::new(queue))
+    };
 
     if opt.swf.is_file() {
-        capture_single_swf(Rc::new(device), Rc::new(queue), &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(Rc::new(device), Rc::new(queue), &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err("Output directory is required when exporting multiple files.".into());
     }
@@ -1,17 +1,18 @@
+use ruffle_core::backend::render::filters::FilterImage;
 use ruffle_core::backend::render::{
     swf::{self, CharacterId, GradientInterpolation, GradientSpread},
-    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, JpegTagFormat, Letterbox, RenderBackend,
-    ShapeHandle, Transform,
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, JpegTagFormat, Letterbox,
+    NullBitmapSource, RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::color_transform::ColorTransform;
 use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
 use ruffle_web_common::JsResult;
 use std::collections::HashMap;
 use std::convert::TryInto;
-use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen::{Clamped, JsCast, JsValue};
 use web_sys::{
     CanvasGradient, CanvasPattern, CanvasRenderingContext2d, Element, HtmlCanvasElement,
-    HtmlImageElement, Path2d, SvgsvgElement,
+    HtmlImageElement, ImageData, Path2d, SvgsvgElement,
 };
 
 type Error = Box<dyn std::error::Error>;
@@ -22,10 +23,11 @@ pub struct WebCanvasRenderBackend {
     root_canvas: HtmlCanvasElement,
     render_targets: Vec<(HtmlCanvasElement, CanvasRenderingContext2d)>,
     cur_render_target: usize,
+    filter_stack: Vec<Vec<swf::Filter>>,
+    blend_mode_stack: Vec<swf::BlendMode>,
     color_matrix: Element,
     shapes: Vec<ShapeData>,
     bitmaps: Vec<BitmapData>,
-    id_to_bitmap: HashMap<CharacterId, BitmapHandle>,
     viewport_width: u32,
     viewport_height: u32,
     use_color_transform_hack: bool,
@@ -204,11 +206,12 @@ impl WebCanvasRenderBackend {
             root_canvas: canvas.clone(),
             render_targets,
             cur_render_target: 0,
+            filter_stack: vec![],
+            blend_mode_stack: vec![],
             color_matrix,
             context,
             shapes: vec![],
             bitmaps: vec![],
-            id_to_bitmap: HashMap::new(),
             viewport_width: 0,
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
@@ -368,11 +371,7 @@ impl WebCanvasRenderBackend {
         self.context.set_global_alpha(1.0);
     }
 
-    fn register_bitmap_pure_jpeg(
-        &mut self,
-        id: CharacterId,
-        data: &[u8],
-    ) -> Result<BitmapInfo, Error> {
+    fn register_bitmap_pure_jpeg(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
         let data = ruffle_core::backend::render::remove_invalid_jpeg_data(data);
         let mut decoder = jpeg_decoder::Decoder::new(&data[..]);
         decoder.read_info().unwrap();
@@ -389,7 +388,6 @@ impl WebCanvasRenderBackend {
             height: metadata.height.into(),
             data: jpeg_encoded,
         });
-        self.id_to_bitmap.insert(id, handle);
         Ok(BitmapInfo {
             handle,
             width: metadata.width,
@@ -397,11 +395,35 @@ impl WebCanvasRenderBackend {
         })
     }
 
-    fn register_bitmap_raw(
-        &mut self,
-        id: CharacterId,
-        bitmap: Bitmap,
-    ) -> Result<BitmapInfo, Error> {
+    /// Looks up the data of each bitmap used by the fills of a shape.
+    fn shape_bitmaps(
+        &self,
+        shape: &DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> HashMap<CharacterId, (&str, u32, u32)> {
+        use ruffle_core::shape_utils::DrawPath;
+        let mut bitmaps = HashMap::new();
+        for path in &shape.paths {
+            if let DrawPath::Fill {
+                style: swf::FillStyle::Bitmap { id, .. },
+                ..
+            } = path
+            {
+                if let Some(bitmap_data) = bitmap_source
+                    .bitmap(*id)
+                    .and_then(|bitmap| self.bitmaps.get(bitmap.handle.0))
+                {
+                    bitmaps.insert(
+                        *id,
+                        (&bitmap_data.data[..], bitmap_data.width, bitmap_data.height),
+                    );
+                }
+            }
+        }
+        bitmaps
+    }
+
+    fn push_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapInfo, Error> {
         let (width, height) = (bitmap.width, bitmap.height);
         let png = Self::bitmap_to_png_data_uri(bitmap)?;
 
@@ -416,7 +438,6 @@ impl WebCanvasRenderBackend {
             data: png,
         });
 
-        self.id_to_bitmap.insert(id, handle);
         Ok(BitmapInfo {
             handle,
             width: width.try_into().expect("JPEG dimensions too large"),
@@ -431,17 +452,14 @@ impl RenderBackend for WebCanvasRenderBackend {
         self.viewport_height = height;
     }
 
-    fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle {
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
         let handle = ShapeHandle(self.shapes.len());
 
-        let mut bitmaps = HashMap::new();
-        for (id, handle) in &self.id_to_bitmap {
-            let bitmap_data = &self.bitmaps[handle.0];
-            bitmaps.insert(
-                *id,
-                (&bitmap_data.data[..], bitmap_data.width, bitmap_data.height),
-            );
-        }
+        let bitmaps = self.shape_bitmaps(&shape, bitmap_source);
 
         let data = swf_shape_to_canvas_commands(
             &shape,
@@ -456,15 +474,13 @@ impl RenderBackend for WebCanvasRenderBackend {
         handle
     }
 
-    fn replace_shape(&mut self, shape: DistilledShape, handle: ShapeHandle) {
-        let mut bitmaps = HashMap::new();
-        for (id, handle) in &self.id_to_bitmap {
-            let bitmap_data = &self.bitmaps[handle.0];
-            bitmaps.insert(
-                *id,
-                (&bitmap_data.data[..], bitmap_data.width, bitmap_data.height),
-            );
-        }
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let bitmaps = self.shape_bitmaps(&shape, bitmap_source);
 
         let data = swf_shape_to_canvas_commands(
             &shape,
@@ -507,41 +523,41 @@ impl RenderBackend for WebCanvasRenderBackend {
             },
             shape: glyph.shape_records.clone(),
         };
-        self.register_shape((&shape).into())
+        self.register_shape((&shape).into(), &NullBitmapSource)
     }
 
     fn register_bitmap_jpeg(
         &mut self,
-        id: CharacterId,
+        _id: CharacterId,
         data: &[u8],
         jpeg_tables: Option<&[u8]>,
     ) -> Result<BitmapInfo, Error> {
         let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
-        self.register_bitmap_pure_jpeg(id, &data)
+        self.register_bitmap_pure_jpeg(&data)
     }
 
     fn register_bitmap_jpeg_2(
         &mut self,
-        id: CharacterId,
+        _id: CharacterId,
         data: &[u8],
     ) -> Result<BitmapInfo, Error> {
         if ruffle_core::backend::render::determine_jpeg_tag_format(data) == JpegTagFormat::Jpeg {
-            self.register_bitmap_pure_jpeg(id, data)
+            self.register_bitmap_pure_jpeg(data)
         } else {
             let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
-            self.register_bitmap_raw(id, bitmap)
+            self.push_bitmap(bitmap)
         }
     }
 
     fn register_bitmap_jpeg_3(
         &mut self,
-        id: swf::CharacterId,
+        _id: swf::CharacterId,
         jpeg_data: &[u8],
         alpha_data: &[u8],
     ) -> Result<BitmapInfo, Error> {
         let bitmap =
             ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
-        self.register_bitmap_raw(id, bitmap)
+        self.push_bitmap(bitmap)
     }
 
     fn register_bitmap_png(
@@ -562,7 +578,6 @@ impl RenderBackend for WebCanvasRenderBackend {
             height: swf_tag.height.into(),
             data: png,
         });
-        self.id_to_bitmap.insert(swf_tag.id, handle);
         Ok(BitmapInfo {
             handle,
             width: swf_tag.width,
@@ -570,6 +585,42 @@ impl RenderBackend for WebCanvasRenderBackend {
         })
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapInfo, Error> {
+        self.push_bitmap(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        })
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let png = Self::bitmap_to_png_data_uri(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        })?;
+        let bitmap_data = self
+            .bitmaps
+            .get_mut(bitmap.0)
+            .ok_or("update_texture: Unknown bitmap handle")?;
+        bitmap_data.image.set_src(&png);
+        bitmap_data.width = width;
+        bitmap_data.height = height;
+        bitmap_data.data = png;
+        Ok(bitmap)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         // Reset canvas transform in case it was left in a dirty state.
         self.context.reset_transform().unwrap();
@@ -736,6 +787,135 @@ impl RenderBackend for WebCanvasRenderBackend {
             .draw_image_with_html_canvas_element(&maskee_canvas, 0.0, 0.0)
             .unwrap();
     }
+
+    fn push_filters(&mut self, filters: &[swf::Filter]) {
+        // Filtered clips are drawn to their own render target, which is
+        // filtered on the CPU and then drawn onto the previous render target.
+        self.filter_stack.push(filters.to_vec());
+        self.push_render_target();
+    }
+    fn pop_filters(&mut self) {
+        let filters = match self.filter_stack.pop() {
+            Some(filters) => filters,
+            None => {
+                log::error!("Filter stack underflow");
+                return;
+            }
+        };
+        let (canvas, context) = self.pop_render_target();
+        let (width, height) = (canvas.width(), canvas.height());
+
+        match context.get_image_data(0.0, 0.0, width.into(), height.into()) {
+            Ok(image_data) => {
+                let mut image =
+                    FilterImage::from_rgba(width as usize, height as usize, &image_data.data());
+                image.apply_filters(&filters);
+                let mut data = image.to_rgba();
+                match ImageData::new_with_u8_clamped_array_and_sh(Clamped(&mut data), width, height)
+                {
+                    Ok(image_data) => context
+                        .put_image_data(&image_data, 0.0, 0.0)
+                        .warn_on_error(),
+                    Err(e) => log::warn!("Unable to create filtered image data: {:?}", e),
+                }
+            }
+            Err(e) => log::warn!("Unable to read filter render target: {:?}", e),
+        }
+
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&canvas, 0.0, 0.0)
+            .warn_on_error();
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        // Blended clips are drawn to their own render target, which is then
+        // composited onto the previous render target.
+        self.blend_mode_stack.push(blend_mode);
+        self.push_render_target();
+    }
+    fn pop_blend_mode(&mut self) {
+        let blend_mode = match self.blend_mode_stack.pop() {
+            Some(blend_mode) => blend_mode,
+            None => {
+                log::error!("Blend mode stack underflow");
+                return;
+            }
+        };
+        let (canvas, _context) = self.pop_render_target();
+
+        // The alpha and erase modes only change the transparency of the
+        // enclosing layer, which has no visible effect on the stage itself.
+        if self.cur_render_target == 0
+            && (blend_mode == swf::BlendMode::Alpha || blend_mode == swf::BlendMode::Erase)
+        {
+            return;
+        }
+
+        // TODO: Canvas has no equivalent of the subtract and invert blend modes,
+        // so these are drawn normally.
+        let composite_operation = match blend_mode {
+            swf::BlendMode::Multiply => "multiply",
+            swf::BlendMode::Screen => "screen",
+            swf::BlendMode::Lighten => "lighten",
+            swf::BlendMode::Darken => "darken",
+            swf::BlendMode::Difference => "difference",
+            swf::BlendMode::Add => "lighter",
+            swf::BlendMode::Alpha => "destination-in",
+            swf::BlendMode::Erase => "destination-out",
+            swf::BlendMode::Overlay => "overlay",
+            swf::BlendMode::HardLight => "hard-light",
+            _ => "source-over",
+        };
+        self.context.reset_transform().warn_on_error();
+        self.context
+            .set_global_composite_operation(composite_operation)
+            .warn_on_error();
+        self.context
+            .draw_image_with_html_canvas_element(&canvas, 0.0, 0.0)
+            .warn_on_error();
+        self.context
+            .set_global_composite_operation("source-over")
+            .warn_on_error();
+    }
+
+    fn render_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        draw: &mut dyn FnMut(&mut dyn RenderBackend),
+    ) -> Result<Vec<u8>, Error> {
+        if width == 0 || height == 0 {
+            return Ok(vec![]);
+        }
+
+        // Draw to a fresh render target of the requested size. Any render
+        // targets pushed while drawing will also use this size.
+        let viewport = (self.viewport_width, self.viewport_height);
+        self.viewport_width = width;
+        self.viewport_height = height;
+        self.push_render_target();
+        self.context.reset_transform().warn_on_error();
+        draw(self);
+        let (_canvas, context) = self.pop_render_target();
+        let (viewport_width, viewport_height) = viewport;
+        self.viewport_width = viewport_width;
+        self.viewport_height = viewport_height;
+
+        let image_data = context
+            .get_image_data(0.0, 0.0, width.into(), height.into())
+            .into_js_result()?;
+
+        // Canvas image data is straight alpha, but we return premultiplied pixels.
+        let mut pixels = image_data.data().0;
+        for pixel in pixels.chunks_exact_mut(4) {
+            let alpha = u16::from(pixel[3]);
+            for channel in &mut pixel[..3] {
+                *channel = (u16::from(*channel) * alpha / 255) as u8;
+            }
+        }
+        Ok(pixels)
+    }
 }
 
 #[allow(clippy::cognitive_complexity)]
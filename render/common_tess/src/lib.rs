@@ -6,6 +6,7 @@ use lyon::tessellation::{
 };
 use lyon::tessellation::{FillOptions, StrokeOptions};
 use ruffle_core::backend::render::swf::{self, FillStyle, GradientInterpolation, Twips};
+use ruffle_core::backend::render::{BitmapHandle, BitmapSource};
 use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath};
 
 pub struct ShapeTessellator {
@@ -21,10 +22,11 @@ impl ShapeTessellator {
         }
     }
 
-    pub fn tessellate_shape<F>(&mut self, shape: DistilledShape, get_bitmap_dimensions: F) -> Mesh
-    where
-        F: Fn(swf::CharacterId) -> Option<(u32, u32)>,
-    {
+    pub fn tessellate_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
         let mut mesh = Vec::new();
 
         let mut lyon_mesh: VertexBuffers<_, u32> = VertexBuffers::new();
@@ -202,6 +204,14 @@ impl ShapeTessellator {
                         is_smoothed,
                         is_repeating,
                     } => {
+                        let bitmap = match bitmap_source.bitmap(*id) {
+                            Some(bitmap) => bitmap,
+                            None => {
+                                log::error!("Couldn't fill shape with unknown bitmap {}", id);
+                                continue;
+                            }
+                        };
+
                         flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
 
                         let mut buffers_builder = BuffersBuilder::new(
@@ -219,12 +229,13 @@ impl ShapeTessellator {
                             continue;
                         }
 
-                        let (bitmap_width, bitmap_height) =
-                            (get_bitmap_dimensions)(*id).unwrap_or((1, 1));
-
                         let bitmap = Bitmap {
-                            matrix: swf_bitmap_to_gl_matrix(*matrix, bitmap_width, bitmap_height),
-                            id: *id,
+                            matrix: swf_bitmap_to_gl_matrix(
+                                *matrix,
+                                bitmap.width.into(),
+                                bitmap.height.into(),
+                            ),
+                            bitmap_handle: bitmap.handle,
                             is_smoothed: *is_smoothed,
                             is_repeating: *is_repeating,
                         };
@@ -335,7 +346,7 @@ pub struct Vertex {
 #[derive(Clone, Debug)]
 pub struct Bitmap {
     pub matrix: [[f32; 3]; 3],
-    pub id: swf::CharacterId,
+    pub bitmap_handle: BitmapHandle,
     pub is_smoothed: bool,
     pub is_repeating: bool,
 }
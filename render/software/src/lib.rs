@@ -0,0 +1,1089 @@
+//! A render backend that rasterizes entirely on the CPU into an RGBA buffer.
+//!
+//! This is useful for headless environments with no GPU available, such as
+//! exporting frames or comparing rendering output in tests.
+
+use ruffle_core::backend::render::filters::FilterImage;
+use ruffle_core::backend::render::swf::{self, FillStyle};
+use ruffle_core::backend::render::{
+    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, Letterbox,
+    NullBitmapSource, RenderBackend, ShapeHandle, Transform,
+};
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::shape_utils::DistilledShape;
+use ruffle_render_common_tess::{
+    Bitmap as BitmapDraw, Draw, DrawType, Gradient, GradientSpread, GradientType, ShapeTessellator,
+    Vertex,
+};
+
+use crate::raster::{rasterize_triangle, SamplePattern, SAMPLES_1X, SAMPLES_4X};
+
+type Error = Box<dyn std::error::Error>;
+
+mod raster;
+
+pub struct SoftwareRenderBackend {
+    shape_tessellator: ShapeTessellator,
+    meshes: Vec<Mesh>,
+    textures: Vec<(swf::CharacterId, Texture)>,
+    frame_buffer: FrameBuffer,
+
+    num_masks: u32,
+    num_masks_active: u32,
+    write_stencil_mask: u32,
+    test_stencil_mask: u32,
+    next_stencil_mask: u32,
+    mask_stack: Vec<(u32, u32)>,
+
+    /// The layers beneath the filtered or blended display objects currently
+    /// being drawn.
+    layers: Vec<Layer>,
+}
+
+impl SoftwareRenderBackend {
+    /// Creates a software renderer with 4x anti-aliasing.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_sample_count(width, height, 4)
+    }
+
+    /// Creates a software renderer with the given number of samples per pixel.
+    /// Only 1 (no anti-aliasing) and 4 samples are supported.
+    pub fn with_sample_count(width: u32, height: u32, sample_count: u32) -> Self {
+        let sample_pattern = if sample_count >= 4 {
+            SAMPLES_4X
+        } else {
+            SAMPLES_1X
+        };
+
+        Self {
+            shape_tessellator: ShapeTessellator::new(),
+            meshes: Vec::new(),
+            textures: Vec::new(),
+            frame_buffer: FrameBuffer::new(width as usize, height as usize, sample_pattern),
+
+            num_masks: 0,
+            num_masks_active: 0,
+            write_stencil_mask: 0,
+            test_stencil_mask: 0,
+            next_stencil_mask: 1,
+            mask_stack: vec![],
+            layers: vec![],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.frame_buffer.width as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.frame_buffer.height as u32
+    }
+
+    /// The pixels of the last completed frame, as non-premultiplied RGBA rows.
+    pub fn pixels(&self) -> &[u8] {
+        &self.frame_buffer.pixels
+    }
+
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
+        let mut draws = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+
+        // Convert to linear color space if this is a linear-interpolated gradient.
+        // The gradient will be converted back to sRGB after interpolation.
+        for draw in &mut draws {
+            if let DrawType::Gradient(gradient) = &mut draw.draw_type {
+                if gradient.interpolation == swf::GradientInterpolation::LinearRGB {
+                    for color in &mut gradient.colors {
+                        *color = srgb_to_linear(*color);
+                    }
+                }
+            }
+        }
+
+        Mesh { draws }
+    }
+
+    fn register_bitmap(
+        &mut self,
+        id: swf::CharacterId,
+        bitmap: Bitmap,
+    ) -> Result<BitmapInfo, Error> {
+        let data = match bitmap.data {
+            BitmapFormat::Rgb(data) => {
+                let mut rgba = Vec::with_capacity(data.len() / 3 * 4);
+                for rgb in data.chunks_exact(3) {
+                    rgba.extend_from_slice(rgb);
+                    rgba.push(255);
+                }
+                rgba
+            }
+            BitmapFormat::Rgba(data) => data,
+        };
+
+        let handle = BitmapHandle(self.textures.len());
+        self.textures.push((
+            id,
+            Texture {
+                width: bitmap.width,
+                height: bitmap.height,
+                data,
+            },
+        ));
+
+        Ok(BitmapInfo {
+            handle,
+            width: bitmap.width as u16,
+            height: bitmap.height as u16,
+        })
+    }
+
+    fn mask_mode(&self) -> MaskMode {
+        if self.num_masks == 0 {
+            MaskMode::None
+        } else if self.num_masks_active < self.num_masks {
+            MaskMode::Write(self.write_stencil_mask as u8)
+        } else {
+            MaskMode::Test(self.test_stencil_mask as u8)
+        }
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.frame_buffer = FrameBuffer::new(
+            width as usize,
+            height as usize,
+            self.frame_buffer.sample_pattern,
+        );
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = swf::Shape {
+            version: 2,
+            id: 0,
+            shape_bounds: Default::default(),
+            edge_bounds: Default::default(),
+            has_fill_winding_rule: false,
+            has_non_scaling_strokes: false,
+            has_scaling_strokes: true,
+            styles: swf::ShapeStyles {
+                fill_styles: vec![FillStyle::Color(Color {
+                    r: 255,
+                    g: 255,
+                    b: 255,
+                    a: 255,
+                })],
+                line_styles: vec![],
+            },
+            shape: glyph.shape_records.clone(),
+        };
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal((&shape).into(), &NullBitmapSource);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        id: swf::CharacterId,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(id, &data[..])
+    }
+
+    fn register_bitmap_jpeg_2(
+        &mut self,
+        id: swf::CharacterId,
+        data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
+        self.register_bitmap(id, bitmap)
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        id: swf::CharacterId,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap =
+            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        self.register_bitmap(id, bitmap)
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
+        self.register_bitmap(swf_tag.id, bitmap)
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapInfo, Error> {
+        self.register_bitmap(
+            0,
+            Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            },
+        )
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let (_id, texture) = self
+            .textures
+            .get_mut(bitmap.0)
+            .ok_or("update_texture: Unknown bitmap handle")?;
+        *texture = Texture {
+            width,
+            height,
+            data: rgba,
+        };
+        Ok(bitmap)
+    }
+
+    fn begin_frame(&mut self, clear: Color) {
+        self.num_masks = 0;
+        self.num_masks_active = 0;
+        self.write_stencil_mask = 0;
+        self.test_stencil_mask = 0;
+        self.next_stencil_mask = 1;
+        self.mask_stack.clear();
+        if let Some(layer) = self.layers.drain(..).next() {
+            self.frame_buffer = layer.frame_buffer;
+        }
+
+        let a = f32::from(clear.a) / 255.0;
+        self.frame_buffer.clear(
+            [
+                f32::from(clear.r) / 255.0 * a,
+                f32::from(clear.g) / 255.0 * a,
+                f32::from(clear.b) / 255.0 * a,
+                a,
+            ],
+            0,
+        );
+    }
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
+        if let Some((_id, texture)) = self.textures.get(bitmap.0) {
+            // Draw a unit quad textured with the bitmap, scaled to the bitmap's dimensions.
+            let quad = Draw {
+                draw_type: DrawType::Bitmap(BitmapDraw {
+                    matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                    bitmap_handle: bitmap,
+                    is_smoothed: true,
+                    is_repeating: false,
+                }),
+                vertices: [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]
+                    .iter()
+                    .map(|&position| Vertex {
+                        position,
+                        color: 0xffff_ffff,
+                    })
+                    .collect(),
+                indices: vec![0, 1, 2, 0, 2, 3],
+            };
+
+            use ruffle_core::swf::Matrix;
+            let scale_transform = Transform {
+                matrix: transform.matrix
+                    * Matrix {
+                        a: texture.width as f32,
+                        d: texture.height as f32,
+                        ..Default::default()
+                    },
+                ..*transform
+            };
+
+            let mask_mode = self.mask_mode();
+            render_draw(
+                &mut self.frame_buffer,
+                Some(texture),
+                &quad,
+                &scale_transform,
+                mask_mode,
+            );
+        }
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        let mask_mode = self.mask_mode();
+        if let Some(mesh) = self.meshes.get(shape.0) {
+            for draw in &mesh.draws {
+                let texture = match &draw.draw_type {
+                    DrawType::Bitmap(bitmap) => match self.textures.get(bitmap.bitmap_handle.0) {
+                        Some((_id, texture)) => Some(texture),
+                        None => continue,
+                    },
+                    _ => None,
+                };
+                render_draw(&mut self.frame_buffer, texture, draw, transform, mask_mode);
+            }
+        }
+    }
+
+    fn end_frame(&mut self) {
+        self.frame_buffer.resolve();
+    }
+
+    fn draw_letterbox(&mut self, letterbox: Letterbox) {
+        let black = [0.0, 0.0, 0.0, 1.0];
+        let width = self.frame_buffer.width as f32;
+        let height = self.frame_buffer.height as f32;
+        match letterbox {
+            Letterbox::None => (),
+            Letterbox::Letterbox(margin) => {
+                self.frame_buffer.fill_rect(0.0, 0.0, width, margin, black);
+                self.frame_buffer
+                    .fill_rect(0.0, height - margin, width, height, black);
+            }
+            Letterbox::Pillarbox(margin) => {
+                self.frame_buffer.fill_rect(0.0, 0.0, margin, height, black);
+                self.frame_buffer
+                    .fill_rect(width - margin, 0.0, width, height, black);
+            }
+        }
+    }
+
+    fn push_mask(&mut self) {
+        // Masks are drawn into the stencil buffer, one bit per mask, as in the GPU backends.
+        // Masks-within-masks are handled as a bitmask.
+        // This does unfortunately mean we are limited in the number of masks at once (8 bits).
+        if self.next_stencil_mask >= 0x100 {
+            // If we've reached the limit of masks, clear the stencil buffer and start over.
+            // But this may not be correct if there is still a mask active (mask-within-mask).
+            if self.test_stencil_mask != 0 {
+                log::warn!(
+                    "Too many masks active for stencil buffer; possibly incorrect rendering"
+                );
+            }
+            self.next_stencil_mask = 1;
+            self.frame_buffer
+                .clear_stencil(self.test_stencil_mask as u8);
+        }
+        self.num_masks += 1;
+        self.mask_stack
+            .push((self.write_stencil_mask, self.test_stencil_mask));
+        self.write_stencil_mask = self.next_stencil_mask;
+        self.test_stencil_mask |= self.next_stencil_mask;
+        self.next_stencil_mask <<= 1;
+    }
+
+    fn activate_mask(&mut self) {
+        self.num_masks_active += 1;
+    }
+
+    fn pop_mask(&mut self) {
+        if !self.mask_stack.is_empty() {
+            self.num_masks -= 1;
+            self.num_masks_active -= 1;
+            let (write, test) = self.mask_stack.pop().unwrap();
+            self.write_stencil_mask = write;
+            self.test_stencil_mask = test;
+        } else {
+            log::warn!("Mask stack underflow\n");
+        }
+    }
+
+    fn push_filters(&mut self, filters: &[swf::Filter]) {
+        self.push_layer(LayerEffect::Filters(filters.to_vec()));
+    }
+
+    fn pop_filters(&mut self) {
+        if let Some((effect, pixels)) = self.pop_layer() {
+            let mut image = FilterImage {
+                width: self.frame_buffer.width,
+                height: self.frame_buffer.height,
+                pixels,
+            };
+            if let LayerEffect::Filters(filters) = effect {
+                image.apply_filters(&filters);
+            }
+            let mask_mode = self.mask_mode();
+            self.frame_buffer
+                .composite(&image.pixels, mask_mode, swf::BlendMode::Normal);
+        }
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.push_layer(LayerEffect::BlendMode(blend_mode));
+    }
+
+    fn pop_blend_mode(&mut self) {
+        if let Some((effect, pixels)) = self.pop_layer() {
+            let blend_mode = match effect {
+                LayerEffect::BlendMode(blend_mode) => blend_mode,
+                LayerEffect::Filters(_) | LayerEffect::Offscreen => swf::BlendMode::Normal,
+            };
+            // The alpha and erase modes only change the transparency of the
+            // enclosing layer, which has no visible effect on the stage itself.
+            if self.layers.is_empty()
+                && (blend_mode == swf::BlendMode::Alpha || blend_mode == swf::BlendMode::Erase)
+            {
+                return;
+            }
+            let mask_mode = self.mask_mode();
+            self.frame_buffer.composite(&pixels, mask_mode, blend_mode);
+        }
+    }
+
+    fn render_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        draw: &mut dyn FnMut(&mut dyn RenderBackend),
+    ) -> Result<Vec<u8>, Error> {
+        self.push_layer(LayerEffect::Offscreen);
+        self.frame_buffer = FrameBuffer::new(
+            width as usize,
+            height as usize,
+            self.frame_buffer.sample_pattern,
+        );
+        draw(self);
+        let (_effect, pixels) = self
+            .pop_layer()
+            .ok_or("render_offscreen: Layer stack underflow")?;
+        Ok(pixels
+            .iter()
+            .flat_map(|color| color.iter().map(|&n| to_u8(n)))
+            .collect())
+    }
+}
+
+impl SoftwareRenderBackend {
+    /// Redirects drawing to a new transparent layer.
+    fn push_layer(&mut self, effect: LayerEffect) {
+        let frame_buffer = FrameBuffer::new(
+            self.frame_buffer.width,
+            self.frame_buffer.height,
+            self.frame_buffer.sample_pattern,
+        );
+        self.layers.push(Layer {
+            frame_buffer: std::mem::replace(&mut self.frame_buffer, frame_buffer),
+            effect,
+            num_masks: self.num_masks,
+            num_masks_active: self.num_masks_active,
+            write_stencil_mask: self.write_stencil_mask,
+            test_stencil_mask: self.test_stencil_mask,
+            next_stencil_mask: self.next_stencil_mask,
+            mask_stack: std::mem::replace(&mut self.mask_stack, vec![]),
+        });
+
+        // The layer has its own stencil buffer, so masks start over.
+        self.num_masks = 0;
+        self.num_masks_active = 0;
+        self.write_stencil_mask = 0;
+        self.test_stencil_mask = 0;
+        self.next_stencil_mask = 1;
+    }
+
+    /// Restores the frame buffer beneath the current layer, returning the
+    /// effect of the layer and its resolved premultiplied pixels.
+    fn pop_layer(&mut self) -> Option<(LayerEffect, Vec<[f32; 4]>)> {
+        let layer = match self.layers.pop() {
+            Some(layer) => layer,
+            None => {
+                log::warn!("Layer stack underflow");
+                return None;
+            }
+        };
+
+        let pixels = self.frame_buffer.resolve_premultiplied();
+        self.frame_buffer = layer.frame_buffer;
+        self.num_masks = layer.num_masks;
+        self.num_masks_active = layer.num_masks_active;
+        self.write_stencil_mask = layer.write_stencil_mask;
+        self.test_stencil_mask = layer.test_stencil_mask;
+        self.next_stencil_mask = layer.next_stencil_mask;
+        self.mask_stack = layer.mask_stack;
+        Some((layer.effect, pixels))
+    }
+}
+
+/// What happens to a layer when it is composited onto the layer beneath it.
+enum LayerEffect {
+    Filters(Vec<swf::Filter>),
+    BlendMode(swf::BlendMode),
+    /// The layer is read back by `render_offscreen` instead of being composited.
+    Offscreen,
+}
+
+/// The state of the frame buffer beneath a filtered or blended display object.
+struct Layer {
+    frame_buffer: FrameBuffer,
+    effect: LayerEffect,
+    num_masks: u32,
+    num_masks_active: u32,
+    write_stencil_mask: u32,
+    test_stencil_mask: u32,
+    next_stencil_mask: u32,
+    mask_stack: Vec<(u32, u32)>,
+}
+
+struct Mesh {
+    draws: Vec<Draw>,
+}
+
+/// Bitmap data with premultiplied alpha.
+struct Texture {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Texture {
+    /// Samples the texture at the given UV coordinates, returning a premultiplied color.
+    fn sample(&self, u: f32, v: f32, is_smoothed: bool, is_repeating: bool) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !is_smoothed {
+            return self.texel(x.floor() as i64, y.floor() as i64, is_repeating);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let top = lerp(
+            self.texel(x0, y0, is_repeating),
+            self.texel(x0 + 1, y0, is_repeating),
+            fx,
+        );
+        let bottom = lerp(
+            self.texel(x0, y0 + 1, is_repeating),
+            self.texel(x0 + 1, y0 + 1, is_repeating),
+            fx,
+        );
+        lerp(top, bottom, fy)
+    }
+
+    fn texel(&self, x: i64, y: i64, is_repeating: bool) -> [f32; 4] {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        let (x, y) = if is_repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.max(0).min(width - 1), y.max(0).min(height - 1))
+        };
+        let i = ((y * width + x) * 4) as usize;
+        let texel = &self.data[i..i + 4];
+        [
+            f32::from(texel[0]) / 255.0,
+            f32::from(texel[1]) / 255.0,
+            f32::from(texel[2]) / 255.0,
+            f32::from(texel[3]) / 255.0,
+        ]
+    }
+}
+
+/// How a draw interacts with the stencil buffer.
+#[derive(Copy, Clone, Debug)]
+enum MaskMode {
+    /// No masks are active.
+    None,
+
+    /// A masker is being drawn; covered samples have the given stencil bits set,
+    /// and no color is written.
+    Write(u8),
+
+    /// Masks are active; only samples with all of the given stencil bits set are drawn.
+    Test(u8),
+}
+
+/// A multisampled color and stencil buffer.
+struct FrameBuffer {
+    width: usize,
+    height: usize,
+    sample_pattern: SamplePattern,
+
+    /// Premultiplied colors, `sample_pattern.len()` per pixel.
+    colors: Vec<[f32; 4]>,
+    stencil: Vec<u8>,
+
+    /// The resolved, non-premultiplied RGBA output.
+    pixels: Vec<u8>,
+}
+
+impl FrameBuffer {
+    fn new(width: usize, height: usize, sample_pattern: SamplePattern) -> Self {
+        let num_samples = width * height * sample_pattern.len();
+        Self {
+            width,
+            height,
+            sample_pattern,
+            colors: vec![[0.0; 4]; num_samples],
+            stencil: vec![0; num_samples],
+            pixels: vec![0; width * height * 4],
+        }
+    }
+
+    fn clear(&mut self, color: [f32; 4], stencil: u8) {
+        for sample in &mut self.colors {
+            *sample = color;
+        }
+        self.clear_stencil(stencil);
+    }
+
+    fn clear_stencil(&mut self, stencil: u8) {
+        for sample in &mut self.stencil {
+            *sample = stencil;
+        }
+    }
+
+    /// Fills the pixels whose centers lie in the given rectangle, ignoring masks.
+    fn fill_rect(&mut self, left: f32, top: f32, right: f32, bottom: f32, color: [f32; 4]) {
+        let num_samples = self.sample_pattern.len();
+        let clamp = |n: f32, max: usize| (n.round().max(0.0) as usize).min(max);
+        let (left, right) = (clamp(left, self.width), clamp(right, self.width));
+        let (top, bottom) = (clamp(top, self.height), clamp(bottom, self.height));
+        for y in top..bottom {
+            let row = (y * self.width + left) * num_samples;
+            let row_end = (y * self.width + right) * num_samples;
+            for sample in &mut self.colors[row..row_end] {
+                *sample = color;
+            }
+        }
+    }
+
+    /// Averages the samples of each pixel, keeping premultiplied alpha.
+    fn resolve_premultiplied(&self) -> Vec<[f32; 4]> {
+        let num_samples = self.sample_pattern.len();
+        self.colors
+            .chunks_exact(num_samples)
+            .map(|samples| {
+                let mut sum = [0.0; 4];
+                for sample in samples {
+                    for (total, component) in sum.iter_mut().zip(sample.iter()) {
+                        *total += component;
+                    }
+                }
+                for total in &mut sum {
+                    *total /= num_samples as f32;
+                }
+                sum
+            })
+            .collect()
+    }
+
+    /// Blends one premultiplied color per pixel onto every sample of the pixel.
+    fn composite(&mut self, pixels: &[[f32; 4]], mask_mode: MaskMode, blend_mode: swf::BlendMode) {
+        let num_samples = self.sample_pattern.len();
+        for ((color, samples), stencil) in pixels
+            .iter()
+            .zip(self.colors.chunks_exact_mut(num_samples))
+            .zip(self.stencil.chunks_exact_mut(num_samples))
+        {
+            // Transparent pixels leave the destination unchanged, except in
+            // the alpha mode, where they make it transparent.
+            if color[3] <= 0.0 && blend_mode != swf::BlendMode::Alpha {
+                continue;
+            }
+            for (sample, stencil) in samples.iter_mut().zip(stencil.iter_mut()) {
+                match mask_mode {
+                    MaskMode::None => (),
+                    MaskMode::Write(bits) => {
+                        *stencil |= bits;
+                        continue;
+                    }
+                    MaskMode::Test(bits) => {
+                        if *stencil & bits != bits {
+                            continue;
+                        }
+                    }
+                }
+                *sample = blend(*color, *sample, blend_mode);
+            }
+        }
+    }
+
+    /// Averages the samples of each pixel into the output buffer.
+    fn resolve(&mut self) {
+        let num_samples = self.sample_pattern.len();
+        for (pixel, samples) in self
+            .pixels
+            .chunks_exact_mut(4)
+            .zip(self.colors.chunks_exact(num_samples))
+        {
+            let mut sum = [0.0; 4];
+            for sample in samples {
+                for (total, component) in sum.iter_mut().zip(sample.iter()) {
+                    *total += component;
+                }
+            }
+            let a = sum[3] / num_samples as f32;
+            if a > 0.0 {
+                for i in 0..3 {
+                    pixel[i] = to_u8(sum[i] / num_samples as f32 / a);
+                }
+            } else {
+                pixel[0] = 0;
+                pixel[1] = 0;
+                pixel[2] = 0;
+            }
+            pixel[3] = to_u8(a);
+        }
+    }
+}
+
+/// Blends a premultiplied source color onto a premultiplied destination color.
+///
+/// The separable modes follow the W3C compositing specification; the
+/// remaining modes match the behavior of the Flash Player.
+fn blend(src: [f32; 4], dst: [f32; 4], blend_mode: swf::BlendMode) -> [f32; 4] {
+    use swf::BlendMode;
+    let (sa, da) = (src[3], dst[3]);
+    let separable = |f: fn(f32, f32) -> f32| {
+        let mut out = [0.0, 0.0, 0.0, sa + da - sa * da];
+        for i in 0..3 {
+            let cs = if sa > 0.0 { src[i] / sa } else { 0.0 };
+            let cd = if da > 0.0 { dst[i] / da } else { 0.0 };
+            out[i] = src[i] * (1.0 - da) + dst[i] * (1.0 - sa) + sa * da * f(cs, cd);
+        }
+        out
+    };
+    fn hard_light(cs: f32, cd: f32) -> f32 {
+        if cs <= 0.5 {
+            2.0 * cs * cd
+        } else {
+            let cs = 2.0 * cs - 1.0;
+            cs + cd - cs * cd
+        }
+    }
+    match blend_mode {
+        BlendMode::Normal | BlendMode::Layer => {
+            let mut out = dst;
+            for (out, src) in out.iter_mut().zip(src.iter()) {
+                *out = src + *out * (1.0 - sa);
+            }
+            out
+        }
+        BlendMode::Multiply => separable(|cs, cd| cs * cd),
+        BlendMode::Screen => separable(|cs, cd| cs + cd - cs * cd),
+        BlendMode::Lighten => separable(|cs, cd| cs.max(cd)),
+        BlendMode::Darken => separable(|cs, cd| cs.min(cd)),
+        BlendMode::Difference => separable(|cs, cd| (cs - cd).abs()),
+        BlendMode::Overlay => separable(|cs, cd| hard_light(cd, cs)),
+        BlendMode::HardLight => separable(hard_light),
+        BlendMode::Add => [
+            (src[0] + dst[0]).min(1.0),
+            (src[1] + dst[1]).min(1.0),
+            (src[2] + dst[2]).min(1.0),
+            sa + da - sa * da,
+        ],
+        BlendMode::Subtract => [
+            (dst[0] - src[0]).max(0.0),
+            (dst[1] - src[1]).max(0.0),
+            (dst[2] - src[2]).max(0.0),
+            sa + da - sa * da,
+        ],
+        BlendMode::Invert => [
+            dst[0] * (1.0 - sa) + sa * (da - dst[0]),
+            dst[1] * (1.0 - sa) + sa * (da - dst[1]),
+            dst[2] * (1.0 - sa) + sa * (da - dst[2]),
+            da,
+        ],
+        BlendMode::Alpha => [dst[0] * sa, dst[1] * sa, dst[2] * sa, da * sa],
+        BlendMode::Erase => {
+            let keep = 1.0 - sa;
+            [dst[0] * keep, dst[1] * keep, dst[2] * keep, da * keep]
+        }
+    }
+}
+
+/// Rasterizes a single tessellated draw into the frame buffer.
+///
+/// `texture` is the bitmap sampled by bitmap fills; it is ignored for other draws.
+fn render_draw(
+    frame_buffer: &mut FrameBuffer,
+    texture: Option<&Texture>,
+    draw: &Draw,
+    transform: &Transform,
+    mask_mode: MaskMode,
+) {
+    let matrix = &transform.matrix;
+    let tx = matrix.tx.to_pixels() as f32;
+    let ty = matrix.ty.to_pixels() as f32;
+    let to_screen = |position: [f32; 2]| {
+        [
+            matrix.a * position[0] + matrix.c * position[1] + tx,
+            matrix.b * position[0] + matrix.d * position[1] + ty,
+        ]
+    };
+
+    // The inverse world matrix, used to find the shape-space position of a pixel
+    // when sampling gradients and bitmaps.
+    let det = matrix.a * matrix.d - matrix.b * matrix.c;
+    if det == 0.0 {
+        // This draw is squashed flat and can't be seen.
+        return;
+    }
+    let to_local = |x: f32, y: f32| {
+        let (x, y) = (x - tx, y - ty);
+        [
+            (matrix.d * x - matrix.c * y) / det,
+            (matrix.a * y - matrix.b * x) / det,
+        ]
+    };
+
+    if texture.is_none() && matches!(draw.draw_type, DrawType::Bitmap(_)) {
+        return;
+    }
+
+    let color_transform = &transform.color_transform;
+    let width = frame_buffer.width;
+    let height = frame_buffer.height;
+    let sample_pattern = frame_buffer.sample_pattern;
+    let num_samples = sample_pattern.len();
+
+    for triangle in draw.indices.chunks_exact(3) {
+        let vertices = [
+            &draw.vertices[triangle[0] as usize],
+            &draw.vertices[triangle[1] as usize],
+            &draw.vertices[triangle[2] as usize],
+        ];
+        let points = [
+            to_screen(vertices[0].position),
+            to_screen(vertices[1].position),
+            to_screen(vertices[2].position),
+        ];
+
+        rasterize_triangle(points, width, height, sample_pattern, |x, y, coverage| {
+            let first_sample = (y * width + x) * num_samples;
+            let stencil = &mut frame_buffer.stencil[first_sample..first_sample + num_samples];
+
+            let coverage = match mask_mode {
+                MaskMode::None => coverage,
+                MaskMode::Write(bits) => {
+                    for (i, sample) in stencil.iter_mut().enumerate() {
+                        if coverage & (1 << i) != 0 {
+                            *sample |= bits;
+                        }
+                    }
+                    return;
+                }
+                MaskMode::Test(bits) => {
+                    let mut coverage = coverage;
+                    for (i, sample) in stencil.iter().enumerate() {
+                        if *sample & bits != bits {
+                            coverage &= !(1 << i);
+                        }
+                    }
+                    coverage
+                }
+            };
+            if coverage == 0 {
+                return;
+            }
+
+            // Shade once per pixel at the pixel center.
+            let color = match &draw.draw_type {
+                DrawType::Color => {
+                    let color = vertices[0].color;
+                    premultiply(apply_color_transform(
+                        [
+                            (color & 0xff) as f32 / 255.0,
+                            ((color >> 8) & 0xff) as f32 / 255.0,
+                            ((color >> 16) & 0xff) as f32 / 255.0,
+                            ((color >> 24) & 0xff) as f32 / 255.0,
+                        ],
+                        color_transform,
+                    ))
+                }
+                DrawType::Gradient(gradient) => {
+                    let position = to_local(x as f32 + 0.5, y as f32 + 0.5);
+                    let uv = transform_uv(&gradient.matrix, position);
+                    premultiply(apply_color_transform(
+                        sample_gradient(gradient, uv),
+                        color_transform,
+                    ))
+                }
+                DrawType::Bitmap(bitmap) => {
+                    let position = to_local(x as f32 + 0.5, y as f32 + 0.5);
+                    let uv = transform_uv(&bitmap.matrix, position);
+                    let color = texture.unwrap().sample(
+                        uv[0],
+                        uv[1],
+                        bitmap.is_smoothed,
+                        bitmap.is_repeating,
+                    );
+
+                    // Unmultiply alpha before applying the color transform.
+                    if color[3] > 0.0 {
+                        let a = color[3];
+                        premultiply(apply_color_transform(
+                            [color[0] / a, color[1] / a, color[2] / a, a],
+                            color_transform,
+                        ))
+                    } else {
+                        color
+                    }
+                }
+            };
+
+            let colors = &mut frame_buffer.colors[first_sample..first_sample + num_samples];
+            for (i, sample) in colors.iter_mut().enumerate() {
+                if coverage & (1 << i) != 0 {
+                    let inverse_alpha = 1.0 - color[3];
+                    for (dst, src) in sample.iter_mut().zip(color.iter()) {
+                        *dst = src + *dst * inverse_alpha;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Transforms a shape-space position into texture coordinates using a
+/// column-major texture matrix built by the tessellator.
+fn transform_uv(matrix: &[[f32; 3]; 3], position: [f32; 2]) -> [f32; 2] {
+    [
+        matrix[0][0] * position[0] + matrix[1][0] * position[1] + matrix[2][0],
+        matrix[0][1] * position[0] + matrix[1][1] * position[1] + matrix[2][1],
+    ]
+}
+
+/// Returns the non-premultiplied color of the gradient at the given texture coordinates.
+fn sample_gradient(gradient: &Gradient, uv: [f32; 2]) -> [f32; 4] {
+    let t = match gradient.gradient_type {
+        GradientType::Linear => uv[0],
+        GradientType::Radial => {
+            let (x, y) = (uv[0] * 2.0 - 1.0, uv[1] * 2.0 - 1.0);
+            (x * x + y * y).sqrt()
+        }
+        GradientType::Focal => {
+            let focal_point = gradient.focal_point;
+            let (x, y) = (uv[0] * 2.0 - 1.0, uv[1] * 2.0 - 1.0);
+            let (dx, dy) = (focal_point - x, -y);
+            let l = (dx * dx + dy * dy).sqrt();
+            if l > 0.0 {
+                let (dx, dy) = (dx / l, dy / l);
+                l / ((1.0 - focal_point * focal_point * dy * dy).sqrt() + focal_point * dx)
+            } else {
+                0.0
+            }
+        }
+    };
+
+    let t = match gradient.repeat_mode {
+        GradientSpread::Pad => t.max(0.0).min(1.0),
+        GradientSpread::Repeat => t - t.floor(),
+        GradientSpread::Reflect => {
+            let t = t.abs();
+            if (t % 2.0) as i32 == 0 {
+                t.fract()
+            } else {
+                1.0 - t.fract()
+            }
+        }
+    };
+
+    let ratios = &gradient.ratios;
+    let colors = &gradient.colors;
+    let color = if ratios.is_empty() {
+        [0.0; 4]
+    } else if t <= ratios[0] {
+        colors[0]
+    } else if let Some(i) = ratios.iter().position(|&ratio| t <= ratio) {
+        let a = (t - ratios[i - 1]) / (ratios[i] - ratios[i - 1]);
+        lerp(colors[i - 1], colors[i], a)
+    } else {
+        colors[colors.len() - 1]
+    };
+
+    if gradient.interpolation == swf::GradientInterpolation::LinearRGB {
+        [
+            linear_to_srgb(color[0]),
+            linear_to_srgb(color[1]),
+            linear_to_srgb(color[2]),
+            color[3],
+        ]
+    } else {
+        color
+    }
+}
+
+fn linear_to_srgb(n: f32) -> f32 {
+    if n < 0.003_130_8 {
+        n * 12.92
+    } else {
+        1.055 * n.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Applies a color transform to a non-premultiplied color, clamping the result.
+fn apply_color_transform(color: [f32; 4], transform: &ColorTransform) -> [f32; 4] {
+    let clamp = |n: f32| n.max(0.0).min(1.0);
+    [
+        clamp(color[0] * transform.r_mult + transform.r_add),
+        clamp(color[1] * transform.g_mult + transform.g_add),
+        clamp(color[2] * transform.b_mult + transform.b_add),
+        clamp(color[3] * transform.a_mult + transform.a_add),
+    ]
+}
+
+fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+fn lerp(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+fn to_u8(n: f32) -> u8 {
+    (n.max(0.0).min(1.0) * 255.0).round() as u8
+}
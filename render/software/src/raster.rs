@@ -0,0 +1,105 @@
+//! Triangle scan conversion for the software renderer.
+
+use std::cmp::Ordering;
+
+/// Vertex positions are snapped to a grid with this many subpixels per pixel.
+/// This keeps edge tests exact, so two triangles sharing an edge never both
+/// cover (or both miss) a sample lying on it.
+const SUBPIXEL_BITS: u32 = 8;
+const SUBPIXEL_SCALE: f32 = (1 << SUBPIXEL_BITS) as f32;
+
+/// Coordinates are clamped to this many pixels away from the origin to avoid
+/// overflowing the fixed point edge equations.
+const MAX_COORDINATE: f32 = (1 << 20) as f32;
+
+/// The positions of the samples inside of a pixel, in subpixel units relative
+/// to the top-left corner of the pixel.
+pub type SamplePattern = &'static [(i64, i64)];
+
+/// A single sample at the center of the pixel (no anti-aliasing).
+pub const SAMPLES_1X: SamplePattern = &[(128, 128)];
+
+/// The standard rotated grid pattern used by 4x MSAA.
+pub const SAMPLES_4X: SamplePattern = &[(96, 32), (224, 96), (32, 160), (160, 224)];
+
+/// Calls `f(x, y, coverage)` for every pixel touched by the given triangle,
+/// where bit `i` of `coverage` is set if sample `i` of `samples` lies inside the triangle.
+///
+/// Samples that lie exactly on an edge are only covered by one of the two triangles
+/// sharing that edge, so adjacent triangles of a tessellated shape never overlap.
+pub fn rasterize_triangle<F>(
+    points: [[f32; 2]; 3],
+    width: usize,
+    height: usize,
+    samples: SamplePattern,
+    mut f: F,
+) where
+    F: FnMut(usize, usize, u32),
+{
+    let mut v = [(0, 0); 3];
+    for (out, point) in v.iter_mut().zip(points.iter()) {
+        if !point[0].is_finite() || !point[1].is_finite() {
+            return;
+        }
+        *out = (to_fixed(point[0]), to_fixed(point[1]));
+    }
+
+    let area = edge_function(v[0], v[1], v[2]);
+    match area.cmp(&0) {
+        // Degenerate triangle.
+        Ordering::Equal => return,
+        Ordering::Less => v.swap(1, 2),
+        Ordering::Greater => (),
+    }
+
+    // Edges that own their boundary samples get a bias of 1, so that `e + bias > 0`
+    // becomes an inclusive test for them and an exclusive test for the others.
+    let edges = [(v[1], v[2]), (v[2], v[0]), (v[0], v[1])];
+    let mut biases = [0; 3];
+    for (bias, &(a, b)) in biases.iter_mut().zip(edges.iter()) {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        if dy > 0 || (dy == 0 && dx < 0) {
+            *bias = 1;
+        }
+    }
+
+    let min_x = v.iter().map(|p| p.0).min().unwrap() >> SUBPIXEL_BITS;
+    let max_x = v.iter().map(|p| p.0).max().unwrap() >> SUBPIXEL_BITS;
+    let min_y = v.iter().map(|p| p.1).min().unwrap() >> SUBPIXEL_BITS;
+    let max_y = v.iter().map(|p| p.1).max().unwrap() >> SUBPIXEL_BITS;
+
+    let min_x = min_x.max(0);
+    let min_y = min_y.max(0);
+    let max_x = max_x.min(width as i64 - 1);
+    let max_y = max_y.min(height as i64 - 1);
+
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let mut coverage = 0;
+            for (i, &(sample_x, sample_y)) in samples.iter().enumerate() {
+                let p = (
+                    (x << SUBPIXEL_BITS) + sample_x,
+                    (y << SUBPIXEL_BITS) + sample_y,
+                );
+                let inside = edges
+                    .iter()
+                    .zip(biases.iter())
+                    .all(|(&(a, b), &bias)| edge_function(a, b, p) + bias > 0);
+                if inside {
+                    coverage |= 1 << i;
+                }
+            }
+            if coverage != 0 {
+                f(x as usize, y as usize, coverage);
+            }
+        }
+    }
+}
+
+fn to_fixed(n: f32) -> i64 {
+    (n.max(-MAX_COORDINATE).min(MAX_COORDINATE) * SUBPIXEL_SCALE).round() as i64
+}
+
+fn edge_function(a: (i64, i64), b: (i64, i64), p: (i64, i64)) -> i64 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
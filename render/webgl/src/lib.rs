@@ -1,7 +1,7 @@
 use ruffle_core::backend::render::swf::{self, FillStyle};
 use ruffle_core::backend::render::{
-    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
-    RenderBackend, ShapeHandle, Transform,
+    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, Letterbox,
+    NullBitmapSource, RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_render_common_tess::{GradientSpread, GradientType, ShapeTessellator, Vertex};
@@ -57,6 +57,8 @@ pub struct WebGlRenderBackend {
     active_program: *const ShaderProgram,
     mask_state_dirty: bool,
     blend_func: (u32, u32),
+    blend_equation: u32,
+    blend_modes: Vec<swf::BlendMode>,
     mult_color: Option<[f32; 4]>,
     add_color: Option<[f32; 4]>,
 
@@ -185,6 +187,8 @@ impl WebGlRenderBackend {
             active_program: std::ptr::null(),
             mask_state_dirty: true,
             blend_func: (Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
+            blend_equation: Gl::FUNC_ADD,
+            blend_modes: vec![],
             mult_color: None,
             add_color: None,
         };
@@ -280,8 +284,7 @@ impl WebGlRenderBackend {
             draws: vec![Draw {
                 draw_type: DrawType::Bitmap(BitmapDraw {
                     matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
-                    id: 0,
-
+                    handle: BitmapHandle(0),
                     is_smoothed: true,
                     is_repeating: false,
                 }),
@@ -422,16 +425,16 @@ impl WebGlRenderBackend {
         Ok(())
     }
 
-    fn register_shape_internal(&mut self, shape: DistilledShape) -> Mesh {
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
         use ruffle_render_common_tess::DrawType as TessDrawType;
 
-        let textures = &self.textures;
-        let lyon_mesh = self.shape_tessellator.tessellate_shape(shape, |id| {
-            textures
-                .iter()
-                .find(|(other_id, _tex)| *other_id == id)
-                .map(|tex| (tex.1.width, tex.1.height))
-        });
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
 
         let mut draws = Vec::with_capacity(lyon_mesh.len());
 
@@ -527,7 +530,7 @@ impl WebGlRenderBackend {
                     Draw {
                         draw_type: DrawType::Bitmap(BitmapDraw {
                             matrix: bitmap.matrix,
-                            id: bitmap.id,
+                            handle: bitmap.bitmap_handle,
                             is_smoothed: bitmap.is_smoothed,
                             is_repeating: bitmap.is_repeating,
                         }),
@@ -719,15 +722,24 @@ impl RenderBackend for WebGlRenderBackend {
         self.build_matrices();
     }
 
-    fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle {
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
         let handle = ShapeHandle(self.meshes.len());
-        let mesh = self.register_shape_internal(shape);
+        let mesh = self.register_shape_internal(shape, bitmap_source);
         self.meshes.push(mesh);
         handle
     }
 
-    fn replace_shape(&mut self, shape: DistilledShape, handle: ShapeHandle) {
-        let mesh = self.register_shape_internal(shape);
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, bitmap_source);
         self.meshes[handle.0] = mesh;
     }
 
@@ -752,7 +764,7 @@ impl RenderBackend for WebGlRenderBackend {
             shape: glyph.shape_records.clone(),
         };
         let handle = ShapeHandle(self.meshes.len());
-        let mesh = self.register_shape_internal((&shape).into());
+        let mesh = self.register_shape_internal((&shape).into(), &NullBitmapSource);
         self.meshes.push(mesh);
         handle
     }
@@ -795,12 +807,59 @@ impl RenderBackend for WebGlRenderBackend {
         self.register_bitmap(swf_tag.id, bitmap)
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapInfo, Error> {
+        self.register_bitmap(
+            0,
+            Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            },
+        )
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let (_id, texture) = self
+            .textures
+            .get_mut(bitmap.0)
+            .ok_or("update_texture: Unknown bitmap handle")?;
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture.texture));
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                Gl::RGBA as i32,
+                width as i32,
+                height as i32,
+                0,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&rgba),
+            )
+            .into_js_result()?;
+        texture.width = width;
+        texture.height = height;
+        Ok(bitmap)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         self.num_masks = 0;
         self.num_masks_active = 0;
         self.write_stencil_mask = 0;
         self.test_stencil_mask = 0;
         self.next_stencil_mask = 1;
+        self.blend_modes.clear();
 
         self.active_program = std::ptr::null();
         self.mask_state_dirty = true;
@@ -851,6 +910,10 @@ impl RenderBackend for WebGlRenderBackend {
 
             // Render the resolved framebuffer texture to a quad on the screen.
             gl.bind_framebuffer(Gl2::FRAMEBUFFER, None);
+            self.gl.blend_equation(Gl::FUNC_ADD);
+            self.gl.blend_func(Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+            self.blend_equation = Gl::FUNC_ADD;
+            self.blend_func = (Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
             let program = &self.bitmap_program;
             self.gl.use_program(Some(&program.program));
 
@@ -906,14 +969,19 @@ impl RenderBackend for WebGlRenderBackend {
         // TODO: Might be better to make this separate code to render the bitmap
         // instead of going through render_shape. But render_shape already handles
         // masking etc.
-        if let Some((id, bitmap)) = self.textures.get(bitmap.0) {
+        let handle = bitmap;
+        if let Some((_id, bitmap)) = self.textures.get(handle.0) {
             // Adjust the quad draw to use the target bitmap.
             let mesh = &mut self.meshes[self.quad_shape.0];
             let draw = &mut mesh.draws[0];
             let width = bitmap.width as f32;
             let height = bitmap.height as f32;
-            if let DrawType::Bitmap(BitmapDraw { id: draw_id, .. }) = &mut draw.draw_type {
-                *draw_id = *id;
+            if let DrawType::Bitmap(BitmapDraw {
+                handle: draw_handle,
+                ..
+            }) = &mut draw.draw_type
+            {
+                *draw_handle = handle;
             }
 
             // Scale the quad to the bitmap's dimensions.
@@ -960,22 +1028,40 @@ impl RenderBackend for WebGlRenderBackend {
             transform.color_transform.a_add,
         ];
 
+        let blend_mode = self
+            .blend_modes
+            .last()
+            .copied()
+            .unwrap_or(swf::BlendMode::Normal);
+        // Without offscreen layers, the alpha and erase modes would change the
+        // transparency of the stage itself, which has no visible effect.
+        if blend_mode == swf::BlendMode::Alpha || blend_mode == swf::BlendMode::Erase {
+            return;
+        }
+
         self.set_stencil_state();
 
         let mesh = &self.meshes[shape.0];
         for draw in &mesh.draws {
             self.bind_vertex_array(Some(&draw.vao));
 
-            let (program, src_blend, dst_blend) = match &draw.draw_type {
-                DrawType::Color => (&self.color_program, Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
-                DrawType::Gradient(_) => (
-                    &self.gradient_program,
-                    Gl::SRC_ALPHA,
-                    Gl::ONE_MINUS_SRC_ALPHA,
-                ),
+            let (program, premultiplied) = match &draw.draw_type {
+                DrawType::Color => (&self.color_program, false),
+                DrawType::Gradient(_) => (&self.gradient_program, false),
                 // Bitmaps use pre-multiplied alpha.
-                DrawType::Bitmap { .. } => (&self.bitmap_program, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA),
+                DrawType::Bitmap { .. } => (&self.bitmap_program, true),
             };
+            let (equation, src_blend, dst_blend) =
+                blend_state(blend_mode, premultiplied, self.gl2.is_some());
+            if (src_blend, dst_blend) != self.blend_func || equation != self.blend_equation {
+                self.gl.blend_equation(equation);
+                // Alpha is always accumulated as premultiplied "over", so that
+                // offscreen renders have a meaningful alpha channel.
+                self.gl
+                    .blend_func_separate(src_blend, dst_blend, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+                self.blend_equation = equation;
+                self.blend_func = (src_blend, dst_blend);
+            }
 
             // Set common render state, while minimizing unnecessary state changes.
             // TODO: Using designated layout specifiers in WebGL2/OpenGL ES 3, we could guarantee that uniforms
@@ -988,11 +1074,6 @@ impl RenderBackend for WebGlRenderBackend {
 
                 self.mult_color = None;
                 self.add_color = None;
-
-                if (src_blend, dst_blend) != self.blend_func {
-                    self.gl.blend_func(src_blend, dst_blend);
-                    self.blend_func = (src_blend, dst_blend);
-                }
             }
 
             program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
@@ -1045,12 +1126,7 @@ impl RenderBackend for WebGlRenderBackend {
                     );
                 }
                 DrawType::Bitmap(bitmap) => {
-                    let texture = &self
-                        .textures
-                        .iter()
-                        .find(|(id, _tex)| *id == bitmap.id)
-                        .unwrap()
-                        .1;
+                    let texture = &self.textures[bitmap.handle.0].1;
 
                     program.uniform_matrix3fv(
                         &self.gl,
@@ -1180,6 +1256,190 @@ impl RenderBackend for WebGlRenderBackend {
             log::warn!("Mask stack underflow\n");
         }
     }
+
+    // TODO: Filters are not supported yet, so filtered clips are drawn as is.
+    fn push_filters(&mut self, _filters: &[swf::Filter]) {}
+    fn pop_filters(&mut self) {}
+
+    // Blend modes are applied to each shape as it is drawn, rather than to an
+    // offscreen layer holding the whole clip, so overlapping shapes within a
+    // blended clip also blend with each other.
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.blend_modes.push(blend_mode);
+    }
+    fn pop_blend_mode(&mut self) {
+        if self.blend_modes.pop().is_none() {
+            log::warn!("Blend mode stack underflow");
+        }
+    }
+
+    fn render_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        draw: &mut dyn FnMut(&mut dyn RenderBackend),
+    ) -> Result<Vec<u8>, Error> {
+        if width == 0 || height == 0 {
+            return Ok(vec![]);
+        }
+
+        // Create a framebuffer with an RGBA texture and a stencil buffer to draw into.
+        let texture = self.gl.create_texture().ok_or("Unable to create texture")?;
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                Gl::RGBA as i32,
+                width as i32,
+                height as i32,
+                0,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                None,
+            )
+            .into_js_result()?;
+
+        let stencil_renderbuffer = self
+            .gl
+            .create_renderbuffer()
+            .ok_or("Unable to create renderbuffer")?;
+        self.gl
+            .bind_renderbuffer(Gl::RENDERBUFFER, Some(&stencil_renderbuffer));
+        self.gl.renderbuffer_storage(
+            Gl::RENDERBUFFER,
+            Gl::STENCIL_INDEX8,
+            width as i32,
+            height as i32,
+        );
+
+        let framebuffer = self
+            .gl
+            .create_framebuffer()
+            .ok_or("Unable to create framebuffer")?;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        self.gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&texture),
+            0,
+        );
+        self.gl.framebuffer_renderbuffer(
+            Gl::FRAMEBUFFER,
+            Gl::STENCIL_ATTACHMENT,
+            Gl::RENDERBUFFER,
+            Some(&stencil_renderbuffer),
+        );
+
+        // Stash the state of the current frame, if any, and render at the offscreen size.
+        let viewport = (self.viewport_width, self.viewport_height, self.view_matrix);
+        let masks = (
+            self.num_masks,
+            self.num_masks_active,
+            self.write_stencil_mask,
+            self.test_stencil_mask,
+            self.next_stencil_mask,
+        );
+        let mask_stack = std::mem::take(&mut self.mask_stack);
+        let blend_modes = std::mem::take(&mut self.blend_modes);
+
+        self.viewport_width = width as f32;
+        self.viewport_height = height as f32;
+        self.build_matrices();
+        self.gl.viewport(0, 0, width as i32, height as i32);
+        self.num_masks = 0;
+        self.num_masks_active = 0;
+        self.write_stencil_mask = 0;
+        self.test_stencil_mask = 0;
+        self.next_stencil_mask = 1;
+        self.active_program = std::ptr::null();
+        self.mask_state_dirty = true;
+
+        self.set_stencil_state();
+        self.gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        self.gl.stencil_mask(0xff);
+        self.gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
+
+        draw(self);
+
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let result = self
+            .gl
+            .read_pixels_with_opt_u8_array(
+                0,
+                0,
+                width as i32,
+                height as i32,
+                Gl::RGBA,
+                Gl::UNSIGNED_BYTE,
+                Some(&mut pixels),
+            )
+            .into_js_result();
+
+        // Restore the previous render target and state.
+        let (viewport_width, viewport_height, view_matrix) = viewport;
+        self.viewport_width = viewport_width;
+        self.viewport_height = viewport_height;
+        self.view_matrix = view_matrix;
+        let (num_masks, num_masks_active, write_stencil_mask, test_stencil_mask, next_stencil_mask) =
+            masks;
+        self.num_masks = num_masks;
+        self.num_masks_active = num_masks_active;
+        self.write_stencil_mask = write_stencil_mask;
+        self.test_stencil_mask = test_stencil_mask;
+        self.next_stencil_mask = next_stencil_mask;
+        self.mask_stack = mask_stack;
+        self.blend_modes = blend_modes;
+        self.active_program = std::ptr::null();
+        self.mask_state_dirty = true;
+
+        self.gl.bind_framebuffer(
+            Gl::FRAMEBUFFER,
+            self.msaa_buffers
+                .as_ref()
+                .map(|msaa_buffers| &msaa_buffers.render_framebuffer),
+        );
+        self.gl
+            .viewport(0, 0, viewport_width as i32, viewport_height as i32);
+        self.gl.delete_framebuffer(Some(&framebuffer));
+        self.gl.delete_renderbuffer(Some(&stencil_renderbuffer));
+        self.gl.delete_texture(Some(&texture));
+
+        result?;
+
+        // GL reads rows bottom-up, so flip them to match the bitmap layout.
+        let row_len = (width * 4) as usize;
+        let mut flipped = Vec::with_capacity(pixels.len());
+        for row in pixels.chunks_exact(row_len).rev() {
+            flipped.extend_from_slice(row);
+        }
+        Ok(flipped)
+    }
+}
+
+/// Returns the blend equation and source and destination blend factors
+/// used to draw with the given blend mode.
+///
+/// The difference, invert, overlay and hard light modes can't be expressed
+/// with fixed-function blending, and are drawn normally. The lighten and
+/// darken modes require WebGL2.
+fn blend_state(blend_mode: swf::BlendMode, premultiplied: bool, webgl2: bool) -> (u32, u32, u32) {
+    let src = if premultiplied {
+        Gl::ONE
+    } else {
+        Gl::SRC_ALPHA
+    };
+    match blend_mode {
+        swf::BlendMode::Multiply => (Gl::FUNC_ADD, Gl::DST_COLOR, Gl::ONE_MINUS_SRC_ALPHA),
+        swf::BlendMode::Screen => (Gl::FUNC_ADD, src, Gl::ONE_MINUS_SRC_COLOR),
+        swf::BlendMode::Lighten if webgl2 => (Gl2::MAX, Gl::ONE, Gl::ONE),
+        swf::BlendMode::Darken if webgl2 => (Gl2::MIN, Gl::ONE, Gl::ONE),
+        swf::BlendMode::Add => (Gl::FUNC_ADD, src, Gl::ONE),
+        swf::BlendMode::Subtract => (Gl::FUNC_REVERSE_SUBTRACT, src, Gl::ONE),
+        _ => (Gl::FUNC_ADD, src, Gl::ONE_MINUS_SRC_ALPHA),
+    }
 }
 
 struct Texture {
@@ -1203,7 +1463,7 @@ struct Gradient {
 #[derive(Clone, Debug)]
 struct BitmapDraw {
     matrix: [[f32; 3]; 3],
-    id: swf::CharacterId,
+    handle: BitmapHandle,
     is_repeating: bool,
     is_smoothed: bool,
 }
@@ -3,10 +3,11 @@ use lyon::tessellation::{
     geometry_builder::{BuffersBuilder, FillVertexConstructor, VertexBuffers},
     FillAttributes, FillTessellator, StrokeAttributes, StrokeTessellator, StrokeVertexConstructor,
 };
+use ruffle_core::backend::render::filters::FilterImage;
 use ruffle_core::backend::render::swf::{self, FillStyle};
 use ruffle_core::backend::render::{
-    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, Letterbox,
-    RenderBackend, ShapeHandle, Transform,
+    srgb_to_linear, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, Letterbox,
+    NullBitmapSource, RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::{DistilledShape, DrawPath};
 use std::convert::TryInto;
@@ -24,7 +25,7 @@ use crate::utils::{
     ruffle_path_to_lyon_path, swf_bitmap_to_gl_matrix, swf_to_gl_matrix,
 };
 use ruffle_core::color_transform::ColorTransform;
-use std::mem::replace;
+use std::mem::{replace, swap};
 use std::rc::Rc;
 
 type Error = Box<dyn std::error::Error>;
@@ -44,7 +45,9 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     pipelines: Pipelines,
     frame_buffer_view: wgpu::TextureView,
     depth_texture_view: wgpu::TextureView,
-    current_frame: Option<(T::Frame, wgpu::CommandEncoder)>,
+    /// The frame being rendered and the encoder recording its commands.
+    /// There is no frame while rendering offscreen outside of a frame.
+    current_frame: Option<(Option<T::Frame>, wgpu::CommandEncoder)>,
     register_encoder: wgpu::CommandEncoder,
     meshes: Vec<Mesh>,
     viewport_width: f32,
@@ -60,6 +63,57 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     quad_vbo: wgpu::Buffer,
     quad_ibo: wgpu::Buffer,
     quad_tex_transforms: wgpu::Buffer,
+
+    /// The offscreen layers of the filtered or blended display objects
+    /// currently being drawn.
+    layers: Vec<Layer>,
+}
+
+/// What happens to a layer when it is composited onto the layer beneath it.
+#[derive(Debug)]
+enum LayerEffect {
+    Filters(Vec<swf::Filter>),
+    BlendMode(swf::BlendMode),
+    /// The layer is read back by `render_offscreen` instead of being composited.
+    Offscreen,
+}
+
+/// An offscreen layer that a filtered or blended display object is drawn to.
+///
+/// While a layer is active, the frame buffer and depth views are replaced by
+/// the layer's own; the views and mask state of the layer beneath are kept
+/// here and restored when the layer is composited.
+#[derive(Debug)]
+struct Layer {
+    effect: LayerEffect,
+    width: u32,
+    height: u32,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    parent_frame_buffer_view: wgpu::TextureView,
+    parent_depth_texture_view: wgpu::TextureView,
+    num_masks: u32,
+    num_masks_active: u32,
+    write_stencil_mask: u32,
+    test_stencil_mask: u32,
+    next_stencil_mask: u32,
+    mask_stack: Vec<(u32, u32)>,
+}
+
+/// The view that draws are resolved to: the innermost layer, or the frame
+/// being rendered.
+///
+/// Offscreen rendering always draws into a layer, so there is a frame
+/// whenever there are no layers.
+fn output_view<'a, F: RenderTargetFrame>(
+    layers: &'a [Layer],
+    frame_output: &'a Option<F>,
+) -> &'a wgpu::TextureView {
+    match (layers.last(), frame_output) {
+        (Some(layer), _) => &layer.view,
+        (None, Some(frame_output)) => frame_output.view(),
+        (None, None) => unreachable!("Drawing outside of a frame or offscreen layer"),
+    }
 }
 
 #[repr(C)]
@@ -121,7 +175,11 @@ unsafe impl Pod for GPUVertex {}
 unsafe impl Zeroable for GPUVertex {}
 
 impl WgpuRenderBackend<SwapChainTarget> {
-    pub fn for_window<W: HasRawWindowHandle>(window: &W, size: (u32, u32)) -> Result<Self, Error> {
+    pub fn for_window<W: HasRawWindowHandle>(
+        window: &W,
+        size: (u32, u32),
+        msaa_sample_count: u32,
+    ) -> Result<Self, Error> {
         let surface = wgpu::Surface::create(window);
 
         let adapter = block_on(wgpu::Adapter::request(
@@ -143,15 +201,17 @@ impl WgpuRenderBackend<SwapChainTarget> {
         }));
 
         let target = SwapChainTarget::new(surface, size, &device);
-        Self::new(Rc::new(device), Rc::new(queue), target)
+        Self::new(Rc::new(device), Rc::new(queue), target, msaa_sample_count)
     }
 }
 
 impl<T: RenderTarget> WgpuRenderBackend<T> {
-    pub fn new(device: Rc<wgpu::Device>, queue: Rc<wgpu::Queue>, target: T) -> Result<Self, Error> {
-        // TODO: Allow this to be set from command line/settings file.
-        let msaa_sample_count = 4;
-
+    pub fn new(
+        device: Rc<wgpu::Device>,
+        queue: Rc<wgpu::Queue>,
+        target: T,
+        msaa_sample_count: u32,
+    ) -> Result<Self, Error> {
         let pipelines = Pipelines::new(&device, msaa_sample_count)?;
 
         let extent = wgpu::Extent3d {
@@ -222,11 +282,16 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             quad_vbo,
             quad_ibo,
             quad_tex_transforms,
+            layers: Vec::new(),
         })
     }
 
     #[allow(clippy::cognitive_complexity)]
-    fn register_shape_internal(&mut self, shape: DistilledShape) -> Mesh {
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
         use lyon::tessellation::{FillOptions, StrokeOptions};
 
         let transforms_label = create_debug_label!("Shape {} transforms ubo", shape.id);
@@ -497,10 +562,9 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             continue;
                         }
 
-                        let texture = match self
-                            .textures
-                            .iter()
-                            .find(|(other_id, _tex)| *other_id == *id)
+                        let texture = match bitmap_source
+                            .bitmap(*id)
+                            .and_then(|bitmap| self.textures.get(bitmap.handle.0))
                         {
                             None => {
                                 log::error!("Couldn't fill shape with unknown bitmap {}", id);
@@ -637,6 +701,32 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             }
         };
 
+        let texture = self.create_texture(id, extent, &data, debug_str);
+        let handle = BitmapHandle(self.textures.len());
+        self.textures.push((
+            id,
+            Texture {
+                texture,
+                width: bitmap.width,
+                height: bitmap.height,
+            },
+        ));
+
+        Ok(BitmapInfo {
+            handle,
+            width: bitmap.width.try_into().unwrap(),
+            height: bitmap.height.try_into().unwrap(),
+        })
+    }
+
+    /// Creates a texture and queues an upload of its RGBA pixels.
+    fn create_texture(
+        &mut self,
+        id: swf::CharacterId,
+        extent: wgpu::Extent3d,
+        data: &[u8],
+        debug_str: &str,
+    ) -> wgpu::Texture {
         let texture_label = create_debug_label!("{} Texture {}", debug_str, id);
         let texture = self.device.create_texture(&wgpu::TextureDescriptor {
             label: texture_label.as_deref(),
@@ -651,7 +741,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
         let buffer = create_buffer_with_data(
             &self.device,
-            &data,
+            data,
             wgpu::BufferUsage::COPY_SRC,
             create_debug_label!("{} transfer buffer {}", debug_str, id),
         );
@@ -672,21 +762,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             extent,
         );
 
-        let handle = BitmapHandle(self.textures.len());
-        self.textures.push((
-            id,
-            Texture {
-                texture,
-                width: bitmap.width,
-                height: bitmap.height,
-            },
-        ));
-
-        Ok(BitmapInfo {
-            handle,
-            width: bitmap.width.try_into().unwrap(),
-            height: bitmap.height.try_into().unwrap(),
-        })
+        texture
     }
 
     pub fn target(&self) -> &T {
@@ -697,6 +773,151 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         &self.device
     }
 
+    /// Draws a unit quad textured with a premultiplied texture.
+    fn draw_texture(
+        &mut self,
+        texture_view: &wgpu::TextureView,
+        transform: &Transform,
+        blend_mode: swf::BlendMode,
+        name: std::fmt::Arguments,
+    ) {
+        let (frame_output, encoder) = if let Some((frame_output, encoder)) = &mut self.current_frame
+        {
+            (frame_output, encoder)
+        } else {
+            return;
+        };
+
+        let world_matrix = [
+            [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
+            [transform.matrix.c, transform.matrix.d, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [
+                transform.matrix.tx.to_pixels() as f32,
+                transform.matrix.ty.to_pixels() as f32,
+                0.0,
+                1.0,
+            ],
+        ];
+
+        let transforms_ubo = create_buffer_with_data(
+            &self.device,
+            bytemuck::cast_slice(&[Transforms {
+                view_matrix: self.view_matrix,
+                world_matrix,
+            }]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("{} transforms transfer buffer", name),
+        );
+
+        let colors_ubo = create_buffer_with_data(
+            &self.device,
+            bytemuck::cast_slice(&[ColorAdjustments::from(transform.color_transform)]),
+            wgpu::BufferUsage::UNIFORM,
+            create_debug_label!("{} colors transfer buffer", name),
+        );
+
+        let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 100.0,
+            compare: wgpu::CompareFunction::Undefined,
+        });
+
+        let bind_group_label = create_debug_label!("{} bind group", name);
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.pipelines.bitmap.bind_layout,
+            bindings: &[
+                wgpu::Binding {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer {
+                        buffer: &transforms_ubo,
+                        range: 0..std::mem::size_of::<Transforms>() as u64,
+                    },
+                },
+                wgpu::Binding {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Buffer {
+                        buffer: &self.quad_tex_transforms,
+                        range: 0..std::mem::size_of::<TextureTransforms>() as u64,
+                    },
+                },
+                wgpu::Binding {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Buffer {
+                        buffer: &colors_ubo,
+                        range: 0..std::mem::size_of::<ColorAdjustments>() as u64,
+                    },
+                },
+                wgpu::Binding {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(texture_view),
+                },
+                wgpu::Binding {
+                    binding: 4,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: bind_group_label.as_deref(),
+        });
+
+        let output_view = output_view(&self.layers, frame_output);
+        let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
+            (&self.frame_buffer_view, Some(output_view))
+        } else {
+            (output_view, None)
+        };
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: color_attachment,
+                load_op: wgpu::LoadOp::Load,
+                store_op: wgpu::StoreOp::Store,
+                clear_color: wgpu::Color::WHITE,
+                resolve_target,
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment: &self.depth_texture_view,
+                depth_load_op: wgpu::LoadOp::Load,
+                depth_store_op: wgpu::StoreOp::Store,
+                stencil_load_op: wgpu::LoadOp::Load,
+                stencil_store_op: wgpu::StoreOp::Store,
+                clear_depth: 0.0,
+                clear_stencil: 0,
+            }),
+        });
+
+        if blend_mode == swf::BlendMode::Normal || self.num_masks_active < self.num_masks {
+            render_pass.set_pipeline(&self.pipelines.bitmap.pipeline_for(
+                self.num_masks,
+                self.num_masks_active,
+                self.test_stencil_mask,
+                self.write_stencil_mask,
+            ));
+        } else {
+            render_pass.set_pipeline(self.pipelines.blend_pipeline(
+                &self.device,
+                blend_mode,
+                self.test_stencil_mask,
+            ));
+        }
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_vertex_buffer(0, &self.quad_vbo, 0, 0);
+        render_pass.set_index_buffer(&self.quad_ibo, 0, 0);
+
+        if self.num_masks_active < self.num_masks {
+            render_pass.set_stencil_reference(self.write_stencil_mask);
+        } else {
+            render_pass.set_stencil_reference(self.test_stencil_mask);
+        }
+
+        render_pass.draw_indexed(0..6, 0, 0..1);
+    }
+
     fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
         let (frame_output, encoder) = if let Some((frame_output, encoder)) = &mut self.current_frame
         {
@@ -763,10 +984,11 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             label: bind_group_label.as_deref(),
         });
 
+        let output_view = output_view(&self.layers, frame_output);
         let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_output.view()))
+            (&self.frame_buffer_view, Some(output_view))
         } else {
-            (frame_output.view(), None)
+            (output_view, None)
         };
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
@@ -805,6 +1027,291 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
         render_pass.draw_indexed(0..6, 0, 0..1);
     }
+
+    /// Copies the contents of a layer back to the CPU.
+    ///
+    /// This submits all of the commands recorded so far in the frame and
+    /// waits for them to complete.
+    fn read_layer(&mut self, layer: &Layer) -> Option<FilterImage> {
+        let data = self.read_layer_rgba(layer)?;
+        Some(FilterImage::from_premultiplied_rgba(
+            layer.width as usize,
+            layer.height as usize,
+            &data,
+        ))
+    }
+
+    /// Copies the contents of a layer back to the CPU as RGBA rows with
+    /// premultiplied alpha.
+    fn read_layer_rgba(&mut self, layer: &Layer) -> Option<Vec<u8>> {
+        let (frame_output, mut encoder) = self.current_frame.take()?;
+
+        // Rows of texture copies must be aligned to 256 bytes.
+        let row_size = layer.width as usize * 4;
+        let padded_row_size = (row_size + 255) & !255;
+        let buffer_size = padded_row_size as u64 * u64::from(layer.height);
+        let label = create_debug_label!("Filter layer readback buffer");
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: label.as_deref(),
+            size: buffer_size,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &layer.texture,
+                mip_level: 0,
+                array_layer: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::BufferCopyView {
+                buffer: &buffer,
+                offset: 0,
+                bytes_per_row: padded_row_size as u32,
+                rows_per_image: 0,
+            },
+            wgpu::Extent3d {
+                width: layer.width,
+                height: layer.height,
+                depth: 1,
+            },
+        );
+
+        let label = create_debug_label!("Register encoder");
+        let register_encoder =
+            self.device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+        let register_buffer = replace(&mut self.register_encoder, register_encoder).finish();
+        self.queue.submit(&[register_buffer, encoder.finish()]);
+
+        let label = create_debug_label!("Frame encoder");
+        let encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: label.as_deref(),
+            });
+        self.current_frame = Some((frame_output, encoder));
+
+        let buffer_future = buffer.map_read(0, buffer_size);
+        self.device.poll(wgpu::Maintain::Wait);
+        let map = match block_on(buffer_future) {
+            Ok(map) => map,
+            Err(e) => {
+                log::error!("Unknown error reading filter layer: {:?}", e);
+                return None;
+            }
+        };
+
+        let mut data = Vec::with_capacity(row_size * layer.height as usize);
+        for row in map.as_slice().chunks(padded_row_size) {
+            data.extend_from_slice(&row[..row_size]);
+        }
+        if let wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb =
+            self.target.format()
+        {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Some(data)
+    }
+
+    /// Uploads a filtered image and draws it over the current layer.
+    fn draw_filter_image(&mut self, image: &FilterImage) {
+        let extent = wgpu::Extent3d {
+            width: image.width as u32,
+            height: image.height as u32,
+            depth: 1,
+        };
+        let label = create_debug_label!("Filtered layer texture");
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size: extent,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        let buffer = create_buffer_with_data(
+            &self.device,
+            &image.to_premultiplied_rgba(),
+            wgpu::BufferUsage::COPY_SRC,
+            create_debug_label!("Filtered layer transfer buffer"),
+        );
+
+        if let Some((_frame_output, encoder)) = &mut self.current_frame {
+            encoder.copy_buffer_to_texture(
+                wgpu::BufferCopyView {
+                    buffer: &buffer,
+                    offset: 0,
+                    bytes_per_row: 4 * extent.width,
+                    rows_per_image: 0,
+                },
+                wgpu::TextureCopyView {
+                    texture: &texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                extent,
+            );
+        }
+
+        use ruffle_core::swf::Matrix;
+        let transform = Transform {
+            matrix: Matrix {
+                a: extent.width as f32,
+                d: extent.height as f32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let texture_view = texture.create_default_view();
+        self.draw_texture(
+            &texture_view,
+            &transform,
+            swf::BlendMode::Normal,
+            format_args!("Filtered layer"),
+        );
+    }
+
+    /// Redirects drawing to a new transparent offscreen layer.
+    fn push_layer(&mut self, effect: LayerEffect) {
+        let extent = wgpu::Extent3d {
+            width: self.viewport_width as u32,
+            height: self.viewport_height as u32,
+            depth: 1,
+        };
+
+        let label = create_debug_label!("Layer texture");
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size: extent,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.target.format(),
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                | wgpu::TextureUsage::COPY_SRC
+                | wgpu::TextureUsage::SAMPLED,
+        });
+
+        let label = create_debug_label!("Layer framebuffer texture");
+        let frame_buffer = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size: extent,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count: self.msaa_sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.target.format(),
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        });
+
+        let label = create_debug_label!("Layer depth texture");
+        let depth_texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: label.as_deref(),
+            size: extent,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count: self.msaa_sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth24PlusStencil8,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        });
+
+        let view = texture.create_default_view();
+        let parent_frame_buffer_view = replace(
+            &mut self.frame_buffer_view,
+            frame_buffer.create_default_view(),
+        );
+        let parent_depth_texture_view = replace(
+            &mut self.depth_texture_view,
+            depth_texture.create_default_view(),
+        );
+        self.layers.push(Layer {
+            effect,
+            width: extent.width,
+            height: extent.height,
+            texture,
+            view,
+            parent_frame_buffer_view,
+            parent_depth_texture_view,
+            num_masks: self.num_masks,
+            num_masks_active: self.num_masks_active,
+            write_stencil_mask: self.write_stencil_mask,
+            test_stencil_mask: self.test_stencil_mask,
+            next_stencil_mask: self.next_stencil_mask,
+            mask_stack: replace(&mut self.mask_stack, Vec::new()),
+        });
+
+        // The layer has its own stencil buffer, so masks start over.
+        self.num_masks = 0;
+        self.num_masks_active = 0;
+        self.write_stencil_mask = 0;
+        self.test_stencil_mask = 0;
+        self.next_stencil_mask = 1;
+
+        if let Some((frame_output, encoder)) = &mut self.current_frame {
+            let output_view = output_view(&self.layers, frame_output);
+            let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
+                (&self.frame_buffer_view, Some(output_view))
+            } else {
+                (output_view, None)
+            };
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: color_attachment,
+                    load_op: wgpu::LoadOp::Clear,
+                    store_op: wgpu::StoreOp::Store,
+                    clear_color: wgpu::Color::TRANSPARENT,
+                    resolve_target,
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: &self.depth_texture_view,
+                    depth_load_op: wgpu::LoadOp::Clear,
+                    depth_store_op: wgpu::StoreOp::Store,
+                    stencil_load_op: wgpu::LoadOp::Clear,
+                    stencil_store_op: wgpu::StoreOp::Store,
+                    clear_depth: 0.0,
+                    clear_stencil: 0,
+                }),
+            });
+        }
+    }
+
+    /// Restores the views and mask state of the layer beneath the current
+    /// layer, returning the current layer so that it can be composited.
+    fn pop_layer(&mut self) -> Option<Layer> {
+        let mut layer = match self.layers.pop() {
+            Some(layer) => layer,
+            None => {
+                log::warn!("Layer stack underflow");
+                return None;
+            }
+        };
+
+        swap(
+            &mut self.frame_buffer_view,
+            &mut layer.parent_frame_buffer_view,
+        );
+        swap(
+            &mut self.depth_texture_view,
+            &mut layer.parent_depth_texture_view,
+        );
+        self.num_masks = layer.num_masks;
+        self.num_masks_active = layer.num_masks_active;
+        self.write_stencil_mask = layer.write_stencil_mask;
+        self.test_stencil_mask = layer.test_stencil_mask;
+        self.next_stencil_mask = layer.next_stencil_mask;
+        self.mask_stack = replace(&mut layer.mask_stack, Vec::new());
+        Some(layer)
+    }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
@@ -854,15 +1361,24 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.view_matrix = build_view_matrix(width, height);
     }
 
-    fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle {
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
         let handle = ShapeHandle(self.meshes.len());
-        let mesh = self.register_shape_internal(shape);
+        let mesh = self.register_shape_internal(shape, bitmap_source);
         self.meshes.push(mesh);
         handle
     }
 
-    fn replace_shape(&mut self, shape: DistilledShape, handle: ShapeHandle) {
-        let mesh = self.register_shape_internal(shape);
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, bitmap_source);
         self.meshes[handle.0] = mesh;
     }
 
@@ -887,7 +1403,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             shape: glyph.shape_records.clone(),
         };
         let handle = ShapeHandle(self.meshes.len());
-        let mesh = self.register_shape_internal((&shape).into());
+        let mesh = self.register_shape_internal((&shape).into(), &NullBitmapSource);
         self.meshes.push(mesh);
         handle
     }
@@ -923,13 +1439,82 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.register_bitmap(swf_tag.id, bitmap, "PNG")
     }
 
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapInfo, Error> {
+        self.register_bitmap(
+            0,
+            Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            },
+            "RAW",
+        )
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        if bitmap.0 >= self.textures.len() {
+            return Err("update_texture: Unknown bitmap handle".into());
+        }
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        let texture = &self.textures[bitmap.0].1;
+        if texture.width == width && texture.height == height {
+            // Upload into the existing texture, so that shapes filled with
+            // this bitmap see the new pixels.
+            let texture = &self.textures[bitmap.0].1.texture;
+            let buffer = create_buffer_with_data(
+                &self.device,
+                &rgba,
+                wgpu::BufferUsage::COPY_SRC,
+                create_debug_label!("RAW transfer buffer {}", bitmap.0),
+            );
+            self.register_encoder.copy_buffer_to_texture(
+                wgpu::BufferCopyView {
+                    buffer: &buffer,
+                    offset: 0,
+                    bytes_per_row: 4 * extent.width,
+                    rows_per_image: 0,
+                },
+                wgpu::TextureCopyView {
+                    texture,
+                    mip_level: 0,
+                    array_layer: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                extent,
+            );
+            return Ok(bitmap);
+        }
+        let texture = self.create_texture(0, extent, &rgba, "RAW");
+        self.textures[bitmap.0].1 = Texture {
+            texture,
+            width,
+            height,
+        };
+        Ok(bitmap)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         assert!(self.current_frame.is_none());
         self.current_frame = match self.target.get_next_texture() {
             Ok(frame) => {
                 let label = create_debug_label!("Frame encoder");
                 Some((
-                    frame,
+                    Some(frame),
                     self.device
                         .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                             label: label.as_deref(),
@@ -946,12 +1531,17 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.write_stencil_mask = 0;
         self.test_stencil_mask = 0;
         self.next_stencil_mask = 1;
+        if let Some(layer) = self.layers.drain(..).next() {
+            self.frame_buffer_view = layer.parent_frame_buffer_view;
+            self.depth_texture_view = layer.parent_depth_texture_view;
+        }
 
         if let Some((frame_output, encoder)) = &mut self.current_frame {
+            let output_view = output_view(&self.layers, frame_output);
             let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-                (&self.frame_buffer_view, Some(frame_output.view()))
+                (&self.frame_buffer_view, Some(output_view))
             } else {
-                (frame_output.view(), None)
+                (output_view, None)
             };
             encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
@@ -981,13 +1571,6 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
         if let Some((_id, texture)) = self.textures.get(bitmap.0) {
-            let (frame_output, encoder) =
-                if let Some((frame_output, encoder)) = &mut self.current_frame {
-                    (frame_output, encoder)
-                } else {
-                    return;
-                };
-
             use ruffle_core::swf::Matrix;
             let transform = Transform {
                 matrix: transform.matrix
@@ -998,127 +1581,13 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     },
                 ..*transform
             };
-
-            let world_matrix = [
-                [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
-                [transform.matrix.c, transform.matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    transform.matrix.tx.to_pixels() as f32,
-                    transform.matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
-            ];
-
-            let transforms_ubo = create_buffer_with_data(
-                &self.device,
-                bytemuck::cast_slice(&[Transforms {
-                    view_matrix: self.view_matrix,
-                    world_matrix,
-                }]),
-                wgpu::BufferUsage::UNIFORM,
-                create_debug_label!("Bitmap {} transforms transfer buffer", bitmap.0),
-            );
-
-            let colors_ubo = create_buffer_with_data(
-                &self.device,
-                bytemuck::cast_slice(&[ColorAdjustments::from(transform.color_transform)]),
-                wgpu::BufferUsage::UNIFORM,
-                create_debug_label!("Bitmap {} colors transfer buffer", bitmap.0),
-            );
-
             let texture_view = texture.texture.create_default_view();
-            let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor {
-                address_mode_u: wgpu::AddressMode::ClampToEdge,
-                address_mode_v: wgpu::AddressMode::ClampToEdge,
-                address_mode_w: wgpu::AddressMode::ClampToEdge,
-                mag_filter: wgpu::FilterMode::Linear,
-                min_filter: wgpu::FilterMode::Linear,
-                mipmap_filter: wgpu::FilterMode::Linear,
-                lod_min_clamp: 0.0,
-                lod_max_clamp: 100.0,
-                compare: wgpu::CompareFunction::Undefined,
-            });
-
-            let bind_group_label = create_debug_label!("Bitmap {} bind group", bitmap.0);
-            let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                layout: &self.pipelines.bitmap.bind_layout,
-                bindings: &[
-                    wgpu::Binding {
-                        binding: 0,
-                        resource: wgpu::BindingResource::Buffer {
-                            buffer: &transforms_ubo,
-                            range: 0..std::mem::size_of::<Transforms>() as u64,
-                        },
-                    },
-                    wgpu::Binding {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Buffer {
-                            buffer: &self.quad_tex_transforms,
-                            range: 0..std::mem::size_of::<TextureTransforms>() as u64,
-                        },
-                    },
-                    wgpu::Binding {
-                        binding: 2,
-                        resource: wgpu::BindingResource::Buffer {
-                            buffer: &colors_ubo,
-                            range: 0..std::mem::size_of::<ColorAdjustments>() as u64,
-                        },
-                    },
-                    wgpu::Binding {
-                        binding: 3,
-                        resource: wgpu::BindingResource::TextureView(&texture_view),
-                    },
-                    wgpu::Binding {
-                        binding: 4,
-                        resource: wgpu::BindingResource::Sampler(&sampler),
-                    },
-                ],
-                label: bind_group_label.as_deref(),
-            });
-
-            let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-                (&self.frame_buffer_view, Some(frame_output.view()))
-            } else {
-                (frame_output.view(), None)
-            };
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                    attachment: color_attachment,
-                    load_op: wgpu::LoadOp::Load,
-                    store_op: wgpu::StoreOp::Store,
-                    clear_color: wgpu::Color::WHITE,
-                    resolve_target,
-                }],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                    attachment: &self.depth_texture_view,
-                    depth_load_op: wgpu::LoadOp::Load,
-                    depth_store_op: wgpu::StoreOp::Store,
-                    stencil_load_op: wgpu::LoadOp::Load,
-                    stencil_store_op: wgpu::StoreOp::Store,
-                    clear_depth: 0.0,
-                    clear_stencil: 0,
-                }),
-            });
-
-            render_pass.set_pipeline(&self.pipelines.bitmap.pipeline_for(
-                self.num_masks,
-                self.num_masks_active,
-                self.test_stencil_mask,
-                self.write_stencil_mask,
-            ));
-            render_pass.set_bind_group(0, &bind_group, &[]);
-            render_pass.set_vertex_buffer(0, &self.quad_vbo, 0, 0);
-            render_pass.set_index_buffer(&self.quad_ibo, 0, 0);
-
-            if self.num_masks_active < self.num_masks {
-                render_pass.set_stencil_reference(self.write_stencil_mask);
-            } else {
-                render_pass.set_stencil_reference(self.test_stencil_mask);
-            }
-
-            render_pass.draw_indexed(0..6, 0, 0..1);
+            self.draw_texture(
+                &texture_view,
+                &transform,
+                swf::BlendMode::Normal,
+                format_args!("Bitmap {}", bitmap.0),
+            );
         }
     }
 
@@ -1181,10 +1650,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             std::mem::size_of::<Transforms>() as u64,
         );
 
+        let output_view = output_view(&self.layers, frame_output);
         let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_output.view()))
+            (&self.frame_buffer_view, Some(output_view))
         } else {
-            (frame_output.view(), None)
+            (output_view, None)
         };
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
@@ -1337,10 +1807,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             }
             self.next_stencil_mask = 1;
             if let Some((frame_output, encoder)) = &mut self.current_frame {
+                let output_view = output_view(&self.layers, frame_output);
                 let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-                    (&self.frame_buffer_view, Some(frame_output.view()))
+                    (&self.frame_buffer_view, Some(output_view))
                 } else {
-                    (frame_output.view(), None)
+                    (output_view, None)
                 };
                 encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
@@ -1385,6 +1856,103 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             self.test_stencil_mask = test;
         }
     }
+
+    fn push_filters(&mut self, filters: &[swf::Filter]) {
+        self.push_layer(LayerEffect::Filters(filters.to_vec()));
+    }
+
+    fn pop_filters(&mut self) {
+        if let Some(layer) = self.pop_layer() {
+            if let Some(mut image) = self.read_layer(&layer) {
+                if let LayerEffect::Filters(filters) = &layer.effect {
+                    image.apply_filters(filters);
+                }
+                self.draw_filter_image(&image);
+            }
+        }
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.push_layer(LayerEffect::BlendMode(blend_mode));
+    }
+
+    fn pop_blend_mode(&mut self) {
+        if let Some(layer) = self.pop_layer() {
+            let blend_mode = match layer.effect {
+                LayerEffect::BlendMode(blend_mode) => blend_mode,
+                LayerEffect::Filters(_) | LayerEffect::Offscreen => swf::BlendMode::Normal,
+            };
+            // The alpha and erase modes only change the transparency of the
+            // enclosing layer, which has no visible effect on the stage itself.
+            if self.layers.is_empty()
+                && (blend_mode == swf::BlendMode::Alpha || blend_mode == swf::BlendMode::Erase)
+            {
+                return;
+            }
+            use ruffle_core::swf::Matrix;
+            let transform = Transform {
+                matrix: Matrix {
+                    a: layer.width as f32,
+                    d: layer.height as f32,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            self.draw_texture(
+                &layer.view,
+                &transform,
+                blend_mode,
+                format_args!("Blended layer"),
+            );
+        }
+    }
+
+    fn render_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        draw: &mut dyn FnMut(&mut dyn RenderBackend),
+    ) -> Result<Vec<u8>, Error> {
+        if width == 0 || height == 0 {
+            return Ok(vec![]);
+        }
+
+        // Scripts draw between frames, so there is usually no frame to
+        // record the commands in; record them without one.
+        let in_frame = self.current_frame.is_some();
+        if !in_frame {
+            let label = create_debug_label!("Offscreen encoder");
+            let encoder = self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+            self.current_frame = Some((None, encoder));
+        }
+
+        // Layers and the view matrix follow the size of the viewport.
+        let viewport = (self.viewport_width, self.viewport_height, self.view_matrix);
+        self.viewport_width = width as f32;
+        self.viewport_height = height as f32;
+        self.view_matrix = build_view_matrix(width, height);
+
+        self.push_layer(LayerEffect::Offscreen);
+        draw(self);
+        let rgba = self
+            .pop_layer()
+            .and_then(|layer| self.read_layer_rgba(&layer));
+
+        let (viewport_width, viewport_height, view_matrix) = viewport;
+        self.viewport_width = viewport_width;
+        self.viewport_height = viewport_height;
+        self.view_matrix = view_matrix;
+        if !in_frame {
+            // Everything was submitted when the layer was read back.
+            self.current_frame = None;
+        }
+
+        rgba.ok_or_else(|| "render_offscreen: Unable to read back the offscreen layer".into())
+    }
 }
 
 fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
@@ -1,4 +1,6 @@
 use crate::{Error, GPUVertex};
+use ruffle_core::swf::BlendMode;
+use std::collections::HashMap;
 use wgpu::vertex_attr_array;
 
 #[derive(Debug)]
@@ -13,6 +15,16 @@ pub struct Pipelines {
     pub color: ShapePipeline,
     pub bitmap: ShapePipeline,
     pub gradient: ShapePipeline,
+
+    /// Pipelines that draw offscreen layers with a non-normal blend mode,
+    /// keyed by blend mode and stencil read mask.
+    ///
+    /// These are only created when a blend mode is first used.
+    blend_pipelines: HashMap<(u8, u32), wgpu::RenderPipeline>,
+    blend_vertex_shader: wgpu::ShaderModule,
+    blend_fragment_shader: wgpu::ShaderModule,
+    blend_pipeline_layout: wgpu::PipelineLayout,
+    msaa_sample_count: u32,
 }
 
 impl ShapePipeline {
@@ -54,17 +66,156 @@ impl Pipelines {
             &bitmap_fs_bytes[..],
         ))?);
 
+        let bitmap = create_bitmap_pipeline(&device, &texture_vs, &bitmap_fs, msaa_sample_count);
+        let blend_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                bind_group_layouts: &[&bitmap.bind_layout],
+            });
+
         Ok(Self {
             color: create_color_pipelines(&device, &color_vs, &color_fs, msaa_sample_count),
-            bitmap: create_bitmap_pipeline(&device, &texture_vs, &bitmap_fs, msaa_sample_count),
+            bitmap,
             gradient: create_gradient_pipeline(
                 &device,
                 &texture_vs,
                 &gradient_fs,
                 msaa_sample_count,
             ),
+            blend_pipelines: HashMap::new(),
+            blend_vertex_shader: texture_vs,
+            blend_fragment_shader: bitmap_fs,
+            blend_pipeline_layout,
+            msaa_sample_count,
         })
     }
+
+    /// Returns the pipeline that draws a premultiplied texture with the given
+    /// blend mode, using the bitmap bind group layout.
+    ///
+    /// Only blend modes that can be expressed with fixed-function blending are
+    /// supported; `Difference`, `Invert`, `Overlay` and `HardLight` are drawn
+    /// like `Normal`.
+    pub fn blend_pipeline(
+        &mut self,
+        device: &wgpu::Device,
+        blend_mode: BlendMode,
+        read_mask: u32,
+    ) -> &wgpu::RenderPipeline {
+        let (color_blend, alpha_blend) = match blend_state(blend_mode) {
+            Some(state) => state,
+            None => return &self.bitmap.read_mask_pipelines[read_mask as usize],
+        };
+        let vertex_shader = &self.blend_vertex_shader;
+        let fragment_shader = &self.blend_fragment_shader;
+        let pipeline_layout = &self.blend_pipeline_layout;
+        let msaa_sample_count = self.msaa_sample_count;
+        self.blend_pipelines
+            .entry((blend_mode as u8, read_mask))
+            .or_insert_with(|| {
+                device.create_render_pipeline(&create_pipeline_descriptor(
+                    vertex_shader,
+                    fragment_shader,
+                    pipeline_layout,
+                    Some(wgpu::DepthStencilStateDescriptor {
+                        format: wgpu::TextureFormat::Depth24PlusStencil8,
+                        depth_write_enabled: true,
+                        depth_compare: wgpu::CompareFunction::Always,
+                        stencil_front: wgpu::StencilStateFaceDescriptor {
+                            compare: wgpu::CompareFunction::Equal,
+                            fail_op: wgpu::StencilOperation::Keep,
+                            depth_fail_op: wgpu::StencilOperation::Keep,
+                            pass_op: wgpu::StencilOperation::Keep,
+                        },
+                        stencil_back: wgpu::StencilStateFaceDescriptor {
+                            compare: wgpu::CompareFunction::Equal,
+                            fail_op: wgpu::StencilOperation::Keep,
+                            depth_fail_op: wgpu::StencilOperation::Keep,
+                            pass_op: wgpu::StencilOperation::Keep,
+                        },
+                        stencil_read_mask: read_mask,
+                        stencil_write_mask: 0,
+                    }),
+                    &[wgpu::ColorStateDescriptor {
+                        format: wgpu::TextureFormat::Bgra8Unorm,
+                        color_blend,
+                        alpha_blend,
+                        write_mask: wgpu::ColorWrite::ALL,
+                    }],
+                    msaa_sample_count,
+                ))
+            })
+    }
+}
+
+/// The color and alpha blend states of a blend mode, for premultiplied sources.
+fn blend_state(blend_mode: BlendMode) -> Option<(wgpu::BlendDescriptor, wgpu::BlendDescriptor)> {
+    use wgpu::{BlendDescriptor, BlendFactor, BlendOperation};
+    let blend = |src_factor, dst_factor, operation| BlendDescriptor {
+        src_factor,
+        dst_factor,
+        operation,
+    };
+    let over = blend(
+        BlendFactor::One,
+        BlendFactor::OneMinusSrcAlpha,
+        BlendOperation::Add,
+    );
+    let state = match blend_mode {
+        BlendMode::Multiply => (
+            blend(
+                BlendFactor::DstColor,
+                BlendFactor::OneMinusSrcAlpha,
+                BlendOperation::Add,
+            ),
+            over,
+        ),
+        BlendMode::Screen => (
+            blend(
+                BlendFactor::One,
+                BlendFactor::OneMinusSrcColor,
+                BlendOperation::Add,
+            ),
+            over,
+        ),
+        BlendMode::Lighten => (
+            blend(BlendFactor::One, BlendFactor::One, BlendOperation::Max),
+            over,
+        ),
+        BlendMode::Darken => (
+            blend(BlendFactor::One, BlendFactor::One, BlendOperation::Min),
+            over,
+        ),
+        BlendMode::Add => (
+            blend(BlendFactor::One, BlendFactor::One, BlendOperation::Add),
+            over,
+        ),
+        BlendMode::Subtract => (
+            blend(
+                BlendFactor::One,
+                BlendFactor::One,
+                BlendOperation::ReverseSubtract,
+            ),
+            over,
+        ),
+        BlendMode::Alpha => {
+            let state = blend(
+                BlendFactor::Zero,
+                BlendFactor::SrcAlpha,
+                BlendOperation::Add,
+            );
+            (state.clone(), state)
+        }
+        BlendMode::Erase => {
+            let state = blend(
+                BlendFactor::Zero,
+                BlendFactor::OneMinusSrcAlpha,
+                BlendOperation::Add,
+            );
+            (state.clone(), state)
+        }
+        _ => return None,
+    };
+    Some(state)
 }
 
 fn create_pipeline_descriptor<'a>(
@@ -216,7 +367,7 @@ fn create_color_pipelines(
                     operation: wgpu::BlendOperation::Add,
                 },
                 alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    src_factor: wgpu::BlendFactor::One,
                     dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                     operation: wgpu::BlendOperation::Add,
                 },
@@ -356,7 +507,7 @@ fn create_bitmap_pipeline(
                     operation: wgpu::BlendOperation::Add,
                 },
                 alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    src_factor: wgpu::BlendFactor::One,
                     dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                     operation: wgpu::BlendOperation::Add,
                 },
@@ -490,7 +641,7 @@ fn create_gradient_pipeline(
                     operation: wgpu::BlendOperation::Add,
                 },
                 alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    src_factor: wgpu::BlendFactor::One,
                     dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                     operation: wgpu::BlendOperation::Add,
                 },
@@ -106,19 +106,19 @@ impl<R: Read> Reader<R> {
             | (i32::from(self.read_u8()? as i8) << 16))
     }
     fn read_i32(&mut self) -> Result<i32> {
-        let mut n: i32 = 0;
+        // The groups are not sign-extended; negative values always use the
+        // full five bytes, the last of which only contributes its low 4 bits.
+        let mut n: u32 = 0;
         let mut i = 0;
         loop {
-            let byte: i32 = self.read_u8()?.into();
+            let byte: u32 = self.read_u8()?.into();
             n |= (byte & 0b0111_1111) << i;
             i += 7;
-            if byte & 0b1000_0000 == 0 {
-                n <<= 32 - i;
-                n >>= 32 - i;
+            if byte & 0b1000_0000 == 0 || i >= 35 {
                 break;
             }
         }
-        Ok(n)
+        Ok(n as i32)
     }
 
     fn read_string(&mut self) -> Result<String> {
@@ -897,4 +897,15 @@ pub mod tests {
             }
         }
     }
+    #[test]
+    fn read_i32() {
+        let read = |data: &[u8]| Reader::new(data).read_i32().unwrap();
+        assert_eq!(read(&[0]), 0);
+        assert_eq!(read(&[0x7f]), 127);
+        assert_eq!(read(&[0x80, 0x01]), 128);
+        assert_eq!(read(&[0x80, 0x89, 0x7a]), 2000000);
+        assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x07]), 2147483647);
+        assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x0f]), -1);
+        assert_eq!(read(&[0x80, 0x80, 0x80, 0x80, 0x08]), -2147483648);
+    }
 }
@@ -84,11 +84,13 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
-    fn write_i32(&mut self, mut n: i32) -> Result<()> {
+    fn write_i32(&mut self, n: i32) -> Result<()> {
+        // Written as unsigned 7-bit groups, so negative values take five bytes.
+        let mut n = n as u32;
         loop {
             let byte = (n as u8) & 0x7f;
             n >>= 7;
-            if n != 0 && n != -1 {
+            if n != 0 {
                 self.write_u8(0b1_0000000 | byte)?;
             } else {
                 self.write_u8(byte)?;
@@ -988,4 +990,19 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn write_i32() {
+        let write = |n: i32| {
+            let mut out = vec![];
+            Writer::new(&mut out).write_i32(n).unwrap();
+            out
+        };
+        assert_eq!(write(0), [0]);
+        assert_eq!(write(127), [0x7f]);
+        assert_eq!(write(2000000), [0x80, 0x89, 0x7a]);
+        assert_eq!(write(i32::MAX), [0xff, 0xff, 0xff, 0xff, 0x07]);
+        assert_eq!(write(-1), [0xff, 0xff, 0xff, 0xff, 0x0f]);
+        assert_eq!(write(i32::MIN), [0x80, 0x80, 0x80, 0x80, 0x08]);
+    }
 }
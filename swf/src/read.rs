@@ -452,7 +452,9 @@ impl<R: Read> Reader<R> {
             Some(TagCode::DefineText2) => {
                 Tag::DefineText(Box::new(tag_reader.read_define_text(2)?))
             }
-            Some(TagCode::DefineVideoStream) => tag_reader.read_define_video_stream()?,
+            Some(TagCode::DefineVideoStream) => {
+                Tag::DefineVideoStream(tag_reader.read_define_video_stream()?)
+            }
             Some(TagCode::EnableTelemetry) => {
                 tag_reader.read_u16()?; // Reserved
                 let password_hash = if length > 2 {
@@ -584,17 +586,7 @@ impl<R: Read> Reader<R> {
                 tab_index: tag_reader.read_u16()?,
             },
 
-            Some(TagCode::SymbolClass) => {
-                let num_symbols = tag_reader.read_u16()?;
-                let mut symbols = Vec::with_capacity(num_symbols as usize);
-                for _ in 0..num_symbols {
-                    symbols.push(SymbolClassLink {
-                        id: tag_reader.read_u16()?,
-                        class_name: tag_reader.read_c_string()?,
-                    });
-                }
-                Tag::SymbolClass(symbols)
-            }
+            Some(TagCode::SymbolClass) => Tag::SymbolClass(tag_reader.read_symbol_class()?),
 
             Some(TagCode::ExportAssets) => Tag::ExportAssets(tag_reader.read_export_assets()?),
 
@@ -643,7 +635,7 @@ impl<R: Read> Reader<R> {
 
             Some(TagCode::RemoveObject2) => Tag::RemoveObject(tag_reader.read_remove_object_2()?),
 
-            Some(TagCode::VideoFrame) => tag_reader.read_video_frame()?,
+            Some(TagCode::VideoFrame) => Tag::VideoFrame(tag_reader.read_video_frame()?),
             Some(TagCode::ProductInfo) => Tag::ProductInfo(tag_reader.read_product_info()?),
             _ => {
                 let size = length as usize;
@@ -2066,6 +2058,18 @@ impl<R: Read> Reader<R> {
         Ok(exports)
     }
 
+    pub fn read_symbol_class(&mut self) -> Result<Vec<SymbolClassLink>> {
+        let num_symbols = self.read_u16()?;
+        let mut symbols = Vec::with_capacity(num_symbols.into());
+        for _ in 0..num_symbols {
+            symbols.push(SymbolClassLink {
+                id: self.read_u16()?,
+                class_name: self.read_c_string()?,
+            });
+        }
+        Ok(symbols)
+    }
+
     pub fn read_place_object(&mut self, tag_length: usize) -> Result<PlaceObject> {
         // TODO: What's a best way to know if the tag has a color transform?
         // You only know if there is still data remaining after the matrix.
@@ -2723,7 +2727,7 @@ impl<R: Read> Reader<R> {
         })
     }
 
-    fn read_define_video_stream(&mut self) -> Result<Tag> {
+    pub fn read_define_video_stream(&mut self) -> Result<DefineVideoStream> {
         let id = self.read_character_id()?;
         let num_frames = self.read_u16()?;
         let width = self.read_u16()?;
@@ -2737,14 +2741,14 @@ impl<R: Read> Reader<R> {
             5 => VideoCodec::VP6WithAlpha,
             _ => return Err(Error::invalid_data("Invalid video codec.")),
         };
-        Ok(Tag::DefineVideoStream(DefineVideoStream {
+        Ok(DefineVideoStream {
             id,
             num_frames,
             width,
             height,
             is_smoothed: flags & 0b1 != 0,
             codec,
-            deblocking: match flags & 0b100_0 {
+            deblocking: match flags & 0b111_0 {
                 0b000_0 => VideoDeblocking::UseVideoPacketValue,
                 0b001_0 => VideoDeblocking::None,
                 0b010_0 => VideoDeblocking::Level1,
@@ -2753,19 +2757,19 @@ impl<R: Read> Reader<R> {
                 0b101_0 => VideoDeblocking::Level4,
                 _ => return Err(Error::invalid_data("Invalid video deblocking value.")),
             },
-        }))
+        })
     }
 
-    fn read_video_frame(&mut self) -> Result<Tag> {
+    pub fn read_video_frame(&mut self) -> Result<VideoFrame> {
         let stream_id = self.read_character_id()?;
         let frame_num = self.read_u16()?;
         let mut data = vec![];
         self.input.read_to_end(&mut data)?;
-        Ok(Tag::VideoFrame(VideoFrame {
+        Ok(VideoFrame {
             stream_id,
             frame_num,
             data,
-        }))
+        })
     }
 
     fn read_define_bits_jpeg_3(&mut self, version: u8) -> Result<Tag> {
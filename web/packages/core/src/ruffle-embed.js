@@ -17,7 +17,14 @@ module.exports = class RuffleEmbed extends RufflePlayer {
 
     connectedCallback() {
         super.connectedCallback();
-        this.stream_swf_url(this.attributes.src.value);
+        this.allow_script_access = this.getAttribute("allowScriptAccess");
+        this.stream_swf_url(this.attributes.src.value, this.parameters);
+    }
+
+    get parameters() {
+        return RufflePlayer.parse_flashvars(
+            this.attributes.flashvars && this.attributes.flashvars.value
+        );
     }
 
     get src() {
@@ -36,7 +43,7 @@ module.exports = class RuffleEmbed extends RufflePlayer {
         super.attributeChangedCallback(name, oldValue, newValue);
         console.log(name + " " + oldValue + " " + newValue);
         if (this.isConnected && name === "src") {
-            this.stream_swf_url(this.attributes.src.value);
+            this.stream_swf_url(this.attributes.src.value, this.parameters);
         }
     }
 
@@ -19,11 +19,25 @@ module.exports = class RuffleObject extends RufflePlayer {
 
         this.params = RuffleObject.params_of(this);
 
+        // Parameter names are case-insensitive, e.g. `FlashVars`.
+        let flashvars_name = Object.keys(this.params).find(
+            (name) => name.toLowerCase() === "flashvars"
+        );
+        let parameters = RufflePlayer.parse_flashvars(
+            flashvars_name && this.params[flashvars_name]
+        );
+        let allow_script_access_name = Object.keys(this.params).find(
+            (name) => name.toLowerCase() === "allowscriptaccess"
+        );
+        this.allow_script_access = allow_script_access_name
+            ? this.params[allow_script_access_name]
+            : null;
+
         //Kick off the SWF download.
         if (this.attributes.data) {
-            this.stream_swf_url(this.attributes.data.value);
+            this.stream_swf_url(this.attributes.data.value, parameters);
         } else if (this.params.movie) {
-            this.stream_swf_url(this.params.movie);
+            this.stream_swf_url(this.params.movie, parameters);
         }
     }
 
@@ -29,6 +29,9 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
 
         self.instance = null;
 
+        // The `allowScriptAccess` parameter of the movie, if given.
+        self.allow_script_access = null;
+
         self.Ruffle = load_ruffle();
 
         return self;
@@ -116,7 +119,12 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
         return false;
     }
 
-    async stream_swf_url(url) {
+    /*
+     * Downloads and plays a SWF file.
+     * `parameters` are passed to the movie as its flashvars, alongside the
+     * query string of the URL.
+     */
+    async stream_swf_url(url, parameters = {}) {
         //TODO: Actually stream files...
         try {
             if (this.isConnected && !this.is_unused_fallback_object()) {
@@ -127,7 +135,7 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
 
                 if (response.ok) {
                     let data = await response.arrayBuffer();
-                    await this.play_swf_data(data);
+                    await this.play_swf_data(data, abs_url, parameters);
                     console.log("Playing " + url);
                 } else {
                     console.error(
@@ -159,7 +167,7 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
         }
     }
 
-    async play_swf_data(data) {
+    async play_swf_data(data, url = null, parameters = {}) {
         if (this.isConnected && !this.is_unused_fallback_object()) {
             console.log("Got SWF data");
 
@@ -174,7 +182,13 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
                 throw e;
             });
 
-            this.instance = Ruffle.new(this.container, new Uint8Array(data));
+            this.instance = Ruffle.new(
+                this.container,
+                new Uint8Array(data),
+                url,
+                parameters,
+                this
+            );
             console.log("New Ruffle instance created.");
 
             if (this.play_button) {
@@ -187,6 +201,50 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
         }
     }
 
+    /*
+     * Called when the movie exposes a function with
+     * `ExternalInterface.addCallback`; makes it a method of this element.
+     */
+    on_callback_available(name) {
+        this[name] = (...args) => this.call_exposed_callback(name, args);
+    }
+
+    call_exposed_callback(name, args) {
+        if (this.instance) {
+            return this.instance.call_exposed_callback(name, args);
+        }
+        return null;
+    }
+
+    /*
+     * Called when the movie sends an FSCommand. Like Flash Player, this
+     * calls the page's `<id>_DoFSCommand` function, if it has one.
+     * Returns whether the command was handled.
+     */
+    on_fs_command(command, args) {
+        let id = this.id || this.getAttribute("name");
+        let handler = id ? window[id + "_DoFSCommand"] : null;
+        if (typeof handler === "function") {
+            handler(command, args);
+            return true;
+        }
+        return false;
+    }
+
+    /*
+     * Parses a `flashvars` string (e.g. `a=1&b=2`) into an object of movie
+     * parameters.
+     */
+    static parse_flashvars(flashvars) {
+        let parameters = {};
+        if (flashvars) {
+            for (let [key, value] of new URLSearchParams(flashvars)) {
+                parameters[key] = value;
+            }
+        }
+        return parameters;
+    }
+
     /*
      * Copies attributes and children from another element to this player element.
      * Used by the polyfill elements, RuffleObject and RuffleEmbed.
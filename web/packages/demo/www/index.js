@@ -101,7 +101,10 @@ function localFileSelected() {
 
 function loadRemoteFile(url) {
     fetch(url).then((response) => {
-        response.arrayBuffer().then((data) => player.play_swf_data(data));
+        let abs_url = new URL(url, window.location.href).toString();
+        response
+            .arrayBuffer()
+            .then((data) => player.play_swf_data(data, abs_url));
     });
 }
 
@@ -0,0 +1,138 @@
+use js_sys::{Array, Function, Object, Reflect};
+use ruffle_core::external::{ExternalInterfaceProvider, Value};
+use std::collections::BTreeMap;
+use wasm_bindgen::{prelude::*, JsCast, JsValue};
+
+#[wasm_bindgen]
+extern "C" {
+    /// The `RufflePlayer` element that a movie is embedded in.
+    pub type JavascriptPlayer;
+
+    #[wasm_bindgen(method)]
+    fn on_callback_available(this: &JavascriptPlayer, name: &str);
+
+    #[wasm_bindgen(method)]
+    fn on_fs_command(this: &JavascriptPlayer, command: &str, args: &str) -> bool;
+
+    /// The `allowScriptAccess` parameter of the movie, if given.
+    #[wasm_bindgen(method, getter)]
+    pub fn allow_script_access(this: &JavascriptPlayer) -> Option<String>;
+}
+
+/// Lets a movie talk to the page it is embedded in.
+pub struct WebExternalInterfaceProvider {
+    js_player: JavascriptPlayer,
+}
+
+impl WebExternalInterfaceProvider {
+    pub fn new(js_player: JavascriptPlayer) -> Self {
+        Self { js_player }
+    }
+}
+
+impl ExternalInterfaceProvider for WebExternalInterfaceProvider {
+    fn call_method(&mut self, name: &str, args: &[Value]) -> Value {
+        let (this, function) = match resolve_method(name) {
+            Some(method) => method,
+            None => {
+                log::warn!("Unknown ExternalInterface method {}", name);
+                return Value::Null;
+            }
+        };
+        let args: Array = args.iter().map(value_to_js).collect();
+        match function.apply(&this, &args) {
+            Ok(result) => js_to_value(&result),
+            Err(e) => {
+                log::warn!("Error calling ExternalInterface method {}: {:?}", name, e);
+                Value::Null
+            }
+        }
+    }
+
+    fn on_callback_available(&mut self, name: &str) {
+        self.js_player.on_callback_available(name);
+    }
+
+    fn on_fs_command(&mut self, command: &str, args: &str) -> bool {
+        self.js_player.on_fs_command(command, args)
+    }
+}
+
+pub fn value_to_js(value: &Value) -> JsValue {
+    match value {
+        Value::Null => JsValue::NULL,
+        Value::Bool(value) => JsValue::from_bool(*value),
+        Value::Number(value) => JsValue::from_f64(*value),
+        Value::String(value) => JsValue::from_str(value),
+        Value::Object(values) => {
+            let object = Object::new();
+            for (key, value) in values {
+                let _ = Reflect::set(&object, &JsValue::from_str(key), &value_to_js(value));
+            }
+            object.into()
+        }
+        Value::List(values) => values.iter().map(value_to_js).collect::<Array>().into(),
+    }
+}
+
+/// Look up a method by its dotted path from `window` (e.g. `console.log`).
+///
+/// Returns the function along with the object holding it, to be used as
+/// `this` when calling it, like Flash Player does. The name is only ever used
+/// as a property path and is never evaluated as script.
+fn resolve_method(name: &str) -> Option<(JsValue, Function)> {
+    let mut this = JsValue::NULL;
+    let mut value: JsValue = web_sys::window()?.into();
+    for part in name.split('.') {
+        if part.is_empty() || !(value.is_object() || value.is_function()) {
+            return None;
+        }
+        this = value;
+        value = Reflect::get(&this, &JsValue::from_str(part)).ok()?;
+    }
+    value
+        .dyn_into::<Function>()
+        .ok()
+        .map(|function| (this, function))
+}
+
+pub fn js_to_value(value: &JsValue) -> Value {
+    js_to_value_inner(value, &mut Vec::new())
+}
+
+/// Copy a JavaScript value, where `ancestors` are the objects currently being
+/// copied. An object that contains itself is copied as `Null` where it
+/// recurs.
+fn js_to_value_inner(value: &JsValue, ancestors: &mut Vec<JsValue>) -> Value {
+    if let Some(value) = value.as_bool() {
+        Value::Bool(value)
+    } else if let Some(value) = value.as_f64() {
+        Value::Number(value)
+    } else if let Some(value) = value.as_string() {
+        Value::String(value)
+    } else if ancestors.contains(value) {
+        Value::Null
+    } else if let Some(array) = value.dyn_ref::<Array>() {
+        ancestors.push(value.clone());
+        let values = array
+            .iter()
+            .map(|value| js_to_value_inner(&value, ancestors))
+            .collect();
+        ancestors.pop();
+        Value::List(values)
+    } else if value.is_object() && !value.is_function() {
+        let object: &Object = value.unchecked_ref();
+        let mut values = BTreeMap::new();
+        ancestors.push(value.clone());
+        for key in Object::keys(object).iter() {
+            if let Some(name) = key.as_string() {
+                let value = Reflect::get(object, &key).unwrap_or(JsValue::NULL);
+                values.insert(name, js_to_value_inner(&value, ancestors));
+            }
+        }
+        ancestors.pop();
+        Value::Object(values)
+    } else {
+        Value::Null
+    }
+}
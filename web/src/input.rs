@@ -59,6 +59,7 @@ impl InputBackend for WebInputBackend {
         match key {
             KeyCode::Unknown => false,
             KeyCode::Backspace => self.keys_down.contains("Backspace"),
+            KeyCode::Tab => self.keys_down.contains("Tab"),
             KeyCode::Return => self.keys_down.contains("Enter"),
             KeyCode::Shift => {
                 self.keys_down.contains("ShiftLeft") || self.keys_down.contains("ShiftRight")
@@ -187,6 +188,11 @@ impl InputBackend for WebInputBackend {
     fn set_clipboard_content(&mut self, _content: String) {
         log::warn!("set clipboard not implemented");
     }
+
+    fn clipboard_content(&mut self) -> String {
+        log::warn!("get clipboard not implemented");
+        String::new()
+    }
 }
 
 /// Converts a Web `KeyboardEvent.code` value into a Ruffle `KeyCode`.
@@ -194,6 +200,7 @@ impl InputBackend for WebInputBackend {
 pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
     let out = match key_code {
         "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
         "Enter" => KeyCode::Return,
         "ShiftLeft" | "ShiftRight" => KeyCode::Shift,
         "ControlLeft" | "ControlRight" => KeyCode::Control,
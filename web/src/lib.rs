@@ -1,22 +1,35 @@
 //! Ruffle web frontend.
 mod audio;
+mod external_interface;
 mod input;
+mod locale;
 mod navigator;
 mod storage;
 
 use crate::storage::LocalStorageBackend;
-use crate::{audio::WebAudioBackend, input::WebInputBackend, navigator::WebNavigatorBackend};
+use crate::{
+    audio::WebAudioBackend,
+    external_interface::{
+        js_to_value, value_to_js, JavascriptPlayer, WebExternalInterfaceProvider,
+    },
+    input::WebInputBackend,
+    locale::WebLocaleBackend,
+    navigator::WebNavigatorBackend,
+};
 use generational_arena::{Arena, Index};
-use js_sys::Uint8Array;
+use js_sys::{Array, Object, Uint8Array};
 use ruffle_core::backend::render::RenderBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::backend::video::SoftwareVideoBackend;
+use ruffle_core::security::ScriptAccess;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerEvent;
 use ruffle_web_common::JsResult;
 use std::mem::drop;
 use std::sync::{Arc, Mutex};
 use std::{cell::RefCell, error::Error, num::NonZeroI32};
+use url::Url;
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{Element, EventTarget, HtmlCanvasElement, HtmlElement, KeyboardEvent, PointerEvent};
 
@@ -57,8 +70,23 @@ pub struct Ruffle(Index);
 
 #[wasm_bindgen]
 impl Ruffle {
-    pub fn new(parent: HtmlElement, swf_data: Uint8Array) -> Result<Ruffle, JsValue> {
-        Ruffle::new_internal(parent, swf_data).map_err(|_| "Error creating player".into())
+    /// Create a player for the given SWF data.
+    ///
+    /// `url` is the URL the movie was loaded from, if known; its query string
+    /// and the `parameters` object (e.g. the `flashvars` of the embedding
+    /// element) are passed to the movie as its parameters.
+    ///
+    /// `js_player` is the `RufflePlayer` element, which the movie talks to
+    /// through `ExternalInterface` and FSCommands.
+    pub fn new(
+        parent: HtmlElement,
+        swf_data: Uint8Array,
+        url: Option<String>,
+        parameters: &JsValue,
+        js_player: JavascriptPlayer,
+    ) -> Result<Ruffle, JsValue> {
+        Ruffle::new_internal(parent, swf_data, url, parameters, js_player)
+            .map_err(|_| "Error creating player".into())
     }
 
     pub fn play(&mut self) {
@@ -71,6 +99,36 @@ impl Ruffle {
         });
     }
 
+    /// Call a function that the movie exposed with
+    /// `ExternalInterface.addCallback`.
+    ///
+    /// Returns `undefined` if the player is busy, e.g. because the movie is
+    /// the one calling into the page.
+    pub fn call_exposed_callback(&self, name: &str, args: Vec<JsValue>) -> JsValue {
+        let args = args.iter().map(js_to_value).collect();
+        INSTANCES.with(|instances| {
+            let instances = match instances.try_borrow() {
+                Ok(instances) => instances,
+                Err(_) => {
+                    log::warn!("Can't call ExternalInterface callback {} while busy", name);
+                    return JsValue::UNDEFINED;
+                }
+            };
+            let instance = match instances.get(self.0) {
+                Some(instance) => instance,
+                None => return JsValue::UNDEFINED,
+            };
+            let mut core = match instance.core.try_lock() {
+                Ok(core) => core,
+                Err(_) => {
+                    log::warn!("Can't call ExternalInterface callback {} while busy", name);
+                    return JsValue::UNDEFINED;
+                }
+            };
+            value_to_js(&core.call_internal_interface(name, args))
+        })
+    }
+
     pub fn destroy(&mut self) -> Result<(), JsValue> {
         // Remove instance from the active list.
         if let Some(mut instance) = INSTANCES.with(|instances| {
@@ -106,14 +164,22 @@ impl Ruffle {
 }
 
 impl Ruffle {
-    fn new_internal(parent: HtmlElement, swf_data: Uint8Array) -> Result<Ruffle, Box<dyn Error>> {
+    fn new_internal(
+        parent: HtmlElement,
+        swf_data: Uint8Array,
+        url: Option<String>,
+        parameters: &JsValue,
+        js_player: JavascriptPlayer,
+    ) -> Result<Ruffle, Box<dyn Error>> {
         console_error_panic_hook::set_once();
         let _ = console_log::init_with_level(log::Level::Trace);
 
         let movie = {
             let mut data = vec![0; swf_data.length() as usize];
             swf_data.copy_to(&mut data[..]);
-            SwfMovie::from_data(&data)?
+            let mut movie = SwfMovie::from_data(&data, url)?;
+            movie.append_parameters(parse_movie_parameters(parameters));
+            movie
         };
 
         let window = web_sys::window().ok_or_else(|| "Expected window")?;
@@ -138,11 +204,35 @@ impl Ruffle {
             })
             .unwrap_or_else(|| Box::new(MemoryStorageBackend::default()));
 
-        let core =
-            ruffle_core::Player::new(renderer, audio, navigator, input, movie, local_storage)?;
+        let video = Box::new(SoftwareVideoBackend::new());
+        let core = ruffle_core::Player::new(
+            renderer,
+            audio,
+            video,
+            navigator,
+            input,
+            movie,
+            local_storage,
+            Box::new(WebLocaleBackend::new()),
+        )?;
         let mut core_lock = core.lock().unwrap();
         let frame_rate = core_lock.frame_rate();
         core_lock.audio_mut().set_frame_rate(frame_rate);
+        // Like Flash Player, only let movies from the page's own domain call
+        // into the page unless `allowScriptAccess` says otherwise.
+        let script_access = js_player
+            .allow_script_access()
+            .and_then(|value| ScriptAccess::from_parameter(&value))
+            .unwrap_or(ScriptAccess::SameDomain);
+        let host_url = window
+            .location()
+            .href()
+            .ok()
+            .and_then(|href| Url::parse(&href).ok());
+        core_lock.set_script_access(script_access, host_url);
+        core_lock.set_external_interface_provider(Some(Box::new(
+            WebExternalInterfaceProvider::new(js_player),
+        )));
         drop(core_lock);
 
         // Create instance.
@@ -539,3 +629,19 @@ fn create_renderer(
 
     Err("Unable to create renderer".into())
 }
+
+/// Read the key/value pairs of a JS object of movie parameters.
+///
+/// Non-string values are ignored.
+fn parse_movie_parameters(input: &JsValue) -> Vec<(String, String)> {
+    let mut parameters = Vec::new();
+    if let Some(object) = input.dyn_ref::<Object>() {
+        for entry in Object::entries(object).iter() {
+            let entry = Array::from(&entry);
+            if let (Some(key), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string()) {
+                parameters.push((key, value));
+            }
+        }
+    }
+    parameters
+}
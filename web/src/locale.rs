@@ -0,0 +1,23 @@
+use js_sys::Date;
+use ruffle_core::backend::locale::LocaleBackend;
+use wasm_bindgen::JsValue;
+
+/// Locale backend that uses the browser's clock and time zone.
+pub struct WebLocaleBackend {}
+
+impl WebLocaleBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl LocaleBackend for WebLocaleBackend {
+    fn current_time(&self) -> f64 {
+        Date::now()
+    }
+
+    fn local_utc_offset(&self, utc_time: f64) -> i32 {
+        // JS reports the offset in minutes *west* of UTC.
+        -Date::new(&JsValue::from_f64(utc_time)).get_timezone_offset() as i32
+    }
+}
@@ -9,7 +9,9 @@ use std::collections::HashMap;
 use std::time::Duration;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
-use web_sys::{window, Blob, BlobPropertyBag, Performance, Request, RequestInit, Response};
+use web_sys::{
+    window, Blob, BlobPropertyBag, Headers, Performance, Request, RequestInit, Response,
+};
 
 pub struct WebNavigatorBackend {
     performance: Performance,
@@ -126,6 +128,16 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
+            if !options.headers().is_empty() {
+                let headers = Headers::new().unwrap();
+                for (name, value) in options.headers() {
+                    if headers.set(name, value).is_err() {
+                        log::warn!("Unable to set request header {}", name);
+                    }
+                }
+                init.headers(&headers);
+            }
+
             let request = Request::new_with_str_and_init(&url, &init).unwrap();
 
             let window = web_sys::window().unwrap();
@@ -138,6 +150,9 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let resp: Response = fetchval.unwrap().dyn_into().unwrap();
+            if !resp.ok() {
+                return Err(Error::HttpNotOk(url, resp.status()));
+            }
             let data: ArrayBuffer = JsFuture::from(resp.array_buffer().unwrap())
                 .await
                 .unwrap()